@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::trace::TraceEntry;
+
 /// Conversion mode for the v2 API.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
@@ -34,6 +36,10 @@ pub struct ConvertV2Query {
 
     /// Run validation and include report in response. Defaults to `false`.
     pub validate: Option<bool>,
+
+    /// Include the mapping trace in the response (`bo4e` mode only).
+    /// Defaults to `false`.
+    pub trace: Option<bool>,
 }
 
 /// Response body for `POST /api/v2/convert`.
@@ -53,4 +59,8 @@ pub struct ConvertV2Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<Object>)]
     pub validation: Option<serde_json::Value>,
+
+    /// Mapping trace (present when `?trace=true`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceEntry>>,
 }
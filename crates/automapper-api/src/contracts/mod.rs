@@ -6,6 +6,7 @@ pub mod error;
 pub mod fixtures;
 pub mod health;
 pub mod inspect;
+pub mod query_v2;
 pub mod reverse_v2;
 pub mod trace;
 pub mod validate_bo4e;
@@ -0,0 +1,63 @@
+//! V2 path query request/response types.
+
+use serde::{Deserialize, Serialize};
+
+/// Request body for `POST /api/v2/query`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct QueryV2Request {
+    /// Raw EDIFACT content to query.
+    pub input: String,
+    /// Format version (e.g., "FV2504").
+    pub format_version: String,
+    /// Path query, e.g. `SG4[IDE.0=24]/SG8[SEQ.0=Z01]/SG10/CCI.2.0`.
+    pub query: String,
+}
+
+/// Response body for `POST /api/v2/query`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueryV2Response {
+    /// The query in normalized form.
+    pub query: String,
+    /// Matches per message, in input order.
+    pub messages: Vec<QueryV2Message>,
+    /// Query duration in milliseconds.
+    pub duration_ms: f64,
+}
+
+/// Query matches within one message.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueryV2Message {
+    /// Index of the interchange (UNB) the message belongs to.
+    pub interchange: usize,
+    /// Index of the message within its interchange.
+    pub message: usize,
+    /// Detected Pruefidentifikator.
+    pub pid: String,
+    /// Nodes the query selected, in document order.
+    pub matches: Vec<QueryV2Match>,
+}
+
+/// One node selected by a query.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueryV2Match {
+    /// Canonical path of the node, e.g. `SG4[0]/SG8[1]/CCI[0].2.0`.
+    pub path: String,
+    /// "group", "segment", or "value".
+    pub kind: String,
+    /// The selected value (`value` matches).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The group instance's own segments (`group` matches) or the segment
+    /// itself (`segment` matches).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<QueryV2Segment>,
+}
+
+/// A segment of a query match.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueryV2Segment {
+    /// Segment tag (e.g., "SEQ").
+    pub tag: String,
+    /// `elements[i][j]` = component `j` of element `i`.
+    pub elements: Vec<Vec<String>>,
+}
@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::trace::TraceEntry;
+
 /// Input level for the reverse endpoint.
 #[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -67,6 +69,13 @@ fn default_mode() -> ReverseMode {
     ReverseMode::Edifact
 }
 
+/// Query parameters for `POST /api/v2/reverse`.
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct ReverseV2Query {
+    /// Include the mapping trace in the response. Defaults to `false`.
+    pub trace: Option<bool>,
+}
+
 /// Response body for `POST /api/v2/reverse`.
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -80,6 +89,10 @@ pub struct ReverseV2Response {
 
     /// Conversion duration in milliseconds.
     pub duration_ms: f64,
+
+    /// Mapping trace (present when `?trace=true`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceEntry>>,
 }
 
 /// Normalize input JSON to an `Interchange`, wrapping lower-level inputs as needed.
@@ -107,6 +120,7 @@ pub fn normalize_to_interchange(
                     nachrichten_typ,
                     ..nachricht
                 }],
+                gruppen: vec![],
             })
         }
         InputLevel::Transaktion => {
@@ -124,9 +138,11 @@ pub fn normalize_to_interchange(
                 nachrichten: vec![mig_bo4e::Nachricht {
                     unh_referenz: "00001".to_string(),
                     nachrichten_typ,
+                    nachrichtenkopf: serde_json::Value::Null,
                     stammdaten: serde_json::json!({}),
                     transaktionen: vec![tx],
                 }],
+                gruppen: vec![],
             })
         }
     }
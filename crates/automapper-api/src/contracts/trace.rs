@@ -3,12 +3,12 @@
 use serde::{Deserialize, Serialize};
 
 /// A single entry in the mapping trace.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct TraceEntry {
     /// Name of the mapper/writer that processed this step.
     pub mapper: String,
 
-    /// Source EDIFACT segment reference (e.g., "NAD (line 5)").
+    /// Source EDIFACT segment reference (e.g., "NAD (segment 5)").
     pub source_segment: String,
 
     /// Target BO4E path (e.g., "geschaeftspartner.name1").
@@ -19,4 +19,71 @@ pub struct TraceEntry {
 
     /// Optional note about the mapping step.
     pub note: Option<String>,
+
+    /// TOML mapping file that defined the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// Field key within the TOML file (e.g., "loc.1.0").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    /// MIG location of the field (e.g., "sg4.sg5_z16.loc.1.0").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mig_path: Option<String>,
+
+    /// 1-based number of the source segment within its message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_number: Option<u32>,
+
+    /// Value before transform and enum_map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_value: Option<String>,
+
+    /// Outcome: "mapped", "default_applied", "discriminator_mismatch",
+    /// "empty_value" or "condition_not_met".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+}
+
+impl From<mig_bo4e::TraceEntry> for TraceEntry {
+    fn from(entry: mig_bo4e::TraceEntry) -> Self {
+        let outcome = match entry.outcome {
+            mig_bo4e::TraceOutcome::Mapped => "mapped",
+            mig_bo4e::TraceOutcome::DefaultApplied => "default_applied",
+            mig_bo4e::TraceOutcome::DiscriminatorMismatch => "discriminator_mismatch",
+            mig_bo4e::TraceOutcome::EmptyValue => "empty_value",
+            mig_bo4e::TraceOutcome::ConditionNotMet => "condition_not_met",
+        };
+        let note = match entry.outcome {
+            mig_bo4e::TraceOutcome::Mapped => None,
+            mig_bo4e::TraceOutcome::DefaultApplied => Some("default applied".to_string()),
+            mig_bo4e::TraceOutcome::DiscriminatorMismatch => {
+                Some("skipped: discriminator matched no group repetition".to_string())
+            }
+            mig_bo4e::TraceOutcome::EmptyValue => Some("skipped: empty value".to_string()),
+            mig_bo4e::TraceOutcome::ConditionNotMet => {
+                Some("skipped: when condition not met".to_string())
+            }
+        };
+        let source_segment = match (&entry.segment_tag, entry.segment_number) {
+            (Some(tag), Some(n)) => format!("{tag} (segment {n})"),
+            (Some(tag), None) => tag.clone(),
+            (None, _) => entry.mig_path.clone(),
+        };
+
+        Self {
+            mapper: entry.entity,
+            source_segment,
+            target_path: entry.target,
+            value: entry.mapped_value,
+            note,
+            file: entry.file,
+            field: (!entry.field.is_empty()).then_some(entry.field),
+            mig_path: Some(entry.mig_path),
+            segment_number: entry.segment_number,
+            raw_value: entry.value,
+            outcome: Some(outcome.to_string()),
+        }
+    }
 }
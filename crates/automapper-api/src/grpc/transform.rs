@@ -1,26 +1,122 @@
 //! gRPC TransformService implementation.
 //!
-//! Legacy pipeline has been removed. All transform methods return UNIMPLEMENTED
-//! status, directing callers to use the v2 REST API instead.
+//! Runs the same MIG-driven pipeline as the v2 REST API: `ConvertEdifactToBo4e`
+//! mirrors `POST /api/v2/convert`, `ConvertBo4eToEdifact` mirrors
+//! `POST /api/v2/reverse`, and `ConvertEdifactToBo4eStream` converts one
+//! request per incoming stream item.
 
 use std::pin::Pin;
 
-use tokio_stream::Stream;
+use tokio_stream::{Stream, StreamExt};
 use tonic::{Request, Response, Status, Streaming};
 
+use crate::contracts::convert_v2::ConvertMode;
+use crate::contracts::reverse_v2::{EnvelopeOverrides, InputLevel, ReverseMode};
+use crate::contracts::trace::TraceEntry;
+use crate::error::ApiError;
 use crate::grpc::transform_proto::transform_service_server::TransformService;
 use crate::grpc::transform_proto::{
-    Bo4eToEdifactRequest, ConvertResponse as ProtoConvertResponse, EdifactToBo4eRequest,
+    self as proto, Bo4eToEdifactRequest, ConversionError as ProtoConversionError,
+    ConvertResponse as ProtoConvertResponse, EdifactToBo4eRequest, ErrorSeverity,
 };
+use crate::routes::convert_v2::run_convert;
+use crate::routes::reverse_v2::run_reverse;
+use crate::state::AppState;
 
 /// gRPC implementation of TransformService.
-///
-/// All methods currently return `UNIMPLEMENTED` — callers should use the v2 REST API.
-pub struct TransformServiceImpl;
+pub struct TransformServiceImpl {
+    state: AppState,
+}
 
 impl TransformServiceImpl {
-    pub fn new(_registry: std::sync::Arc<crate::state::CoordinatorRegistry>) -> Self {
-        Self
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Convert one EDIFACT request. Conversion failures are returned as
+    /// `Err(ApiError)` so unary and streaming callers can report them differently.
+    fn convert_edifact(
+        &self,
+        req: &EdifactToBo4eRequest,
+    ) -> Result<ProtoConvertResponse, ApiError> {
+        let start = std::time::Instant::now();
+        require_format_version(&req.format_version)?;
+
+        let mode = match req.mode() {
+            proto::ConvertMode::MigTree => ConvertMode::MigTree,
+            proto::ConvertMode::Unspecified | proto::ConvertMode::Bo4e => ConvertMode::Bo4e,
+        };
+
+        let output = run_convert(
+            &self.state,
+            &req.edifact,
+            &mode,
+            &req.format_version,
+            req.enrich_codes.unwrap_or(true),
+            req.validate,
+            req.include_trace,
+        )?;
+
+        let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+        Ok(ProtoConvertResponse {
+            success: true,
+            result: output.result.to_string(),
+            trace: output
+                .trace
+                .map(|entries| trace_to_proto(entries, duration_ms)),
+            errors: vec![],
+            duration_ms,
+            mode: output.mode.to_string(),
+            validation: output.validation.as_ref().map(report_to_proto),
+        })
+    }
+
+    fn convert_bo4e(&self, req: &Bo4eToEdifactRequest) -> Result<ProtoConvertResponse, ApiError> {
+        let start = std::time::Instant::now();
+        require_format_version(&req.format_version)?;
+
+        let input: serde_json::Value =
+            serde_json::from_str(&req.bo4e_json).map_err(|e| ApiError::BadRequest {
+                message: format!("Invalid BO4E JSON: {e}"),
+            })?;
+
+        let level = match req.level() {
+            proto::InputLevel::Interchange => InputLevel::Interchange,
+            proto::InputLevel::Nachricht => InputLevel::Nachricht,
+            proto::InputLevel::Unspecified | proto::InputLevel::Transaktion => {
+                InputLevel::Transaktion
+            }
+        };
+        let mode = match req.mode() {
+            proto::ReverseMode::MigTree => ReverseMode::MigTree,
+            proto::ReverseMode::Unspecified | proto::ReverseMode::Edifact => ReverseMode::Edifact,
+        };
+        let envelope = envelope_from_proto(req.envelope.as_ref(), &req.message_type);
+
+        let output = run_reverse(
+            &self.state,
+            &input,
+            &level,
+            &mode,
+            &req.format_version,
+            envelope.as_ref(),
+            false,
+        )?;
+
+        let result = match output.result {
+            serde_json::Value::String(edifact) => edifact,
+            other => other.to_string(),
+        };
+
+        Ok(ProtoConvertResponse {
+            success: true,
+            result,
+            trace: None,
+            errors: vec![],
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            mode: output.mode.to_string(),
+            validation: None,
+        })
     }
 }
 
@@ -28,20 +124,30 @@ impl TransformServiceImpl {
 impl TransformService for TransformServiceImpl {
     async fn convert_edifact_to_bo4e(
         &self,
-        _request: Request<EdifactToBo4eRequest>,
+        request: Request<EdifactToBo4eRequest>,
     ) -> Result<Response<ProtoConvertResponse>, Status> {
-        Err(Status::unimplemented(
-            "Legacy pipeline removed, use v2 REST API",
-        ))
+        let req = request.into_inner();
+        tracing::info!(
+            "gRPC: Converting EDIFACT to BO4E, length={}, format_version={}",
+            req.edifact.len(),
+            req.format_version
+        );
+
+        unary_response(self.convert_edifact(&req)).map(Response::new)
     }
 
     async fn convert_bo4e_to_edifact(
         &self,
-        _request: Request<Bo4eToEdifactRequest>,
+        request: Request<Bo4eToEdifactRequest>,
     ) -> Result<Response<ProtoConvertResponse>, Status> {
-        Err(Status::unimplemented(
-            "Legacy pipeline removed, use v2 REST API",
-        ))
+        let req = request.into_inner();
+        tracing::info!(
+            "gRPC: Converting BO4E to EDIFACT, length={}, format_version={}",
+            req.bo4e_json.len(),
+            req.format_version
+        );
+
+        unary_response(self.convert_bo4e(&req)).map(Response::new)
     }
 
     type ConvertEdifactToBo4eStreamStream =
@@ -49,10 +155,199 @@ impl TransformService for TransformServiceImpl {
 
     async fn convert_edifact_to_bo4e_stream(
         &self,
-        _request: Request<Streaming<EdifactToBo4eRequest>>,
+        request: Request<Streaming<EdifactToBo4eRequest>>,
     ) -> Result<Response<Self::ConvertEdifactToBo4eStreamStream>, Status> {
-        Err(Status::unimplemented(
-            "Legacy pipeline removed, use v2 REST API",
-        ))
+        tracing::info!("gRPC: Opening EDIFACT to BO4E stream");
+
+        let service = Self::new(self.state.clone());
+        let mut inbound = request.into_inner();
+
+        // One response per request, in order. A failed conversion yields an
+        // unsuccessful response instead of ending the stream; transport errors
+        // from the client end it.
+        let output = async_stream::try_stream! {
+            while let Some(req) = inbound.next().await {
+                let req = req?;
+                let start = std::time::Instant::now();
+                let response = service
+                    .convert_edifact(&req)
+                    .unwrap_or_else(|e| error_response(&e, start));
+                yield response;
+            }
+        };
+
+        Ok(Response::new(Box::pin(output)))
+    }
+}
+
+fn require_format_version(format_version: &str) -> Result<(), ApiError> {
+    if format_version.is_empty() {
+        return Err(ApiError::BadRequest {
+            message: "format_version is required (e.g., \"FV2504\")".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Map a conversion result to a unary gRPC reply.
+///
+/// Conversion errors are part of the response (`success = false`), while bad
+/// requests and server-side failures become gRPC status codes.
+fn unary_response(
+    result: Result<ProtoConvertResponse, ApiError>,
+) -> Result<ProtoConvertResponse, Status> {
+    match result {
+        Ok(response) => Ok(response),
+        Err(e @ ApiError::ConversionError { .. }) => {
+            Ok(error_response(&e, std::time::Instant::now()))
+        }
+        Err(ApiError::BadRequest { message }) => Err(Status::invalid_argument(message)),
+        Err(ApiError::NotFound { message }) => Err(Status::not_found(message)),
+        Err(ApiError::Internal { message }) => Err(Status::internal(message)),
+    }
+}
+
+/// Build an unsuccessful response carrying `error`.
+fn error_response(error: &ApiError, start: std::time::Instant) -> ProtoConvertResponse {
+    let (code, message) = match error {
+        ApiError::BadRequest { message } => ("BAD_REQUEST", message),
+        ApiError::NotFound { message } => ("NOT_FOUND", message),
+        ApiError::ConversionError { message } => ("CONVERSION_ERROR", message),
+        ApiError::Internal { message } => ("INTERNAL_ERROR", message),
+    };
+
+    ProtoConvertResponse {
+        success: false,
+        result: String::new(),
+        trace: None,
+        errors: vec![ProtoConversionError {
+            code: code.to_string(),
+            message: message.clone(),
+            location: String::new(),
+            severity: ErrorSeverity::Error as i32,
+        }],
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        mode: String::new(),
+        validation: None,
+    }
+}
+
+/// Convert proto envelope overrides; empty strings mean "not set".
+///
+/// `message_type` from the request fills in `nachrichten_typ` when the
+/// overrides do not specify one.
+fn envelope_from_proto(
+    envelope: Option<&proto::EnvelopeOverrides>,
+    message_type: &str,
+) -> Option<EnvelopeOverrides> {
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+
+    let overrides = EnvelopeOverrides {
+        absender_code: envelope.and_then(|e| non_empty(&e.absender_code)),
+        empfaenger_code: envelope.and_then(|e| non_empty(&e.empfaenger_code)),
+        nachrichten_typ: envelope
+            .and_then(|e| non_empty(&e.nachrichten_typ))
+            .or_else(|| non_empty(message_type)),
+    };
+
+    if overrides.absender_code.is_none()
+        && overrides.empfaenger_code.is_none()
+        && overrides.nachrichten_typ.is_none()
+    {
+        None
+    } else {
+        Some(overrides)
+    }
+}
+
+/// Convert mapping trace entries to their proto representation.
+fn trace_to_proto(entries: Vec<mig_bo4e::TraceEntry>, duration_ms: f64) -> proto::MappingTrace {
+    proto::MappingTrace {
+        coordinator_used: String::new(),
+        steps: entries
+            .into_iter()
+            .map(TraceEntry::from)
+            .map(|entry| proto::MappingStep {
+                mapper: entry.mapper,
+                source_segment: entry.source_segment,
+                target_path: entry.target_path,
+                value: entry.value.unwrap_or_default(),
+                note: entry.note.unwrap_or_default(),
+            })
+            .collect(),
+        duration_ms,
+    }
+}
+
+/// Convert a validation report to its proto representation.
+fn report_to_proto(report: &automapper_validation::ValidationReport) -> proto::ValidationReport {
+    proto::ValidationReport {
+        message_type: report.message_type.clone(),
+        pruefidentifikator: report.pruefidentifikator.clone().unwrap_or_default(),
+        format_version: report.format_version.clone().unwrap_or_default(),
+        level: report.level.to_string(),
+        issues: report
+            .issues
+            .iter()
+            .map(|issue| proto::ValidationIssue {
+                severity: issue.severity.to_string(),
+                category: issue.category.to_string(),
+                code: issue.code.clone(),
+                message: issue.message.clone(),
+                position: issue.segment_position.map(|pos| proto::SegmentPosition {
+                    segment_number: pos.segment_number,
+                    byte_offset: pos.byte_offset as u64,
+                    message_number: pos.message_number,
+                    line: pos.line,
+                    column: pos.column,
+                }),
+                field_path: issue.field_path.clone().unwrap_or_default(),
+                rule: issue.rule.clone().unwrap_or_default(),
+                actual_value: issue.actual_value.clone().unwrap_or_default(),
+                expected_value: issue.expected_value.clone().unwrap_or_default(),
+                bo4e_path: issue.bo4e_path.clone().unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_from_proto_uses_message_type_fallback() {
+        assert!(envelope_from_proto(None, "").is_none());
+
+        let env = envelope_from_proto(None, "UTILMD").unwrap();
+        assert_eq!(env.nachrichten_typ.as_deref(), Some("UTILMD"));
+        assert!(env.absender_code.is_none());
+
+        let proto_env = proto::EnvelopeOverrides {
+            absender_code: "9900123".to_string(),
+            empfaenger_code: String::new(),
+            nachrichten_typ: "MSCONS".to_string(),
+        };
+        let env = envelope_from_proto(Some(&proto_env), "UTILMD").unwrap();
+        assert_eq!(env.absender_code.as_deref(), Some("9900123"));
+        assert!(env.empfaenger_code.is_none());
+        assert_eq!(env.nachrichten_typ.as_deref(), Some("MSCONS"));
+    }
+
+    #[test]
+    fn test_unary_response_maps_errors() {
+        let ok = unary_response(Err(ApiError::ConversionError {
+            message: "assembly error".to_string(),
+        }))
+        .unwrap();
+        assert!(!ok.success);
+        assert_eq!(ok.errors[0].code, "CONVERSION_ERROR");
+        assert_eq!(ok.errors[0].severity, ErrorSeverity::Error as i32);
+
+        let status = unary_response(Err(ApiError::BadRequest {
+            message: "no MIG".to_string(),
+        }))
+        .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
     }
 }
@@ -43,6 +43,7 @@ use grpc::transform_proto::transform_service_server::TransformServiceServer;
         routes::reverse_v2::reverse_v2,
         routes::validate_v2::validate_v2,
         routes::validate_bo4e::validate_bo4e,
+        routes::query_v2::query_v2,
     ),
     tags(
         (name = "health", description = "Service health"),
@@ -127,8 +128,7 @@ pub fn build_http_router(state: state::AppState) -> Router {
 }
 
 fn build_grpc_router(state: &state::AppState) -> Router {
-    let transform_service =
-        TransformServiceServer::new(TransformServiceImpl::new(state.registry.clone()));
+    let transform_service = TransformServiceServer::new(TransformServiceImpl::new(state.clone()));
     let inspection_service =
         InspectionServiceServer::new(InspectionServiceImpl::new(state.registry.clone()));
 
@@ -2,10 +2,15 @@
 //!
 //! Generates a response message based on validation outcome:
 //! - **No errors** → positive APERAK (BGM+312) or positive CONTRL (UCI action=7)
-//! - **Errors** → negative APERAK (BGM+313) or negative CONTRL (UCI action=4)
-
+//! - **Errors** → negative APERAK (BGM+313) or negative CONTRL, which rejects
+//!   the interchange (UCI action=4) or the message (UCM action=4) and points
+//!   at each offending segment and data element via UCS/UCD
+
+use edifact_types::{
+    EdifactWriter, InterchangeParty, MessageIdentifier, PreparationDateTime, ServiceSegment,
+    SyntaxIdentification, Unb, Unh,
+};
 use mig_assembly::disassembler::Disassembler;
-use mig_assembly::renderer::render_edifact;
 use mig_assembly::tokenize::OwnedSegment;
 use serde::{Deserialize, Serialize};
 
@@ -66,6 +71,12 @@ pub struct OriginalMessageMeta {
     pub receiver_code_agency: String,
     /// UNH d0062 — message reference number.
     pub message_ref: String,
+    /// UNH S009 — message identifier (d0065, d0052, d0054, d0051, d0057).
+    pub message_identifier: MessageIdentifier,
+    /// Segment number of UNH in the original interchange, used to turn
+    /// validation issue positions into positions within the message.
+    /// 0 if the issue positions are already relative to the message.
+    pub unh_segment_number: u32,
     /// BGM d1004 or IDE d7402 — document/transaction reference.
     pub transaction_ref: Option<String>,
 }
@@ -76,40 +87,23 @@ pub fn extract_meta_from_edifact(
     msg_body: &[OwnedSegment],
     unh: &OwnedSegment,
 ) -> OriginalMessageMeta {
-    let mut meta = OriginalMessageMeta::default();
+    let unh_segment_number = unh.segment_number;
+    let unh = Unh::from_elements(&unh.elements).unwrap_or_default();
+    let mut meta = OriginalMessageMeta {
+        message_ref: unh.message_reference,
+        message_identifier: unh.message_identifier,
+        unh_segment_number,
+        ..Default::default()
+    };
 
-    // UNH d0062 (element 0)
-    if !unh.elements.is_empty() && !unh.elements[0].is_empty() {
-        meta.message_ref = unh.elements[0][0].clone();
-    }
-
-    // UNB: element 0 = S001 (syntax), element 1 = S002 (sender), element 2 = S003 (receiver),
-    //       element 3 = S004 (date/time), element 4 = d0020 (interchange ref)
-    for seg in envelope {
-        if seg.id == "UNB" {
-            // S002 sender (element 1)
-            if seg.elements.len() > 1 {
-                if !seg.elements[1].is_empty() {
-                    meta.sender_id = seg.elements[1][0].clone();
-                }
-                if seg.elements[1].len() > 1 {
-                    meta.sender_qualifier = seg.elements[1][1].clone();
-                }
-            }
-            // S003 receiver (element 2)
-            if seg.elements.len() > 2 {
-                if !seg.elements[2].is_empty() {
-                    meta.receiver_id = seg.elements[2][0].clone();
-                }
-                if seg.elements[2].len() > 1 {
-                    meta.receiver_qualifier = seg.elements[2][1].clone();
-                }
-            }
-            // d0020 interchange ref (element 4)
-            if seg.elements.len() > 4 && !seg.elements[4].is_empty() {
-                meta.interchange_ref = seg.elements[4][0].clone();
-            }
-        }
+    // UNB: S002 sender, S003 receiver, d0020 interchange ref
+    if let Some(seg) = envelope.iter().find(|seg| seg.id == Unb::TAG) {
+        let unb = Unb::from_elements(&seg.elements).unwrap_or_default();
+        meta.sender_id = unb.sender.id;
+        meta.sender_qualifier = unb.sender.qualifier.unwrap_or_default();
+        meta.receiver_id = unb.recipient.id;
+        meta.receiver_qualifier = unb.recipient.qualifier.unwrap_or_default();
+        meta.interchange_ref = unb.control_reference;
     }
 
     // Scan body for BGM, IDE, and NAD segments
@@ -313,41 +307,246 @@ fn build_aperak_bo4e(
 ///
 /// The JSON structure must match what the MappingEngine's `map_all_forward` produces.
 /// Companion fields are keyed by camelCase companion_type, not `_edifact`.
+///
+/// Following the BDEW CONTRL AHB, errors in the interchange envelope
+/// (UNB/UNZ) reject the whole interchange (UCI action 4). Any other error
+/// rejects the message (UCI action 7, UCM action 4): UNH/UNT errors and
+/// errors without a segment position are reported on the UCM itself, all
+/// others as an SG2 with UCS pointing at the segment and, for data element
+/// errors, UCD pointing at the element/component.
 fn build_contrl_bo4e(
     is_positive: bool,
     meta: &OriginalMessageMeta,
-    _report: &automapper_validation::ValidationReport,
+    report: &automapper_validation::ValidationReport,
 ) -> serde_json::Value {
-    let action = if is_positive { "7" } else { "4" };
+    let mut interchange_error: Option<ContrlError> = None;
+    let mut message_error: Option<ContrlError> = None;
+    let mut segment_errors: Vec<ContrlError> = Vec::new();
+    if !is_positive {
+        for issue in report.errors() {
+            let error = contrl_error(issue, meta);
+            match error.level {
+                ContrlLevel::Interchange => {
+                    interchange_error.get_or_insert(error);
+                }
+                ContrlLevel::Message => {
+                    message_error.get_or_insert(error);
+                }
+                ContrlLevel::Segment(_) => {
+                    if !segment_errors.contains(&error) {
+                        segment_errors.push(error);
+                    }
+                }
+            }
+        }
+    }
+    let action = if interchange_error.is_some() {
+        "4"
+    } else {
+        "7"
+    };
 
     // Root: UCI → entity "Uebertragungspruefung"
     // uebertragungspruefung.toml: fields={datenaustauschreferenz, aktion},
     //   companion_fields under "uebertragungspruefungEdifact"={absenderMpId, ...}
+    let mut uci_edifact = serde_json::json!({
+        "absenderMpId": &meta.sender_id,
+        "absenderCodeQualifier": &meta.sender_qualifier,
+        "empfaengerMpId": &meta.receiver_id,
+        "empfaengerCodeQualifier": &meta.receiver_qualifier,
+    });
+    if let Some(ref error) = interchange_error {
+        error.write_service_error(&mut uci_edifact);
+    }
     let mut result = serde_json::json!({
         "uebertragungspruefung": {
             "datenaustauschreferenz": &meta.interchange_ref,
             "aktion": action,
-            "uebertragungspruefungEdifact": {
-                "absenderMpId": &meta.sender_id,
-                "absenderCodeQualifier": &meta.sender_qualifier,
-                "empfaengerMpId": &meta.receiver_id,
-                "empfaengerCodeQualifier": &meta.receiver_qualifier,
-            }
+            "uebertragungspruefungEdifact": uci_edifact,
         }
     });
 
     // SG1: UCM → entity "Nachrichtenpruefung"
-    // nachrichtenpruefung.toml: fields={nachrichtenReferenznummer, aktion}
-    if !is_positive {
+    // nachrichtenpruefung.toml: fields={nachrichtenReferenznummer, aktion},
+    //   companion_fields under "nachrichtenpruefungEdifact"={nachrichtentyp, syntaxFehlerCode, ...}
+    if message_error.is_some() || !segment_errors.is_empty() {
+        let mut ucm_edifact = serde_json::Map::new();
+        let id = &meta.message_identifier;
+        let s009 = [
+            ("nachrichtentyp", Some(&id.message_type)),
+            ("versionsnummer", Some(&id.version)),
+            ("freigabenummer", Some(&id.release)),
+            ("verwaltungsOrganisation", Some(&id.controlling_agency)),
+            ("anwendungscode", id.association_code.as_ref()),
+        ];
+        for (key, value) in s009 {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                ucm_edifact.insert(key.to_string(), serde_json::json!(value));
+            }
+        }
+        let mut ucm_edifact = serde_json::Value::Object(ucm_edifact);
+        if let Some(ref error) = message_error {
+            error.write_service_error(&mut ucm_edifact);
+        }
         result["nachrichtenpruefung"] = serde_json::json!({
             "nachrichtenReferenznummer": &meta.message_ref,
             "aktion": "4",
+            "nachrichtenpruefungEdifact": ucm_edifact,
         });
     }
 
+    // SG1.SG2: UCS + UCD → entity "Segmentfehler"
+    // segmentfehler.toml: fields={segmentPosition, syntaxFehlerCode},
+    //   companion_fields under "segmentfehlerEdifact"={datenelementFehlerCode, ...}
+    if !segment_errors.is_empty() {
+        let segmentfehler: Vec<serde_json::Value> = segment_errors
+            .iter()
+            .filter_map(|error| match error.level {
+                ContrlLevel::Segment(position) => Some(error.segmentfehler_bo4e(position)),
+                _ => None,
+            })
+            .collect();
+        result["segmentfehler"] = serde_json::Value::Array(segmentfehler);
+    }
+
     result
 }
 
+/// The CONTRL level a syntax error is reported on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContrlLevel {
+    /// UCI: the interchange envelope (UNB/UNZ).
+    Interchange,
+    /// UCM: the message as a whole, including UNH/UNT.
+    Message,
+    /// UCS: the segment at this position in the message (UNH = 1).
+    Segment(u32),
+}
+
+/// A validation issue translated to CONTRL terms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContrlError {
+    level: ContrlLevel,
+    /// Syntax error code (d0085).
+    code: &'static str,
+    /// Service segment in error (d0013) for UCI/UCM level errors.
+    service_segment: Option<&'static str>,
+    /// Erroneous data element / component position (S011 d0098, d0104).
+    element: Option<(u32, Option<u32>)>,
+}
+
+impl ContrlError {
+    /// Add d0085, d0013 and S011 to a UCI/UCM companion object.
+    fn write_service_error(&self, companion: &mut serde_json::Value) {
+        companion["syntaxFehlerCode"] = serde_json::json!(self.code);
+        if let Some(tag) = self.service_segment {
+            companion["serviceSegmentBezeichner"] = serde_json::json!(tag);
+        }
+        if let Some((element, component)) = self.element {
+            companion["fehlerSegmentPosition"] = serde_json::json!(element.to_string());
+            if let Some(component) = component {
+                companion["fehlerGruppendatenelementPosition"] =
+                    serde_json::json!(component.to_string());
+            }
+        }
+    }
+
+    /// Build one SG2 "segmentfehler" object. A segment level error carries
+    /// its code on UCS; a data element error carries it on UCD instead.
+    fn segmentfehler_bo4e(&self, position: u32) -> serde_json::Value {
+        let mut obj = serde_json::json!({
+            "segmentPosition": position.to_string(),
+        });
+        match self.element {
+            Some((element, component)) => {
+                let mut ucd = serde_json::json!({
+                    "datenelementFehlerCode": self.code,
+                    "fehlerDatenelementPosition": element.to_string(),
+                });
+                if let Some(component) = component {
+                    ucd["fehlerGruppendatenelementPosition"] =
+                        serde_json::json!(component.to_string());
+                }
+                obj["segmentfehlerEdifact"] = ucd;
+            }
+            None => obj["syntaxFehlerCode"] = serde_json::json!(self.code),
+        }
+        obj
+    }
+}
+
+/// Translate a validation issue into a CONTRL syntax error.
+///
+/// UCS d0096 counts from UNH = 1, so interchange segment numbers are shifted
+/// by `meta.unh_segment_number`. S011 d0098 counts the segment tag as 1, so
+/// the first data element is 2; d0104 counts components from 1.
+fn contrl_error(
+    issue: &automapper_validation::ValidationIssue,
+    meta: &OriginalMessageMeta,
+) -> ContrlError {
+    let code = map_validation_issue_to_contrl_code(issue);
+    let element = issue
+        .element_position
+        .map(|p| (p.element as u32 + 2, p.component.map(|c| c as u32 + 1)));
+
+    let (level, service_segment) = match issue.code.as_str() {
+        "STR009" | "STR010" | "STR012" => (ContrlLevel::Interchange, Some("UNZ")),
+        "STR007" | "STR008" | "STR011" => (ContrlLevel::Message, Some("UNT")),
+        _ => match issue.segment_position {
+            Some(pos) => {
+                let offset = meta.unh_segment_number.saturating_sub(1);
+                (
+                    ContrlLevel::Segment(pos.segment_number.saturating_sub(offset)),
+                    None,
+                )
+            }
+            None => (ContrlLevel::Message, None),
+        },
+    };
+
+    // Without a service segment, S011 on UCM has nothing to refer to
+    let element = if level == ContrlLevel::Message && service_segment.is_none() {
+        None
+    } else {
+        element
+    };
+
+    ContrlError {
+        level,
+        code,
+        service_segment,
+        element,
+    }
+}
+
+/// Map a validation issue to a CONTRL syntax error code (d0085).
+///
+/// - 12 Ungueltiger Wert, 13 Fehlt, 14 Wert an dieser Stelle nicht unterstuetzt,
+///   15 An dieser Stelle nicht unterstuetzt, 18 Nicht spezifizierter Fehler
+/// - 21 Ungueltige Zeichen, 28 Referenzen stimmen nicht ueberein,
+///   29 Kontrollzaehler stimmt nicht
+/// - 35 Zu viele Wiederholungen, 36 Zu viele Segmentgruppenwiederholungen
+/// - 37 Ungueltige Zeichenart, 39 Datenelement zu lang, 40 Datenelement zu kurz
+fn map_validation_issue_to_contrl_code(
+    issue: &automapper_validation::ValidationIssue,
+) -> &'static str {
+    match issue.code.as_str() {
+        "STR001" | "STR005" | "STR011" | "STR012" | "FMT006" | "AHB001" => "13",
+        "STR002" => "35",
+        "STR003" | "STR004" | "AHB002" => "15",
+        "STR006" => "36",
+        "STR007" | "STR009" => "29",
+        "STR008" | "STR010" => "28",
+        "FMT001" => "39",
+        "FMT002" | "FMT003" => "37",
+        "FMT004" | "COD001" => "12",
+        "FMT005" => "40",
+        "FMT007" => "21",
+        "COD002" => "14",
+        _ => "18",
+    }
+}
+
 /// Render the response BO4E JSON as EDIFACT using the response engine + MIG.
 ///
 /// Error segments (SG4/SG5) are rendered manually because the reverse mapper
@@ -380,48 +579,76 @@ fn render_response_edifact(
     // Reverse map header BO4E → AssembledTree
     let tree = engine.map_all_reverse(&header_bo4e, None);
 
-    // Disassemble and render header
+    // Disassemble header
     let disassembler = Disassembler::new(mig);
     let dis_segments = disassembler.disassemble(&tree);
-    let delimiters = edifact_types::EdifactDelimiters::default();
-    let header_edifact = render_edifact(&dis_segments, &delimiters);
-
-    // Render error segments manually from nested fehler array
-    let error_edifact = render_error_segments(bo4e, &delimiters);
-    let error_seg_count = count_error_segments(bo4e);
-
-    // Wrap with UNB/UNH/UNT/UNZ envelope
-    let unh_version = match msg_type {
-        "APERAK" => "APERAK:D:07B:UN:2.1i",
-        "CONTRL" => "CONTRL:D:07B:UN:2.0b",
-        _ => msg_type,
+
+    let (association_code, release) = match msg_type {
+        "APERAK" => (Some("2.1i"), "07B"),
+        "CONTRL" => (Some("2.0b"), "07B"),
+        _ => (None, ""),
+    };
+    let unh = Unh {
+        message_reference: "1".to_string(),
+        message_identifier: MessageIdentifier {
+            message_type: msg_type.to_string(),
+            version: if release.is_empty() { "" } else { "D" }.to_string(),
+            release: release.to_string(),
+            controlling_agency: if release.is_empty() { "" } else { "UN" }.to_string(),
+            association_code: association_code.map(str::to_string),
+            ..Default::default()
+        },
+        ..Default::default()
     };
-    let now_date = chrono_now_compact();
-    let seg_count = dis_segments.len() + error_seg_count + 2; // +2 for UNH+UNT
 
-    let mut out = String::new();
     // UNB — swap sender/receiver for response
-    out.push_str(&format!(
-        "UNB+UNOC:3+{}:{}+{}:{}+{}+{}'",
-        meta.receiver_id,
-        meta.receiver_qualifier,
-        meta.sender_id,
-        meta.sender_qualifier,
-        now_date,
-        format_args!("RESP{}", &meta.interchange_ref.get(..6).unwrap_or("000000"))
-    ));
-    // UNH
-    out.push_str(&format!("UNH+1+{}'", unh_version));
-    // Header body (BGM, DTM, RFF, NAD)
-    out.push_str(&header_edifact);
-    // Error segments (ERC+FTX, then SG5 RFFs per error)
-    out.push_str(&error_edifact);
-    // UNT
-    out.push_str(&format!("UNT+{}+1'", seg_count));
-    // UNZ
-    out.push_str("UNZ+1+1'");
-
-    Ok(out)
+    let now_date = chrono_now_compact();
+    let (date, time) = now_date.split_once(':').unwrap_or((&now_date, ""));
+    let unb = Unb {
+        syntax: SyntaxIdentification {
+            identifier: "UNOC".to_string(),
+            version: "3".to_string(),
+            ..Default::default()
+        },
+        sender: InterchangeParty {
+            id: meta.receiver_id.clone(),
+            qualifier: Some(meta.receiver_qualifier.clone()),
+            ..Default::default()
+        },
+        recipient: InterchangeParty {
+            id: meta.sender_id.clone(),
+            qualifier: Some(meta.sender_qualifier.clone()),
+            ..Default::default()
+        },
+        prepared: PreparationDateTime {
+            date: date.to_string(),
+            time: time.to_string(),
+        },
+        control_reference: format!("RESP{}", meta.interchange_ref.get(..6).unwrap_or("000000")),
+        ..Default::default()
+    };
+
+    // The writer closes UNT/UNZ with counts and references of UNH/UNB
+    let write = |writer: &mut EdifactWriter<Vec<u8>>| -> std::io::Result<()> {
+        writer.begin_interchange(unb.to_elements())?;
+        writer.begin_message(unh.to_elements())?;
+        // Header body (BGM, DTM, RFF, NAD)
+        for seg in &dis_segments {
+            writer.write_segment(&seg.tag, &seg.elements)?;
+        }
+        // Error segments (ERC+FTX, then SG5 RFFs per error)
+        write_error_segments(bo4e, writer)?;
+        writer.end_message()?;
+        writer.end_interchange()
+    };
+
+    let mut writer = EdifactWriter::new(Vec::new());
+    write(&mut writer).map_err(|e| crate::error::ApiError::Internal {
+        message: format!("Failed to write {msg_type} response: {e}"),
+    })?;
+    String::from_utf8(writer.into_inner()).map_err(|e| crate::error::ApiError::Internal {
+        message: format!("Failed to write {msg_type} response: {e}"),
+    })
 }
 
 /// Strip error-related entities from BO4E JSON, returning only header entities.
@@ -439,7 +666,7 @@ fn strip_error_entities(bo4e: &serde_json::Value) -> serde_json::Value {
     }
 }
 
-/// Render SG4/SG5 error segments manually from the nested fehler array.
+/// Write SG4/SG5 error segments manually from the nested fehler array.
 ///
 /// Each fehler object produces:
 /// - `ERC+{code}'`
@@ -447,115 +674,46 @@ fn strip_error_entities(bo4e: &serde_json::Value) -> serde_json::Value {
 /// - `RFF+ACW:{nachrichtenReferenz}'` (from referenzen)
 /// - `RFF+AGO:{dokumentennummer}'` (from referenzen, if present)
 /// - `RFF+TN:{vorgangsnummer}'` (from referenzen, if present)
-fn render_error_segments(
+fn write_error_segments<W: std::io::Write>(
     bo4e: &serde_json::Value,
-    delimiters: &edifact_types::EdifactDelimiters,
-) -> String {
+    writer: &mut EdifactWriter<W>,
+) -> std::io::Result<()> {
     let fehler = match bo4e.get("fehler").and_then(|v| v.as_array()) {
         Some(arr) => arr,
-        None => return String::new(),
+        None => return Ok(()),
     };
 
-    let release = delimiters.release as char;
-    let mut out = String::new();
-
     for error in fehler {
         // ERC segment
         let code = error
             .get("fehlerCode")
             .and_then(|v| v.as_str())
             .unwrap_or("Z31");
-        out.push_str(&format!("ERC+{code}'"));
+        writer.write_segment("ERC", [[code]])?;
 
         // FTX+ABO segment
         let text = error
             .pointer("/fehlerEdifact/abweichungsInfo")
             .and_then(|v| v.as_str())
             .unwrap_or("");
-        out.push_str("FTX+ABO+++");
-        escape_edifact_value(text, delimiters, release, &mut out);
-        out.push('\'');
+        writer.write_segment("FTX", [&["ABO"][..], &[], &[], &[text]])?;
 
         let refs = error.get("referenzen");
 
-        // SG5: RFF+ACW (message reference)
-        if let Some(ref_val) = refs
-            .and_then(|r| r.get("nachrichtenReferenz"))
-            .and_then(|v| v.as_str())
-        {
-            out.push_str("RFF+ACW:");
-            escape_edifact_value(ref_val, delimiters, release, &mut out);
-            out.push('\'');
-        }
-
-        // SG5: RFF+AGO (document number)
-        if let Some(doc_val) = refs
-            .and_then(|r| r.get("dokumentennummer"))
-            .and_then(|v| v.as_str())
-        {
-            out.push_str("RFF+AGO:");
-            escape_edifact_value(doc_val, delimiters, release, &mut out);
-            out.push('\'');
-        }
-
-        // SG5: RFF+TN (transaction reference)
-        if let Some(txn_val) = refs
-            .and_then(|r| r.get("vorgangsnummer"))
-            .and_then(|v| v.as_str())
-        {
-            out.push_str("RFF+TN:");
-            escape_edifact_value(txn_val, delimiters, release, &mut out);
-            out.push('\'');
-        }
-    }
-
-    out
-}
-
-/// Count the number of EDIFACT segments generated for errors.
-fn count_error_segments(bo4e: &serde_json::Value) -> usize {
-    let fehler = match bo4e.get("fehler").and_then(|v| v.as_array()) {
-        Some(arr) => arr,
-        None => return 0,
-    };
-
-    let mut count = 0;
-    for error in fehler {
-        count += 2; // ERC + FTX always present
-        if let Some(refs) = error.get("referenzen") {
-            if refs.get("nachrichtenReferenz").is_some() {
-                count += 1;
-            }
-            if refs.get("dokumentennummer").is_some() {
-                count += 1;
-            }
-            if refs.get("vorgangsnummer").is_some() {
-                count += 1;
+        // SG5: RFF+ACW (message reference), RFF+AGO (document number),
+        // RFF+TN (transaction reference)
+        for (key, qualifier) in [
+            ("nachrichtenReferenz", "ACW"),
+            ("dokumentennummer", "AGO"),
+            ("vorgangsnummer", "TN"),
+        ] {
+            if let Some(value) = refs.and_then(|r| r.get(key)).and_then(|v| v.as_str()) {
+                writer.write_segment("RFF", [[qualifier, value]])?;
             }
         }
     }
-    count
-}
 
-/// Escape a value for inline EDIFACT rendering (same rules as renderer::escape_component).
-fn escape_edifact_value(
-    value: &str,
-    delimiters: &edifact_types::EdifactDelimiters,
-    release: char,
-    out: &mut String,
-) {
-    let special = [
-        delimiters.element,
-        delimiters.component,
-        delimiters.segment,
-        delimiters.release,
-    ];
-    for b in value.bytes() {
-        if special.contains(&b) {
-            out.push(release);
-        }
-        out.push(b as char);
-    }
+    Ok(())
 }
 
 /// Derive the code list responsible agency (NAD C082 d3055) from an MP-ID prefix.
@@ -595,12 +753,19 @@ fn format_german_error(issue: &automapper_validation::ValidationIssue) -> String
         "STR004" => format!("Falsche Segmentreihenfolge bei \"{path}\""),
         "STR005" => format!("Pflichtgruppe fehlt bei \"{path}\""),
         "STR006" => format!("Maximale Gruppenwiederholung ueberschritten bei \"{path}\""),
+        "STR007" => "Segmentanzahl in UNT stimmt nicht".to_string(),
+        "STR008" => "Nachrichtenreferenz in UNT stimmt nicht mit UNH ueberein".to_string(),
+        "STR009" => "Nachrichtenanzahl in UNZ stimmt nicht".to_string(),
+        "STR010" => "Datenaustauschreferenz in UNZ stimmt nicht mit UNB ueberein".to_string(),
+        "STR011" => "UNH und UNT nicht paarweise vorhanden".to_string(),
+        "STR012" => "UNB und UNZ nicht paarweise vorhanden".to_string(),
         "FMT001" => format!("Wert zu lang bei \"{path}\""),
         "FMT002" => format!("Ungueltiges numerisches Format bei \"{path}\""),
         "FMT003" => format!("Ungueltiges alphanumerisches Format bei \"{path}\""),
         "FMT004" => format!("Ungueltiges Datumsformat bei \"{path}\""),
         "FMT005" => format!("Wert zu kurz bei \"{path}\""),
         "FMT006" => format!("Pflichtfeld leer bei \"{path}\""),
+        "FMT007" => format!("Ungueltiges Zeichen fuer den Zeichensatz bei \"{path}\""),
         _ => format!("Fehler bei \"{path}\""),
     }
 }
@@ -753,6 +918,7 @@ mod tests {
             receiver_code_agency: "293".into(),
             message_ref: "MSG001".into(),
             transaction_ref: Some("TXN001".into()),
+            ..Default::default()
         };
 
         let report = automapper_validation::ValidationReport::new(
@@ -809,6 +975,7 @@ mod tests {
             receiver_code_agency: "293".into(),
             message_ref: "MSG001".into(),
             transaction_ref: Some("TXN001".into()),
+            ..Default::default()
         };
 
         let mut report = automapper_validation::ValidationReport::new(
@@ -862,6 +1029,7 @@ mod tests {
             receiver_code_agency: "293".into(),
             message_ref: "MSG001".into(),
             transaction_ref: Some("TXN001".into()),
+            ..Default::default()
         };
 
         let mut report = automapper_validation::ValidationReport::new(
@@ -903,7 +1071,7 @@ mod tests {
     }
 
     #[test]
-    fn test_render_error_segments() {
+    fn test_write_error_segments() {
         let bo4e = serde_json::json!({
             "fehler": [
                 {
@@ -927,8 +1095,9 @@ mod tests {
             ]
         });
 
-        let delimiters = edifact_types::EdifactDelimiters::default();
-        let rendered = render_error_segments(&bo4e, &delimiters);
+        let mut writer = EdifactWriter::new(Vec::new());
+        write_error_segments(&bo4e, &mut writer).unwrap();
+        let rendered = String::from_utf8(writer.into_inner()).unwrap();
 
         // Should have: ERC+FTX+RFF(ACW)+RFF(AGO)+RFF(TN) per error = 5 segments each
         let segments: Vec<&str> = rendered.split('\'').filter(|s| !s.is_empty()).collect();
@@ -949,41 +1118,6 @@ mod tests {
         assert_eq!(segments[9], "RFF+TN:DOC001");
     }
 
-    #[test]
-    fn test_count_error_segments() {
-        // With all SG5 references
-        let bo4e = serde_json::json!({
-            "fehler": [
-                {
-                    "fehlerCode": "Z31",
-                    "fehlerEdifact": {},
-                    "referenzen": {
-                        "nachrichtenReferenz": "MSG001",
-                        "dokumentennummer": "DOC001",
-                        "vorgangsnummer": "DOC001",
-                    },
-                }
-            ]
-        });
-        assert_eq!(count_error_segments(&bo4e), 5); // ERC + FTX + 3 RFFs
-
-        // Without optional SG5 references
-        let bo4e_minimal = serde_json::json!({
-            "fehler": [
-                {
-                    "fehlerCode": "Z31",
-                    "fehlerEdifact": {},
-                    "referenzen": { "nachrichtenReferenz": "MSG001" },
-                }
-            ]
-        });
-        assert_eq!(count_error_segments(&bo4e_minimal), 3); // ERC + FTX + 1 RFF
-
-        // No errors
-        let bo4e_empty = serde_json::json!({});
-        assert_eq!(count_error_segments(&bo4e_empty), 0);
-    }
-
     #[test]
     fn test_positive_contrl_bo4e() {
         let meta = OriginalMessageMeta {
@@ -996,6 +1130,7 @@ mod tests {
             receiver_code_agency: "293".into(),
             message_ref: "MSG001".into(),
             transaction_ref: None,
+            ..Default::default()
         };
 
         let report = automapper_validation::ValidationReport::new(
@@ -1029,6 +1164,7 @@ mod tests {
             receiver_code_agency: "293".into(),
             message_ref: "MSG001".into(),
             transaction_ref: None,
+            ..Default::default()
         };
 
         let mut report = automapper_validation::ValidationReport::new(
@@ -1043,12 +1179,201 @@ mod tests {
         ));
 
         let bo4e = build_contrl_bo4e(false, &meta, &report);
-        assert_eq!(bo4e["uebertragungspruefung"]["aktion"], "4");
+        // Only the message is rejected; the interchange itself is fine
+        assert_eq!(bo4e["uebertragungspruefung"]["aktion"], "7");
         assert_eq!(bo4e["nachrichtenpruefung"]["aktion"], "4");
         assert_eq!(
             bo4e["nachrichtenpruefung"]["nachrichtenReferenznummer"],
             "MSG001"
         );
+        // No position: reported on UCM as unspecified error
+        assert_eq!(
+            bo4e["nachrichtenpruefung"]["nachrichtenpruefungEdifact"]["syntaxFehlerCode"],
+            "18"
+        );
+        assert!(bo4e.get("segmentfehler").is_none());
+    }
+
+    fn contrl_meta() -> OriginalMessageMeta {
+        OriginalMessageMeta {
+            interchange_ref: "INTREF001".into(),
+            sender_id: "9900000000001".into(),
+            sender_qualifier: "500".into(),
+            receiver_id: "9900000000002".into(),
+            receiver_qualifier: "500".into(),
+            message_ref: "MSG001".into(),
+            message_identifier: MessageIdentifier {
+                message_type: "UTILMD".into(),
+                version: "D".into(),
+                release: "11A".into(),
+                controlling_agency: "UN".into(),
+                association_code: Some("S2.1".into()),
+                ..Default::default()
+            },
+            unh_segment_number: 2,
+            ..Default::default()
+        }
+    }
+
+    fn issue_at(code: &str, segment_number: u32) -> automapper_validation::ValidationIssue {
+        automapper_validation::ValidationIssue::new(
+            automapper_validation::Severity::Error,
+            automapper_validation::ValidationCategory::Format,
+            code,
+            "test issue",
+        )
+        .with_position(edifact_types::SegmentPosition::new(segment_number, 0, 1))
+    }
+
+    #[test]
+    fn test_negative_contrl_segment_and_element_errors() {
+        let mut report = automapper_validation::ValidationReport::new(
+            "UTILMD",
+            automapper_validation::ValidationLevel::Full,
+        );
+        // Value too long in NAD C082 d3039 (element 1, component 0), 5th segment of interchange
+        report.add_issue(issue_at("FMT001", 5).with_element_position(1, Some(0)));
+        // Unexpected segment at 7th segment of interchange
+        report.add_issue(issue_at("STR003", 7));
+
+        let bo4e = build_contrl_bo4e(false, &contrl_meta(), &report);
+
+        assert_eq!(bo4e["uebertragungspruefung"]["aktion"], "7");
+        let ucm = &bo4e["nachrichtenpruefung"];
+        assert_eq!(ucm["aktion"], "4");
+        assert_eq!(
+            ucm["nachrichtenpruefungEdifact"]["nachrichtentyp"],
+            "UTILMD"
+        );
+        assert_eq!(ucm["nachrichtenpruefungEdifact"]["anwendungscode"], "S2.1");
+        assert!(ucm["nachrichtenpruefungEdifact"]
+            .get("syntaxFehlerCode")
+            .is_none());
+
+        let sg2 = bo4e["segmentfehler"].as_array().unwrap();
+        assert_eq!(sg2.len(), 2);
+        // UNH is segment 2 of the interchange, so segment 5 is position 4
+        assert_eq!(sg2[0]["segmentPosition"], "4");
+        assert!(sg2[0].get("syntaxFehlerCode").is_none());
+        assert_eq!(
+            sg2[0]["segmentfehlerEdifact"]["datenelementFehlerCode"],
+            "39"
+        );
+        // Segment tag counts as 1, so element index 1 is position 3
+        assert_eq!(
+            sg2[0]["segmentfehlerEdifact"]["fehlerDatenelementPosition"],
+            "3"
+        );
+        assert_eq!(
+            sg2[0]["segmentfehlerEdifact"]["fehlerGruppendatenelementPosition"],
+            "1"
+        );
+        assert_eq!(sg2[1]["segmentPosition"], "6");
+        assert_eq!(sg2[1]["syntaxFehlerCode"], "15");
+        assert!(sg2[1].get("segmentfehlerEdifact").is_none());
+    }
+
+    #[test]
+    fn test_negative_contrl_unt_error_on_ucm() {
+        let mut report = automapper_validation::ValidationReport::new(
+            "UTILMD",
+            automapper_validation::ValidationLevel::Full,
+        );
+        report.add_issue(issue_at("STR007", 9).with_element_position(0, None));
+
+        let bo4e = build_contrl_bo4e(false, &contrl_meta(), &report);
+
+        let ucm = &bo4e["nachrichtenpruefung"]["nachrichtenpruefungEdifact"];
+        assert_eq!(ucm["syntaxFehlerCode"], "29");
+        assert_eq!(ucm["serviceSegmentBezeichner"], "UNT");
+        assert_eq!(ucm["fehlerSegmentPosition"], "2");
+        assert!(bo4e.get("segmentfehler").is_none());
+    }
+
+    #[test]
+    fn test_negative_contrl_interchange_error_rejects_uci() {
+        let mut report = automapper_validation::ValidationReport::new(
+            "UTILMD",
+            automapper_validation::ValidationLevel::Full,
+        );
+        report.add_issue(issue_at("STR010", 12).with_element_position(1, None));
+
+        let bo4e = build_contrl_bo4e(false, &contrl_meta(), &report);
+
+        let uci = &bo4e["uebertragungspruefung"];
+        assert_eq!(uci["aktion"], "4");
+        assert_eq!(
+            uci["uebertragungspruefungEdifact"]["syntaxFehlerCode"],
+            "28"
+        );
+        assert_eq!(
+            uci["uebertragungspruefungEdifact"]["serviceSegmentBezeichner"],
+            "UNZ"
+        );
+        assert_eq!(
+            uci["uebertragungspruefungEdifact"]["fehlerSegmentPosition"],
+            "3"
+        );
+        assert!(bo4e.get("nachrichtenpruefung").is_none());
+    }
+
+    #[test]
+    fn test_negative_contrl_reverse_maps_through_contrl_tomls() {
+        let mapping_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../mappings/FV2504/CONTRL");
+        let schema_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../mig-types/src/generated/fv2504/contrl/pids");
+        let engine = mig_bo4e::MappingEngine::load(&mapping_dir)
+            .unwrap()
+            .with_path_resolver(mig_bo4e::path_resolver::PathResolver::from_schema_dir(
+                &schema_dir,
+            ));
+
+        let mut report = automapper_validation::ValidationReport::new(
+            "UTILMD",
+            automapper_validation::ValidationLevel::Full,
+        );
+        report.add_issue(issue_at("FMT001", 5).with_element_position(1, Some(0)));
+        report.add_issue(issue_at("STR001", 6));
+        let bo4e = build_contrl_bo4e(false, &contrl_meta(), &report);
+
+        let tree = engine.map_all_reverse(&bo4e, None);
+
+        let uci = tree.segments.iter().find(|s| s.tag == "UCI").unwrap();
+        assert_eq!(uci.elements[0][0], "INTREF001");
+        let sg1 = tree.groups.iter().find(|g| g.group_id == "SG1").unwrap();
+        assert_eq!(sg1.repetitions.len(), 1);
+        let ucm = &sg1.repetitions[0].segments[0];
+        assert_eq!(ucm.tag, "UCM");
+        assert_eq!(ucm.elements[0][0], "MSG001");
+        assert_eq!(ucm.elements[1][0], "UTILMD");
+
+        let sg2 = &sg1.repetitions[0].child_groups[0];
+        assert_eq!(sg2.group_id, "SG2");
+        assert_eq!(sg2.repetitions.len(), 2);
+        let tags: Vec<&str> = sg2.repetitions[0]
+            .segments
+            .iter()
+            .map(|s| s.tag.as_ref())
+            .collect();
+        assert_eq!(tags, ["UCS", "UCD"]);
+        let ucd = &sg2.repetitions[0].segments[1];
+        assert_eq!(ucd.elements[0][0], "39");
+        assert_eq!(ucd.elements[1], ["3", "1"]);
+        assert_eq!(sg2.repetitions[1].segments[0].elements, [["5"], ["13"]]);
+    }
+
+    #[test]
+    fn test_contrl_code_mapping() {
+        let code = |c: &str| map_validation_issue_to_contrl_code(&issue_at(c, 1));
+        assert_eq!(code("STR001"), "13");
+        assert_eq!(code("STR002"), "35");
+        assert_eq!(code("STR004"), "15");
+        assert_eq!(code("FMT002"), "37");
+        assert_eq!(code("FMT004"), "12");
+        assert_eq!(code("FMT007"), "21");
+        assert_eq!(code("COD002"), "14");
+        assert_eq!(code("AHB003"), "18");
     }
 
     #[test]
@@ -1104,12 +1429,14 @@ mod tests {
                 vec!["ALEXANDE121980".into()],              // d0020
             ],
             segment_number: 0,
+            location: None,
         }];
 
         let unh = OwnedSegment {
             id: "UNH".into(),
             elements: vec![vec!["MSG001".into()]],
             segment_number: 1,
+            location: None,
         };
 
         // Body with NAD+MS/MR that have d3055=293 (different from UNB d0007=500)
@@ -1118,6 +1445,7 @@ mod tests {
                 id: "BGM".into(),
                 elements: vec![vec!["E01".into()], vec!["DOC001".into()]],
                 segment_number: 2,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
@@ -1126,6 +1454,7 @@ mod tests {
                     vec!["9978842000002".into(), String::new(), "293".into()],
                 ],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
@@ -1134,6 +1463,7 @@ mod tests {
                     vec!["9900269000000".into(), String::new(), "293".into()],
                 ],
                 segment_number: 4,
+                location: None,
             },
         ];
 
@@ -1145,6 +1475,8 @@ mod tests {
         // NAD code agencies should be "293" (from NAD d3055, not UNB d0007)
         assert_eq!(meta.sender_code_agency, "293");
         assert_eq!(meta.receiver_code_agency, "293");
+        // UCS positions are derived relative to UNH
+        assert_eq!(meta.unh_segment_number, 1);
     }
 
     #[test]
@@ -1162,16 +1494,19 @@ mod tests {
                 vec!["REF001".into()],
             ],
             segment_number: 0,
+            location: None,
         }];
         let unh = OwnedSegment {
             id: "UNH".into(),
             elements: vec![vec!["MSG001".into()]],
             segment_number: 1,
+            location: None,
         };
         let body = vec![OwnedSegment {
             id: "BGM".into(),
             elements: vec![vec!["E01".into()]],
             segment_number: 2,
+            location: None,
         }];
 
         let meta = extract_meta_from_edifact(&envelope, &body, &unh);
@@ -3,6 +3,7 @@
 //! Supports two conversion modes:
 //! - `mig-tree`: tokenize + assemble → return tree as JSON
 //! - `bo4e`: tokenize + split messages + per-message PID detection + assemble + TOML mapping → return hierarchical `Interchange` JSON
+//!   (an array with one `Interchange` per UNB when the input holds several interchanges)
 
 use std::collections::HashSet;
 
@@ -10,15 +11,15 @@ use axum::extract::{Query, State};
 use axum::routing::post;
 use axum::{Json, Router};
 
-use mig_assembly::assembler::Assembler;
+use mig_assembly::assembler::{Assembler, AssemblerConfig};
 use mig_assembly::navigator::AssembledTreeNavigator;
-use mig_assembly::pid_detect::detect_pid;
 use mig_assembly::pid_filter::filter_mig_for_pid;
-use mig_assembly::tokenize::{parse_to_segments, InterchangeChunks};
+use mig_assembly::tokenize::{parse_str_to_segments, InterchangeChunks};
 
 use crate::contracts::convert_v2::{
     ConvertMode, ConvertV2Query, ConvertV2Request, ConvertV2Response,
 };
+use crate::contracts::trace::TraceEntry;
 use crate::error::ApiError;
 use crate::state::AppState;
 
@@ -32,6 +33,8 @@ pub fn routes() -> Router<AppState> {
 /// Query parameters:
 /// - `enrich_codes` (bool, default `true`): When `false`, code fields are emitted
 ///   as plain strings instead of `{"code": "...", "meaning": "..."}` objects.
+/// - `validate` (bool, default `false`): Run AHB validation on the first message.
+/// - `trace` (bool, default `false`): Include the mapping trace (`bo4e` mode only).
 #[utoipa::path(
     post,
     path = "/api/v2/convert",
@@ -49,264 +52,348 @@ pub(crate) async fn convert_v2(
     Query(query): Query<ConvertV2Query>,
     Json(req): Json<ConvertV2Request>,
 ) -> Result<Json<ConvertV2Response>, ApiError> {
-    let enrich_codes = query.enrich_codes.unwrap_or(true);
     let start = std::time::Instant::now();
 
-    match req.mode {
-        ConvertMode::MigTree => {
-            let service = state
-                .mig_registry
-                .service(&req.format_version)
-                .ok_or_else(|| ApiError::BadRequest {
-                    message: format!(
-                        "No MIG service available for format version '{}'",
-                        req.format_version
-                    ),
-                })?;
+    let output = run_convert(
+        &state,
+        &req.input,
+        &req.mode,
+        &req.format_version,
+        query.enrich_codes.unwrap_or(true),
+        query.validate.unwrap_or(false),
+        query.trace.unwrap_or(false),
+    )?;
+
+    let validation = output
+        .validation
+        .map(|report| serde_json::to_value(&report))
+        .transpose()
+        .map_err(|e| ApiError::Internal {
+            message: format!("Failed to serialize validation report: {e}"),
+        })?;
 
-            let tree =
-                service
-                    .convert_to_tree(&req.input)
-                    .map_err(|e| ApiError::ConversionError {
-                        message: e.to_string(),
+    Ok(Json(ConvertV2Response {
+        mode: output.mode.to_string(),
+        result: output.result,
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        validation,
+        trace: output
+            .trace
+            .map(|entries| entries.into_iter().map(TraceEntry::from).collect()),
+    }))
+}
+
+/// Result of a v2 EDIFACT → BO4E conversion, shared by the REST and gRPC front ends.
+pub(crate) struct ConvertOutput {
+    /// The mode used for conversion ("bo4e" or "mig-tree").
+    pub mode: &'static str,
+    /// The converted result (tree JSON or `Interchange` JSON).
+    pub result: serde_json::Value,
+    /// Validation report for the first message (when validation was requested).
+    pub validation: Option<automapper_validation::ValidationReport>,
+    /// Mapping trace with pointers relative to the result (when a trace was
+    /// requested and mapping ran).
+    pub trace: Option<Vec<mig_bo4e::TraceEntry>>,
+}
+
+/// Run the v2 conversion pipeline for `input`.
+///
+/// This is the transport-independent core of `POST /api/v2/convert`.
+pub(crate) fn run_convert(
+    state: &AppState,
+    input: &str,
+    mode: &ConvertMode,
+    format_version: &str,
+    enrich_codes: bool,
+    validate: bool,
+    trace: bool,
+) -> Result<ConvertOutput, ApiError> {
+    match mode {
+        ConvertMode::MigTree => {
+            let service =
+                state
+                    .mig_registry
+                    .service(format_version)
+                    .ok_or_else(|| ApiError::BadRequest {
+                        message: format!(
+                            "No MIG service available for format version '{format_version}'"
+                        ),
                     })?;
 
-            Ok(Json(ConvertV2Response {
-                mode: "mig-tree".to_string(),
+            let tree = service
+                .convert_to_tree(input)
+                .map_err(|e| ApiError::ConversionError {
+                    message: e.to_string(),
+                })?;
+
+            Ok(ConvertOutput {
+                mode: "mig-tree",
                 result: serde_json::json!({ "tree": tree }),
-                duration_ms: start.elapsed().as_secs_f64() * 1000.0,
                 validation: None,
-            }))
+                trace: None,
+            })
         }
         ConvertMode::Bo4e => {
             // Step 1: Tokenize
-            let segments =
-                parse_to_segments(req.input.as_bytes()).map_err(|e| ApiError::ConversionError {
-                    message: format!("tokenization error: {e}"),
-                })?;
+            let segments = parse_str_to_segments(input).map_err(|e| ApiError::ConversionError {
+                message: format!("tokenization error: {e}"),
+            })?;
 
-            // Step 2: Split into messages
-            let chunks =
-                mig_assembly::split_messages(segments).map_err(|e| ApiError::ConversionError {
+            // Step 2: Split into interchanges and messages
+            let interchanges = mig_assembly::split_interchanges(segments).map_err(|e| {
+                ApiError::ConversionError {
                     message: format!("message splitting error: {e}"),
-                })?;
-
-            // Step 3: Extract envelope nachrichtendaten
-            let nachrichtendaten = mig_bo4e::model::extract_nachrichtendaten(&chunks.envelope);
+                }
+            })?;
 
-            // Step 4: Detect message type from UNH
-            let first_chunk = chunks
-                .messages
-                .first()
+            // Step 3: Detect message type from the first UNH
+            let first_chunk = interchanges
+                .iter()
+                .flat_map(|chunks| &chunks.messages)
+                .next()
                 .ok_or_else(|| ApiError::BadRequest {
                     message: "No messages found in EDIFACT content".to_string(),
                 })?;
             let (_, msg_type) = mig_bo4e::model::extract_unh_fields(&first_chunk.unh);
             let msg_type_upper = msg_type.split(':').next().unwrap_or("").to_uppercase();
 
-            // Step 5: For APERAK/CONTRL, use the response MIG + flat engine
+            // Step 4: For APERAK/CONTRL, use the response MIG + flat engine
             if msg_type_upper == "APERAK" || msg_type_upper == "CONTRL" {
                 return convert_response_message(
-                    &state,
-                    &req.format_version,
-                    &chunks,
-                    &nachrichtendaten,
+                    state,
+                    format_version,
+                    &interchanges,
                     &msg_type_upper,
                     enrich_codes,
-                    start,
+                    trace,
                 );
             }
 
-            // Step 6: Detect PID from the first message to resolve variant
+            // Step 5: Detect PID from the first message to resolve variant
             let first_segments = first_chunk.message_segments();
-            let first_pid = detect_pid(&first_segments).map_err(|e| ApiError::ConversionError {
-                message: format!("PID detection error: {e}"),
-            })?;
+            let first_pid = state
+                .mig_registry
+                .detect_pid(format_version, &first_segments)
+                .map_err(|e| ApiError::ConversionError {
+                    message: format!("PID detection error: {e}"),
+                })?;
 
             let msg_variant = state
                 .mig_registry
-                .resolve_variant(&req.format_version, &first_pid)
+                .resolve_variant(format_version, &first_pid)
                 .ok_or_else(|| ApiError::ConversionError {
                     message: format!(
                         "Could not determine message variant for PID {first_pid} in {}",
-                        req.format_version
+                        format_version
                     ),
                 })?;
 
-            // Step 7: Look up variant-specific ConversionService for MIG
+            // Step 6: Look up variant-specific ConversionService for MIG
             let service = state
                 .mig_registry
-                .service_for_variant(&req.format_version, msg_variant)
+                .service_for_variant(format_version, msg_variant)
                 .ok_or_else(|| ApiError::BadRequest {
                     message: format!(
                         "No MIG service available for format version '{}' variant '{}'",
-                        req.format_version, msg_variant
+                        format_version, msg_variant
                     ),
                 })?;
 
-            let mut nachrichten = Vec::new();
+            let mut results = Vec::with_capacity(interchanges.len());
+            let mut trace_entries = trace.then(Vec::new);
 
             // Track the last filtered MIG for optional validation
             let mut last_filtered_mig = None;
 
-            for (msg_idx, msg_chunk) in chunks.messages.iter().enumerate() {
-                let all_segments = msg_chunk.message_segments();
-
-                // Detect PID from this message's segments
-                let pid = detect_pid(&all_segments).map_err(|e| ApiError::ConversionError {
-                    message: format!("PID detection error in message {msg_idx}: {e}"),
-                })?;
-
-                // Get AHB segment numbers from cache
-                let ahb_numbers: HashSet<String> = state
-                    .mig_registry
-                    .segment_numbers_for_pid(&req.format_version, msg_variant, &pid)
-                    .ok_or_else(|| ApiError::ConversionError {
-                        message: format!(
-                            "No segment numbers cached for PID {pid} in {}/{}",
-                            req.format_version, msg_variant
-                        ),
-                    })?
-                    .iter()
-                    .cloned()
-                    .collect();
-
-                // Filter MIG for this PID and assemble
-                let filtered_mig = filter_mig_for_pid(service.mig(), &ahb_numbers);
-                let assembler = Assembler::new(&filtered_mig);
-                let tree = assembler.assemble_generic(&all_segments).map_err(|e| {
-                    ApiError::ConversionError {
-                        message: format!("assembly error in message {msg_idx}: {e}"),
-                    }
-                })?;
-
-                // Load split engines (message-level + transaction-level)
-                let (msg_engine, tx_engine) = state
-                    .mig_registry
-                    .mapping_engines_split(&req.format_version, msg_variant, &pid)
-                    .ok_or_else(|| ApiError::Internal {
-                        message: format!(
-                            "No mapping engines for {}/{}/pid_{}",
-                            req.format_version, msg_variant, pid
-                        ),
-                    })?;
-
-                // Map with split engines into hierarchical result
-                let mapped = mig_bo4e::MappingEngine::map_interchange(
-                    msg_engine,
-                    tx_engine,
-                    &tree,
-                    "SG4",
-                    enrich_codes,
-                );
+            // Messages are numbered across the whole input in error messages
+            let mut msg_idx = 0;
 
-                // Extract UNH fields
-                let (unh_referenz, nachrichten_typ) =
-                    mig_bo4e::model::extract_unh_fields(&msg_chunk.unh);
+            for (ic_idx, chunks) in interchanges.iter().enumerate() {
+                let mut nachrichten = Vec::with_capacity(chunks.messages.len());
 
-                nachrichten.push(mig_bo4e::Nachricht {
-                    unh_referenz,
-                    nachrichten_typ,
-                    stammdaten: mapped.stammdaten,
-                    transaktionen: mapped.transaktionen,
-                });
+                for (idx, msg_chunk) in chunks.messages.iter().enumerate() {
+                    let all_segments = msg_chunk.message_segments();
 
-                last_filtered_mig = Some(filtered_mig);
-            }
-
-            let interchange = mig_bo4e::Interchange {
-                nachrichtendaten,
-                nachrichten,
-            };
-
-            // Optional validation when ?validate=true
-            let validation = if query.validate.unwrap_or(false) {
-                // Reuse the first message's segments + PID for validation
-                if let Some(first_chunk) = chunks.messages.first() {
-                    let val_segments = first_chunk.message_segments();
-                    let val_pid =
-                        detect_pid(&val_segments).map_err(|e| ApiError::ConversionError {
-                            message: format!("PID detection error during validation: {e}"),
+                    // Detect PID from this message's segments
+                    let pid = state
+                        .mig_registry
+                        .detect_pid(format_version, &all_segments)
+                        .map_err(|e| ApiError::ConversionError {
+                            message: format!("PID detection error in message {msg_idx}: {e}"),
                         })?;
 
-                    let val_workflow = state
+                    // Get AHB segment numbers from cache
+                    let ahb_numbers: HashSet<String> = state
                         .mig_registry
-                        .ahb_workflow_for_pid(&req.format_version, msg_variant, &val_pid)
+                        .segment_numbers_for_pid(format_version, msg_variant, &pid)
                         .ok_or_else(|| ApiError::ConversionError {
                             message: format!(
-                                "No AHB workflow available for PID {val_pid} in {}/{}",
-                                req.format_version, msg_variant
+                                "No segment numbers cached for PID {pid} in {}/{}",
+                                format_version, msg_variant
                             ),
-                        })?;
+                        })?
+                        .iter()
+                        .cloned()
+                        .collect();
+
+                    // Filter MIG for this PID and assemble, with the PID's
+                    // automaton from the variant cache when there is one
+                    let filtered_mig = filter_mig_for_pid(service.mig(), &ahb_numbers);
+                    let assembler = match state.mig_registry.compiled_mig_for_pid(
+                        format_version,
+                        msg_variant,
+                        &pid,
+                    ) {
+                        Some(compiled) => Assembler::with_compiled(
+                            &filtered_mig,
+                            compiled,
+                            AssemblerConfig::default(),
+                        ),
+                        None => Assembler::new(&filtered_mig),
+                    };
+                    let tree = assembler.assemble_generic(&all_segments).map_err(|e| {
+                        ApiError::ConversionError {
+                            message: format!("assembly error in message {msg_idx}: {e}"),
+                        }
+                    })?;
 
-                    let external = automapper_validation::eval::NoOpExternalProvider;
-                    let evaluator = state
+                    // Load split engines (message-level + transaction-level)
+                    let (msg_engine, tx_engine) = state
                         .mig_registry
-                        .evaluator_registry()
-                        .get(msg_variant, &req.format_version)
-                        .unwrap_or_else(|| {
-                            std::sync::Arc::new(
-                                automapper_validation::UtilmdStromConditionEvaluatorFV2504::default(),
-                            )
-                        });
-                    let validator = automapper_validation::EdifactValidator::new(evaluator);
-
-                    // Assemble tree for navigator + structure diagnostics
-                    let (tree, structure_diagnostics) = if let Some(ref fmig) = last_filtered_mig {
-                        let assembler = Assembler::new(fmig);
-                        let (t, d) = assembler.assemble_with_diagnostics(&val_segments);
-                        (Some(t), d)
-                    } else {
-                        (None, vec![])
-                    };
+                        .mapping_engines_split(format_version, msg_variant, &pid)
+                        .ok_or_else(|| ApiError::Internal {
+                            message: format!(
+                                "No mapping engines for {}/{}/pid_{}",
+                                format_version, msg_variant, pid
+                            ),
+                        })?;
 
-                    // Validate with navigator when tree is available (avoids
-                    // false positives for mandatory fields in absent optional groups)
-                    let mut report = if let Some(ref t) = tree {
-                        let navigator = AssembledTreeNavigator::new(t);
-                        validator.validate_with_navigator(
-                            &val_segments,
-                            &val_workflow,
-                            &external,
-                            automapper_validation::ValidationLevel::Full,
-                            &navigator,
-                        )
+                    // Map with split engines into hierarchical result
+                    let mapped = if let Some(entries) = trace_entries.as_mut() {
+                        let mut msg_trace = mig_bo4e::MappingTrace::new();
+                        let mapped = mig_bo4e::MappingEngine::map_interchange_traced(
+                            msg_engine,
+                            tx_engine,
+                            &tree,
+                            "SG4",
+                            enrich_codes,
+                            &mut msg_trace,
+                        );
+                        let prefix = message_pointer(&interchanges, ic_idx, idx);
+                        entries.extend(reroot_trace(&prefix, msg_trace));
+                        mapped
                     } else {
-                        validator.validate(
-                            &val_segments,
-                            &val_workflow,
-                            &external,
-                            automapper_validation::ValidationLevel::Full,
+                        mig_bo4e::MappingEngine::map_interchange(
+                            msg_engine,
+                            tx_engine,
+                            &tree,
+                            "SG4",
+                            enrich_codes,
                         )
                     };
 
-                    for diag in structure_diagnostics {
-                        report.add_issue(automapper_validation::ValidationIssue::new(
-                            automapper_validation::Severity::Warning,
-                            automapper_validation::ValidationCategory::Structure,
-                            automapper_validation::ErrorCodes::UNEXPECTED_SEGMENT,
-                            diag.message,
-                        ));
-                    }
-
-                    let report_json =
-                        serde_json::to_value(&report).map_err(|e| ApiError::Internal {
-                            message: format!("Failed to serialize validation report: {e}"),
-                        })?;
-                    Some(report_json)
+                    // Extract UNH fields
+                    let (unh_referenz, nachrichten_typ) =
+                        mig_bo4e::model::extract_unh_fields(&msg_chunk.unh);
+
+                    nachrichten.push(mig_bo4e::Nachricht {
+                        unh_referenz,
+                        nachrichten_typ,
+                        nachrichtenkopf: mig_bo4e::model::extract_nachrichtenkopf(&msg_chunk.unh),
+                        stammdaten: mapped.stammdaten,
+                        transaktionen: mapped.transaktionen,
+                    });
+
+                    last_filtered_mig = Some(filtered_mig);
+
+                    msg_idx += 1;
+                }
+
+                results.push(mig_bo4e::Interchange::from_chunks(chunks, nachrichten));
+            }
+
+            // Optional validation when ?validate=true
+            let validation = if validate {
+                // Reuse the first message's segments + PID for validation
+                let val_segments = first_chunk.message_segments();
+                let val_pid = state
+                    .mig_registry
+                    .detect_pid(format_version, &val_segments)
+                    .map_err(|e| ApiError::ConversionError {
+                        message: format!("PID detection error during validation: {e}"),
+                    })?;
+
+                let val_workflow = state
+                    .mig_registry
+                    .ahb_workflow_for_pid(format_version, msg_variant, &val_pid)
+                    .ok_or_else(|| ApiError::ConversionError {
+                        message: format!(
+                            "No AHB workflow available for PID {val_pid} in {}/{}",
+                            format_version, msg_variant
+                        ),
+                    })?;
+
+                let external = automapper_validation::eval::NoOpExternalProvider;
+                let evaluator = state
+                    .mig_registry
+                    .evaluator_registry()
+                    .get(msg_variant, format_version)
+                    .unwrap_or_else(|| {
+                        std::sync::Arc::new(
+                            automapper_validation::UtilmdStromConditionEvaluatorFV2504::default(),
+                        )
+                    });
+                let validator = automapper_validation::EdifactValidator::new(evaluator);
+
+                // Assemble tree for navigator + structure diagnostics
+                let (tree, structure_diagnostics) = if let Some(ref fmig) = last_filtered_mig {
+                    let assembler = Assembler::new(fmig);
+                    let (t, d) = assembler.assemble_with_diagnostics(&val_segments);
+                    (Some(t), d)
                 } else {
-                    None
+                    (None, vec![])
+                };
+
+                // Validate with navigator when tree is available (avoids
+                // false positives for mandatory fields in absent optional groups)
+                let mut report = if let Some(ref t) = tree {
+                    let navigator = AssembledTreeNavigator::new(t);
+                    validator.validate_with_navigator(
+                        &val_segments,
+                        &val_workflow,
+                        &external,
+                        automapper_validation::ValidationLevel::Full,
+                        &navigator,
+                    )
+                } else {
+                    validator.validate(
+                        &val_segments,
+                        &val_workflow,
+                        &external,
+                        automapper_validation::ValidationLevel::Full,
+                    )
+                };
+
+                for diag in structure_diagnostics {
+                    report.add_issue(automapper_validation::ValidationIssue::new(
+                        automapper_validation::Severity::Warning,
+                        automapper_validation::ValidationCategory::Structure,
+                        automapper_validation::ErrorCodes::UNEXPECTED_SEGMENT,
+                        diag.message,
+                    ));
                 }
+
+                Some(report)
             } else {
                 None
             };
 
-            Ok(Json(ConvertV2Response {
-                mode: "bo4e".to_string(),
-                result: serde_json::to_value(&interchange).unwrap_or_default(),
-                duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            Ok(ConvertOutput {
+                mode: "bo4e",
+                result: interchanges_json(&results),
                 validation,
-            }))
+                trace: trace_entries,
+            })
         }
     }
 }
@@ -318,12 +405,11 @@ pub(crate) async fn convert_v2(
 fn convert_response_message(
     state: &AppState,
     format_version: &str,
-    chunks: &InterchangeChunks,
-    nachrichtendaten: &serde_json::Value,
+    interchanges: &[InterchangeChunks],
     msg_type: &str,
     enrich_codes: bool,
-    start: std::time::Instant,
-) -> Result<Json<ConvertV2Response>, ApiError> {
+    trace: bool,
+) -> Result<ConvertOutput, ApiError> {
     let response_mig = state
         .mig_registry
         .response_mig(format_version, msg_type)
@@ -344,43 +430,108 @@ fn convert_response_message(
             ),
         })?;
 
-    let mut nachrichten = Vec::new();
+    let mut results = Vec::with_capacity(interchanges.len());
+    let mut trace_entries = trace.then(Vec::new);
+    let mut msg_idx = 0;
 
-    for (msg_idx, msg_chunk) in chunks.messages.iter().enumerate() {
-        let all_segments = msg_chunk.message_segments();
+    for (ic_idx, chunks) in interchanges.iter().enumerate() {
+        let mut nachrichten = Vec::with_capacity(chunks.messages.len());
 
-        // Assemble using the full response MIG (no PID filtering)
-        let assembler = Assembler::new(response_mig);
-        let tree =
-            assembler
-                .assemble_generic(&all_segments)
-                .map_err(|e| ApiError::ConversionError {
+        for (idx, msg_chunk) in chunks.messages.iter().enumerate() {
+            let all_segments = msg_chunk.message_segments();
+
+            // Assemble using the full response MIG (no PID filtering)
+            let assembler = Assembler::new(response_mig);
+            let tree = assembler.assemble_generic(&all_segments).map_err(|e| {
+                ApiError::ConversionError {
                     message: format!("assembly error in {} message {msg_idx}: {e}", msg_type),
-                })?;
+                }
+            })?;
 
-        // Flat forward mapping (no message/transaction split)
-        let mapped = response_engine.map_all_forward_enriched(&tree, enrich_codes);
+            // Flat forward mapping (no message/transaction split)
+            let mapped = if let Some(entries) = trace_entries.as_mut() {
+                let mut msg_trace = mig_bo4e::MappingTrace::new();
+                let mapped =
+                    response_engine.map_all_forward_traced(&tree, enrich_codes, &mut msg_trace);
+                // The flat result becomes the message's stammdaten.
+                let prefix = format!("{}/stammdaten", message_pointer(interchanges, ic_idx, idx));
+                entries.extend(reroot_trace(&prefix, msg_trace));
+                mapped
+            } else {
+                response_engine.map_all_forward_enriched(&tree, enrich_codes)
+            };
 
-        // Extract UNH fields
-        let (unh_referenz, nachrichten_typ) = mig_bo4e::model::extract_unh_fields(&msg_chunk.unh);
+            // Extract UNH fields
+            let (unh_referenz, nachrichten_typ) =
+                mig_bo4e::model::extract_unh_fields(&msg_chunk.unh);
+
+            nachrichten.push(mig_bo4e::Nachricht {
+                unh_referenz,
+                nachrichten_typ,
+                nachrichtenkopf: mig_bo4e::model::extract_nachrichtenkopf(&msg_chunk.unh),
+                stammdaten: mapped,
+                transaktionen: vec![],
+            });
+            msg_idx += 1;
+        }
 
-        nachrichten.push(mig_bo4e::Nachricht {
-            unh_referenz,
-            nachrichten_typ,
-            stammdaten: mapped,
-            transaktionen: vec![],
-        });
+        results.push(mig_bo4e::Interchange::from_chunks(chunks, nachrichten));
     }
 
-    let interchange = mig_bo4e::Interchange {
-        nachrichtendaten: nachrichtendaten.clone(),
-        nachrichten,
+    Ok(ConvertOutput {
+        mode: "bo4e",
+        result: interchanges_json(&results),
+        validation: None,
+        trace: trace_entries,
+    })
+}
+
+/// The `bo4e` result: the `Interchange` itself for input with a single
+/// interchange, an array with one `Interchange` per UNB otherwise.
+fn interchanges_json(interchanges: &[mig_bo4e::Interchange]) -> serde_json::Value {
+    match interchanges {
+        [interchange] => serde_json::to_value(interchange).unwrap_or_default(),
+        _ => serde_json::to_value(interchanges).unwrap_or_default(),
+    }
+}
+
+/// JSON pointer of message `index` of interchange `interchange` within the
+/// result of [`interchanges_json`].
+///
+/// Mirrors [`mig_bo4e::Interchange::from_chunks`]: messages of a functional
+/// group are nested in `gruppen`, the others are numbered among themselves.
+fn message_pointer(interchanges: &[InterchangeChunks], interchange: usize, index: usize) -> String {
+    let chunks = &interchanges[interchange];
+    let root = if interchanges.len() > 1 {
+        format!("/{interchange}")
+    } else {
+        String::new()
     };
+    let in_group = |i: &usize| chunks.groups.iter().position(|g| g.messages.contains(i));
 
-    Ok(Json(ConvertV2Response {
-        mode: "bo4e".to_string(),
-        result: serde_json::to_value(&interchange).unwrap_or_default(),
-        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
-        validation: None,
-    }))
+    match in_group(&index) {
+        Some(g) => {
+            let position = index - chunks.groups[g].messages.start;
+            format!("{root}/gruppen/{g}/nachrichten/{position}")
+        }
+        None => {
+            let position = (0..index).filter(|i| in_group(i).is_none()).count();
+            format!("{root}/nachrichten/{position}")
+        }
+    }
+}
+
+/// Prefix a message's trace pointers with its position in the `Interchange` JSON.
+pub(crate) fn reroot_trace(
+    prefix: &str,
+    trace: mig_bo4e::MappingTrace,
+) -> Vec<mig_bo4e::TraceEntry> {
+    trace
+        .into_entries()
+        .into_iter()
+        .map(|mut entry| {
+            entry.target = format!("{prefix}{}", entry.target);
+            entry
+        })
+        .collect()
 }
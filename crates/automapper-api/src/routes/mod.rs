@@ -5,6 +5,7 @@ pub mod coordinators;
 pub mod fixtures;
 pub mod health;
 pub mod inspect;
+pub mod query_v2;
 pub(crate) mod reverse_pipeline;
 pub mod reverse_v2;
 pub mod validate_bo4e;
@@ -29,4 +30,5 @@ pub fn api_v2_routes() -> Router<AppState> {
         .merge(reverse_v2::routes())
         .merge(validate_v2::routes())
         .merge(validate_bo4e::routes())
+        .merge(query_v2::routes())
 }
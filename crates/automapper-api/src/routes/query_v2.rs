@@ -0,0 +1,166 @@
+//! V2 path query endpoint.
+//!
+//! Assembles each message of the EDIFACT input and evaluates a
+//! [`mig_assembly::query`] path query against it.
+
+use std::collections::HashSet;
+
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+
+use mig_assembly::assembler::{AssembledSegment, Assembler, AssemblerConfig};
+use mig_assembly::pid_filter::filter_mig_for_pid;
+use mig_assembly::query::{Query, QueryMatch, QueryNode};
+use mig_assembly::tokenize::parse_str_to_segments;
+
+use crate::contracts::query_v2::{
+    QueryV2Match, QueryV2Message, QueryV2Request, QueryV2Response, QueryV2Segment,
+};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Build v2 query routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/query", post(query_v2))
+}
+
+/// `POST /api/v2/query` -- select nodes from EDIFACT messages by path query.
+///
+/// Parses the query, then tokenizes the input, detects each message's PID,
+/// assembles it against the PID-filtered MIG, and returns the matches per
+/// message. An invalid query is rejected with 400 before any assembly.
+#[utoipa::path(
+    post,
+    path = "/api/v2/query",
+    request_body = QueryV2Request,
+    responses(
+        (status = 200, description = "Query matches per message", body = QueryV2Response),
+        (status = 400, description = "Bad request or invalid query"),
+        (status = 422, description = "Conversion error"),
+    ),
+    tag = "v2"
+)]
+pub(crate) async fn query_v2(
+    State(state): State<AppState>,
+    Json(req): Json<QueryV2Request>,
+) -> Result<Json<QueryV2Response>, ApiError> {
+    let start = std::time::Instant::now();
+
+    let query = Query::parse(&req.query).map_err(|e| ApiError::BadRequest {
+        message: e.to_string(),
+    })?;
+
+    // Step 1: Tokenize
+    let segments = parse_str_to_segments(&req.input).map_err(|e| ApiError::ConversionError {
+        message: format!("tokenization error: {e}"),
+    })?;
+
+    // Step 2: Split into interchanges and messages
+    let interchanges =
+        mig_assembly::split_interchanges(segments).map_err(|e| ApiError::ConversionError {
+            message: format!("message splitting error: {e}"),
+        })?;
+
+    let mut messages = Vec::new();
+    for (ic_idx, chunks) in interchanges.iter().enumerate() {
+        for (msg_idx, msg_chunk) in chunks.messages.iter().enumerate() {
+            let all_segments = msg_chunk.message_segments();
+
+            // Step 3: Detect PID and resolve the message variant
+            let pid = state
+                .mig_registry
+                .detect_pid(&req.format_version, &all_segments)
+                .map_err(|e| ApiError::ConversionError {
+                    message: format!("PID detection error in message {msg_idx}: {e}"),
+                })?;
+            let msg_variant = state
+                .mig_registry
+                .resolve_variant(&req.format_version, &pid)
+                .ok_or_else(|| ApiError::ConversionError {
+                    message: format!(
+                        "Could not determine message variant for PID {pid} in {}",
+                        req.format_version
+                    ),
+                })?;
+            let service = state
+                .mig_registry
+                .service_for_variant(&req.format_version, msg_variant)
+                .ok_or_else(|| ApiError::BadRequest {
+                    message: format!(
+                        "No MIG service available for format version '{}' variant '{}'",
+                        req.format_version, msg_variant
+                    ),
+                })?;
+
+            // Step 4: Assemble against the PID-filtered MIG
+            let ahb_numbers: HashSet<String> = state
+                .mig_registry
+                .segment_numbers_for_pid(&req.format_version, msg_variant, &pid)
+                .ok_or_else(|| ApiError::ConversionError {
+                    message: format!(
+                        "No segment numbers cached for PID {pid} in {}/{}",
+                        req.format_version, msg_variant
+                    ),
+                })?
+                .iter()
+                .cloned()
+                .collect();
+            let filtered_mig = filter_mig_for_pid(service.mig(), &ahb_numbers);
+            let assembler = match state.mig_registry.compiled_mig_for_pid(
+                &req.format_version,
+                msg_variant,
+                &pid,
+            ) {
+                Some(compiled) => {
+                    Assembler::with_compiled(&filtered_mig, compiled, AssemblerConfig::default())
+                }
+                None => Assembler::new(&filtered_mig),
+            };
+            let tree = assembler.assemble_generic(&all_segments).map_err(|e| {
+                ApiError::ConversionError {
+                    message: format!("assembly error in message {msg_idx}: {e}"),
+                }
+            })?;
+
+            // Step 5: Evaluate the query
+            messages.push(QueryV2Message {
+                interchange: ic_idx,
+                message: msg_idx,
+                pid,
+                matches: query.select(&tree).iter().map(to_match).collect(),
+            });
+        }
+    }
+
+    Ok(Json(QueryV2Response {
+        query: query.to_string(),
+        messages,
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+    }))
+}
+
+fn to_match(m: &QueryMatch<'_>) -> QueryV2Match {
+    let (kind, value, segments) = match m.node {
+        QueryNode::Group(instance) => ("group", None, instance.segments.iter().collect()),
+        QueryNode::Segment(segment) => ("segment", None, vec![segment]),
+        QueryNode::Value { value, .. } => ("value", Some(value.to_string()), vec![]),
+    };
+    QueryV2Match {
+        path: m.canonical_path(),
+        kind: kind.to_string(),
+        value,
+        segments: segments.into_iter().map(to_segment).collect(),
+    }
+}
+
+fn to_segment(segment: &AssembledSegment<'_>) -> QueryV2Segment {
+    QueryV2Segment {
+        tag: segment.tag.to_string(),
+        elements: segment
+            .elements
+            .iter()
+            .map(|e| e.iter().map(|c| c.to_string()).collect())
+            .collect(),
+    }
+}
@@ -79,16 +79,36 @@ pub(crate) fn extract_pid(nachricht: &mig_bo4e::Nachricht) -> Result<&str, ApiEr
 }
 
 /// Reverse-map a Nachricht to an AssembledTree using the loaded engines.
+///
+/// When `trace` is given, every field mapping is recorded in it with pointers
+/// relative to the Nachricht.
 pub(crate) fn reverse_map_nachricht(
     ctx: &ReversePipelineContext,
     nachricht: &mig_bo4e::Nachricht,
-) -> mig_assembly::assembler::AssembledTree {
+    trace: Option<&mut mig_bo4e::MappingTrace>,
+) -> mig_assembly::assembler::AssembledTree<'static> {
     let mapped = mig_bo4e::model::MappedMessage {
         stammdaten: nachricht.stammdaten.clone(),
         transaktionen: nachricht.transaktionen.clone(),
         nesting_info: Default::default(),
     };
-    MappingEngine::map_interchange_reverse(ctx.msg_engine, ctx.tx_engine, &mapped, "SG4", None)
+    match trace {
+        Some(trace) => MappingEngine::map_interchange_reverse_traced(
+            ctx.msg_engine,
+            ctx.tx_engine,
+            &mapped,
+            "SG4",
+            None,
+            trace,
+        ),
+        None => MappingEngine::map_interchange_reverse(
+            ctx.msg_engine,
+            ctx.tx_engine,
+            &mapped,
+            "SG4",
+            None,
+        ),
+    }
 }
 
 /// Disassemble an AssembledTree and render as EDIFACT message segments (UNH + body + UNT).
@@ -101,7 +121,11 @@ pub(crate) fn render_message_segments(
     let disassembler = Disassembler::new(&ctx.filtered_mig);
     let dis_segments = disassembler.disassemble(tree);
 
-    let unh = mig_bo4e::model::rebuild_unh(&nachricht.unh_referenz, &nachricht.nachrichten_typ);
+    let unh = mig_bo4e::model::rebuild_unh(
+        &nachricht.unh_referenz,
+        &nachricht.nachrichten_typ,
+        &nachricht.nachrichtenkopf,
+    );
     let unh_dis = DisassembledSegment {
         tag: unh.id,
         elements: unh.elements,
@@ -122,36 +146,67 @@ pub(crate) fn render_message_segments(
 }
 
 /// Render a full EDIFACT interchange string with UNA/UNB/messages/UNZ envelope.
+///
+/// `message_parts` are in [`Interchange::messages`](mig_bo4e::Interchange::messages)
+/// order: messages outside functional groups first, then each group's
+/// messages, which are wrapped in UNG/UNE rebuilt from `gruppendaten`.
 pub(crate) fn render_full_edifact(
     interchange: &mig_bo4e::Interchange,
     message_parts: &[String],
 ) -> String {
     let delimiters = edifact_types::EdifactDelimiters::default();
     let una_str = delimiters.to_una_string();
+    let render = |segment: mig_types::segment::OwnedSegment| {
+        let dis = DisassembledSegment {
+            tag: segment.id,
+            elements: segment.elements,
+        };
+        render_edifact(&[dis], &delimiters)
+    };
+
+    let mut full_edifact = una_str;
+    full_edifact.push_str(&render(mig_bo4e::model::rebuild_unb(
+        &interchange.nachrichtendaten,
+    )));
+
+    let ungrouped = interchange.nachrichten.len().min(message_parts.len());
+    let (ungrouped_parts, mut rest) = message_parts.split_at(ungrouped);
+    for part in ungrouped_parts {
+        full_edifact.push_str(part);
+    }
 
-    let unb = mig_bo4e::model::rebuild_unb(&interchange.nachrichtendaten);
-    let unb_segments = vec![DisassembledSegment {
-        tag: unb.id,
-        elements: unb.elements,
-    }];
+    // UNZ counts groups, if present, instead of the messages inside them
+    let mut unz_count = ungrouped_parts.len();
+    for gruppe in &interchange.gruppen {
+        let n = gruppe.nachrichten.len().min(rest.len());
+        if n == 0 {
+            break;
+        }
+        let (group_parts, remaining) = rest.split_at(n);
+        rest = remaining;
+
+        let group_ref = gruppe
+            .gruppendaten
+            .get("gruppenRef")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        full_edifact.push_str(&render(mig_bo4e::model::rebuild_ung(&gruppe.gruppendaten)));
+        for part in group_parts {
+            full_edifact.push_str(part);
+        }
+        full_edifact.push_str(&render(mig_bo4e::model::rebuild_une(n, group_ref)));
+        unz_count += 1;
+    }
 
     let interchange_ref = interchange
         .nachrichtendaten
         .get("interchangeRef")
         .and_then(|v| v.as_str())
         .unwrap_or("00000");
-    let unz = mig_bo4e::model::rebuild_unz(message_parts.len(), interchange_ref);
-    let unz_segments = vec![DisassembledSegment {
-        tag: unz.id,
-        elements: unz.elements,
-    }];
-
-    let mut full_edifact = una_str;
-    full_edifact.push_str(&render_edifact(&unb_segments, &delimiters));
-    for part in message_parts {
-        full_edifact.push_str(part);
-    }
-    full_edifact.push_str(&render_edifact(&unz_segments, &delimiters));
+    full_edifact.push_str(&render(mig_bo4e::model::rebuild_unz(
+        unz_count,
+        interchange_ref,
+    )));
 
     full_edifact
 }
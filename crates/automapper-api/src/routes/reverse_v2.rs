@@ -3,14 +3,17 @@
 //! Accepts BO4E JSON at interchange/nachricht/transaktion level
 //! and converts back to an EDIFACT string or MIG tree.
 
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::routing::post;
 use axum::{Json, Router};
 
 use crate::contracts::reverse_v2::{
-    normalize_to_interchange, ReverseMode, ReverseV2Request, ReverseV2Response,
+    normalize_to_interchange, EnvelopeOverrides, InputLevel, ReverseMode, ReverseV2Query,
+    ReverseV2Request, ReverseV2Response,
 };
+use crate::contracts::trace::TraceEntry;
 use crate::error::ApiError;
+use crate::routes::convert_v2::reroot_trace;
 use crate::routes::reverse_pipeline::{
     extract_pid, load_reverse_context, render_full_edifact, render_message_segments,
     reverse_map_nachricht,
@@ -23,9 +26,14 @@ pub fn routes() -> Router<AppState> {
 }
 
 /// `POST /api/v2/reverse` — BO4E to EDIFACT reverse conversion.
+///
+/// Query parameters:
+/// - `trace` (bool, default `false`): Include the mapping trace. Target paths
+///   point into the input normalized to interchange level.
 #[utoipa::path(
     post,
     path = "/api/v2/reverse",
+    params(ReverseV2Query),
     request_body = ReverseV2Request,
     responses(
         (status = 200, description = "Reverse conversion result", body = ReverseV2Response),
@@ -36,42 +44,92 @@ pub fn routes() -> Router<AppState> {
 )]
 pub(crate) async fn reverse_v2(
     State(state): State<AppState>,
+    Query(query): Query<ReverseV2Query>,
     Json(req): Json<ReverseV2Request>,
 ) -> Result<Json<ReverseV2Response>, ApiError> {
     let start = std::time::Instant::now();
 
+    let output = run_reverse(
+        &state,
+        &req.input,
+        &req.level,
+        &req.mode,
+        &req.format_version,
+        req.envelope.as_ref(),
+        query.trace.unwrap_or(false),
+    )?;
+
+    Ok(Json(ReverseV2Response {
+        mode: output.mode.to_string(),
+        result: output.result,
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        trace: output
+            .trace
+            .map(|entries| entries.into_iter().map(TraceEntry::from).collect()),
+    }))
+}
+
+/// Result of a v2 BO4E → EDIFACT conversion, shared by the REST and gRPC front ends.
+pub(crate) struct ReverseOutput {
+    /// The mode used for conversion ("edifact" or "mig-tree").
+    pub mode: &'static str,
+    /// The EDIFACT string or MIG tree JSON.
+    pub result: serde_json::Value,
+    /// Mapping trace with pointers into the normalized interchange (when requested).
+    pub trace: Option<Vec<mig_bo4e::TraceEntry>>,
+}
+
+/// Run the v2 reverse pipeline for `input`.
+///
+/// This is the transport-independent core of `POST /api/v2/reverse`.
+pub(crate) fn run_reverse(
+    state: &AppState,
+    input: &serde_json::Value,
+    level: &InputLevel,
+    mode: &ReverseMode,
+    format_version: &str,
+    envelope: Option<&EnvelopeOverrides>,
+    trace: bool,
+) -> Result<ReverseOutput, ApiError> {
     // Step 1: Normalize input to Interchange
-    let interchange = normalize_to_interchange(&req.input, &req.level, req.envelope.as_ref())
-        .map_err(|e| ApiError::BadRequest {
+    let interchange =
+        normalize_to_interchange(input, level, envelope).map_err(|e| ApiError::BadRequest {
             message: format!("Input normalization error: {e}"),
         })?;
 
     let delimiters = edifact_types::EdifactDelimiters::default();
 
     let mut all_edifact_parts: Vec<String> = Vec::new();
+    let mut trace_entries = trace.then(Vec::new);
 
     // Step 2: Process each message
-    for nachricht in &interchange.nachrichten {
+    for (pointer, nachricht) in interchange.messages() {
         let pid = extract_pid(nachricht)?;
         let msg_variant = state
             .mig_registry
-            .resolve_variant(&req.format_version, pid)
+            .resolve_variant(format_version, pid)
             .ok_or_else(|| ApiError::ConversionError {
                 message: format!(
-                    "Could not determine message variant for PID {pid} in {}",
-                    req.format_version
+                    "Could not determine message variant for PID {pid} in {format_version}"
                 ),
             })?;
-        let ctx = load_reverse_context(&state, &req.format_version, msg_variant, pid)?;
-        let tree = reverse_map_nachricht(&ctx, nachricht);
+        let ctx = load_reverse_context(state, format_version, msg_variant, pid)?;
+        let tree = if let Some(entries) = trace_entries.as_mut() {
+            let mut msg_trace = mig_bo4e::MappingTrace::new();
+            let tree = reverse_map_nachricht(&ctx, nachricht, Some(&mut msg_trace));
+            entries.extend(reroot_trace(&pointer, msg_trace));
+            tree
+        } else {
+            reverse_map_nachricht(&ctx, nachricht, None)
+        };
 
-        match req.mode {
+        match mode {
             ReverseMode::MigTree => {
-                return Ok(Json(ReverseV2Response {
-                    mode: "mig-tree".to_string(),
+                return Ok(ReverseOutput {
+                    mode: "mig-tree",
                     result: serde_json::to_value(&tree).unwrap_or_default(),
-                    duration_ms: start.elapsed().as_secs_f64() * 1000.0,
-                }));
+                    trace: trace_entries,
+                });
             }
             ReverseMode::Edifact => {
                 all_edifact_parts.push(render_message_segments(
@@ -87,9 +145,9 @@ pub(crate) async fn reverse_v2(
     // Step 3: Wrap with envelope
     let full_edifact = render_full_edifact(&interchange, &all_edifact_parts);
 
-    Ok(Json(ReverseV2Response {
-        mode: "edifact".to_string(),
+    Ok(ReverseOutput {
+        mode: "edifact",
         result: serde_json::Value::String(full_edifact),
-        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
-    }))
+        trace: trace_entries,
+    })
 }
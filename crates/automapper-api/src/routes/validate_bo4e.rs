@@ -9,7 +9,7 @@ use axum::{Json, Router};
 
 use mig_assembly::assembler::Assembler;
 use mig_assembly::navigator::AssembledTreeNavigator;
-use mig_assembly::tokenize::parse_to_segments;
+use mig_assembly::tokenize::parse_str_to_segments;
 
 use crate::contracts::reverse_v2::normalize_to_interchange;
 use crate::contracts::validate_bo4e::{ValidateBo4eRequest, ValidateBo4eResponse};
@@ -53,9 +53,9 @@ pub(crate) async fn validate_bo4e(
         })?;
 
     // Step 2: Process the first message
-    let nachricht = interchange
-        .nachrichten
-        .first()
+    let (_, nachricht) = interchange
+        .messages()
+        .next()
         .ok_or_else(|| ApiError::BadRequest {
             message: "No messages (nachrichten) in input".to_string(),
         })?;
@@ -73,16 +73,15 @@ pub(crate) async fn validate_bo4e(
     let ctx = load_reverse_context(&state, &req.format_version, msg_variant, pid)?;
 
     // Step 3: Reverse map BO4E → AssembledTree → EDIFACT
-    let tree = reverse_map_nachricht(&ctx, nachricht);
+    let tree = reverse_map_nachricht(&ctx, nachricht, None);
     let delimiters = edifact_types::EdifactDelimiters::default();
     let msg_edifact = render_message_segments(&ctx, nachricht, &tree, &delimiters);
     let full_edifact = render_full_edifact(&interchange, &[msg_edifact]);
 
     // Step 4: Re-tokenize the rendered EDIFACT
-    let segments =
-        parse_to_segments(full_edifact.as_bytes()).map_err(|e| ApiError::ConversionError {
-            message: format!("Re-tokenization error: {e}"),
-        })?;
+    let segments = parse_str_to_segments(&full_edifact).map_err(|e| ApiError::ConversionError {
+        message: format!("Re-tokenization error: {e}"),
+    })?;
 
     let chunks = mig_assembly::split_messages(segments).map_err(|e| ApiError::ConversionError {
         message: format!("Message splitting error: {e}"),
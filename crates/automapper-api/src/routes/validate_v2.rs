@@ -9,11 +9,8 @@ use axum::extract::State;
 use axum::routing::post;
 use axum::{Json, Router};
 
-use mig_assembly::assembler::Assembler;
-use mig_assembly::navigator::AssembledTreeNavigator;
-use mig_assembly::pid_detect::detect_pid;
 use mig_assembly::pid_filter::filter_mig_for_pid;
-use mig_assembly::tokenize::parse_to_segments;
+use mig_assembly::tokenize::parse_str_to_segments;
 
 use crate::contracts::validate_v2::{ValidateV2Request, ValidateV2Response};
 use crate::error::ApiError;
@@ -53,10 +50,9 @@ pub(crate) async fn validate_v2(
     };
 
     // Step 1: Tokenize EDIFACT
-    let segments =
-        parse_to_segments(req.input.as_bytes()).map_err(|e| ApiError::ConversionError {
-            message: format!("tokenization error: {e}"),
-        })?;
+    let segments = parse_str_to_segments(&req.input).map_err(|e| ApiError::ConversionError {
+        message: format!("tokenization error: {e}"),
+    })?;
 
     // Step 2: Split into messages
     let chunks = mig_assembly::split_messages(segments).map_err(|e| ApiError::ConversionError {
@@ -74,9 +70,12 @@ pub(crate) async fn validate_v2(
     let all_segments = msg_chunk.message_segments();
 
     // Step 4: Detect PID
-    let pid = detect_pid(&all_segments).map_err(|e| ApiError::ConversionError {
-        message: format!("PID detection error: {e}"),
-    })?;
+    let pid = state
+        .mig_registry
+        .detect_pid(&req.format_version, &all_segments)
+        .map_err(|e| ApiError::ConversionError {
+            message: format!("PID detection error: {e}"),
+        })?;
 
     // Step 5: Resolve message variant from PID
     let msg_variant = state
@@ -100,7 +99,7 @@ pub(crate) async fn validate_v2(
             ),
         })?;
 
-    // Step 7: Load variant-specific MIG service, filter for PID, assemble with diagnostics
+    // Step 7: Load variant-specific MIG service and filter it for the PID
     let service = state
         .mig_registry
         .service_for_variant(&req.format_version, msg_variant)
@@ -125,8 +124,6 @@ pub(crate) async fn validate_v2(
         .cloned()
         .collect();
     let filtered_mig = filter_mig_for_pid(service.mig(), &ahb_numbers);
-    let assembler = Assembler::new(&filtered_mig);
-    let (tree, structure_diagnostics) = assembler.assemble_with_diagnostics(&all_segments);
 
     // Step 8: Build external condition provider
     let external: Box<dyn automapper_validation::eval::ExternalConditionProvider> =
@@ -148,34 +145,32 @@ pub(crate) async fn validate_v2(
                 automapper_validation::UtilmdStromConditionEvaluatorFV2504::default(),
             )
         });
-    let validator = automapper_validation::EdifactValidator::new(evaluator);
+    let mut validator = automapper_validation::EdifactValidator::new(evaluator);
+    // Message segments exclude the UNB, so pass its declared character set
+    let syntax_identifier = chunks
+        .envelope
+        .iter()
+        .find(|s| s.is("UNB"))
+        .and_then(|unb| edifact_types::SyntaxIdentifier::from_code(unb.get_component(0, 0)));
+    if let Some(syntax_identifier) = syntax_identifier {
+        validator = validator.with_syntax_identifier(syntax_identifier);
+    }
 
-    // Step 10: Run validation with group navigator
-    let navigator = AssembledTreeNavigator::new(&tree);
-    let mut report = validator.validate_with_navigator(
+    // Step 10: Run structure and condition validation against the filtered MIG
+    let report = validator.validate_with_mig(
         &all_segments,
         &workflow,
         external.as_ref(),
         level,
-        &navigator,
+        &filtered_mig,
     );
 
-    // Step 11: Add structure diagnostics as ValidationIssues
-    for diag in structure_diagnostics {
-        report.add_issue(automapper_validation::ValidationIssue::new(
-            automapper_validation::Severity::Warning,
-            automapper_validation::ValidationCategory::Structure,
-            automapper_validation::ErrorCodes::UNEXPECTED_SEGMENT,
-            diag.message,
-        ));
-    }
-
-    // Step 12: Serialize and return
+    // Step 11: Serialize and return
     let report_json = serde_json::to_value(&report).map_err(|e| ApiError::Internal {
         message: format!("Failed to serialize validation report: {e}"),
     })?;
 
-    // Step 13: Generate response message if requested
+    // Step 12: Generate response message if requested
     let response_message = if let Some(ref gen_opts) = req.generate_response {
         let opts = crate::response_generator::parse_response_options(
             gen_opts.response_type.as_deref(),
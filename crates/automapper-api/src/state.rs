@@ -1,10 +1,12 @@
 //! Application state and coordinator registry.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use mig_assembly::parsing::parse_mig;
-use mig_assembly::ConversionService;
+use mig_assembly::pid_detect::PidDetector;
+use mig_assembly::tokenize::OwnedSegment;
+use mig_assembly::{AssemblyError, CompiledMig, ConversionService};
 use mig_bo4e::code_lookup::CodeLookup;
 use mig_bo4e::engine::VariantCache;
 use mig_bo4e::path_resolver::PathResolver;
@@ -59,6 +61,9 @@ pub struct MigServiceRegistry {
     /// Per-PID AHB segment numbers from cache.
     /// Key: "{fv}/{variant}/pid_{pid}" → segment number list
     pid_segment_numbers: HashMap<String, Vec<String>>,
+    /// Per-PID assembly automata (PID-filtered MIG, compiled) from cache.
+    /// Key: "{fv}/{variant}/pid_{pid}"
+    compiled_migs: HashMap<String, CompiledMig>,
     /// Flat mapping engines for response messages (APERAK, CONTRL).
     /// Key: "{fv}/{msg_type}" e.g. "FV2504/APERAK"
     response_engines: HashMap<String, MappingEngine>,
@@ -67,6 +72,9 @@ pub struct MigServiceRegistry {
     response_migs: HashMap<String, MigSchema>,
     /// Condition evaluator registry keyed by (message_type, format_version).
     evaluator_registry: automapper_validation::eval::EvaluatorRegistry,
+    /// AHB-driven PID detectors, built from the PID schemas on first use.
+    /// Key: "{fv}" e.g. "FV2504"
+    pid_detectors: HashMap<String, OnceLock<PidDetector>>,
 }
 
 impl MigServiceRegistry {
@@ -81,6 +89,7 @@ impl MigServiceRegistry {
         // PID resolution indices built from cache
         let mut pid_to_variant: HashMap<String, String> = HashMap::new();
         let mut pid_segment_numbers: HashMap<String, Vec<String>> = HashMap::new();
+        let mut compiled_migs: HashMap<String, CompiledMig> = HashMap::new();
 
         // Load TOML mapping definitions: mappings/{FV}/{msg_variant}/{pid}/
         // Also loads shared message-level definitions from mappings/{FV}/{msg_variant}/message/
@@ -88,6 +97,7 @@ impl MigServiceRegistry {
         let mut mapping_engines = HashMap::new();
         let mut message_engines = HashMap::new();
         let mut transaction_engines = HashMap::new();
+        let mut pid_detectors = HashMap::new();
         let mappings_base = std::path::Path::new("mappings");
         let cache_base = std::path::Path::new("cache/mappings");
         if mappings_base.exists() {
@@ -98,6 +108,7 @@ impl MigServiceRegistry {
                         continue;
                     }
                     let fv = fv_entry.file_name().to_string_lossy().to_string();
+                    pid_detectors.insert(fv.clone(), OnceLock::new());
                     // Iterate msg_variant dirs (e.g., UTILMD_Strom)
                     if let Ok(variant_entries) = std::fs::read_dir(&fv_path) {
                         for variant_entry in variant_entries.flatten() {
@@ -211,6 +222,10 @@ impl MigServiceRegistry {
                                             let key = format!("{}/{}/{}", fv, variant, pid_dirname);
                                             pid_segment_numbers.insert(key, numbers.clone());
                                         }
+                                        for (pid_dirname, compiled) in vc.compiled_migs {
+                                            let key = format!("{}/{}/{}", fv, variant, pid_dirname);
+                                            compiled_migs.insert(key, compiled);
+                                        }
 
                                         tracing::info!(
                                             "Loaded variant cache for {}/{} ({} combined, {} tx engines, {} code lookups, {} pid seg nums, mig: {})",
@@ -698,9 +713,11 @@ impl MigServiceRegistry {
             empty_engine: MappingEngine::from_definitions(vec![]),
             pid_to_variant,
             pid_segment_numbers,
+            compiled_migs,
             response_engines,
             response_migs,
             evaluator_registry,
+            pid_detectors,
         }
     }
 
@@ -803,6 +820,32 @@ impl MigServiceRegistry {
         self.pid_to_variant.get(&cache_key).map(|s| s.as_str())
     }
 
+    /// Detect the PID of a message (UNH..UNT segments).
+    ///
+    /// Uses the AHB-driven [`PidDetector`] for `fv`, which reports ambiguous
+    /// messages as [`AssemblyError::AmbiguousPid`]. Falls back to the UTILMD
+    /// heuristic when no PID schemas are available for `fv`.
+    pub fn detect_pid(
+        &self,
+        fv: &str,
+        segments: &[OwnedSegment],
+    ) -> Result<String, AssemblyError> {
+        match self.pid_detector(fv) {
+            Some(detector) => detector.detect(segments),
+            None => mig_assembly::pid_detect::detect_pid(segments),
+        }
+    }
+
+    /// Get the PID detector for a format version, building it on first use
+    /// from `crates/mig-types/src/generated/{fv}/*/pids/`.
+    pub fn pid_detector(&self, fv: &str) -> Option<&PidDetector> {
+        let detector = self
+            .pid_detectors
+            .get(fv)?
+            .get_or_init(|| load_pid_detector(fv));
+        (!detector.is_empty()).then_some(detector)
+    }
+
     /// Get cached AHB segment numbers for a specific PID.
     ///
     /// Key: "{fv}/{variant}/pid_{pid}". Returns None if not cached (caller should
@@ -817,6 +860,21 @@ impl MigServiceRegistry {
         self.pid_segment_numbers.get(&key)
     }
 
+    /// Get the cached assembly automaton for a specific PID, compiled from
+    /// the MIG filtered by [`Self::segment_numbers_for_pid`].
+    ///
+    /// Key: "{fv}/{variant}/pid_{pid}". Returns None if not cached (caller
+    /// should let the assembler compile the filtered MIG).
+    pub fn compiled_mig_for_pid(
+        &self,
+        fv: &str,
+        msg_variant: &str,
+        pid: &str,
+    ) -> Option<&CompiledMig> {
+        let key = format!("{}/{}/pid_{}", fv, msg_variant, pid);
+        self.compiled_migs.get(&key)
+    }
+
     /// Get a response mapping engine for a message type (e.g., APERAK, CONTRL).
     /// Key format: "FV2504/APERAK"
     pub fn response_engine(&self, fv: &str, msg_type: &str) -> Option<&MappingEngine> {
@@ -865,6 +923,31 @@ impl MigServiceRegistry {
 
 /// Try to load a CodeLookup from the PID schema JSON and attach it to the engine.
 ///
+/// Build a PID detector from the PID schemas of all message types of `fv`.
+fn load_pid_detector(fv: &str) -> PidDetector {
+    let mut detector = PidDetector::new();
+    let fv_dir = std::path::Path::new("crates/mig-types/src/generated").join(fv.to_lowercase());
+    if let Ok(msg_entries) = std::fs::read_dir(&fv_dir) {
+        for msg_entry in msg_entries.flatten() {
+            let pids_dir = msg_entry.path().join("pids");
+            if !pids_dir.is_dir() {
+                continue;
+            }
+            if let Err(e) = detector.add_schema_dir(&pids_dir) {
+                tracing::warn!(
+                    "Failed to load PID schemas from {}: {e}",
+                    pids_dir.display()
+                );
+            }
+        }
+    }
+    tracing::info!(
+        "Built PID detector for {fv} from {} PID schemas",
+        detector.signatures().len()
+    );
+    detector
+}
+
 /// Schema path: `crates/mig-types/src/generated/{fv_lower}/{msg_type_lower}/pids/{pid_dirname}_schema.json`
 fn attach_code_lookup(
     fv: &str,
@@ -1104,9 +1187,11 @@ mod tests {
             empty_engine: MappingEngine::from_definitions(vec![]),
             pid_to_variant,
             pid_segment_numbers: HashMap::new(),
+            compiled_migs: HashMap::new(),
             response_engines: HashMap::new(),
             response_migs: HashMap::new(),
             evaluator_registry: automapper_validation::eval::EvaluatorRegistry::new(),
+            pid_detectors: HashMap::new(),
         };
 
         assert_eq!(
@@ -1133,9 +1218,11 @@ mod tests {
             empty_engine: MappingEngine::from_definitions(vec![]),
             pid_to_variant,
             pid_segment_numbers: HashMap::new(),
+            compiled_migs: HashMap::new(),
             response_engines: HashMap::new(),
             response_migs: HashMap::new(),
             evaluator_registry: automapper_validation::eval::EvaluatorRegistry::new(),
+            pid_detectors: HashMap::new(),
         };
 
         // Resolves from cache without any AHB schemas loaded
@@ -1162,9 +1249,11 @@ mod tests {
             empty_engine: MappingEngine::from_definitions(vec![]),
             pid_to_variant: HashMap::new(),
             pid_segment_numbers,
+            compiled_migs: HashMap::new(),
             response_engines: HashMap::new(),
             response_migs: HashMap::new(),
             evaluator_registry: automapper_validation::eval::EvaluatorRegistry::new(),
+            pid_detectors: HashMap::new(),
         };
 
         let nums = registry.segment_numbers_for_pid("FV2504", "UTILMD_Strom", "55001");
@@ -1204,9 +1293,11 @@ mod tests {
             empty_engine: MappingEngine::from_definitions(vec![]),
             pid_to_variant: HashMap::new(),
             pid_segment_numbers: HashMap::new(),
+            compiled_migs: HashMap::new(),
             response_engines: HashMap::new(),
             response_migs: HashMap::new(),
             evaluator_registry: automapper_validation::eval::EvaluatorRegistry::new(),
+            pid_detectors: HashMap::new(),
         };
 
         let workflow = registry.ahb_workflow_for_pid("FV2504", "UTILMD_Strom", "55001");
@@ -5,7 +5,7 @@
 //! specific PID. This module converts between them.
 
 use automapper_generator::schema::ahb::{AhbSchema, Pruefidentifikator};
-use automapper_validation::{AhbCodeRule, AhbFieldRule, AhbWorkflow};
+use automapper_validation::{AhbCodeRule, AhbFieldRule, AhbWorkflow, ConditionDefinitions};
 
 /// Convert an [`AhbSchema`] + PID string into an [`AhbWorkflow`] for the validator.
 ///
@@ -26,6 +26,26 @@ pub fn ahb_workflow_from_schema(schema: &AhbSchema, pid: &str) -> Option<AhbWork
     Some(ahb_workflow_from_pruefidentifikator(pruefid))
 }
 
+/// Collect the package (`Pakete`) and Unterbedingung (`UB_Bedingungen`)
+/// definitions of an [`AhbSchema`] for the validator.
+///
+/// Entries that cannot be parsed, or Unterbedingungen defined in terms of
+/// themselves, are skipped; references to them evaluate to Unknown.
+pub fn condition_definitions_from_schema(schema: &AhbSchema) -> ConditionDefinitions {
+    let mut definitions = ConditionDefinitions::new();
+    for paket in &schema.pakete {
+        if let Err(e) = definitions.add_package_definition(&paket.id, &paket.description) {
+            tracing::warn!("Skipping AHB package [{}]: {e}", paket.id);
+        }
+    }
+    for ub in &schema.ub_bedingungen {
+        if let Err(e) = definitions.add_sub_condition_definition(&ub.id, &ub.description) {
+            tracing::warn!("Skipping AHB Unterbedingung [{}]: {e}", ub.id);
+        }
+    }
+    definitions
+}
+
 /// Convert a single [`Pruefidentifikator`] into an [`AhbWorkflow`].
 ///
 /// This is useful when you already have the specific PID object and don't need
@@ -278,6 +298,8 @@ mod tests {
                 segment_numbers: vec!["0001".to_string(), "0002".to_string()],
             }],
             bedingungen: vec![],
+            pakete: vec![],
+            ub_bedingungen: vec![],
         }
     }
 
@@ -388,6 +410,26 @@ mod tests {
         assert_eq!(workflow.fields[0].codes[0].ahb_status, "X");
     }
 
+    #[test]
+    fn test_bridge_condition_definitions() {
+        use automapper_generator::schema::ahb::BedingungDefinition;
+
+        let def = |id: &str, description: &str| BedingungDefinition {
+            id: id.to_string(),
+            description: description.to_string(),
+        };
+        let mut schema = make_schema("55001", vec![]);
+        schema.pakete = vec![def("4P", "[1] [2] [3]")];
+        schema.ub_bedingungen = vec![def("UB1", "[1] ∧ [2]"), def("UB2", "[UB2]")];
+
+        let definitions = condition_definitions_from_schema(&schema);
+
+        assert_eq!(definitions.package(4), Some(&[1, 2, 3][..]));
+        assert!(definitions.sub_condition(1).is_some());
+        // Self-referencing Unterbedingung is skipped
+        assert!(definitions.sub_condition(2).is_none());
+    }
+
     #[test]
     fn test_bridge_communication_direction_none() {
         let schema = AhbSchema {
@@ -404,6 +446,8 @@ mod tests {
                 segment_numbers: vec![],
             }],
             bedingungen: vec![],
+            pakete: vec![],
+            ub_bedingungen: vec![],
         };
 
         let workflow = ahb_workflow_from_schema(&schema, "11001").unwrap();
@@ -454,6 +498,8 @@ mod tests {
                 },
             ],
             bedingungen: vec![],
+            pakete: vec![],
+            ub_bedingungen: vec![],
         };
 
         let w1 = ahb_workflow_from_schema(&schema, "55001").unwrap();
@@ -339,6 +339,76 @@ async fn test_convert_v2_bo4e_with_validate_flag() {
     }
 }
 
+// --- Trace flag ---
+
+#[tokio::test]
+async fn test_convert_v2_bo4e_with_trace_flag() {
+    let fixture_path = std::path::Path::new(
+        "example_market_communication_bo4e_transactions/UTILMD/FV2504/55001_UTILMD_S2.1_ALEXANDE121980.edi",
+    );
+    if !fixture_path.exists() {
+        eprintln!(
+            "Skipping test: fixture not found at {}",
+            fixture_path.display()
+        );
+        return;
+    }
+    let input = std::fs::read_to_string(fixture_path).unwrap();
+
+    let app = app();
+
+    let body = serde_json::json!({
+        "input": input,
+        "mode": "bo4e",
+        "format_version": "FV2504"
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v2/convert?trace=true")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    if status == StatusCode::OK {
+        let resp: ConvertV2Response = serde_json::from_slice(&body_bytes).unwrap();
+        let trace = resp
+            .trace
+            .expect("trace should be present when ?trace=true");
+        assert!(!trace.is_empty(), "trace should have entries");
+
+        // Every entry points into the converted interchange
+        for entry in &trace {
+            assert!(
+                entry.target_path.starts_with("/nachrichten/"),
+                "unexpected target {}",
+                entry.target_path
+            );
+            assert!(entry.outcome.is_some());
+        }
+        assert!(trace
+            .iter()
+            .any(|e| e.outcome.as_deref() == Some("mapped") && e.segment_number.is_some()));
+    } else {
+        let body_str = String::from_utf8_lossy(&body_bytes);
+        eprintln!("bo4e+trace mode returned {status}: {body_str}");
+        assert!(
+            status == StatusCode::BAD_REQUEST
+                || status == StatusCode::UNPROCESSABLE_ENTITY
+                || status == StatusCode::INTERNAL_SERVER_ERROR,
+            "Unexpected status: {status}"
+        );
+    }
+}
+
 // --- Missing fields ---
 
 #[tokio::test]
@@ -9,7 +9,9 @@ use tokio::net::TcpListener;
 use automapper_api::grpc::inspection_proto::inspection_service_client::InspectionServiceClient;
 use automapper_api::grpc::inspection_proto::{InspectEdifactRequest, ListCoordinatorsRequest};
 use automapper_api::grpc::transform_proto::transform_service_client::TransformServiceClient;
-use automapper_api::grpc::transform_proto::EdifactToBo4eRequest;
+use automapper_api::grpc::transform_proto::{
+    Bo4eToEdifactRequest, EdifactToBo4eRequest, InputLevel,
+};
 use automapper_api::state::AppState;
 
 /// Start a test server and return the address.
@@ -70,12 +72,13 @@ async fn test_grpc_inspect_edifact() {
 }
 
 #[tokio::test]
-async fn test_grpc_convert_edifact_to_bo4e_returns_unimplemented() {
+async fn test_grpc_convert_edifact_to_bo4e_reports_conversion_error() {
     let addr = start_test_server().await;
     let url = format!("http://{addr}");
 
     let mut client = TransformServiceClient::connect(url).await.unwrap();
 
+    // No PID can be detected from a bare BGM — conversion fails inside the pipeline
     let edifact = concat!(
         "UNB+UNOC:3+sender+receiver+231215:1200+ref001'",
         "UNH+1+UTILMD:D:11A:UN:5.2e'",
@@ -84,18 +87,94 @@ async fn test_grpc_convert_edifact_to_bo4e_returns_unimplemented() {
         "UNZ+1+ref001'"
     );
 
-    let result = client
+    let response = client
         .convert_edifact_to_bo4e(EdifactToBo4eRequest {
             edifact: edifact.to_string(),
             format_version: "FV2504".to_string(),
-            include_trace: false,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!response.success);
+    assert!(response.result.is_empty());
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].code, "CONVERSION_ERROR");
+}
+
+#[tokio::test]
+async fn test_grpc_convert_edifact_to_bo4e_without_format_version_is_invalid() {
+    let addr = start_test_server().await;
+    let url = format!("http://{addr}");
+
+    let mut client = TransformServiceClient::connect(url).await.unwrap();
+
+    let result = client
+        .convert_edifact_to_bo4e(EdifactToBo4eRequest {
+            edifact: "UNH+1+UTILMD:D:11A:UN:S2.1'BGM+E01'UNT+2+1'".to_string(),
+            ..Default::default()
         })
         .await;
 
-    // Legacy pipeline removed — should return UNIMPLEMENTED
-    assert!(result.is_err());
-    let status = result.unwrap_err();
-    assert_eq!(status.code(), tonic::Code::Unimplemented);
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_grpc_convert_bo4e_to_edifact_invalid_json_is_invalid() {
+    let addr = start_test_server().await;
+    let url = format!("http://{addr}");
+
+    let mut client = TransformServiceClient::connect(url).await.unwrap();
+
+    let result = client
+        .convert_bo4e_to_edifact(Bo4eToEdifactRequest {
+            bo4e_json: "{not json".to_string(),
+            format_version: "FV2504".to_string(),
+            level: InputLevel::Interchange as i32,
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_grpc_convert_stream_answers_every_request() {
+    let addr = start_test_server().await;
+    let url = format!("http://{addr}");
+
+    let mut client = TransformServiceClient::connect(url).await.unwrap();
+
+    let requests = vec![
+        EdifactToBo4eRequest {
+            edifact: "UNH+1+UTILMD:D:11A:UN:S2.1'BGM+E01'UNT+2+1'".to_string(),
+            format_version: "FV2504".to_string(),
+            ..Default::default()
+        },
+        EdifactToBo4eRequest {
+            edifact: "UNH+2+UTILMD:D:11A:UN:S2.1'BGM+E01'UNT+2+2'".to_string(),
+            format_version: String::new(),
+            ..Default::default()
+        },
+    ];
+
+    let mut stream = client
+        .convert_edifact_to_bo4e_stream(tokio_stream::iter(requests))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut responses = Vec::new();
+    while let Some(item) = stream.message().await.unwrap() {
+        responses.push(item);
+    }
+
+    // Failures are reported per item instead of terminating the stream
+    assert_eq!(responses.len(), 2);
+    assert!(!responses[0].success);
+    assert!(!responses[1].success);
+    assert_eq!(responses[1].errors[0].code, "BAD_REQUEST");
 }
 
 #[tokio::test]
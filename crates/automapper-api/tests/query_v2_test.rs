@@ -0,0 +1,100 @@
+//! Integration tests for POST /api/v2/query.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use automapper_api::contracts::query_v2::QueryV2Response;
+use automapper_api::state::AppState;
+
+fn app() -> axum::Router {
+    let state = AppState::new();
+    automapper_api::build_http_router(state)
+}
+
+async fn post_query(body: serde_json::Value) -> (StatusCode, Vec<u8>) {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v2/query")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_query_v2_missing_query_returns_422() {
+    let (status, _) = post_query(serde_json::json!({
+        "input": "UNA:+.? 'UNB+UNOC:3'",
+        "format_version": "FV2504"
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_query_v2_invalid_query_returns_400() {
+    let (status, body) = post_query(serde_json::json!({
+        "input": "UNA:+.? 'UNB+UNOC:3'",
+        "format_version": "FV2504",
+        "query": "SG4[IDE.0=24/SG8"
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "BAD_REQUEST");
+    assert!(json["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid query at offset 4"));
+}
+
+#[tokio::test]
+async fn test_query_v2_selects_values_per_message() {
+    let fixture_path = std::path::Path::new(
+        "example_market_communication_bo4e_transactions/UTILMD/FV2504/55001_UTILMD_S2.1_ALEXANDE121980.edi",
+    );
+    if !fixture_path.exists() {
+        eprintln!(
+            "Skipping test: fixture not found at {}",
+            fixture_path.display()
+        );
+        return;
+    }
+    let input = std::fs::read_to_string(fixture_path).unwrap();
+
+    let (status, body) = post_query(serde_json::json!({
+        "input": input,
+        "format_version": "FV2504",
+        "query": "sg4/ide.1"
+    }))
+    .await;
+
+    if status == StatusCode::OK {
+        let resp: QueryV2Response = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.query, "SG4/IDE.1.0");
+        assert!(!resp.messages.is_empty());
+        let first = &resp.messages[0];
+        assert_eq!(first.pid, "55001");
+        assert_eq!(first.matches.len(), 1);
+        assert_eq!(first.matches[0].kind, "value");
+        assert_eq!(first.matches[0].path, "SG4[0]/IDE[0].1.0");
+        assert!(first.matches[0].value.is_some());
+    } else {
+        // MIG/AHB XML not available in this environment
+        assert!(
+            status == StatusCode::BAD_REQUEST || status == StatusCode::UNPROCESSABLE_ENTITY,
+            "unexpected status {status}"
+        );
+    }
+}
@@ -159,6 +159,67 @@ async fn test_reverse_transaktion_level_produces_edifact() {
     }
 }
 
+// --- Trace flag ---
+
+#[tokio::test]
+async fn test_reverse_with_trace_flag_returns_trace() {
+    let app = app();
+
+    let body = serde_json::json!({
+        "input": {
+            "stammdaten": {
+                "Marktlokation": { "marktlokationsId": "51238696781" }
+            },
+            "transaktionsdaten": {
+                "pruefidentifikator": "55001",
+                "kategorie": "E01"
+            }
+        },
+        "level": "transaktion",
+        "formatVersion": "FV2504",
+        "mode": "edifact"
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v2/reverse?trace=true")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    if status == StatusCode::OK {
+        let resp: ReverseV2Response = serde_json::from_slice(&body_bytes).unwrap();
+        let trace = resp
+            .trace
+            .expect("trace should be present when ?trace=true");
+        assert!(trace
+            .iter()
+            .all(|e| e.target_path.starts_with("/nachrichten/0/")));
+        assert!(trace
+            .iter()
+            .any(|e| e.value.as_deref() == Some("51238696781")
+                || e.raw_value.as_deref() == Some("51238696781")));
+    } else {
+        // MIG XML or AHB not available — acceptable in CI
+        let body_str = String::from_utf8_lossy(&body_bytes);
+        eprintln!("reverse with trace returned {status}: {body_str}");
+        assert!(
+            status == StatusCode::BAD_REQUEST
+                || status == StatusCode::UNPROCESSABLE_ENTITY
+                || status == StatusCode::INTERNAL_SERVER_ERROR,
+            "Unexpected status: {status}"
+        );
+    }
+}
+
 // --- MIG-tree mode ---
 
 #[tokio::test]
@@ -1145,6 +1145,7 @@ fn is_empty_group(group: &PidGroupInfo) -> bool {
 /// Collected wrapper struct definition: name → (doc_comment, segments, child_field_lines).
 /// Used to deduplicate child types (e.g., SG10) that appear under multiple parent instances.
 struct WrapperDef {
+    group_id: String,
     doc: String,
     segments: BTreeSet<String>,
     child_fields: Vec<String>,
@@ -1213,6 +1214,7 @@ fn collect_wrapper_defs(
         defs.insert(
             wrapper_name,
             WrapperDef {
+                group_id: group.group_id.clone(),
                 doc,
                 segments: tag_set,
                 child_fields,
@@ -1230,7 +1232,7 @@ fn emit_wrapper_structs(pid_struct_name: &str, groups: &[PidGroupInfo], out: &mu
 
     for (name, def) in &defs {
         out.push_str(&def.doc);
-        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        out.push_str("#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]\n");
         out.push_str(&format!("pub struct {name} {{\n"));
         for seg_id in &def.segments {
             let field_name = seg_id.to_lowercase();
@@ -1412,6 +1414,412 @@ fn parse_child_field_line(line: &str) -> Option<(String, String)> {
     Some((field, inner.to_string()))
 }
 
+// ---------------------------------------------------------------------------
+// Serialization Code Generation: to_segments() / into_assembled_tree()
+// ---------------------------------------------------------------------------
+
+/// A segment or child group field of a generated struct.
+#[derive(Debug, Clone, PartialEq)]
+enum SerializedField {
+    /// A segment field, named after the lowercased tag.
+    Segment(String),
+    /// A `Vec` of wrapper structs for one (qualified) occurrence of a group.
+    Group {
+        field: String,
+        type_name: String,
+        group_id: String,
+    },
+}
+
+/// MIG counters of the segments (by tag) and groups (by ID) directly below
+/// a MIG node, used to emit fields in MIG order.
+#[derive(Debug, Default)]
+struct MigCounters {
+    segments: HashMap<String, u32>,
+    groups: HashMap<String, u32>,
+}
+
+impl MigCounters {
+    /// Counters of the root segments and top-level groups.
+    fn root(mig: &MigSchema) -> Self {
+        let mut counters = Self::default();
+        counters.add(&mig.segments, &mig.segment_groups);
+        counters
+    }
+
+    /// Counters below every MIG group with the given ID.
+    fn group(mig: &MigSchema, group_id: &str) -> Self {
+        fn visit(groups: &[MigSegmentGroup], group_id: &str, counters: &mut MigCounters) {
+            for group in groups {
+                if group.id == group_id {
+                    counters.add(&group.segments, &group.nested_groups);
+                }
+                visit(&group.nested_groups, group_id, counters);
+            }
+        }
+        let mut counters = Self::default();
+        visit(&mig.segment_groups, group_id, &mut counters);
+        counters
+    }
+
+    fn add(&mut self, segments: &[MigSegment], groups: &[MigSegmentGroup]) {
+        // Service segments have counter "0000"; they keep the position of the
+        // segment before them, so UNZ stays after UNT.
+        let mut previous = 0;
+        for seg in segments {
+            let counter = parse_mig_counter(seg.counter.as_deref()).unwrap_or(previous);
+            previous = counter;
+            let entry = self.segments.entry(seg.id.clone()).or_insert(counter);
+            *entry = (*entry).min(counter);
+        }
+        for group in groups {
+            let counter = parse_mig_counter(group.counter.as_deref())
+                .or_else(|| {
+                    let entry = group.segments.first()?;
+                    parse_mig_counter(entry.counter.as_deref())
+                })
+                .unwrap_or(u32::MAX);
+            let entry = self.groups.entry(group.id.clone()).or_insert(counter);
+            *entry = (*entry).min(counter);
+        }
+    }
+
+    /// Sort fields into MIG order. Fields the MIG does not know go last;
+    /// ties (e.g. qualifier variants of one group) keep their order.
+    fn sort(&self, fields: &mut [SerializedField]) {
+        fields.sort_by_key(|field| match field {
+            SerializedField::Segment(tag) => self.segments.get(tag),
+            SerializedField::Group { group_id, .. } => self.groups.get(group_id),
+        }
+        .copied()
+        .unwrap_or(u32::MAX));
+    }
+}
+
+fn parse_mig_counter(counter: Option<&str>) -> Option<u32> {
+    counter?.parse().ok().filter(|&c| c > 0)
+}
+
+/// Group ID of a child field line's field name: "sg8_z79" -> "SG8".
+fn child_group_id(field: &str) -> String {
+    field.split('_').next().unwrap_or(field).to_uppercase()
+}
+
+/// Generate `to_segments()`, `into_assembled_tree()`, builder methods, and
+/// the `PidTree` impl for a PID type and its wrapper structs.
+///
+/// Segments and child groups are emitted in MIG order (by MIG counter),
+/// independent of the struct field order.
+pub fn generate_pid_to_segments(
+    pid: &Pruefidentifikator,
+    mig: &MigSchema,
+    ahb: &AhbSchema,
+) -> String {
+    let structure = analyze_pid_structure_with_qualifiers(pid, mig, ahb);
+    let struct_name = format!("Pid{}", pid.id);
+    let mut out = String::new();
+
+    let mut defs: BTreeMap<String, WrapperDef> = BTreeMap::new();
+    for group in &structure.groups {
+        collect_wrapper_defs(&struct_name, group, &mut defs);
+    }
+
+    for (wrapper_name, def) in &defs {
+        let mut fields: Vec<SerializedField> = def
+            .segments
+            .iter()
+            .map(|tag| SerializedField::Segment(tag.clone()))
+            .collect();
+        for line in &def.child_fields {
+            if let Some((field, type_name)) = parse_child_field_line(line) {
+                let group_id = child_group_id(&field);
+                fields.push(SerializedField::Group {
+                    field,
+                    type_name,
+                    group_id,
+                });
+            }
+        }
+        MigCounters::group(mig, &def.group_id).sort(&mut fields);
+        emit_wrapper_serialization(wrapper_name, &fields, &mut out);
+    }
+
+    let mut fields: Vec<SerializedField> = structure
+        .top_level_segments
+        .iter()
+        .map(|tag| SerializedField::Segment(tag.clone()))
+        .collect();
+    for group in &structure.groups {
+        if is_empty_group(group) {
+            continue;
+        }
+        fields.push(SerializedField::Group {
+            field: make_wrapper_field_name(group),
+            type_name: make_wrapper_type_name(&struct_name, group),
+            group_id: group.group_id.clone(),
+        });
+    }
+    MigCounters::root(mig).sort(&mut fields);
+    emit_pid_serialization(&struct_name, &fields, &mut out);
+    emit_pid_tree_impl(&struct_name, pid, mig, &mut out);
+
+    out
+}
+
+/// Emit builder methods, `to_segments()`, and `into_group_instance()` for a wrapper struct.
+fn emit_wrapper_serialization(wrapper_name: &str, fields: &[SerializedField], out: &mut String) {
+    out.push_str(&format!("impl {wrapper_name} {{\n"));
+
+    for field in fields {
+        match field {
+            SerializedField::Segment(tag) => {
+                let name = tag.to_lowercase();
+                out.push_str(&format!("    /// Set the `{tag}` segment.\n"));
+                out.push_str(&format!(
+                    "    pub fn with_{name}(mut self, segment: OwnedSegment) -> Self {{\n"
+                ));
+                out.push_str(&format!("        self.{name} = Some(segment);\n"));
+            }
+            SerializedField::Group {
+                field,
+                type_name,
+                group_id,
+            } => {
+                out.push_str(&format!("    /// Add an `{group_id}` repetition.\n"));
+                out.push_str(&format!(
+                    "    pub fn with_{field}(mut self, item: {type_name}) -> Self {{\n"
+                ));
+                out.push_str(&format!("        self.{field}.push(item);\n"));
+            }
+        }
+        out.push_str("        self\n");
+        out.push_str("    }\n\n");
+    }
+
+    out.push_str("    /// Serialize this group back to segments, in MIG order.\n");
+    out.push_str("    pub fn to_segments(&self) -> Vec<OwnedSegment> {\n");
+    if fields.is_empty() {
+        out.push_str("        Vec::new()\n");
+    } else {
+        out.push_str("        let mut segments = Vec::new();\n");
+        for field in fields {
+            match field {
+                SerializedField::Segment(tag) => {
+                    let name = tag.to_lowercase();
+                    out.push_str(&format!(
+                        "        segments.extend(self.{name}.iter().cloned());\n"
+                    ));
+                }
+                SerializedField::Group { field, .. } => {
+                    out.push_str(&format!("        for item in &self.{field} {{\n"));
+                    out.push_str("            segments.extend(item.to_segments());\n");
+                    out.push_str("        }\n");
+                }
+            }
+        }
+        out.push_str("        segments\n");
+    }
+    out.push_str("    }\n\n");
+
+    out.push_str("    /// Convert this group into a group instance of an assembled tree.\n");
+    out.push_str("    pub fn into_group_instance(self) -> OwnedGroupInstance {\n");
+    if fields.is_empty() {
+        out.push_str("        OwnedGroupInstance::default()\n");
+    } else {
+        out.push_str("        let mut instance = OwnedGroupInstance::default();\n");
+        for field in fields {
+            match field {
+                SerializedField::Segment(tag) => {
+                    let name = tag.to_lowercase();
+                    out.push_str(&format!("        instance.segments.extend(self.{name});\n"));
+                }
+                SerializedField::Group {
+                    field,
+                    type_name,
+                    group_id,
+                } => {
+                    out.push_str(&format!(
+                        "        instance.push_group(\"{group_id}\", self.{field}.into_iter().map({type_name}::into_group_instance));\n"
+                    ));
+                }
+            }
+        }
+        out.push_str("        instance\n");
+    }
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+}
+
+/// Emit the constructor, builder methods, `to_segments()`, and
+/// `into_assembled_tree()` for the main PID struct.
+///
+/// Root segments before the first group go before the groups, segments after
+/// the last group after them (e.g. UNT), and segments in between become
+/// inter-group segments (e.g. UNS).
+fn emit_pid_serialization(struct_name: &str, fields: &[SerializedField], out: &mut String) {
+    let is_group = |f: &SerializedField| matches!(f, SerializedField::Group { .. });
+    let first_group = fields.iter().position(is_group).unwrap_or(fields.len());
+    let last_group = fields.iter().rposition(is_group);
+    let segment_names: Vec<String> = fields
+        .iter()
+        .filter_map(|f| match f {
+            SerializedField::Segment(tag) => Some(tag.to_lowercase()),
+            SerializedField::Group { .. } => None,
+        })
+        .collect();
+
+    out.push_str(&format!("impl {struct_name} {{\n"));
+
+    // Constructor: top-level segments are mandatory, groups start empty
+    out.push_str("    /// Create this PID from its top-level segments, without any groups.\n");
+    if segment_names.len() > 7 {
+        out.push_str("    #[allow(clippy::too_many_arguments)]\n");
+    }
+    let params: Vec<String> = segment_names
+        .iter()
+        .map(|name| format!("{name}: OwnedSegment"))
+        .collect();
+    out.push_str(&format!("    pub fn new({}) -> Self {{\n", params.join(", ")));
+    out.push_str("        Self {\n");
+    for name in &segment_names {
+        out.push_str(&format!("            {name},\n"));
+    }
+    for field in fields {
+        if let SerializedField::Group { field, .. } = field {
+            out.push_str(&format!("            {field}: Vec::new(),\n"));
+        }
+    }
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    for field in fields {
+        if let SerializedField::Group {
+            field,
+            type_name,
+            group_id,
+        } = field
+        {
+            out.push_str(&format!("    /// Add an `{group_id}` repetition.\n"));
+            out.push_str(&format!(
+                "    pub fn with_{field}(mut self, item: {type_name}) -> Self {{\n"
+            ));
+            out.push_str(&format!("        self.{field}.push(item);\n"));
+            out.push_str("        self\n");
+            out.push_str("    }\n\n");
+        }
+    }
+
+    // to_segments(): leading segments as a vec! literal, the rest appended
+    out.push_str("    /// Serialize this message back to segments, in MIG order.\n");
+    out.push_str("    pub fn to_segments(&self) -> Vec<OwnedSegment> {\n");
+    let leading: Vec<String> = fields[..first_group]
+        .iter()
+        .filter_map(|f| match f {
+            SerializedField::Segment(tag) => Some(format!("self.{}.clone()", tag.to_lowercase())),
+            SerializedField::Group { .. } => None,
+        })
+        .collect();
+    if first_group == fields.len() {
+        out.push_str(&format!("        vec![{}]\n", leading.join(", ")));
+    } else {
+        if leading.is_empty() {
+            out.push_str("        let mut segments = Vec::new();\n");
+        } else {
+            out.push_str(&format!(
+                "        let mut segments = vec![{}];\n",
+                leading.join(", ")
+            ));
+        }
+        for field in &fields[first_group..] {
+            match field {
+                SerializedField::Segment(tag) => {
+                    let name = tag.to_lowercase();
+                    out.push_str(&format!("        segments.push(self.{name}.clone());\n"));
+                }
+                SerializedField::Group { field, .. } => {
+                    out.push_str(&format!("        for item in &self.{field} {{\n"));
+                    out.push_str("            segments.extend(item.to_segments());\n");
+                    out.push_str("        }\n");
+                }
+            }
+        }
+        out.push_str("        segments\n");
+    }
+    out.push_str("    }\n\n");
+
+    // into_assembled_tree()
+    out.push_str("    /// Convert this message into an assembled tree.\n");
+    out.push_str("    pub fn into_assembled_tree(self) -> OwnedTree {\n");
+    out.push_str("        let mut tree = OwnedTree::default();\n");
+    for (idx, field) in fields.iter().enumerate() {
+        match field {
+            SerializedField::Segment(tag) => {
+                let name = tag.to_lowercase();
+                if idx > first_group && last_group.is_some_and(|last| idx < last) {
+                    out.push_str(&format!(
+                        "        tree.push_inter_group_segment(self.{name});\n"
+                    ));
+                } else {
+                    out.push_str(&format!("        tree.segments.push(self.{name});\n"));
+                }
+            }
+            SerializedField::Group {
+                field,
+                type_name,
+                group_id,
+            } => {
+                out.push_str(&format!(
+                    "        tree.push_group(\"{group_id}\", self.{field}.into_iter().map({type_name}::into_group_instance));\n"
+                ));
+            }
+        }
+        if Some(idx) == last_group {
+            out.push_str("        tree.post_group_start = tree.segments.len();\n");
+        }
+    }
+    if last_group.is_none() {
+        out.push_str("        tree.post_group_start = tree.segments.len();\n");
+    }
+    out.push_str("        tree\n");
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+}
+
+/// Emit the `PidTree` impl with the PID's metadata.
+fn emit_pid_tree_impl(
+    struct_name: &str,
+    pid: &Pruefidentifikator,
+    mig: &MigSchema,
+    out: &mut String,
+) {
+    let kommunikation_von = match pid.kommunikation_von {
+        Some(ref komm) => format!("Some({:?})", sanitize_doc(komm)),
+        None => "None".to_string(),
+    };
+    let methods = [
+        ("pid_id", "&str", format!("{:?}", pid.id)),
+        (
+            "beschreibung",
+            "&str",
+            format!("{:?}", sanitize_doc(&pid.beschreibung)),
+        ),
+        ("kommunikation_von", "Option<&str>", kommunikation_von),
+        ("message_type", "&str", format!("{:?}", mig.message_type)),
+        ("format_version", "&str", format!("{:?}", mig.format_version)),
+    ];
+
+    out.push_str(&format!("impl PidTree for {struct_name} {{\n"));
+    for (name, ret, value) in methods {
+        out.push_str(&format!("    fn {name}(&self) -> {ret} {{\n"));
+        out.push_str(&format!("        {value}\n"));
+        out.push_str("    }\n\n");
+    }
+    out.push_str("    fn to_segments(&self) -> Vec<OwnedSegment> {\n");
+    out.push_str(&format!("        {struct_name}::to_segments(self)\n"));
+    out.push_str("    }\n");
+    out.push_str("}\n");
+}
+
 // ---------------------------------------------------------------------------
 // JSON Schema Generation
 // ---------------------------------------------------------------------------
@@ -1994,6 +2402,7 @@ pub fn generate_pid_types(
     for pid in &ahb.workflows {
         let struct_source = generate_pid_struct(pid, mig, ahb);
         let assembly_source = generate_pid_from_segments(pid, mig, ahb);
+        let serialization_source = generate_pid_to_segments(pid, mig, ahb);
         let module_name = format!("pid_{}", pid.id.to_lowercase());
         let filename = format!("{module_name}.rs");
 
@@ -2003,9 +2412,12 @@ pub fn generate_pid_types(
              //! Do not edit manually.\n\n\
              use serde::{{Deserialize, Serialize}};\n\
              use crate::segment::OwnedSegment;\n\
-             use crate::cursor::{{SegmentCursor, SegmentNotFound, peek_is, consume, expect_segment}};\n\n\
+             use crate::cursor::{{SegmentCursor, SegmentNotFound, peek_is, consume, expect_segment}};\n\
+             use crate::traits::PidTree;\n\
+             use crate::tree::{{OwnedGroupInstance, OwnedTree}};\n\n\
              {struct_source}\n\
-             {assembly_source}",
+             {assembly_source}\n\
+             {serialization_source}",
             pid.id,
             sanitize_doc(&pid.beschreibung)
         );
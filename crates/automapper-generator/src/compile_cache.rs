@@ -5,6 +5,8 @@
 
 use std::path::Path;
 
+use mig_assembly::pid_filter::filter_mig_for_pid;
+use mig_assembly::CompiledMig;
 use mig_bo4e::code_lookup::CodeLookup;
 use mig_bo4e::path_resolver::PathResolver;
 use mig_bo4e::pid_schema_index::PidSchemaIndex;
@@ -84,7 +86,7 @@ pub fn compile_variant(
     output_base: &Path,
 ) -> Result<CompileStats, Box<dyn std::error::Error>> {
     use mig_bo4e::engine::VariantCache;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     let mut stats = CompileStats::default();
     let variant_path = mappings_base.join(fv).join(variant);
@@ -110,6 +112,19 @@ pub fn compile_variant(
     // Parse AHB XML for PID segment numbers
     let pid_segment_numbers = find_and_parse_ahb_numbers(xml_base, fv, msg_type_upper, variant_suffix);
 
+    // Compile each PID's filtered MIG into its assembly automaton
+    let compiled_migs: HashMap<String, CompiledMig> = match &mig_schema {
+        Some(mig) => pid_segment_numbers
+            .iter()
+            .map(|(pid_dirname, numbers)| {
+                let numbers: HashSet<String> = numbers.iter().cloned().collect();
+                let filtered = filter_mig_for_pid(mig, &numbers);
+                (pid_dirname.clone(), CompiledMig::compile(&filtered))
+            })
+            .collect(),
+        None => HashMap::new(),
+    };
+
     // VariantCache accumulator
     let mut variant_message_defs = Vec::new();
     let mut variant_transaction_defs: HashMap<String, Vec<_>> = HashMap::new();
@@ -261,6 +276,7 @@ pub fn compile_variant(
         mig_schema,
         segment_structure,
         pid_segment_numbers,
+        compiled_migs,
     };
     let variant_cache_path = output_base.join(fv).join(format!("{variant}.json"));
     if let Err(e) = variant_cache.save(&variant_cache_path) {
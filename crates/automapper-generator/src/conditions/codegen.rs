@@ -147,6 +147,45 @@ pub fn generate_condition_evaluator_file(
     code
 }
 
+/// Returns the method body for a 9xx format condition, or `None` if the
+/// condition is not one.
+///
+/// Format conditions ("Format: max. 2 Nachkommastellen", "Format: TR-ID", ...)
+/// are not generated individually: the body hands the description to
+/// `EvaluationContext::evaluate_format`, which maps it to a format validator
+/// and checks the values of the data element the condition is attached to.
+pub fn format_condition_body(condition_number: u32, description: &str) -> Option<String> {
+    let description = description.trim();
+    if !(900..1000).contains(&condition_number) || !description.starts_with("Format:") {
+        return None;
+    }
+    let literal = description
+        .replace('\n', " ")
+        .replace('\r', "")
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    Some(format!("ctx.evaluate_format(\"{}\")", literal))
+}
+
+/// Builds the generated condition for a 9xx format condition without
+/// involving the LLM. Returns `None` if the condition is not a format condition.
+pub fn format_condition(condition_number: u32, description: &str) -> Option<GeneratedCondition> {
+    let body = format_condition_body(condition_number, description)?;
+    Some(GeneratedCondition {
+        condition_number,
+        rust_code: Some(body),
+        is_external: false,
+        confidence: ConfidenceLevel::High,
+        reasoning: Some(
+            "Format condition: evaluated against the attached data element via ctx.evaluate_format"
+                .to_string(),
+        ),
+        external_name: None,
+        original_description: Some(description.to_string()),
+        referencing_fields: None,
+    })
+}
+
 /// Generates a single condition evaluation method.
 fn generate_condition_method(code: &mut String, condition: &GeneratedCondition) {
     let num = condition.condition_number;
@@ -695,6 +695,7 @@ mod tests {
             fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         };
 
         let code_map = build_code_map(&schema, &[def]);
@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use mig_assembly::assembler::{owned_to_assembled, Assembler};
 use mig_assembly::disassembler::Disassembler;
 use mig_assembly::renderer::render_edifact;
-use mig_assembly::tokenize::{parse_to_segments, split_messages};
+use mig_assembly::tokenize::{parse_str_to_segments, split_messages};
 use mig_bo4e::engine::MappingEngine;
 use mig_types::schema::mig::MigSchema;
 
@@ -167,7 +167,7 @@ pub fn generate_enhanced_fixture(
     let edi = generate_fixture(schema, Some(&mig_order));
 
     // Step 2: Tokenize
-    let segments = parse_to_segments(edi.as_bytes()).map_err(|e| {
+    let segments = parse_str_to_segments(&edi).map_err(|e| {
         crate::error::GeneratorError::Validation {
             message: format!("tokenization of generated fixture failed: {e}"),
         }
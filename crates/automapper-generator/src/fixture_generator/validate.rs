@@ -5,7 +5,7 @@ use crate::parsing::ahb_parser::parse_ahb;
 use mig_assembly::assembler::Assembler;
 use mig_assembly::parsing::parse_mig;
 use mig_assembly::pid_filter::filter_mig_for_pid;
-use mig_assembly::tokenize::parse_to_segments;
+use mig_assembly::tokenize::parse_str_to_segments;
 use std::collections::HashSet;
 
 /// Result of fixture validation.
@@ -50,10 +50,9 @@ pub fn validate_fixture(
     let mut errors = Vec::new();
 
     // 1. Tokenize
-    let segments =
-        parse_to_segments(edifact.as_bytes()).map_err(|e| GeneratorError::Validation {
-            message: format!("tokenization failed: {e}"),
-        })?;
+    let segments = parse_str_to_segments(edifact).map_err(|e| GeneratorError::Validation {
+        message: format!("tokenization failed: {e}"),
+    })?;
     let segment_count = segments.len();
 
     // 2. Parse MIG + AHB
@@ -800,6 +800,13 @@ fn generate_condition_stubs(
                         "    fn evaluate_{}(&self, ctx: &EvaluationContext) -> ConditionResult {{\n        ctx.external.evaluate(\"{}\")\n    }}\n\n",
                         id, ext_name
                     ));
+                } else if let Some(body) =
+                    automapper_generator::conditions::codegen::format_condition_body(*id, desc)
+                {
+                    code.push_str(&format!(
+                        "    fn evaluate_{}(&self, ctx: &EvaluationContext) -> ConditionResult {{\n        {}\n    }}\n\n",
+                        id, body
+                    ));
                 } else {
                     code.push_str(&format!(
                         "    fn evaluate_{}(&self, _ctx: &EvaluationContext) -> ConditionResult {{\n        // TODO: implement\n        ConditionResult::Unknown\n    }}\n\n",
@@ -1193,9 +1200,33 @@ fn run(cli: Cli) -> Result<(), automapper_generator::GeneratorError> {
             } else {
                 &decision.to_regenerate
             };
+
+            // Format conditions (9xx) are mapped to format validators at
+            // runtime and need no generation by Claude.
+            let mut all_generated = Vec::new();
+            let mut claude_conditions = Vec::new();
+            for c in to_regenerate {
+                let format = c.condition_id.parse().ok().and_then(|num| {
+                    automapper_generator::conditions::codegen::format_condition(
+                        num,
+                        &c.description,
+                    )
+                });
+                match format {
+                    Some(generated) => all_generated.push(generated),
+                    None => claude_conditions.push(c),
+                }
+            }
+            if !all_generated.is_empty() {
+                eprintln!(
+                    "{} format conditions mapped to format validators",
+                    all_generated.len()
+                );
+            }
+
             let condition_inputs: Vec<
                 automapper_generator::conditions::condition_types::ConditionInput,
-            > = to_regenerate
+            > = claude_conditions
                 .iter()
                 .map(
                     |c| automapper_generator::conditions::condition_types::ConditionInput {
@@ -1219,8 +1250,6 @@ fn run(cli: Cli) -> Result<(), automapper_generator::GeneratorError> {
                     automapper_generator::conditions::prompt::default_example_implementations(),
             };
 
-            let mut all_generated = Vec::new();
-
             for (i, batch) in condition_inputs.chunks(batch_size).enumerate() {
                 let batch_ids: Vec<&str> = batch.iter().map(|c| c.id.as_str()).collect();
                 let id_range = format!(
@@ -13,6 +13,7 @@ use crate::schema::ahb::*;
 /// - `AWF` elements with `Pruefidentifikator`, `Beschreibung`, `Kommunikation_von`
 ///   - `Uebertragungsdatei` -> `M_UTILMD` -> nested segments/groups with `AHB_Status`
 /// - `Bedingungen` -> `Bedingung` elements with `Nummer` attribute and text content
+/// - `Pakete` -> `Paket` and `UB_Bedingungen` -> `UB_Bedingung`, shaped like `Bedingung`
 pub fn parse_ahb(
     path: &Path,
     message_type: &str,
@@ -35,6 +36,8 @@ pub fn parse_ahb(
         source_file: path.to_string_lossy().to_string(),
         workflows: Vec::new(),
         bedingungen: Vec::new(),
+        pakete: Vec::new(),
+        ub_bedingungen: Vec::new(),
     };
 
     let mut buf = Vec::new();
@@ -52,7 +55,13 @@ pub fn parse_ahb(
                     let workflow = parse_workflow(e, &mut reader, path)?;
                     schema.workflows.push(workflow);
                 } else if name == "Bedingungen" {
-                    schema.bedingungen = parse_bedingungen(&mut reader, path)?;
+                    schema.bedingungen =
+                        parse_bedingungen(&mut reader, path, "Bedingungen", "Bedingung")?;
+                } else if name == "Pakete" {
+                    schema.pakete = parse_bedingungen(&mut reader, path, "Pakete", "Paket")?;
+                } else if name == "UB_Bedingungen" {
+                    schema.ub_bedingungen =
+                        parse_bedingungen(&mut reader, path, "UB_Bedingungen", "UB_Bedingung")?;
                 }
             }
             Ok(Event::Eof) => break,
@@ -348,9 +357,13 @@ fn parse_ahb_codes(
     Ok(codes)
 }
 
+/// Parses the `entry` elements of a definition section (`Bedingungen`,
+/// `Pakete`, `UB_Bedingungen`) until the closing `section` tag.
 fn parse_bedingungen(
     reader: &mut Reader<&[u8]>,
     path: &Path,
+    section: &str,
+    entry: &str,
 ) -> Result<Vec<BedingungDefinition>, GeneratorError> {
     let mut bedingungen = Vec::new();
     let mut buf = Vec::new();
@@ -359,7 +372,7 @@ fn parse_bedingungen(
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) => {
                 let name = elem_name(e);
-                if name == "Bedingung" {
+                if name == entry {
                     let nummer = get_attr(e, "Nummer").unwrap_or_default();
                     let id = nummer.trim_matches(|c| c == '[' || c == ']').to_string();
 
@@ -372,7 +385,7 @@ fn parse_bedingungen(
                             }
                             Ok(Event::End(ref end)) => {
                                 let end_tag = end_name(end);
-                                if end_tag == "Bedingung" {
+                                if end_tag == entry {
                                     break;
                                 }
                             }
@@ -396,7 +409,7 @@ fn parse_bedingungen(
             }
             Ok(Event::End(ref e)) => {
                 let name = end_name(e);
-                if name == "Bedingungen" {
+                if name == section {
                     break;
                 }
             }
@@ -17,6 +17,12 @@ pub struct AhbSchema {
     pub workflows: Vec<Pruefidentifikator>,
     /// All condition definitions from the Bedingungen section.
     pub bedingungen: Vec<BedingungDefinition>,
+    /// Package definitions from the Pakete section (id e.g. "10P").
+    #[serde(default)]
+    pub pakete: Vec<BedingungDefinition>,
+    /// Unterbedingung definitions from the UB_Bedingungen section (id e.g. "UB1").
+    #[serde(default)]
+    pub ub_bedingungen: Vec<BedingungDefinition>,
 }
 
 /// An Anwendungsfall (AWF/workflow) definition from an AHB.
@@ -75,9 +81,12 @@ pub struct AhbCodeValue {
 }
 
 /// A condition definition from the Bedingungen section of an AHB XML.
+///
+/// Also used for the Pakete and UB_Bedingungen sections, whose entries have
+/// the same shape.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BedingungDefinition {
-    /// The condition ID (e.g., "931", "494").
+    /// The condition ID (e.g., "931", "494", "10P", "UB1").
     pub id: String,
     /// The German description text.
     pub description: String,
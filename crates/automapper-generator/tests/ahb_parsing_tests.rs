@@ -97,6 +97,19 @@ fn test_parse_ahb_bedingungen() {
     );
 }
 
+#[test]
+fn test_parse_ahb_pakete_and_ub_bedingungen() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/minimal_ahb.xml");
+    let schema = parse_ahb(&path, "UTILMD", Some("Strom"), "FV2510").unwrap();
+
+    assert_eq!(schema.pakete.len(), 1);
+    assert_eq!(schema.pakete[0].id, "1P");
+    assert_eq!(schema.pakete[0].description, "[1] [2]");
+    assert_eq!(schema.ub_bedingungen.len(), 1);
+    assert_eq!(schema.ub_bedingungen[0].id, "UB1");
+    assert_eq!(schema.ub_bedingungen[0].description, "[1] ∧ [931]");
+}
+
 #[test]
 fn test_parse_ahb_second_workflow() {
     let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/minimal_ahb.xml");
@@ -106,6 +106,8 @@ fn test_ahb_schema_serialization_roundtrip() {
             id: "1".to_string(),
             description: "Wenn Aufteilung vorhanden".to_string(),
         }],
+        pakete: vec![],
+        ub_bedingungen: vec![],
     };
 
     let json = serde_json::to_string_pretty(&schema).unwrap();
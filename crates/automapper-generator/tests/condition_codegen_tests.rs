@@ -1,4 +1,6 @@
-use automapper_generator::conditions::codegen::generate_condition_evaluator_file;
+use automapper_generator::conditions::codegen::{
+    format_condition, format_condition_body, generate_condition_evaluator_file,
+};
 use automapper_generator::conditions::condition_types::*;
 use std::collections::{HashMap, HashSet};
 
@@ -252,3 +254,26 @@ fn test_condition_evaluator_snapshot() {
 
     insta::assert_snapshot!("condition_evaluator_fv2510", stable_output);
 }
+
+#[test]
+fn test_format_condition_delegates_to_evaluate_format() {
+    assert_eq!(
+        format_condition_body(930, "Format: max. 2 Nachkommastellen").as_deref(),
+        Some("ctx.evaluate_format(\"Format: max. 2 Nachkommastellen\")")
+    );
+    assert!(format_condition_body(930, "Wenn SG4 vorhanden").is_none());
+    assert!(format_condition_body(42, "Format: TR-ID").is_none());
+
+    let condition = format_condition(922, "Format: TR-ID").unwrap();
+    let output = generate_condition_evaluator_file(
+        "UTILMD",
+        "FV2510",
+        &[condition],
+        "test.xml",
+        &HashMap::new(),
+        &HashSet::new(),
+    );
+    assert!(output.contains("fn evaluate_922(&self, ctx: &EvaluationContext) -> ConditionResult {"));
+    assert!(output.contains("ctx.evaluate_format(\"Format: TR-ID\")"));
+    assert!(!output.contains("REVIEW"));
+}
@@ -465,3 +465,138 @@ fn generate_real_pid_types() {
 
     println!("Generated PID types to {:?}", output_dir);
 }
+
+/// MIG whose counters order segments differently from their tags
+/// (IDE before DTM, CCI before CAV).
+const ORDERED_MIG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<M_UTILMD Versionsnummer="S2.1" Veroeffentlichungsdatum="20250320" Author="BDEW" Bezeichnung="UTILMD Strom">
+    <S_UNH Name="Nachrichtenkopfsegment" Counter="0010" Level="0" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+        <D_0062 Name="Nachrichten-Referenznummer" Status_Std="M" Status_Specification="M" Format_Std="an..14" Format_Specification="an..14"/>
+    </S_UNH>
+    <S_BGM Name="Beginn der Nachricht" Counter="0020" Level="0" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+        <D_1004 Name="Dokumentennummer" Status_Std="M" Status_Specification="M" Format_Std="an..35" Format_Specification="an..35"/>
+    </S_BGM>
+    <S_UNT Name="Nachrichtenendesegment" Counter="0670" Level="0" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+        <D_0074 Name="Anzahl der Segmente" Status_Std="M" Status_Specification="M" Format_Std="n..10" Format_Specification="n..10"/>
+    </S_UNT>
+    <G_SG4 Name="Vorgang" Counter="0180" Level="1" MaxRep_Std="99999" MaxRep_Specification="99999" Status_Std="M" Status_Specification="M">
+        <S_IDE Name="Vorgang" Counter="0190" Level="1" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+            <D_7495 Name="Objekt, Qualifier" Status_Std="M" Status_Specification="M" Format_Std="an..3" Format_Specification="an..3">
+                <Code Name="Transaktion">24</Code>
+            </D_7495>
+        </S_IDE>
+        <S_DTM Name="Datum" Counter="0230" Level="2" MaxRep_Std="99" MaxRep_Specification="99" Status_Std="C" Status_Specification="R">
+            <D_2005 Name="Datums-Qualifier" Status_Std="M" Status_Specification="M" Format_Std="an..3" Format_Specification="an..3"/>
+        </S_DTM>
+        <G_SG8 Name="Daten der Marktlokation" Counter="0320" Level="2" MaxRep_Std="99" MaxRep_Specification="99" Status_Std="C" Status_Specification="R">
+            <S_SEQ Name="Handlung" Counter="0330" Level="2" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+                <D_1229 Name="Handlung, Code" Status_Std="M" Status_Specification="M" Format_Std="an..3" Format_Specification="an..3">
+                    <Code Name="Daten der Marktlokation">Z01</Code>
+                </D_1229>
+            </S_SEQ>
+            <G_SG10 Name="Merkmal" Counter="0390" Level="3" MaxRep_Std="99" MaxRep_Specification="99" Status_Std="C" Status_Specification="R">
+                <S_CCI Name="Merkmal" Counter="0400" Level="3" MaxRep_Std="1" MaxRep_Specification="1" Status_Std="M" Status_Specification="M">
+                    <D_7059 Name="Klassentyp, Code" Status_Std="C" Status_Specification="R" Format_Std="an..3" Format_Specification="an..3"/>
+                </S_CCI>
+                <S_CAV Name="Merkmalswert" Counter="0410" Level="4" MaxRep_Std="99" MaxRep_Specification="99" Status_Std="C" Status_Specification="R">
+                    <D_7111 Name="Merkmalswert, Code" Status_Std="C" Status_Specification="R" Format_Std="an..3" Format_Specification="an..3"/>
+                </S_CAV>
+            </G_SG10>
+        </G_SG8>
+    </G_SG4>
+</M_UTILMD>
+"#;
+
+const ORDERED_AHB: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AHB_UTILMD Versionsnummer="2.1">
+    <AWF Pruefidentifikator="55001" Beschreibung="Anmeldung" Kommunikation_von="NB an LF">
+        <Uebertragungsdatei>
+            <M_UTILMD>
+                <S_UNH Name="Nachrichtenkopfsegment">
+                    <D_0062 Name="Nachrichtenreferenznummer" AHB_Status="X"/>
+                </S_UNH>
+                <S_BGM Name="Beginn der Nachricht">
+                    <D_1004 Name="Dokumentennummer" AHB_Status="X"/>
+                </S_BGM>
+                <G_SG4 Name="Vorgang" AHB_Status="Muss">
+                    <S_IDE Name="Vorgang">
+                        <D_7495 Name="Objekt, Qualifier" AHB_Status="X">
+                            <Code Name="Transaktion" AHB_Status="X">24</Code>
+                        </D_7495>
+                    </S_IDE>
+                    <S_DTM Name="Datum">
+                        <D_2005 Name="Datums-Qualifier" AHB_Status="X"/>
+                    </S_DTM>
+                    <G_SG8 Name="Daten der Marktlokation" AHB_Status="Muss">
+                        <S_SEQ Name="Handlung">
+                            <D_1229 Name="Handlung, Code" AHB_Status="X">
+                                <Code Name="Daten der Marktlokation" AHB_Status="X">Z01</Code>
+                            </D_1229>
+                        </S_SEQ>
+                        <G_SG10 Name="Merkmal" AHB_Status="Muss">
+                            <S_CCI Name="Merkmal">
+                                <D_7059 Name="Klassentyp, Code" AHB_Status="X"/>
+                            </S_CCI>
+                            <S_CAV Name="Merkmalswert">
+                                <D_7111 Name="Merkmalswert, Code" AHB_Status="X"/>
+                            </S_CAV>
+                        </G_SG10>
+                    </G_SG8>
+                </G_SG4>
+                <S_UNT Name="Nachrichtenendesegment">
+                    <D_0074 Name="Anzahl der Segmente" AHB_Status="X"/>
+                </S_UNT>
+            </M_UTILMD>
+        </Uebertragungsdatei>
+    </AWF>
+</AHB_UTILMD>
+"#;
+
+#[test]
+fn test_generate_pid_to_segments_follows_mig_order() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("mig.xml"), ORDERED_MIG).unwrap();
+    std::fs::write(dir.path().join("ahb.xml"), ORDERED_AHB).unwrap();
+    let mig = parse_mig(
+        &dir.path().join("mig.xml"),
+        "UTILMD",
+        Some("Strom"),
+        "FV2504",
+    )
+    .unwrap();
+    let ahb = parse_ahb(
+        &dir.path().join("ahb.xml"),
+        "UTILMD",
+        Some("Strom"),
+        "FV2504",
+    )
+    .unwrap();
+    let pid = &ahb.workflows[0];
+
+    let source = pid_type_gen::generate_pid_to_segments(pid, &mig, &ahb);
+
+    // Segments follow MIG counters, not the alphabetical struct field order
+    let pos = |needle: &str| {
+        source
+            .find(needle)
+            .unwrap_or_else(|| panic!("missing `{needle}` in:\n{source}"))
+    };
+    assert!(pos("segments.extend(self.cci.iter()") < pos("segments.extend(self.cav.iter()"));
+    assert!(pos("segments.extend(self.ide.iter()") < pos("segments.extend(self.dtm.iter()"));
+
+    // Root segments around the groups: UNH and BGM before, UNT after
+    assert!(source.contains("pub fn new(unh: OwnedSegment, bgm: OwnedSegment, unt: OwnedSegment)"));
+    assert!(source.contains("let mut segments = vec![self.unh.clone(), self.bgm.clone()];"));
+    assert!(pos("tree.push_group(\"SG4\"") < pos("tree.post_group_start = tree.segments.len();"));
+    assert!(pos("tree.post_group_start") < pos("tree.segments.push(self.unt);"));
+
+    // Qualifier variants push repetitions of the MIG group
+    assert!(source.contains(
+        "instance.push_group(\"SG8\", self.sg8_z01.into_iter().map(Pid55001Sg8Z01::into_group_instance));"
+    ));
+    assert!(source.contains("pub fn with_sg10(mut self, item: Pid55001Sg10) -> Self {"));
+
+    assert!(source.contains("impl PidTree for Pid55001 {"));
+    assert!(source.contains("        \"UTILMD\"\n"));
+    assert!(source.contains("        Some(\"NB an LF\")\n"));
+}
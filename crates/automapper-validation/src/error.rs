@@ -22,6 +22,10 @@ pub enum ParseError {
     /// Invalid condition reference content.
     #[error("invalid condition reference: '{content}'")]
     InvalidConditionRef { content: String },
+
+    /// An Unterbedingung definition refers back to itself.
+    #[error("Unterbedingung [UB{id}] is defined in terms of itself")]
+    CyclicSubCondition { id: u32 },
 }
 
 /// Errors that can occur during validation.
@@ -3,6 +3,7 @@
 mod context;
 mod evaluator;
 mod expr_eval;
+pub mod format_conditions;
 pub mod format_validators;
 pub mod providers;
 mod registry;
@@ -13,6 +14,7 @@ pub use evaluator::{
     ConditionEvaluator, ConditionResult, ExternalConditionProvider, NoOpExternalProvider,
 };
 pub use expr_eval::ConditionExprEvaluator;
+pub use format_conditions::FormatCondition;
 pub use format_validators::*;
 pub use mig_types::navigator::GroupNavigator;
 pub use providers::{
@@ -1,6 +1,7 @@
 //! Evaluation context for condition evaluation.
 
 use super::evaluator::{ConditionResult, ExternalConditionProvider};
+use super::format_conditions::FormatCondition;
 use mig_types::navigator::GroupNavigator;
 use mig_types::segment::OwnedSegment;
 
@@ -24,6 +25,11 @@ pub struct EvaluationContext<'a> {
     /// Optional group navigator for group-scoped condition queries.
     /// When None, group-scoped methods return empty / false / 0.
     pub navigator: Option<&'a dyn GroupNavigator>,
+
+    /// Values of the data element the evaluated condition is attached to.
+    /// Format conditions (9xx) are checked against these values; empty when
+    /// no field is in scope.
+    pub field_values: &'a [String],
 }
 
 /// A no-op group navigator that returns empty results for all queries.
@@ -60,6 +66,7 @@ impl<'a> EvaluationContext<'a> {
             external,
             segments,
             navigator: None,
+            field_values: &[],
         }
     }
 
@@ -75,9 +82,16 @@ impl<'a> EvaluationContext<'a> {
             external,
             segments,
             navigator: Some(navigator),
+            field_values: &[],
         }
     }
 
+    /// Set the values of the data element the evaluated conditions are attached to.
+    pub fn with_field_values(mut self, field_values: &'a [String]) -> Self {
+        self.field_values = field_values;
+        self
+    }
+
     /// Get the group navigator, if one is set.
     pub fn navigator(&self) -> Option<&'a dyn GroupNavigator> {
         self.navigator
@@ -235,9 +249,29 @@ impl<'a> EvaluationContext<'a> {
         )
     }
 
+    /// Values selected by a path query such as `"SG4/SG8[SEQ.0=Z01]/SG10/CCI.2.0"`
+    /// (see `mig_assembly::query`). Returns empty if no navigator is set or
+    /// the navigator cannot answer the query.
+    pub fn query_values(&self, query: &str) -> Vec<String> {
+        self.navigator
+            .and_then(|nav| nav.query_values(query))
+            .unwrap_or_default()
+    }
+
     // --- High-level condition helpers ---
     // These reduce generated condition evaluator boilerplate by ~50%.
 
+    /// Check whether a path query selects anything, e.g.
+    /// `ctx.matches_query("SG4/SG8[SEQ.0=Z01]/SG10[CCI.2.0=Z15]")`.
+    /// Returns `Unknown` if no navigator is set or the navigator cannot
+    /// answer the query.
+    pub fn matches_query(&self, query: &str) -> ConditionResult {
+        match self.navigator.and_then(|nav| nav.query_count(query)) {
+            Some(count) => ConditionResult::from(count > 0),
+            None => ConditionResult::Unknown,
+        }
+    }
+
     /// Check if any segment with the given tag + qualifier exists (message-wide).
     /// Returns `True` if found, `False` if not.
     pub fn has_qualifier(
@@ -772,6 +806,31 @@ impl<'a> EvaluationContext<'a> {
         self.has_segment_matching(tag, checks)
     }
 
+    // --- Format condition helpers ---
+
+    /// Evaluate a 9xx format condition against the field values in scope.
+    ///
+    /// `description` is the AHB condition text (e.g. "Format: max. 2
+    /// Nachkommastellen"), mapped to a format validator by
+    /// [`FormatCondition::parse`]. Returns `False` if any value violates the
+    /// format, `True` if at least one value was checked and all conform, and
+    /// `Unknown` if the text is not a known format or no value is in scope.
+    pub fn evaluate_format(&self, description: &str) -> ConditionResult {
+        let condition = match FormatCondition::parse(description) {
+            Some(c) => c,
+            None => return ConditionResult::Unknown,
+        };
+        let mut result = ConditionResult::Unknown;
+        for value in self.field_values {
+            match condition.check(value) {
+                ConditionResult::False => return ConditionResult::False,
+                ConditionResult::True => result = ConditionResult::True,
+                ConditionResult::Unknown => {}
+            }
+        }
+        result
+    }
+
     // --- DTM date comparison helpers ---
 
     /// Check if a DTM segment with the given qualifier has a value >= threshold.
@@ -876,6 +935,7 @@ mod tests {
                 .map(|e| e.into_iter().map(|c| c.to_string()).collect())
                 .collect(),
             segment_number: 0,
+            location: None,
         }
     }
 
@@ -1146,6 +1206,46 @@ mod tests {
         assert!(!ctx.has_segment_in_group("SEQ", &["SG4", "SG5"], 0));
     }
 
+    #[test]
+    fn test_query_helpers() {
+        use mig_assembly::assembler::{
+            AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
+        };
+        use mig_assembly::AssembledTreeNavigator;
+
+        let seq = |code: &str| AssembledGroupInstance {
+            segments: vec![AssembledSegment {
+                tag: "SEQ".into(),
+                elements: vec![vec![code.to_string().into()]],
+                location: None,
+            }],
+            child_groups: vec![],
+            skipped_segments: vec![],
+        };
+        let tree = AssembledTree {
+            segments: vec![],
+            groups: vec![AssembledGroup {
+                group_id: "SG8".to_string(),
+                repetitions: vec![seq("Z98"), seq("Z01")],
+            }],
+            post_group_start: 0,
+            inter_group_segments: Default::default(),
+        };
+        let external = NoOpExternalProvider;
+        let nav = AssembledTreeNavigator::new(&tree);
+        let ctx = EvaluationContext::with_navigator("55001", &external, &[], &nav);
+        assert_eq!(ctx.query_values("SG8/SEQ.0"), vec!["Z98", "Z01"]);
+        assert_eq!(ctx.matches_query("SG8[SEQ.0=Z01]"), ConditionResult::True);
+        assert_eq!(ctx.matches_query("SG8[SEQ.0=Z02]"), ConditionResult::False);
+        assert_eq!(ctx.matches_query("SG8["), ConditionResult::Unknown);
+
+        // Navigators without query support leave the result open
+        let mock = MockGroupNavigator::new();
+        let ctx = EvaluationContext::with_navigator("55001", &external, &[], &mock);
+        assert_eq!(ctx.matches_query("SG8"), ConditionResult::Unknown);
+        assert!(ctx.query_values("SG8/SEQ.0").is_empty());
+    }
+
     // --- High-level helper tests ---
 
     #[test]
@@ -1832,4 +1932,29 @@ mod tests {
         );
         assert_eq!(ctx.count_in_group("CCI", &["SG4", "SG8"]), 3);
     }
+
+    #[test]
+    fn test_evaluate_format_checks_all_field_values() {
+        let external = NoOpExternalProvider;
+        let ctx = EvaluationContext::new("11001", &external, &[]);
+        assert_eq!(
+            ctx.evaluate_format("Format: max. 2 Nachkommastellen"),
+            ConditionResult::Unknown
+        );
+
+        let values = vec!["1.5".to_string(), "12.25".to_string()];
+        let ctx = ctx.with_field_values(&values);
+        assert_eq!(
+            ctx.evaluate_format("Format: max. 2 Nachkommastellen"),
+            ConditionResult::True
+        );
+        assert_eq!(
+            ctx.evaluate_format("Format: max. 1 Nachkommastellen"),
+            ConditionResult::False
+        );
+        assert_eq!(
+            ctx.evaluate_format("Wenn SG4 vorhanden"),
+            ConditionResult::Unknown
+        );
+    }
 }
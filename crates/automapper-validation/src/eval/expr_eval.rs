@@ -2,7 +2,7 @@
 
 use super::context::EvaluationContext;
 use super::evaluator::{ConditionEvaluator, ConditionResult};
-use crate::expr::ConditionExpr;
+use crate::expr::{ConditionDefinitions, ConditionExpr};
 
 /// Evaluates a `ConditionExpr` AST against an evaluation context.
 ///
@@ -11,14 +11,29 @@ use crate::expr::ConditionExpr;
 /// - OR: True short-circuits to True; all False -> False; else Unknown
 /// - XOR: requires both operands known; Unknown if either is Unknown
 /// - NOT: inverts True/False; preserves Unknown
+/// - Package `[nPa..b]`: True if between a and b members hold, False if that
+///   is impossible whatever the Unknown members turn out to be, else Unknown
+///
+/// Packages and Unterbedingungen are resolved through
+/// [`ConditionDefinitions`]; without a definition they evaluate to Unknown.
 pub struct ConditionExprEvaluator<'a, E: ConditionEvaluator> {
     evaluator: &'a E,
+    definitions: Option<&'a ConditionDefinitions>,
 }
 
 impl<'a, E: ConditionEvaluator> ConditionExprEvaluator<'a, E> {
     /// Create a new expression evaluator wrapping a condition evaluator.
     pub fn new(evaluator: &'a E) -> Self {
-        Self { evaluator }
+        Self {
+            evaluator,
+            definitions: None,
+        }
+    }
+
+    /// Resolve packages and Unterbedingungen using the given AHB definitions.
+    pub fn with_definitions(mut self, definitions: &'a ConditionDefinitions) -> Self {
+        self.definitions = Some(definitions);
+        self
     }
 
     /// Evaluate a condition expression tree.
@@ -40,6 +55,20 @@ impl<'a, E: ConditionEvaluator> ConditionExprEvaluator<'a, E> {
                 let result = self.evaluate(inner, ctx);
                 self.evaluate_not(result)
             }
+
+            ConditionExpr::Package { id, min, max } => {
+                match self.definitions.and_then(|d| d.package(*id)) {
+                    Some(members) => self.evaluate_package(members, *min, *max, ctx),
+                    None => ConditionResult::Unknown,
+                }
+            }
+
+            ConditionExpr::SubCondition(id) => {
+                match self.definitions.and_then(|d| d.sub_condition(*id)) {
+                    Some(inner) => self.evaluate(inner, ctx),
+                    None => ConditionResult::Unknown,
+                }
+            }
         }
     }
 
@@ -92,6 +121,35 @@ impl<'a, E: ConditionEvaluator> ConditionExprEvaluator<'a, E> {
         }
     }
 
+    /// Package: between `min` and `max` of the member conditions must hold.
+    ///
+    /// Unknown members could go either way, so the result is only known when
+    /// every possible outcome agrees.
+    fn evaluate_package(
+        &self,
+        members: &[u32],
+        min: u32,
+        max: u32,
+        ctx: &EvaluationContext,
+    ) -> ConditionResult {
+        let (mut true_count, mut unknown_count) = (0u32, 0u32);
+        for id in members {
+            match self.evaluator.evaluate(*id, ctx) {
+                ConditionResult::True => true_count += 1,
+                ConditionResult::Unknown => unknown_count += 1,
+                ConditionResult::False => {}
+            }
+        }
+
+        if true_count > max || true_count + unknown_count < min {
+            ConditionResult::False
+        } else if true_count >= min && true_count + unknown_count <= max {
+            ConditionResult::True
+        } else {
+            ConditionResult::Unknown
+        }
+    }
+
     /// NOT: inverts True/False, preserves Unknown.
     fn evaluate_not(&self, result: ConditionResult) -> ConditionResult {
         match result {
@@ -169,6 +227,18 @@ impl<'a, E: ConditionEvaluator> ConditionExprEvaluator<'a, E> {
             ConditionExpr::Not(inner) => {
                 self.collect_unknown_ids_inner(inner, ctx, ids);
             }
+            ConditionExpr::Package { id, .. } => {
+                for member in self.definitions.and_then(|d| d.package(*id)).unwrap_or(&[]) {
+                    if self.evaluator.evaluate(*member, ctx).is_unknown() {
+                        ids.push(*member);
+                    }
+                }
+            }
+            ConditionExpr::SubCondition(id) => {
+                if let Some(inner) = self.definitions.and_then(|d| d.sub_condition(*id)) {
+                    self.collect_unknown_ids_inner(inner, ctx, ids);
+                }
+            }
         }
     }
 }
@@ -702,4 +772,98 @@ mod tests {
         assert_eq!(result, CR::False);
         assert!(unknown_ids.is_empty());
     }
+
+    // === Packages and Unterbedingungen ===
+
+    fn package(id: u32, min: u32, max: u32) -> ConditionExpr {
+        ConditionExpr::Package { id, min, max }
+    }
+
+    #[test]
+    fn test_eval_package_cardinality() {
+        // Package 10 = {1, 2, 3}; [1]=T, [2]=T, [3]=F
+        let eval = MockEvaluator::new()
+            .with_condition(1, CR::True)
+            .with_condition(2, CR::True)
+            .with_condition(3, CR::False);
+        let defs = ConditionDefinitions::new().with_package(10, [1, 2, 3]);
+        let (ext, segs) = empty_context();
+        let ctx = make_ctx(&ext, &segs);
+        let expr_eval = ConditionExprEvaluator::new(&eval).with_definitions(&defs);
+
+        assert_eq!(expr_eval.evaluate(&package(10, 1, 5), &ctx), CR::True);
+        assert_eq!(expr_eval.evaluate(&package(10, 2, 2), &ctx), CR::True);
+        assert_eq!(expr_eval.evaluate(&package(10, 0, 1), &ctx), CR::False);
+        assert_eq!(expr_eval.evaluate(&package(10, 3, 3), &ctx), CR::False);
+    }
+
+    #[test]
+    fn test_eval_package_with_unknown_members() {
+        // Package 10 = {1, 2, 3}; [1]=T, [2]=Unknown, [3]=F
+        let eval = MockEvaluator::new()
+            .with_condition(1, CR::True)
+            .with_condition(3, CR::False);
+        let defs = ConditionDefinitions::new().with_package(10, [1, 2, 3]);
+        let (ext, segs) = empty_context();
+        let ctx = make_ctx(&ext, &segs);
+        let expr_eval = ConditionExprEvaluator::new(&eval).with_definitions(&defs);
+
+        // 1 or 2 hold: both within 1..5
+        assert_eq!(expr_eval.evaluate(&package(10, 1, 5), &ctx), CR::True);
+        // 1 or 2 hold: depends on [2]
+        assert_eq!(expr_eval.evaluate(&package(10, 1, 1), &ctx), CR::Unknown);
+        // at most 2 can hold
+        assert_eq!(expr_eval.evaluate(&package(10, 3, 3), &ctx), CR::False);
+    }
+
+    #[test]
+    fn test_eval_undefined_package_or_sub_condition_is_unknown() {
+        let eval = MockEvaluator::new().with_condition(1, CR::True);
+        let (ext, segs) = empty_context();
+        let ctx = make_ctx(&ext, &segs);
+        let expr_eval = ConditionExprEvaluator::new(&eval);
+
+        assert_eq!(expr_eval.evaluate(&package(10, 1, 5), &ctx), CR::Unknown);
+        assert_eq!(
+            expr_eval.evaluate(&ConditionExpr::SubCondition(1), &ctx),
+            CR::Unknown
+        );
+    }
+
+    #[test]
+    fn test_eval_sub_condition_uses_definition() {
+        // [UB1] = [1] ∧ [2]; [1]=T, [2]=F
+        let eval = MockEvaluator::new()
+            .with_condition(1, CR::True)
+            .with_condition(2, CR::False);
+        let mut defs = ConditionDefinitions::new();
+        defs.add_sub_condition_definition("[UB1]", "[1] ∧ [2]")
+            .unwrap();
+        let (ext, segs) = empty_context();
+        let ctx = make_ctx(&ext, &segs);
+        let expr_eval = ConditionExprEvaluator::new(&eval).with_definitions(&defs);
+
+        assert_eq!(expr_eval.evaluate_status("Muss [UB1]", &ctx), CR::False);
+        assert_eq!(
+            expr_eval.evaluate_status("Muss NOT [UB1] ∧ [1]", &ctx),
+            CR::True
+        );
+    }
+
+    #[test]
+    fn test_detailed_reports_unknown_ids_inside_packages_and_sub_conditions() {
+        // [UB1] = [4] ∨ [5]; package 10 = {1, 2}; only [1]=T is known
+        let eval = MockEvaluator::new().with_condition(1, CR::True);
+        let mut defs = ConditionDefinitions::new().with_package(10, [1, 2]);
+        defs.add_sub_condition_definition("[UB1]", "[4] ∨ [5]")
+            .unwrap();
+        let (ext, segs) = empty_context();
+        let ctx = make_ctx(&ext, &segs);
+        let expr_eval = ConditionExprEvaluator::new(&eval).with_definitions(&defs);
+
+        let (result, unknown_ids) =
+            expr_eval.evaluate_status_detailed("Muss [10P2..2] ∧ [UB1]", &ctx);
+        assert_eq!(result, CR::Unknown);
+        assert_eq!(unknown_ids, vec![2, 4, 5]);
+    }
 }
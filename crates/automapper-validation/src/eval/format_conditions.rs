@@ -0,0 +1,497 @@
+//! Declarative mapping of AHB format conditions to format validators.
+//!
+//! The 900-series conditions ("Format: max. 2 Nachkommastellen",
+//! "Format: ZZZ = +00", "Format: TR-ID", ...) constrain the value of the data
+//! element they are attached to in the AHB. Instead of hand-writing an
+//! evaluator per condition and format version, [`FormatCondition::parse`]
+//! recognises the wording used across the AHBs and [`FormatCondition::check`]
+//! applies the matching helper from [`super::format_validators`].
+//!
+//! Generated evaluators call [`EvaluationContext::evaluate_format`] with the
+//! condition description; the context supplies the element values.
+//!
+//! [`EvaluationContext::evaluate_format`]: super::EvaluationContext::evaluate_format
+
+use super::evaluator::ConditionResult;
+use super::format_validators::*;
+
+/// Dash-separated digit pattern of a BDEW Artikelnummer (e.g. "1-01-6-005").
+const ARTIKELNUMMER_PATTERN: &[usize] = &[1, 2, 1, 3];
+
+/// A format constraint recognised from an AHB condition description.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatCondition {
+    /// "max. N Nachkommastellen", "keine Nachkommastelle"
+    MaxDecimalPlaces(usize),
+    /// "max. N Vorkommastellen"
+    MaxIntegerDigits(usize),
+    /// "Möglicher Wert: > 0", "Möglicher Wert: 1", "Wert darf nur positiv oder 0 sein"
+    Numeric { op: &'static str, threshold: f64 },
+    /// "Möglicher Wert: < 0 oder ≥ 0" — any number.
+    AnyNumber,
+    /// "Mögliche Werte: 1 bis n", "Mögliche Werte: 1 bis 99999"
+    IntegerRange { min: u64, max: Option<u64> },
+    /// "HHMM = 2200"
+    HhmmEquals(String),
+    /// "HHMM ≥ 0000", "HHMM ≤ 2359"
+    HhmmRange { min: String, max: String },
+    /// "MMDDHHMM = 12312300"
+    MmddhhmmEquals(String),
+    /// "ZZZ = +00"
+    TimezoneUtc,
+    /// "Die Zeichenkette muss die Zeichen @ und . enthalten"
+    Email,
+    /// "Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen"
+    Phone,
+    /// "Marktlokations-ID"
+    MaloId,
+    /// "Netzlokations-ID"
+    NeloId,
+    /// "TR-ID"
+    TrId,
+    /// "SR-ID"
+    SrId,
+    /// "Zählpunktbezeichnung"
+    Zahlpunkt,
+    /// "Marktlokations-ID oder Zählpunktbezeichnung"
+    MaloOrZahlpunkt,
+    /// "n1-n2-n1-n3", "n13-n2", "Artikelnummer"
+    DigitPattern(Vec<usize>),
+    /// "genau N Stellen"
+    ExactLength(usize),
+    /// "max. N Stellen"
+    MaxLength(usize),
+    /// "Gerätenummer nach DIN 43863-5"
+    Geraetenummer,
+    /// "Zertifikatskörper gemäß X509.1, BSI TR-03109-4"
+    Certificate,
+    /// "Zeichen aus dem über UNOC definierten Zeichensatz, wobei von den
+    /// Buchstaben nur Großbuchstaben erlaubt sind."
+    UppercaseOnly,
+}
+
+impl FormatCondition {
+    /// Recognise a format condition from its AHB description.
+    ///
+    /// Accepts the description with or without the leading "Format:" and
+    /// with HTML-escaped comparison operators. Returns `None` for text that
+    /// is not a known format pattern.
+    pub fn parse(description: &str) -> Option<Self> {
+        let text = normalize(description);
+        let text = text.as_str();
+
+        if text == "ZZZ = +00" {
+            return Some(Self::TimezoneUtc);
+        }
+        if let Some(rest) = text.strip_prefix("MMDDHHMM") {
+            let (op, value) = split_operator(rest)?;
+            return (op == "==" && is_digits(value, 8))
+                .then(|| Self::MmddhhmmEquals(value.to_string()));
+        }
+        if let Some(rest) = text.strip_prefix("HHMM") {
+            let (op, value) = split_operator(rest)?;
+            if !is_digits(value, 4) {
+                return None;
+            }
+            let value = value.to_string();
+            return match op {
+                "==" => Some(Self::HhmmEquals(value)),
+                ">=" => Some(Self::HhmmRange {
+                    min: value,
+                    max: "2359".to_string(),
+                }),
+                "<=" => Some(Self::HhmmRange {
+                    min: "0000".to_string(),
+                    max: value,
+                }),
+                _ => None,
+            };
+        }
+
+        if text.contains("keine Nachkommastelle") {
+            return Some(Self::MaxDecimalPlaces(0));
+        }
+        if text.contains("Nachkommastelle") {
+            return first_number(text).map(Self::MaxDecimalPlaces);
+        }
+        if text.contains("Vorkommastelle") {
+            return first_number(text).map(Self::MaxIntegerDigits);
+        }
+        if text.ends_with(" Stellen") {
+            if text.starts_with("max.") {
+                return first_number(text).map(Self::MaxLength);
+            }
+            if text.starts_with("genau") {
+                return first_number(text).map(Self::ExactLength);
+            }
+        }
+
+        let fixed = match text {
+            "Marktlokations-ID oder Zählpunktbezeichnung" => Some(Self::MaloOrZahlpunkt),
+            "Marktlokations-ID" => Some(Self::MaloId),
+            "Netzlokations-ID" => Some(Self::NeloId),
+            "Zählpunktbezeichnung" => Some(Self::Zahlpunkt),
+            "TR-ID" => Some(Self::TrId),
+            "SR-ID" => Some(Self::SrId),
+            "Artikelnummer" => Some(Self::DigitPattern(ARTIKELNUMMER_PATTERN.to_vec())),
+            "Wert darf nur positiv oder 0 sein" => Some(Self::Numeric {
+                op: ">=",
+                threshold: 0.0,
+            }),
+            _ => None,
+        };
+        if fixed.is_some() {
+            return fixed;
+        }
+        if text.starts_with("Gerätenummer nach DIN 43863-5") {
+            return Some(Self::Geraetenummer);
+        }
+        if text.starts_with("Zertifikatskörper") {
+            return Some(Self::Certificate);
+        }
+        if text.starts_with("Zeichen aus dem über UNOC definierten Zeichensatz")
+            && text.contains("nur Großbuchstaben")
+        {
+            return Some(Self::UppercaseOnly);
+        }
+        if text.starts_with("Die Zeichenkette muss die Zeichen @ und . enthalten") {
+            return Some(Self::Email);
+        }
+        if text.starts_with("Die Zeichenkette muss mit dem Zeichen + beginnen") {
+            return Some(Self::Phone);
+        }
+        if let Some(pattern) = parse_digit_pattern(text) {
+            return Some(Self::DigitPattern(pattern));
+        }
+        if let Some(rest) = text.strip_prefix("Mögliche Werte:") {
+            return parse_integer_range(rest);
+        }
+        // "Möglicher Wer:" is a typo found in some AHBs.
+        if let Some(rest) = text
+            .strip_prefix("Möglicher Wert:")
+            .or_else(|| text.strip_prefix("Möglicher Wer:"))
+        {
+            return parse_possible_value(rest);
+        }
+        None
+    }
+
+    /// Check a data element value against this format condition.
+    pub fn check(&self, value: &str) -> ConditionResult {
+        match self {
+            Self::MaxDecimalPlaces(max) => validate_max_decimal_places(value, *max),
+            Self::MaxIntegerDigits(max) => validate_max_integer_digits(value, *max),
+            Self::Numeric { op, threshold } => validate_numeric(value, op, *threshold),
+            Self::AnyNumber => {
+                if value.is_empty() {
+                    ConditionResult::Unknown
+                } else {
+                    ConditionResult::from(value.parse::<f64>().is_ok())
+                }
+            }
+            Self::IntegerRange { min, max } => match validate_all_digits(value) {
+                ConditionResult::True => {
+                    let in_range = value
+                        .parse::<u64>()
+                        .is_ok_and(|n| n >= *min && max.map_or(true, |max| n <= max));
+                    ConditionResult::from(in_range)
+                }
+                other => other,
+            },
+            Self::HhmmEquals(expected) => validate_hhmm_equals(value, expected),
+            Self::HhmmRange { min, max } => validate_hhmm_range(value, min, max),
+            Self::MmddhhmmEquals(expected) => validate_mmddhhmm_equals(value, expected),
+            Self::TimezoneUtc => validate_timezone_utc(value),
+            Self::Email => validate_email(value),
+            Self::Phone => validate_phone(value),
+            Self::MaloId => validate_malo_id(value),
+            Self::NeloId => validate_nelo_id(value),
+            Self::TrId => validate_tr_id(value),
+            Self::SrId => validate_sr_id(value),
+            Self::Zahlpunkt => validate_zahlpunkt(value),
+            Self::MaloOrZahlpunkt => validate_malo_or_zahlpunkt(value),
+            Self::DigitPattern(lengths) => validate_artikel_pattern(value, lengths),
+            Self::ExactLength(len) => validate_exact_length(value, *len),
+            Self::MaxLength(max) => validate_max_length(value, *max),
+            Self::Geraetenummer => validate_geraetenummer(value),
+            Self::Certificate => validate_base64(value),
+            Self::UppercaseOnly => validate_no_lowercase(value),
+        }
+    }
+}
+
+/// Strip "Format:" prefixes, unescape comparison operators and collapse whitespace.
+fn normalize(description: &str) -> String {
+    let mut text = description.trim();
+    while let Some(rest) = text.strip_prefix("Format:") {
+        text = rest.trim_start();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a leading comparison operator off `text`.
+///
+/// Returns the operator in `validate_numeric` notation and the trimmed rest.
+/// Without an operator, `"=="` is returned.
+fn split_operator(text: &str) -> Option<(&'static str, &str)> {
+    const OPERATORS: &[(&str, &str)] = &[
+        ("<=", "<="),
+        (">=", ">="),
+        ("≤", "<="),
+        ("≥", ">="),
+        ("≠", "!="),
+        ("<", "<"),
+        (">", ">"),
+        ("=", "=="),
+    ];
+    let text = text.trim();
+    for (symbol, op) in OPERATORS {
+        if let Some(rest) = text.strip_prefix(symbol) {
+            return Some((op, rest.trim()));
+        }
+    }
+    (!text.is_empty()).then_some(("==", text))
+}
+
+/// "> 0", "1", "≠ 1", "< 0 oder ≥ 0"
+fn parse_possible_value(text: &str) -> Option<FormatCondition> {
+    let text = text.trim();
+    if text == "< 0 oder ≥ 0" {
+        return Some(FormatCondition::AnyNumber);
+    }
+    let (op, value) = split_operator(text)?;
+    let threshold = value.parse::<f64>().ok()?;
+    Some(FormatCondition::Numeric { op, threshold })
+}
+
+/// "1 bis n", "0 bis n, je Nachricht ...", "1 bis 99999"
+fn parse_integer_range(text: &str) -> Option<FormatCondition> {
+    let range = text.split(',').next()?.trim();
+    let (min, max) = range.split_once(" bis ")?;
+    let min = min.trim().parse().ok()?;
+    let max = match max.trim() {
+        "n" => None,
+        max => Some(max.parse().ok()?),
+    };
+    Some(FormatCondition::IntegerRange { min, max })
+}
+
+/// "n1-n2-n1-n3" → `[1, 2, 1, 3]`
+fn parse_digit_pattern(text: &str) -> Option<Vec<usize>> {
+    text.split('-')
+        .map(|part| part.strip_prefix('n')?.parse().ok())
+        .collect()
+}
+
+fn first_number(text: &str) -> Option<usize> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|s| !s.is_empty())?
+        .parse()
+        .ok()
+}
+
+fn is_digits(value: &str, len: usize) -> bool {
+    value.len() == len && value.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(description: &str) -> FormatCondition {
+        FormatCondition::parse(description)
+            .unwrap_or_else(|| panic!("not recognised: {description}"))
+    }
+
+    #[test]
+    fn test_parse_decimal_and_integer_places() {
+        assert_eq!(
+            parse("Format: max. 2 Nachkommastellen"),
+            FormatCondition::MaxDecimalPlaces(2)
+        );
+        assert_eq!(
+            parse("Format: Wert kann mit maximal 6 Nachkommastellen angegeben werden"),
+            FormatCondition::MaxDecimalPlaces(6)
+        );
+        assert_eq!(
+            parse("Format: keine Nachkommastelle"),
+            FormatCondition::MaxDecimalPlaces(0)
+        );
+        assert_eq!(
+            parse("Format: max. 6 Vorkommastellen"),
+            FormatCondition::MaxIntegerDigits(6)
+        );
+    }
+
+    #[test]
+    fn test_parse_possible_values() {
+        assert_eq!(
+            parse("Format: Möglicher Wert: > 0"),
+            FormatCondition::Numeric {
+                op: ">",
+                threshold: 0.0
+            }
+        );
+        assert_eq!(
+            parse("Format: Möglicher Wert: &lt;= 10"),
+            FormatCondition::Numeric {
+                op: "<=",
+                threshold: 10.0
+            }
+        );
+        assert_eq!(
+            parse("Format: Format: Möglicher Wert: ≥ 0"),
+            FormatCondition::Numeric {
+                op: ">=",
+                threshold: 0.0
+            }
+        );
+        assert_eq!(
+            parse("Format: Möglicher Wer: ≤ 1"),
+            FormatCondition::Numeric {
+                op: "<=",
+                threshold: 1.0
+            }
+        );
+        assert_eq!(
+            parse("Format: Möglicher Wert: -1"),
+            FormatCondition::Numeric {
+                op: "==",
+                threshold: -1.0
+            }
+        );
+        assert_eq!(
+            parse("Format: Möglicher Wert: &lt; 0 oder ≥ 0"),
+            FormatCondition::AnyNumber
+        );
+        assert_eq!(
+            parse("Format: Mögliche Werte: 1 bis n, je Nachricht bei 1 beginnend und fortlaufend aufsteigend"),
+            FormatCondition::IntegerRange { min: 1, max: None }
+        );
+        assert_eq!(
+            parse("Format: Mögliche Werte: 1 bis 99999"),
+            FormatCondition::IntegerRange {
+                min: 1,
+                max: Some(99999)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_time_patterns() {
+        assert_eq!(parse("Format: ZZZ = +00"), FormatCondition::TimezoneUtc);
+        assert_eq!(
+            parse("Format: HHMM = 2200"),
+            FormatCondition::HhmmEquals("2200".to_string())
+        );
+        assert_eq!(
+            parse("Format: HHMM ≤ 2359"),
+            FormatCondition::HhmmRange {
+                min: "0000".to_string(),
+                max: "2359".to_string()
+            }
+        );
+        assert_eq!(
+            parse("Format: MMDDHHMM = 12312300"),
+            FormatCondition::MmddhhmmEquals("12312300".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_identifiers_and_patterns() {
+        assert_eq!(parse("Format: TR-ID"), FormatCondition::TrId);
+        assert_eq!(
+            parse("Format: Marktlokations-ID oder Zählpunktbezeichnung"),
+            FormatCondition::MaloOrZahlpunkt
+        );
+        assert_eq!(
+            parse("Format: n1-n2-n1-n8-n2"),
+            FormatCondition::DigitPattern(vec![1, 2, 1, 8, 2])
+        );
+        assert_eq!(
+            parse("Format: genau 16 Stellen"),
+            FormatCondition::ExactLength(16)
+        );
+        assert_eq!(
+            parse("Format: Die Zeichenkette muss die Zeichen @ und . enthalten"),
+            FormatCondition::Email
+        );
+        assert_eq!(
+            parse("Format: Zeichen aus dem über UNOC definierten Zeichensatz, wobei von den Buchstaben nur Großbuchstaben erlaubt sind."),
+            FormatCondition::UppercaseOnly
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_non_format_text() {
+        assert_eq!(
+            FormatCondition::parse("Wenn SG4 STS+7++ZW4 vorhanden"),
+            None
+        );
+        assert_eq!(
+            FormatCondition::parse("Format: Möglicher Wert: viele"),
+            None
+        );
+        assert_eq!(FormatCondition::parse("Format: HHMM = 22"), None);
+    }
+
+    #[test]
+    fn test_check_applies_validator() {
+        let decimals = parse("Format: max. 2 Nachkommastellen");
+        assert_eq!(decimals.check("12.34"), ConditionResult::True);
+        assert_eq!(decimals.check("12.345"), ConditionResult::False);
+
+        let utc = parse("Format: ZZZ = +00");
+        assert_eq!(utc.check("202601012200+00"), ConditionResult::True);
+        assert_eq!(utc.check("202601012200+01"), ConditionResult::False);
+
+        let positive = parse("Format: Möglicher Wert: > 0");
+        assert_eq!(positive.check("5"), ConditionResult::True);
+        assert_eq!(positive.check("0"), ConditionResult::False);
+
+        let counter = parse("Format: Mögliche Werte: 1 bis n");
+        assert_eq!(counter.check("3"), ConditionResult::True);
+        assert_eq!(counter.check("0"), ConditionResult::False);
+        assert_eq!(counter.check("1.5"), ConditionResult::False);
+
+        let any = parse("Format: Möglicher Wert: < 0 oder ≥ 0");
+        assert_eq!(any.check("-3.5"), ConditionResult::True);
+        assert_eq!(any.check("abc"), ConditionResult::False);
+    }
+
+    #[test]
+    fn test_every_generated_format_condition_is_recognised() {
+        let generated = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/generated");
+        let mut checked = 0;
+        for fv in std::fs::read_dir(&generated).unwrap() {
+            let fv = fv.unwrap().path();
+            if !fv.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(&fv).unwrap() {
+                let source = std::fs::read_to_string(file.unwrap().path()).unwrap_or_default();
+                for line in source.lines() {
+                    let Some(description) = line
+                        .trim()
+                        .strip_prefix("ctx.evaluate_format(\"")
+                        .and_then(|rest| rest.strip_suffix("\")"))
+                    else {
+                        continue;
+                    };
+                    assert!(
+                        FormatCondition::parse(description).is_some(),
+                        "{}: {description}",
+                        fv.display()
+                    );
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+}
@@ -209,6 +209,43 @@ pub fn validate_malo_or_zahlpunkt(value: &str) -> ConditionResult {
     ConditionResult::False
 }
 
+/// Validate Netzlokations-ID: 'E' followed by 9 uppercase alphanumeric characters
+/// and a check digit (11 characters in total).
+///
+/// Only the structure is checked; the check digit itself is not verified.
+pub fn validate_nelo_id(value: &str) -> ConditionResult {
+    if value.is_empty() {
+        return ConditionResult::Unknown;
+    }
+    let valid = value.len() == 11
+        && value.starts_with('E')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && value.ends_with(|c: char| c.is_ascii_digit());
+    ConditionResult::from(valid)
+}
+
+/// Validate Gerätenummer nach DIN 43863-5: 14 characters made of the Sparte
+/// (1 digit), Hersteller (3 uppercase letters), Fabrikationsblock (2 uppercase
+/// alphanumeric characters) and Fabrikationsnummer (8 digits).
+///
+/// Example: `validate_geraetenummer("1EMH0012345678")` → True
+pub fn validate_geraetenummer(value: &str) -> ConditionResult {
+    if value.is_empty() {
+        return ConditionResult::Unknown;
+    }
+    let b = value.as_bytes();
+    let valid = b.len() == 14
+        && b[0].is_ascii_digit()
+        && b[1..4].iter().all(u8::is_ascii_uppercase)
+        && b[4..6]
+            .iter()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && b[6..].iter().all(u8::is_ascii_digit);
+    ConditionResult::from(valid)
+}
+
 // --- Artikelnummer pattern validation ---
 
 /// Validate a dash-separated digit pattern like "n1-n2-n1-n3".
@@ -260,6 +297,33 @@ pub fn validate_all_digits(value: &str) -> ConditionResult {
     ConditionResult::from(value.chars().all(|c| c.is_ascii_digit()))
 }
 
+/// Validate that a string contains no lowercase letters.
+///
+/// Used for "Zeichen aus dem über UNOC definierten Zeichensatz, wobei von den
+/// Buchstaben nur Großbuchstaben erlaubt sind".
+pub fn validate_no_lowercase(value: &str) -> ConditionResult {
+    if value.is_empty() {
+        return ConditionResult::Unknown;
+    }
+    ConditionResult::from(!value.chars().any(char::is_lowercase))
+}
+
+/// Validate that a string is Base64 encoded (e.g., an X.509 certificate body).
+///
+/// Checks the alphabet, the padding and that the length is a multiple of 4.
+pub fn validate_base64(value: &str) -> ConditionResult {
+    if value.is_empty() {
+        return ConditionResult::Unknown;
+    }
+    let data = value.trim_end_matches('=');
+    let valid = value.len() % 4 == 0
+        && value.len() - data.len() <= 2
+        && data
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/');
+    ConditionResult::from(valid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,4 +552,44 @@ mod tests {
         assert_eq!(validate_all_digits("123a5"), ConditionResult::False);
         assert_eq!(validate_all_digits(""), ConditionResult::Unknown);
     }
+
+    #[test]
+    fn test_nelo_id() {
+        assert_eq!(validate_nelo_id("E1234567890"), ConditionResult::True);
+        assert_eq!(validate_nelo_id("EABCDE12345"), ConditionResult::True);
+        assert_eq!(validate_nelo_id("51234567890"), ConditionResult::False);
+        assert_eq!(validate_nelo_id("E123456789A"), ConditionResult::False);
+        assert_eq!(validate_nelo_id("E123"), ConditionResult::False);
+        assert_eq!(validate_nelo_id(""), ConditionResult::Unknown);
+    }
+
+    #[test]
+    fn test_geraetenummer() {
+        assert_eq!(
+            validate_geraetenummer("1EMH0012345678"),
+            ConditionResult::True
+        );
+        assert_eq!(
+            validate_geraetenummer("1emh0012345678"),
+            ConditionResult::False
+        );
+        assert_eq!(validate_geraetenummer("1EMH00123"), ConditionResult::False);
+        assert_eq!(validate_geraetenummer(""), ConditionResult::Unknown);
+    }
+
+    #[test]
+    fn test_no_lowercase() {
+        assert_eq!(validate_no_lowercase("MUSTER GMBH"), ConditionResult::True);
+        assert_eq!(validate_no_lowercase("Muster GmbH"), ConditionResult::False);
+        assert_eq!(validate_no_lowercase(""), ConditionResult::Unknown);
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(validate_base64("TUlJQg=="), ConditionResult::True);
+        assert_eq!(validate_base64("TUlJQ2Fn"), ConditionResult::True);
+        assert_eq!(validate_base64("TUlJQg="), ConditionResult::False);
+        assert_eq!(validate_base64("TUl*Qg=="), ConditionResult::False);
+        assert_eq!(validate_base64(""), ConditionResult::Unknown);
+    }
 }
@@ -1,9 +1,11 @@
 //! Condition expression AST and parser.
 
 mod ast;
+mod definitions;
 mod parser;
 mod token;
 
 pub use ast::ConditionExpr;
+pub use definitions::ConditionDefinitions;
 pub use parser::ConditionParser;
 pub use token::{strip_status_prefix, Token};
@@ -5,7 +5,8 @@ use std::collections::BTreeSet;
 /// A parsed AHB condition expression tree.
 ///
 /// Represents boolean combinations of condition references like `[1] ∧ [2]` or
-/// `([3] ∨ [4]) ⊻ [5]`.
+/// `([3] ∨ [4]) ⊻ [5]`, including package conditions (`[10P1..5]`) and
+/// Unterbedingungen (`[UB1]`).
 ///
 /// # Examples
 ///
@@ -33,33 +34,74 @@ pub enum ConditionExpr {
 
     /// Boolean NOT of an expression.
     Not(Box<ConditionExpr>),
+
+    /// A package condition (Paketbedingung), e.g., `[10P1..5]`: between `min`
+    /// and `max` (inclusive) of the conditions in package `id` must hold.
+    Package { id: u32, min: u32, max: u32 },
+
+    /// A reference to an Unterbedingung by number, e.g., `[UB1]`. Its
+    /// expression is defined in the AHB, see
+    /// [`ConditionDefinitions`](super::ConditionDefinitions).
+    SubCondition(u32),
 }
 
 impl ConditionExpr {
     /// Extracts all condition IDs referenced in this expression tree.
+    ///
+    /// Package and Unterbedingung numbers are not condition IDs; see
+    /// [`package_ids`](Self::package_ids) and
+    /// [`sub_condition_ids`](Self::sub_condition_ids).
     pub fn condition_ids(&self) -> BTreeSet<u32> {
         let mut ids = BTreeSet::new();
-        self.collect_ids(&mut ids);
+        self.visit(&mut |expr| {
+            if let ConditionExpr::Ref(id) = expr {
+                ids.insert(*id);
+            }
+        });
         ids
     }
 
-    fn collect_ids(&self, ids: &mut BTreeSet<u32>) {
-        match self {
-            ConditionExpr::Ref(id) => {
+    /// Extracts all package numbers referenced in this expression tree,
+    /// e.g., `10` for `[10P1..5]`.
+    pub fn package_ids(&self) -> BTreeSet<u32> {
+        let mut ids = BTreeSet::new();
+        self.visit(&mut |expr| {
+            if let ConditionExpr::Package { id, .. } = expr {
                 ids.insert(*id);
             }
+        });
+        ids
+    }
+
+    /// Extracts all Unterbedingung numbers referenced in this expression
+    /// tree, e.g., `1` for `[UB1]`.
+    pub fn sub_condition_ids(&self) -> BTreeSet<u32> {
+        let mut ids = BTreeSet::new();
+        self.visit(&mut |expr| {
+            if let ConditionExpr::SubCondition(id) = expr {
+                ids.insert(*id);
+            }
+        });
+        ids
+    }
+
+    /// Calls `f` for this node and all descendants, depth-first.
+    fn visit(&self, f: &mut impl FnMut(&ConditionExpr)) {
+        f(self);
+        match self {
             ConditionExpr::And(exprs) | ConditionExpr::Or(exprs) => {
                 for expr in exprs {
-                    expr.collect_ids(ids);
+                    expr.visit(f);
                 }
             }
             ConditionExpr::Xor(left, right) => {
-                left.collect_ids(ids);
-                right.collect_ids(ids);
-            }
-            ConditionExpr::Not(inner) => {
-                inner.collect_ids(ids);
+                left.visit(f);
+                right.visit(f);
             }
+            ConditionExpr::Not(inner) => inner.visit(f),
+            ConditionExpr::Ref(_)
+            | ConditionExpr::Package { .. }
+            | ConditionExpr::SubCondition(_) => {}
         }
     }
 }
@@ -78,6 +120,8 @@ impl std::fmt::Display for ConditionExpr {
             }
             ConditionExpr::Xor(left, right) => write!(f, "({left} ⊻ {right})"),
             ConditionExpr::Not(inner) => write!(f, "NOT {inner}"),
+            ConditionExpr::Package { id, min, max } => write!(f, "[{id}P{min}..{max}]"),
+            ConditionExpr::SubCondition(id) => write!(f, "[UB{id}]"),
         }
     }
 }
@@ -121,6 +165,34 @@ mod tests {
         assert_eq!(expr.condition_ids(), [42].into());
     }
 
+    #[test]
+    fn test_package_and_sub_condition_ids_are_reported_separately() {
+        // [1] ∧ [10P1..5] ∧ NOT [UB2]
+        let expr = ConditionExpr::And(vec![
+            ConditionExpr::Ref(1),
+            ConditionExpr::Package {
+                id: 10,
+                min: 1,
+                max: 5,
+            },
+            ConditionExpr::Not(Box::new(ConditionExpr::SubCondition(2))),
+        ]);
+        assert_eq!(expr.condition_ids(), [1].into());
+        assert_eq!(expr.package_ids(), [10].into());
+        assert_eq!(expr.sub_condition_ids(), [2].into());
+    }
+
+    #[test]
+    fn test_display_package_and_sub_condition() {
+        let package = ConditionExpr::Package {
+            id: 10,
+            min: 1,
+            max: 5,
+        };
+        assert_eq!(format!("{package}"), "[10P1..5]");
+        assert_eq!(format!("{}", ConditionExpr::SubCondition(1)), "[UB1]");
+    }
+
     #[test]
     fn test_display_ref() {
         let expr = ConditionExpr::Ref(931);
@@ -0,0 +1,186 @@
+//! Package and Unterbedingung definitions from the AHB.
+//!
+//! AHB status expressions can reference packages (`[10P1..5]`) and
+//! Unterbedingungen (`[UB1]`) whose meaning is defined once per AHB, in the
+//! `Pakete` and `UB_Bedingungen` sections. [`ConditionDefinitions`] holds
+//! those definitions so that
+//! [`ConditionExprEvaluator`](crate::eval::ConditionExprEvaluator) can
+//! resolve them.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::ast::ConditionExpr;
+use super::parser::ConditionParser;
+use super::token::{tokenize, Token};
+use crate::error::ParseError;
+
+/// Package members and Unterbedingung expressions, keyed by their number.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionDefinitions {
+    packages: HashMap<u32, Vec<u32>>,
+    sub_conditions: HashMap<u32, ConditionExpr>,
+}
+
+impl ConditionDefinitions {
+    /// Create an empty set of definitions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define package `id` as consisting of the given condition IDs.
+    pub fn with_package(mut self, id: u32, members: impl IntoIterator<Item = u32>) -> Self {
+        self.add_package(id, members);
+        self
+    }
+
+    /// Define Unterbedingung `id` by an expression.
+    ///
+    /// Returns an error if the definition refers back to `id`, directly or
+    /// through other Unterbedingungen.
+    pub fn with_sub_condition(mut self, id: u32, expr: ConditionExpr) -> Result<Self, ParseError> {
+        self.add_sub_condition(id, expr)?;
+        Ok(self)
+    }
+
+    /// Define package `id` as consisting of the given condition IDs.
+    pub fn add_package(&mut self, id: u32, members: impl IntoIterator<Item = u32>) {
+        let members: BTreeSet<u32> = members.into_iter().collect();
+        self.packages.insert(id, members.into_iter().collect());
+    }
+
+    /// Define Unterbedingung `id` by an expression.
+    ///
+    /// Returns an error if the definition refers back to `id`, directly or
+    /// through other Unterbedingungen.
+    pub fn add_sub_condition(&mut self, id: u32, expr: ConditionExpr) -> Result<(), ParseError> {
+        if self.reaches(&expr, id, &mut BTreeSet::new()) {
+            return Err(ParseError::CyclicSubCondition { id });
+        }
+        self.sub_conditions.insert(id, expr);
+        Ok(())
+    }
+
+    /// Add a package definition as it appears in the AHB, e.g.,
+    /// `nummer = "[10P]"` and `text = "[1] [2] [3]"`.
+    ///
+    /// The members are the numeric condition references in `text`.
+    pub fn add_package_definition(&mut self, nummer: &str, text: &str) -> Result<(), ParseError> {
+        let id = parse_nummer(nummer, "", "P")?;
+        let members = tokenize(text)?.into_iter().filter_map(|t| match t.token {
+            Token::ConditionId(content) => content.parse::<u32>().ok(),
+            _ => None,
+        });
+        self.add_package(id, members);
+        Ok(())
+    }
+
+    /// Add an Unterbedingung definition as it appears in the AHB, e.g.,
+    /// `nummer = "[UB1]"` and `text = "[1] ∧ [2]"`.
+    ///
+    /// Definitions without any condition reference are ignored.
+    pub fn add_sub_condition_definition(
+        &mut self,
+        nummer: &str,
+        text: &str,
+    ) -> Result<(), ParseError> {
+        let id = parse_nummer(nummer, "UB", "")?;
+        match ConditionParser::parse_raw(text)? {
+            Some(expr) => self.add_sub_condition(id, expr),
+            None => Ok(()),
+        }
+    }
+
+    /// The condition IDs of package `id`, if defined.
+    pub fn package(&self, id: u32) -> Option<&[u32]> {
+        self.packages.get(&id).map(Vec::as_slice)
+    }
+
+    /// The expression of Unterbedingung `id`, if defined.
+    pub fn sub_condition(&self, id: u32) -> Option<&ConditionExpr> {
+        self.sub_conditions.get(&id)
+    }
+
+    /// Returns `true` if no packages or Unterbedingungen are defined.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.sub_conditions.is_empty()
+    }
+
+    /// Whether evaluating `expr` would evaluate Unterbedingung `target`.
+    fn reaches(&self, expr: &ConditionExpr, target: u32, seen: &mut BTreeSet<u32>) -> bool {
+        expr.sub_condition_ids().into_iter().any(|ub| {
+            ub == target
+                || (seen.insert(ub)
+                    && self
+                        .sub_conditions
+                        .get(&ub)
+                        .is_some_and(|e| self.reaches(e, target, seen)))
+        })
+    }
+}
+
+/// Parse an AHB `Nummer` attribute like `[10P]` or `[UB1]`.
+fn parse_nummer(nummer: &str, prefix: &str, suffix: &str) -> Result<u32, ParseError> {
+    let trimmed = nummer.trim();
+    let inner = trimmed
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(trimmed);
+    inner
+        .strip_prefix(prefix)
+        .and_then(|s| s.strip_suffix(suffix))
+        .and_then(|s| s.parse::<u32>().ok())
+        .ok_or_else(|| ParseError::InvalidConditionRef {
+            content: inner.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_package_definition_collects_numeric_members() {
+        let mut defs = ConditionDefinitions::new();
+        defs.add_package_definition("[10P]", "[3] ∨ [1] ∨ [2] [UB1]")
+            .unwrap();
+        assert_eq!(defs.package(10), Some(&[1, 2, 3][..]));
+        assert_eq!(defs.package(11), None);
+    }
+
+    #[test]
+    fn test_add_sub_condition_definition_parses_expression() {
+        let mut defs = ConditionDefinitions::new();
+        defs.add_sub_condition_definition("[UB1]", "[1] ∧ [2]")
+            .unwrap();
+        assert_eq!(
+            defs.sub_condition(1),
+            Some(&ConditionExpr::And(vec![
+                ConditionExpr::Ref(1),
+                ConditionExpr::Ref(2)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_invalid_nummer_is_rejected() {
+        let mut defs = ConditionDefinitions::new();
+        assert!(defs.add_package_definition("[UB1]", "[1]").is_err());
+        assert!(defs.add_sub_condition_definition("[10P]", "[1]").is_err());
+    }
+
+    #[test]
+    fn test_cyclic_sub_conditions_are_rejected() {
+        let mut defs = ConditionDefinitions::new();
+        defs.add_sub_condition_definition("[UB1]", "[1] ∧ [UB2]")
+            .unwrap();
+        assert!(matches!(
+            defs.add_sub_condition_definition("[UB2]", "[UB1] ∨ [3]"),
+            Err(ParseError::CyclicSubCondition { id: 2 })
+        ));
+        assert!(matches!(
+            defs.add_sub_condition_definition("[UB3]", "NOT [UB3]"),
+            Err(ParseError::CyclicSubCondition { id: 3 })
+        ));
+        assert!(defs.sub_condition(2).is_none());
+    }
+}
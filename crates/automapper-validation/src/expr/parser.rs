@@ -220,7 +220,7 @@ fn parse_primary(
 
     match &tokens[*pos].token {
         Token::ConditionId(id) => {
-            let parsed_id = parse_condition_id(id);
+            let parsed_id = parse_condition_id(id)?;
             *pos += 1;
             Ok(Some(parsed_id))
         }
@@ -237,27 +237,36 @@ fn parse_primary(
     }
 }
 
-/// Parse a condition ID string into a ConditionExpr.
+/// Parse the content of a condition reference into a ConditionExpr.
 ///
-/// Numeric IDs become `Ref(n)`. Non-numeric IDs (like `UB1`, `10P1..5`)
-/// are kept as-is by extracting numeric portions. For the Rust port,
-/// pure numeric IDs use `Ref(u32)`. Non-numeric IDs extract leading
-/// digits if present, otherwise use 0 as a sentinel.
-fn parse_condition_id(id: &str) -> ConditionExpr {
-    // Try to parse as a pure numeric ID
+/// - `931` becomes `Ref(931)`
+/// - `UB1` (Unterbedingung) becomes `SubCondition(1)`
+/// - `10P1..5` (Paketbedingung with cardinality) becomes
+///   `Package { id: 10, min: 1, max: 5 }`
+///
+/// Anything else is rejected as [`ParseError::InvalidConditionRef`].
+fn parse_condition_id(id: &str) -> Result<ConditionExpr, ParseError> {
     if let Ok(num) = id.parse::<u32>() {
-        ConditionExpr::Ref(num)
-    } else {
-        // For non-numeric IDs, extract leading digits if any
-        let numeric_part: String = id.chars().take_while(|c| c.is_ascii_digit()).collect();
-        if let Ok(num) = numeric_part.parse::<u32>() {
-            // Use the numeric prefix (e.g., "10P1..5" -> 10)
-            ConditionExpr::Ref(num)
-        } else {
-            // No numeric prefix (e.g., "UB1") - use 0 as a sentinel
-            ConditionExpr::Ref(0)
-        }
+        return Ok(ConditionExpr::Ref(num));
     }
+    if let Some(num) = id.strip_prefix("UB").and_then(|n| n.parse::<u32>().ok()) {
+        return Ok(ConditionExpr::SubCondition(num));
+    }
+    parse_package(id).ok_or_else(|| ParseError::InvalidConditionRef {
+        content: id.to_string(),
+    })
+}
+
+/// Parse a package reference of the form `{id}P{min}..{max}`.
+fn parse_package(id: &str) -> Option<ConditionExpr> {
+    let (package, cardinality) = id.split_once('P')?;
+    let (min, max) = cardinality.split_once("..")?;
+    let (id, min, max) = (
+        package.parse::<u32>().ok()?,
+        min.parse::<u32>().ok()?,
+        max.parse::<u32>().ok()?,
+    );
+    (min <= max).then_some(ConditionExpr::Package { id, min, max })
 }
 
 #[cfg(test)]
@@ -589,6 +598,58 @@ mod tests {
         assert_eq!(result, ConditionExpr::Ref(1));
     }
 
+    // === Packages and Unterbedingungen ===
+
+    #[test]
+    fn test_parse_package_condition() {
+        let result = ConditionParser::parse("Muss [10P1..5]").unwrap().unwrap();
+        assert_eq!(
+            result,
+            ConditionExpr::Package {
+                id: 10,
+                min: 1,
+                max: 5
+            }
+        );
+        assert!(result.condition_ids().is_empty());
+        assert_eq!(result.package_ids(), [10].into());
+    }
+
+    #[test]
+    fn test_parse_sub_condition() {
+        let result = ConditionParser::parse("Soll [UB1] ∧ [492]")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result,
+            ConditionExpr::And(vec![
+                ConditionExpr::SubCondition(1),
+                ConditionExpr::Ref(492),
+            ])
+        );
+        assert_eq!(result.condition_ids(), [492].into());
+        assert_eq!(result.sub_condition_ids(), [1].into());
+    }
+
+    #[test]
+    fn test_parse_package_display_roundtrip() {
+        let result = ConditionParser::parse("X [4P0..1] ∨ [UB3]")
+            .unwrap()
+            .unwrap();
+        assert_eq!(format!("{result}"), "([4P0..1] ∨ [UB3])");
+    }
+
+    #[test]
+    fn test_parse_invalid_condition_ref() {
+        for input in ["[UBx]", "[10P5..1]", "[10P1]", "[abc]"] {
+            let err = ConditionParser::parse(input).unwrap_err();
+            assert!(
+                matches!(err, ParseError::InvalidConditionRef { .. }),
+                "{input}: {err:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_condition_ids_extraction_full() {
         let result = ConditionParser::parse("Muss ([102] ∧ [2006]) ⊻ ([103] ∧ [2005])")
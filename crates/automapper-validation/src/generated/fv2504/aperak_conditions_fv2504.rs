@@ -239,44 +239,17 @@ impl AperakConditionEvaluatorFV2504 {
     }
 
     /// [931] Format: ZZZ = +00
-    // REVIEW: Timezone format ZZZ = +00 means the datetime value string in DTM element 0 component 1 must end with '+00'. Checking all DTM segments for this suffix covers the format requirement. (medium confidence)
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        ConditionResult::from(dtm_segs.iter().any(|s| {
-            s.elements
-                .first()
-                .and_then(|e| e.get(1))
-                .map(|v| v.ends_with("+00"))
-                .unwrap_or(false)
-        }))
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Email address format check: the communication address in COM element 0 component 0 must contain both '@' and '.' characters. Checking all COM segments covers the relevant occurrences. (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let com_segs = ctx.find_segments("COM");
-        ConditionResult::from(com_segs.iter().any(|s| {
-            s.elements
-                .first()
-                .and_then(|e| e.first())
-                .map(|v| v.contains('@') && v.contains('.'))
-                .unwrap_or(false)
-        }))
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Phone number format check: the string must start with '+' followed by only digits. Checking COM element 0 component 0 across all COM segments covers international phone number format validation. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let com_segs = ctx.find_segments("COM");
-        ConditionResult::from(com_segs.iter().any(|s| {
-            s.elements
-                .first()
-                .and_then(|e| e.first())
-                .map(|v| {
-                    let mut chars = v.chars();
-                    chars.next() == Some('+') && chars.all(|c| c.is_ascii_digit())
-                })
-                .unwrap_or(false)
-        }))
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 }
@@ -550,57 +550,22 @@ impl ComdisConditionEvaluatorFV2504 {
     }
 
     /// [930] Format: max. 2 Nachkommastellen
-    // REVIEW: Format condition for max 2 decimal places. COMDIS carries monetary amounts (MOA) as the primary numeric value segment. Using MOA elements[0][1] for the amount value. (medium confidence)
     fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 2 Nachkommastellen
-        let segs = ctx.find_segments("MOA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 2),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: ZZZ = +00 — timezone must be UTC
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_email(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_phone(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 }
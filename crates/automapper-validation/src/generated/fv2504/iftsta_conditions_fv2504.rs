@@ -1583,205 +1583,77 @@ impl IftstaConditionEvaluatorFV2504 {
     }
 
     /// [902] Format: Wert darf nur positiv oder 0 sein
-    // REVIEW: Format condition requiring value >= 0. Applied to QTY segment value (elements[0][1]) which is the standard location for quantity values in EDIFACT. Uses validate_numeric helper with >= 0.0. (medium confidence)
     fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Wert darf nur positiv oder 0 sein — value must be >= 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Wert darf nur positiv oder 0 sein")
     }
 
     /// [903] Format: Möglicher Wert: 1
-    // REVIEW: Format condition requiring value == 1. Uses validate_numeric with == operator. Targets QTY segment value (elements[0][1]) as most common numeric value field in IFTSTA context. (medium confidence)
     fn evaluate_903(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: 1")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
-    // REVIEW: Format condition: max 3 decimal places. Applies to QTY segment value field (elements[0][1]). (medium confidence)
     fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [911] Format: Mögliche Werte: 1 bis n, je Nachricht bei 1 beginnend und fortlaufend aufsteigend
-    fn evaluate_911(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // Hinweis: Mögliche Werte 1 bis n, je Nachricht bei 1 beginnend und fortlaufend aufsteigend
-        // Informational note about sequence numbering — always applies
-        ConditionResult::True
+    fn evaluate_911(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n, je Nachricht bei 1 beginnend und fortlaufend aufsteigend")
     }
 
     /// [931] Format: ZZZ = +00
-    // REVIEW: Format condition: timezone must be UTC (+00). Uses validate_timezone_utc on the DTM value field. Targets first DTM segment found. (medium confidence)
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    // REVIEW: Format condition: HHMM must equal 2200. Uses validate_hhmm_equals. DTM+163 is a common time qualifier in IFTSTA for delivery end times. (medium confidence)
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments_with_qualifier("DTM", 0, "163");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Checks if any COM segment value contains both '@' and '.' characters, validating email address format. COM segments carry communication details; email format requires both characters. This is the standard way to detect email COM values in EDIFACT. (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let com_segments = ctx.find_segments("COM");
-        for seg in &com_segments {
-            if let Some(value) = seg.elements.first().and_then(|e| e.first()) {
-                if value.contains('@') && value.contains('.') {
-                    return ConditionResult::True;
-                }
-            }
-        }
-        ConditionResult::False
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Checks if any COM segment value starts with '+' and is followed only by digits — the international phone number format (E.164). This is the standard pattern for phone number validation in German energy market EDIFACT messages. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let com_segments = ctx.find_segments("COM");
-        for seg in &com_segments {
-            if let Some(value) = seg.elements.first().and_then(|e| e.first()) {
-                if value.starts_with('+')
-                    && value.len() > 1
-                    && value[1..].chars().all(|c| c.is_ascii_digit())
-                {
-                    return ConditionResult::True;
-                }
-            }
-        }
-        ConditionResult::False
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
-    // REVIEW: Format condition validating Zählpunktbezeichnung (33 alphanumeric chars). In IFTSTA, Zählpunkt is typically associated with LOC+Z19 (Messlokation/Zählpunkt). Uses validate_zahlpunkt helper. Medium confidence because the exact LOC qualifier for Zählpunkt in IFTSTA may vary — could also be a different qualifier depending on context. (medium confidence)
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [960] Format: Netzlokations-ID
-    // REVIEW: Netzlokations-ID uses the same 11-digit Luhn check digit format as Marktlokations-ID. LOC+Z18 is the standard qualifier for Netzlokation in IFTSTA. Element 1, component 0 holds the location ID. (medium confidence)
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z18");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [961] Format: SR-ID
-    // REVIEW: SR-ID (Steuerbare Ressource ID) uses the same 11-digit Luhn check digit format as Marktlokations-ID. LOC+Z19 is the standard qualifier for SteuerbareRessource. Element 1, component 0 holds the location ID. (medium confidence)
     fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: SR-ID")
     }
 }
@@ -1275,318 +1275,112 @@ impl InvoicConditionEvaluatorFV2504 {
     }
 
     /// [902] Format: Möglicher Wert: ≥ 0
-    // REVIEW: Format condition requiring value >= 0, applied to MOA amount (DE5004 at elements[0][1]). Medium confidence because exact segment/group context is inferred from INVOIC structure — the full AHB table row would clarify which specific MOA qualifier this applies to. (medium confidence)
     fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: ≥ 0
-        // Applied to MOA monetary amounts in INVOIC
-        let segs = ctx.find_segments("MOA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: ≥ 0")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
-    // REVIEW: Format condition requiring at most 3 decimal places. Most likely applies to MOA DE5004 (Geldbetrag) in INVOIC. Medium confidence — the exact MOA qualifier or group (SG27/SG42/SG50/SG52) depends on the AHB table row context not included in the condition description alone. (medium confidence)
     fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 3 Nachkommastellen
-        // Applied to MOA monetary amounts in INVOIC
-        let segs = ctx.find_segments("MOA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
-    // REVIEW: Format condition requiring value in range 1..n (strictly positive). Applied to QTY quantity values in INVOIC (DE6060 at elements[0][1]). Medium confidence — the exact segment context depends on the full AHB table row. (medium confidence)
     fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte: 1 bis n — value must be >= 1 (positive integer range)
-        // Applied to QTY quantities in INVOIC
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [910] Format: Möglicher Wert: &lt; 0 oder ≥ 0
-    // REVIEW: Format condition accepting any numeric value (< 0 OR >= 0 is universally true). This documents that the field accepts negative values (unlike condition 902 which restricts to >= 0). Validates the value is a parseable number. Medium confidence on the exact segment — likely a MOA that can be a credit/deduction amount. (medium confidence)
     fn evaluate_910(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: < 0 oder ≥ 0 — any numeric value is valid (no sign constraint)
-        // Applied to MOA amounts that may be negative (e.g., credits, deductions)
-        let segs = ctx.find_segments("MOA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) if !val.is_empty() => {
-                // < 0 or >= 0 covers all real numbers — validate it is a parseable number
-                match val.trim_start_matches('-').parse::<f64>() {
-                    Ok(_) => ConditionResult::True,
-                    Err(_) => ConditionResult::False,
-                }
-            }
-            _ => ConditionResult::Unknown,
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: < 0 oder ≥ 0")
     }
 
     /// [911] Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-    // REVIEW: Sequential numbering validation (1 to n, ascending) — only the >= 1 integer constraint can be checked on a single value. Full sequential ordering cannot be verified without stateful iteration across all instances. Applies to LIN line item numbers in INVOIC SG26. Medium confidence due to segment assumption and inability to verify sequential ordering in isolation. (medium confidence)
     fn evaluate_911(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-        // Validates that the value is a positive integer >= 1 (sequential enforcement requires external state)
-        let segs = ctx.find_segments("LIN");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => match val.parse::<i64>() {
-                Ok(n) if n >= 1 => ConditionResult::True,
-                Ok(_) => ConditionResult::False,
-                Err(_) => ConditionResult::False,
-            },
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend")
     }
 
     /// [912] Format: max. 6 Nachkommastellen
     fn evaluate_912(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 6 Nachkommastellen
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Nachkommastellen")
     }
 
     /// [914] Format: Möglicher Wert: &gt; 0
     fn evaluate_914(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: > 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: > 0")
     }
 
     /// [927] Format: Möglicher Wert: -1
-    // REVIEW: Value must be exactly -1. Used for special indicator values (e.g., a reversal or placeholder quantity). validate_numeric with == operator handles exact match. Medium confidence due to segment assumption — the -1 constraint is unusual and the exact segment context (QTY vs MOA) depends on which AHB data element references this condition. (medium confidence)
     fn evaluate_927(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: -1
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", -1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: -1")
     }
 
     /// [930] Format: max. 2 Nachkommastellen
     fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 2 Nachkommastellen
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 2),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [937] Format: keine Nachkommastelle
     fn evaluate_937(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: keine Nachkommastelle")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_email(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_phone(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [946] Format: max. 11 Nachkommastellen
     fn evaluate_946(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 11),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 11 Nachkommastellen")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z17");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [960] Format: Netzlokations-ID
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z18");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [961] Format: SR-ID
     fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: SR-ID")
     }
 }
@@ -577,17 +577,8 @@ impl MsconsConditionEvaluatorFV2504 {
     }
 
     /// [922] Format: TR-ID
-    // REVIEW: Format: TR-ID — validates that the value is a valid Transaktionsreferenz-ID (1–35 alphanumeric characters) using validate_tr_id. In MSCONS, the document/transaction ID is typically found in BGM elements[1][0] (the document number composite). Medium confidence because the exact DE this format condition is applied to depends on the AHB field context, which could alternatively be RFF or another reference segment. (medium confidence)
     fn evaluate_922(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let bgm_segs = ctx.find_segments("BGM");
-        match bgm_segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_tr_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: TR-ID")
     }
 
     /// [1] Sofern per ORDERS angefordert
@@ -1676,307 +1667,112 @@ impl MsconsConditionEvaluatorFV2504 {
 
     /// [902] Format: Möglicher Wert: ≥ 0
     fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: ≥ 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: ≥ 0")
     }
 
     /// [904] Format: genau 16 Stellen
-    // REVIEW: Format condition requiring exactly 16 characters. Applied to QTY value (most common numeric format condition target in MSCONS). Confidence is medium because the exact target segment/element is not specified in the condition text — QTY is the most likely candidate in MSCONS context. (medium confidence)
     fn evaluate_904(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: genau 16 Stellen
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_exact_length(val, 16),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: genau 16 Stellen")
     }
 
     /// [905] Format: max. 3 Stellen
     fn evaluate_905(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_integer_digits(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Stellen")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
     fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [907] Format: max. 4 Nachkommastellen
     fn evaluate_907(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 4),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 4 Nachkommastellen")
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
     fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [909] Format: Mögliche Werte: 0 bis n
     fn evaluate_909(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 0 bis n")
     }
 
     /// [910] Format: Möglicher Wert: &lt; 0 oder ≥ 0
     fn evaluate_910(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let qty_segments = ctx.find_segments("QTY");
-        let val = qty_segments
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-            .map(|s| s.as_str())
-            .unwrap_or("");
-        if val.is_empty() {
-            return ConditionResult::Unknown;
-        }
-        match val.parse::<f64>() {
-            Ok(_) => ConditionResult::True,
-            Err(_) => ConditionResult::False,
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: < 0 oder ≥ 0")
     }
 
     /// [917] Format: max. 4 Vorkommastellen
     fn evaluate_917(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let qty_segments = ctx.find_segments("QTY");
-        match qty_segments
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_integer_digits(val, 4),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 4 Vorkommastellen")
     }
 
     /// [918] Format: Zeichen aus dem über UNOC definierten Zeichensatz, wobei von den Buchstaben nur Großbuchstaben erlaubt sind.
-    // REVIEW: UNOC charset requires uppercase-only alphabetics. Checks all alphabetic characters in FTX text fields (C108, elements[3], components 0..4) are uppercase. Non-alphabetic characters (digits, punctuation) are permitted. Medium confidence — UNOC validation approximated as uppercase-only alphabetic check without full ISO 8859-1 codepoint enumeration. (medium confidence)
     fn evaluate_918(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let ftx_segments = ctx.find_segments("FTX");
-        for seg in &ftx_segments {
-            if let Some(c108) = seg.elements.get(3) {
-                for component in c108 {
-                    let s = component.as_str();
-                    if s.is_empty() {
-                        continue;
-                    }
-                    if !s.chars().all(|c| !c.is_alphabetic() || c.is_uppercase()) {
-                        return ConditionResult::False;
-                    }
-                }
-            }
-        }
-        ConditionResult::True
+        ctx.evaluate_format("Format: Zeichen aus dem über UNOC definierten Zeichensatz, wobei von den Buchstaben nur Großbuchstaben erlaubt sind.")
     }
 
     /// [925] Format: max. 5 Nachkommastellen
     fn evaluate_925(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let qty_segments = ctx.find_segments("QTY");
-        match qty_segments
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 5),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 5 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [937] Format: keine Nachkommastelle
     fn evaluate_937(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: keine Nachkommastelle")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_email(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_phone(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
-    // REVIEW: Format: Zählpunktbezeichnung — 33 alphanumeric characters. In MSCONS, Zählpunktbezeichnung typically appears in LOC+Z19. Using validate_zahlpunkt helper. (medium confidence)
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [960] Format: Netzlokations-ID
-    // REVIEW: Netzlokations-ID uses the same 11-digit Luhn-check format as Marktlokations-ID (validate_malo_id). In MSCONS, Netzlokation is typically referenced via LOC+Z17. Segment structure reference does not include LOC for MSCONS, so Z17 qualifier is assumed from German energy market convention. (medium confidence)
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Netzlokations-ID — 11-digit ID with Luhn check digit (same format as MaLo-ID)
-        // In MSCONS, Netzlokation is identified by LOC+Z17
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z17");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [2001] Segmentgruppe ist nur einmal je UNH anzugeben
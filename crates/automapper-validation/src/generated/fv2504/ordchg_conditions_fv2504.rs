@@ -132,68 +132,17 @@ impl OrdchgConditionEvaluatorFV2504 {
     }
 
     /// [931] Format: ZZZ = +00
-    // REVIEW: Format constraint: ZZZ (timezone) must be '+00' (UTC). DTM elements[0][1] contains the datetime value including timezone suffix. Checks all DTM segments to ensure their datetime values end with '+00'. Returns Unknown when no DTM segments present, False if any DTM violates the constraint. (medium confidence)
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtms = ctx.find_segments("DTM");
-        if dtms.is_empty() {
-            return ConditionResult::Unknown;
-        }
-        for dtm in &dtms {
-            if let Some(value) = dtm.elements.first().and_then(|e| e.get(1)) {
-                if !value.is_empty() && !value.ends_with("+00") {
-                    return ConditionResult::False;
-                }
-            }
-        }
-        ConditionResult::True
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Format check: the email address string in COM DE3148 (elements[0][0]) when DE3155 (elements[0][1]) is 'EM' must contain both '@' and '.'. Returns Unknown if no EM-typed COM segment is found. (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let coms = ctx.find_segments("COM");
-        for com in &coms {
-            let is_email = com
-                .elements
-                .first()
-                .and_then(|e| e.get(1))
-                .is_some_and(|v| v == "EM");
-            if is_email {
-                let value = com
-                    .elements
-                    .first()
-                    .and_then(|e| e.first())
-                    .cloned()
-                    .unwrap_or_default();
-                return ConditionResult::from(value.contains('@') && value.contains('.'));
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Format check: the phone/fax number string in COM DE3148 (elements[0][0]) when DE3155 (elements[0][1]) is TE/FX/AJ/AL must start with '+' followed by digits only. Returns Unknown if no phone-typed COM segment is found. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let coms = ctx.find_segments("COM");
-        for com in &coms {
-            let is_phone = com
-                .elements
-                .first()
-                .and_then(|e| e.get(1))
-                .is_some_and(|v| matches!(v.as_str(), "TE" | "FX" | "AJ" | "AL"));
-            if is_phone {
-                let value = com
-                    .elements
-                    .first()
-                    .and_then(|e| e.first())
-                    .cloned()
-                    .unwrap_or_default();
-                let mut chars = value.chars();
-                let starts_with_plus = chars.next() == Some('+');
-                let rest_all_digits = chars.all(|c| c.is_ascii_digit());
-                return ConditionResult::from(starts_with_plus && rest_all_digits);
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 }
@@ -591,10 +591,8 @@ impl OrdersConditionEvaluatorFV2504 {
     }
 
     /// [967] Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4
-    fn evaluate_967(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: Condition [967] requires manual implementation
-        // Reason: Format condition requires validating an X.509 certificate body per BSI TR-03109-4. Certificate structural validation (ASN.1/DER encoding, BSI profile compliance) is not supported by the available format validation helpers and cannot be meaningfully checked against raw EDIFACT segment values.
-        ConditionResult::Unknown
+    fn evaluate_967(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4")
     }
 
     /// [2001] Die SG29 ist so oft zu wiederholen, wie ab dem DTM+203 (Ausführungsdatum) Tranchen zu der in der SG2 genannten Marktlokation vorhanden sind.
@@ -1722,249 +1720,98 @@ impl OrdersConditionEvaluatorFV2504 {
     }
 
     /// [903] Format: Möglicher Wert: 1
-    fn evaluate_903(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_903(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: 1")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
     fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 3 Nachkommastellen
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [911] Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-    fn evaluate_911(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // Hinweis: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-        // This is a cardinality/sequencing annotation — informational, always applies
-        ConditionResult::True
+    fn evaluate_911(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend")
     }
 
     /// [914] Format: Möglicher Wert: &gt;0
     fn evaluate_914(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: >0 — QTY value must be greater than zero
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: >0")
     }
 
     /// [922] Format: TR-ID
-    // REVIEW: TR-ID (Transaktionsreferenz-ID) format. In ORDERS the transaction reference ID appears in BGM C106. The exact segment context depends on the AHB field definition — implemented as max-length-35 check on BGM, consistent with EDIFACT AN..35 data element constraints. Medium confidence because the target segment may differ by AHB row. (medium confidence)
     fn evaluate_922(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: TR-ID — Transaktionsreferenz-ID, max 35 alphanumeric characters
-        // In ORDERS, TR-ID typically appears in BGM element 1 (C106 document identifier)
-        let segs = ctx.find_segments("BGM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) if !val.is_empty() => validate_max_length(val, 35),
-            Some(_) => ConditionResult::False,
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: TR-ID")
     }
 
     /// [930] Format: max. 2 Nachkommastellen
     fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 2 Nachkommastellen — QTY value must have at most 2 decimal places
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 2),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: ZZZ = +00 — DTM timezone offset must be UTC (+00)
-        let segs = ctx.find_segments("DTM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    // REVIEW: HHMM = 2200 time constraint on DTM. In ORDERS FV2504, delivery period end (qualifier 163) must terminate at 22:00 UTC, matching the standard German grid day-end convention. Medium confidence because the target DTM qualifier may differ for the specific AHB row — 163 is the most likely candidate for an end-of-period constraint. (medium confidence)
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 2200 — DTM+163 (delivery period end) time must be 2200
-        let dtm_segs = ctx.find_segments_with_qualifier("DTM", 0, "163");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_email(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_phone(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z17");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [955] Format: Möglicher Wert: &lt;100
-    // REVIEW: Format: Möglicher Wert: <100 — numeric value must be less than 100. Most likely applies to a QTY quantity value (C186.D6060, elements[0][1]). validate_numeric with "<" and threshold 100.0 covers this. Medium confidence because the exact segment context (QTY vs PRI vs PCT) is not specified — QTY is the most common numeric value carrier in ORDERS. (medium confidence)
     fn evaluate_955(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "<", 100.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: <100")
     }
 
     /// [960] Format: Netzlokations-ID
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z18");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [961] Format: SR-ID
     fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: SR-ID")
     }
 
     /// [962] Format: max. 6 Vorkommastellen
-    // REVIEW: Format: max 6 integer digits (Vorkommastellen). Applied to QTY element[0][1] as the most common numeric data field in ORDERS requiring integer-part validation. Uses validate_max_integer_digits helper. Confidence medium because the target segment is inferred from context rather than explicitly stated. (medium confidence)
     fn evaluate_962(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 6 Vorkommastellen — applied to QTY value (most likely numeric field in ORDERS)
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_integer_digits(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Vorkommastellen")
     }
 
     /// [2002] Ist mindestens zwei Mal anzugeben
@@ -1001,160 +1001,53 @@ impl OrdrspConditionEvaluatorFV2504 {
     }
 
     /// [902] Format: Format: Möglicher Wert: ≥ 0
-    // REVIEW: Format condition requiring numeric value >= 0. Applied to QTY segment value (elements[0][1]) by convention matching the example pattern. Medium confidence because the exact segment this applies to is not specified in the condition text, but QTY is the standard target for quantity format conditions. (medium confidence)
     fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: >= 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Format: Möglicher Wert: ≥ 0")
     }
 
     /// [903] Format: Möglicher Wert: 1
-    // REVIEW: Format condition requiring the value to equal exactly 1. Applied to QTY segment value (elements[0][1]) by convention. Medium confidence as the exact target segment is not stated, but QTY quantity value is the standard target for this kind of format constraint. (medium confidence)
     fn evaluate_903(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: 1
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: 1")
     }
 
     /// [930] Format: max. 2 Nachkommastellen
-    // REVIEW: Format condition requiring at most 2 decimal places. Applied to QTY segment value (elements[0][1]) following the standard pattern from example 19. Medium confidence as the exact target segment is not specified in the condition text, but QTY quantity value is the canonical target for decimal place constraints. (medium confidence)
     fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 2 Nachkommastellen
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 2),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Format condition checking that the string contains '@' and '.' — characteristic of an email address format requirement. Applied to COM segment with EM (Electronic Mail) qualifier where elements[0][0] is the address and elements[0][1] is the communication means type code. Returns Unknown when no COM+EM segment is found (condition not applicable). (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: string must contain '@' and '.' — email address format check
-        // Applied to COM segment with EM (Electronic Mail) qualifier
-        // COM+address:qualifier → elements[0] = ["address", "qualifier"]
-        let com_segs = ctx.find_segments("COM");
-        for seg in &com_segs {
-            if let Some(elem0) = seg.elements.first() {
-                if elem0.get(1).map(|q| q == "EM").unwrap_or(false) {
-                    let value = elem0.first().map(|s| s.as_str()).unwrap_or("");
-                    if value.is_empty() {
-                        return ConditionResult::Unknown;
-                    }
-                    return ConditionResult::from(value.contains('@') && value.contains('.'));
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Format condition checking that the string starts with '+' and contains only digits afterwards — the international phone number format (e.g. +4912345678). Applied to COM segments with phone-type qualifiers (TE=Telephone, FX=Telefax, AH=After Hours). Returns Unknown when no COM segment with a phone qualifier is found. Requires at least one digit after '+' (len > 1) since a bare '+' is not a valid phone number. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: string must start with '+' and be followed only by digits — international phone number format
-        // Applied to COM segments with phone-type qualifiers: TE (Telephone), FX (Telefax), AH (After Hours)
-        // COM+number:qualifier → elements[0] = ["number", "qualifier"]
-        let com_segs = ctx.find_segments("COM");
-        for seg in &com_segs {
-            if let Some(elem0) = seg.elements.first() {
-                let qualifier = elem0.get(1).map(|s| s.as_str()).unwrap_or("");
-                if matches!(qualifier, "TE" | "FX" | "AH") {
-                    let value = elem0.first().map(|s| s.as_str()).unwrap_or("");
-                    if value.is_empty() {
-                        return ConditionResult::Unknown;
-                    }
-                    return ConditionResult::from(
-                        value.starts_with('+')
-                            && value.len() > 1
-                            && value[1..].chars().all(|c| c.is_ascii_digit()),
-                    );
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [2036] Pro Nachricht ist die SG27 genau einmal anzugeben
@@ -540,78 +540,32 @@ impl PartinConditionEvaluatorFV2504 {
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
-    fn evaluate_908(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte: 1 bis n — informational cardinality note, always applies
-        ConditionResult::True
+    fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: ZZZ = +00 — DTM timezone must be UTC
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 2200 — time portion of DTM value must be 2200
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 2300 — time portion of DTM value must be 2300
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: string must contain @ and . — email address validation
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_email(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: string must start with + followed only by digits — phone number validation
-        let segs = ctx.find_segments("COM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_phone(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 }
@@ -813,382 +813,107 @@ impl PricatConditionEvaluatorFV2504 {
     }
 
     /// [902] Format: Möglicher Wert: ≥ 0
-    fn evaluate_902(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: ≥ 0")
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
-    fn evaluate_908(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [909] Format: Mögliche Werte: 0 bis n
-    // REVIEW: Format condition specifying possible values 0 to n (non-negative). Applied to LIN DE1082 (Positionsnummer) as the most likely numeric element in PRICAT that can take values starting from 0. Medium confidence because the AHB context specifying which exact element this condition applies to is not included in the description. (medium confidence)
     fn evaluate_909(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte 0 bis n — numeric value must be >= 0
-        // Applied to LIN DE1082 (Positionsnummer) which can start from 0
-        let lin_segs = ctx.find_segments("LIN");
-        if lin_segs.is_empty() {
-            return ConditionResult::Unknown;
-        }
-        for seg in &lin_segs {
-            let val = match seg.elements.first().and_then(|e| e.first()) {
-                Some(v) => v,
-                None => return ConditionResult::Unknown,
-            };
-            match validate_numeric(val, ">=", 0.0) {
-                ConditionResult::False => return ConditionResult::False,
-                ConditionResult::Unknown => return ConditionResult::Unknown,
-                ConditionResult::True => {}
-            }
-        }
-        ConditionResult::True
+        ctx.evaluate_format("Format: Mögliche Werte: 0 bis n")
     }
 
     /// [911] Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-    // REVIEW: Format condition specifying values 1 to n, starting at 1 and continuously ascending per message or segment group. This describes sequential position numbering, which in PRICAT is DE1082 in the LIN segment. The implementation collects all LIN segments and verifies their position numbers are 1, 2, 3, ... in order. Medium confidence because the specific element and scope (per-message vs per-SG17 group) are not explicit in the condition description alone. (medium confidence)
     fn evaluate_911(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte 1 bis n, sequential per message/segment group starting at 1
-        // Validates LIN DE1082 (Positionsnummer) is sequential starting from 1
-        let lin_segs = ctx.find_segments("LIN");
-        if lin_segs.is_empty() {
-            return ConditionResult::Unknown;
-        }
-        let mut expected: u64 = 1;
-        for seg in &lin_segs {
-            let pos_str = match seg.elements.first().and_then(|e| e.first()) {
-                Some(v) => v,
-                None => return ConditionResult::Unknown,
-            };
-            let pos: u64 = match pos_str.parse() {
-                Ok(n) => n,
-                Err(_) => return ConditionResult::False,
-            };
-            if pos != expected {
-                return ConditionResult::False;
-            }
-            expected += 1;
-        }
-        ConditionResult::True
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend")
     }
 
     /// [912] Format: max. 6 Nachkommastellen
-    // REVIEW: Max 6 decimal places format check. In PRICAT context, price values (PRI segment, element 0, component 1) are the primary numeric values with decimal places. Medium confidence because the exact segment this applies to depends on context — it could also apply to QTY or other numeric fields. (medium confidence)
     fn evaluate_912(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 6 Nachkommastellen — applies to PRI price value in PRICAT
-        let segs = ctx.find_segments("PRI");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Nachkommastellen")
     }
 
     /// [926] Format: Möglicher Wert: 0
-    // REVIEW: Value must be exactly 0. Applies to a numeric data element — QTY is most common in PRICAT for this kind of constraint (e.g., minimum order quantity of 0). Medium confidence because the specific segment depends on where this condition is referenced in the AHB. (medium confidence)
     fn evaluate_926(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: 0 — value must equal 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: 0")
     }
 
     /// [929] Format: Möglicher Wert: 1000
-    // REVIEW: Value must be exactly 1000. In PRICAT, 1000 is a common reference quantity (e.g., price per 1000 units in QTY). Medium confidence because the exact target segment depends on where this condition is used in the AHB. (medium confidence)
     fn evaluate_929(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: 1000 — value must equal 1000
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", 1000.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: 1000")
     }
 
     /// [931] Format: ZZZ = +00
-    // REVIEW: DTM timezone check: EDIFACT DTM format 303 (CCYYMMDDHHMMzzz) or 719 encodes timezone offset. The condition requires ZZZ=+00 (UTC offset). elements[0][1] holds the datetime value string; checking it ends with '+00' covers standard EDIFACT timezone encoding. Medium confidence because the exact DTM qualifier and format code context is unknown without the segment structure reference. (medium confidence)
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // ZZZ = +00: timezone offset in DTM value must be +00
-        // DTM format 303: YYYYMMDDHHMMzzz, timezone encoded as last part of elements[0][1]
-        let dtm_segments = ctx.find_segments("DTM");
-        for seg in &dtm_segments {
-            if let Some(value) = seg.elements.first().and_then(|e| e.get(1)) {
-                if !value.is_empty() {
-                    // Timezone +00 appears at end of value string
-                    if value.ends_with("+00") {
-                        return ConditionResult::True;
-                    } else {
-                        return ConditionResult::False;
-                    }
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    // REVIEW: DTM time check: extracts HHMM portion from DTM value. Format 203 (CCYYMMDDHHmm) places hours at positions 8-11. Format 401 (HHmm) starts at position 0. Condition requires exactly 2200. Medium confidence because the specific DTM qualifier context in PRICAT is unknown without the segment structure reference. (medium confidence)
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // HHMM = 2200: time value in DTM must be 2200
-        // In DTM format 203 (YYYYMMDDHHMM) or 401 (HHMM), the time portion is HHMM
-        let dtm_segments = ctx.find_segments("DTM");
-        for seg in &dtm_segments {
-            if let Some(value) = seg.elements.first().and_then(|e| e.get(1)) {
-                let format_code = seg
-                    .elements
-                    .first()
-                    .and_then(|e| e.get(2))
-                    .map(|s| s.as_str())
-                    .unwrap_or("");
-                let time_part = match format_code {
-                    "203" if value.len() >= 12 => &value[8..12],
-                    "401" if value.len() >= 4 => &value[0..4],
-                    _ if value.len() >= 12 => &value[8..12],
-                    _ => continue,
-                };
-                if time_part == "2200" {
-                    return ConditionResult::True;
-                } else {
-                    return ConditionResult::False;
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
-    // REVIEW: Same structure as condition 932 but checks for 2300 instead of 2200. Extracts HHMM portion from DTM value using format code to determine position. (medium confidence)
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // HHMM = 2300: time value in DTM must be 2300
-        let dtm_segments = ctx.find_segments("DTM");
-        for seg in &dtm_segments {
-            if let Some(value) = seg.elements.first().and_then(|e| e.get(1)) {
-                let format_code = seg
-                    .elements
-                    .first()
-                    .and_then(|e| e.get(2))
-                    .map(|s| s.as_str())
-                    .unwrap_or("");
-                let time_part = match format_code {
-                    "203" if value.len() >= 12 => &value[8..12],
-                    "401" if value.len() >= 4 => &value[0..4],
-                    _ if value.len() >= 12 => &value[8..12],
-                    _ => continue,
-                };
-                if time_part == "2300" {
-                    return ConditionResult::True;
-                } else {
-                    return ConditionResult::False;
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [937] Format: keine Nachkommastelle
-    // REVIEW: No decimal places allowed — integer values only. Implemented as validate_max_decimal_places with 0. In PRICAT, this typically applies to QTY (quantity must be whole number). Medium confidence because the exact target segment depends on AHB context. (medium confidence)
     fn evaluate_937(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: keine Nachkommastelle — no decimal places allowed (max 0)
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: keine Nachkommastelle")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Email address format validation: the string must contain both '@' and '.'. This pattern matches COM segment (communication address) with channel code EM (Electronic Mail). elements[0][0] holds the address, elements[0][1] holds the channel qualifier. Checks channel=EM first, then falls back to any non-empty COM value. Medium confidence because PRICAT segment structure reference not provided. (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Email format: string must contain both '@' and '.'
-        // Typically applies to COM segment (communication channel EM = email)
-        let com_segments = ctx.find_segments("COM");
-        for seg in &com_segments {
-            // COM: elements[0][0] = communication address, elements[0][1] = channel code
-            let channel = seg
-                .elements
-                .first()
-                .and_then(|e| e.get(1))
-                .map(|s| s.as_str())
-                .unwrap_or("");
-            if channel == "EM" {
-                if let Some(address) = seg.elements.first().and_then(|e| e.first()) {
-                    let contains_at = address.contains('@');
-                    let contains_dot = address.contains('.');
-                    return ConditionResult::from(contains_at && contains_dot);
-                }
-            }
-        }
-        // Fallback: check any COM value for @ and .
-        for seg in &com_segments {
-            if let Some(address) = seg.elements.first().and_then(|e| e.first()) {
-                if !address.is_empty() {
-                    return ConditionResult::from(address.contains('@') && address.contains('.'));
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Phone number format: must start with '+' then only digits (international E.164 format). Applies to COM segment with telephone/fax channel codes (TE, FX, AJ). elements[0][0] = number, elements[0][1] = channel code. Medium confidence because PRICAT segment structure reference not provided. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Phone number format: must start with '+' followed only by digits
-        // Typically applies to COM segment (TEL/FAX channel)
-        let com_segments = ctx.find_segments("COM");
-        for seg in &com_segments {
-            let channel = seg
-                .elements
-                .first()
-                .and_then(|e| e.get(1))
-                .map(|s| s.as_str())
-                .unwrap_or("");
-            if matches!(channel, "TE" | "FX" | "AJ") {
-                if let Some(number) = seg.elements.first().and_then(|e| e.first()) {
-                    if !number.is_empty() {
-                        let valid = number.starts_with('+')
-                            && number.len() > 1
-                            && number[1..].chars().all(|c| c.is_ascii_digit());
-                        return ConditionResult::from(valid);
-                    }
-                }
-            }
-        }
-        // Fallback: check any COM value for +digit format
-        for seg in &com_segments {
-            if let Some(number) = seg.elements.first().and_then(|e| e.first()) {
-                if !number.is_empty() {
-                    let valid = number.starts_with('+')
-                        && number.len() > 1
-                        && number[1..].chars().all(|c| c.is_ascii_digit());
-                    return ConditionResult::from(valid);
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [941] Format: Artikelnummer
-    // REVIEW: Article number format validation on PIA segment (Additional Product ID), element 1, component 0. The pattern n1-n2-n1-n3 is a common PRICAT Artikelnummer format in the German energy market. Medium confidence because the exact digit-segment pattern may differ — should be verified against the specific AHB table for this condition reference. (medium confidence)
     fn evaluate_941(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Artikelnummer — validate article number format in PIA segment
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 3]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Artikelnummer")
     }
 
     /// [942] Format: n1-n2-n1-n3
     fn evaluate_942(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 3]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n1-n2-n1-n3")
     }
 
     /// [946] Format: max. 11 Nachkommastellen
-    // REVIEW: Format: max. 11 Nachkommastellen — validates that a numeric value has at most 11 decimal places. In PRICAT, price values appear in PRI segment element 0 component 1. Using PRI as the most likely price segment in PRICAT context. (medium confidence)
     fn evaluate_946(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PRI");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 11),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 11 Nachkommastellen")
     }
 
     /// [948] Format: n1-n2-n1-n8-n2
     fn evaluate_948(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 8, 2]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n1-n2-n1-n8-n2")
     }
 
     /// [949] Format: n1-n2-n1-n8-n2-n1
     fn evaluate_949(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 8, 2, 1]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n1-n2-n1-n8-n2-n1")
     }
 
     /// [957] Format: n1-n2-n1-n8
     fn evaluate_957(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 8]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n1-n2-n1-n8")
     }
 
     /// [959] Format: n13-n2
-    // REVIEW: Format: n13-n2 is an Artikelnummer pattern — 13 digits, dash, 2 digits. In PRICAT, article identifiers appear in PIA element 1 component 0. Using validate_artikel_pattern with &[13, 2]. Medium confidence because the exact target segment/element depends on AHB context not provided here. (medium confidence)
     fn evaluate_959(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[13, 2]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n13-n2")
     }
 
     /// [968] Format: Möglicher Wert: ≤ 0
-    // REVIEW: Format: Möglicher Wert: ≤ 0 means the numeric value must be less than or equal to zero. In PRICAT, PRI segment element 0 component 1 holds the price amount. Using validate_numeric with "<=", 0.0. Medium confidence because the exact target segment depends on AHB context — could also be MOA or another numeric field. (medium confidence)
     fn evaluate_968(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("PRI");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "<=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: ≤ 0")
     }
 }
@@ -288,17 +288,8 @@ impl QuotesConditionEvaluatorFV2504 {
     }
 
     /// [961] Format: SR-ID
-    // REVIEW: Format: SR-ID — 11-digit Luhn check digit number. In QUOTES context, Steuerbare Ressource IDs typically appear in LOC+Z19 at elements[1][0]. validate_sr_id checks 11 digits with Luhn validation. Medium confidence because QUOTES segment structure may differ from UTILMD. (medium confidence)
     fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_sr_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: SR-ID")
     }
 
     /// [2066] Diese SG28 ist so oft zu wiederholen, wie zu den unterschiedlichen Messprodukt-Position-Codes zu dem innerhalb derselben SG27 LIN im PIA+5 DE7140 (Erforderliches Produkt Konfigurationserlaubnis fü...
@@ -871,263 +862,93 @@ impl QuotesConditionEvaluatorFV2504 {
     }
 
     /// [903] Format: Möglicher Wert: 1
-    // REVIEW: Format condition requiring the value to equal exactly 1. Applies to a numeric field — most likely QTY[0][1] in QUOTES context. validate_numeric with == 1.0 covers this. Medium confidence because the exact segment binding isn't specified in the condition text alone. (medium confidence)
     fn evaluate_903(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: 1 — the only permitted value is exactly 1
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "==", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: 1")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
-    // REVIEW: Format condition for maximum 3 decimal places. Uses validate_max_decimal_places helper. Targeting QTY[0][1] as the most likely numeric value field in QUOTES. Medium confidence because the specific data element binding is not stated in the condition text. (medium confidence)
     fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 3 Nachkommastellen — at most 3 decimal places
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 3),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
-    // REVIEW: Format condition for values in range 1 to n (any positive integer). validate_numeric with >= 1.0 captures the lower bound; the upper bound n is open-ended. Targeting QTY as the most common numeric field. Medium confidence due to unspecified exact segment. (medium confidence)
     fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte: 1 bis n — value must be >= 1 (positive integer range)
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [911] Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend
-    fn evaluate_911(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // Hinweis: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend
-        // und fortlaufend aufsteigend — informational annotation about sequential numbering,
-        // always applies unconditionally
-        ConditionResult::True
+    fn evaluate_911(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n, je Nachricht oder Segmentgruppe bei 1 beginnend und fortlaufend aufsteigend")
     }
 
     /// [912] Format: max. 6 Nachkommastellen
-    // REVIEW: Format condition for maximum 6 decimal places. Uses validate_max_decimal_places helper with limit 6. Targeting QTY[0][1] as the primary numeric value field in QUOTES. Medium confidence because the exact data element binding is not given in the condition text. (medium confidence)
     fn evaluate_912(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 6 Nachkommastellen — at most 6 decimal places
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Nachkommastellen")
     }
 
     /// [914] Format: Möglicher Wert: &gt; 0
-    // REVIEW: Format condition requiring value > 0. In QUOTES context this most likely applies to QTY element[0][1] (the quantity value). Medium confidence because the exact segment is not specified in the condition text — it depends on which data element this condition is attached to in the AHB. (medium confidence)
     fn evaluate_914(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: > 0 — numeric value must be greater than 0
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: > 0")
     }
 
     /// [931] Format: ZZZ = +00
-    // REVIEW: Format condition validating that the timezone portion (ZZZ) of a DTM value equals +00 (UTC). Uses validate_timezone_utc helper. Medium confidence because the specific DTM qualifier this applies to is not stated in the condition text — the AHB attaches this to a specific DTM element. (medium confidence)
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: ZZZ = +00 — timezone must be UTC (+00)
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    // REVIEW: Format condition requiring the HHMM portion of a DTM value to equal 2200. Uses validate_hhmm_equals helper. Medium confidence because the specific DTM qualifier (e.g., 163, 164, 137) this applies to is not stated in the condition text alone — it depends on which DTM element in the QUOTES AHB carries this constraint. (medium confidence)
     fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 2200 — time portion must be 2200
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2200"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
-    // REVIEW: Format condition requiring the HHMM portion of a DTM value to equal 2300. Uses validate_hhmm_equals helper. Medium confidence for the same reason as 932 — specific DTM qualifier not identified from condition text alone. (medium confidence)
     fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 2300 — time portion must be 2300
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "2300"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
-    // REVIEW: Format condition requiring the HHMM portion of a DTM value to equal 0400. Uses validate_hhmm_equals helper. Medium confidence for the same reason as 932/933 — specific DTM qualifier not identified from condition text alone. In QUOTES context, 0400 likely represents a gas day start time. (medium confidence)
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 0400 — time portion must be 0400
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
-    // REVIEW: Format condition checking HHMM = 0500 on a DTM segment. Without a specific qualifier reference provided for this QUOTES condition, falls back to the first DTM segment. validate_hhmm_equals extracts the HHMM portion of the datetime value and compares it to '0500'. (medium confidence)
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 0500 — validate time portion of DTM value equals 0500
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    // REVIEW: Checks COM segments for email-format values (must contain '@' and '.'). COM is the standard EDIFACT segment for communication channels where email addresses appear (qualifier EM). Scans all COM values since context doesn't specify which occurrence to validate. (medium confidence)
     fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
-        for seg in ctx.find_segments("COM") {
-            if let Some(elem) = seg.elements.first() {
-                if let Some(value) = elem.first() {
-                    if value.contains('@') && value.contains('.') {
-                        return ConditionResult::True;
-                    }
-                }
-            }
-        }
-        ConditionResult::False
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    // REVIEW: Checks COM segments for international phone number format (starts with '+' followed only by digits). COM is the standard segment for telephone contacts. Scans all COM values since context doesn't specify which occurrence to validate. (medium confidence)
     fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
-        for seg in ctx.find_segments("COM") {
-            if let Some(elem) = seg.elements.first() {
-                if let Some(value) = elem.first() {
-                    if value.starts_with('+') && value.chars().skip(1).all(|c| c.is_ascii_digit()) {
-                        return ConditionResult::True;
-                    }
-                }
-            }
-        }
-        ConditionResult::False
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [942] Format: n1-n2-n1-n3
     fn evaluate_942(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: n1-n2-n1-n3 — Artikelnummer dash-separated digit segment pattern
-        let segs = ctx.find_segments("PIA");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_artikel_pattern(val, &[1, 2, 1, 3]),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: n1-n2-n1-n3")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Marktlokations-ID — 11 digits with Luhn check digit
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Zählpunktbezeichnung — 33 alphanumeric characters
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z17");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [960] Format: Netzlokations-ID
-    // REVIEW: Netzlokations-ID is associated with LOC+Z18 (Netzlokation qualifier). The Netzlokations-ID shares the same 11-digit structure with Luhn check as the Marktlokations-ID. No dedicated validate_netzlokation_id helper exists, so validate_malo_id is used as the structurally equivalent validator. (medium confidence)
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Netzlokations-ID — 11 digits with Luhn check digit (same format as MaLo-ID)
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z18");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [962] Format: max. 6 Vorkommastellen
-    // REVIEW: Uses validate_max_integer_digits(value, 6) on the PRI price amount value. Standard EDIFACT PRI C509 places DE5118 (price amount) at elements[0][1]. PRI segment structure is not explicitly listed in the provided MIG reference so medium confidence on the element index. (medium confidence)
     fn evaluate_962(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: max. 6 Vorkommastellen — applies to PRI price amount (C509.DE5118 = elements[0][1])
-        let segs = ctx.find_segments("PRI");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_integer_digits(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Vorkommastellen")
     }
 
     /// [2042] Innerhalb dieser LIN-Position muss das PIA+Z02 (Artikel-ID) mindestens ein Mal angegeben werden und kann bis zu drei Mal angegeben werden.
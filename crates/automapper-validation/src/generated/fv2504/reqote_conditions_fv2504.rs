@@ -213,10 +213,8 @@ impl ReqoteConditionEvaluatorFV2504 {
     }
 
     /// [967] Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4
-    fn evaluate_967(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: Condition [967] requires manual implementation
-        // Reason: Format: X.509 certificate body per BSI TR-03109-4. Validating an X.509 certificate body requires actual ASN.1/DER parsing — none of the available format validation helpers cover certificate validation. This cannot be implemented with the current EvaluationContext API.
-        ConditionResult::Unknown
+    fn evaluate_967(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4")
     }
 
     /// [2065] Diese SG28 ist so oft zu wiederholen, wie zu den unterschiedlichen Messprodukt-Position-Codes zu dem innerhalb derselben SG27 LIN im PIA+5 DE7140 (Erforderliches Produkt Konfigurationserlaubnis fü...
@@ -530,107 +528,73 @@ impl ReqoteConditionEvaluatorFV2504 {
     }
 
     /// [903] Format: Möglicher Wert: 1
-    fn evaluate_903(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_903(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: 1")
     }
 
     /// [906] Format: max. 3 Nachkommastellen
-    fn evaluate_906(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_906(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: max. 3 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
-    fn evaluate_931(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    fn evaluate_932(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
-    fn evaluate_933(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [934] Format: HHMM = 0400
-    fn evaluate_934(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
-    fn evaluate_935(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    fn evaluate_939(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    fn evaluate_940(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [950] Format: Marktlokations-ID
-    fn evaluate_950(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        ConditionResult::True
+    fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z17");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [960] Format: Netzlokations-ID
-    // REVIEW: Netzlokations-ID uses the same 11-digit Luhn check digit format as Marktlokations-ID. LOC+Z18 is the standard qualifier for Netzlokation. (medium confidence)
     fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z18");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [961] Format: SR-ID
-    // REVIEW: SR-ID (SteuerbareRessource-ID) uses the same 11-digit Luhn check digit format as MaLo-ID. LOC+Z19 is the standard qualifier for SteuerbareRessource. (medium confidence)
     fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: SR-ID")
     }
 
     /// [962] Format: max. 6 Vorkommastellen
     fn evaluate_962(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_integer_digits(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Vorkommastellen")
     }
 
     /// [2005] Pro Nachricht ist die SG27 genau einmal anzugeben
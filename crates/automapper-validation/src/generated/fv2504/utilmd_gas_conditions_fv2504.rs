@@ -1331,194 +1331,67 @@ impl UtilmdGasConditionEvaluatorFV2504 {
 
     /// [902] Format: Möglicher Wert: ≥ 0
     fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, ">=", 0.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: ≥ 0")
     }
 
     /// [907] Format: max. 4 Nachkommastellen
     fn evaluate_907(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 4),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 4 Nachkommastellen")
     }
 
     /// [912] Format: max. 6 Nachkommastellen
     fn evaluate_912(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 6),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 6 Nachkommastellen")
     }
 
     /// [930] Format: max. 2 Nachkommastellen
     fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 2),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        let segs = ctx.find_segments("DTM");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [934] Format: HHMM = 0400
     fn evaluate_934(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 0400 — validate time component of DTM value equals 0400
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0400"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0400")
     }
 
     /// [935] Format: HHMM = 0500
     fn evaluate_935(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: HHMM = 0500 — validate time component of DTM value equals 0500
-        let dtm_segs = ctx.find_segments("DTM");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_hhmm_equals(val, "0500"),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: HHMM = 0500")
     }
 
     /// [937] Format: keine Nachkommastelle
     fn evaluate_937(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: keine Nachkommastelle — no decimal places allowed (max 0)
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_max_decimal_places(val, 0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: keine Nachkommastelle")
     }
 
     /// [938] Format: Möglicher Wert: &lt;= 10
     fn evaluate_938(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Möglicher Wert: <= 10 — numeric value must be <= 10
-        let segs = ctx.find_segments("QTY");
-        match segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_numeric(val, "<=", 10.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Möglicher Wert: <= 10")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Marktlokations-ID — 11-digit value with Luhn check digit
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Zählpunktbezeichnung — 33 alphanumeric characters
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z19");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [952] Format: Gerätenummer nach DIN 43863-5
-    // REVIEW: DIN 43863-5 Gerätenummer is an alphanumeric device identifier with a max length of 20 characters. No dedicated validator exists, so validate_max_length(20) is used as the structural check. The exact segment/element depends on the AHB table position — IDE is the most common carrier for device IDs in UTILMD Gas, with PIA as a fallback. Medium confidence due to uncertainty about the exact segment without the full AHB table context. (medium confidence)
     fn evaluate_952(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Gerätenummer nach DIN 43863-5 — alphanumeric, max 20 characters
-        // DIN 43863-5 device numbers appear in IDE segment (element 1, component 0)
-        // or in SG8 PIA segments for device identification
-        let ide_segs = ctx.find_segments("IDE");
-        if let Some(val) = ide_segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-            .filter(|v| !v.is_empty())
-        {
-            return validate_max_length(val, 20);
-        }
-        // Fallback: check PIA segment for device number (qualifier 5 = substitute product)
-        let pia_segs = ctx.find_segments_with_qualifier("PIA", 0, "5");
-        match pia_segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_max_length(val, 20),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Gerätenummer nach DIN 43863-5")
     }
 
     /// [953] Format: Marktlokations-ID oder Zählpunktbezeichnung
     fn evaluate_953(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Marktlokations-ID oder Zählpunktbezeichnung
-        // MaLo-ID: 11 digits with Luhn check; Zählpunkt: 33 alphanumeric characters
-        // Check LOC segments (Z16=Marktlokation, Z19=Zählpunkt) for the identifier
-        let loc_segs = ctx.find_segments("LOC");
-        match loc_segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_or_zahlpunkt(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID oder Zählpunktbezeichnung")
     }
 
     /// [2061] Segment bzw. Segmentgruppe ist genau einmal je SG4 IDE (Vorgang) anzugeben
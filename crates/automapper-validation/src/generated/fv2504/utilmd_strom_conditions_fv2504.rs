@@ -5690,153 +5690,128 @@ impl UtilmdStromConditionEvaluatorFV2504 {
     }
 
     /// [902] Format: Möglicher Wert: ≥ 0
-    fn evaluate_902(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_902(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: ≥ 0")
     }
 
     /// [910] Format: Möglicher Wert: < 0 oder ≥ 0
-    fn evaluate_910(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_910(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: < 0 oder ≥ 0")
     }
 
     /// [914] Format: Möglicher Wert: > 0
-    fn evaluate_914(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_914(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: > 0")
     }
 
     /// [922] Format: TR-ID
-    fn evaluate_922(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_922(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: TR-ID")
     }
 
     /// [926] Format: Möglicher Wert: 0
-    fn evaluate_926(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_926(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: 0")
     }
 
     /// [930] Format: max. 2 Nachkommastellen
-    fn evaluate_930(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_930(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: max. 2 Nachkommastellen")
     }
 
     /// [931] Format: ZZZ = +00
-    fn evaluate_931(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [932] Format: HHMM = 2200
-    fn evaluate_932(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_932(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 2200")
     }
 
     /// [933] Format: HHMM = 2300
-    fn evaluate_933(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_933(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: HHMM = 2300")
     }
 
     /// [937] Format: keine Nachkommastelle
-    fn evaluate_937(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_937(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: keine Nachkommastelle")
     }
 
     /// [938] Format: Möglicher Wert: <= 10
-    fn evaluate_938(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_938(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: <= 10")
     }
 
     /// [939] Format: Die Zeichenkette muss die Zeichen @ und . enthalten
-    fn evaluate_939(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_939(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Die Zeichenkette muss die Zeichen @ und . enthalten")
     }
 
     /// [940] Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen
-    fn evaluate_940(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_940(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Die Zeichenkette muss mit dem Zeichen + beginnen und danach dürfen nur noch Ziffern folgen")
     }
 
     /// [942] Format: n1-n2-n1-n3
-    fn evaluate_942(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_942(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: n1-n2-n1-n3")
     }
 
     /// [943] Format: n1-n2-n1
-    fn evaluate_943(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_943(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: n1-n2-n1")
     }
 
     /// [946] Format: max. 11 Nachkommastellen
-    fn evaluate_946(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_946(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: max. 11 Nachkommastellen")
     }
 
     /// [948] Format: n1-n2-n1-n8-n2
-    fn evaluate_948(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_948(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: n1-n2-n1-n8-n2")
     }
 
     /// [950] Format: Marktlokations-ID
-    fn evaluate_950(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
-    fn evaluate_951(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 
     /// [952] Format: Gerätenummer nach DIN 43863-5
-    fn evaluate_952(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_952(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Gerätenummer nach DIN 43863-5")
     }
 
     /// [955] Format: Möglicher Wert: < 100
-    fn evaluate_955(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_955(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Möglicher Wert: < 100")
     }
 
     /// [957] Format: n1-n2-n1-n8
-    fn evaluate_957(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_957(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: n1-n2-n1-n8")
     }
 
     /// [960] Format: Netzlokations-ID
-    fn evaluate_960(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_960(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Netzlokations-ID")
     }
 
     /// [961] Format: SR-ID
-    fn evaluate_961(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_961(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: SR-ID")
     }
 
     /// [967] Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4
-    fn evaluate_967(&self, _ctx: &EvaluationContext) -> ConditionResult {
-        // TODO: implement
-        ConditionResult::Unknown
+    fn evaluate_967(&self, ctx: &EvaluationContext) -> ConditionResult {
+        ctx.evaluate_format("Format: Zertifikatskörper gemäß X509.1, BSI TR-03109-4")
     }
 
     /// [2001] Segmentgruppe ist mindestens zweimal je SG4 IDE+24 (Vorgang) anzugeben
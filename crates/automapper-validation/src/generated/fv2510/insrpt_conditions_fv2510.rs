@@ -281,74 +281,22 @@ impl InsrptConditionEvaluatorFV2510 {
     }
 
     /// [908] Format: Mögliche Werte: 1 bis n
-    // REVIEW: Format condition 'Mögliche Werte: 1 bis n' means the value must be a positive integer (>= 1). In INSRPT, the most likely target is a sequence number element (SEQ DE1050). Implemented using validate_numeric with '>=' 1.0. Medium confidence because the exact segment/element target is not specified in the provided structure reference. (medium confidence)
     fn evaluate_908(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Mögliche Werte: 1 bis n — numeric value must be >= 1 (positive integer)
-        // Applies to a sequence/count element in INSRPT (e.g. SEQ DE1050)
-        let segs = ctx.find_segments("SEQ");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_numeric(val, ">=", 1.0),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Mögliche Werte: 1 bis n")
     }
 
     /// [931] Format: ZZZ = +00
     fn evaluate_931(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: ZZZ = +00 — DTM value must use UTC timezone offset +00
-        // Applies to DTM+137 (Dokumentendatum) in INSRPT
-        let dtm_segs = ctx.find_segments_with_qualifier("DTM", 0, "137");
-        match dtm_segs
-            .first()
-            .and_then(|s| s.elements.first())
-            .and_then(|e| e.get(1))
-        {
-            Some(val) => validate_timezone_utc(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: ZZZ = +00")
     }
 
     /// [950] Format: Marktlokations-ID
     fn evaluate_950(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Marktlokations-ID — 11-digit ID with Luhn check digit
-        // Applies to LOC+Z16 segment (Marktlokation identifier)
-        let segs = ctx.find_segments_with_qualifier("LOC", 0, "Z16");
-        match segs
-            .first()
-            .and_then(|s| s.elements.get(1))
-            .and_then(|e| e.first())
-        {
-            Some(val) => validate_malo_id(val),
-            None => ConditionResult::False, // segment absent → condition not applicable
-        }
+        ctx.evaluate_format("Format: Marktlokations-ID")
     }
 
     /// [951] Format: Zählpunktbezeichnung
-    // REVIEW: Format condition for Zählpunktbezeichnung (meter point designation) — 33 alphanumeric characters. Uses validate_zahlpunkt helper. Without the exact INSRPT segment structure reference, the implementation checks LOC segment element 1 (typical location for identifiers) with a fallback to NAD C082. Confidence is medium because the exact segment path for the Zählpunktbezeichnung in INSRPT is not confirmed without the MIG segment structure reference. (medium confidence)
     fn evaluate_951(&self, ctx: &EvaluationContext) -> ConditionResult {
-        // Format: Zählpunktbezeichnung — 33 alphanumeric characters
-        // Zählpunktbezeichnung is stored in a LOC or reference segment in INSRPT
-        // Try LOC segments (common location for Zählpunkt identifiers)
-        let loc_segs = ctx.find_segments("LOC");
-        for seg in &loc_segs {
-            if let Some(val) = seg.elements.get(1).and_then(|e| e.first()) {
-                if !val.is_empty() {
-                    return validate_zahlpunkt(val);
-                }
-            }
-        }
-        // Fallback: check NAD segment identification (C082)
-        let nad_segs = ctx.find_segments("NAD");
-        for seg in &nad_segs {
-            if let Some(val) = seg.elements.get(1).and_then(|e| e.first()) {
-                if !val.is_empty() && val.len() == 33 {
-                    return validate_zahlpunkt(val);
-                }
-            }
-        }
-        ConditionResult::Unknown
+        ctx.evaluate_format("Format: Zählpunktbezeichnung")
     }
 }
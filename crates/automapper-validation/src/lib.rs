@@ -41,10 +41,10 @@ pub use eval::{
     KonfigurationenProvider, MapExternalProvider, MarketRole, MarketRoleProvider,
     NoOpGroupNavigator, Sector, SectorProvider,
 };
-pub use expr::{ConditionExpr, ConditionParser};
+pub use expr::{ConditionDefinitions, ConditionExpr, ConditionParser};
 pub use validator::{
-    EdifactValidator, ErrorCodes, Severity, ValidationCategory, ValidationIssue, ValidationLevel,
-    ValidationReport,
+    EdifactValidator, ElementPosition, ErrorCodes, Severity, ValidationCategory, ValidationIssue,
+    ValidationLevel, ValidationReport,
 };
 
 // Re-export AHB workflow types for callers
@@ -1,13 +1,17 @@
 //! EDIFACT message validator.
 
+pub mod charset;
 mod codes;
+pub mod format;
+pub mod integrity;
 mod issue;
 mod level;
 mod report;
+pub mod structure;
 pub mod validate;
 
 pub use codes::ErrorCodes;
-pub use issue::{Severity, ValidationCategory, ValidationIssue};
+pub use issue::{ElementPosition, Severity, ValidationCategory, ValidationIssue};
 pub use level::ValidationLevel;
 pub use report::ValidationReport;
 pub use validate::EdifactValidator;
@@ -0,0 +1,109 @@
+//! Character repertoire validation against the declared syntax identifier.
+//!
+//! UNB S001 names the character set of an interchange (UNOA, UNOB, UNOC,
+//! ...). Every data element value must stay within its repertoire, e.g.
+//! UNOA has no lowercase letters and UNOC no `€`.
+
+use edifact_types::SyntaxIdentifier;
+use mig_types::segment::OwnedSegment;
+
+use super::codes::ErrorCodes;
+use super::issue::{Severity, ValidationCategory, ValidationIssue};
+
+/// Check every element value against the character repertoire.
+///
+/// The syntax identifier is taken from a UNB among `segments`, falling back
+/// to `declared` for message segments without their envelope. Without
+/// either, nothing is checked. One issue is reported per offending
+/// component, naming its first invalid character.
+pub fn charset_issues(
+    segments: &[OwnedSegment],
+    declared: Option<SyntaxIdentifier>,
+) -> Vec<ValidationIssue> {
+    let charset = segments
+        .iter()
+        .find(|s| s.is("UNB"))
+        .and_then(|unb| SyntaxIdentifier::from_code(unb.get_component(0, 0)))
+        .or(declared);
+    let Some(charset) = charset else {
+        return Vec::new();
+    };
+
+    let mut issues = Vec::new();
+    for segment in segments {
+        for (e, element) in segment.elements.iter().enumerate() {
+            for (c, value) in element.iter().enumerate() {
+                let Some(invalid) = value.chars().find(|&ch| !charset.contains(ch)) else {
+                    continue;
+                };
+                let component = (element.len() > 1).then_some(c);
+                issues.push(
+                    ValidationIssue::new(
+                        Severity::Error,
+                        ValidationCategory::Format,
+                        ErrorCodes::INVALID_CHARACTER,
+                        format!(
+                            "Character {invalid:?} in {} is not in the {charset} character repertoire",
+                            segment.id
+                        ),
+                    )
+                    .with_field_path(segment.id.clone())
+                    .with_element_position(e, component)
+                    .at_segment(segment)
+                    .with_actual(value.clone())
+                    .with_expected(charset.code()),
+                );
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(id: &str, elements: &[&[&str]], segment_number: u32) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: elements
+                .iter()
+                .map(|e| e.iter().map(|c| c.to_string()).collect())
+                .collect(),
+            segment_number,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_unb_declaration_drives_repertoire() {
+        let segments = vec![
+            seg("UNB", &[&["UNOA", "3"]], 1),
+            seg("UNH", &[&["1"], &["UTILMD", "D"]], 2),
+            seg("NAD", &[&["Z09"], &[], &[], &["Mueller", "Max"]], 3),
+        ];
+        let issues = charset_issues(&segments, None);
+
+        // Both name components contain lowercase letters
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].code, ErrorCodes::INVALID_CHARACTER);
+        assert_eq!(issues[0].segment_position.unwrap().segment_number, 3);
+        let position = issues[0].element_position.unwrap();
+        assert_eq!((position.element, position.component), (3, Some(0)));
+        assert_eq!(issues[0].actual_value.as_deref(), Some("Mueller"));
+        assert_eq!(issues[1].element_position.unwrap().component, Some(1));
+    }
+
+    #[test]
+    fn test_declared_fallback_for_message_segments() {
+        let segments = vec![seg("FTX", &[&["ACB"], &[], &[], &["5 €"]], 7)];
+
+        assert!(charset_issues(&segments, None).is_empty());
+        assert!(charset_issues(&segments, Some(SyntaxIdentifier::Unow)).is_empty());
+
+        let issues = charset_issues(&segments, Some(SyntaxIdentifier::Unoc));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].element_position.unwrap().component, None);
+        assert!(issues[0].message.contains("'€'"));
+    }
+}
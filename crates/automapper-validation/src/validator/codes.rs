@@ -30,6 +30,33 @@ impl ErrorCodes {
     /// Segment group repetitions exceed MaxRep count.
     pub const GROUP_MAX_REP_EXCEEDED: &'static str = "STR006";
 
+    /// UNT segment count does not match the segments in the message.
+    pub const SEGMENT_COUNT_MISMATCH: &'static str = "STR007";
+
+    /// UNT message reference does not match the UNH reference.
+    pub const MESSAGE_REFERENCE_MISMATCH: &'static str = "STR008";
+
+    /// UNZ message count does not match the messages in the interchange.
+    pub const MESSAGE_COUNT_MISMATCH: &'static str = "STR009";
+
+    /// UNZ interchange reference does not match the UNB reference.
+    pub const INTERCHANGE_REFERENCE_MISMATCH: &'static str = "STR010";
+
+    /// UNH without UNT, or UNT without UNH.
+    pub const UNBALANCED_MESSAGE: &'static str = "STR011";
+
+    /// UNB without UNZ, or UNZ without UNB.
+    pub const UNBALANCED_INTERCHANGE: &'static str = "STR012";
+
+    /// UNE message count does not match the messages in the functional group.
+    pub const GROUP_MESSAGE_COUNT_MISMATCH: &'static str = "STR013";
+
+    /// UNE group reference does not match the UNG reference.
+    pub const GROUP_REFERENCE_MISMATCH: &'static str = "STR014";
+
+    /// UNG without UNE, or UNE without UNG.
+    pub const UNBALANCED_GROUP: &'static str = "STR015";
+
     // --- Format validation (FMT001-FMT099) ---
 
     /// Value exceeds maximum allowed length.
@@ -50,6 +77,9 @@ impl ErrorCodes {
     /// A required element is empty.
     pub const REQUIRED_ELEMENT_EMPTY: &'static str = "FMT006";
 
+    /// Value contains a character outside the declared character repertoire.
+    pub const INVALID_CHARACTER: &'static str = "FMT007";
+
     // --- Code validation (COD001-COD099) ---
 
     /// Code value is not in the allowed code list for this element.
@@ -97,12 +127,22 @@ mod tests {
             ErrorCodes::WRONG_SEGMENT_ORDER,
             ErrorCodes::MISSING_MANDATORY_GROUP,
             ErrorCodes::GROUP_MAX_REP_EXCEEDED,
+            ErrorCodes::SEGMENT_COUNT_MISMATCH,
+            ErrorCodes::MESSAGE_REFERENCE_MISMATCH,
+            ErrorCodes::MESSAGE_COUNT_MISMATCH,
+            ErrorCodes::INTERCHANGE_REFERENCE_MISMATCH,
+            ErrorCodes::UNBALANCED_MESSAGE,
+            ErrorCodes::UNBALANCED_INTERCHANGE,
+            ErrorCodes::GROUP_MESSAGE_COUNT_MISMATCH,
+            ErrorCodes::GROUP_REFERENCE_MISMATCH,
+            ErrorCodes::UNBALANCED_GROUP,
             ErrorCodes::VALUE_TOO_LONG,
             ErrorCodes::INVALID_NUMERIC_FORMAT,
             ErrorCodes::INVALID_ALPHANUMERIC_FORMAT,
             ErrorCodes::INVALID_DATE_FORMAT,
             ErrorCodes::VALUE_TOO_SHORT,
             ErrorCodes::REQUIRED_ELEMENT_EMPTY,
+            ErrorCodes::INVALID_CHARACTER,
             ErrorCodes::INVALID_CODE_VALUE,
             ErrorCodes::CODE_NOT_ALLOWED_FOR_PID,
             ErrorCodes::MISSING_REQUIRED_FIELD,
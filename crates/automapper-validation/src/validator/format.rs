@@ -0,0 +1,459 @@
+//! Format validation of data elements against the MIG.
+//!
+//! Every element of every placed segment is checked against the MIG format
+//! of its slot (`an..35`, `n13`, `n..15`, ...), mandatory elements must not
+//! be empty, and DTM values must match their 2379 format qualifier.
+
+use mig_assembly::structure::SegmentPlacement;
+use mig_types::schema::common::{Cardinality, EdifactDataType, EdifactFormat};
+use mig_types::schema::mig::{MigDataElement, MigSegment};
+use mig_types::segment::OwnedSegment;
+
+use super::codes::ErrorCodes;
+use super::issue::{Severity, ValidationCategory, ValidationIssue};
+
+/// Check all placed segments against the formats of their MIG slots.
+///
+/// `placements` comes from [`mig_assembly::structure::place_segments`] and
+/// has one entry per segment; unplaced segments are skipped (they are
+/// already reported as structure issues).
+pub fn format_issues(
+    segments: &[OwnedSegment],
+    placements: &[Option<SegmentPlacement<'_>>],
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for (segment, placement) in segments.iter().zip(placements) {
+        if let Some(placement) = placement {
+            check_segment(segment, placement, &mut issues);
+        }
+    }
+    issues
+}
+
+fn check_segment(
+    segment: &OwnedSegment,
+    placement: &SegmentPlacement<'_>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let definition: &MigSegment = placement.definition;
+    let segment_path = if placement.group_path.is_empty() {
+        definition.id.clone()
+    } else {
+        format!("{}/{}", placement.group_path, definition.id)
+    };
+
+    for de in &definition.data_elements {
+        let value = segment.get_component(de.position, 0);
+        let path = format!("{segment_path}/{}", de.id);
+        check_element(segment, de, value, true, &path, (de.position, None), issues);
+    }
+
+    for composite in &definition.composites {
+        let present = segment
+            .elements
+            .get(composite.position)
+            .is_some_and(|components| components.iter().any(|c| !c.is_empty()));
+        let composite_required = is_required(&composite.status_spec, &composite.status_std);
+        for de in &composite.data_elements {
+            let value = segment.get_component(composite.position, de.position);
+            let path = format!("{segment_path}/{}/{}", composite.id, de.id);
+            check_element(
+                segment,
+                de,
+                value,
+                present || composite_required,
+                &path,
+                (composite.position, Some(de.position)),
+                issues,
+            );
+        }
+    }
+
+    if definition.id.eq_ignore_ascii_case("DTM") {
+        check_dtm(segment, &segment_path, issues);
+    }
+}
+
+/// Check one data element value. `parent_present` is false for components
+/// of an absent optional composite, which are then not required.
+/// `position` is the element and component index of the value.
+fn check_element(
+    segment: &OwnedSegment,
+    de: &MigDataElement,
+    value: &str,
+    parent_present: bool,
+    path: &str,
+    position: (usize, Option<usize>),
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if value.is_empty() {
+        if parent_present && is_required(&de.status_spec, &de.status_std) {
+            issues.push(
+                format_issue(
+                    segment,
+                    ErrorCodes::REQUIRED_ELEMENT_EMPTY,
+                    format!("Mandatory data element {path} ({}) is empty", de.name),
+                    path,
+                    position,
+                )
+                .with_actual(""),
+            );
+        }
+        return;
+    }
+
+    let Some(format_str) = de.format_spec.as_deref().or(de.format_std.as_deref()) else {
+        return;
+    };
+    let Some(format) = EdifactFormat::parse(format_str) else {
+        return;
+    };
+    if let Err((code, reason)) = check_value_format(value, &format) {
+        issues.push(
+            format_issue(
+                segment,
+                code,
+                format!("Data element {path} ({}) {reason}", de.name),
+                path,
+                position,
+            )
+            .with_actual(value)
+            .with_expected(format_str),
+        );
+    }
+}
+
+/// Check a value against an EDIFACT format.
+///
+/// Numeric values may carry a leading minus sign and one decimal mark
+/// (`.` or `,` — the UNA decimal notation is not known at this point);
+/// neither counts towards the length. Returns the error code and a short
+/// reason on failure.
+pub fn check_value_format(
+    value: &str,
+    format: &EdifactFormat,
+) -> Result<(), (&'static str, String)> {
+    let length = match format.data_type {
+        EdifactDataType::Numeric => {
+            let unsigned = value.strip_prefix('-').unwrap_or(value);
+            let mut parts = unsigned.splitn(2, ['.', ',']);
+            let integer = parts.next().unwrap_or_default();
+            let fraction = parts.next();
+            let digits_only = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+            if !digits_only(integer) || fraction.is_some_and(|f| !digits_only(f)) {
+                return Err((
+                    ErrorCodes::INVALID_NUMERIC_FORMAT,
+                    format!("must be numeric, found '{value}'"),
+                ));
+            }
+            integer.len() + fraction.map_or(0, str::len)
+        }
+        EdifactDataType::Alphabetic => {
+            if value.chars().any(|c| c.is_ascii_digit()) {
+                return Err((
+                    ErrorCodes::INVALID_ALPHANUMERIC_FORMAT,
+                    format!("must not contain digits, found '{value}'"),
+                ));
+            }
+            value.chars().count()
+        }
+        EdifactDataType::Alphanumeric => value.chars().count(),
+    };
+
+    if length > format.max_length {
+        return Err((
+            ErrorCodes::VALUE_TOO_LONG,
+            format!(
+                "exceeds maximum length {} (length {length})",
+                format.max_length
+            ),
+        ));
+    }
+    if let Some(min) = format.min_length {
+        if length < min {
+            return Err((
+                ErrorCodes::VALUE_TOO_SHORT,
+                format!("requires exactly {min} characters (length {length})"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a DTM date/time value (C507/2380) against its format qualifier
+/// (C507/2379).
+fn check_dtm(segment: &OwnedSegment, segment_path: &str, issues: &mut Vec<ValidationIssue>) {
+    let value = segment.get_component(0, 1);
+    let format_code = segment.get_component(0, 2);
+    if value.is_empty() || format_code.is_empty() {
+        return;
+    }
+    let Some((pattern, tokens)) = dtm_format(format_code) else {
+        return;
+    };
+    if !matches_dtm(value, tokens) {
+        let path = format!("{segment_path}/C507/2380");
+        issues.push(
+            format_issue(
+                segment,
+                ErrorCodes::INVALID_DATE_FORMAT,
+                format!(
+                    "Date/time {path} '{value}' does not match format {format_code} ({pattern})"
+                ),
+                &path,
+                (0, Some(1)),
+            )
+            .with_actual(value)
+            .with_expected(format!("{pattern} ({format_code})")),
+        );
+    }
+}
+
+/// One field of a 2379 date/time pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DtmToken {
+    /// CCYY
+    Year,
+    /// YY
+    ShortYear,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// ZZZ: signed two-digit UTC offset as used by BDEW (e.g. `+00`).
+    Offset,
+    /// `-` between the two halves of a period.
+    Dash,
+}
+
+/// Pattern and tokens for the 2379 format qualifiers in use.
+fn dtm_format(format_code: &str) -> Option<(&'static str, &'static [DtmToken])> {
+    use DtmToken::*;
+    Some(match format_code {
+        "101" => ("YYMMDD", &[ShortYear, Month, Day]),
+        "102" => ("CCYYMMDD", &[Year, Month, Day]),
+        "106" => ("MMDD", &[Month, Day]),
+        "201" => ("YYMMDDHHMM", &[ShortYear, Month, Day, Hour, Minute]),
+        "202" => (
+            "YYMMDDHHMMSS",
+            &[ShortYear, Month, Day, Hour, Minute, Second],
+        ),
+        "203" => ("CCYYMMDDHHMM", &[Year, Month, Day, Hour, Minute]),
+        "204" => ("CCYYMMDDHHMMSS", &[Year, Month, Day, Hour, Minute, Second]),
+        "303" => ("CCYYMMDDHHMMZZZ", &[Year, Month, Day, Hour, Minute, Offset]),
+        "304" => (
+            "CCYYMMDDHHMMSSZZZ",
+            &[Year, Month, Day, Hour, Minute, Second, Offset],
+        ),
+        "401" => ("HHMM", &[Hour, Minute]),
+        "402" => ("HHMMSS", &[Hour, Minute, Second]),
+        "602" => ("CCYY", &[Year]),
+        "609" => ("YYMM", &[ShortYear, Month]),
+        "610" => ("CCYYMM", &[Year, Month]),
+        "718" => (
+            "CCYYMMDD-CCYYMMDD",
+            &[Year, Month, Day, Dash, Year, Month, Day],
+        ),
+        _ => return None,
+    })
+}
+
+/// Match a value against 2379 pattern tokens, including calendar checks.
+fn matches_dtm(value: &str, tokens: &[DtmToken]) -> bool {
+    if let Some(dash) = tokens.iter().position(|t| *t == DtmToken::Dash) {
+        return value.split_once('-').is_some_and(|(from, to)| {
+            matches_dtm(from, &tokens[..dash]) && matches_dtm(to, &tokens[dash + 1..])
+        });
+    }
+
+    let mut rest = value;
+    let (mut year, mut month, mut day) = (2000, None, None);
+    for token in tokens {
+        let width = match token {
+            DtmToken::Year => 4,
+            DtmToken::Offset => 3,
+            _ => 2,
+        };
+        if rest.len() < width || !rest.is_char_boundary(width) {
+            return false;
+        }
+        let (part, tail) = rest.split_at(width);
+        rest = tail;
+
+        if *token == DtmToken::Offset {
+            let bytes = part.as_bytes();
+            if !matches!(bytes[0], b'+' | b'-') || !bytes[1..].iter().all(u8::is_ascii_digit) {
+                return false;
+            }
+            continue;
+        }
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        let n: u32 = part.parse().unwrap_or(0);
+        let valid = match token {
+            DtmToken::Year => {
+                year = n;
+                true
+            }
+            DtmToken::ShortYear => {
+                year = 2000 + n;
+                true
+            }
+            DtmToken::Month => {
+                month = Some(n);
+                (1..=12).contains(&n)
+            }
+            DtmToken::Day => {
+                day = Some(n);
+                n >= 1
+            }
+            DtmToken::Hour => n <= 23,
+            DtmToken::Minute | DtmToken::Second => n <= 59,
+            DtmToken::Offset | DtmToken::Dash => false,
+        };
+        if !valid {
+            return false;
+        }
+    }
+
+    rest.is_empty()
+        && match (month, day) {
+            (Some(m), Some(d)) => d <= days_in_month(year, m),
+            _ => true,
+        }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn is_required(status_spec: &Option<String>, status_std: &Option<String>) -> bool {
+    let status = status_spec
+        .as_deref()
+        .or(status_std.as_deref())
+        .unwrap_or("C");
+    Cardinality::from_status(status).is_required()
+}
+
+fn format_issue(
+    segment: &OwnedSegment,
+    code: &str,
+    message: String,
+    path: &str,
+    (element, component): (usize, Option<usize>),
+) -> ValidationIssue {
+    ValidationIssue::new(Severity::Error, ValidationCategory::Format, code, message)
+        .with_field_path(path)
+        .with_element_position(element, component)
+        .at_segment(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt(s: &str) -> EdifactFormat {
+        EdifactFormat::parse(s).unwrap()
+    }
+
+    fn code_of(value: &str, format: &str) -> Option<&'static str> {
+        check_value_format(value, &fmt(format))
+            .err()
+            .map(|(c, _)| c)
+    }
+
+    #[test]
+    fn test_alphanumeric_length() {
+        assert_eq!(code_of("ABC", "an..3"), None);
+        assert_eq!(code_of("ABCD", "an..3"), Some(ErrorCodes::VALUE_TOO_LONG));
+        assert_eq!(code_of("ÄÖÜ", "an..3"), None, "length counts characters");
+    }
+
+    #[test]
+    fn test_fixed_length_too_short() {
+        assert_eq!(code_of("1234567890123", "n13"), None);
+        assert_eq!(
+            code_of("123456789012", "n13"),
+            Some(ErrorCodes::VALUE_TOO_SHORT)
+        );
+    }
+
+    #[test]
+    fn test_numeric_sign_and_decimal_mark_do_not_count() {
+        assert_eq!(code_of("-12.345", "n..5"), None);
+        assert_eq!(code_of("12,345", "n..5"), None);
+        assert_eq!(code_of("123.456", "n..5"), Some(ErrorCodes::VALUE_TOO_LONG));
+    }
+
+    #[test]
+    fn test_numeric_rejects_non_digits() {
+        for value in ["12a", "1.2.3", ".5", "5.", "+5", "-", "1 2"] {
+            assert_eq!(
+                code_of(value, "n..15"),
+                Some(ErrorCodes::INVALID_NUMERIC_FORMAT),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_alphabetic_rejects_digits() {
+        assert_eq!(code_of("ABC", "a3"), None);
+        assert_eq!(
+            code_of("AB1", "a3"),
+            Some(ErrorCodes::INVALID_ALPHANUMERIC_FORMAT)
+        );
+    }
+
+    #[test]
+    fn test_dtm_formats() {
+        let ok = |value: &str, code: &str| matches_dtm(value, dtm_format(code).unwrap().1);
+        assert!(ok("20250131", "102"));
+        assert!(!ok("20250231", "102"));
+        assert!(ok("20240229", "102"));
+        assert!(!ok("20250229", "102"));
+        assert!(ok("202501312359", "203"));
+        assert!(!ok("202501312460", "203"));
+        assert!(ok("202501010000+00", "303"));
+        assert!(!ok("202501010000", "303"));
+        assert!(ok("2025", "602"));
+        assert!(ok("202512", "610"));
+        assert!(!ok("202513", "610"));
+        assert!(ok("1231", "106"));
+        assert!(ok("0615", "401"));
+        assert!(ok("20250101-20251231", "718"));
+        assert!(!ok("20250101", "718"));
+        assert!(dtm_format("999").is_none());
+    }
+
+    #[test]
+    fn test_dtm_issue_has_element_path() {
+        let segment = OwnedSegment {
+            id: "DTM".to_string(),
+            elements: vec![vec![
+                "137".to_string(),
+                "2025013".to_string(),
+                "102".to_string(),
+            ]],
+            segment_number: 4,
+            location: None,
+        };
+        let mut issues = Vec::new();
+        check_dtm(&segment, "SG4/DTM", &mut issues);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, ErrorCodes::INVALID_DATE_FORMAT);
+        assert_eq!(issues[0].field_path.as_deref(), Some("SG4/DTM/C507/2380"));
+        assert_eq!(issues[0].actual_value.as_deref(), Some("2025013"));
+        assert_eq!(issues[0].segment_position.unwrap().segment_number, 4);
+        let position = issues[0].element_position.unwrap();
+        assert_eq!((position.element, position.component), (0, Some(1)));
+    }
+}
@@ -0,0 +1,255 @@
+//! Syntax integrity checks on interchange control segments.
+//!
+//! The checks themselves live in [`edifact_parser::IntegrityChecker`], which
+//! the streaming parser also uses to report errors to its handler; this
+//! module runs it over pre-parsed segments and turns each
+//! [`IntegrityError`] into an STR-coded [`ValidationIssue`].
+
+use edifact_parser::{IntegrityChecker, IntegrityError};
+use edifact_types::RawSegment;
+use mig_types::segment::OwnedSegment;
+
+use super::codes::ErrorCodes;
+use super::issue::{Severity, ValidationCategory, ValidationIssue};
+
+/// Returns the STR error code for an integrity error.
+pub fn integrity_error_code(error: &IntegrityError) -> &'static str {
+    match error {
+        IntegrityError::SegmentCountMismatch { .. } => ErrorCodes::SEGMENT_COUNT_MISMATCH,
+        IntegrityError::MessageReferenceMismatch { .. } => ErrorCodes::MESSAGE_REFERENCE_MISMATCH,
+        IntegrityError::MessageCountMismatch { .. } => ErrorCodes::MESSAGE_COUNT_MISMATCH,
+        IntegrityError::InterchangeReferenceMismatch { .. } => {
+            ErrorCodes::INTERCHANGE_REFERENCE_MISMATCH
+        }
+        IntegrityError::GroupMessageCountMismatch { .. } => {
+            ErrorCodes::GROUP_MESSAGE_COUNT_MISMATCH
+        }
+        IntegrityError::GroupReferenceMismatch { .. } => ErrorCodes::GROUP_REFERENCE_MISMATCH,
+        IntegrityError::MissingUnt { .. } | IntegrityError::UnexpectedUnt { .. } => {
+            ErrorCodes::UNBALANCED_MESSAGE
+        }
+        IntegrityError::MissingUne { .. } | IntegrityError::UnexpectedUne { .. } => {
+            ErrorCodes::UNBALANCED_GROUP
+        }
+        IntegrityError::MissingUnz { .. } | IntegrityError::UnexpectedUnz { .. } => {
+            ErrorCodes::UNBALANCED_INTERCHANGE
+        }
+    }
+}
+
+/// Convert an integrity error into a validation issue.
+///
+/// Count and reference mismatches carry the declared value as actual value
+/// and the value derived from the interchange as expected value.
+pub fn integrity_issue(error: &IntegrityError) -> ValidationIssue {
+    let (message, actual, expected) = match error {
+        IntegrityError::SegmentCountMismatch {
+            declared, actual, ..
+        } => (
+            format!("UNT segment count {declared} does not match the {actual} segments of the message"),
+            Some(declared.clone()),
+            Some(actual.to_string()),
+        ),
+        IntegrityError::MessageReferenceMismatch { unh, unt, .. } => (
+            format!("UNT reference '{unt}' does not match UNH reference '{unh}'"),
+            Some(unt.clone()),
+            Some(unh.clone()),
+        ),
+        IntegrityError::MessageCountMismatch {
+            declared, actual, ..
+        } => (
+            format!("UNZ message count {declared} does not match the {actual} messages of the interchange"),
+            Some(declared.clone()),
+            Some(actual.to_string()),
+        ),
+        IntegrityError::InterchangeReferenceMismatch { unb, unz, .. } => (
+            format!("UNZ reference '{unz}' does not match UNB reference '{unb}'"),
+            Some(unz.clone()),
+            Some(unb.clone()),
+        ),
+        IntegrityError::GroupMessageCountMismatch {
+            declared, actual, ..
+        } => (
+            format!("UNE message count {declared} does not match the {actual} messages of the functional group"),
+            Some(declared.clone()),
+            Some(actual.to_string()),
+        ),
+        IntegrityError::GroupReferenceMismatch { ung, une, .. } => (
+            format!("UNE reference '{une}' does not match UNG reference '{ung}'"),
+            Some(une.clone()),
+            Some(ung.clone()),
+        ),
+        IntegrityError::MissingUnt { reference, .. } => (
+            format!("Message '{reference}' is not terminated by UNT"),
+            None,
+            None,
+        ),
+        IntegrityError::UnexpectedUnt { .. } => {
+            ("UNT without preceding UNH".to_string(), None, None)
+        }
+        IntegrityError::MissingUne { reference, .. } => (
+            format!("Functional group '{reference}' is not terminated by UNE"),
+            None,
+            None,
+        ),
+        IntegrityError::UnexpectedUne { .. } => {
+            ("UNE without preceding UNG".to_string(), None, None)
+        }
+        IntegrityError::MissingUnz { reference, .. } => (
+            format!("Interchange '{reference}' is not terminated by UNZ"),
+            None,
+            None,
+        ),
+        IntegrityError::UnexpectedUnz { .. } => {
+            ("UNZ without preceding UNB".to_string(), None, None)
+        }
+    };
+
+    let mut issue = ValidationIssue::new(
+        Severity::Error,
+        ValidationCategory::Structure,
+        integrity_error_code(error),
+        message,
+    )
+    .with_position(error.position());
+    // Counts are the first data element of UNT/UNE/UNZ, references the second
+    match error {
+        IntegrityError::SegmentCountMismatch { .. }
+        | IntegrityError::MessageCountMismatch { .. }
+        | IntegrityError::GroupMessageCountMismatch { .. } => {
+            issue = issue.with_element_position(0, None);
+        }
+        IntegrityError::MessageReferenceMismatch { .. }
+        | IntegrityError::InterchangeReferenceMismatch { .. }
+        | IntegrityError::GroupReferenceMismatch { .. } => {
+            issue = issue.with_element_position(1, None);
+        }
+        _ => {}
+    }
+    issue.actual_value = actual;
+    issue.expected_value = expected;
+    issue
+}
+
+/// Check UNH/UNT, UNG/UNE and UNB/UNZ control information of pre-parsed
+/// segments.
+///
+/// `segments` may be a single message (UNH to UNT) or a whole interchange.
+/// Positions use the segments' own numbers; message numbers are counted
+/// from the UNH segments, and byte offsets are unknown and left at 0.
+pub fn integrity_issues(segments: &[OwnedSegment]) -> Vec<ValidationIssue> {
+    let mut checker = IntegrityChecker::new();
+    let mut errors = Vec::new();
+    let mut message_number = 0;
+
+    for segment in segments {
+        if segment.is("UNH") {
+            message_number += 1;
+        }
+        let position = edifact_types::SegmentPosition::new(
+            segment.segment_number,
+            0,
+            if ["UNB", "UNG", "UNE", "UNZ"].iter().any(|id| segment.is(id)) {
+                0
+            } else {
+                message_number
+            },
+        );
+        let elements = segment
+            .elements
+            .iter()
+            .map(|e| e.iter().map(String::as_str).collect())
+            .collect();
+        errors.extend(checker.check_segment(&RawSegment::new(&segment.id, elements, position)));
+    }
+    errors.extend(checker.finish());
+
+    errors.iter().map(integrity_issue).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(id: &str, elements: &[&str], segment_number: u32) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: elements.iter().map(|e| vec![e.to_string()]).collect(),
+            segment_number,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_consistent_message_has_no_issues() {
+        let segments = vec![
+            seg("UNH", &["1", "UTILMD"], 2),
+            seg("BGM", &["E01"], 3),
+            seg("UNT", &["3", "1"], 4),
+        ];
+        assert!(integrity_issues(&segments).is_empty());
+    }
+
+    #[test]
+    fn test_unt_mismatches_become_structure_issues() {
+        let segments = vec![
+            seg("UNH", &["1", "UTILMD"], 2),
+            seg("BGM", &["E01"], 3),
+            seg("UNT", &["5", "2"], 4),
+        ];
+        let issues = integrity_issues(&segments);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].code, ErrorCodes::SEGMENT_COUNT_MISMATCH);
+        assert_eq!(issues[0].category, ValidationCategory::Structure);
+        assert_eq!(issues[0].actual_value.as_deref(), Some("5"));
+        assert_eq!(issues[0].expected_value.as_deref(), Some("3"));
+        let position = issues[0].segment_position.unwrap();
+        assert_eq!(position.segment_number, 4);
+        assert_eq!(position.message_number, 1);
+        assert_eq!(issues[1].code, ErrorCodes::MESSAGE_REFERENCE_MISMATCH);
+        assert_eq!(issues[1].element_position.unwrap().element, 1);
+    }
+
+    #[test]
+    fn test_interchange_issues() {
+        let segments = vec![
+            seg("UNB", &["UNOC", "S", "R", "250101", "REF1"], 1),
+            seg("UNH", &["1"], 2),
+            seg("UNT", &["2", "1"], 3),
+            seg("UNH", &["2"], 4),
+            seg("UNZ", &["1", "REF2"], 5),
+        ];
+        let codes: Vec<String> = integrity_issues(&segments)
+            .into_iter()
+            .map(|i| i.code)
+            .collect();
+
+        assert_eq!(
+            codes,
+            [
+                ErrorCodes::UNBALANCED_MESSAGE,
+                ErrorCodes::MESSAGE_COUNT_MISMATCH,
+                ErrorCodes::INTERCHANGE_REFERENCE_MISMATCH,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_issues() {
+        let segments = vec![
+            seg("UNB", &["UNOC", "S", "R", "250101", "REF1"], 1),
+            seg("UNG", &["UTILMD", "S", "R", "250101", "G1"], 2),
+            seg("UNH", &["1"], 3),
+            seg("UNT", &["2", "1"], 4),
+            seg("UNE", &["2", "G1"], 5),
+            seg("UNZ", &["1", "REF1"], 6),
+        ];
+        let issues = integrity_issues(&segments);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, ErrorCodes::GROUP_MESSAGE_COUNT_MISMATCH);
+        assert_eq!(issues[0].expected_value.as_deref(), Some("1"));
+        assert_eq!(issues[0].segment_position.unwrap().message_number, 0);
+    }
+}
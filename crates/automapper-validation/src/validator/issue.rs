@@ -1,5 +1,6 @@
 //! Validation issue types.
 
+use mig_types::segment::{OwnedSegment, SourceSpan};
 use serde::{Deserialize, Serialize};
 
 /// Severity level of a validation issue.
@@ -59,6 +60,28 @@ pub struct SegmentPosition {
     pub byte_offset: usize,
     /// 1-based message number within the interchange.
     pub message_number: u32,
+    /// 1-based line of the segment in the input (0 if unknown).
+    #[serde(default)]
+    pub line: u32,
+    /// 1-based column, in bytes, of the segment within its line (0 if
+    /// unknown).
+    #[serde(default)]
+    pub column: u32,
+}
+
+impl SegmentPosition {
+    /// The position of an input segment. Everything but the segment number
+    /// is 0 if the segment carries no location.
+    pub fn of(segment: &OwnedSegment) -> Self {
+        let location = segment.location.as_ref();
+        Self {
+            segment_number: segment.segment_number,
+            byte_offset: location.map_or(0, |l| l.byte_offset),
+            message_number: location.map_or(0, |l| l.message_number),
+            line: location.map_or(0, |l| l.line),
+            column: location.map_or(0, |l| l.column),
+        }
+    }
 }
 
 impl From<edifact_types::SegmentPosition> for SegmentPosition {
@@ -67,10 +90,24 @@ impl From<edifact_types::SegmentPosition> for SegmentPosition {
             segment_number: pos.segment_number,
             byte_offset: pos.byte_offset,
             message_number: pos.message_number,
+            line: pos.line,
+            column: pos.column,
         }
     }
 }
 
+/// Position of a data element within its segment.
+///
+/// Indices are 0-based like `OwnedSegment::elements`; `component` is `None`
+/// for a stand-alone data element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ElementPosition {
+    /// Index of the stand-alone or composite data element.
+    pub element: usize,
+    /// Index of the component within a composite data element.
+    pub component: Option<usize>,
+}
+
 /// A single validation issue found in an EDIFACT message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationIssue {
@@ -92,6 +129,15 @@ pub struct ValidationIssue {
     /// Field path within the segment (e.g., "SG2/NAD/C082/3039").
     pub field_path: Option<String>,
 
+    /// Position of the offending data element within the segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_position: Option<ElementPosition>,
+
+    /// Byte span in the input of the offending data element or component,
+    /// or of the whole segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+
     /// The AHB rule that triggered this issue (e.g., "Muss [182] ∧ [152]").
     pub rule: Option<String>,
 
@@ -123,6 +169,8 @@ impl ValidationIssue {
             message: message.into(),
             segment_position: None,
             field_path: None,
+            element_position: None,
+            span: None,
             rule: None,
             actual_value: None,
             expected_value: None,
@@ -142,6 +190,27 @@ impl ValidationIssue {
         self
     }
 
+    /// Builder: set the data element position within the segment.
+    pub fn with_element_position(mut self, element: usize, component: Option<usize>) -> Self {
+        self.element_position = Some(ElementPosition { element, component });
+        self
+    }
+
+    /// Builder: set the position, and the span of the data element set by
+    /// [`with_element_position`](Self::with_element_position) (or of the
+    /// whole segment), from an input segment.
+    pub fn at_segment(mut self, segment: &OwnedSegment) -> Self {
+        self.segment_position = Some(SegmentPosition::of(segment));
+        self.span = segment
+            .location
+            .as_ref()
+            .and_then(|location| match self.element_position {
+                Some(pos) => location.element_span(pos.element, pos.component),
+                None => Some(location.span),
+            });
+        self
+    }
+
     /// Builder: set the AHB rule.
     pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
         self.rule = Some(rule.into());
@@ -184,11 +253,19 @@ impl std::fmt::Display for ValidationIssue {
             write!(f, " at {path}")?;
         }
         if let Some(ref pos) = self.segment_position {
-            write!(
-                f,
-                " (segment #{}, byte {})",
-                pos.segment_number, pos.byte_offset
-            )?;
+            if pos.line > 0 {
+                write!(
+                    f,
+                    " (segment #{}, line {}, column {})",
+                    pos.segment_number, pos.line, pos.column
+                )?;
+            } else {
+                write!(
+                    f,
+                    " (segment #{}, byte {})",
+                    pos.segment_number, pos.byte_offset
+                )?;
+            }
         }
         Ok(())
     }
@@ -218,6 +295,8 @@ mod tests {
             segment_number: 5,
             byte_offset: 234,
             message_number: 1,
+            line: 0,
+            column: 0,
         });
 
         assert!(issue.is_error());
@@ -290,6 +369,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_element_position_builder_and_serialization() {
+        let issue = ValidationIssue::new(
+            Severity::Error,
+            ValidationCategory::Format,
+            "FMT001",
+            "Value too long",
+        );
+        let json = serde_json::to_string(&issue).unwrap();
+        assert!(!json.contains("element_position"));
+
+        let issue = issue.with_element_position(1, Some(2));
+        let json = serde_json::to_string(&issue).unwrap();
+        let deserialized: ValidationIssue = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.element_position,
+            Some(ElementPosition {
+                element: 1,
+                component: Some(2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_at_segment_takes_location_and_element_span() {
+        use mig_types::segment::SegmentLocation;
+
+        let span = |start, end| SourceSpan { start, end };
+        let segment = OwnedSegment {
+            id: "NAD".to_string(),
+            elements: vec![
+                vec!["MS".to_string()],
+                vec!["99".to_string(), "293".to_string()],
+            ],
+            segment_number: 4,
+            location: Some(SegmentLocation {
+                byte_offset: 40,
+                line: 3,
+                column: 1,
+                message_number: 1,
+                span: span(40, 53),
+                components: vec![vec![span(44, 46)], vec![span(47, 49), span(50, 53)]],
+            }),
+        };
+        let issue = ValidationIssue::new(
+            Severity::Error,
+            ValidationCategory::Format,
+            "FMT001",
+            "Value too long",
+        );
+
+        let at_component = issue
+            .clone()
+            .with_element_position(1, Some(1))
+            .at_segment(&segment);
+        let pos = at_component.segment_position.unwrap();
+        assert_eq!((pos.byte_offset, pos.line, pos.column), (40, 3, 1));
+        assert_eq!(at_component.span, Some(span(50, 53)));
+        assert!(at_component.to_string().contains("line 3, column 1"));
+
+        let at_element = issue
+            .clone()
+            .with_element_position(1, None)
+            .at_segment(&segment);
+        assert_eq!(at_element.span, Some(span(47, 53)));
+        assert_eq!(issue.at_segment(&segment).span, Some(span(40, 53)));
+    }
+
     #[test]
     fn test_category_display() {
         assert_eq!(format!("{}", ValidationCategory::Structure), "Structure");
@@ -0,0 +1,135 @@
+//! Structure validation against the MIG.
+//!
+//! Structure checks are performed by MIG-guided assembly
+//! ([`mig_assembly::assembler::Assembler::assemble_with_diagnostics`]); this
+//! module turns the resulting [`StructureDiagnostic`]s into STR-coded
+//! [`ValidationIssue`]s.
+
+use mig_assembly::{StructureDiagnostic, StructureDiagnosticKind};
+use mig_types::segment::OwnedSegment;
+
+use super::codes::ErrorCodes;
+use super::issue::{Severity, ValidationCategory, ValidationIssue};
+
+/// Returns the STR error code for a structure diagnostic kind.
+pub fn structure_error_code(kind: StructureDiagnosticKind) -> &'static str {
+    match kind {
+        StructureDiagnosticKind::MissingRequiredSegment => ErrorCodes::MISSING_MANDATORY_SEGMENT,
+        StructureDiagnosticKind::MaxRepetitionsExceeded => ErrorCodes::MAX_REPETITIONS_EXCEEDED,
+        StructureDiagnosticKind::UnexpectedSegment
+        | StructureDiagnosticKind::UnrecognizedQualifier => ErrorCodes::UNEXPECTED_SEGMENT,
+        StructureDiagnosticKind::OutOfOrderSegment => ErrorCodes::WRONG_SEGMENT_ORDER,
+        StructureDiagnosticKind::MissingRequiredGroup => ErrorCodes::MISSING_MANDATORY_GROUP,
+        StructureDiagnosticKind::GroupMaxRepetitionsExceeded => ErrorCodes::GROUP_MAX_REP_EXCEEDED,
+    }
+}
+
+/// Convert a structure diagnostic into a validation issue.
+///
+/// `segments` must be the input the diagnostic was produced from; its
+/// `position` is resolved to that input segment, whose location (if any)
+/// gives the byte offset, line, column and span.
+pub fn structure_issue(
+    diagnostic: &StructureDiagnostic,
+    segments: &[OwnedSegment],
+) -> ValidationIssue {
+    let mut issue = ValidationIssue::new(
+        Severity::Error,
+        ValidationCategory::Structure,
+        structure_error_code(diagnostic.kind),
+        diagnostic.message.clone(),
+    );
+
+    let segment = segments
+        .get(diagnostic.position)
+        .or_else(|| segments.last());
+    if let Some(segment) = segment {
+        issue = issue.at_segment(segment);
+    }
+    if let Some(ref path) = diagnostic.mig_path {
+        issue = issue.with_field_path(path.clone());
+    }
+    if matches!(
+        diagnostic.kind,
+        StructureDiagnosticKind::UnexpectedSegment
+            | StructureDiagnosticKind::OutOfOrderSegment
+            | StructureDiagnosticKind::UnrecognizedQualifier
+    ) && !diagnostic.segment_id.is_empty()
+    {
+        issue.actual_value = Some(diagnostic.segment_id.clone());
+    }
+    issue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(id: &str, segment_number: u32) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: vec![],
+            segment_number,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_every_kind_maps_to_a_structure_code() {
+        let kinds = [
+            StructureDiagnosticKind::UnexpectedSegment,
+            StructureDiagnosticKind::MissingRequiredSegment,
+            StructureDiagnosticKind::MaxRepetitionsExceeded,
+            StructureDiagnosticKind::UnrecognizedQualifier,
+            StructureDiagnosticKind::MissingRequiredGroup,
+            StructureDiagnosticKind::GroupMaxRepetitionsExceeded,
+            StructureDiagnosticKind::OutOfOrderSegment,
+        ];
+        for kind in kinds {
+            assert!(structure_error_code(kind).starts_with("STR"), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_structure_issue_carries_path_and_position() {
+        let segments = vec![seg("UNH", 2), seg("BGM", 3), seg("IDE", 4)];
+        let diagnostic = StructureDiagnostic {
+            kind: StructureDiagnosticKind::MissingRequiredSegment,
+            segment_id: "STS".to_string(),
+            position: 2,
+            message: "Mandatory segment SG4/STS is missing".to_string(),
+            mig_path: Some("SG4/STS".to_string()),
+            location: None,
+            repair: None,
+        };
+
+        let issue = structure_issue(&diagnostic, &segments);
+
+        assert_eq!(issue.code, ErrorCodes::MISSING_MANDATORY_SEGMENT);
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.category, ValidationCategory::Structure);
+        assert_eq!(issue.field_path.as_deref(), Some("SG4/STS"));
+        assert_eq!(issue.segment_position.unwrap().segment_number, 4);
+        assert!(issue.actual_value.is_none());
+    }
+
+    #[test]
+    fn test_unexpected_segment_reports_actual_tag() {
+        let segments = vec![seg("UNH", 1), seg("ZZZ", 2)];
+        let diagnostic = StructureDiagnostic {
+            kind: StructureDiagnosticKind::UnexpectedSegment,
+            segment_id: "ZZZ".to_string(),
+            position: 1,
+            message: "Segment 'ZZZ' at position 1 was not consumed".to_string(),
+            mig_path: None,
+            location: None,
+            repair: None,
+        };
+
+        let issue = structure_issue(&diagnostic, &segments);
+
+        assert_eq!(issue.code, ErrorCodes::UNEXPECTED_SEGMENT);
+        assert_eq!(issue.actual_value.as_deref(), Some("ZZZ"));
+        assert_eq!(issue.segment_position.unwrap().segment_number, 2);
+    }
+}
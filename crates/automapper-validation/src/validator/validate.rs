@@ -6,13 +6,23 @@ use crate::eval::{
     ConditionEvaluator, ConditionExprEvaluator, ConditionResult, EvaluationContext,
     ExternalConditionProvider,
 };
+use crate::expr::ConditionDefinitions;
+use edifact_types::SyntaxIdentifier;
+use mig_assembly::assembler::Assembler;
+use mig_assembly::navigator::AssembledTreeNavigator;
+use mig_assembly::structure::place_segments;
 use mig_types::navigator::GroupNavigator;
+use mig_types::schema::mig::MigSchema;
 use mig_types::segment::OwnedSegment;
 
+use super::charset::charset_issues;
 use super::codes::ErrorCodes;
+use super::format::format_issues;
+use super::integrity::integrity_issues;
 use super::issue::{Severity, ValidationCategory, ValidationIssue};
 use super::level::ValidationLevel;
 use super::report::ValidationReport;
+use super::structure::structure_issue;
 
 /// AHB field definition for validation.
 ///
@@ -110,12 +120,33 @@ pub struct AhbWorkflow {
 /// ```
 pub struct EdifactValidator<E: ConditionEvaluator> {
     evaluator: E,
+    definitions: ConditionDefinitions,
+    syntax_identifier: Option<SyntaxIdentifier>,
 }
 
 impl<E: ConditionEvaluator> EdifactValidator<E> {
     /// Create a new validator with the given condition evaluator.
     pub fn new(evaluator: E) -> Self {
-        Self { evaluator }
+        Self {
+            evaluator,
+            definitions: ConditionDefinitions::default(),
+            syntax_identifier: None,
+        }
+    }
+
+    /// Set the AHB package and Unterbedingung definitions used to resolve
+    /// `[nPa..b]` and `[UBn]` references in AHB status expressions.
+    pub fn with_condition_definitions(mut self, definitions: ConditionDefinitions) -> Self {
+        self.definitions = definitions;
+        self
+    }
+
+    /// Set the syntax identifier from the interchange's UNB, used to check
+    /// the character repertoire when the validated segments do not include
+    /// the UNB themselves.
+    pub fn with_syntax_identifier(mut self, syntax_identifier: SyntaxIdentifier) -> Self {
+        self.syntax_identifier = Some(syntax_identifier);
+        self
     }
 
     /// Validate pre-parsed EDIFACT segments against an AHB workflow.
@@ -141,6 +172,9 @@ impl<E: ConditionEvaluator> EdifactValidator<E> {
             .with_format_version(self.evaluator.format_version())
             .with_pruefidentifikator(&workflow.pruefidentifikator);
 
+        report.add_issues(integrity_issues(segments));
+        report.add_issues(charset_issues(segments, self.syntax_identifier));
+
         let ctx = EvaluationContext::new(&workflow.pruefidentifikator, external, segments);
 
         if matches!(level, ValidationLevel::Conditions | ValidationLevel::Full) {
@@ -167,6 +201,9 @@ impl<E: ConditionEvaluator> EdifactValidator<E> {
             .with_format_version(self.evaluator.format_version())
             .with_pruefidentifikator(&workflow.pruefidentifikator);
 
+        report.add_issues(integrity_issues(segments));
+        report.add_issues(charset_issues(segments, self.syntax_identifier));
+
         let ctx = EvaluationContext::with_navigator(
             &workflow.pruefidentifikator,
             external,
@@ -181,6 +218,59 @@ impl<E: ConditionEvaluator> EdifactValidator<E> {
         report
     }
 
+    /// Validate against the MIG structure and the AHB workflow.
+    ///
+    /// Assembles `segments` against `mig` (typically already filtered for the
+    /// PID) and reports structure issues (STR001–STR006) at every level.
+    /// At `Full` level every element is also checked against the format of
+    /// its MIG slot (FMT001–FMT006). Condition checks then run as in
+    /// [`validate_with_navigator`], using the assembled tree for group
+    /// navigation.
+    ///
+    /// [`validate`] and [`validate_with_navigator`] have no MIG to check
+    /// against and therefore report no MIG structure issues. All three
+    /// report UNH/UNT and UNB/UNZ integrity issues (STR007–STR012) of
+    /// `segments` at every level, as well as characters outside the
+    /// repertoire of the declared syntax identifier (FMT007, see
+    /// [`with_syntax_identifier`](Self::with_syntax_identifier)).
+    pub fn validate_with_mig(
+        &self,
+        segments: &[OwnedSegment],
+        workflow: &AhbWorkflow,
+        external: &dyn ExternalConditionProvider,
+        level: ValidationLevel,
+        mig: &MigSchema,
+    ) -> ValidationReport {
+        let (tree, diagnostics) = Assembler::new(mig).assemble_with_diagnostics(segments);
+        let navigator = AssembledTreeNavigator::new(&tree);
+
+        let mut report = ValidationReport::new(self.evaluator.message_type(), level)
+            .with_format_version(self.evaluator.format_version())
+            .with_pruefidentifikator(&workflow.pruefidentifikator);
+
+        report.add_issues(integrity_issues(segments));
+        report.add_issues(charset_issues(segments, self.syntax_identifier));
+        report.add_issues(diagnostics.iter().map(|d| structure_issue(d, segments)));
+
+        if level == ValidationLevel::Full {
+            let placements = place_segments(mig, &tree, segments);
+            report.add_issues(format_issues(segments, &placements));
+        }
+
+        let ctx = EvaluationContext::with_navigator(
+            &workflow.pruefidentifikator,
+            external,
+            segments,
+            &navigator,
+        );
+
+        if matches!(level, ValidationLevel::Conditions | ValidationLevel::Full) {
+            self.validate_conditions(workflow, &ctx, &mut report);
+        }
+
+        report
+    }
+
     /// Validate AHB conditions for each field in the workflow.
     fn validate_conditions(
         &self,
@@ -188,7 +278,8 @@ impl<E: ConditionEvaluator> EdifactValidator<E> {
         ctx: &EvaluationContext,
         report: &mut ValidationReport,
     ) {
-        let expr_eval = ConditionExprEvaluator::new(&self.evaluator);
+        let expr_eval =
+            ConditionExprEvaluator::new(&self.evaluator).with_definitions(&self.definitions);
 
         for field in &workflow.fields {
             // If the parent group has a conditional status, evaluate it first.
@@ -209,6 +300,15 @@ impl<E: ConditionEvaluator> EdifactValidator<E> {
                 }
             }
 
+            // Format conditions (9xx) in the status are checked against the
+            // values of the field's own data element.
+            let values = field_values(ctx, field);
+            let field_ctx = EvaluationContext {
+                field_values: &values,
+                ..*ctx
+            };
+            let ctx = &field_ctx;
+
             // Evaluate the AHB status condition expression, collecting
             // which specific condition IDs are unknown when the result is Unknown.
             let (condition_result, unknown_ids) =
@@ -519,6 +619,41 @@ fn is_field_present(ctx: &EvaluationContext, field: &AhbFieldRule) -> bool {
     ctx.has_segment(&segment_id)
 }
 
+/// Collect the non-empty values of the data element a field rule points at.
+///
+/// With a navigator, fields inside a segment group are looked up in every
+/// instance of that group; otherwise all segments with the field's tag are
+/// considered.
+fn field_values(ctx: &EvaluationContext, field: &AhbFieldRule) -> Vec<String> {
+    let segment_id = extract_segment_id(&field.segment_path);
+    let el_idx = field.element_index.unwrap_or(0);
+    let comp_idx = field.component_index.unwrap_or(0);
+    let value_of = |seg: &OwnedSegment| {
+        seg.elements
+            .get(el_idx)
+            .and_then(|e| e.get(comp_idx))
+            .filter(|v| !v.is_empty())
+            .cloned()
+    };
+
+    let group_path: Vec<&str> = field
+        .segment_path
+        .split('/')
+        .take_while(|p| p.starts_with("SG"))
+        .collect();
+    match ctx.navigator {
+        Some(nav) if !group_path.is_empty() => (0..nav.group_instance_count(&group_path))
+            .flat_map(|i| nav.find_segments_in_group(&segment_id, &group_path, i))
+            .filter_map(|seg| value_of(&seg))
+            .collect(),
+        _ => ctx
+            .find_segments(&segment_id)
+            .into_iter()
+            .filter_map(value_of)
+            .collect(),
+    }
+}
+
 /// Check if the group variant for a field is absent from the message.
 ///
 /// Uses tree-based logic:
@@ -827,6 +962,82 @@ mod tests {
         assert_eq!(report.by_category(ValidationCategory::Ahb).count(), 0);
     }
 
+    #[test]
+    fn test_validate_reports_integrity_issues_at_structure_level() {
+        let evaluator = MockEvaluator::all_true(&[]);
+        let validator = EdifactValidator::new(evaluator);
+        let external = NoOpExternalProvider;
+
+        let workflow = AhbWorkflow {
+            pruefidentifikator: "11001".to_string(),
+            description: "Test".to_string(),
+            communication_direction: None,
+            fields: vec![],
+        };
+        let segments = vec![
+            OwnedSegment {
+                id: "UNH".to_string(),
+                elements: vec![vec!["1".to_string()]],
+                segment_number: 1,
+                location: None,
+            },
+            OwnedSegment {
+                id: "UNT".to_string(),
+                elements: vec![vec!["9".to_string()], vec!["1".to_string()]],
+                segment_number: 2,
+                location: None,
+            },
+        ];
+
+        let report = validator.validate(&segments, &workflow, &external, ValidationLevel::Structure);
+
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCodes::SEGMENT_COUNT_MISMATCH);
+    }
+
+    #[test]
+    fn test_validate_checks_repertoire_of_configured_syntax_identifier() {
+        let evaluator = MockEvaluator::all_true(&[]);
+        let validator =
+            EdifactValidator::new(evaluator).with_syntax_identifier(SyntaxIdentifier::Unoa);
+        let external = NoOpExternalProvider;
+
+        let workflow = AhbWorkflow {
+            pruefidentifikator: "11001".to_string(),
+            description: "Test".to_string(),
+            communication_direction: None,
+            fields: vec![],
+        };
+        let segments = vec![
+            OwnedSegment {
+                id: "UNH".to_string(),
+                elements: vec![vec!["1".to_string()]],
+                segment_number: 1,
+                location: None,
+            },
+            OwnedSegment {
+                id: "BGM".to_string(),
+                elements: vec![vec!["E01".to_string()], vec!["doc1".to_string()]],
+                segment_number: 2,
+                location: None,
+            },
+            OwnedSegment {
+                id: "UNT".to_string(),
+                elements: vec![vec!["3".to_string()], vec!["1".to_string()]],
+                segment_number: 3,
+                location: None,
+            },
+        ];
+
+        let report = validator.validate(&segments, &workflow, &external, ValidationLevel::Structure);
+
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCodes::INVALID_CHARACTER);
+        assert_eq!(errors[0].element_position.unwrap().element, 1);
+    }
+
     #[test]
     fn test_validate_empty_workflow_no_condition_errors() {
         let evaluator = MockEvaluator::all_true(&[]);
@@ -992,6 +1203,7 @@ mod tests {
                 ],
             ],
             segment_number: 1,
+            location: None,
         };
 
         let workflow = AhbWorkflow {
@@ -1057,11 +1269,13 @@ mod tests {
             id: "NAD".to_string(),
             elements: vec![vec!["MS".to_string()]],
             segment_number: 4,
+            location: None,
         };
         let nad_mr = OwnedSegment {
             id: "NAD".to_string(),
             elements: vec![vec!["MR".to_string()]],
             segment_number: 5,
+            location: None,
         };
 
         let workflow = AhbWorkflow {
@@ -1125,11 +1339,13 @@ mod tests {
             id: "NAD".to_string(),
             elements: vec![vec!["MS".to_string()]],
             segment_number: 4,
+            location: None,
         };
         let nad_mt = OwnedSegment {
             id: "NAD".to_string(),
             elements: vec![vec!["MT".to_string()]], // invalid
             segment_number: 5,
+            location: None,
         };
 
         let workflow = AhbWorkflow {
@@ -1195,26 +1411,31 @@ mod tests {
                 id: "NAD".to_string(),
                 elements: vec![vec!["MS".to_string()]],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".to_string(),
                 elements: vec![vec!["MR".to_string()]],
                 segment_number: 4,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".to_string(),
                 elements: vec![vec!["Z04".to_string()]],
                 segment_number: 20,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".to_string(),
                 elements: vec![vec!["Z09".to_string()]],
                 segment_number: 21,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".to_string(),
                 elements: vec![vec!["MT".to_string()]], // invalid
                 segment_number: 22,
+                location: None,
             },
         ];
 
@@ -1323,6 +1544,7 @@ mod tests {
             id: "NAD".to_string(),
             elements: vec![vec!["MS".to_string()]],
             segment_number: 3,
+            location: None,
         };
 
         let workflow = AhbWorkflow {
@@ -1385,11 +1607,13 @@ mod tests {
                 id: "NAD".to_string(),
                 elements: vec![vec!["MS".to_string()]],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".to_string(),
                 elements: vec![vec!["MR".to_string()]],
                 segment_number: 4,
+                location: None,
             },
         ];
 
@@ -1488,11 +1712,13 @@ mod tests {
                 id: "NAD".into(),
                 elements: vec![vec!["MS".into()]],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
                 elements: vec![vec!["MR".into()]],
                 segment_number: 4,
+                location: None,
             },
         ];
 
@@ -1578,6 +1804,7 @@ mod tests {
             id: "NAD".into(),
             elements: vec![vec!["MS".into()]],
             segment_number: 3,
+            location: None,
         }];
 
         let workflow = AhbWorkflow {
@@ -1633,6 +1860,7 @@ mod tests {
                         id: "NAD".into(),
                         elements: vec![vec!["MS".into()]],
                         segment_number: 3,
+                        location: None,
                     }]
                 } else {
                     vec![]
@@ -1665,6 +1893,7 @@ mod tests {
             id: "NAD".into(),
             elements: vec![vec!["MS".into()]],
             segment_number: 3,
+            location: None,
         }];
 
         let workflow = AhbWorkflow {
@@ -1741,11 +1970,13 @@ mod tests {
                         id: "LOC".into(),
                         elements: vec![vec!["Z16".into()], vec!["DE00012345".into()]],
                         segment_number: 10,
+                        location: None,
                     }],
                     ("NAD", ["SG2"], 0) => vec![OwnedSegment {
                         id: "NAD".into(),
                         elements: vec![vec!["MS".into()]],
                         segment_number: 3,
+                        location: None,
                     }],
                     _ => vec![],
                 }
@@ -1780,11 +2011,13 @@ mod tests {
                 id: "NAD".into(),
                 elements: vec![vec!["MS".into()]],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "LOC".into(),
                 elements: vec![vec!["Z16".into()], vec!["DE00012345".into()]],
                 segment_number: 10,
+                location: None,
             },
         ];
 
@@ -1894,6 +2127,7 @@ mod tests {
                         id: "LOC".into(),
                         elements: vec![vec!["Z16".into()], vec!["DE00012345".into()]],
                         segment_number: 10,
+                        location: None,
                     }]
                 } else {
                     vec![]
@@ -1929,6 +2163,7 @@ mod tests {
             id: "LOC".into(),
             elements: vec![vec!["Z16".into()], vec!["DE00012345".into()]],
             segment_number: 10,
+            location: None,
         }];
 
         let workflow = AhbWorkflow {
@@ -2054,11 +2289,13 @@ mod tests {
                             id: "NAD".into(),
                             elements: vec![vec!["MS".into()]],
                             segment_number: 3,
+                            location: None,
                         },
                         OwnedSegment {
                             id: "NAD".into(),
                             elements: vec![vec!["MT".into()]], // invalid in SG2
                             segment_number: 4,
+                            location: None,
                         },
                     ],
                     ["SG4", "SG12"] => vec![
@@ -2066,11 +2303,13 @@ mod tests {
                             id: "NAD".into(),
                             elements: vec![vec!["Z04".into()]],
                             segment_number: 20,
+                            location: None,
                         },
                         OwnedSegment {
                             id: "NAD".into(),
                             elements: vec![vec!["Z09".into()]],
                             segment_number: 21,
+                            location: None,
                         },
                     ],
                     _ => vec![],
@@ -2161,21 +2400,25 @@ mod tests {
                 id: "NAD".into(),
                 elements: vec![vec!["MS".into()]],
                 segment_number: 3,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
                 elements: vec![vec!["MT".into()]],
                 segment_number: 4,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
                 elements: vec![vec!["Z04".into()]],
                 segment_number: 20,
+                location: None,
             },
             OwnedSegment {
                 id: "NAD".into(),
                 elements: vec![vec!["Z09".into()]],
                 segment_number: 21,
+                location: None,
             },
         ];
 
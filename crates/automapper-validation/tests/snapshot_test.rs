@@ -61,6 +61,7 @@ fn make_segment(id: &str, elements: Vec<Vec<&str>>) -> OwnedSegment {
             .map(|e| e.into_iter().map(|c| c.to_string()).collect())
             .collect(),
         segment_number: 1,
+        location: None,
     }
 }
 
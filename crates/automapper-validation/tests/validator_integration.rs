@@ -363,3 +363,363 @@ fn test_validate_report_metadata() {
     assert_eq!(report.message_type, "UTILMD"); // Message type from evaluator
     assert_eq!(report.pruefidentifikator.as_deref(), Some("11001"));
 }
+
+fn structure_test_mig() -> mig_types::schema::mig::MigSchema {
+    use mig_assembly::test_support::{make_mig_group, make_mig_segment};
+
+    let mut sg2 = make_mig_group("SG2", vec!["NAD"], vec![]);
+    sg2.max_rep_spec = 2;
+    sg2.max_rep_std = 2;
+    mig_types::schema::mig::MigSchema {
+        message_type: "UTILMD".to_string(),
+        variant: Some("Strom".to_string()),
+        version: "S2.1".to_string(),
+        publication_date: String::new(),
+        author: "BDEW".to_string(),
+        format_version: "FV2510".to_string(),
+        source_file: "test".to_string(),
+        segments: ["UNH", "BGM", "UNT"]
+            .into_iter()
+            .map(make_mig_segment)
+            .collect(),
+        segment_groups: vec![sg2, make_mig_group("SG4", vec!["IDE", "STS"], vec![])],
+    }
+}
+
+fn owned(id: &str, value: &str, segment_number: u32) -> mig_types::segment::OwnedSegment {
+    mig_types::segment::OwnedSegment {
+        id: id.to_string(),
+        elements: vec![vec![value.to_string()]],
+        segment_number,
+        location: None,
+    }
+}
+
+#[test]
+fn test_validate_with_mig_structure_level_reports_str_issues() {
+    let validator = EdifactValidator::new(ConfigurableEvaluator::new());
+    let external = NoOpExternalProvider;
+    let workflow = make_workflow(vec![simple_field("NAD", "Partnerrolle", "Muss")]);
+
+    // BGM missing, SG2 repeated three times (MaxRep 2), SG4 without STS
+    let segments = vec![
+        owned("UNH", "1", 2),
+        owned("NAD", "MS", 3),
+        owned("NAD", "MR", 4),
+        owned("NAD", "DP", 5),
+        owned("IDE", "24", 6),
+        owned("UNT", "7", 7),
+    ];
+
+    let report = validator.validate_with_mig(
+        &segments,
+        &workflow,
+        &external,
+        ValidationLevel::Structure,
+        &structure_test_mig(),
+    );
+
+    let codes: Vec<&str> = report.issues.iter().map(|i| i.code.as_str()).collect();
+    assert!(
+        codes.contains(&ErrorCodes::MISSING_MANDATORY_SEGMENT),
+        "{codes:?}"
+    );
+    assert!(
+        codes.contains(&ErrorCodes::GROUP_MAX_REP_EXCEEDED),
+        "{codes:?}"
+    );
+    assert_eq!(report.by_category(ValidationCategory::Ahb).count(), 0);
+    assert!(!report.is_valid());
+
+    let group_overrun = report
+        .issues
+        .iter()
+        .find(|i| i.code == ErrorCodes::GROUP_MAX_REP_EXCEEDED)
+        .unwrap();
+    assert_eq!(group_overrun.field_path.as_deref(), Some("SG2"));
+    assert_eq!(group_overrun.segment_position.unwrap().segment_number, 5);
+
+    let missing_sts = report
+        .issues
+        .iter()
+        .find(|i| i.field_path.as_deref() == Some("SG4/STS"))
+        .expect("SG4/STS should be reported missing");
+    assert_eq!(missing_sts.code, ErrorCodes::MISSING_MANDATORY_SEGMENT);
+    assert_eq!(missing_sts.segment_position.unwrap().segment_number, 6);
+}
+
+#[test]
+fn test_validate_with_mig_clean_message_has_no_structure_issues() {
+    let validator = EdifactValidator::new(ConfigurableEvaluator::new());
+    let external = NoOpExternalProvider;
+    let workflow = make_workflow(vec![]);
+
+    let segments = vec![
+        owned("UNH", "1", 1),
+        owned("BGM", "E01", 2),
+        owned("NAD", "MS", 3),
+        owned("IDE", "24", 4),
+        owned("STS", "7", 5),
+        mig_types::segment::OwnedSegment {
+            id: "UNT".to_string(),
+            elements: vec![vec!["6".to_string()], vec!["1".to_string()]],
+            segment_number: 6,
+            location: None,
+        },
+    ];
+
+    let report = validator.validate_with_mig(
+        &segments,
+        &workflow,
+        &external,
+        ValidationLevel::Full,
+        &structure_test_mig(),
+    );
+
+    assert_eq!(
+        report.by_category(ValidationCategory::Structure).count(),
+        0,
+        "{:?}",
+        report.issues
+    );
+}
+
+fn mig_element(
+    id: &str,
+    position: usize,
+    status: &str,
+    format: &str,
+) -> mig_types::schema::mig::MigDataElement {
+    mig_types::schema::mig::MigDataElement {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: None,
+        status_std: Some(status.to_string()),
+        status_spec: Some(status.to_string()),
+        format_std: Some(format.to_string()),
+        format_spec: None,
+        codes: vec![],
+        position,
+    }
+}
+
+fn format_test_mig() -> mig_types::schema::mig::MigSchema {
+    use mig_assembly::test_support::make_mig_segment;
+    use mig_types::schema::mig::MigComposite;
+
+    let mut bgm = make_mig_segment("BGM");
+    bgm.composites.push(MigComposite {
+        id: "C002".to_string(),
+        name: "Dokumentenname".to_string(),
+        description: None,
+        status_std: Some("M".to_string()),
+        status_spec: Some("M".to_string()),
+        data_elements: vec![mig_element("1001", 0, "M", "an..3")],
+        position: 0,
+    });
+    bgm.data_elements.push(mig_element("1225", 2, "R", "an..3"));
+
+    let mut dtm = make_mig_segment("DTM");
+    dtm.composites.push(MigComposite {
+        id: "C507".to_string(),
+        name: "Datum/Uhrzeit/Zeitspanne".to_string(),
+        description: None,
+        status_std: Some("M".to_string()),
+        status_spec: Some("M".to_string()),
+        data_elements: vec![
+            mig_element("2005", 0, "M", "an..3"),
+            mig_element("2380", 1, "R", "an..35"),
+            mig_element("2379", 2, "R", "an..3"),
+        ],
+        position: 0,
+    });
+
+    let mut qty = make_mig_segment("QTY");
+    qty.composites.push(MigComposite {
+        id: "C186".to_string(),
+        name: "Menge".to_string(),
+        description: None,
+        status_std: Some("M".to_string()),
+        status_spec: Some("M".to_string()),
+        data_elements: vec![
+            mig_element("6063", 0, "M", "an..3"),
+            mig_element("6060", 1, "M", "n..5"),
+        ],
+        position: 0,
+    });
+
+    mig_types::schema::mig::MigSchema {
+        message_type: "UTILMD".to_string(),
+        variant: Some("Strom".to_string()),
+        version: "S2.1".to_string(),
+        publication_date: String::new(),
+        author: "BDEW".to_string(),
+        format_version: "FV2510".to_string(),
+        source_file: "test".to_string(),
+        segments: vec![
+            make_mig_segment("UNH"),
+            bgm,
+            dtm,
+            qty,
+            make_mig_segment("UNT"),
+        ],
+        segment_groups: vec![],
+    }
+}
+
+fn format_test_segments() -> Vec<mig_types::segment::OwnedSegment> {
+    let seg = |id: &str, elements: Vec<Vec<&str>>, number: u32| mig_types::segment::OwnedSegment {
+        id: id.to_string(),
+        elements: elements
+            .into_iter()
+            .map(|e| e.into_iter().map(String::from).collect())
+            .collect(),
+        segment_number: number,
+        location: None,
+    };
+    vec![
+        seg("UNH", vec![vec!["1"]], 1),
+        // 1001 too long, 1225 mandatory but missing
+        seg("BGM", vec![vec!["E01X"], vec!["DOC1"]], 2),
+        // 31 February
+        seg("DTM", vec![vec!["137", "20250231", "102"]], 3),
+        // Non-numeric quantity
+        seg("QTY", vec![vec!["220", "12a"]], 4),
+        seg("UNT", vec![vec!["5", "1"]], 5),
+    ]
+}
+
+#[test]
+fn test_validate_with_mig_full_level_reports_format_issues() {
+    let validator = EdifactValidator::new(ConfigurableEvaluator::new());
+    let external = NoOpExternalProvider;
+    let workflow = make_workflow(vec![]);
+    let segments = format_test_segments();
+
+    let report = validator.validate_with_mig(
+        &segments,
+        &workflow,
+        &external,
+        ValidationLevel::Full,
+        &format_test_mig(),
+    );
+
+    let format_issues: Vec<_> = report.by_category(ValidationCategory::Format).collect();
+    let find = |path: &str| {
+        format_issues
+            .iter()
+            .find(|i| i.field_path.as_deref() == Some(path))
+            .unwrap_or_else(|| panic!("no format issue at {path}: {format_issues:#?}"))
+    };
+
+    let too_long = find("BGM/C002/1001");
+    assert_eq!(too_long.code, ErrorCodes::VALUE_TOO_LONG);
+    assert_eq!(too_long.actual_value.as_deref(), Some("E01X"));
+    assert_eq!(too_long.expected_value.as_deref(), Some("an..3"));
+    assert_eq!(too_long.segment_position.unwrap().segment_number, 2);
+
+    assert_eq!(find("BGM/1225").code, ErrorCodes::REQUIRED_ELEMENT_EMPTY);
+    assert_eq!(find("DTM/C507/2380").code, ErrorCodes::INVALID_DATE_FORMAT);
+    assert_eq!(
+        find("QTY/C186/6060").code,
+        ErrorCodes::INVALID_NUMERIC_FORMAT
+    );
+    assert_eq!(format_issues.len(), 4, "{format_issues:#?}");
+}
+
+#[test]
+fn test_validate_with_mig_conditions_level_skips_format_checks() {
+    let validator = EdifactValidator::new(ConfigurableEvaluator::new());
+    let external = NoOpExternalProvider;
+    let workflow = make_workflow(vec![]);
+
+    let report = validator.validate_with_mig(
+        &format_test_segments(),
+        &workflow,
+        &external,
+        ValidationLevel::Conditions,
+        &format_test_mig(),
+    );
+
+    assert_eq!(report.by_category(ValidationCategory::Format).count(), 0);
+}
+
+// === Format conditions (9xx) ===
+
+#[test]
+fn test_generated_format_conditions_check_attached_element() {
+    use automapper_validation::generated::UtilmdStromConditionEvaluatorFV2504;
+
+    let evaluator = UtilmdStromConditionEvaluatorFV2504::default();
+    let external = NoOpExternalProvider;
+    let ctx = EvaluationContext::new("55001", &external, &[]);
+
+    // No field in scope: the format cannot be checked.
+    assert_eq!(evaluator.evaluate(930, &ctx), ConditionResult::Unknown);
+
+    let quantities = vec!["12.5".to_string(), "3.25".to_string()];
+    let ctx = EvaluationContext::new("55001", &external, &[]).with_field_values(&quantities);
+    assert_eq!(evaluator.evaluate(930, &ctx), ConditionResult::True);
+    assert_eq!(evaluator.evaluate(914, &ctx), ConditionResult::True);
+
+    let quantities = vec!["12.5".to_string(), "3.125".to_string()];
+    let ctx = EvaluationContext::new("55001", &external, &[]).with_field_values(&quantities);
+    assert_eq!(evaluator.evaluate(930, &ctx), ConditionResult::False);
+
+    let dates = vec!["202601012200+01".to_string()];
+    let ctx = EvaluationContext::new("55001", &external, &[]).with_field_values(&dates);
+    assert_eq!(evaluator.evaluate(931, &ctx), ConditionResult::False);
+    assert_eq!(evaluator.evaluate(932, &ctx), ConditionResult::True);
+}
+
+#[test]
+fn test_validate_format_condition_uses_field_value() {
+    use automapper_validation::generated::UtilmdStromConditionEvaluatorFV2504;
+
+    let segments = vec![
+        mig_types::segment::OwnedSegment {
+            id: "QTY".to_string(),
+            elements: vec![vec!["220".to_string(), "4.25".to_string()]],
+            segment_number: 5,
+            location: None,
+        },
+        mig_types::segment::OwnedSegment {
+            id: "DTM".to_string(),
+            elements: vec![vec![
+                "137".to_string(),
+                "202601012200+00".to_string(),
+                "303".to_string(),
+            ]],
+            segment_number: 6,
+            location: None,
+        },
+    ];
+    let workflow = make_workflow(vec![
+        AhbFieldRule {
+            element_index: Some(0),
+            component_index: Some(1),
+            ..simple_field("QTY/C186/6060", "Menge", "X [930]")
+        },
+        AhbFieldRule {
+            element_index: Some(0),
+            component_index: Some(1),
+            ..simple_field("DTM/C507/2380", "Nachrichtendatum", "X [931]")
+        },
+    ]);
+
+    let validator = EdifactValidator::new(UtilmdStromConditionEvaluatorFV2504::default());
+    let report = validator.validate(
+        &segments,
+        &workflow,
+        &NoOpExternalProvider,
+        ValidationLevel::Conditions,
+    );
+
+    assert!(
+        !report
+            .infos()
+            .any(|i| i.code == ErrorCodes::CONDITION_UNKNOWN),
+        "format conditions should be determined from the field values: {:?}",
+        report.issues
+    );
+}
@@ -0,0 +1,157 @@
+use std::io::Cursor;
+
+use edifact_types::{EdifactDelimiters, OwnedRawSegment, RawSegment};
+use futures_util::Stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, Chain};
+
+use crate::error::ParseError;
+use crate::segment_reader::SegmentState;
+
+/// Incremental EDIFACT segment reader over a tokio `AsyncBufRead`.
+///
+/// The async counterpart of [`SegmentReader`](crate::SegmentReader): it
+/// reads one segment at a time, with the same splitting, decoding and
+/// position numbering, so memory use is bounded by the largest segment and
+/// segments are available before the rest of the input has arrived (e.g.
+/// while a request body is still being received).
+///
+/// Requires the `tokio` feature.
+///
+/// # Example
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use edifact_parser::AsyncSegmentReader;
+///
+/// let input = b"UNA:+.? 'UNB+UNOC:3'UNH+1+MSCONS:D:04B:UN:2.4c'UNT+2+1'UNZ+1'";
+/// let mut reader = AsyncSegmentReader::new(&input[..]).await.unwrap();
+/// let mut ids = Vec::new();
+/// while let Some(segment) = reader.next_segment().await.unwrap() {
+///     ids.push(segment.id.to_string());
+/// }
+/// assert_eq!(ids, ["UNB", "UNH", "UNT", "UNZ"]);
+/// # });
+/// ```
+pub struct AsyncSegmentReader<R> {
+    reader: Chain<Cursor<Vec<u8>>, R>,
+    state: SegmentState,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncSegmentReader<R> {
+    /// Creates a reader, consuming a leading UNA service string advice if
+    /// present to determine the delimiters.
+    pub async fn new(mut reader: R) -> Result<Self, ParseError> {
+        let mut header = Vec::with_capacity(9);
+        (&mut reader).take(9).read_to_end(&mut header).await?;
+
+        let state = SegmentState::new(&mut header);
+        Ok(Self {
+            reader: Cursor::new(header).chain(reader),
+            state,
+        })
+    }
+
+    /// The delimiters in effect (from UNA or defaults).
+    pub fn delimiters(&self) -> &EdifactDelimiters {
+        self.state.delimiters()
+    }
+
+    /// Whether the input started with an explicit UNA segment.
+    pub fn has_una(&self) -> bool {
+        self.state.has_una()
+    }
+
+    /// Reads the next segment.
+    ///
+    /// Returns `Ok(None)` at end of input. The returned segment borrows the
+    /// reader's buffer and must be dropped (or copied) before the next call.
+    pub async fn next_segment(&mut self) -> Result<Option<RawSegment<'_>>, ParseError> {
+        let id_len = loop {
+            if !self.read_raw().await? {
+                return Ok(None);
+            }
+            if let Some(id_len) = self.state.segment_id_len() {
+                break id_len;
+            }
+        };
+        self.state.build(id_len)
+    }
+
+    /// Turns the reader into a stream of owned segments.
+    ///
+    /// The stream reads input only while it is polled, so a slow consumer
+    /// applies backpressure to the underlying reader. It ends after the
+    /// first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<OwnedRawSegment, ParseError>> {
+        futures_util::stream::unfold(Some(self), |reader| async move {
+            let mut reader = reader?;
+            match reader.next_segment().await {
+                Ok(Some(segment)) => {
+                    let segment = OwnedRawSegment::from(&segment);
+                    Some((Ok(segment), Some(reader)))
+                }
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Reads the bytes of the next segment into the state's buffer.
+    ///
+    /// Returns `false` if the input is exhausted before a segment starts.
+    async fn read_raw(&mut self) -> Result<bool, ParseError> {
+        self.state.begin();
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                // Content after the last terminator is still a segment
+                return Ok(self.state.started());
+            }
+
+            let (used, done) = self.state.feed(available);
+            self.reader.consume(used);
+            if done {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_async_reader_matches_sync_reader() {
+        let input: &[u8] = b"UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF'\nUNH+1+UTILMD'NAD+Z09+++M?'Neill'UNT+3+1'\nUNZ+1+REF'";
+
+        let mut expected = Vec::new();
+        let mut sync_reader = crate::SegmentReader::new(input).unwrap();
+        while let Some(segment) = sync_reader.next_segment().unwrap() {
+            expected.push(OwnedRawSegment::from(&segment));
+        }
+
+        // A tiny buffer forces segments to span several fill_buf calls
+        let reader = AsyncSegmentReader::new(BufReader::with_capacity(3, input))
+            .await
+            .unwrap();
+        assert!(reader.has_una());
+        let segments: Vec<_> = reader.into_stream().map(Result::unwrap).collect().await;
+
+        assert_eq!(segments, expected);
+        assert_eq!(segments[2].elements[3], ["M?'Neill"]);
+    }
+
+    #[tokio::test]
+    async fn test_async_stream_decodes_and_ends_after_error() {
+        let input: &[u8] = b"UNB+UNOW:3'NAD+Z09+++M\xfcller'UNZ+0'";
+        let reader = AsyncSegmentReader::new(input).await.unwrap();
+        let items: Vec<_> = reader.into_stream().collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_ok());
+        assert!(matches!(items[1], Err(ParseError::InvalidUtf8 { .. })));
+    }
+}
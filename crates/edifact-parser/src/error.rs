@@ -1,5 +1,7 @@
 use edifact_types::SegmentPosition;
 
+use crate::integrity::IntegrityError;
+
 /// Errors that can occur during EDIFACT parsing.
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -27,6 +29,23 @@ pub enum ParseError {
     #[error("empty segment ID at byte {offset}")]
     EmptySegmentId { offset: usize },
 
+    /// Content between segments that does not start with a segment tag.
+    #[error("unexpected content at byte {offset}: {content:?}")]
+    UnexpectedContent { offset: usize, content: String },
+
+    /// A release character with nothing to escape, or escaping what is
+    /// evidently the segment terminator.
+    #[error("dangling release character at byte {offset}")]
+    DanglingRelease { offset: usize },
+
+    /// Reading from the underlying input failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// UNH/UNT or UNB/UNZ control information is inconsistent.
+    #[error("syntax integrity violation: {0}")]
+    Integrity(#[from] IntegrityError),
+
     /// Handler returned Control::Stop.
     #[error("parsing stopped by handler at {position}")]
     StoppedByHandler { position: SegmentPosition },
@@ -48,6 +67,17 @@ mod tests {
         assert_eq!(err.to_string(), "unterminated segment at byte 42");
     }
 
+    #[test]
+    fn test_parse_error_display_recovery_errors() {
+        let err = ParseError::UnexpectedContent {
+            offset: 7,
+            content: "@@".to_string(),
+        };
+        assert_eq!(err.to_string(), "unexpected content at byte 7: \"@@\"");
+        let err = ParseError::DanglingRelease { offset: 12 };
+        assert_eq!(err.to_string(), "dangling release character at byte 12");
+    }
+
     #[test]
     fn test_parse_error_display_unexpected_eof() {
         let err = ParseError::UnexpectedEof;
@@ -65,6 +95,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_error_display_integrity() {
+        let err = ParseError::from(IntegrityError::UnexpectedUnt {
+            position: SegmentPosition::new(4, 60, 1),
+        });
+        assert_eq!(
+            err.to_string(),
+            "syntax integrity violation: UNT without matching UNH at segment 4 at byte 60 (message 1)"
+        );
+    }
+
     #[test]
     fn test_parse_error_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
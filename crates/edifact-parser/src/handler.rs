@@ -12,10 +12,19 @@ use crate::ParseError;
 /// For a typical EDIFACT interchange:
 /// 1. `on_delimiters()` — always called first
 /// 2. `on_interchange_start()` — when UNB is encountered
-/// 3. `on_message_start()` — when UNH is encountered
-/// 4. `on_segment()` — for EVERY segment (including UNB, UNH, UNT, UNZ)
-/// 5. `on_message_end()` — when UNT is encountered
-/// 6. `on_interchange_end()` — when UNZ is encountered
+/// 3. `on_group_start()` — when UNG is encountered (functional groups are optional)
+/// 4. `on_message_start()` — when UNH is encountered
+/// 5. `on_segment()` — for EVERY segment (including UNB, UNG, UNH, UNT, UNE, UNZ)
+/// 6. `on_message_end()` — when UNT is encountered
+/// 7. `on_group_end()` — when UNE is encountered
+/// 8. `on_interchange_end()` — when UNZ is encountered
+///
+/// A file may hold several interchanges one after another; steps 2–8 then
+/// repeat for each of them.
+///
+/// `on_error()` follows the `on_segment()` call of a segment that reveals an
+/// integrity error (e.g., a UNT whose segment count is wrong); errors about
+/// messages or interchanges left open are reported at end of input.
 pub trait EdifactHandler {
     /// Called when delimiters are determined (from UNA or defaults).
     fn on_delimiters(&mut self, _delimiters: &EdifactDelimiters, _explicit_una: bool) {}
@@ -25,6 +34,11 @@ pub trait EdifactHandler {
         Control::Continue
     }
 
+    /// Called when a functional group begins (UNG segment).
+    fn on_group_start(&mut self, _ung: &RawSegment) -> Control {
+        Control::Continue
+    }
+
     /// Called when a message begins (UNH segment).
     fn on_message_start(&mut self, _unh: &RawSegment) -> Control {
         Control::Continue
@@ -33,7 +47,7 @@ pub trait EdifactHandler {
     /// Called for every segment in the interchange.
     ///
     /// This is called for ALL segments, including service segments
-    /// (UNB, UNH, UNT, UNZ). The specific `on_*` methods are called
+    /// (UNB, UNG, UNH, UNT, UNE, UNZ). The specific `on_*` methods are called
     /// BEFORE `on_segment()` for service segments.
     fn on_segment(&mut self, _segment: &RawSegment) -> Control {
         Control::Continue
@@ -42,6 +56,9 @@ pub trait EdifactHandler {
     /// Called when a message ends (UNT segment).
     fn on_message_end(&mut self, _unt: &RawSegment) {}
 
+    /// Called when a functional group ends (UNE segment).
+    fn on_group_end(&mut self, _une: &RawSegment) {}
+
     /// Called when an interchange ends (UNZ segment).
     fn on_interchange_end(&mut self, _unz: &RawSegment) {}
 
@@ -49,8 +66,17 @@ pub trait EdifactHandler {
     ///
     /// Return `Control::Continue` to attempt recovery, or
     /// `Control::Stop` to abort parsing.
-    fn on_error(&mut self, _error: ParseError) -> Control {
-        Control::Stop
+    ///
+    /// [`ParseError::Integrity`] errors leave the segment stream intact, so
+    /// by default parsing continues after them; other errors stop it. With
+    /// [`EdifactStreamParser::parse_lenient`](crate::EdifactStreamParser::parse_lenient),
+    /// continuing after those resumes at the next segment tag.
+    fn on_error(&mut self, error: ParseError) -> Control {
+        if matches!(error, ParseError::Integrity(_)) {
+            Control::Continue
+        } else {
+            Control::Stop
+        }
     }
 }
 
@@ -118,6 +144,19 @@ mod tests {
         handler.on_interchange_end(&seg);
     }
 
+    #[test]
+    fn test_default_on_error_continues_only_on_integrity_errors() {
+        struct EmptyHandler;
+        impl EdifactHandler for EmptyHandler {}
+
+        let mut handler = EmptyHandler;
+        let integrity = crate::IntegrityError::UnexpectedUnt {
+            position: SegmentPosition::new(4, 0, 1),
+        };
+        assert_eq!(handler.on_error(integrity.into()), Control::Continue);
+        assert_eq!(handler.on_error(ParseError::UnexpectedEof), Control::Stop);
+    }
+
     #[test]
     fn test_recording_handler() {
         let mut handler = RecordingHandler::new();
@@ -0,0 +1,520 @@
+use edifact_types::{RawSegment, SegmentPosition};
+
+/// A violation of the interchange's own control information.
+///
+/// UNT, UNE and UNZ repeat what the sender believes it sent (segment,
+/// message and group counts, references); a mismatch means the interchange was truncated,
+/// concatenated or otherwise damaged in transit.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IntegrityError {
+    /// UNT's segment count (UNH to UNT inclusive) does not match.
+    #[error("UNT declares {declared:?} segments but message has {actual} at {position}")]
+    SegmentCountMismatch {
+        declared: String,
+        actual: usize,
+        position: SegmentPosition,
+    },
+
+    /// UNT's message reference differs from its UNH.
+    #[error("UNT reference {unt:?} does not match UNH reference {unh:?} at {position}")]
+    MessageReferenceMismatch {
+        unh: String,
+        unt: String,
+        position: SegmentPosition,
+    },
+
+    /// UNZ's message (or group) count does not match.
+    #[error("UNZ declares {declared:?} messages but interchange has {actual} at {position}")]
+    MessageCountMismatch {
+        declared: String,
+        actual: usize,
+        position: SegmentPosition,
+    },
+
+    /// UNZ's interchange reference differs from its UNB.
+    #[error("UNZ reference {unz:?} does not match UNB reference {unb:?} at {position}")]
+    InterchangeReferenceMismatch {
+        unb: String,
+        unz: String,
+        position: SegmentPosition,
+    },
+
+    /// UNE's message count does not match.
+    #[error("UNE declares {declared:?} messages but group has {actual} at {position}")]
+    GroupMessageCountMismatch {
+        declared: String,
+        actual: usize,
+        position: SegmentPosition,
+    },
+
+    /// UNE's group reference differs from its UNG.
+    #[error("UNE reference {une:?} does not match UNG reference {ung:?} at {position}")]
+    GroupReferenceMismatch {
+        ung: String,
+        une: String,
+        position: SegmentPosition,
+    },
+
+    /// A message started at `position` was never closed by UNT.
+    #[error("message {reference:?} is not terminated by UNT, started at {position}")]
+    MissingUnt {
+        reference: String,
+        position: SegmentPosition,
+    },
+
+    /// UNT without a preceding UNH.
+    #[error("UNT without matching UNH at {position}")]
+    UnexpectedUnt { position: SegmentPosition },
+
+    /// A functional group started at `position` was never closed by UNE.
+    #[error("functional group {reference:?} is not terminated by UNE, started at {position}")]
+    MissingUne {
+        reference: String,
+        position: SegmentPosition,
+    },
+
+    /// UNE without a preceding UNG.
+    #[error("UNE without matching UNG at {position}")]
+    UnexpectedUne { position: SegmentPosition },
+
+    /// An interchange started at `position` was never closed by UNZ.
+    #[error("interchange {reference:?} is not terminated by UNZ, started at {position}")]
+    MissingUnz {
+        reference: String,
+        position: SegmentPosition,
+    },
+
+    /// UNZ without a preceding UNB.
+    #[error("UNZ without matching UNB at {position}")]
+    UnexpectedUnz { position: SegmentPosition },
+}
+
+impl IntegrityError {
+    /// Position of the segment the error refers to.
+    pub fn position(&self) -> SegmentPosition {
+        match self {
+            Self::SegmentCountMismatch { position, .. }
+            | Self::MessageReferenceMismatch { position, .. }
+            | Self::MessageCountMismatch { position, .. }
+            | Self::InterchangeReferenceMismatch { position, .. }
+            | Self::GroupMessageCountMismatch { position, .. }
+            | Self::GroupReferenceMismatch { position, .. }
+            | Self::MissingUnt { position, .. }
+            | Self::UnexpectedUnt { position }
+            | Self::MissingUne { position, .. }
+            | Self::UnexpectedUne { position }
+            | Self::MissingUnz { position, .. }
+            | Self::UnexpectedUnz { position } => *position,
+        }
+    }
+}
+
+/// Checks UNH/UNT, UNG/UNE and UNB/UNZ control information while segments
+/// stream by.
+///
+/// Feed every segment to [`check_segment`](Self::check_segment) in input
+/// order and call [`finish`](Self::finish) at end of input. Used by
+/// [`EdifactStreamParser`](crate::EdifactStreamParser) to report
+/// [`ParseError::Integrity`](crate::ParseError::Integrity) through
+/// [`EdifactHandler::on_error`](crate::EdifactHandler::on_error).
+///
+/// # Example
+///
+/// ```
+/// use edifact_parser::{IntegrityChecker, IntegrityError};
+/// use edifact_types::{RawSegment, SegmentPosition};
+///
+/// let mut checker = IntegrityChecker::new();
+/// let unh = RawSegment::new("UNH", vec![vec!["1"]], SegmentPosition::new(1, 0, 1));
+/// let unt = RawSegment::new("UNT", vec![vec!["3"], vec!["1"]], SegmentPosition::new(2, 9, 1));
+/// assert!(checker.check_segment(&unh).is_empty());
+/// let errors = checker.check_segment(&unt);
+/// assert!(matches!(errors[0], IntegrityError::SegmentCountMismatch { actual: 2, .. }));
+/// ```
+#[derive(Debug, Default)]
+pub struct IntegrityChecker {
+    interchange: Option<OpenInterchange>,
+    group: Option<OpenGroup>,
+    message: Option<OpenMessage>,
+}
+
+#[derive(Debug)]
+struct OpenInterchange {
+    reference: String,
+    position: SegmentPosition,
+    messages: usize,
+    groups: usize,
+}
+
+#[derive(Debug)]
+struct OpenGroup {
+    reference: String,
+    position: SegmentPosition,
+    messages: usize,
+}
+
+#[derive(Debug)]
+struct OpenMessage {
+    reference: String,
+    position: SegmentPosition,
+    /// Segments seen so far, including UNH.
+    segments: usize,
+}
+
+impl IntegrityChecker {
+    /// Creates a checker with no open interchange, group or message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks one segment, returning the errors it reveals.
+    pub fn check_segment(&mut self, segment: &RawSegment) -> Vec<IntegrityError> {
+        let mut errors = Vec::new();
+        let id = segment.id.to_ascii_uppercase();
+
+        match id.as_str() {
+            "UNB" => {
+                errors.extend(self.close_message());
+                errors.extend(self.close_group());
+                errors.extend(self.close_interchange());
+                self.interchange = Some(OpenInterchange {
+                    reference: segment.get_element(4).to_string(),
+                    position: segment.position,
+                    messages: 0,
+                    groups: 0,
+                });
+            }
+            "UNG" => {
+                errors.extend(self.close_message());
+                errors.extend(self.close_group());
+                if let Some(interchange) = self.interchange.as_mut() {
+                    interchange.groups += 1;
+                }
+                self.group = Some(OpenGroup {
+                    reference: segment.get_element(4).to_string(),
+                    position: segment.position,
+                    messages: 0,
+                });
+            }
+            "UNH" => {
+                errors.extend(self.close_message());
+                if let Some(interchange) = self.interchange.as_mut() {
+                    interchange.messages += 1;
+                }
+                if let Some(group) = self.group.as_mut() {
+                    group.messages += 1;
+                }
+                self.message = Some(OpenMessage {
+                    reference: segment.get_element(0).to_string(),
+                    position: segment.position,
+                    segments: 1,
+                });
+            }
+            "UNT" => match self.message.take() {
+                Some(message) => {
+                    let actual = message.segments + 1;
+                    let declared = segment.get_element(0);
+                    if declared.parse::<usize>().ok() != Some(actual) {
+                        errors.push(IntegrityError::SegmentCountMismatch {
+                            declared: declared.to_string(),
+                            actual,
+                            position: segment.position,
+                        });
+                    }
+                    let reference = segment.get_element(1);
+                    if reference != message.reference {
+                        errors.push(IntegrityError::MessageReferenceMismatch {
+                            unh: message.reference,
+                            unt: reference.to_string(),
+                            position: segment.position,
+                        });
+                    }
+                }
+                None => errors.push(IntegrityError::UnexpectedUnt {
+                    position: segment.position,
+                }),
+            },
+            "UNE" => {
+                errors.extend(self.close_message());
+                match self.group.take() {
+                    Some(group) => {
+                        let declared = segment.get_element(0);
+                        if declared.parse::<usize>().ok() != Some(group.messages) {
+                            errors.push(IntegrityError::GroupMessageCountMismatch {
+                                declared: declared.to_string(),
+                                actual: group.messages,
+                                position: segment.position,
+                            });
+                        }
+                        let reference = segment.get_element(1);
+                        if reference != group.reference {
+                            errors.push(IntegrityError::GroupReferenceMismatch {
+                                ung: group.reference,
+                                une: reference.to_string(),
+                                position: segment.position,
+                            });
+                        }
+                    }
+                    None => errors.push(IntegrityError::UnexpectedUne {
+                        position: segment.position,
+                    }),
+                }
+            }
+            "UNZ" => {
+                errors.extend(self.close_message());
+                errors.extend(self.close_group());
+                match self.interchange.take() {
+                    Some(interchange) => {
+                        // UNZ counts functional groups if there are any
+                        let actual = if interchange.groups > 0 {
+                            interchange.groups
+                        } else {
+                            interchange.messages
+                        };
+                        let declared = segment.get_element(0);
+                        if declared.parse::<usize>().ok() != Some(actual) {
+                            errors.push(IntegrityError::MessageCountMismatch {
+                                declared: declared.to_string(),
+                                actual,
+                                position: segment.position,
+                            });
+                        }
+                        let reference = segment.get_element(1);
+                        if reference != interchange.reference {
+                            errors.push(IntegrityError::InterchangeReferenceMismatch {
+                                unb: interchange.reference,
+                                unz: reference.to_string(),
+                                position: segment.position,
+                            });
+                        }
+                    }
+                    None => errors.push(IntegrityError::UnexpectedUnz {
+                        position: segment.position,
+                    }),
+                }
+            }
+            _ => {
+                if let Some(message) = self.message.as_mut() {
+                    message.segments += 1;
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Reports a message, group or interchange still open at end of input.
+    pub fn finish(&mut self) -> Vec<IntegrityError> {
+        self.close_message()
+            .into_iter()
+            .chain(self.close_group())
+            .chain(self.close_interchange())
+            .collect()
+    }
+
+    fn close_message(&mut self) -> Option<IntegrityError> {
+        self.message.take().map(|m| IntegrityError::MissingUnt {
+            reference: m.reference,
+            position: m.position,
+        })
+    }
+
+    fn close_group(&mut self) -> Option<IntegrityError> {
+        self.group.take().map(|g| IntegrityError::MissingUne {
+            reference: g.reference,
+            position: g.position,
+        })
+    }
+
+    fn close_interchange(&mut self) -> Option<IntegrityError> {
+        self.interchange.take().map(|i| IntegrityError::MissingUnz {
+            reference: i.reference,
+            position: i.position,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs the checker over `segments`, given as `(id, elements)`, numbering
+    /// them from 1.
+    fn check(segments: &[(&str, &[&str])]) -> Vec<IntegrityError> {
+        let mut checker = IntegrityChecker::new();
+        let mut errors = Vec::new();
+        for (i, (id, elements)) in segments.iter().enumerate() {
+            let elements = elements.iter().map(|e| vec![*e]).collect();
+            let position = SegmentPosition::new(i as u32 + 1, 0, 0);
+            errors.extend(checker.check_segment(&RawSegment::new(id, elements, position)));
+        }
+        errors.extend(checker.finish());
+        errors
+    }
+
+    const UNB: (&str, &[&str]) = ("UNB", &["UNOC", "S", "R", "250101", "REF1"]);
+
+    #[test]
+    fn test_consistent_interchange_has_no_errors() {
+        let errors = check(&[
+            UNB,
+            ("UNH", &["M1", "UTILMD"]),
+            ("BGM", &["E01"]),
+            ("UNT", &["3", "M1"]),
+            ("UNH", &["M2", "UTILMD"]),
+            ("UNT", &["2", "M2"]),
+            ("UNZ", &["2", "REF1"]),
+        ]);
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn test_unt_count_and_reference_mismatch() {
+        let errors = check(&[
+            ("UNH", &["M1", "UTILMD"]),
+            ("BGM", &["E01"]),
+            ("DTM", &["137"]),
+            ("UNT", &["3", "M2"]),
+        ]);
+        assert_eq!(
+            errors,
+            vec![
+                IntegrityError::SegmentCountMismatch {
+                    declared: "3".to_string(),
+                    actual: 4,
+                    position: SegmentPosition::new(4, 0, 0),
+                },
+                IntegrityError::MessageReferenceMismatch {
+                    unh: "M1".to_string(),
+                    unt: "M2".to_string(),
+                    position: SegmentPosition::new(4, 0, 0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unz_count_and_reference_mismatch() {
+        let errors = check(&[
+            UNB,
+            ("UNH", &["M1"]),
+            ("UNT", &["2", "M1"]),
+            ("UNZ", &["2", "REF2"]),
+        ]);
+        assert!(matches!(
+            &errors[..],
+            [
+                IntegrityError::MessageCountMismatch { actual: 1, .. },
+                IntegrityError::InterchangeReferenceMismatch { unb, unz, .. },
+            ] if unb == "REF1" && unz == "REF2"
+        ));
+    }
+
+    #[test]
+    fn test_unz_counts_groups_when_present() {
+        let errors = check(&[
+            UNB,
+            ("UNG", &["UTILMD"]),
+            ("UNH", &["M1"]),
+            ("UNT", &["2", "M1"]),
+            ("UNH", &["M2"]),
+            ("UNT", &["2", "M2"]),
+            ("UNE", &["2"]),
+            ("UNZ", &["1", "REF1"]),
+        ]);
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn test_une_count_and_reference_mismatch() {
+        let errors = check(&[
+            UNB,
+            ("UNG", &["UTILMD", "S", "R", "250101", "G1"]),
+            ("UNH", &["M1"]),
+            ("UNT", &["2", "M1"]),
+            ("UNE", &["2", "G2"]),
+            ("UNZ", &["1", "REF1"]),
+        ]);
+        assert!(matches!(
+            &errors[..],
+            [
+                IntegrityError::GroupMessageCountMismatch { actual: 1, .. },
+                IntegrityError::GroupReferenceMismatch { ung, une, .. },
+            ] if ung == "G1" && une == "G2"
+        ));
+    }
+
+    #[test]
+    fn test_unbalanced_ung_une() {
+        let errors = check(&[
+            UNB,
+            ("UNG", &["UTILMD", "S", "R", "250101", "G1"]),
+            ("UNH", &["M1"]),
+            ("UNT", &["2", "M1"]),
+            ("UNZ", &["1", "REF1"]),
+            ("UNE", &["0", "G1"]),
+        ]);
+        assert!(matches!(
+            &errors[..],
+            [
+                IntegrityError::MissingUne { reference, position },
+                IntegrityError::UnexpectedUne { .. },
+            ] if reference == "G1" && position.segment_number == 2
+        ));
+    }
+
+    #[test]
+    fn test_unbalanced_unh_unt() {
+        let errors = check(&[
+            UNB,
+            ("UNH", &["M1"]),
+            ("BGM", &["E01"]),
+            ("UNH", &["M2"]),
+            ("UNT", &["2", "M2"]),
+            ("UNT", &["2", "M2"]),
+            ("UNZ", &["2", "REF1"]),
+        ]);
+        assert_eq!(
+            errors,
+            vec![
+                IntegrityError::MissingUnt {
+                    reference: "M1".to_string(),
+                    position: SegmentPosition::new(2, 0, 0),
+                },
+                IntegrityError::UnexpectedUnt {
+                    position: SegmentPosition::new(6, 0, 0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncated_interchange_reported_at_finish() {
+        let errors = check(&[UNB, ("UNH", &["M1"]), ("BGM", &["E01"])]);
+        assert!(matches!(
+            &errors[..],
+            [
+                IntegrityError::MissingUnt { position: unh, .. },
+                IntegrityError::MissingUnz { position: unb, .. },
+            ] if unh.segment_number == 2 && unb.segment_number == 1
+        ));
+    }
+
+    #[test]
+    fn test_non_numeric_count_is_a_mismatch() {
+        let errors = check(&[("UNH", &["M1"]), ("UNT", &["x", "M1"])]);
+        assert!(matches!(
+            &errors[..],
+            [IntegrityError::SegmentCountMismatch { declared, actual: 2, .. }] if declared == "x"
+        ));
+    }
+
+    #[test]
+    fn test_position_accessor() {
+        let position = SegmentPosition::new(7, 120, 2);
+        assert_eq!(
+            IntegrityError::UnexpectedUnz { position }.position(),
+            position
+        );
+    }
+}
@@ -7,17 +7,55 @@
 //!
 //! The parser uses a SAX-style streaming model:
 //! 1. Tokenizer splits raw bytes into segments
-//! 2. Parser routes segments to handler callbacks
-//! 3. Handler accumulates state as needed
+//! 2. [`EdifactReader`] turns segments into [`Event`]s
+//! 3. Parser routes events to handler callbacks
+//! 4. Handler accumulates state as needed
+//!
+//! The reader can also be used on its own as an iterator, for pull-based
+//! parsing with iterator adapters and `?`.
+//!
+//! Input can be a complete byte slice ([`EdifactStreamParser::parse`]) or any
+//! `BufRead` ([`EdifactStreamParser::parse_reader`], [`SegmentReader`]), the
+//! latter keeping only one segment in memory at a time. With the `tokio`
+//! feature, `AsyncSegmentReader` does the same for tokio's `AsyncBufRead`
+//! and can be turned into a `Stream` of owned segments.
+//!
+//! Input bytes are decoded according to the syntax identifier declared in
+//! UNB S001 (UNOA/UNOB/UNOC/UNOD/UNOW, see [`edifact_types::SyntaxIdentifier`]);
+//! [`EdifactStreamParser::parse_str`] takes input that is already text.
+//!
+//! Optional UNG/UNE functional groups are reported through their own
+//! handler callbacks, and input holding several UNB…UNZ interchanges one
+//! after another is parsed as such.
+//!
+//! While parsing, UNT, UNE and UNZ counts and references are checked
+//! against the segments actually read; mismatches are reported to
+//! [`EdifactHandler::on_error`] as [`ParseError::Integrity`].
+//!
+//! [`EdifactStreamParser::parse_lenient`] also reports malformed input
+//! (garbage between segments, missing terminators, an invalid UNA, ...) to
+//! `on_error` and resynchronises at the next segment tag when the handler
+//! continues.
 
+#[cfg(feature = "tokio")]
+mod async_reader;
 mod error;
 mod handler;
+mod integrity;
 mod parser;
+mod reader;
+mod recovery;
 mod segment_builder;
+mod segment_reader;
 mod tokenizer;
 
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncSegmentReader;
 pub use error::ParseError;
 pub use handler::EdifactHandler;
+pub use integrity::{IntegrityChecker, IntegrityError};
 pub use parser::EdifactStreamParser;
+pub use reader::{EdifactReader, Event};
 pub use segment_builder::SegmentBuilder;
+pub use segment_reader::SegmentReader;
 pub use tokenizer::EdifactTokenizer;
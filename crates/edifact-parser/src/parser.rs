@@ -1,17 +1,19 @@
-#[cfg(test)]
 use edifact_types::Control;
-use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition};
 
 use crate::error::ParseError;
 use crate::handler::EdifactHandler;
-use crate::segment_builder::SegmentBuilder as SegBuilder;
-use crate::tokenizer::EdifactTokenizer;
+use crate::integrity::IntegrityChecker;
+use crate::reader::{segment_events, EdifactReader, Event};
+use crate::segment_reader::SegmentReader;
 
 /// Streaming EDIFACT parser.
 ///
 /// Parses a byte slice by tokenizing it into segments and routing them
 /// to an `EdifactHandler`. Service segments (UNB, UNH, UNT, UNZ) are
 /// dispatched to specific handler methods in addition to `on_segment()`.
+///
+/// The handler is driven by the [`Event`]s of an [`EdifactReader`], which
+/// can be used directly for pull-based parsing.
 pub struct EdifactStreamParser;
 
 impl EdifactStreamParser {
@@ -23,126 +25,141 @@ impl EdifactStreamParser {
     /// 3. Routes each segment to the handler
     /// 4. Stops if the handler returns `Control::Stop`
     ///
-    /// Supports both UTF-8 and ISO-8859-1 encoded input. If the input
-    /// contains non-UTF-8 bytes (raw ISO-8859-1), it is transparently
-    /// transcoded to UTF-8 before parsing. EDIFACT delimiters are always
-    /// ASCII, so transcoding does not affect delimiter detection.
+    /// UNH/UNT and UNB/UNZ control information is checked along the way;
+    /// each inconsistency is passed to `on_error()` as
+    /// [`ParseError::Integrity`] after the segment revealing it has been
+    /// routed. Returning `Control::Stop` there ends parsing.
+    ///
+    /// The input is decoded according to the syntax identifier in UNB
+    /// S001, see [`EdifactReader::decode`]. EDIFACT delimiters are always
+    /// ASCII, so decoding does not affect delimiter detection.
     pub fn parse(input: &[u8], handler: &mut dyn EdifactHandler) -> Result<(), ParseError> {
-        if std::str::from_utf8(input).is_ok() {
-            Self::parse_inner(input, handler)
-        } else {
-            let transcoded = transcode_iso_8859_1_to_utf8(input);
-            Self::parse_inner(&transcoded, handler)
-        }
+        let text = EdifactReader::decode(input)?;
+        Self::parse_str(&text, handler)
     }
 
-    fn parse_inner(input: &[u8], handler: &mut dyn EdifactHandler) -> Result<(), ParseError> {
-        // Step 1: Detect delimiters
-        let (has_una, delimiters) = EdifactDelimiters::detect(input);
-        handler.on_delimiters(&delimiters, has_una);
-
-        // Step 2: Determine where actual content starts (after UNA if present)
-        let content_start = if has_una { 9 } else { 0 };
-        let content = &input[content_start..];
-
-        // Step 3: Tokenize and process segments
-        let tokenizer = EdifactTokenizer::new(delimiters);
-        let seg_builder = SegBuilder::new(delimiters);
-
-        let mut segment_number: u32 = 0;
-        let mut message_number: u32 = 0;
-        let mut byte_offset = content_start;
-
-        for segment_str in tokenizer.tokenize_segments(content) {
-            segment_number += 1;
-
-            let position = SegmentPosition::new(segment_number, byte_offset, message_number);
-
-            let Some(raw_segment) = seg_builder.build(segment_str, position) else {
-                byte_offset += segment_str.len() + 1; // +1 for terminator
-                continue;
-            };
+    /// Parse an EDIFACT interchange that has already been decoded to text.
+    ///
+    /// Use this for input received as a string (e.g. in a JSON request),
+    /// where the declared character set no longer applies to the bytes.
+    pub fn parse_str(input: &str, handler: &mut dyn EdifactHandler) -> Result<(), ParseError> {
+        dispatch_all(EdifactReader::new(input), handler);
+        Ok(())
+    }
 
-            // Skip UNA segments in content
-            if raw_segment.is("UNA") {
-                byte_offset += segment_str.len() + 1;
-                segment_number -= 1; // don't count UNA
-                continue;
+    /// Parse an EDIFACT interchange incrementally from a reader.
+    ///
+    /// Produces the same handler events as [`parse`](Self::parse), but reads
+    /// one segment at a time via [`SegmentReader`], so memory use is bounded
+    /// by the largest segment instead of the whole input. Handler state is
+    /// then the only thing that grows with the interchange.
+    pub fn parse_reader<R: std::io::BufRead>(
+        reader: R,
+        handler: &mut dyn EdifactHandler,
+    ) -> Result<(), ParseError> {
+        let mut segments = SegmentReader::new(reader)?;
+        handler.on_delimiters(segments.delimiters(), segments.has_una());
+
+        let mut integrity = IntegrityChecker::new();
+        while let Some(segment) = segments.next_segment()? {
+            let events = segment_events(segment, &mut integrity).map(Ok);
+            if dispatch_all(events, handler).should_stop() {
+                return Ok(());
             }
+        }
 
-            let id_upper = raw_segment.id.to_ascii_uppercase();
-
-            // Track message numbering
-            if id_upper == "UNH" {
-                message_number += 1;
-            }
+        let errors = integrity.finish().into_iter();
+        dispatch_all(errors.map(|e| Ok(Event::Error(e.into()))), handler);
+        Ok(())
+    }
 
-            // Rebuild position with correct message number
-            let effective_message_number = if id_upper == "UNB" || id_upper == "UNZ" {
-                0
-            } else {
-                message_number
-            };
-            let position =
-                SegmentPosition::new(segment_number, byte_offset, effective_message_number);
-            let raw_segment = RawSegment::new(raw_segment.id, raw_segment.elements, position);
-
-            // Route service segments
-            match id_upper.as_str() {
-                "UNB" => {
-                    if handler.on_interchange_start(&raw_segment).should_stop() {
-                        return Ok(());
-                    }
-                }
-                "UNH" => {
-                    if handler.on_message_start(&raw_segment).should_stop() {
-                        return Ok(());
-                    }
-                }
-                "UNT" => {
-                    handler.on_message_end(&raw_segment);
-                }
-                "UNZ" => {
-                    handler.on_interchange_end(&raw_segment);
-                }
-                _ => {}
-            }
+    /// Parse an EDIFACT interchange, recovering from malformed input.
+    ///
+    /// Decodes and routes segments like [`parse`](Self::parse), but
+    /// malformed input is reported to `on_error()` instead of being
+    /// misread: an invalid UNA ([`ParseError::InvalidUna`], parsing then
+    /// uses the standard delimiters), content between segments that is not
+    /// a segment ([`ParseError::EmptySegmentId`],
+    /// [`ParseError::UnexpectedContent`]), a segment running into the next
+    /// line's segment tag or cut off by the end of input
+    /// ([`ParseError::UnterminatedSegment`]), and a release character that
+    /// escapes the terminator at the end of a line or ends the input
+    /// ([`ParseError::DanglingRelease`]). Input that is not valid UTF-8
+    /// despite UNOW is reported as [`ParseError::InvalidUtf8`] and decoded
+    /// lossily.
+    ///
+    /// If `on_error()` returns `Control::Continue`, parsing resynchronises
+    /// at the next plausible segment tag, so one corrupt message does not
+    /// prevent processing the rest of the interchange. The default
+    /// `on_error()` stops on these errors; handlers that want recovery
+    /// must override it.
+    pub fn parse_lenient(input: &[u8], handler: &mut dyn EdifactHandler) -> Result<(), ParseError> {
+        let (text, decode_error) = match EdifactReader::decode(input) {
+            Ok(text) => (text, None),
+            Err(error) => (String::from_utf8_lossy(input), Some(error)),
+        };
 
-            // Always call on_segment
-            if handler.on_segment(&raw_segment).should_stop() {
-                return Ok(());
-            }
+        // The decode error follows the delimiters, which always come first
+        let mut reader = EdifactReader::lenient(&text);
+        let delimiters = reader.next();
+        let events = delimiters
+            .into_iter()
+            .chain(decode_error.map(Err))
+            .chain(reader);
+        dispatch_all(events, handler);
+        Ok(())
+    }
+}
 
-            byte_offset += segment_str.len() + 1; // +1 for terminator
+/// Pass events to the handler until they run out or the handler asks to
+/// stop. Returns `Control::Stop` in the latter case.
+fn dispatch_all<'a>(
+    events: impl IntoIterator<Item = Result<Event<'a>, ParseError>>,
+    handler: &mut dyn EdifactHandler,
+) -> Control {
+    for event in events {
+        if dispatch(event, handler).should_stop() {
+            return Control::Stop;
         }
-
-        Ok(())
     }
+    Control::Continue
 }
 
-/// Transcode ISO-8859-1 bytes to UTF-8.
-///
-/// ISO-8859-1 code points 0x00–0xFF map directly to Unicode U+0000–U+00FF.
-/// - 0x00–0x7F: single UTF-8 byte (identical)
-/// - 0x80–0xBF: two UTF-8 bytes: 0xC2 + original byte
-/// - 0xC0–0xFF: two UTF-8 bytes: 0xC3 + (original byte - 0x40)
-fn transcode_iso_8859_1_to_utf8(input: &[u8]) -> Vec<u8> {
-    let mut output = Vec::with_capacity(input.len() + input.len() / 4);
-    for &b in input {
-        if b < 0x80 {
-            output.push(b);
-        } else {
-            // ISO-8859-1 byte to UTF-8 two-byte sequence
-            output.push(0xC0 | (b >> 6));
-            output.push(0x80 | (b & 0x3F));
-        }
-    }
-    output
+/// Call the handler method matching an event.
+fn dispatch(event: Result<Event<'_>, ParseError>, handler: &mut dyn EdifactHandler) -> Control {
+    match event {
+        Ok(Event::Delimiters {
+            delimiters,
+            explicit_una,
+        }) => {
+            handler.on_delimiters(&delimiters, explicit_una);
+            Control::Continue
+        }
+        Ok(Event::InterchangeStart(unb)) => handler.on_interchange_start(&unb),
+        Ok(Event::GroupStart(ung)) => handler.on_group_start(&ung),
+        Ok(Event::MessageStart(unh)) => handler.on_message_start(&unh),
+        Ok(Event::Segment(segment)) => handler.on_segment(&segment),
+        Ok(Event::MessageEnd(unt)) => {
+            handler.on_message_end(&unt);
+            Control::Continue
+        }
+        Ok(Event::GroupEnd(une)) => {
+            handler.on_group_end(&une);
+            Control::Continue
+        }
+        Ok(Event::InterchangeEnd(unz)) => {
+            handler.on_interchange_end(&unz);
+            Control::Continue
+        }
+        Ok(Event::Error(error)) | Err(error) => handler.on_error(error),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IntegrityError;
+    use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition};
     use std::cell::RefCell;
 
     /// Handler that collects all event names in order.
@@ -176,6 +193,13 @@ mod tests {
             Control::Continue
         }
 
+        fn on_group_start(&mut self, ung: &RawSegment) -> Control {
+            self.events
+                .borrow_mut()
+                .push(format!("GROUP_START(ref={})", ung.get_element(4)));
+            Control::Continue
+        }
+
         fn on_message_start(&mut self, unh: &RawSegment) -> Control {
             self.events
                 .borrow_mut()
@@ -194,6 +218,10 @@ mod tests {
             self.events.borrow_mut().push("MESSAGE_END".to_string());
         }
 
+        fn on_group_end(&mut self, _une: &RawSegment) {
+            self.events.borrow_mut().push("GROUP_END".to_string());
+        }
+
         fn on_interchange_end(&mut self, _unz: &RawSegment) {
             self.events.borrow_mut().push("INTERCHANGE_END".to_string());
         }
@@ -231,6 +259,38 @@ mod tests {
         assert_eq!(events[1], "INTERCHANGE_START(UNB)");
     }
 
+    #[test]
+    fn test_parse_functional_groups_and_concatenated_interchanges() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+IC1'UNG+UTILMD+S+R+210101:1200+G1'\
+UNH+M1+UTILMD'UNT+2+M1'UNE+1+G1'UNZ+1+IC1'\
+UNB+UNOC:3+S+R+210101:1200+IC2'UNH+M2+UTILMD'UNT+2+M2'UNZ+1+IC2'";
+
+        let mut handler = EventCollector::new();
+        EdifactStreamParser::parse(input, &mut handler).unwrap();
+
+        let events: Vec<String> = handler
+            .events()
+            .into_iter()
+            .filter(|e| !e.starts_with("SEGMENT"))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "DELIMITERS(una=false)",
+                "INTERCHANGE_START(UNB)",
+                "GROUP_START(ref=G1)",
+                "MESSAGE_START(ref=M1)",
+                "MESSAGE_END",
+                "GROUP_END",
+                "INTERCHANGE_END",
+                "INTERCHANGE_START(UNB)",
+                "MESSAGE_START(ref=M2)",
+                "MESSAGE_END",
+                "INTERCHANGE_END",
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_handler_stops_early() {
         struct StopOnBgm {
@@ -409,6 +469,352 @@ mod tests {
         assert_eq!(handler.dtm_values[0], "137=202506190130?+00");
     }
 
+    /// Collects the first component of the first element of every NAD.
+    struct NadNames(Vec<String>);
+    impl EdifactHandler for NadNames {
+        fn on_segment(&mut self, seg: &RawSegment) -> Control {
+            if seg.is("NAD") {
+                self.0.push(seg.get_component(0, 0).to_string());
+            }
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn test_parse_decodes_by_declared_syntax_identifier() {
+        // "Mü" as UTF-8 bytes, read as ISO-8859-1 because UNB declares UNOC
+        let mut unoc = NadNames(Vec::new());
+        EdifactStreamParser::parse("UNB+UNOC:3'NAD+Mü'".as_bytes(), &mut unoc).unwrap();
+        assert_eq!(unoc.0, ["MÃ¼"]);
+
+        let mut unod = NadNames(Vec::new());
+        EdifactStreamParser::parse(b"UNB+UNOD:3'NAD+\xA3\xF3d\xBC'", &mut unod).unwrap();
+        assert_eq!(unod.0, ["Łódź"]);
+
+        let mut unow = NadNames(Vec::new());
+        EdifactStreamParser::parse("UNB+UNOW:3'NAD+5 €'".as_bytes(), &mut unow).unwrap();
+        assert_eq!(unow.0, ["5 €"]);
+    }
+
+    #[test]
+    fn test_parse_unow_rejects_invalid_utf8() {
+        let mut handler = NadNames(Vec::new());
+        let err =
+            EdifactStreamParser::parse(b"UNB+UNOW:3'NAD+M\xFCller'", &mut handler).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidUtf8 { offset: 16, .. }));
+    }
+
+    #[test]
+    fn test_parse_str_keeps_decoded_text() {
+        let mut handler = NadNames(Vec::new());
+        EdifactStreamParser::parse_str("UNB+UNOC:3'NAD+Müller'", &mut handler).unwrap();
+        assert_eq!(handler.0, ["Müller"]);
+    }
+
+    #[test]
+    fn test_parse_reader_decodes_by_declared_syntax_identifier() {
+        let input = b"UNA:+.? 'UNB+UNOD:3'NAD+\xA3\xF3d\xBC'";
+        let mut from_reader = NadNames(Vec::new());
+        EdifactStreamParser::parse_reader(&input[..], &mut from_reader).unwrap();
+        assert_eq!(from_reader.0, ["Łódź"]);
+
+        let mut handler = NadNames(Vec::new());
+        let err =
+            EdifactStreamParser::parse_reader(&b"UNB+UNOW:3'NAD+M\xFCller'"[..], &mut handler)
+                .unwrap_err();
+        assert!(matches!(err, ParseError::InvalidUtf8 { offset: 16, .. }));
+    }
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = b"UNA:+.? 'UNB+UNOC:3+SENDER+RECEIVER+210101:1200+REF001'UNH+001+UTILMD:D:11A:UN:S2.1'BGM+E03+DOC001'UNT+3+001'UNH+002+UTILMD:D:11A:UN:S2.1'UNT+2+002'UNZ+2+REF001'";
+
+        let mut from_slice = EventCollector::new();
+        EdifactStreamParser::parse(input, &mut from_slice).unwrap();
+        let mut from_reader = EventCollector::new();
+        EdifactStreamParser::parse_reader(&input[..], &mut from_reader).unwrap();
+
+        assert_eq!(from_reader.events(), from_slice.events());
+    }
+
+    #[test]
+    fn test_parse_reader_handler_stops_early() {
+        struct CountSegments(usize);
+        impl EdifactHandler for CountSegments {
+            fn on_segment(&mut self, _seg: &RawSegment) -> Control {
+                self.0 += 1;
+                if self.0 == 2 {
+                    Control::Stop
+                } else {
+                    Control::Continue
+                }
+            }
+        }
+
+        let input = b"UNB+UNOC:3'UNH+001'BGM+E03'UNT+2+001'UNZ+1'";
+        let mut handler = CountSegments(0);
+        EdifactStreamParser::parse_reader(&input[..], &mut handler).unwrap();
+        assert_eq!(handler.0, 2);
+    }
+
+    /// Handler that records segment IDs and integrity errors, continuing on
+    /// errors unless `stop_on_error` is set.
+    struct IntegrityRecorder {
+        stop_on_error: bool,
+        segments: Vec<String>,
+        errors: Vec<IntegrityError>,
+    }
+
+    impl IntegrityRecorder {
+        fn new(stop_on_error: bool) -> Self {
+            Self {
+                stop_on_error,
+                segments: Vec::new(),
+                errors: Vec::new(),
+            }
+        }
+    }
+
+    impl EdifactHandler for IntegrityRecorder {
+        fn on_segment(&mut self, seg: &RawSegment) -> Control {
+            self.segments.push(seg.id.to_string());
+            Control::Continue
+        }
+
+        fn on_error(&mut self, error: ParseError) -> Control {
+            if let ParseError::Integrity(e) = error {
+                self.errors.push(e);
+            }
+            if self.stop_on_error {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        }
+    }
+
+    const BROKEN: &[u8] = b"UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF001'UNH+001+UTILMD:D:11A:UN:S2.1'BGM+E03'UNT+5+002'UNH+002+UTILMD:D:11A:UN:S2.1'BGM+E03'UNZ+1+REF999'";
+
+    #[test]
+    fn test_parse_reports_integrity_errors_to_on_error() {
+        let mut handler = IntegrityRecorder::new(false);
+        EdifactStreamParser::parse(BROKEN, &mut handler).unwrap();
+
+        assert_eq!(handler.segments.len(), 7);
+        assert_eq!(
+            handler.errors,
+            vec![
+                IntegrityError::SegmentCountMismatch {
+                    declared: "5".to_string(),
+                    actual: 3,
+                    position: SegmentPosition::new(4, 80, 1).with_line_column(1, 81),
+                },
+                IntegrityError::MessageReferenceMismatch {
+                    unh: "001".to_string(),
+                    unt: "002".to_string(),
+                    position: SegmentPosition::new(4, 80, 1).with_line_column(1, 81),
+                },
+                IntegrityError::MissingUnt {
+                    reference: "002".to_string(),
+                    position: SegmentPosition::new(5, 90, 2).with_line_column(1, 91),
+                },
+                IntegrityError::MessageCountMismatch {
+                    declared: "1".to_string(),
+                    actual: 2,
+                    position: SegmentPosition::new(7, 127, 0).with_line_column(1, 128),
+                },
+                IntegrityError::InterchangeReferenceMismatch {
+                    unb: "REF001".to_string(),
+                    unz: "REF999".to_string(),
+                    position: SegmentPosition::new(7, 127, 0).with_line_column(1, 128),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_stops_when_on_error_stops() {
+        let mut handler = IntegrityRecorder::new(true);
+        EdifactStreamParser::parse(BROKEN, &mut handler).unwrap();
+
+        // Stops right after the UNT that revealed the first error
+        assert_eq!(handler.segments, ["UNB", "UNH", "BGM", "UNT"]);
+        assert_eq!(handler.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_reader_reports_same_integrity_errors() {
+        let mut from_slice = IntegrityRecorder::new(false);
+        EdifactStreamParser::parse(BROKEN, &mut from_slice).unwrap();
+        let mut from_reader = IntegrityRecorder::new(false);
+        EdifactStreamParser::parse_reader(BROKEN, &mut from_reader).unwrap();
+
+        assert_eq!(from_reader.errors, from_slice.errors);
+    }
+
+    #[test]
+    fn test_parse_reports_unterminated_interchange_at_end() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+REF001'UNH+001+UTILMD'BGM+E03'";
+        let mut handler = IntegrityRecorder::new(false);
+        EdifactStreamParser::parse(input, &mut handler).unwrap();
+
+        assert!(matches!(
+            &handler.errors[..],
+            [
+                IntegrityError::MissingUnt { .. },
+                IntegrityError::MissingUnz { .. }
+            ]
+        ));
+    }
+
+    /// Handler that records segments and all errors, and always continues.
+    #[derive(Default)]
+    struct LenientRecorder {
+        segments: Vec<String>,
+        errors: Vec<String>,
+        messages: u32,
+    }
+
+    impl EdifactHandler for LenientRecorder {
+        fn on_message_start(&mut self, _unh: &RawSegment) -> Control {
+            self.messages += 1;
+            Control::Continue
+        }
+
+        fn on_segment(&mut self, seg: &RawSegment) -> Control {
+            self.segments.push(seg.elements.first().map_or_else(
+                || seg.id.to_string(),
+                |e| format!("{}+{}", seg.id, e.join(":")),
+            ));
+            Control::Continue
+        }
+
+        fn on_error(&mut self, error: ParseError) -> Control {
+            self.errors.push(error.to_string());
+            Control::Continue
+        }
+    }
+
+    fn parse_lenient(input: &[u8]) -> LenientRecorder {
+        let mut handler = LenientRecorder::default();
+        EdifactStreamParser::parse_lenient(input, &mut handler).unwrap();
+        handler
+    }
+
+    #[test]
+    fn test_parse_lenient_matches_parse_on_valid_input() {
+        let input = b"UNA:+.? '\nUNB+UNOC:3+S+R+210101:1200+REF001'\nUNH+001+UTILMD:D:11A:UN:S2.1'\nBGM+E03'\nUNT+3+001'\nUNZ+1+REF001'\n";
+        let mut strict = LenientRecorder::default();
+        EdifactStreamParser::parse(input, &mut strict).unwrap();
+        let lenient = parse_lenient(input);
+
+        assert_eq!(lenient.segments, strict.segments);
+        assert!(lenient.errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_lenient_skips_garbage_between_segments() {
+        let input = b"UNH+001+UTILMD'BGM+E03'### garbage ###\nDTM+137'+LOC+Z16'UNT+5+001'";
+        let handler = parse_lenient(input);
+
+        assert_eq!(
+            handler.segments,
+            ["UNH+001", "BGM+E03", "DTM+137", "LOC+Z16", "UNT+5"]
+        );
+        assert_eq!(
+            handler.errors,
+            [
+                "unexpected content at byte 23: \"### garbage ###\"",
+                "empty segment ID at byte 47",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_splits_unterminated_segment_at_line_break() {
+        let input = b"UNH+001+UTILMD'\nBGM+E03\nDTM+137:20250101:102'\nUNT+4+001'";
+        let handler = parse_lenient(input);
+
+        assert_eq!(
+            handler.segments,
+            ["UNH+001", "BGM+E03", "DTM+137:20250101:102", "UNT+4"]
+        );
+        assert_eq!(handler.errors, ["unterminated segment at byte 16"]);
+    }
+
+    #[test]
+    fn test_parse_lenient_falls_back_to_default_delimiters_for_invalid_una() {
+        let input = b"UNA++.? 'UNH+001+UTILMD'BGM+E03'UNT+3+001'";
+        let mut handler = EventCollector::new();
+        EdifactStreamParser::parse_lenient(input, &mut handler).unwrap();
+        assert_eq!(handler.events(), ["DELIMITERS(una=false)"]);
+
+        let handler = parse_lenient(input);
+        assert_eq!(handler.segments, ["UNH+001", "BGM+E03", "UNT+3"]);
+        assert_eq!(handler.errors, ["invalid UNA header at byte 0"]);
+    }
+
+    #[test]
+    fn test_parse_lenient_reports_dangling_release() {
+        let input = b"UNH+001+UTILMD'FTX+ACB+++Text?'\nUNT+3+001'";
+        let handler = parse_lenient(input);
+
+        assert_eq!(handler.segments, ["UNH+001", "FTX+ACB", "UNT+3"]);
+        assert_eq!(handler.errors, ["dangling release character at byte 29"]);
+    }
+
+    #[test]
+    fn test_parse_lenient_reports_truncated_input() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+REF001'UNH+001+UTILMD'BGM+E0";
+        let handler = parse_lenient(input);
+
+        assert_eq!(handler.segments, ["UNB+UNOC:3", "UNH+001", "BGM+E0"]);
+        assert_eq!(
+            handler.errors[0],
+            "unterminated segment at byte 49".to_string()
+        );
+        // End-of-input integrity errors still follow
+        assert_eq!(handler.errors.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_lenient_processes_messages_after_corrupt_one() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+REF001'\n\
+            UNH+001+UTILMD'\n\
+            BGM+E03'\n\
+            ~~~~\n\
+            UNT+3+001'\n\
+            UNH+002+UTILMD'\n\
+            BGM+E03\n\
+            UNT+3+002'\n\
+            UNZ+2+REF001'";
+        let handler = parse_lenient(input);
+
+        assert_eq!(handler.messages, 2);
+        assert_eq!(handler.errors.len(), 2);
+        assert_eq!(
+            &handler.segments[4..],
+            ["UNH+002", "BGM+E03", "UNT+3", "UNZ+2"]
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_stops_when_on_error_stops() {
+        let input = b"UNH+001+UTILMD'###'BGM+E03'UNT+3+001'";
+        let mut handler = EventCollector::new();
+        EdifactStreamParser::parse_lenient(input, &mut handler).unwrap();
+
+        // The default on_error stops on malformed input
+        assert_eq!(
+            handler.events(),
+            [
+                "DELIMITERS(una=false)",
+                "MESSAGE_START(ref=001)",
+                "SEGMENT(UNH)"
+            ]
+        );
+    }
+
     mod fuzz {
         use super::*;
         use proptest::prelude::*;
@@ -454,6 +860,24 @@ mod tests {
                 let _ = EdifactStreamParser::parse(&input, &mut handler);
             }
 
+            #[test]
+            fn parse_lenient_never_panics_on_arbitrary_input(input in proptest::collection::vec(any::<u8>(), 0..1024)) {
+                let mut handler = FuzzHandler { segment_count: 0 };
+                let _ = EdifactStreamParser::parse_lenient(&input, &mut handler);
+            }
+
+            #[test]
+            fn parse_lenient_never_panics_on_ascii_input(input in "[A-Z0-9:+.?' \n\r#]{0,512}") {
+                let mut handler = FuzzHandler { segment_count: 0 };
+                let _ = EdifactStreamParser::parse_lenient(input.as_bytes(), &mut handler);
+            }
+
+            #[test]
+            fn parse_reader_never_panics_on_arbitrary_input(input in proptest::collection::vec(any::<u8>(), 0..1024)) {
+                let mut handler = FuzzHandler { segment_count: 0 };
+                let _ = EdifactStreamParser::parse_reader(&input[..], &mut handler);
+            }
+
             #[test]
             fn parser_never_panics_on_ascii_input(input in "[A-Z0-9:+.?' \n\r]{0,512}") {
                 let mut handler = FuzzHandler { segment_count: 0 };
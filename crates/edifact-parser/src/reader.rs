@@ -0,0 +1,448 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition, SyntaxIdentifier};
+
+use crate::error::ParseError;
+use crate::integrity::IntegrityChecker;
+use crate::recovery::{self, LenientScanner, Token};
+use crate::segment_builder::SegmentBuilder;
+use crate::tokenizer::{EdifactTokenizer, LineCounter, SegmentIter};
+
+/// A parsing event produced by [`EdifactReader`].
+///
+/// The events correspond to the [`EdifactHandler`](crate::EdifactHandler)
+/// callbacks: a service segment (UNB, UNG, UNH, UNT, UNE, UNZ) yields its specific
+/// event followed by [`Event::Segment`], and integrity errors it reveals
+/// follow as [`Event::Error`].
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// Delimiters are determined (from UNA or defaults); always the first event.
+    Delimiters {
+        delimiters: EdifactDelimiters,
+        explicit_una: bool,
+    },
+    /// An interchange begins (UNB segment).
+    InterchangeStart(RawSegment<'a>),
+    /// A functional group begins (UNG segment).
+    GroupStart(RawSegment<'a>),
+    /// A message begins (UNH segment).
+    MessageStart(RawSegment<'a>),
+    /// Any segment, including service segments.
+    Segment(RawSegment<'a>),
+    /// A message ends (UNT segment).
+    MessageEnd(RawSegment<'a>),
+    /// A functional group ends (UNE segment).
+    GroupEnd(RawSegment<'a>),
+    /// An interchange ends (UNZ segment).
+    InterchangeEnd(RawSegment<'a>),
+    /// An error that leaves the segment stream intact, i.e. a
+    /// [`ParseError::Integrity`] error.
+    Error(ParseError),
+}
+
+/// Pull-based EDIFACT reader.
+///
+/// Iterates over the [`Event`]s of an interchange that has been decoded to
+/// text (see [`decode`](Self::decode)), with segments borrowing from the
+/// input. [`EdifactStreamParser`](crate::EdifactStreamParser) drives its
+/// handler from the same events.
+///
+/// Segment positions include the line and column, and segments carry the
+/// byte spans of their components; offsets refer to the decoded text.
+///
+/// Iteration yields `Err` for malformed input, which only the lenient
+/// reader detects; it then resynchronises, so iteration may go on.
+/// Integrity errors, after which the segment stream is still intact, are
+/// yielded as `Ok(Event::Error)`.
+///
+/// ```
+/// use edifact_parser::{EdifactReader, Event};
+///
+/// let input = "UNH+1+UTILMD'BGM+E03'UNT+3+1'";
+/// let mut ids = Vec::new();
+/// for event in EdifactReader::new(input) {
+///     if let Event::Segment(segment) = event? {
+///         ids.push(segment.id);
+///     }
+/// }
+/// assert_eq!(ids, ["UNH", "BGM", "UNT"]);
+/// # Ok::<(), edifact_parser::ParseError>(())
+/// ```
+pub struct EdifactReader<'a> {
+    input: &'a str,
+    lines: LineCounter,
+    delimiters: EdifactDelimiters,
+    has_una: bool,
+    builder: SegmentBuilder,
+    segments: Segments<'a>,
+    segment_number: u32,
+    message_number: u32,
+    integrity: IntegrityChecker,
+    pending: VecDeque<Result<Event<'a>, ParseError>>,
+    finished: bool,
+}
+
+/// Segment source of a reader.
+enum Segments<'a> {
+    Strict {
+        iter: SegmentIter<'a>,
+        input: &'a [u8],
+    },
+    Lenient(LenientScanner<'a>),
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Segments::Strict { iter, input } => {
+                // Segment strings are subslices of the input
+                let text = iter.next()?;
+                let offset = text.as_ptr() as usize - input.as_ptr() as usize;
+                Some(Token::Segment { text, offset })
+            }
+            Segments::Lenient(scanner) => scanner.next(),
+        }
+    }
+}
+
+impl<'a> EdifactReader<'a> {
+    /// Create a reader over decoded EDIFACT text.
+    pub fn new(input: &'a str) -> Self {
+        let (has_una, delimiters) = EdifactDelimiters::detect(input.as_bytes());
+        let content_start = if has_una { 9 } else { 0 };
+        let iter =
+            EdifactTokenizer::new(delimiters).tokenize_segments(&input.as_bytes()[content_start..]);
+        Self::with_segments(
+            input,
+            delimiters,
+            has_una,
+            Segments::Strict {
+                iter,
+                input: input.as_bytes(),
+            },
+        )
+    }
+
+    /// Create a reader that recovers from malformed input.
+    ///
+    /// Malformed input is yielded as `Err` as described for
+    /// [`EdifactStreamParser::parse_lenient`](crate::EdifactStreamParser::parse_lenient),
+    /// and reading resumes at the next plausible segment tag. An invalid
+    /// UNA is reported first and the standard delimiters are used instead.
+    pub fn lenient(input: &'a str) -> Self {
+        let bytes = input.as_bytes();
+        let (mut has_una, mut delimiters) = EdifactDelimiters::detect(bytes);
+        let mut content_start = if has_una { 9 } else { 0 };
+        let invalid_una =
+            input.starts_with("UNA") && !(has_una && recovery::plausible_delimiters(&delimiters));
+        if invalid_una {
+            has_una = false;
+            delimiters = EdifactDelimiters::default();
+            content_start = recovery::next_tag(bytes, 3, &delimiters).unwrap_or(bytes.len());
+        }
+
+        let scanner = LenientScanner::new(input, content_start, delimiters);
+        let mut reader =
+            Self::with_segments(input, delimiters, has_una, Segments::Lenient(scanner));
+        if invalid_una {
+            reader
+                .pending
+                .push_back(Err(ParseError::InvalidUna { offset: 0 }));
+        }
+        reader
+    }
+
+    fn with_segments(
+        input: &'a str,
+        delimiters: EdifactDelimiters,
+        has_una: bool,
+        segments: Segments<'a>,
+    ) -> Self {
+        let mut pending = VecDeque::new();
+        pending.push_back(Ok(Event::Delimiters {
+            delimiters,
+            explicit_una: has_una,
+        }));
+        Self {
+            input,
+            lines: LineCounter::new(),
+            delimiters,
+            has_una,
+            builder: SegmentBuilder::new(delimiters),
+            segments,
+            segment_number: 0,
+            message_number: 0,
+            integrity: IntegrityChecker::new(),
+            pending,
+            finished: false,
+        }
+    }
+
+    /// Decode raw input according to its declared syntax identifier.
+    ///
+    /// The syntax identifier in UNB S001 selects ISO-8859-1 for
+    /// UNOA/UNOB/UNOC, ISO-8859-2 for UNOD and UTF-8 for UNOW, where
+    /// invalid UTF-8 fails with [`ParseError::InvalidUtf8`]. Without a
+    /// recognised UNB, input that is valid UTF-8 is taken as is and
+    /// anything else is read as ISO-8859-1.
+    pub fn decode(input: &[u8]) -> Result<Cow<'_, str>, ParseError> {
+        let charset = match SyntaxIdentifier::detect(input) {
+            Some(charset) => charset,
+            None if std::str::from_utf8(input).is_ok() => SyntaxIdentifier::Unow,
+            None => SyntaxIdentifier::Unoc,
+        };
+        charset
+            .decode(input)
+            .map_err(|source| ParseError::InvalidUtf8 {
+                offset: source.valid_up_to(),
+                source,
+            })
+    }
+
+    /// Returns the delimiters in use.
+    pub fn delimiters(&self) -> &EdifactDelimiters {
+        &self.delimiters
+    }
+
+    /// Returns `true` if the input started with a UNA service string advice
+    /// that is used for parsing.
+    pub fn has_una(&self) -> bool {
+        self.has_una
+    }
+
+    /// Number and queue the events of one segment string.
+    ///
+    /// Segments without an ID are skipped but still counted; UNA segments
+    /// are skipped and not counted.
+    fn push_segment(&mut self, text: &'a str, offset: usize) {
+        self.segment_number += 1;
+        let position = SegmentPosition::new(self.segment_number, offset, self.message_number);
+        let Some(segment) = self.builder.build(text, position) else {
+            return;
+        };
+
+        // Skip UNA segments in content
+        if segment.is("UNA") {
+            self.segment_number -= 1; // don't count UNA
+            return;
+        }
+
+        // Track message numbering
+        if segment.is("UNH") {
+            self.message_number += 1;
+        }
+        let message_number = if ["UNB", "UNG", "UNE", "UNZ"].iter().any(|id| segment.is(id)) {
+            0
+        } else {
+            self.message_number
+        };
+        let (line, column) = self.lines.locate(self.input.as_bytes(), offset);
+        let position = SegmentPosition::new(self.segment_number, offset, message_number)
+            .with_line_column(line, column);
+        let segment = RawSegment {
+            position,
+            ..segment
+        };
+
+        self.pending
+            .extend(segment_events(segment, &mut self.integrity).map(Ok));
+    }
+}
+
+impl<'a> Iterator for EdifactReader<'a> {
+    type Item = Result<Event<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.segments.next() {
+                Some(Token::Segment { text, offset }) => self.push_segment(text, offset),
+                Some(Token::Error(error)) => return Some(Err(error)),
+                None => {
+                    self.finished = true;
+                    self.pending.extend(
+                        self.integrity
+                            .finish()
+                            .into_iter()
+                            .map(|e| Ok(Event::Error(e.into()))),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The events of a numbered segment: its service segment event if any, the
+/// segment itself, then the integrity errors it reveals.
+pub(crate) fn segment_events<'a>(
+    segment: RawSegment<'a>,
+    integrity: &mut IntegrityChecker,
+) -> impl Iterator<Item = Event<'a>> {
+    let errors = integrity.check_segment(&segment);
+    let service = match segment.id.to_ascii_uppercase().as_str() {
+        "UNB" => Some(Event::InterchangeStart(segment.clone())),
+        "UNG" => Some(Event::GroupStart(segment.clone())),
+        "UNH" => Some(Event::MessageStart(segment.clone())),
+        "UNT" => Some(Event::MessageEnd(segment.clone())),
+        "UNE" => Some(Event::GroupEnd(segment.clone())),
+        "UNZ" => Some(Event::InterchangeEnd(segment.clone())),
+        _ => None,
+    };
+    service
+        .into_iter()
+        .chain(std::iter::once(Event::Segment(segment)))
+        .chain(errors.into_iter().map(|e| Event::Error(e.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntegrityError;
+
+    /// Event names with segment IDs, for comparing event sequences.
+    fn names(reader: EdifactReader<'_>) -> Vec<String> {
+        reader
+            .map(|event| match event {
+                Ok(Event::Delimiters { explicit_una, .. }) => {
+                    format!("DELIMITERS(una={explicit_una})")
+                }
+                Ok(Event::InterchangeStart(s)) => format!("INTERCHANGE_START({})", s.id),
+                Ok(Event::GroupStart(s)) => format!("GROUP_START({})", s.id),
+                Ok(Event::MessageStart(s)) => format!("MESSAGE_START({})", s.id),
+                Ok(Event::Segment(s)) => format!("SEGMENT({})", s.id),
+                Ok(Event::MessageEnd(s)) => format!("MESSAGE_END({})", s.id),
+                Ok(Event::GroupEnd(s)) => format!("GROUP_END({})", s.id),
+                Ok(Event::InterchangeEnd(s)) => format!("INTERCHANGE_END({})", s.id),
+                Ok(Event::Error(e)) => format!("ERROR({e})"),
+                Err(e) => format!("ERR({e})"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reader_yields_service_events_before_segments() {
+        let input =
+            "UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF'UNH+1+UTILMD'BGM+E03'UNT+3+1'UNZ+1+REF'";
+
+        assert_eq!(
+            names(EdifactReader::new(input)),
+            [
+                "DELIMITERS(una=true)",
+                "INTERCHANGE_START(UNB)",
+                "SEGMENT(UNB)",
+                "MESSAGE_START(UNH)",
+                "SEGMENT(UNH)",
+                "SEGMENT(BGM)",
+                "MESSAGE_END(UNT)",
+                "SEGMENT(UNT)",
+                "INTERCHANGE_END(UNZ)",
+                "SEGMENT(UNZ)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reader_yields_group_events() {
+        let input = "UNB+UNOC:3+S+R+210101:1200+REF'UNG+UTILMD+S+R+210101:1200+G1'\
+                     UNH+1+UTILMD'UNT+2+1'UNE+1+G1'UNZ+1+REF'";
+
+        assert_eq!(
+            names(EdifactReader::new(input)),
+            [
+                "DELIMITERS(una=false)",
+                "INTERCHANGE_START(UNB)",
+                "SEGMENT(UNB)",
+                "GROUP_START(UNG)",
+                "SEGMENT(UNG)",
+                "MESSAGE_START(UNH)",
+                "SEGMENT(UNH)",
+                "MESSAGE_END(UNT)",
+                "SEGMENT(UNT)",
+                "GROUP_END(UNE)",
+                "SEGMENT(UNE)",
+                "INTERCHANGE_END(UNZ)",
+                "SEGMENT(UNZ)",
+            ]
+        );
+
+        // Group segments belong to no message
+        let numbers: Vec<u32> = EdifactReader::new(input)
+            .filter_map(|event| match event {
+                Ok(Event::Segment(s)) => Some(s.position.message_number),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(numbers, [0, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_reader_positions_borrow_from_input() {
+        let input = String::from("UNH+1+UTILMD'\r\n  BGM+E03+DOC1'UNT+3+1'");
+        let segments: Vec<RawSegment<'_>> = EdifactReader::new(&input)
+            .filter_map(|event| match event {
+                Ok(Event::Segment(segment)) => Some(segment),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(segments[1].get_element(1), "DOC1");
+        assert_eq!(
+            segments[1].position,
+            SegmentPosition::new(2, 17, 1).with_line_column(2, 3)
+        );
+        assert_eq!(
+            &input[segments[1].span.start..segments[1].span.end],
+            "BGM+E03+DOC1"
+        );
+        let doc = segments[1].component_span(1, 0).unwrap();
+        assert_eq!(&input[doc.start..doc.end], "DOC1");
+        assert_eq!(segments[2].position.byte_offset, 30);
+    }
+
+    #[test]
+    fn test_reader_yields_integrity_errors_as_events() {
+        let input = "UNH+1+UTILMD'BGM+E03'UNT+9+1'UNH+2+UTILMD'";
+        let events: Vec<_> = EdifactReader::new(input).collect();
+
+        // After the UNT revealing the wrong count, and for the open message at the end
+        assert!(matches!(events[5], Ok(Event::Segment(ref s)) if s.is("UNT")));
+        assert!(matches!(
+            events[6],
+            Ok(Event::Error(ParseError::Integrity(
+                IntegrityError::SegmentCountMismatch { actual: 3, .. }
+            )))
+        ));
+        assert!(matches!(
+            events.last(),
+            Some(Ok(Event::Error(ParseError::Integrity(
+                IntegrityError::MissingUnt { .. }
+            ))))
+        ));
+    }
+
+    #[test]
+    fn test_lenient_reader_yields_malformed_input_as_err() {
+        let input = "UNA++.? 'UNH+1+UTILMD'###'UNT+2+1'";
+
+        assert_eq!(
+            names(EdifactReader::lenient(input)),
+            [
+                "DELIMITERS(una=false)",
+                "ERR(invalid UNA header at byte 0)",
+                "MESSAGE_START(UNH)",
+                "SEGMENT(UNH)",
+                "ERR(unexpected content at byte 22: \"###'\")",
+                "MESSAGE_END(UNT)",
+                "SEGMENT(UNT)",
+            ]
+        );
+    }
+}
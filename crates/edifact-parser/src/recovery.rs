@@ -0,0 +1,248 @@
+use edifact_types::EdifactDelimiters;
+
+use crate::error::ParseError;
+
+/// Item produced by [`LenientScanner`].
+pub(crate) enum Token<'a> {
+    /// A segment without its terminator, and its byte offset in the input.
+    Segment { text: &'a str, offset: usize },
+    /// A malformation detected while scanning.
+    Error(ParseError),
+}
+
+/// Segment scanner for [`EdifactStreamParser::parse_lenient`](crate::EdifactStreamParser::parse_lenient).
+///
+/// Splits input like [`SegmentIter`](crate::tokenizer::SegmentIter), but
+/// reports malformed input as [`Token::Error`] and resynchronises at the
+/// next plausible segment tag (three letters or digits, starting with a
+/// letter, followed by an element separator or segment terminator):
+///
+/// - content that does not start with a tag is skipped up to the next tag
+///   ([`ParseError::EmptySegmentId`] or [`ParseError::UnexpectedContent`])
+/// - a line break followed by a tag ends a segment that is missing its
+///   terminator ([`ParseError::UnterminatedSegment`])
+/// - an escaped terminator followed by a line break and a tag is taken as
+///   a terminator ([`ParseError::DanglingRelease`])
+/// - a segment cut off by the end of input is reported, then still yielded
+///   (as [`ParseError::DanglingRelease`] if it ends in a release character)
+pub(crate) struct LenientScanner<'a> {
+    input: &'a str,
+    pos: usize,
+    delimiters: EdifactDelimiters,
+    /// Segment to yield after the error that was yielded for it.
+    pending: Option<Token<'a>>,
+}
+
+impl<'a> LenientScanner<'a> {
+    /// Scan `input` from byte `start` on.
+    pub(crate) fn new(input: &'a str, start: usize, delimiters: EdifactDelimiters) -> Self {
+        Self {
+            input,
+            pos: start,
+            delimiters,
+            pending: None,
+        }
+    }
+
+    /// Ends the current segment at `end` and resumes scanning at `resume`.
+    fn segment(&mut self, start: usize, end: usize, resume: usize) -> Token<'a> {
+        self.pos = resume;
+        Token::Segment {
+            text: &self.input[start..end],
+            offset: start,
+        }
+    }
+
+    /// Yields `error` now and the segment `start..end` next.
+    fn error_then_segment(
+        &mut self,
+        error: ParseError,
+        start: usize,
+        end: usize,
+        resume: usize,
+    ) -> Token<'a> {
+        self.pending = Some(self.segment(start, end, resume));
+        Token::Error(error)
+    }
+}
+
+impl<'a> Iterator for LenientScanner<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = self.pending.take() {
+            return Some(token);
+        }
+
+        let bytes = self.input.as_bytes();
+        let d = self.delimiters;
+        self.pos = skip_whitespace(bytes, self.pos);
+        if self.pos >= bytes.len() {
+            return None;
+        }
+        let start = self.pos;
+
+        // A UNA inside the content (e.g., before a second interchange)
+        if bytes[start..].starts_with(b"UNA") && bytes.len() - start >= 9 {
+            self.pos = start + 9;
+            return self.next();
+        }
+
+        if !is_tag_start(bytes, start, &d) {
+            let resume = next_tag(bytes, start + 1, &d).unwrap_or(bytes.len());
+            self.pos = resume;
+            let error = if bytes[start] == d.element {
+                ParseError::EmptySegmentId { offset: start }
+            } else {
+                ParseError::UnexpectedContent {
+                    offset: start,
+                    content: excerpt(self.input[start..resume].trim_end()),
+                }
+            };
+            return Some(Token::Error(error));
+        }
+
+        let mut i = start + 3;
+        while i < bytes.len() {
+            let b = bytes[i];
+            if b == d.release {
+                if i + 1 >= bytes.len() {
+                    // Input ends after the release character; drop it
+                    return Some(self.error_then_segment(
+                        ParseError::DanglingRelease { offset: i },
+                        start,
+                        i,
+                        bytes.len(),
+                    ));
+                }
+                if bytes[i + 1] == d.segment && line_break_then_tag(bytes, i + 2, &d).is_some() {
+                    let resume = i + 2;
+                    return Some(self.error_then_segment(
+                        ParseError::DanglingRelease { offset: i },
+                        start,
+                        i,
+                        resume,
+                    ));
+                }
+                i += 2;
+                continue;
+            }
+            if b == b'\r' || b == b'\n' {
+                if let Some(next) = line_break_then_tag(bytes, i, &d) {
+                    return Some(self.error_then_segment(
+                        ParseError::UnterminatedSegment { offset: start },
+                        start,
+                        i,
+                        next,
+                    ));
+                }
+            } else if b == d.segment {
+                return Some(self.segment(start, i, i + 1));
+            }
+            i += 1;
+        }
+
+        // Truncated input: the last segment has no terminator
+        let end = trim_end_whitespace(bytes, start, bytes.len());
+        Some(self.error_then_segment(
+            ParseError::UnterminatedSegment { offset: start },
+            start,
+            end,
+            bytes.len(),
+        ))
+    }
+}
+
+/// Whether delimiters from a UNA can be used for parsing: the component
+/// and element separators, release character and segment terminator must
+/// be distinct and neither letters, digits nor line breaks.
+pub(crate) fn plausible_delimiters(delimiters: &EdifactDelimiters) -> bool {
+    let service = [
+        delimiters.component,
+        delimiters.element,
+        delimiters.release,
+        delimiters.segment,
+    ];
+    service.iter().enumerate().all(|(i, &b)| {
+        !b.is_ascii_alphanumeric() && b != b'\r' && b != b'\n' && !service[i + 1..].contains(&b)
+    })
+}
+
+/// Position of the next plausible segment tag at or after `from`.
+pub(crate) fn next_tag(bytes: &[u8], from: usize, delimiters: &EdifactDelimiters) -> Option<usize> {
+    (from..bytes.len()).find(|&i| is_tag_start(bytes, i, delimiters))
+}
+
+/// Whether a segment tag starts at `i`: a letter and two letters or digits,
+/// followed by an element separator or segment terminator.
+fn is_tag_start(bytes: &[u8], i: usize, delimiters: &EdifactDelimiters) -> bool {
+    bytes.len() > i + 3
+        && bytes[i].is_ascii_alphabetic()
+        && bytes[i + 1].is_ascii_alphanumeric()
+        && bytes[i + 2].is_ascii_alphanumeric()
+        && (bytes[i + 3] == delimiters.element || bytes[i + 3] == delimiters.segment)
+}
+
+/// If a line break at `i` is followed by a segment tag, the tag's position.
+fn line_break_then_tag(bytes: &[u8], i: usize, delimiters: &EdifactDelimiters) -> Option<usize> {
+    if !matches!(bytes.get(i), Some(b'\r' | b'\n')) {
+        return None;
+    }
+    let next = skip_whitespace(bytes, i);
+    is_tag_start(bytes, next, delimiters).then_some(next)
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && matches!(bytes[i], b'\r' | b'\n' | b' ' | b'\t') {
+        i += 1;
+    }
+    i
+}
+
+fn trim_end_whitespace(bytes: &[u8], start: usize, mut end: usize) -> usize {
+    while end > start && matches!(bytes[end - 1], b'\r' | b'\n' | b' ' | b'\t') {
+        end -= 1;
+    }
+    end
+}
+
+/// Shortens skipped content for error messages.
+fn excerpt(content: &str) -> String {
+    const MAX_CHARS: usize = 32;
+    match content.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &content[..end]),
+        None => content.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plausible_delimiters() {
+        assert!(plausible_delimiters(&EdifactDelimiters::default()));
+        assert!(plausible_delimiters(
+            &EdifactDelimiters::from_una(b"UNA|*.# ~").unwrap()
+        ));
+        assert!(!plausible_delimiters(
+            &EdifactDelimiters::from_una(b"UNA++.? '").unwrap()
+        ));
+        assert!(!plausible_delimiters(
+            &EdifactDelimiters::from_una(b"UNA:+.A '").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_next_tag_requires_separator_after_tag() {
+        let d = EdifactDelimiters::default();
+        assert_eq!(next_tag(b"xx BGMX UNT+1'", 0, &d), Some(8));
+        assert_eq!(next_tag(b"garbage only", 0, &d), None);
+    }
+
+    #[test]
+    fn test_excerpt_shortens_long_content() {
+        assert_eq!(excerpt("short"), "short");
+        assert_eq!(excerpt(&"x".repeat(40)), format!("{}...", "x".repeat(32)));
+    }
+}
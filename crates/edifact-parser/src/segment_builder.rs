@@ -1,4 +1,4 @@
-use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition};
+use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition, Span};
 
 use crate::EdifactTokenizer;
 
@@ -23,6 +23,9 @@ impl SegmentBuilder {
     /// The input is a single segment WITHOUT its terminator character.
     /// Example: `"NAD+Z04+9900123000002::293"`
     ///
+    /// Spans are computed relative to `position.byte_offset`, which is taken
+    /// to be the offset of `segment_str` in the input.
+    ///
     /// Returns `None` if the segment string is empty.
     pub fn build<'a>(
         &self,
@@ -41,15 +44,28 @@ impl SegmentBuilder {
             return None;
         }
 
+        // Components are subslices of `segment_str`, so their spans follow
+        // from their address
+        let base = segment_str.as_ptr() as usize;
+        let span_of = |text: &str| {
+            let start = position.byte_offset + (text.as_ptr() as usize - base);
+            Span::new(start, start + text.len())
+        };
+
         // Remaining elements are data elements, each split into components
         let mut elements = Vec::new();
+        let mut component_spans = Vec::new();
         for element_str in elements_iter {
             let components: Vec<&'a str> =
                 self.tokenizer.tokenize_components(element_str).collect();
+            component_spans.push(components.iter().map(|c| span_of(c)).collect());
             elements.push(components);
         }
 
-        Some(RawSegment::new(id, elements, position))
+        Some(
+            RawSegment::new(id, elements, position)
+                .with_spans(span_of(segment_str), component_spans),
+        )
     }
 }
 
@@ -142,6 +158,19 @@ mod tests {
         assert_eq!(seg.position.message_number, 1);
     }
 
+    #[test]
+    fn test_build_computes_spans_from_position() {
+        let builder = SegmentBuilder::new(EdifactDelimiters::default());
+        let seg = builder.build("NAD+Z04+99::293", pos(5, 100)).unwrap();
+
+        assert_eq!(seg.span, Span::new(100, 115));
+        assert_eq!(seg.component_span(0, 0), Some(Span::new(104, 107)));
+        assert_eq!(seg.component_span(1, 0), Some(Span::new(108, 110)));
+        assert_eq!(seg.component_span(1, 1), Some(Span::new(111, 111)));
+        assert_eq!(seg.component_span(1, 2), Some(Span::new(112, 115)));
+        assert_eq!(seg.element_span(1), Some(Span::new(108, 115)));
+    }
+
     #[test]
     fn test_build_rff_segment() {
         let builder = SegmentBuilder::new(EdifactDelimiters::default());
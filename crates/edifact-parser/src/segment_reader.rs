@@ -0,0 +1,417 @@
+use std::borrow::Cow;
+use std::io::{BufRead, Cursor, Read};
+
+use edifact_types::{EdifactDelimiters, RawSegment, SegmentPosition, Span, SyntaxIdentifier};
+
+use crate::error::ParseError;
+use crate::segment_builder::SegmentBuilder;
+use crate::tokenizer::LineCounter;
+
+/// Incremental EDIFACT segment reader over any `BufRead`.
+///
+/// Reads one segment at a time into an internal buffer that is reused for
+/// the next segment, so memory use is bounded by the largest single segment
+/// rather than the size of the interchange. Segment splitting, release
+/// character handling and position numbering follow
+/// [`EdifactStreamParser::parse`](crate::EdifactStreamParser::parse).
+///
+/// Segments are decoded according to the syntax identifier of the UNB, as
+/// in [`parse`](crate::EdifactStreamParser::parse). Before a UNB (or if it
+/// declares no supported identifier), a segment that is not valid UTF-8 is
+/// read as ISO-8859-1. Byte offsets, lines, columns and spans refer to the
+/// original input.
+///
+/// # Example
+///
+/// ```
+/// use edifact_parser::SegmentReader;
+///
+/// let input = b"UNA:+.? 'UNB+UNOC:3'UNH+1+MSCONS:D:04B:UN:2.4c'UNT+2+1'UNZ+1'";
+/// let mut reader = SegmentReader::new(&input[..]).unwrap();
+/// let mut ids = Vec::new();
+/// while let Some(segment) = reader.next_segment().unwrap() {
+///     ids.push(segment.id.to_string());
+/// }
+/// assert_eq!(ids, ["UNB", "UNH", "UNT", "UNZ"]);
+/// ```
+pub struct SegmentReader<R> {
+    reader: std::io::Chain<Cursor<Vec<u8>>, R>,
+    state: SegmentState,
+}
+
+impl<R: BufRead> SegmentReader<R> {
+    /// Creates a reader, consuming a leading UNA service string advice if
+    /// present to determine the delimiters.
+    pub fn new(mut reader: R) -> Result<Self, ParseError> {
+        let mut header = Vec::with_capacity(9);
+        (&mut reader).take(9).read_to_end(&mut header)?;
+
+        let state = SegmentState::new(&mut header);
+        Ok(Self {
+            reader: Cursor::new(header).chain(reader),
+            state,
+        })
+    }
+
+    /// The delimiters in effect (from UNA or defaults).
+    pub fn delimiters(&self) -> &EdifactDelimiters {
+        self.state.delimiters()
+    }
+
+    /// Whether the input started with an explicit UNA segment.
+    pub fn has_una(&self) -> bool {
+        self.state.has_una()
+    }
+
+    /// Reads the next segment.
+    ///
+    /// Returns `Ok(None)` at end of input. The returned segment borrows the
+    /// reader's buffer and must be dropped (or copied) before the next call.
+    pub fn next_segment(&mut self) -> Result<Option<RawSegment<'_>>, ParseError> {
+        let id_len = loop {
+            if !self.read_raw()? {
+                return Ok(None);
+            }
+            if let Some(id_len) = self.state.segment_id_len() {
+                break id_len;
+            }
+        };
+        self.state.build(id_len)
+    }
+
+    /// Reads the bytes of the next segment into the state's buffer.
+    ///
+    /// Returns `false` if the input is exhausted before a segment starts.
+    fn read_raw(&mut self) -> Result<bool, ParseError> {
+        self.state.begin();
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if available.is_empty() {
+                // Content after the last terminator is still a segment
+                return Ok(self.state.started());
+            }
+
+            let (used, done) = self.state.feed(available);
+            self.reader.consume(used);
+            if done {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+/// I/O independent state of an incremental segment reader, shared by
+/// [`SegmentReader`] and the async reader.
+///
+/// A segment is read by calling [`begin`](Self::begin), then
+/// [`feed`](Self::feed) with buffered input until it reports the
+/// terminator or the input ends, then [`segment_id_len`](Self::segment_id_len)
+/// and [`build`](Self::build).
+pub(crate) struct SegmentState {
+    delimiters: EdifactDelimiters,
+    has_una: bool,
+    builder: SegmentBuilder,
+    /// Bytes of the current segment, without terminator.
+    raw: Vec<u8>,
+    /// Decoded copy of `raw` when it is not UTF-8.
+    decoded: String,
+    /// Character set declared in the UNB, once read.
+    charset: Option<SyntaxIdentifier>,
+    /// Number of input bytes consumed so far.
+    offset: usize,
+    /// Byte offset of the current segment in the input.
+    segment_offset: usize,
+    lines: LineCounter,
+    /// Line and column of the current segment.
+    segment_line_column: (u32, u32),
+    segment_number: u32,
+    message_number: u32,
+    /// Whether the current segment has started (leading whitespace skipped).
+    started: bool,
+    /// Whether the previous byte was an unescaped release character.
+    escaped: bool,
+}
+
+impl SegmentState {
+    /// Creates the state from the first (up to) 9 bytes of input.
+    ///
+    /// A UNA in `header` determines the delimiters and is cleared from it;
+    /// otherwise `header` is left for the reader to consume as content.
+    pub(crate) fn new(header: &mut Vec<u8>) -> Self {
+        let (has_una, delimiters) = EdifactDelimiters::detect(header);
+        let mut lines = LineCounter::new();
+        let offset = if has_una {
+            lines.scan(header);
+            header.clear();
+            9
+        } else {
+            0
+        };
+        Self {
+            delimiters,
+            has_una,
+            builder: SegmentBuilder::new(delimiters),
+            raw: Vec::new(),
+            decoded: String::new(),
+            charset: None,
+            offset,
+            segment_offset: offset,
+            lines,
+            segment_line_column: (0, 0),
+            segment_number: 0,
+            message_number: 0,
+            started: false,
+            escaped: false,
+        }
+    }
+
+    pub(crate) fn delimiters(&self) -> &EdifactDelimiters {
+        &self.delimiters
+    }
+
+    pub(crate) fn has_una(&self) -> bool {
+        self.has_una
+    }
+
+    /// Whether the current segment has started.
+    pub(crate) fn started(&self) -> bool {
+        self.started
+    }
+
+    /// Starts reading a new segment.
+    pub(crate) fn begin(&mut self) {
+        self.raw.clear();
+        self.started = false;
+        self.escaped = false;
+    }
+
+    /// Consumes bytes of the current segment from `available`, skipping
+    /// whitespace before it and consuming its terminator.
+    ///
+    /// Returns how many bytes were used and whether the terminator was
+    /// reached.
+    pub(crate) fn feed(&mut self, available: &[u8]) -> (usize, bool) {
+        let terminator = self.delimiters.segment;
+        let release = self.delimiters.release;
+        let mut used = 0;
+        let mut scanned = 0;
+        let mut done = false;
+        for &b in available {
+            used += 1;
+            if !self.started {
+                if matches!(b, b'\r' | b'\n' | b' ' | b'\t') {
+                    continue;
+                }
+                self.started = true;
+                self.segment_offset = self.offset + used - 1;
+                scanned = used - 1;
+                self.lines.scan(&available[..scanned]);
+                self.segment_line_column = self.lines.line_column(self.segment_offset);
+            }
+            if self.escaped {
+                self.escaped = false;
+            } else if b == release {
+                self.escaped = true;
+            } else if b == terminator {
+                done = true;
+                break;
+            }
+            self.raw.push(b);
+        }
+        self.lines.scan(&available[scanned..used]);
+        self.offset += used;
+        (used, done)
+    }
+
+    /// The length of the current segment's ID, or `None` if the segment is
+    /// to be skipped: empty segments, UNA, and segments without an ID,
+    /// which are still counted.
+    pub(crate) fn segment_id_len(&mut self) -> Option<usize> {
+        let segment = trim_crlf(&self.raw);
+        if segment.is_empty() {
+            return None;
+        }
+        let id_len = segment
+            .iter()
+            .position(|&b| b == self.delimiters.element)
+            .unwrap_or(segment.len());
+        if id_len == 0 {
+            // Segments without an ID are skipped but still counted
+            self.segment_number += 1;
+            return None;
+        }
+        (!segment[..id_len].eq_ignore_ascii_case(b"UNA")).then_some(id_len)
+    }
+
+    /// Numbers, decodes and builds the current segment.
+    pub(crate) fn build(&mut self, id_len: usize) -> Result<Option<RawSegment<'_>>, ParseError> {
+        self.segment_number += 1;
+        let segment = trim_crlf(&self.raw);
+        let id = &segment[..id_len];
+        if id.eq_ignore_ascii_case(b"UNH") {
+            self.message_number += 1;
+        }
+        let envelope = [b"UNB", b"UNG", b"UNE", b"UNZ"];
+        let message_number = if envelope.iter().any(|tag| id.eq_ignore_ascii_case(*tag)) {
+            0
+        } else {
+            self.message_number
+        };
+        let (line, column) = self.segment_line_column;
+        let position =
+            SegmentPosition::new(self.segment_number, self.segment_offset, message_number)
+                .with_line_column(line, column);
+
+        if id.eq_ignore_ascii_case(b"UNB") {
+            self.charset = SyntaxIdentifier::from_unb(segment, &self.delimiters);
+        }
+        let charset = match self.charset {
+            Some(charset) => charset,
+            None if std::str::from_utf8(segment).is_ok() => SyntaxIdentifier::Unow,
+            None => SyntaxIdentifier::Unoc,
+        };
+        match charset.decode(segment) {
+            Ok(Cow::Borrowed(text)) => Ok(self.builder.build(text, position)),
+            Ok(Cow::Owned(decoded)) => {
+                self.decoded = decoded;
+                let segment = self.builder.build(&self.decoded, position);
+                Ok(segment.map(|s| raw_spans(s, &self.decoded, self.segment_offset)))
+            }
+            Err(source) => Err(ParseError::InvalidUtf8 {
+                offset: self.segment_offset + source.valid_up_to(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Maps the spans of a segment built from `decoded` text back to the raw
+/// input, in which the decoded charsets use one byte per character.
+fn raw_spans<'a>(segment: RawSegment<'a>, decoded: &str, offset: usize) -> RawSegment<'a> {
+    let raw = |span: Span| {
+        let start = offset + decoded[..span.start - offset].chars().count();
+        Span::new(
+            start,
+            start
+                + decoded[span.start - offset..span.end - offset]
+                    .chars()
+                    .count(),
+        )
+    };
+    let span = raw(segment.span);
+    let component_spans = segment
+        .component_spans
+        .iter()
+        .map(|e| e.iter().map(|&c| raw(c)).collect())
+        .collect();
+    segment.with_spans(span, component_spans)
+}
+
+/// Strips leading and trailing `\r` / `\n` bytes, like the slice tokenizer.
+fn trim_crlf(bytes: &[u8]) -> &[u8] {
+    let is_crlf = |b: &u8| *b == b'\r' || *b == b'\n';
+    let start = bytes
+        .iter()
+        .position(|b| !is_crlf(b))
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !is_crlf(b))
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    fn read_all(input: &[u8]) -> Vec<(String, SegmentPosition, Vec<Vec<String>>)> {
+        // A tiny buffer forces segments to span several fill_buf calls
+        let mut reader = SegmentReader::new(BufReader::with_capacity(3, input)).unwrap();
+        let mut out = Vec::new();
+        while let Some(seg) = reader.next_segment().unwrap() {
+            out.push((
+                seg.id.to_string(),
+                seg.position,
+                seg.elements
+                    .iter()
+                    .map(|e| e.iter().map(|c| c.to_string()).collect())
+                    .collect(),
+            ));
+        }
+        out
+    }
+
+    #[test]
+    fn test_reader_detects_una_and_numbers_segments() {
+        let input = b"UNA:+.? 'UNB+UNOC:3'UNH+001'BGM+E03'UNT+2+001'UNH+002'UNT+1+002'UNZ+2'";
+        let segments = read_all(input);
+
+        let summary: Vec<(&str, u32, u32)> = segments
+            .iter()
+            .map(|(id, pos, _)| (id.as_str(), pos.segment_number, pos.message_number))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("UNB", 1, 0),
+                ("UNH", 2, 1),
+                ("BGM", 3, 1),
+                ("UNT", 4, 1),
+                ("UNH", 5, 2),
+                ("UNT", 6, 2),
+                ("UNZ", 7, 0),
+            ]
+        );
+        // Offsets point at the segment tag in the original input
+        assert_eq!(segments[0].1.byte_offset, 9);
+        assert_eq!(segments[1].1.byte_offset, 20);
+    }
+
+    #[test]
+    fn test_reader_respects_release_character_and_line_breaks() {
+        let input = b"UNA:+.? '\r\nDTM+137:202506190130?+00:303'\r\nFTX+ACB+++a?'b'\n";
+        let segments = read_all(input);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].2[0][1], "202506190130?+00");
+        assert_eq!(segments[1].0, "FTX");
+        assert_eq!(segments[1].2[3][0], "a?'b");
+        assert_eq!((segments[0].1.line, segments[0].1.column), (2, 1));
+        assert_eq!((segments[1].1.line, segments[1].1.column), (3, 1));
+    }
+
+    #[test]
+    fn test_reader_without_una_uses_defaults() {
+        let mut reader = SegmentReader::new(&b"UNB+UNOC:3'UNZ+0'"[..]).unwrap();
+        assert!(!reader.has_una());
+        assert_eq!(*reader.delimiters(), EdifactDelimiters::default());
+        assert_eq!(reader.next_segment().unwrap().unwrap().id, "UNB");
+        assert_eq!(reader.next_segment().unwrap().unwrap().id, "UNZ");
+        assert!(reader.next_segment().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reader_transcodes_iso_8859_1_segments() {
+        let input: Vec<u8> = [b"UNA:+.? 'NAD+Z09+++M".as_slice(), &[0xFC], b"ller'"].concat();
+        let segments = read_all(&input);
+        assert_eq!(segments[0].2[3][0], "Müller");
+
+        // Spans count the single input byte of 'ü'
+        let mut reader = SegmentReader::new(&input[..]).unwrap();
+        let nad = reader.next_segment().unwrap().unwrap();
+        assert_eq!(nad.span, Span::new(9, 25));
+        assert_eq!(nad.component_span(3, 0), Some(Span::new(19, 25)));
+    }
+
+    #[test]
+    fn test_reader_empty_input() {
+        let mut reader = SegmentReader::new(&b""[..]).unwrap();
+        assert!(reader.next_segment().unwrap().is_none());
+    }
+}
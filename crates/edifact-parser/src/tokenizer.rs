@@ -245,6 +245,55 @@ impl<'a> Iterator for ComponentIter<'a> {
     }
 }
 
+/// Line numbering of input that is scanned front to back.
+///
+/// Lines end at `\n`, so `\r\n` line breaks count once; columns count
+/// bytes from 1.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LineCounter {
+    line: u32,
+    /// Byte offset of the first byte of the current line.
+    line_start: usize,
+    /// Number of input bytes scanned so far.
+    scanned: usize,
+}
+
+impl LineCounter {
+    pub(crate) fn new() -> Self {
+        Self {
+            line: 1,
+            line_start: 0,
+            scanned: 0,
+        }
+    }
+
+    /// Scans `bytes`, the input following what was scanned so far.
+    pub(crate) fn scan(&mut self, bytes: &[u8]) {
+        for (i, &b) in bytes.iter().enumerate() {
+            if b == b'\n' {
+                self.line += 1;
+                self.line_start = self.scanned + i + 1;
+            }
+        }
+        self.scanned += bytes.len();
+    }
+
+    /// Line and column of `offset`, which must not lie before the start of
+    /// the current line or after the scanned input.
+    pub(crate) fn line_column(&self, offset: usize) -> (u32, u32) {
+        (self.line, (offset - self.line_start) as u32 + 1)
+    }
+
+    /// Scans `input` (the whole input) up to `offset` and returns the line
+    /// and column there. Offsets must not decrease between calls.
+    pub(crate) fn locate(&mut self, input: &[u8], offset: usize) -> (u32, u32) {
+        if offset > self.scanned {
+            self.scan(&input[self.scanned..offset]);
+        }
+        self.line_column(offset)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,4 +427,14 @@ mod tests {
         let dtm_components: Vec<&str> = tokenizer.tokenize_components(dtm_elements[1]).collect();
         assert_eq!(dtm_components, vec!["137", "202501010000?+01", "303"]);
     }
+
+    #[test]
+    fn test_line_counter() {
+        let input = b"UNB+X'\r\nUNH+1'\nBGM'DTM'";
+        let mut lines = LineCounter::new();
+        assert_eq!(lines.locate(input, 0), (1, 1));
+        assert_eq!(lines.locate(input, 8), (2, 1));
+        assert_eq!(lines.locate(input, 15), (3, 1));
+        assert_eq!(lines.locate(input, 19), (3, 5));
+    }
 }
@@ -0,0 +1,456 @@
+use std::borrow::Cow;
+
+use crate::EdifactDelimiters;
+
+/// Syntax identifier from UNB S001 (DE 0001), naming the character set of
+/// an interchange.
+///
+/// Determines both how input bytes are decoded and which characters data
+/// element values may contain (the character repertoire).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxIdentifier {
+    /// Level A: uppercase letters, digits, space and `.,-()/'+:=?!"%&*;<>`.
+    Unoa,
+    /// Level B: level A plus lowercase letters.
+    Unob,
+    /// ISO 8859-1 (Latin-1), the BDEW default.
+    Unoc,
+    /// ISO 8859-2 (Latin-2).
+    Unod,
+    /// ISO 10646 encoded as UTF-8.
+    Unow,
+}
+
+/// Graphic characters of level A besides letters and digits.
+const LEVEL_A_SPECIAL: &str = " .,-()/'+:=?!\"%&*;<>";
+
+/// ISO 8859-2 characters for the bytes 0xA0–0xFF.
+const LATIN2_HIGH: [char; 96] = [
+    '\u{00A0}', '\u{0104}', '\u{02D8}', '\u{0141}', '\u{00A4}', '\u{013D}', '\u{015A}', '\u{00A7}',
+    '\u{00A8}', '\u{0160}', '\u{015E}', '\u{0164}', '\u{0179}', '\u{00AD}', '\u{017D}', '\u{017B}',
+    '\u{00B0}', '\u{0105}', '\u{02DB}', '\u{0142}', '\u{00B4}', '\u{013E}', '\u{015B}', '\u{02C7}',
+    '\u{00B8}', '\u{0161}', '\u{015F}', '\u{0165}', '\u{017A}', '\u{02DD}', '\u{017E}', '\u{017C}',
+    '\u{0154}', '\u{00C1}', '\u{00C2}', '\u{0102}', '\u{00C4}', '\u{0139}', '\u{0106}', '\u{00C7}',
+    '\u{010C}', '\u{00C9}', '\u{0118}', '\u{00CB}', '\u{011A}', '\u{00CD}', '\u{00CE}', '\u{010E}',
+    '\u{0110}', '\u{0143}', '\u{0147}', '\u{00D3}', '\u{00D4}', '\u{0150}', '\u{00D6}', '\u{00D7}',
+    '\u{0158}', '\u{016E}', '\u{00DA}', '\u{0170}', '\u{00DC}', '\u{00DD}', '\u{0162}', '\u{00DF}',
+    '\u{0155}', '\u{00E1}', '\u{00E2}', '\u{0103}', '\u{00E4}', '\u{013A}', '\u{0107}', '\u{00E7}',
+    '\u{010D}', '\u{00E9}', '\u{0119}', '\u{00EB}', '\u{011B}', '\u{00ED}', '\u{00EE}', '\u{010F}',
+    '\u{0111}', '\u{0144}', '\u{0148}', '\u{00F3}', '\u{00F4}', '\u{0151}', '\u{00F6}', '\u{00F7}',
+    '\u{0159}', '\u{016F}', '\u{00FA}', '\u{0171}', '\u{00FC}', '\u{00FD}', '\u{0163}', '\u{02D9}',
+];
+
+impl SyntaxIdentifier {
+    /// Parse a syntax identifier code such as `UNOC` (case-insensitive).
+    ///
+    /// Returns `None` for identifiers this crate does not support.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "UNOA" => Some(Self::Unoa),
+            "UNOB" => Some(Self::Unob),
+            "UNOC" => Some(Self::Unoc),
+            "UNOD" => Some(Self::Unod),
+            "UNOW" => Some(Self::Unow),
+            _ => None,
+        }
+    }
+
+    /// The code as written in UNB S001, e.g. `"UNOC"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Unoa => "UNOA",
+            Self::Unob => "UNOB",
+            Self::Unoc => "UNOC",
+            Self::Unod => "UNOD",
+            Self::Unow => "UNOW",
+        }
+    }
+
+    /// Detect the syntax identifier declared in the UNB of raw input.
+    ///
+    /// Looks at the first segment after an optional UNA. Returns `None` if
+    /// the input does not start with UNB or declares an unsupported
+    /// identifier. Service segment tags and delimiters are always ASCII, so
+    /// this works before the input has been decoded.
+    pub fn detect(input: &[u8]) -> Option<Self> {
+        let (has_una, delimiters) = EdifactDelimiters::detect(input);
+        let mut rest = if has_una { &input[9..] } else { input };
+        while let [b'\r' | b'\n' | b' ' | b'\t', tail @ ..] = rest {
+            rest = tail;
+        }
+        Self::from_unb(rest, &delimiters)
+    }
+
+    /// Read the syntax identifier from the raw bytes of a UNB segment.
+    ///
+    /// Returns `None` if `unb` is not a UNB segment or declares an
+    /// unsupported identifier.
+    pub fn from_unb(unb: &[u8], delimiters: &EdifactDelimiters) -> Option<Self> {
+        if unb.len() < 4 || !unb[..3].eq_ignore_ascii_case(b"UNB") || unb[3] != delimiters.element {
+            return None;
+        }
+
+        let value = &unb[4..];
+        let end = value
+            .iter()
+            .position(|&b| {
+                b == delimiters.component || b == delimiters.element || b == delimiters.segment
+            })
+            .unwrap_or(value.len());
+        std::str::from_utf8(&value[..end])
+            .ok()
+            .and_then(Self::from_code)
+    }
+
+    /// Whether `c` belongs to the character repertoire of this syntax level.
+    ///
+    /// Control characters are outside every repertoire.
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Self::Unoa => {
+                c.is_ascii_uppercase() || c.is_ascii_digit() || LEVEL_A_SPECIAL.contains(c)
+            }
+            Self::Unob => c.is_ascii_alphanumeric() || LEVEL_A_SPECIAL.contains(c),
+            Self::Unoc => matches!(c, ' '..='~' | '\u{A0}'..='\u{FF}'),
+            Self::Unod => matches!(c, ' '..='~') || LATIN2_HIGH.contains(&c),
+            Self::Unow => !c.is_control(),
+        }
+    }
+
+    /// Decode input bytes in this character set.
+    ///
+    /// UNOA and UNOB are ASCII subsets; bytes above 0x7F are decoded as
+    /// ISO 8859-1 so that repertoire validation can report them instead of
+    /// losing them. Only UNOW can fail, on invalid UTF-8.
+    pub fn decode<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, str>, std::str::Utf8Error> {
+        if *self == Self::Unow || input.is_ascii() {
+            return std::str::from_utf8(input).map(Cow::Borrowed);
+        }
+        let decoded = input
+            .iter()
+            .map(|&b| match self {
+                Self::Unod if b >= 0xA0 => LATIN2_HIGH[usize::from(b - 0xA0)],
+                _ => char::from(b),
+            })
+            .collect();
+        Ok(Cow::Owned(decoded))
+    }
+
+    /// Encode text into bytes of this character set.
+    ///
+    /// Fails on the first character that has no byte representation (e.g.
+    /// `€` in UNOC). This does not check the repertoire beyond that: use
+    /// [`to_repertoire`](Self::to_repertoire) on values first.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, EncodeError> {
+        if *self == Self::Unow {
+            return Ok(text.as_bytes().to_vec());
+        }
+        let mut out = Vec::with_capacity(text.len());
+        for (offset, c) in text.char_indices() {
+            let byte = match self {
+                _ if c.is_ascii() => Some(c as u8),
+                Self::Unoc => u8::try_from(u32::from(c)).ok(),
+                Self::Unod => LATIN2_HIGH
+                    .iter()
+                    .position(|&l| l == c)
+                    .map(|i| 0xA0 + i as u8),
+                _ => None,
+            };
+            match byte {
+                Some(byte) => out.push(byte),
+                None => {
+                    return Err(EncodeError {
+                        character: c,
+                        offset,
+                        charset: *self,
+                    })
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Bring a value into the repertoire of this syntax level.
+    ///
+    /// Characters outside the repertoire are transliterated if enabled in
+    /// `options`, otherwise replaced by `options.replacement`; if neither
+    /// applies, an error names the first offending character.
+    pub fn to_repertoire<'a>(
+        &self,
+        value: &'a str,
+        options: &EncodeOptions,
+    ) -> Result<Cow<'a, str>, EncodeError> {
+        if value.chars().all(|c| self.contains(c)) {
+            return Ok(Cow::Borrowed(value));
+        }
+
+        let mut out = String::with_capacity(value.len());
+        for (offset, c) in value.char_indices() {
+            if self.contains(c) {
+                out.push(c);
+                continue;
+            }
+            if options.transliterate {
+                if let Some(replacement) = self.transliterate(c) {
+                    out.push_str(&replacement);
+                    continue;
+                }
+            }
+            match options.replacement {
+                Some(replacement) if self.contains(replacement) => out.push(replacement),
+                _ => {
+                    return Err(EncodeError {
+                        character: c,
+                        offset,
+                        charset: *self,
+                    })
+                }
+            }
+        }
+        Ok(Cow::Owned(out))
+    }
+
+    /// A replacement for `c` within the repertoire, if there is one.
+    fn transliterate(&self, c: char) -> Option<String> {
+        let mut candidate = transliterate_char(c).unwrap_or_else(|| c.to_string());
+        if *self == Self::Unoa {
+            candidate = candidate.to_uppercase();
+        }
+        candidate
+            .chars()
+            .all(|t| self.contains(t))
+            .then_some(candidate)
+    }
+}
+
+impl std::fmt::Display for SyntaxIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Spell out characters that are missing from the smaller repertoires.
+///
+/// German umlauts follow DIN 5007 (`ä` → `ae`); other accented letters
+/// lose their diacritics. Uppercase input yields a capitalised result.
+fn transliterate_char(c: char) -> Option<String> {
+    let mut lower = c.to_lowercase();
+    let base = match (lower.next()?, lower.next()) {
+        (l, None) => l,
+        _ => return None,
+    };
+    let spelled = match base {
+        'ä' | 'æ' => "ae",
+        'ö' | 'œ' => "oe",
+        'ü' => "ue",
+        'ß' => "ss",
+        'à' | 'á' | 'â' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+        'ĺ' | 'ľ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ø' | 'ō' | 'ő' => "o",
+        'ŕ' | 'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ţ' | 'ť' => "t",
+        'ù' | 'ú' | 'û' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        '€' => "EUR",
+        '‘' | '’' | '‚' | '´' | '`' => "'",
+        '“' | '”' | '„' | '«' | '»' => "\"",
+        '‐' | '–' | '—' => "-",
+        '…' => "...",
+        '\u{A0}' | '\t' => " ",
+        _ => return None,
+    };
+    if c.is_uppercase() {
+        let mut chars = spelled.chars();
+        let first = chars.next()?.to_uppercase();
+        Some(first.chain(chars).collect())
+    } else {
+        Some(spelled.to_string())
+    }
+}
+
+/// How [`SyntaxIdentifier::to_repertoire`] handles characters outside the
+/// repertoire. The default rejects them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Replace characters by similar ones (`ä` → `ae`, `é` → `e`, and
+    /// lowercase → uppercase for UNOA).
+    pub transliterate: bool,
+    /// Substitute for characters that remain unrepresentable.
+    pub replacement: Option<char>,
+}
+
+/// A character cannot be represented in the target character set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeError {
+    /// The offending character.
+    pub character: char,
+    /// Byte offset of the character in the encoded text.
+    pub offset: usize,
+    /// The target character set.
+    pub charset: SyntaxIdentifier,
+}
+
+impl std::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "character {:?} (U+{:04X}) at byte {} cannot be represented in {}",
+            self.character,
+            u32::from(self.character),
+            self.offset,
+            self.charset
+        )
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code_and_display() {
+        assert_eq!(
+            SyntaxIdentifier::from_code("unoc"),
+            Some(SyntaxIdentifier::Unoc)
+        );
+        assert_eq!(SyntaxIdentifier::from_code("UNOY"), None);
+        assert_eq!(SyntaxIdentifier::Unow.to_string(), "UNOW");
+    }
+
+    #[test]
+    fn test_detect_from_unb() {
+        assert_eq!(
+            SyntaxIdentifier::detect(b"UNA:+.? '\r\nUNB+UNOA:3+S+R'"),
+            Some(SyntaxIdentifier::Unoa)
+        );
+        assert_eq!(
+            SyntaxIdentifier::detect(b"UNA|*.? 'UNB*UNOW|3*S'"),
+            Some(SyntaxIdentifier::Unow)
+        );
+        assert_eq!(
+            SyntaxIdentifier::detect(b"UNB+UNOD'"),
+            Some(SyntaxIdentifier::Unod)
+        );
+        assert_eq!(SyntaxIdentifier::detect(b"UNH+1+UTILMD'"), None);
+        assert_eq!(SyntaxIdentifier::detect(b""), None);
+    }
+
+    #[test]
+    fn test_repertoires() {
+        assert!(SyntaxIdentifier::Unoa.contains('Z'));
+        assert!(SyntaxIdentifier::Unoa.contains('\''));
+        assert!(!SyntaxIdentifier::Unoa.contains('z'));
+        assert!(SyntaxIdentifier::Unob.contains('z'));
+        assert!(!SyntaxIdentifier::Unob.contains('#'));
+        assert!(SyntaxIdentifier::Unoc.contains('ß'));
+        assert!(!SyntaxIdentifier::Unoc.contains('€'));
+        assert!(!SyntaxIdentifier::Unoc.contains('\n'));
+        assert!(SyntaxIdentifier::Unod.contains('Ł'));
+        assert!(!SyntaxIdentifier::Unod.contains('ñ'));
+        assert!(SyntaxIdentifier::Unow.contains('€'));
+    }
+
+    #[test]
+    fn test_decode_single_byte_charsets() {
+        assert_eq!(SyntaxIdentifier::Unoc.decode(&[b'M', 0xFC]).unwrap(), "Mü");
+        assert_eq!(SyntaxIdentifier::Unod.decode(&[0xA3, 0xF3]).unwrap(), "Łó");
+        assert_eq!(SyntaxIdentifier::Unoa.decode(&[0xE9]).unwrap(), "é");
+        assert!(matches!(
+            SyntaxIdentifier::Unoc.decode(b"plain").unwrap(),
+            Cow::Borrowed("plain")
+        ));
+    }
+
+    #[test]
+    fn test_decode_unow_rejects_invalid_utf8() {
+        assert_eq!(SyntaxIdentifier::Unow.decode("€".as_bytes()).unwrap(), "€");
+        let err = SyntaxIdentifier::Unow.decode(&[b'A', 0xFC]).unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+    }
+
+    #[test]
+    fn test_encode_round_trips_decode() {
+        for (charset, text) in [
+            (SyntaxIdentifier::Unoc, "Müller Straße"),
+            (SyntaxIdentifier::Unod, "Łódź"),
+            (SyntaxIdentifier::Unow, "5 €"),
+        ] {
+            let bytes = charset.encode(text).unwrap();
+            assert_eq!(charset.decode(&bytes).unwrap(), text);
+        }
+        assert_eq!(SyntaxIdentifier::Unoc.encode("ü").unwrap(), [0xFC]);
+    }
+
+    #[test]
+    fn test_encode_reports_unrepresentable_character() {
+        let err = SyntaxIdentifier::Unoc.encode("5 €").unwrap_err();
+        assert_eq!(err.character, '€');
+        assert_eq!(err.offset, 2);
+        assert_eq!(
+            err.to_string(),
+            "character '€' (U+20AC) at byte 2 cannot be represented in UNOC"
+        );
+    }
+
+    #[test]
+    fn test_to_repertoire_transliterates() {
+        let options = EncodeOptions {
+            transliterate: true,
+            replacement: None,
+        };
+        assert_eq!(
+            SyntaxIdentifier::Unob
+                .to_repertoire("Müller, Ärger, Straße", &options)
+                .unwrap(),
+            "Mueller, Aerger, Strasse"
+        );
+        assert_eq!(
+            SyntaxIdentifier::Unoa
+                .to_repertoire("Müller", &options)
+                .unwrap(),
+            "MUELLER"
+        );
+        assert_eq!(
+            SyntaxIdentifier::Unoc
+                .to_repertoire("Łukasz 5 €", &options)
+                .unwrap(),
+            "Lukasz 5 EUR"
+        );
+        // Characters within the repertoire are left alone
+        assert_eq!(
+            SyntaxIdentifier::Unoc
+                .to_repertoire("Müller", &options)
+                .unwrap(),
+            "Müller"
+        );
+    }
+
+    #[test]
+    fn test_to_repertoire_replacement_and_error() {
+        let replace = EncodeOptions {
+            transliterate: false,
+            replacement: Some('.'),
+        };
+        assert_eq!(
+            SyntaxIdentifier::Unob
+                .to_repertoire("a#b", &replace)
+                .unwrap(),
+            "a.b"
+        );
+
+        let err = SyntaxIdentifier::Unob
+            .to_repertoire("a#b", &EncodeOptions::default())
+            .unwrap_err();
+        assert_eq!(err.character, '#');
+        assert_eq!(err.offset, 1);
+    }
+}
@@ -6,16 +6,32 @@
 //! # Types
 //!
 //! - [`EdifactDelimiters`] — the six delimiter characters
-//! - [`SegmentPosition`] — byte offset and segment/message numbering
+//! - [`SegmentPosition`], [`Span`] — byte offset, line/column, byte ranges
+//!   and segment/message numbering
 //! - [`RawSegment`] — zero-copy parsed segment borrowing from the input buffer
+//! - [`OwnedRawSegment`] — owned copy of a `RawSegment`
 //! - [`Control`] — handler flow control (Continue / Stop)
+//! - [`SyntaxIdentifier`] — declared character set (UNOA/UNOB/UNOC/UNOD/UNOW)
+//! - [`EdifactWriter`] — segment writer with escaping and UNT/UNZ bookkeeping
+//! - [`Una`], [`Unb`], [`Ung`], [`Unh`], [`Unt`], [`Une`], [`Unz`] — typed service
+//!   segments
 
+mod charset;
 mod control;
 mod delimiters;
 mod position;
 mod segment;
+mod service;
+mod writer;
 
+pub use charset::{EncodeError, EncodeOptions, SyntaxIdentifier};
 pub use control::Control;
 pub use delimiters::{EdifactDelimiters, UnaParseError};
-pub use position::SegmentPosition;
-pub use segment::RawSegment;
+pub use position::{SegmentPosition, Span};
+pub use segment::{OwnedRawSegment, RawSegment};
+pub use service::{
+    ApplicationParty, InterchangeParty, MessageIdentifier, MessageVersion, PreparationDateTime,
+    RecipientPassword, ServiceSegment, ServiceSegmentError, StatusOfTransfer,
+    SyntaxIdentification, Una, Unb, Une, Ung, Unh, Unt, Unz, VersionedIdentification,
+};
+pub use writer::{EdifactWriter, WriterFormat};
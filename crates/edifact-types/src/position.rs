@@ -10,26 +10,79 @@ pub struct SegmentPosition {
     pub byte_offset: usize,
     /// 1-based message number within the interchange (0 for service segments UNB/UNZ).
     pub message_number: u32,
+    /// 1-based line of the segment's first byte (0 if unknown).
+    pub line: u32,
+    /// 1-based column, in bytes, of the segment's first byte within its
+    /// line (0 if unknown).
+    pub column: u32,
 }
 
 impl SegmentPosition {
-    /// Creates a new segment position.
+    /// Creates a new segment position without line and column.
     pub fn new(segment_number: u32, byte_offset: usize, message_number: u32) -> Self {
         Self {
             segment_number,
             byte_offset,
             message_number,
+            line: 0,
+            column: 0,
         }
     }
+
+    /// Returns this position with the given line and column.
+    pub fn with_line_column(mut self, line: u32, column: u32) -> Self {
+        self.line = line;
+        self.column = column;
+        self
+    }
 }
 
 impl std::fmt::Display for SegmentPosition {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "segment {} at byte {} (message {})",
-            self.segment_number, self.byte_offset, self.message_number
-        )
+        if self.line > 0 {
+            write!(
+                f,
+                "segment {} at line {}, column {} (byte {}, message {})",
+                self.segment_number, self.line, self.column, self.byte_offset, self.message_number
+            )
+        } else {
+            write!(
+                f,
+                "segment {} at byte {} (message {})",
+                self.segment_number, self.byte_offset, self.message_number
+            )
+        }
+    }
+}
+
+/// A half-open byte range `start..end` in the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Byte offset of the first byte.
+    pub start: usize,
+    /// Byte offset just past the last byte.
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a new span.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Length of the span in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no bytes (e.g. an empty component).
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
     }
 }
 
@@ -51,6 +104,24 @@ mod tests {
         assert_eq!(pos.to_string(), "segment 3 at byte 42 (message 1)");
     }
 
+    #[test]
+    fn test_segment_position_display_with_line_column() {
+        let pos = SegmentPosition::new(3, 42, 1).with_line_column(2, 5);
+        assert_eq!(
+            pos.to_string(),
+            "segment 3 at line 2, column 5 (byte 42, message 1)"
+        );
+    }
+
+    #[test]
+    fn test_span() {
+        let a = Span::new(4, 7);
+        assert_eq!(a.len(), 3);
+        assert!(!a.is_empty());
+        assert!(Span::new(7, 7).is_empty());
+        assert_eq!(a.to(Span::new(9, 12)), Span::new(4, 12));
+    }
+
     #[test]
     fn test_segment_position_service_segment() {
         let pos = SegmentPosition::new(1, 0, 0);
@@ -1,4 +1,4 @@
-use crate::SegmentPosition;
+use crate::{SegmentPosition, Span};
 
 /// A parsed EDIFACT segment that borrows from the input buffer.
 ///
@@ -20,18 +20,49 @@ pub struct RawSegment<'a> {
     pub elements: Vec<Vec<&'a str>>,
     /// Position metadata for this segment.
     pub position: SegmentPosition,
+    /// Byte span of the segment text (without terminator) in the input.
+    /// Empty if the segment was not read from input.
+    pub span: Span,
+    /// Byte spans of the components in the input, parallel to `elements`.
+    /// Empty if the segment was not read from input.
+    pub component_spans: Vec<Vec<Span>>,
 }
 
 impl<'a> RawSegment<'a> {
-    /// Creates a new RawSegment.
+    /// Creates a new RawSegment without source spans.
     pub fn new(id: &'a str, elements: Vec<Vec<&'a str>>, position: SegmentPosition) -> Self {
         Self {
             id,
             elements,
             position,
+            span: Span::default(),
+            component_spans: Vec::new(),
         }
     }
 
+    /// Returns this segment with the byte spans of the segment text and of
+    /// its components.
+    pub fn with_spans(mut self, span: Span, component_spans: Vec<Vec<Span>>) -> Self {
+        self.span = span;
+        self.component_spans = component_spans;
+        self
+    }
+
+    /// Byte span of a component in the input, if known.
+    pub fn component_span(&self, element_index: usize, component_index: usize) -> Option<Span> {
+        self.component_spans
+            .get(element_index)
+            .and_then(|e| e.get(component_index))
+            .copied()
+    }
+
+    /// Byte span of a whole (possibly composite) element in the input, if
+    /// known.
+    pub fn element_span(&self, element_index: usize) -> Option<Span> {
+        let components = self.component_spans.get(element_index)?;
+        Some(components.first()?.to(*components.last()?))
+    }
+
     /// Returns the number of elements (excluding the segment ID).
     pub fn element_count(&self) -> usize {
         self.elements.len()
@@ -118,6 +149,55 @@ impl<'a> std::fmt::Display for RawSegment<'a> {
     }
 }
 
+/// An owned copy of a [`RawSegment`], for segments that must outlive the
+/// buffer they were parsed from (e.g. when sent across tasks).
+///
+/// Component values keep their release characters, as in `RawSegment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedRawSegment {
+    /// Segment identifier (e.g., "NAD", "LOC", "DTM").
+    pub id: String,
+    /// Elements, where each element is a vector of component strings.
+    pub elements: Vec<Vec<String>>,
+    /// Position metadata for this segment.
+    pub position: SegmentPosition,
+    /// Byte span of the segment text in the input.
+    pub span: Span,
+    /// Byte spans of the components in the input, parallel to `elements`.
+    pub component_spans: Vec<Vec<Span>>,
+}
+
+impl OwnedRawSegment {
+    /// Borrows this segment as a [`RawSegment`].
+    pub fn as_raw(&self) -> RawSegment<'_> {
+        RawSegment::new(
+            &self.id,
+            self.elements
+                .iter()
+                .map(|e| e.iter().map(String::as_str).collect())
+                .collect(),
+            self.position,
+        )
+        .with_spans(self.span, self.component_spans.clone())
+    }
+}
+
+impl<'a> From<&RawSegment<'a>> for OwnedRawSegment {
+    fn from(segment: &RawSegment<'a>) -> Self {
+        Self {
+            id: segment.id.to_string(),
+            elements: segment
+                .elements
+                .iter()
+                .map(|e| e.iter().map(|c| c.to_string()).collect())
+                .collect(),
+            position: segment.position,
+            span: segment.span,
+            component_spans: segment.component_spans.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +353,43 @@ mod tests {
         let delimiters = crate::EdifactDelimiters::default();
         assert_eq!(seg.to_raw_string(&delimiters), "BGM+E03");
     }
+
+    #[test]
+    fn test_owned_raw_segment_roundtrip() {
+        let owned = {
+            let input = String::from("NAD+Z04+9900123000002:500");
+            let seg = RawSegment::new(
+                &input[0..3],
+                vec![vec![&input[4..7]], vec![&input[8..21], &input[22..25]]],
+                make_position(),
+            );
+            OwnedRawSegment::from(&seg)
+        };
+        let raw = owned.as_raw();
+        assert_eq!(raw.to_string(), "NAD+Z04+9900123000002:500");
+        assert_eq!(raw.position, make_position());
+    }
+
+    #[test]
+    fn test_raw_segment_spans() {
+        let seg = RawSegment::new("NAD", vec![vec!["Z04"], vec!["99", "500"]], make_position())
+            .with_spans(
+                Span::new(0, 14),
+                vec![
+                    vec![Span::new(4, 7)],
+                    vec![Span::new(8, 10), Span::new(11, 14)],
+                ],
+            );
+        assert_eq!(seg.component_span(1, 1), Some(Span::new(11, 14)));
+        assert_eq!(seg.component_span(1, 2), None);
+        assert_eq!(seg.element_span(1), Some(Span::new(8, 14)));
+        assert_eq!(seg.element_span(2), None);
+        assert_eq!(
+            OwnedRawSegment::from(&seg).as_raw().component_spans,
+            seg.component_spans
+        );
+
+        let plain = RawSegment::new("BGM", vec![vec!["E03"]], make_position());
+        assert_eq!(plain.component_span(0, 0), None);
+    }
 }
@@ -0,0 +1,1270 @@
+//! Typed service segments (ISO 9735 syntax versions 3 and 4).
+//!
+//! [`Una`], [`Unb`], [`Ung`], [`Unh`], [`Unt`], [`Une`] and [`Unz`] hold
+//! every data element of the envelope. Parsing is structural: mandatory values that are absent are
+//! read as empty strings and only reported by `validate()`, so a
+//! non-conforming envelope can still be read, inspected and written back.
+//! Emitting returns the elements in the shape [`EdifactWriter`] takes, with
+//! trailing empty components and elements dropped.
+//!
+//! [`EdifactWriter`]: crate::EdifactWriter
+
+use crate::{EdifactDelimiters, RawSegment, SyntaxIdentifier, UnaParseError};
+
+/// Error from parsing or validating a service segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceSegmentError {
+    /// The segment has a different tag than the type being parsed.
+    UnexpectedTag {
+        expected: &'static str,
+        found: String,
+    },
+    /// A mandatory data element is absent or empty.
+    Missing {
+        segment: &'static str,
+        element: &'static str,
+    },
+    /// A data element does not have the required format.
+    Invalid {
+        segment: &'static str,
+        element: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl std::fmt::Display for ServiceSegmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedTag { expected, found } => {
+                write!(f, "expected {expected} segment, found {found}")
+            }
+            Self::Missing { segment, element } => {
+                write!(f, "{segment} {element} is missing")
+            }
+            Self::Invalid {
+                segment,
+                element,
+                value,
+                expected,
+            } => write!(
+                f,
+                "{segment} {element} '{value}' is invalid, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ServiceSegmentError {}
+
+/// A typed service segment that can be read from and written as elements.
+///
+/// `from_elements` takes component values as given: values from a
+/// [`RawSegment`] keep their release characters, values from an unescaped
+/// source (and those returned by `to_elements`) do not.
+pub trait ServiceSegment: Sized {
+    /// The segment tag, e.g. `"UNB"`.
+    const TAG: &'static str;
+
+    /// Reads the segment from its elements (without the tag).
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>;
+
+    /// The elements of the segment (without the tag).
+    fn to_elements(&self) -> Vec<Vec<String>>;
+
+    /// Checks presence and format of every data element.
+    ///
+    /// Returns the first violation found.
+    fn validate(&self) -> Result<(), ServiceSegmentError>;
+
+    /// Reads the segment from a parsed segment, checking its tag.
+    fn from_raw(segment: &RawSegment<'_>) -> Result<Self, ServiceSegmentError> {
+        if !segment.is(Self::TAG) {
+            return Err(ServiceSegmentError::UnexpectedTag {
+                expected: Self::TAG,
+                found: segment.id.to_string(),
+            });
+        }
+        Self::from_elements(&segment.elements)
+    }
+}
+
+/// UNA service string advice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Una {
+    /// The advised delimiters.
+    pub delimiters: EdifactDelimiters,
+}
+
+impl Una {
+    /// Parses the 9-byte UNA segment.
+    pub fn parse(una: &[u8]) -> Result<Self, UnaParseError> {
+        EdifactDelimiters::from_una(una).map(|delimiters| Self { delimiters })
+    }
+
+    /// Checks that the separators, release character and terminator are
+    /// distinct and the decimal mark is `.` or `,`.
+    pub fn validate(&self) -> Result<(), ServiceSegmentError> {
+        let d = &self.delimiters;
+        if d.decimal != b'.' && d.decimal != b',' {
+            return Err(invalid(
+                "UNA",
+                "UNA3",
+                (d.decimal as char).to_string(),
+                "'.' or ','",
+            ));
+        }
+        let syntax = [
+            ("UNA1", d.component),
+            ("UNA2", d.element),
+            ("UNA4", d.release),
+            ("UNA6", d.segment),
+        ];
+        for (i, (element, c)) in syntax.iter().enumerate() {
+            if (!c.is_ascii_graphic() && *c != b' ') || syntax[..i].iter().any(|(_, o)| o == c) {
+                return Err(invalid(
+                    "UNA",
+                    element,
+                    (*c as char).to_string(),
+                    "a distinct printable character",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Una {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.delimiters.fmt(f)
+    }
+}
+
+/// UNB S001 — syntax identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SyntaxIdentification {
+    /// 0001 — syntax identifier, e.g. `UNOC`.
+    pub identifier: String,
+    /// 0002 — syntax version number, e.g. `3`.
+    pub version: String,
+    /// 0080 — service code list directory version (v4).
+    pub code_list_directory_version: Option<String>,
+    /// 0133 — character encoding (v4).
+    pub character_encoding: Option<String>,
+}
+
+impl SyntaxIdentification {
+    /// The declared character set, if supported.
+    pub fn charset(&self) -> Option<SyntaxIdentifier> {
+        SyntaxIdentifier::from_code(&self.identifier)
+    }
+
+    /// Whether the interchange uses syntax version 4.
+    pub fn is_v4(&self) -> bool {
+        self.version == "4"
+    }
+}
+
+/// UNB S002/S003 — interchange sender or recipient.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InterchangeParty {
+    /// 0004/0010 — identification, e.g. an MP-ID.
+    pub id: String,
+    /// 0007 — identification code qualifier, e.g. `500`.
+    pub qualifier: Option<String>,
+    /// 0008/0014 — routing address (internal identification in v4).
+    pub routing_address: Option<String>,
+    /// 0042/0046 — internal sub-identification (v4).
+    pub internal_sub_id: Option<String>,
+}
+
+/// UNB S004 — date and time of preparation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PreparationDateTime {
+    /// 0017 — `YYMMDD` (v3) or `CCYYMMDD` (v4).
+    pub date: String,
+    /// 0019 — `HHMM`.
+    pub time: String,
+}
+
+/// UNB S005 — recipient reference/password.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RecipientPassword {
+    /// 0022 — reference/password.
+    pub password: String,
+    /// 0025 — reference/password qualifier.
+    pub qualifier: Option<String>,
+}
+
+/// UNB interchange header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Unb {
+    /// S001 — syntax identifier.
+    pub syntax: SyntaxIdentification,
+    /// S002 — interchange sender.
+    pub sender: InterchangeParty,
+    /// S003 — interchange recipient.
+    pub recipient: InterchangeParty,
+    /// S004 — date and time of preparation.
+    pub prepared: PreparationDateTime,
+    /// 0020 — interchange control reference.
+    pub control_reference: String,
+    /// S005 — recipient reference/password.
+    pub recipient_password: Option<RecipientPassword>,
+    /// 0026 — application reference.
+    pub application_reference: Option<String>,
+    /// 0029 — processing priority code.
+    pub priority: Option<String>,
+    /// 0031 — acknowledgement request (`1` = requested).
+    pub acknowledgement_request: Option<String>,
+    /// 0032 — interchange agreement identifier.
+    pub agreement_id: Option<String>,
+    /// 0035 — test indicator (`1` = test interchange).
+    pub test_indicator: Option<String>,
+}
+
+impl Unb {
+    /// Whether the sender requests an acknowledgement (0031 = `1`).
+    pub fn acknowledgement_requested(&self) -> bool {
+        self.acknowledgement_request.as_deref() == Some("1")
+    }
+
+    /// Whether this is a test interchange (0035 = `1`).
+    pub fn is_test(&self) -> bool {
+        self.test_indicator.as_deref() == Some("1")
+    }
+}
+
+impl ServiceSegment for Unb {
+    const TAG: &'static str = "UNB";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        let get = |e, c| component(elements, e, c);
+        let opt = |e, c| optional(elements, e, c);
+        let party = |e| InterchangeParty {
+            id: get(e, 0),
+            qualifier: opt(e, 1),
+            routing_address: opt(e, 2),
+            internal_sub_id: opt(e, 3),
+        };
+
+        Ok(Self {
+            syntax: SyntaxIdentification {
+                identifier: get(0, 0),
+                version: get(0, 1),
+                code_list_directory_version: opt(0, 2),
+                character_encoding: opt(0, 3),
+            },
+            sender: party(1),
+            recipient: party(2),
+            prepared: PreparationDateTime {
+                date: get(3, 0),
+                time: get(3, 1),
+            },
+            control_reference: get(4, 0),
+            recipient_password: opt(5, 0).or(opt(5, 1)).map(|_| RecipientPassword {
+                password: get(5, 0),
+                qualifier: opt(5, 1),
+            }),
+            application_reference: opt(6, 0),
+            priority: opt(7, 0),
+            acknowledgement_request: opt(8, 0),
+            agreement_id: opt(9, 0),
+            test_indicator: opt(10, 0),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        let party = |p: &InterchangeParty| {
+            composite([
+                Some(&p.id),
+                p.qualifier.as_ref(),
+                p.routing_address.as_ref(),
+                p.internal_sub_id.as_ref(),
+            ])
+        };
+        let password = self.recipient_password.as_ref();
+
+        trim_elements(vec![
+            composite([
+                Some(&self.syntax.identifier),
+                Some(&self.syntax.version),
+                self.syntax.code_list_directory_version.as_ref(),
+                self.syntax.character_encoding.as_ref(),
+            ]),
+            party(&self.sender),
+            party(&self.recipient),
+            composite([Some(&self.prepared.date), Some(&self.prepared.time)]),
+            composite([Some(&self.control_reference)]),
+            composite([
+                password.map(|p| &p.password),
+                password.and_then(|p| p.qualifier.as_ref()),
+            ]),
+            composite([self.application_reference.as_ref()]),
+            composite([self.priority.as_ref()]),
+            composite([self.acknowledgement_request.as_ref()]),
+            composite([self.agreement_id.as_ref()]),
+            composite([self.test_indicator.as_ref()]),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        let check = |element, value: &str, format| check("UNB", element, value, format);
+        let optional = |element, value: &Option<String>, format| {
+            value
+                .as_deref()
+                .map_or(Ok(()), |v| check(element, v, format))
+        };
+
+        check("S001/0001", &self.syntax.identifier, Format::Alpha(4, 4))?;
+        check("S001/0002", &self.syntax.version, Format::Numeric(1, 1))?;
+        optional(
+            "S001/0080",
+            &self.syntax.code_list_directory_version,
+            Format::AlphaNumeric(6),
+        )?;
+        optional(
+            "S001/0133",
+            &self.syntax.character_encoding,
+            Format::AlphaNumeric(3),
+        )?;
+
+        for (party, ids) in [
+            (
+                &self.sender,
+                ["S002/0004", "S002/0007", "S002/0008", "S002/0042"],
+            ),
+            (
+                &self.recipient,
+                ["S003/0010", "S003/0007", "S003/0014", "S003/0046"],
+            ),
+        ] {
+            check(ids[0], &party.id, Format::AlphaNumeric(35))?;
+            optional(ids[1], &party.qualifier, Format::AlphaNumeric(4))?;
+            optional(ids[2], &party.routing_address, Format::AlphaNumeric(35))?;
+            optional(ids[3], &party.internal_sub_id, Format::AlphaNumeric(35))?;
+        }
+
+        let date_len = if self.syntax.is_v4() { 8 } else { 6 };
+        check(
+            "S004/0017",
+            &self.prepared.date,
+            Format::Numeric(date_len, date_len),
+        )?;
+        check("S004/0019", &self.prepared.time, Format::Numeric(4, 4))?;
+        check("0020", &self.control_reference, Format::AlphaNumeric(14))?;
+
+        if let Some(password) = &self.recipient_password {
+            check("S005/0022", &password.password, Format::AlphaNumeric(14))?;
+            optional("S005/0025", &password.qualifier, Format::AlphaNumeric(2))?;
+        }
+        optional(
+            "0026",
+            &self.application_reference,
+            Format::AlphaNumeric(14),
+        )?;
+        optional("0029", &self.priority, Format::Alpha(1, 1))?;
+        optional("0031", &self.acknowledgement_request, Format::Numeric(1, 1))?;
+        optional("0032", &self.agreement_id, Format::AlphaNumeric(35))?;
+        optional("0035", &self.test_indicator, Format::Numeric(1, 1))
+    }
+}
+
+/// UNG S006/S007 — application sender or recipient.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ApplicationParty {
+    /// 0040/0044 — identification.
+    pub id: String,
+    /// 0007 — identification code qualifier.
+    pub qualifier: Option<String>,
+}
+
+/// UNG S008 — message version of the messages in the group.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MessageVersion {
+    /// 0052 — message type version number.
+    pub version: String,
+    /// 0054 — message type release number.
+    pub release: String,
+    /// 0057 — association assigned code.
+    pub association_code: Option<String>,
+}
+
+/// UNG functional group header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ung {
+    /// 0038 — message group identification, e.g. `UTILMD`.
+    pub message_group_id: String,
+    /// S006 — application sender.
+    pub sender: ApplicationParty,
+    /// S007 — application recipient.
+    pub recipient: ApplicationParty,
+    /// S004 — date and time of preparation.
+    pub prepared: PreparationDateTime,
+    /// 0048 — group reference number.
+    pub group_reference: String,
+    /// 0051 — controlling agency.
+    pub controlling_agency: Option<String>,
+    /// S008 — message version.
+    pub message_version: Option<MessageVersion>,
+    /// 0058 — application password.
+    pub application_password: Option<String>,
+}
+
+impl ServiceSegment for Ung {
+    const TAG: &'static str = "UNG";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        let get = |e, c| component(elements, e, c);
+        let opt = |e, c| optional(elements, e, c);
+        let party = |e| ApplicationParty {
+            id: get(e, 0),
+            qualifier: opt(e, 1),
+        };
+
+        Ok(Self {
+            message_group_id: get(0, 0),
+            sender: party(1),
+            recipient: party(2),
+            prepared: PreparationDateTime {
+                date: get(3, 0),
+                time: get(3, 1),
+            },
+            group_reference: get(4, 0),
+            controlling_agency: opt(5, 0),
+            message_version: has_values(elements, 6).then(|| MessageVersion {
+                version: get(6, 0),
+                release: get(6, 1),
+                association_code: opt(6, 2),
+            }),
+            application_password: opt(7, 0),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        let party = |p: &ApplicationParty| composite([Some(&p.id), p.qualifier.as_ref()]);
+        let version = self.message_version.as_ref();
+
+        trim_elements(vec![
+            composite([Some(&self.message_group_id)]),
+            party(&self.sender),
+            party(&self.recipient),
+            composite([Some(&self.prepared.date), Some(&self.prepared.time)]),
+            composite([Some(&self.group_reference)]),
+            composite([self.controlling_agency.as_ref()]),
+            composite([
+                version.map(|v| &v.version),
+                version.map(|v| &v.release),
+                version.and_then(|v| v.association_code.as_ref()),
+            ]),
+            composite([self.application_password.as_ref()]),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        let check = |element, value: &str, format| check("UNG", element, value, format);
+        let optional = |element, value: &Option<String>, format| {
+            value
+                .as_deref()
+                .map_or(Ok(()), |v| check(element, v, format))
+        };
+
+        check("0038", &self.message_group_id, Format::AlphaNumeric(6))?;
+        for (party, ids) in [
+            (&self.sender, ["S006/0040", "S006/0007"]),
+            (&self.recipient, ["S007/0044", "S007/0007"]),
+        ] {
+            check(ids[0], &party.id, Format::AlphaNumeric(35))?;
+            optional(ids[1], &party.qualifier, Format::AlphaNumeric(4))?;
+        }
+
+        // UNG carries no syntax version: accept the v3 and v4 date lengths
+        let date_len = if self.prepared.date.len() > 6 { 8 } else { 6 };
+        check(
+            "S004/0017",
+            &self.prepared.date,
+            Format::Numeric(date_len, date_len),
+        )?;
+        check("S004/0019", &self.prepared.time, Format::Numeric(4, 4))?;
+        check("0048", &self.group_reference, Format::AlphaNumeric(14))?;
+        optional("0051", &self.controlling_agency, Format::AlphaNumeric(3))?;
+
+        if let Some(version) = &self.message_version {
+            check("S008/0052", &version.version, Format::AlphaNumeric(3))?;
+            check("S008/0054", &version.release, Format::AlphaNumeric(3))?;
+            optional(
+                "S008/0057",
+                &version.association_code,
+                Format::AlphaNumeric(6),
+            )?;
+        }
+        optional("0058", &self.application_password, Format::AlphaNumeric(14))
+    }
+}
+
+/// UNE functional group trailer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Une {
+    /// 0060 — number of messages in the group.
+    pub message_count: usize,
+    /// 0048 — group reference number, as in the UNG.
+    pub group_reference: String,
+}
+
+impl Une {
+    /// Checks that this trailer closes the group started by `ung`.
+    pub fn validate_against(&self, ung: &Ung) -> Result<(), ServiceSegmentError> {
+        if self.group_reference != ung.group_reference {
+            return Err(invalid(
+                "UNE",
+                "0048",
+                self.group_reference.clone(),
+                "the UNG group reference",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ServiceSegment for Une {
+    const TAG: &'static str = "UNE";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        Ok(Self {
+            message_count: count(elements, "UNE", "0060")?,
+            group_reference: component(elements, 1, 0),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        trim_elements(vec![
+            vec![self.message_count.to_string()],
+            composite([Some(&self.group_reference)]),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        check_count("UNE", "0060", self.message_count, 6)?;
+        check(
+            "UNE",
+            "0048",
+            &self.group_reference,
+            Format::AlphaNumeric(14),
+        )
+    }
+}
+
+/// UNH S009 — message identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MessageIdentifier {
+    /// 0065 — message type, e.g. `UTILMD`.
+    pub message_type: String,
+    /// 0052 — message type version number, e.g. `D`.
+    pub version: String,
+    /// 0054 — message type release number, e.g. `11A`.
+    pub release: String,
+    /// 0051 — controlling agency, e.g. `UN`.
+    pub controlling_agency: String,
+    /// 0057 — association assigned code, e.g. `S2.1`.
+    pub association_code: Option<String>,
+    /// 0110 — code list directory version number (v4).
+    pub code_list_directory_version: Option<String>,
+    /// 0113 — message type sub-function identification (v4).
+    pub sub_function: Option<String>,
+}
+
+/// UNH S010 — status of the transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StatusOfTransfer {
+    /// 0070 — sequence of transfers.
+    pub sequence: String,
+    /// 0073 — first and last transfer (`C` = creation, `F` = final).
+    pub first_last: Option<String>,
+}
+
+/// UNH S016/S017/S018 — message subset, implementation guideline or
+/// scenario identification (v4).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VersionedIdentification {
+    /// 0115/0121/0127 — identification.
+    pub id: String,
+    /// 0116/0122/0128 — version number.
+    pub version: Option<String>,
+    /// 0118/0124/0130 — release number.
+    pub release: Option<String>,
+    /// 0051 — controlling agency.
+    pub controlling_agency: Option<String>,
+}
+
+/// UNH message header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Unh {
+    /// 0062 — message reference number.
+    pub message_reference: String,
+    /// S009 — message identifier.
+    pub message_identifier: MessageIdentifier,
+    /// 0068 — common access reference.
+    pub common_access_reference: Option<String>,
+    /// S010 — status of the transfer.
+    pub status_of_transfer: Option<StatusOfTransfer>,
+    /// S016 — message subset identification (v4).
+    pub message_subset: Option<VersionedIdentification>,
+    /// S017 — message implementation guideline identification (v4).
+    pub implementation_guideline: Option<VersionedIdentification>,
+    /// S018 — scenario identification (v4).
+    pub scenario: Option<VersionedIdentification>,
+}
+
+impl ServiceSegment for Unh {
+    const TAG: &'static str = "UNH";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        let get = |e, c| component(elements, e, c);
+        let opt = |e, c| optional(elements, e, c);
+        let identification = |e| {
+            has_values(elements, e).then(|| VersionedIdentification {
+                id: get(e, 0),
+                version: opt(e, 1),
+                release: opt(e, 2),
+                controlling_agency: opt(e, 3),
+            })
+        };
+
+        Ok(Self {
+            message_reference: get(0, 0),
+            message_identifier: MessageIdentifier {
+                message_type: get(1, 0),
+                version: get(1, 1),
+                release: get(1, 2),
+                controlling_agency: get(1, 3),
+                association_code: opt(1, 4),
+                code_list_directory_version: opt(1, 5),
+                sub_function: opt(1, 6),
+            },
+            common_access_reference: opt(2, 0),
+            status_of_transfer: has_values(elements, 3).then(|| StatusOfTransfer {
+                sequence: get(3, 0),
+                first_last: opt(3, 1),
+            }),
+            message_subset: identification(4),
+            implementation_guideline: identification(5),
+            scenario: identification(6),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        let id = &self.message_identifier;
+        let identification = |i: Option<&VersionedIdentification>| {
+            composite([
+                i.map(|i| &i.id),
+                i.and_then(|i| i.version.as_ref()),
+                i.and_then(|i| i.release.as_ref()),
+                i.and_then(|i| i.controlling_agency.as_ref()),
+            ])
+        };
+        let status = self.status_of_transfer.as_ref();
+
+        trim_elements(vec![
+            composite([Some(&self.message_reference)]),
+            composite([
+                Some(&id.message_type),
+                Some(&id.version),
+                Some(&id.release),
+                Some(&id.controlling_agency),
+                id.association_code.as_ref(),
+                id.code_list_directory_version.as_ref(),
+                id.sub_function.as_ref(),
+            ]),
+            composite([self.common_access_reference.as_ref()]),
+            composite([
+                status.map(|s| &s.sequence),
+                status.and_then(|s| s.first_last.as_ref()),
+            ]),
+            identification(self.message_subset.as_ref()),
+            identification(self.implementation_guideline.as_ref()),
+            identification(self.scenario.as_ref()),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        let check = |element, value: &str, format| check("UNH", element, value, format);
+        let optional = |element, value: &Option<String>, format| {
+            value
+                .as_deref()
+                .map_or(Ok(()), |v| check(element, v, format))
+        };
+        let id = &self.message_identifier;
+
+        check("0062", &self.message_reference, Format::AlphaNumeric(14))?;
+        check("S009/0065", &id.message_type, Format::AlphaNumeric(6))?;
+        check("S009/0052", &id.version, Format::AlphaNumeric(3))?;
+        check("S009/0054", &id.release, Format::AlphaNumeric(3))?;
+        check("S009/0051", &id.controlling_agency, Format::AlphaNumeric(3))?;
+        optional("S009/0057", &id.association_code, Format::AlphaNumeric(6))?;
+        optional(
+            "S009/0110",
+            &id.code_list_directory_version,
+            Format::AlphaNumeric(6),
+        )?;
+        optional("S009/0113", &id.sub_function, Format::AlphaNumeric(6))?;
+        optional(
+            "0068",
+            &self.common_access_reference,
+            Format::AlphaNumeric(35),
+        )?;
+
+        if let Some(status) = &self.status_of_transfer {
+            check("S010/0070", &status.sequence, Format::Numeric(1, 2))?;
+            if let Some(first_last) = &status.first_last {
+                if first_last != "C" && first_last != "F" {
+                    return Err(invalid("UNH", "S010/0073", first_last.clone(), "C or F"));
+                }
+            }
+        }
+
+        for (identification, ids) in [
+            (
+                &self.message_subset,
+                ["S016/0115", "S016/0116", "S016/0118", "S016/0051"],
+            ),
+            (
+                &self.implementation_guideline,
+                ["S017/0121", "S017/0122", "S017/0124", "S017/0051"],
+            ),
+            (
+                &self.scenario,
+                ["S018/0127", "S018/0128", "S018/0130", "S018/0051"],
+            ),
+        ] {
+            if let Some(i) = identification {
+                check(ids[0], &i.id, Format::AlphaNumeric(14))?;
+                optional(ids[1], &i.version, Format::AlphaNumeric(3))?;
+                optional(ids[2], &i.release, Format::AlphaNumeric(3))?;
+                optional(ids[3], &i.controlling_agency, Format::AlphaNumeric(3))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// UNT message trailer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Unt {
+    /// 0074 — number of segments in the message, UNH and UNT included.
+    pub segment_count: usize,
+    /// 0062 — message reference number, as in the UNH.
+    pub message_reference: String,
+}
+
+impl Unt {
+    /// Checks that this trailer closes the message started by `unh`.
+    pub fn validate_against(&self, unh: &Unh) -> Result<(), ServiceSegmentError> {
+        if self.message_reference != unh.message_reference {
+            return Err(invalid(
+                "UNT",
+                "0062",
+                self.message_reference.clone(),
+                "the UNH message reference",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ServiceSegment for Unt {
+    const TAG: &'static str = "UNT";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        Ok(Self {
+            segment_count: count(elements, "UNT", "0074")?,
+            message_reference: component(elements, 1, 0),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        trim_elements(vec![
+            vec![self.segment_count.to_string()],
+            composite([Some(&self.message_reference)]),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        check_count("UNT", "0074", self.segment_count, 10)?;
+        check(
+            "UNT",
+            "0062",
+            &self.message_reference,
+            Format::AlphaNumeric(14),
+        )
+    }
+}
+
+/// UNZ interchange trailer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Unz {
+    /// 0036 — number of messages (or groups) in the interchange.
+    pub message_count: usize,
+    /// 0020 — interchange control reference, as in the UNB.
+    pub control_reference: String,
+}
+
+impl Unz {
+    /// Checks that this trailer closes the interchange started by `unb`.
+    pub fn validate_against(&self, unb: &Unb) -> Result<(), ServiceSegmentError> {
+        if self.control_reference != unb.control_reference {
+            return Err(invalid(
+                "UNZ",
+                "0020",
+                self.control_reference.clone(),
+                "the UNB control reference",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ServiceSegment for Unz {
+    const TAG: &'static str = "UNZ";
+
+    fn from_elements<E, C>(elements: &[E]) -> Result<Self, ServiceSegmentError>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        Ok(Self {
+            message_count: count(elements, "UNZ", "0036")?,
+            control_reference: component(elements, 1, 0),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Vec<String>> {
+        trim_elements(vec![
+            vec![self.message_count.to_string()],
+            composite([Some(&self.control_reference)]),
+        ])
+    }
+
+    fn validate(&self) -> Result<(), ServiceSegmentError> {
+        check_count("UNZ", "0036", self.message_count, 6)?;
+        check(
+            "UNZ",
+            "0020",
+            &self.control_reference,
+            Format::AlphaNumeric(14),
+        )
+    }
+}
+
+/// Character class and length of a data element (ISO 9735 notation).
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    /// `a` with minimum and maximum length.
+    Alpha(usize, usize),
+    /// `n` with minimum and maximum length.
+    Numeric(usize, usize),
+    /// `an..max`.
+    AlphaNumeric(usize),
+}
+
+/// Checks a mandatory value against its format.
+fn check(
+    segment: &'static str,
+    element: &'static str,
+    value: &str,
+    format: Format,
+) -> Result<(), ServiceSegmentError> {
+    if value.is_empty() {
+        return Err(ServiceSegmentError::Missing { segment, element });
+    }
+    let len = value.chars().count();
+    let (valid, expected) = match format {
+        Format::Alpha(min, max) => (
+            (min..=max).contains(&len) && value.chars().all(|c| c.is_ascii_alphabetic()),
+            "alphabetic characters",
+        ),
+        Format::Numeric(min, max) => (
+            (min..=max).contains(&len) && value.chars().all(|c| c.is_ascii_digit()),
+            "digits",
+        ),
+        Format::AlphaNumeric(max) => (len <= max, "a shorter value"),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid(segment, element, value.to_string(), expected))
+    }
+}
+
+fn check_count(
+    segment: &'static str,
+    element: &'static str,
+    value: usize,
+    max_digits: u32,
+) -> Result<(), ServiceSegmentError> {
+    if value.checked_ilog10().unwrap_or(0) < max_digits {
+        Ok(())
+    } else {
+        Err(invalid(
+            segment,
+            element,
+            value.to_string(),
+            "a shorter value",
+        ))
+    }
+}
+
+fn invalid(
+    segment: &'static str,
+    element: &'static str,
+    value: String,
+    expected: &'static str,
+) -> ServiceSegmentError {
+    ServiceSegmentError::Invalid {
+        segment,
+        element,
+        value,
+        expected,
+    }
+}
+
+/// Component `c` of element `e`, or an empty string if absent.
+fn component<E, C>(elements: &[E], e: usize, c: usize) -> String
+where
+    E: AsRef<[C]>,
+    C: AsRef<str>,
+{
+    elements
+        .get(e)
+        .and_then(|e| e.as_ref().get(c))
+        .map(|c| c.as_ref().to_string())
+        .unwrap_or_default()
+}
+
+/// Component `c` of element `e`, or `None` if absent or empty.
+fn optional<E, C>(elements: &[E], e: usize, c: usize) -> Option<String>
+where
+    E: AsRef<[C]>,
+    C: AsRef<str>,
+{
+    Some(component(elements, e, c)).filter(|v| !v.is_empty())
+}
+
+/// Whether element `e` has any non-empty component.
+fn has_values<E, C>(elements: &[E], e: usize) -> bool
+where
+    E: AsRef<[C]>,
+    C: AsRef<str>,
+{
+    elements
+        .get(e)
+        .is_some_and(|e| e.as_ref().iter().any(|c| !c.as_ref().is_empty()))
+}
+
+/// Reads a mandatory count from the first element.
+fn count<E, C>(
+    elements: &[E],
+    segment: &'static str,
+    element: &'static str,
+) -> Result<usize, ServiceSegmentError>
+where
+    E: AsRef<[C]>,
+    C: AsRef<str>,
+{
+    let value = component(elements, 0, 0);
+    if value.is_empty() {
+        return Err(ServiceSegmentError::Missing { segment, element });
+    }
+    value
+        .parse()
+        .map_err(|_| invalid(segment, element, value, "digits"))
+}
+
+/// Builds a composite element, dropping trailing absent components.
+fn composite<const N: usize>(components: [Option<&String>; N]) -> Vec<String> {
+    let len = components
+        .iter()
+        .rposition(|c| c.is_some_and(|c| !c.is_empty()))
+        .map_or(0, |i| i + 1);
+    components[..len]
+        .iter()
+        .map(|c| c.cloned().unwrap_or_default())
+        .collect()
+}
+
+/// Drops trailing empty elements.
+fn trim_elements(mut elements: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    while elements.last().is_some_and(|e| e.is_empty()) {
+        elements.pop();
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SegmentPosition;
+
+    fn elements(text: &str) -> Vec<Vec<&str>> {
+        text.split('+').map(|e| e.split(':').collect()).collect()
+    }
+
+    fn joined(elements: Vec<Vec<String>>) -> String {
+        elements
+            .iter()
+            .map(|e| e.join(":"))
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    #[test]
+    fn test_unb_v3_roundtrip() {
+        let text =
+            "UNOC:3+9900123000002:500+9900456000001:500+250101:1200+REF001+PW:AA+APP+A+1+AGREE+1";
+        let unb = Unb::from_elements(&elements(text)).unwrap();
+
+        assert_eq!(unb.syntax.charset(), Some(SyntaxIdentifier::Unoc));
+        assert_eq!(unb.sender.id, "9900123000002");
+        assert_eq!(unb.recipient.qualifier.as_deref(), Some("500"));
+        assert_eq!(unb.prepared.time, "1200");
+        assert_eq!(unb.control_reference, "REF001");
+        assert_eq!(unb.recipient_password.as_ref().unwrap().password, "PW");
+        assert_eq!(unb.application_reference.as_deref(), Some("APP"));
+        assert_eq!(unb.priority.as_deref(), Some("A"));
+        assert!(unb.acknowledgement_requested());
+        assert_eq!(unb.agreement_id.as_deref(), Some("AGREE"));
+        assert!(unb.is_test());
+        assert_eq!(unb.validate(), Ok(()));
+        assert_eq!(joined(unb.to_elements()), text);
+    }
+
+    #[test]
+    fn test_unb_keeps_inner_empty_elements_and_drops_trailing_ones() {
+        let unb = Unb::from_elements(&elements("UNOC:3+S:500:ROUTE+R::+250101:1200+REF++++++1+"))
+            .unwrap();
+
+        assert_eq!(unb.sender.routing_address.as_deref(), Some("ROUTE"));
+        assert_eq!(unb.recipient.qualifier, None);
+        assert_eq!(unb.recipient_password, None);
+        assert!(unb.is_test());
+        assert_eq!(
+            joined(unb.to_elements()),
+            "UNOC:3+S:500:ROUTE+R+250101:1200+REF++++++1"
+        );
+    }
+
+    #[test]
+    fn test_unb_v4_fields() {
+        let text = "UNOW:4:1:2+S:14:INT:SUB+R:14:RINT:RSUB+20250101:1200+REF";
+        let unb = Unb::from_elements(&elements(text)).unwrap();
+
+        assert!(unb.syntax.is_v4());
+        assert_eq!(unb.syntax.character_encoding.as_deref(), Some("2"));
+        assert_eq!(unb.recipient.internal_sub_id.as_deref(), Some("RSUB"));
+        // v4 dates have a century
+        assert_eq!(unb.validate(), Ok(()));
+        assert_eq!(joined(unb.to_elements()), text);
+    }
+
+    #[test]
+    fn test_unb_validation() {
+        let unb = Unb::from_elements(&elements("UNOC:3++R+250101:1200+REF")).unwrap();
+        assert_eq!(
+            unb.validate(),
+            Err(ServiceSegmentError::Missing {
+                segment: "UNB",
+                element: "S002/0004"
+            })
+        );
+
+        let unb = Unb::from_elements(&elements("UNOC:3+S+R+20250101:1200+REF")).unwrap();
+        assert!(matches!(
+            unb.validate(),
+            Err(ServiceSegmentError::Invalid {
+                element: "S004/0017",
+                ..
+            })
+        ));
+
+        let unb =
+            Unb::from_elements(&elements("UNOC:3+S+R+250101:1200+REFERENCE_TOO_LONG")).unwrap();
+        assert!(unb.validate().unwrap_err().to_string().contains("0020"));
+    }
+
+    #[test]
+    fn test_unh_roundtrip() {
+        let text = "1+UTILMD:D:11A:UN:S2.1+CAR+2:F";
+        let unh = Unh::from_elements(&elements(text)).unwrap();
+
+        assert_eq!(unh.message_reference, "1");
+        assert_eq!(unh.message_identifier.message_type, "UTILMD");
+        assert_eq!(
+            unh.message_identifier.association_code.as_deref(),
+            Some("S2.1")
+        );
+        assert_eq!(unh.common_access_reference.as_deref(), Some("CAR"));
+        let status = unh.status_of_transfer.as_ref().unwrap();
+        assert_eq!(
+            (status.sequence.as_str(), status.first_last.as_deref()),
+            ("2", Some("F"))
+        );
+        assert_eq!(unh.validate(), Ok(()));
+        assert_eq!(joined(unh.to_elements()), text);
+    }
+
+    #[test]
+    fn test_unh_v4_identifications() {
+        let text = "1+INVOIC:D:01B:UN:EAN010:1:SUB+++SUBSET:1:0:UN+MIG:2+SCEN";
+        let unh = Unh::from_elements(&elements(text)).unwrap();
+
+        assert_eq!(unh.status_of_transfer, None);
+        assert_eq!(unh.message_identifier.sub_function.as_deref(), Some("SUB"));
+        assert_eq!(
+            unh.message_subset
+                .as_ref()
+                .unwrap()
+                .controlling_agency
+                .as_deref(),
+            Some("UN")
+        );
+        assert_eq!(
+            unh.implementation_guideline
+                .as_ref()
+                .unwrap()
+                .version
+                .as_deref(),
+            Some("2")
+        );
+        assert_eq!(unh.scenario.as_ref().unwrap().id, "SCEN");
+        assert_eq!(unh.validate(), Ok(()));
+        assert_eq!(joined(unh.to_elements()), text);
+    }
+
+    #[test]
+    fn test_unh_rejects_invalid_transfer_status() {
+        let unh = Unh::from_elements(&elements("1+UTILMD:D:11A:UN++1:X")).unwrap();
+        assert!(matches!(
+            unh.validate(),
+            Err(ServiceSegmentError::Invalid {
+                element: "S010/0073",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_trailers() {
+        let unh = Unh::from_elements(&elements("7+UTILMD:D:11A:UN")).unwrap();
+        let unt = Unt::from_elements(&elements("12+7")).unwrap();
+        assert_eq!(unt.segment_count, 12);
+        assert_eq!(unt.validate_against(&unh), Ok(()));
+        assert_eq!(joined(unt.to_elements()), "12+7");
+
+        let unb = Unb::from_elements(&elements("UNOC:3+S+R+250101:1200+REF")).unwrap();
+        let unz = Unz::from_elements(&elements("1+OTHER")).unwrap();
+        assert!(unz.validate_against(&unb).is_err());
+
+        assert!(matches!(
+            Unz::from_elements(&elements("x+REF")),
+            Err(ServiceSegmentError::Invalid {
+                element: "0036",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Unt::from_elements::<Vec<&str>, &str>(&[]),
+            Err(ServiceSegmentError::Missing {
+                element: "0074",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_functional_group() {
+        let text = "UTILMD+S:14+R:14+250101:1200+GRP1+UN+D:11A:S2.1+PW";
+        let ung = Ung::from_elements(&elements(text)).unwrap();
+
+        assert_eq!(ung.message_group_id, "UTILMD");
+        assert_eq!(ung.recipient.qualifier.as_deref(), Some("14"));
+        assert_eq!(ung.group_reference, "GRP1");
+        let version = ung.message_version.as_ref().unwrap();
+        assert_eq!(
+            (
+                version.release.as_str(),
+                version.association_code.as_deref()
+            ),
+            ("11A", Some("S2.1"))
+        );
+        assert_eq!(ung.application_password.as_deref(), Some("PW"));
+        assert_eq!(ung.validate(), Ok(()));
+        assert_eq!(joined(ung.to_elements()), text);
+
+        let une = Une::from_elements(&elements("3+GRP1")).unwrap();
+        assert_eq!(une.message_count, 3);
+        assert_eq!(une.validate_against(&ung), Ok(()));
+        assert!(Une::from_elements(&elements("3+GRP2"))
+            .unwrap()
+            .validate_against(&ung)
+            .is_err());
+
+        let ung = Ung::from_elements(&elements("UTILMD+S+R+2501011:1200+GRP1")).unwrap();
+        assert!(matches!(
+            ung.validate(),
+            Err(ServiceSegmentError::Invalid {
+                element: "S004/0017",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_raw_checks_tag() {
+        let segment = RawSegment::new(
+            "UNH",
+            elements("1+UTILMD:D:11A:UN"),
+            SegmentPosition::new(1, 0, 1),
+        );
+        assert!(Unh::from_raw(&segment).is_ok());
+        assert_eq!(
+            Unb::from_raw(&segment),
+            Err(ServiceSegmentError::UnexpectedTag {
+                expected: "UNB",
+                found: "UNH".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_una() {
+        let una = Una::parse(b"UNA:+,? '").unwrap();
+        assert_eq!(una.delimiters.decimal, b',');
+        assert_eq!(una.validate(), Ok(()));
+        assert_eq!(una.to_string(), "UNA:+,? '");
+
+        assert!(Una::parse(b"UNA:+;? '").unwrap().validate().is_err());
+        assert!(Una::parse(b"UNA::.? '").unwrap().validate().is_err());
+    }
+}
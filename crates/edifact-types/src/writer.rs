@@ -0,0 +1,500 @@
+use std::io::{self, Write};
+
+use crate::{EdifactDelimiters, SyntaxIdentifier};
+
+/// How [`EdifactWriter`] separates segments in its output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriterFormat {
+    /// All segments on one line (the usual transmission format).
+    #[default]
+    SingleLine,
+    /// A `\n` after every segment terminator.
+    LinePerSegment,
+    /// A `\r\n` after every segment terminator.
+    LinePerSegmentCrlf,
+}
+
+impl WriterFormat {
+    fn line_ending(self) -> &'static [u8] {
+        match self {
+            WriterFormat::SingleLine => b"",
+            WriterFormat::LinePerSegment => b"\n",
+            WriterFormat::LinePerSegmentCrlf => b"\r\n",
+        }
+    }
+}
+
+/// Reference and running count of an open message or interchange.
+#[derive(Debug)]
+struct OpenEnvelope {
+    reference: String,
+    count: usize,
+}
+
+/// Writes EDIFACT segments to any `io::Write`.
+///
+/// Component values are given unescaped; delimiter characters in them are
+/// escaped with the release character of the active delimiters. Trailing
+/// empty elements and components are written as given.
+///
+/// Envelopes opened with [`begin_interchange`](Self::begin_interchange),
+/// [`begin_group`](Self::begin_group) and
+/// [`begin_message`](Self::begin_message) are closed by
+/// [`end_message`](Self::end_message), [`end_group`](Self::end_group) and
+/// [`end_interchange`](Self::end_interchange), which write UNT, UNE and UNZ
+/// with the segment, message and group counts and the references of the
+/// matching UNH, UNG and UNB. Service segments passed to [`write_segment`](Self::write_segment)
+/// are written as given and do not open or close envelopes.
+///
+/// # Example
+///
+/// ```
+/// use edifact_types::EdifactWriter;
+///
+/// let mut writer = EdifactWriter::new(Vec::new()).with_una(true);
+/// writer.begin_interchange(&[&["UNOC", "3"][..], &["SENDER"], &["RECEIVER"], &["250101", "1200"], &["REF1"]])?;
+/// writer.begin_message(&[&["1"][..], &["CONTRL", "D", "3", "UN"]])?;
+/// writer.write_segment("FTX", &[&["ABO"][..], &[], &[], &["a+b"]])?;
+/// writer.end_message()?;
+/// writer.end_interchange()?;
+///
+/// assert_eq!(
+///     String::from_utf8(writer.into_inner()).unwrap(),
+///     "UNA:+.? 'UNB+UNOC:3+SENDER+RECEIVER+250101:1200+REF1'UNH+1+CONTRL:D:3:UN'\
+///      FTX+ABO+++a?+b'UNT+3+1'UNZ+1+REF1'"
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct EdifactWriter<W> {
+    out: W,
+    delimiters: EdifactDelimiters,
+    format: WriterFormat,
+    charset: Option<SyntaxIdentifier>,
+    /// Whether to write UNA before the first segment.
+    una: bool,
+    started: bool,
+    interchange: Option<OpenEnvelope>,
+    group: Option<OpenEnvelope>,
+    message: Option<OpenEnvelope>,
+    /// Text of the segment being written, reused between segments.
+    buffer: String,
+}
+
+impl<W: Write> EdifactWriter<W> {
+    /// Creates a writer with standard delimiters, no UNA and all segments
+    /// on one line, writing text as UTF-8.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            delimiters: EdifactDelimiters::default(),
+            format: WriterFormat::default(),
+            charset: None,
+            una: false,
+            started: false,
+            interchange: None,
+            group: None,
+            message: None,
+            buffer: String::new(),
+        }
+    }
+
+    /// Uses the given delimiters for separating and escaping.
+    pub fn with_delimiters(mut self, delimiters: EdifactDelimiters) -> Self {
+        self.delimiters = delimiters;
+        self
+    }
+
+    /// Writes a UNA service string advice before the first segment.
+    pub fn with_una(mut self, una: bool) -> Self {
+        self.una = una;
+        self
+    }
+
+    /// Sets how segments are separated.
+    pub fn with_format(mut self, format: WriterFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Encodes the output in the given character set instead of UTF-8.
+    ///
+    /// A character outside the character set fails the segment containing
+    /// it with an `InvalidData` error wrapping an
+    /// [`EncodeError`](crate::EncodeError); nothing of that segment is
+    /// written.
+    pub fn with_syntax_identifier(mut self, charset: SyntaxIdentifier) -> Self {
+        self.charset = Some(charset);
+        self
+    }
+
+    /// The delimiters in use.
+    pub fn delimiters(&self) -> &EdifactDelimiters {
+        &self.delimiters
+    }
+
+    /// Number of segments of the open message so far, UNH included.
+    pub fn message_segment_count(&self) -> Option<usize> {
+        self.message.as_ref().map(|m| m.count)
+    }
+
+    /// Writes one segment.
+    ///
+    /// `elements[i][j]` is component `j` of element `i`. If a message is
+    /// open, the segment counts towards its UNT segment count.
+    pub fn write_segment<E, C>(
+        &mut self,
+        id: &str,
+        elements: impl IntoIterator<Item = E>,
+    ) -> io::Result<()>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        let mut text = std::mem::take(&mut self.buffer);
+        text.clear();
+        text.push_str(id);
+        for element in elements {
+            text.push(self.delimiters.element as char);
+            for (j, component) in element.as_ref().iter().enumerate() {
+                if j > 0 {
+                    text.push(self.delimiters.component as char);
+                }
+                escape_into(component.as_ref(), &self.delimiters, &mut text);
+            }
+        }
+        text.push(self.delimiters.segment as char);
+
+        let result = self.write_text(&text);
+        self.buffer = text;
+        result?;
+
+        if let Some(message) = &mut self.message {
+            message.count += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes a UNB segment and opens an interchange.
+    ///
+    /// The interchange control reference is taken from the fifth element
+    /// (0020) and repeated in the UNZ.
+    pub fn begin_interchange<E, C>(
+        &mut self,
+        elements: impl IntoIterator<Item = E>,
+    ) -> io::Result<()>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        if self.interchange.is_some() {
+            return Err(misuse("interchange is already open"));
+        }
+        let elements = collect_elements(elements);
+        let reference = first_component(&elements, 4);
+        self.write_segment("UNB", &elements)?;
+        self.interchange = Some(OpenEnvelope {
+            reference,
+            count: 0,
+        });
+        Ok(())
+    }
+
+    /// Writes a UNG segment and opens a functional group.
+    ///
+    /// The group reference number is taken from the fifth element (0048)
+    /// and repeated in the UNE. Messages written until
+    /// [`end_group`](Self::end_group) count towards the group, and the
+    /// group counts towards the interchange.
+    pub fn begin_group<E, C>(&mut self, elements: impl IntoIterator<Item = E>) -> io::Result<()>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        if self.message.is_some() {
+            return Err(misuse("message is still open"));
+        }
+        if self.group.is_some() {
+            return Err(misuse("functional group is already open"));
+        }
+        let elements = collect_elements(elements);
+        let reference = first_component(&elements, 4);
+        self.write_segment("UNG", &elements)?;
+        self.group = Some(OpenEnvelope {
+            reference,
+            count: 0,
+        });
+        Ok(())
+    }
+
+    /// Writes the UNE segment of the open functional group.
+    pub fn end_group(&mut self) -> io::Result<()> {
+        if self.message.is_some() {
+            return Err(misuse("message is still open"));
+        }
+        let group = self
+            .group
+            .take()
+            .ok_or_else(|| misuse("no functional group is open"))?;
+        let count = group.count.to_string();
+        self.write_segment("UNE", [[count.as_str()], [group.reference.as_str()]])?;
+        if let Some(interchange) = &mut self.interchange {
+            interchange.count += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes a UNH segment and opens a message.
+    ///
+    /// The message reference number is taken from the first element
+    /// (0062) and repeated in the UNT.
+    pub fn begin_message<E, C>(&mut self, elements: impl IntoIterator<Item = E>) -> io::Result<()>
+    where
+        E: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        if self.message.is_some() {
+            return Err(misuse("message is already open"));
+        }
+        let elements = collect_elements(elements);
+        let reference = first_component(&elements, 0);
+        self.write_segment("UNH", &elements)?;
+        self.message = Some(OpenEnvelope {
+            reference,
+            count: 1,
+        });
+        Ok(())
+    }
+
+    /// Writes the UNT segment of the open message.
+    pub fn end_message(&mut self) -> io::Result<()> {
+        let message = self
+            .message
+            .take()
+            .ok_or_else(|| misuse("no message is open"))?;
+        let count = (message.count + 1).to_string();
+        self.write_segment("UNT", [[count.as_str()], [message.reference.as_str()]])?;
+        if let Some(envelope) = self.group.as_mut().or(self.interchange.as_mut()) {
+            envelope.count += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes the UNZ segment of the open interchange.
+    pub fn end_interchange(&mut self) -> io::Result<()> {
+        if self.message.is_some() {
+            return Err(misuse("message is still open"));
+        }
+        if self.group.is_some() {
+            return Err(misuse("functional group is still open"));
+        }
+        let interchange = self
+            .interchange
+            .take()
+            .ok_or_else(|| misuse("no interchange is open"))?;
+        let count = interchange.count.to_string();
+        self.write_segment("UNZ", [[count.as_str()], [interchange.reference.as_str()]])
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write_text(&mut self, text: &str) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            if self.una {
+                let una = self.delimiters.to_una_string();
+                self.out.write_all(una.as_bytes())?;
+                self.out.write_all(self.format.line_ending())?;
+            }
+        }
+        match self.charset {
+            Some(charset) => {
+                let bytes = charset
+                    .encode(text)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                self.out.write_all(&bytes)?;
+            }
+            None => self.out.write_all(text.as_bytes())?,
+        }
+        self.out.write_all(self.format.line_ending())
+    }
+}
+
+/// Appends `value`, escaping delimiter characters with the release character.
+fn escape_into(value: &str, delimiters: &EdifactDelimiters, out: &mut String) {
+    let special = [
+        delimiters.element,
+        delimiters.component,
+        delimiters.segment,
+        delimiters.release,
+    ];
+    // Delimiters are ASCII, so multi-byte characters never need escaping
+    for c in value.chars() {
+        if c.is_ascii() && special.contains(&(c as u8)) {
+            out.push(delimiters.release as char);
+        }
+        out.push(c);
+    }
+}
+
+fn collect_elements<E, C>(elements: impl IntoIterator<Item = E>) -> Vec<Vec<String>>
+where
+    E: AsRef<[C]>,
+    C: AsRef<str>,
+{
+    elements
+        .into_iter()
+        .map(|e| e.as_ref().iter().map(|c| c.as_ref().to_string()).collect())
+        .collect()
+}
+
+fn first_component(elements: &[Vec<String>], index: usize) -> String {
+    elements
+        .get(index)
+        .and_then(|e| e.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn misuse(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(writer: EdifactWriter<Vec<u8>>) -> String {
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_write_segment_escapes_delimiters() {
+        let mut writer = EdifactWriter::new(Vec::new());
+        writer
+            .write_segment(
+                "FTX",
+                [vec!["ACB"], vec![], vec![], vec!["1+1=2? it's", "ok:"]],
+            )
+            .unwrap();
+        assert_eq!(written(writer), "FTX+ACB+++1?+1=2?? it?'s:ok?:'");
+    }
+
+    #[test]
+    fn test_write_segment_keeps_trailing_empty_components() {
+        let mut writer = EdifactWriter::new(Vec::new());
+        writer.write_segment("CAV", [["SA", "", "", ""]]).unwrap();
+        assert_eq!(written(writer), "CAV+SA:::'");
+    }
+
+    #[test]
+    fn test_custom_delimiters_and_una() {
+        let delimiters = EdifactDelimiters::from_una(b"UNA|*.# ~").unwrap();
+        let mut writer = EdifactWriter::new(Vec::new())
+            .with_delimiters(delimiters)
+            .with_una(true)
+            .with_format(WriterFormat::LinePerSegmentCrlf);
+        writer.write_segment("BGM", [["E03|X"], ["*"]]).unwrap();
+        assert_eq!(written(writer), "UNA|*.# ~\r\nBGM*E03#|X*#*~\r\n");
+    }
+
+    #[test]
+    fn test_envelopes_count_segments_and_messages() {
+        let mut writer = EdifactWriter::new(Vec::new()).with_format(WriterFormat::LinePerSegment);
+        writer
+            .begin_interchange([
+                vec!["UNOC", "3"],
+                vec!["S"],
+                vec!["R"],
+                vec!["250101", "1200"],
+                vec!["IC1"],
+            ])
+            .unwrap();
+        for reference in ["M1", "M2"] {
+            writer
+                .begin_message([vec![reference], vec!["UTILMD", "D", "11A", "UN", "S2.1"]])
+                .unwrap();
+            writer.write_segment("BGM", [["E01"]]).unwrap();
+            if reference == "M2" {
+                writer
+                    .write_segment("DTM", [["137", "20250101", "102"]])
+                    .unwrap();
+                assert_eq!(writer.message_segment_count(), Some(3));
+            }
+            writer.end_message().unwrap();
+        }
+        writer.end_interchange().unwrap();
+
+        let text = written(writer);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[3], "UNT+3+M1'");
+        assert_eq!(lines[7], "UNT+4+M2'");
+        assert_eq!(lines[8], "UNZ+2+IC1'");
+    }
+
+    #[test]
+    fn test_functional_groups_count_messages() {
+        let mut writer = EdifactWriter::new(Vec::new()).with_format(WriterFormat::LinePerSegment);
+        writer
+            .begin_interchange([["UNOC"], ["S"], ["R"], ["250101"], ["IC1"]])
+            .unwrap();
+        for group in ["G1", "G2"] {
+            writer
+                .begin_group([["UTILMD"], ["S"], ["R"], ["250101"], [group]])
+                .unwrap();
+            assert!(writer.begin_group([["UTILMD"]]).is_err());
+            for reference in ["M1", "M2"] {
+                writer.begin_message([[reference]]).unwrap();
+                writer.end_message().unwrap();
+            }
+            assert!(writer.end_interchange().is_err());
+            writer.end_group().unwrap();
+        }
+        writer.end_interchange().unwrap();
+
+        let text = written(writer);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[6], "UNE+2+G1'");
+        assert_eq!(lines[12], "UNE+2+G2'");
+        assert_eq!(lines[13], "UNZ+2+IC1'");
+    }
+
+    #[test]
+    fn test_envelope_misuse_is_an_error() {
+        let mut writer = EdifactWriter::new(Vec::new());
+        assert_eq!(
+            writer.end_message().unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        writer.begin_interchange([["UNOC"]]).unwrap();
+        writer.begin_message([["1"]]).unwrap();
+        assert!(writer.begin_message([["2"]]).is_err());
+        assert!(writer.end_interchange().is_err());
+    }
+
+    #[test]
+    fn test_syntax_identifier_encodes_output() {
+        let mut writer =
+            EdifactWriter::new(Vec::new()).with_syntax_identifier(SyntaxIdentifier::Unoc);
+        writer.write_segment("NAD", [["Müller"]]).unwrap();
+        assert_eq!(writer.into_inner(), b"NAD+M\xfcller'");
+
+        let mut writer =
+            EdifactWriter::new(Vec::new()).with_syntax_identifier(SyntaxIdentifier::Unoa);
+        let error = writer.write_segment("NAD", [["Müller"]]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<crate::EncodeError>())
+            .is_some());
+        assert!(writer.into_inner().is_empty());
+    }
+}
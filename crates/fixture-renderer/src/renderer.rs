@@ -12,8 +12,8 @@ use mig_assembly::pid_filter::filter_mig_for_pid;
 use mig_assembly::renderer::render_edifact;
 use mig_assembly::tokenize::{parse_to_segments, split_messages};
 use mig_bo4e::model::{
-    extract_nachrichtendaten, extract_unh_fields, rebuild_unb, rebuild_unh, rebuild_unt,
-    rebuild_unz, MappedMessage, Transaktion,
+    extract_nachrichtendaten, extract_nachrichtenkopf, extract_unh_fields, rebuild_unb,
+    rebuild_unh, rebuild_unt, rebuild_unz, MappedMessage, Transaktion,
 };
 use mig_bo4e::path_resolver::PathResolver;
 use mig_bo4e::pid_schema_index::PidSchemaIndex;
@@ -162,7 +162,8 @@ pub fn render_fixture(
 
         // Build UNH + body + UNT
         let (unh_ref, nachrichten_typ) = extract_unh_fields(&msg_chunk.unh);
-        let unh = rebuild_unh(&unh_ref, &nachrichten_typ);
+        let nachrichtenkopf = extract_nachrichtenkopf(&msg_chunk.unh);
+        let unh = rebuild_unh(&unh_ref, &nachrichten_typ, &nachrichtenkopf);
         let unh_dis = DisassembledSegment {
             tag: unh.id.clone(),
             elements: unh.elements.clone(),
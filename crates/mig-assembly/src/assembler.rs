@@ -1,22 +1,33 @@
-//! Recursive descent assembler — MIG-guided segment consumption.
+//! MIG-guided segment consumption.
 //!
-//! The assembler walks the MIG tree structure and consumes matching
-//! segments from the input. It produces a generic tree representation
-//! that can be converted to typed PID structs.
+//! The assembler consumes segments from the input as the MIG structure
+//! allows, driven by the MIG compiled into transition tables
+//! ([`crate::automaton`]). It produces a generic tree representation that
+//! can be converted to typed PID structs.
 
-use crate::cursor::SegmentCursor;
+use std::borrow::Cow;
+
+use crate::automaton::CompiledMig;
 use crate::diagnostic::{StructureDiagnostic, StructureDiagnosticKind};
-use crate::matcher;
 use crate::tokenize::OwnedSegment;
 use crate::AssemblyError;
-use mig_types::schema::mig::{MigSchema, MigSegment, MigSegmentGroup};
+use mig_types::schema::mig::MigSchema;
+use mig_types::segment::SegmentLocation;
+use mig_types::tree::{OwnedGroup, OwnedGroupInstance, OwnedTree};
 use serde::{Deserialize, Serialize};
 
 /// A generic assembled tree node (before PID-specific typing).
+///
+/// Segment tags and component values are [`Cow`]s: a tree assembled from
+/// [`tokenize::parse_borrowed_segments`](crate::tokenize::parse_borrowed_segments)
+/// borrows them from the EDIFACT input (owning only values that had release
+/// characters removed), one assembled by [`Assembler::assemble_generic`]
+/// borrows them from the `OwnedSegment`s. Use [`AssembledTree::into_owned`]
+/// to keep a tree beyond its input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssembledTree {
-    pub segments: Vec<AssembledSegment>,
-    pub groups: Vec<AssembledGroup>,
+pub struct AssembledTree<'a> {
+    pub segments: Vec<AssembledSegment<'a>>,
+    pub groups: Vec<AssembledGroup<'a>>,
     /// Index in `segments` where post-group segments start (e.g., UNT, UNZ).
     /// Segments before this index appear before groups in EDIFACT order.
     #[serde(default)]
@@ -26,44 +37,110 @@ pub struct AssembledTree {
     /// segments that appear immediately before that group in the EDIFACT
     /// stream. Empty for messages without inter-group root segments.
     #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
-    pub inter_group_segments: std::collections::BTreeMap<usize, Vec<AssembledSegment>>,
+    pub inter_group_segments: std::collections::BTreeMap<usize, Vec<AssembledSegment<'a>>>,
 }
 
 /// An assembled segment with its data elements.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssembledSegment {
-    pub tag: String,
+pub struct AssembledSegment<'a> {
+    pub tag: Cow<'a, str>,
     /// `elements[i][j]` = component `j` of element `i`
-    pub elements: Vec<Vec<String>>,
+    pub elements: Vec<Vec<Cow<'a, str>>>,
+    /// Where the segment was found in the EDIFACT input; `None` for
+    /// segments built by reverse mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SegmentLocation>,
 }
 
 /// An assembled segment group (may repeat).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssembledGroup {
+pub struct AssembledGroup<'a> {
     pub group_id: String,
-    pub repetitions: Vec<AssembledGroupInstance>,
+    pub repetitions: Vec<AssembledGroupInstance<'a>>,
 }
 
 /// One repetition of a segment group.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssembledGroupInstance {
-    pub segments: Vec<AssembledSegment>,
-    pub child_groups: Vec<AssembledGroup>,
+pub struct AssembledGroupInstance<'a> {
+    pub segments: Vec<AssembledSegment<'a>>,
+    pub child_groups: Vec<AssembledGroup<'a>>,
     /// Segments that were present in the EDIFACT input but not defined in
     /// the PID-filtered MIG for this group. Only populated when the assembler
     /// runs with [`AssemblerConfig::skip_unknown_segments`] enabled.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub skipped_segments: Vec<AssembledSegment>,
+    pub skipped_segments: Vec<AssembledSegment<'a>>,
+}
+
+impl AssembledTree<'_> {
+    /// Copy every borrowed tag and value, detaching the tree from its input.
+    pub fn into_owned(self) -> AssembledTree<'static> {
+        AssembledTree {
+            segments: owned_segments(self.segments),
+            groups: self
+                .groups
+                .into_iter()
+                .map(AssembledGroup::into_owned)
+                .collect(),
+            post_group_start: self.post_group_start,
+            inter_group_segments: self
+                .inter_group_segments
+                .into_iter()
+                .map(|(idx, segments)| (idx, owned_segments(segments)))
+                .collect(),
+        }
+    }
+}
+
+impl AssembledSegment<'_> {
+    /// Copy the borrowed tag and values.
+    pub fn into_owned(self) -> AssembledSegment<'static> {
+        AssembledSegment {
+            tag: Cow::Owned(self.tag.into_owned()),
+            elements: self
+                .elements
+                .into_iter()
+                .map(|e| e.into_iter().map(|c| Cow::Owned(c.into_owned())).collect())
+                .collect(),
+            location: self.location,
+        }
+    }
+}
+
+impl AssembledGroup<'_> {
+    /// Copy the borrowed tags and values of every repetition.
+    pub fn into_owned(self) -> AssembledGroup<'static> {
+        AssembledGroup {
+            group_id: self.group_id,
+            repetitions: self
+                .repetitions
+                .into_iter()
+                .map(AssembledGroupInstance::into_owned)
+                .collect(),
+        }
+    }
 }
 
-impl AssembledGroupInstance {
+impl<'a> AssembledGroupInstance<'a> {
+    /// Copy the borrowed tags and values of the instance and its children.
+    pub fn into_owned(self) -> AssembledGroupInstance<'static> {
+        AssembledGroupInstance {
+            segments: owned_segments(self.segments),
+            child_groups: self
+                .child_groups
+                .into_iter()
+                .map(AssembledGroup::into_owned)
+                .collect(),
+            skipped_segments: owned_segments(self.skipped_segments),
+        }
+    }
+
     /// Create a virtual `AssembledTree` scoped to this group instance.
     ///
     /// The instance's own segments become the tree's root segments,
     /// and its child groups become the tree's groups. This enables
     /// running `MappingEngine::map_all_forward()` on a single
     /// transaction group as if it were a complete message.
-    pub fn as_assembled_tree(&self) -> AssembledTree {
+    pub fn as_assembled_tree(&self) -> AssembledTree<'a> {
         AssembledTree {
             segments: self.segments.clone(),
             groups: self.child_groups.clone(),
@@ -73,6 +150,13 @@ impl AssembledGroupInstance {
     }
 }
 
+fn owned_segments(segments: Vec<AssembledSegment<'_>>) -> Vec<AssembledSegment<'static>> {
+    segments
+        .into_iter()
+        .map(AssembledSegment::into_owned)
+        .collect()
+}
+
 /// Configuration for the assembler.
 #[derive(Debug, Clone, Default)]
 pub struct AssemblerConfig {
@@ -91,265 +175,91 @@ pub struct AssemblerConfig {
 /// of parsed EDIFACT segments. Produces a generic `AssembledTree`.
 pub struct Assembler<'a> {
     mig: &'a MigSchema,
+    compiled: Cow<'a, CompiledMig>,
     config: AssemblerConfig,
 }
 
 impl<'a> Assembler<'a> {
     pub fn new(mig: &'a MigSchema) -> Self {
-        Self {
-            mig,
-            config: AssemblerConfig::default(),
-        }
+        Self::with_config(mig, AssemblerConfig::default())
     }
 
     pub fn with_config(mig: &'a MigSchema, config: AssemblerConfig) -> Self {
-        Self { mig, config }
-    }
-
-    /// Assemble segments into a generic tree following MIG structure.
-    pub fn assemble_generic(
-        &self,
-        segments: &[OwnedSegment],
-    ) -> Result<AssembledTree, AssemblyError> {
-        let mut cursor = SegmentCursor::new(segments.len());
-        let mut tree = AssembledTree {
-            segments: Vec::new(),
-            groups: Vec::new(),
-            post_group_start: 0,
-            inter_group_segments: std::collections::BTreeMap::new(),
-        };
-
-        // Track which MIG segment indices were matched in the first pass
-        let mut matched_seg_indices = Vec::new();
-
-        // Process top-level segments (first pass — before groups)
-        for (i, mig_seg) in self.mig.segments.iter().enumerate() {
-            if cursor.is_exhausted() {
-                break;
-            }
-            if let Some(assembled) = self.try_consume_segment(segments, &mut cursor, mig_seg)? {
-                tree.segments.push(assembled);
-                matched_seg_indices.push(i);
-            }
-        }
-
-        // Process segment groups, interleaving root segment consumption.
-        // Some message types (e.g., MSCONS) have root segments like UNS
-        // between groups (SG2 and SG5). Before trying each group, consume
-        // any unmatched root segments at the current cursor position.
-        for mig_group in &self.mig.segment_groups {
-            if cursor.is_exhausted() {
-                break;
-            }
-
-            // Try consuming unmatched root segments before this group
-            let group_idx = tree.groups.len();
-            for (i, mig_seg) in self.mig.segments.iter().enumerate() {
-                if cursor.is_exhausted() {
-                    break;
-                }
-                if matched_seg_indices.contains(&i) {
-                    continue;
-                }
-                if let Some(assembled) = self.try_consume_segment(segments, &mut cursor, mig_seg)? {
-                    tree.inter_group_segments
-                        .entry(group_idx)
-                        .or_default()
-                        .push(assembled);
-                    matched_seg_indices.push(i);
-                }
-            }
-
-            if let Some(assembled) = self.try_consume_group(segments, &mut cursor, mig_group)? {
-                tree.groups.push(assembled);
-            }
+        Self {
+            mig,
+            compiled: Cow::Owned(CompiledMig::compile(mig)),
+            config,
         }
+    }
 
-        // Mark where post-group segments start
-        tree.post_group_start = tree.segments.len();
-
-        // Second pass: try unmatched top-level segments (e.g., UNT, UNZ after groups)
-        for (i, mig_seg) in self.mig.segments.iter().enumerate() {
-            if cursor.is_exhausted() {
-                break;
-            }
-            if matched_seg_indices.contains(&i) {
-                continue;
-            }
-            if let Some(assembled) = self.try_consume_segment(segments, &mut cursor, mig_seg)? {
-                tree.segments.push(assembled);
-            }
+    /// Assembler driven by an automaton compiled from `mig` ahead of time
+    /// (e.g. loaded from a variant cache), skipping compilation.
+    pub fn with_compiled(
+        mig: &'a MigSchema,
+        compiled: &'a CompiledMig,
+        config: AssemblerConfig,
+    ) -> Self {
+        Self {
+            mig,
+            compiled: Cow::Borrowed(compiled),
+            config,
         }
-
-        Ok(tree)
     }
 
-    fn try_consume_segment(
+    /// Assemble segments into a generic tree following MIG structure.
+    ///
+    /// The tree borrows tags and values from `segments`.
+    pub fn assemble_generic<'s>(
         &self,
-        segments: &[OwnedSegment],
-        cursor: &mut SegmentCursor,
-        mig_seg: &MigSegment,
-    ) -> Result<Option<AssembledSegment>, AssemblyError> {
-        if cursor.is_exhausted() {
-            return Ok(None);
-        }
-        let seg = &segments[cursor.position()];
-        if matcher::matches_segment_tag(&seg.id, &mig_seg.id) {
-            let assembled = owned_to_assembled(seg);
-            cursor.advance();
-            Ok(Some(assembled))
-        } else {
-            Ok(None) // Segment not present (optional)
-        }
+        segments: &'s [OwnedSegment],
+    ) -> Result<AssembledTree<'s>, AssemblyError> {
+        let symbols = self
+            .compiled
+            .symbols(segments.iter().map(|s| s.id.as_str()));
+        self.compiled.assemble(
+            symbols,
+            segments.iter().map(owned_to_assembled),
+            &self.config,
+        )
     }
 
-    fn try_consume_group(
+    /// Assemble segments from
+    /// [`tokenize::parse_borrowed_segments`](crate::tokenize::parse_borrowed_segments),
+    /// moving them into the tree instead of copying them.
+    ///
+    /// Segments assembly does not reach are dropped, as with
+    /// `assemble_generic()`.
+    pub fn assemble_segments<'s>(
         &self,
-        segments: &[OwnedSegment],
-        cursor: &mut SegmentCursor,
-        mig_group: &MigSegmentGroup,
-    ) -> Result<Option<AssembledGroup>, AssemblyError> {
-        let mut repetitions = Vec::new();
-        let entry_segment = mig_group.segments.first().ok_or_else(|| {
-            AssemblyError::ParseError(format!("Group {} has no segments", mig_group.id))
-        })?;
-
-        // Loop for repeating groups
-        while !cursor.is_exhausted() {
-            let seg = &segments[cursor.position()];
-            if !matcher::matches_segment_tag(&seg.id, &entry_segment.id) {
-                break; // Current segment doesn't match group entry — stop repeating
-            }
-
-            let mut instance = AssembledGroupInstance {
-                segments: Vec::new(),
-                child_groups: Vec::new(),
-                skipped_segments: Vec::new(),
-            };
-
-            // Consume segments within this group instance.
-            // Process MIG slots in tag runs: for consecutive slots with the
-            // same tag, consume ALL matching input segments — not just the
-            // defined count. This handles real-world fixtures with more
-            // repetitions than the merged MIG predicts (e.g., 6 RFFs when
-            // the schema defines max 4).
-            //
-            // The entry segment (first tag run) is consumed bounded — one per
-            // defined slot — because the outer while loop uses the entry tag
-            // to delineate group repetitions.
-            let mut slot_idx = 0;
-            let mut is_entry_run = true;
-            while slot_idx < mig_group.segments.len() {
-                if cursor.is_exhausted() {
-                    break;
-                }
-                let current_tag = &mig_group.segments[slot_idx].id;
-                let run_len = mig_group.segments[slot_idx..]
-                    .iter()
-                    .take_while(|s| s.id == *current_tag)
-                    .count();
-
-                if is_entry_run {
-                    // Entry tag: consume at most run_len (preserves group boundaries)
-                    for slot in &mig_group.segments[slot_idx..slot_idx + run_len] {
-                        if cursor.is_exhausted() {
-                            break;
-                        }
-                        if let Some(assembled) = self.try_consume_segment(segments, cursor, slot)? {
-                            instance.segments.push(assembled);
-                        }
-                    }
-                    is_entry_run = false;
-                } else {
-                    // Non-entry tag: greedily consume all matching segments
-                    while !cursor.is_exhausted() {
-                        let seg = &segments[cursor.position()];
-                        if matcher::matches_segment_tag(&seg.id, current_tag) {
-                            instance.segments.push(owned_to_assembled(seg));
-                            cursor.advance();
-                        } else {
-                            break;
-                        }
-                    }
-                }
-
-                slot_idx += run_len;
-
-                // Point A: Skip unknown segments between MIG slot runs.
-                // When skip mode is ON and we just finished a slot run but the
-                // current segment doesn't match any remaining MIG slot, nested
-                // group entry, or the entry tag, skip it.
-                if self.config.skip_unknown_segments {
-                    while !cursor.is_exhausted() {
-                        let seg = &segments[cursor.position()];
-                        // Stop if it matches the entry tag (next group repetition)
-                        if matcher::matches_segment_tag(&seg.id, &entry_segment.id) {
-                            break;
-                        }
-                        // Stop if it matches any remaining MIG slot
-                        if mig_group.segments[slot_idx..]
-                            .iter()
-                            .any(|s| matcher::matches_segment_tag(&seg.id, &s.id))
-                        {
-                            break;
-                        }
-                        // Stop if it matches any nested group entry
-                        if mig_group.nested_groups.iter().any(|ng| {
-                            ng.segments
-                                .first()
-                                .is_some_and(|es| matcher::matches_segment_tag(&seg.id, &es.id))
-                        }) {
-                            break;
-                        }
-                        // Unknown segment — skip it
-                        instance.skipped_segments.push(owned_to_assembled(seg));
-                        cursor.advance();
-                    }
-                }
-            }
-
-            // Consume nested groups
-            for nested in &mig_group.nested_groups {
-                if cursor.is_exhausted() {
-                    break;
-                }
-                if let Some(assembled) = self.try_consume_group(segments, cursor, nested)? {
-                    instance.child_groups.push(assembled);
-                }
-            }
-
-            repetitions.push(instance);
-        }
-
-        if repetitions.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(AssembledGroup {
-                group_id: mig_group.id.clone(),
-                repetitions,
-            }))
-        }
+        segments: Vec<AssembledSegment<'s>>,
+    ) -> Result<AssembledTree<'s>, AssemblyError> {
+        let symbols = self.compiled.symbols(segments.iter().map(|s| &*s.tag));
+        self.compiled
+            .assemble(symbols, segments.into_iter(), &self.config)
     }
 
     /// Assemble segments with diagnostic collection.
     ///
-    /// Returns the assembled tree plus diagnostics for segments not consumed
-    /// by the MIG-guided assembly. Existing `assemble_generic()` is unchanged.
-    pub fn assemble_with_diagnostics(
+    /// Returns the assembled tree plus structure diagnostics: segments not
+    /// consumed by the MIG-guided assembly, missing mandatory segments and
+    /// groups, and MaxRep overruns (see [`crate::structure::check_structure`]).
+    /// Existing `assemble_generic()` is unchanged.
+    pub fn assemble_with_diagnostics<'s>(
         &self,
-        segments: &[OwnedSegment],
-    ) -> (AssembledTree, Vec<StructureDiagnostic>) {
-        let mut diagnostics = Vec::new();
-
+        segments: &'s [OwnedSegment],
+    ) -> (AssembledTree<'s>, Vec<StructureDiagnostic>) {
         let tree = match self.assemble_generic(segments) {
             Ok(tree) => tree,
             Err(e) => {
-                diagnostics.push(StructureDiagnostic {
+                let diagnostics = vec![StructureDiagnostic {
                     kind: StructureDiagnosticKind::UnexpectedSegment,
                     segment_id: String::new(),
                     position: 0,
                     message: format!("Assembly failed: {e}"),
-                });
+                    mig_path: None,
+                    location: None,
+                    repair: None,
+                }];
                 return (
                     AssembledTree {
                         segments: Vec::new(),
@@ -362,57 +272,97 @@ impl<'a> Assembler<'a> {
             }
         };
 
-        // Count consumed segments in the assembled tree
-        let consumed = count_tree_segments(&tree);
-
-        // Segments beyond consumed count are unconsumed
-        for (i, seg) in segments.iter().enumerate().skip(consumed) {
-            diagnostics.push(StructureDiagnostic {
-                kind: StructureDiagnosticKind::UnexpectedSegment,
-                segment_id: seg.id.clone(),
-                position: i,
-                message: format!(
-                    "Segment '{}' at position {} was not consumed by MIG-guided assembly",
-                    seg.id, i
-                ),
-            });
-        }
-
+        let diagnostics = crate::structure::check_structure(self.mig, &tree, segments);
         (tree, diagnostics)
     }
+
+    /// Assemble a structurally broken message as far as the MIG allows.
+    ///
+    /// Where `assemble_generic()` would stop, the input is repaired instead:
+    /// the offending segment is skipped, assembly resyncs on the next group
+    /// entry or root segment, or an empty group entry segment is inserted so
+    /// the segments after it can be placed. Always returns a best-effort
+    /// tree. The diagnostics list every repair (with
+    /// [`StructureDiagnostic::repair`] set) followed by the
+    /// `assemble_with_diagnostics()` findings for the repaired input; all
+    /// positions refer to `segments`.
+    pub fn assemble_lenient(
+        &self,
+        segments: &[OwnedSegment],
+    ) -> (AssembledTree<'static>, Vec<StructureDiagnostic>) {
+        crate::recovery::assemble_lenient(self, self.mig, segments)
+    }
 }
 
-fn count_tree_segments(tree: &AssembledTree) -> usize {
-    let mut count = tree.segments.len();
-    for group in &tree.groups {
-        count += count_group_segments(group);
+/// View an `OwnedSegment` as an `AssembledSegment` borrowing its tag and values.
+pub fn owned_to_assembled(seg: &OwnedSegment) -> AssembledSegment<'_> {
+    AssembledSegment {
+        tag: Cow::Borrowed(&seg.id),
+        elements: seg
+            .elements
+            .iter()
+            .map(|e| e.iter().map(|c| Cow::Borrowed(c.as_str())).collect())
+            .collect(),
+        location: seg.location.clone(),
     }
-    count
 }
 
-fn count_group_segments(group: &AssembledGroup) -> usize {
-    let mut count = 0;
-    for rep in &group.repetitions {
-        count += rep.segments.len();
-        count += rep.skipped_segments.len();
-        for child in &rep.child_groups {
-            count += count_group_segments(child);
+/// Take over an `OwnedSegment`'s tag and values; the segment number is dropped.
+impl From<OwnedSegment> for AssembledSegment<'static> {
+    fn from(seg: OwnedSegment) -> Self {
+        AssembledSegment {
+            tag: Cow::Owned(seg.id),
+            elements: seg
+                .elements
+                .into_iter()
+                .map(|e| e.into_iter().map(Cow::Owned).collect())
+                .collect(),
+            location: seg.location,
         }
     }
-    count
 }
 
-pub fn owned_to_assembled(seg: &OwnedSegment) -> AssembledSegment {
-    AssembledSegment {
-        tag: seg.id.clone(),
-        elements: seg.elements.clone(),
+/// Convert a tree built from generated PID types (`into_assembled_tree()`).
+impl From<OwnedTree> for AssembledTree<'static> {
+    fn from(tree: OwnedTree) -> Self {
+        AssembledTree {
+            segments: tree.segments.into_iter().map(Into::into).collect(),
+            groups: tree.groups.into_iter().map(Into::into).collect(),
+            post_group_start: tree.post_group_start,
+            inter_group_segments: tree
+                .inter_group_segments
+                .into_iter()
+                .map(|(idx, segments)| (idx, segments.into_iter().map(Into::into).collect()))
+                .collect(),
+        }
+    }
+}
+
+impl From<OwnedGroup> for AssembledGroup<'static> {
+    fn from(group: OwnedGroup) -> Self {
+        AssembledGroup {
+            group_id: group.group_id,
+            repetitions: group.repetitions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<OwnedGroupInstance> for AssembledGroupInstance<'static> {
+    fn from(instance: OwnedGroupInstance) -> Self {
+        AssembledGroupInstance {
+            segments: instance.segments.into_iter().map(Into::into).collect(),
+            child_groups: instance.child_groups.into_iter().map(Into::into).collect(),
+            skipped_segments: Vec::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostic::StructureRepair;
     use crate::test_support::{make_mig_group, make_mig_segment};
+    use mig_types::schema::mig::MigSegmentGroup;
 
     fn make_owned_seg(id: &str, elements: Vec<Vec<&str>>) -> OwnedSegment {
         OwnedSegment {
@@ -422,6 +372,7 @@ mod tests {
                 .map(|e| e.into_iter().map(|c| c.to_string()).collect())
                 .collect(),
             segment_number: 0,
+            location: None,
         }
     }
 
@@ -439,6 +390,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generated_pid_tree_matches_assembly_of_its_segments() {
+        use mig_types::generated::fv2504::utilmd::pids::pid_44001::*;
+
+        let seg = |id: &str, value: &str| OwnedSegment::new(id, [[value]]);
+        let pid = Pid44001::new(
+            seg("UNH", "1"),
+            seg("BGM", "E01"),
+            seg("DTM", "137"),
+            seg("UNT", "9"),
+        )
+        .with_sg2(Pid44001Sg2::default().with_nad(seg("NAD", "MS")))
+        .with_sg4(
+            Pid44001Sg4::default()
+                .with_ide(seg("IDE", "24"))
+                .with_sg8_z01(
+                    Pid44001Sg8Z01::default()
+                        .with_seq(seg("SEQ", "Z01"))
+                        .with_sg10(
+                            Pid44001Sg10::default()
+                                .with_cav(seg("CAV", "Z07"))
+                                .with_cci(seg("CCI", "Z30")),
+                        ),
+                )
+                .with_sg8_z03(Pid44001Sg8Z03::default().with_seq(seg("SEQ", "Z03"))),
+        );
+        let mig = make_mig_schema(
+            vec!["UNH", "BGM", "DTM", "UNT"],
+            vec![
+                make_mig_group("SG2", vec!["NAD"], vec![]),
+                make_mig_group(
+                    "SG4",
+                    vec!["IDE"],
+                    vec![make_mig_group(
+                        "SG8",
+                        vec!["SEQ"],
+                        vec![make_mig_group("SG10", vec!["CCI", "CAV"], vec![])],
+                    )],
+                ),
+            ],
+        );
+
+        let segments = pid.to_segments();
+        let tags: Vec<&str> = segments.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(
+            tags,
+            ["UNH", "BGM", "DTM", "NAD", "IDE", "SEQ", "CCI", "CAV", "SEQ", "UNT"]
+        );
+
+        let assembled = Assembler::new(&mig).assemble_generic(&segments).unwrap();
+        let converted = AssembledTree::from(pid.into_assembled_tree());
+        assert_eq!(
+            serde_json::to_value(&converted).unwrap(),
+            serde_json::to_value(&assembled).unwrap()
+        );
+    }
+
     #[test]
     fn test_assembler_top_level_segments_only() {
         let mig = make_mig_schema(vec!["UNH", "BGM", "DTM", "UNT"], vec![]);
@@ -588,8 +596,9 @@ mod tests {
             group_id: "SG5".to_string(),
             repetitions: vec![AssembledGroupInstance {
                 segments: vec![AssembledSegment {
-                    tag: "LOC".to_string(),
-                    elements: vec![vec!["Z16".to_string(), "DE000111222333".to_string()]],
+                    tag: "LOC".into(),
+                    elements: vec![vec!["Z16".into(), "DE000111222333".into()]],
+                    location: None,
                 }],
                 child_groups: vec![],
                 skipped_segments: vec![],
@@ -599,12 +608,14 @@ mod tests {
         let sg4_instance = AssembledGroupInstance {
             segments: vec![
                 AssembledSegment {
-                    tag: "IDE".to_string(),
-                    elements: vec![vec!["24".to_string(), "TX001".to_string()]],
+                    tag: "IDE".into(),
+                    elements: vec![vec!["24".into(), "TX001".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "STS".to_string(),
-                    elements: vec![vec!["7".to_string()]],
+                    tag: "STS".into(),
+                    elements: vec![vec!["7".into()]],
+                    location: None,
                 },
             ],
             child_groups: vec![sg5],
@@ -642,6 +653,34 @@ mod tests {
         assert!(result.segments.iter().any(|s| s.tag == "DTM"));
     }
 
+    #[test]
+    fn test_assemble_segments_borrows_from_input() {
+        let input = "UNA:+.? 'UNH+MSG001+UTILMD:D:11A:UN:S2.1'BGM+E01'IDE+24+TX001'STS+7++E01'FTX+ACB+++A?+B'IDE+24+TX002'UNT+6+MSG001'";
+        let sg4 = make_mig_group("SG4", vec!["IDE", "STS", "FTX"], vec![]);
+        let mig = make_mig_schema(vec!["UNH", "BGM", "UNT"], vec![sg4]);
+        let assembler = Assembler::new(&mig);
+
+        let borrowed = crate::tokenize::parse_borrowed_segments(input).unwrap();
+        let tree = assembler.assemble_segments(borrowed).unwrap();
+        let owned = crate::tokenize::parse_str_to_segments(input).unwrap();
+        assert_eq!(
+            serde_json::to_value(&tree).unwrap(),
+            serde_json::to_value(assembler.assemble_generic(&owned).unwrap()).unwrap()
+        );
+
+        let first = &tree.groups[0].repetitions[0];
+        assert!(matches!(
+            first.segments[0].elements[1][0],
+            Cow::Borrowed("TX001")
+        ));
+        assert!(matches!(&first.segments[2].elements[3][0], Cow::Owned(v) if v == "A+B"));
+
+        let tree = tree.into_owned();
+        let first = &tree.groups[0].repetitions[0];
+        assert!(matches!(&first.segments[0].elements[1][0], Cow::Owned(v) if v == "TX001"));
+        assert_eq!(tree.segments[2].tag, "UNT");
+    }
+
     #[test]
     fn test_assemble_with_diagnostics_clean_input() {
         let mig = make_mig_schema(vec!["UNH", "BGM", "UNT"], vec![]);
@@ -697,6 +736,131 @@ mod tests {
         assert_eq!(diagnostics[2].segment_id, "BAZ");
     }
 
+    // ── Lenient assembly tests ──
+
+    fn repairs(diagnostics: &[StructureDiagnostic]) -> Vec<&StructureDiagnostic> {
+        diagnostics.iter().filter(|d| d.repair.is_some()).collect()
+    }
+
+    #[test]
+    fn test_assemble_lenient_skips_unknown_segment() {
+        let mig = make_mig_schema(vec!["UNH", "BGM", "DTM", "UNT"], vec![]);
+        let segments = vec![
+            make_owned_seg("UNH", vec![vec!["001"]]),
+            make_owned_seg("BGM", vec![vec!["E01"]]),
+            make_owned_seg("XXX", vec![vec!["junk"]]),
+            make_owned_seg("DTM", vec![vec!["137"]]),
+            make_owned_seg("UNT", vec![vec!["5", "001"]]),
+        ];
+        let assembler = Assembler::new(&mig);
+        let (tree, diagnostics) = assembler.assemble_lenient(&segments);
+
+        let tags: Vec<&str> = tree.segments.iter().map(|s| s.tag.as_ref()).collect();
+        assert_eq!(tags, vec!["UNH", "BGM", "DTM", "UNT"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            StructureDiagnosticKind::UnexpectedSegment
+        );
+        assert_eq!(diagnostics[0].repair, Some(StructureRepair::Skipped));
+        assert_eq!(diagnostics[0].segment_id, "XXX");
+        assert_eq!(diagnostics[0].position, 2);
+    }
+
+    #[test]
+    fn test_assemble_lenient_inserts_missing_group_entry() {
+        let sg5 = make_mig_group("SG5", vec!["LOC"], vec![]);
+        let sg4 = make_mig_group("SG4", vec!["IDE", "STS"], vec![sg5]);
+        let mig = make_mig_schema(vec!["UNH", "BGM", "UNT"], vec![sg4]);
+        let segments = vec![
+            make_owned_seg("UNH", vec![vec!["001"]]),
+            make_owned_seg("BGM", vec![vec!["E01"]]),
+            make_owned_seg("IDE", vec![vec!["24", "TX1"]]),
+            make_owned_seg("STS", vec![vec!["7"]]),
+            make_owned_seg("LOC", vec![vec!["Z16", "MALO1"]]),
+            // IDE of the second transaction is missing
+            make_owned_seg("STS", vec![vec!["7"]]),
+            make_owned_seg("LOC", vec![vec!["Z16", "MALO2"]]),
+            make_owned_seg("UNT", vec![vec!["8", "001"]]),
+        ];
+        let assembler = Assembler::new(&mig);
+        let (tree, diagnostics) = assembler.assemble_lenient(&segments);
+
+        assert_eq!(tree.groups[0].repetitions.len(), 2);
+        let second = &tree.groups[0].repetitions[1];
+        assert_eq!(second.segments[0].tag, "IDE");
+        assert!(second.segments[0].elements.is_empty());
+        assert_eq!(
+            second.child_groups[0].repetitions[0].segments[0].elements[0][1],
+            "MALO2"
+        );
+        assert!(tree.segments.iter().any(|s| s.tag == "UNT"));
+
+        let repairs = repairs(&diagnostics);
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].repair, Some(StructureRepair::Inserted));
+        assert_eq!(repairs[0].segment_id, "IDE");
+        assert_eq!(repairs[0].position, 5);
+        assert_eq!(repairs[0].mig_path.as_deref(), Some("SG4/IDE"));
+    }
+
+    #[test]
+    fn test_assemble_lenient_resyncs_on_next_group_entry() {
+        let sg4 = make_mig_group("SG4", vec!["IDE", "STS"], vec![]);
+        let mig = make_mig_schema(vec!["UNH", "BGM", "UNT"], vec![sg4]);
+        let segments = vec![
+            make_owned_seg("UNH", vec![vec!["001"]]),
+            make_owned_seg("BGM", vec![vec!["E01"]]),
+            make_owned_seg("IDE", vec![vec!["24", "TX1"]]),
+            make_owned_seg("STS", vec![vec!["7"]]),
+            make_owned_seg("XXX", vec![]),
+            make_owned_seg("YYY", vec![]),
+            make_owned_seg("IDE", vec![vec!["24", "TX2"]]),
+            make_owned_seg("STS", vec![vec!["7"]]),
+            make_owned_seg("UNT", vec![vec!["9", "001"]]),
+        ];
+        let assembler = Assembler::new(&mig);
+        let (tree, diagnostics) = assembler.assemble_lenient(&segments);
+
+        assert_eq!(tree.groups[0].repetitions.len(), 2);
+        assert_eq!(
+            tree.groups[0].repetitions[1].segments[0].elements[0][1],
+            "TX2"
+        );
+        let repairs = repairs(&diagnostics);
+        let skipped: Vec<(&str, usize)> = repairs
+            .iter()
+            .map(|d| (d.segment_id.as_str(), d.position))
+            .collect();
+        assert_eq!(skipped, vec![("XXX", 4), ("YYY", 5)]);
+        assert!(repairs[0].message.contains("resynced on 'IDE'"));
+    }
+
+    #[test]
+    fn test_assemble_lenient_reports_misplaced_known_segment() {
+        let sg4 = make_mig_group("SG4", vec!["IDE", "STS"], vec![]);
+        let mig = make_mig_schema(vec!["UNH", "BGM", "UNT"], vec![sg4]);
+        let segments = vec![
+            make_owned_seg("UNH", vec![vec!["001"]]),
+            make_owned_seg("IDE", vec![vec!["24", "TX1"]]),
+            make_owned_seg("BGM", vec![vec!["E01"]]),
+            make_owned_seg("STS", vec![vec!["7"]]),
+            make_owned_seg("UNT", vec![vec!["5", "001"]]),
+        ];
+        let assembler = Assembler::new(&mig);
+        let (tree, diagnostics) = assembler.assemble_lenient(&segments);
+
+        // BGM still fills its root slot after the group; STS has lost its IDE.
+        assert!(tree.segments.iter().any(|s| s.tag == "BGM"));
+        assert!(tree.segments.iter().any(|s| s.tag == "UNT"));
+        let repairs = repairs(&diagnostics);
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].kind, StructureDiagnosticKind::OutOfOrderSegment);
+        assert_eq!(repairs[0].segment_id, "STS");
+        assert_eq!(repairs[0].mig_path.as_deref(), Some("SG4/STS"));
+        assert_eq!(repairs[0].position, 3);
+    }
+
     // ── Skip-unknown-segments tests ──
 
     #[test]
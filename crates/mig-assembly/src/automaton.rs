@@ -0,0 +1,650 @@
+//! MIG schemas compiled into table-driven assembly automata.
+//!
+//! [`CompiledMig::compile`] flattens a (usually PID-filtered) MIG once into
+//! per-group transition tables keyed by (state, segment tag). Assembly then
+//! costs one table lookup per input segment instead of a walk over the MIG
+//! for every message. The tables encode the assembly rules exactly: entry
+//! runs bounded by their slot count, greedy runs of repeated slots, nested
+//! groups tried in MIG order, inter-group root segments, and — with
+//! [`AssemblerConfig::skip_unknown_segments`] — skipping segments that match
+//! no remaining slot of the group.
+//!
+//! Transitions are keyed by tag alone: assembly has never told slots apart
+//! by qualifier. Same-tag group variants are merged by
+//! [`crate::pid_filter`] and distinguished during mapping.
+//!
+//! A `CompiledMig` is plain data and serializes with serde, so it can be
+//! cached next to the MIG it was compiled from.
+
+use std::collections::{BTreeMap, HashMap};
+
+use mig_types::schema::mig::{MigSchema, MigSegmentGroup};
+use serde::{Deserialize, Serialize};
+
+use crate::assembler::{
+    AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree, AssemblerConfig,
+};
+use crate::AssemblyError;
+
+/// Interned, upper-cased segment tag.
+type Symbol = u16;
+
+/// A MIG compiled into transition tables for [`crate::assembler::Assembler`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledMig {
+    /// Every tag the MIG mentions, upper-cased.
+    symbols: HashMap<String, Symbol>,
+    root: RootProgram,
+    groups: Vec<GroupProgram>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RootProgram {
+    /// Root segment slots in MIG order.
+    slots: Vec<Symbol>,
+    /// Top-level groups in MIG order (indices into `CompiledMig::groups`).
+    groups: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GroupProgram {
+    id: String,
+    /// Entry segment; `None` for a group without segments, which fails
+    /// assembly when reached.
+    entry: Option<Symbol>,
+    /// Entry segments one repetition takes (slots in the entry run).
+    entry_len: usize,
+    /// Sorted tags with a column in `table`.
+    alphabet: Vec<Symbol>,
+    states: Vec<GroupState>,
+    /// `table[state * alphabet.len() + column]`
+    table: Vec<Transition>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct GroupState {
+    /// State after skipping an unmatched segment; `None` where skipping is
+    /// not allowed (among the nested groups).
+    skip_to: Option<u32>,
+    /// What an unmatched segment does when it is not skipped.
+    fallback: Transition,
+}
+
+/// Step taken for the segment at the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Transition {
+    /// No remaining slot or nested group takes the segment.
+    Unmatched,
+    /// Append the segment to the instance and move to state `next`.
+    Consume { next: u32 },
+    /// Assemble group `group` from here, then continue in state `next`.
+    Enter { group: u32, next: u32 },
+    /// End the instance; the segment belongs to the next repetition or an
+    /// enclosing scope.
+    Close,
+    /// Reached group `group`, which has no segments.
+    Fail { group: u32 },
+}
+
+impl CompiledMig {
+    /// Compile `mig` into transition tables. Compilation never fails; MIG
+    /// defects surface as errors when assembly reaches them, as they would
+    /// when walking the MIG.
+    pub fn compile(mig: &MigSchema) -> Self {
+        let mut compiler = Compiler::default();
+        let slots = mig
+            .segments
+            .iter()
+            .map(|s| compiler.intern(&s.id))
+            .collect();
+        let groups = mig
+            .segment_groups
+            .iter()
+            .map(|g| compiler.compile_group(g))
+            .collect();
+        Self {
+            symbols: compiler.symbols,
+            root: RootProgram { slots, groups },
+            groups: compiler.groups,
+        }
+    }
+
+    /// The symbol of every input segment, from its tag.
+    pub(crate) fn symbols<'t>(&self, tags: impl Iterator<Item = &'t str>) -> Vec<Option<Symbol>> {
+        tags.map(|tag| self.symbol(tag)).collect()
+    }
+
+    /// Assemble the input into a tree; see
+    /// [`crate::assembler::Assembler::assemble_generic`]. `symbols` come
+    /// from [`Self::symbols`]; `segments` yields the input segments in the
+    /// same order and is advanced only as far as assembly consumes.
+    pub(crate) fn assemble<'s>(
+        &self,
+        symbols: Vec<Option<Symbol>>,
+        segments: impl Iterator<Item = AssembledSegment<'s>>,
+        config: &AssemblerConfig,
+    ) -> Result<AssembledTree<'s>, AssemblyError> {
+        let mut run = Run {
+            mig: self,
+            symbols,
+            segments,
+            pos: 0,
+            skip_unknown: config.skip_unknown_segments,
+        };
+        run.assemble_root()
+    }
+
+    fn symbol(&self, tag: &str) -> Option<Symbol> {
+        if tag.bytes().any(|b| b.is_ascii_lowercase()) {
+            self.symbols.get(&tag.to_ascii_uppercase()).copied()
+        } else {
+            self.symbols.get(tag).copied()
+        }
+    }
+}
+
+impl GroupProgram {
+    fn transition(&self, state: usize, symbol: Option<Symbol>) -> Transition {
+        match symbol.and_then(|s| self.alphabet.binary_search(&s).ok()) {
+            Some(column) => self.table[state * self.alphabet.len() + column],
+            None => Transition::Unmatched,
+        }
+    }
+}
+
+/// One assembly over an input.
+struct Run<'c, I> {
+    mig: &'c CompiledMig,
+    symbols: Vec<Option<Symbol>>,
+    /// Input segments from `pos` on.
+    segments: I,
+    /// Cursor into the input.
+    pos: usize,
+    skip_unknown: bool,
+}
+
+impl<'s, I: Iterator<Item = AssembledSegment<'s>>> Run<'_, I> {
+    fn assemble_root(&mut self) -> Result<AssembledTree<'s>, AssemblyError> {
+        let root = &self.mig.root;
+        let mut tree = AssembledTree {
+            segments: Vec::new(),
+            groups: Vec::new(),
+            post_group_start: 0,
+            inter_group_segments: BTreeMap::new(),
+        };
+        let mut matched = vec![false; root.slots.len()];
+
+        // First pass: root segments before the groups.
+        let mut next_slot = 0;
+        while let Some(slot) = self.root_slot(next_slot, &matched) {
+            tree.segments.push(self.take());
+            matched[slot] = true;
+            next_slot = slot + 1;
+        }
+
+        // Groups, each preceded by root segments still unmatched (e.g. the
+        // UNS section separator in MSCONS).
+        for &group in &root.groups {
+            if self.at_end() {
+                break;
+            }
+            let group_idx = tree.groups.len();
+            let mut next_slot = 0;
+            while let Some(slot) = self.root_slot(next_slot, &matched) {
+                let segment = self.take();
+                tree.inter_group_segments
+                    .entry(group_idx)
+                    .or_default()
+                    .push(segment);
+                matched[slot] = true;
+                next_slot = slot + 1;
+            }
+            match self.mig.groups[group].entry {
+                None => return Err(empty_group(&self.mig.groups[group])),
+                Some(entry) if self.symbol() == Some(entry) => {
+                    tree.groups.push(self.assemble_group(group)?);
+                }
+                Some(_) => {}
+            }
+        }
+
+        tree.post_group_start = tree.segments.len();
+
+        // Second pass: root segments after the groups (e.g. UNT).
+        let mut next_slot = 0;
+        while let Some(slot) = self.root_slot(next_slot, &matched) {
+            tree.segments.push(self.take());
+            matched[slot] = true;
+            next_slot = slot + 1;
+        }
+
+        Ok(tree)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.symbols.len()
+    }
+
+    /// Symbol of the segment at the cursor; `None` at the end of the input
+    /// or for a tag the MIG does not mention.
+    fn symbol(&self) -> Option<Symbol> {
+        self.symbols.get(self.pos).copied().flatten()
+    }
+
+    /// Take the segment at the cursor and advance.
+    fn take(&mut self) -> AssembledSegment<'s> {
+        self.pos += 1;
+        self.segments.next().expect("one input segment per symbol")
+    }
+
+    /// First unmatched root slot from `from` on that takes the segment at the cursor.
+    fn root_slot(&self, from: usize, matched: &[bool]) -> Option<usize> {
+        let symbol = self.symbol()?;
+        let slots = &self.mig.root.slots;
+        (from..slots.len()).find(|&i| !matched[i] && slots[i] == symbol)
+    }
+
+    /// Assemble every repetition of `group` from the cursor on, where the
+    /// segment must be the group's entry segment.
+    fn assemble_group(&mut self, group: usize) -> Result<AssembledGroup<'s>, AssemblyError> {
+        let mig = self.mig;
+        let program = &mig.groups[group];
+        let entry = program.entry.ok_or_else(|| empty_group(program))?;
+        let mut repetitions = Vec::new();
+
+        while self.symbol() == Some(entry) {
+            let mut instance = AssembledGroupInstance {
+                segments: Vec::new(),
+                child_groups: Vec::new(),
+                skipped_segments: Vec::new(),
+            };
+            for _ in 0..program.entry_len {
+                if self.symbol() != Some(entry) {
+                    break;
+                }
+                instance.segments.push(self.take());
+            }
+
+            let mut state = 0;
+            while !self.at_end() {
+                let mut transition = program.transition(state, self.symbol());
+                if transition == Transition::Unmatched {
+                    let info = program.states[state];
+                    if let Some(next) = info.skip_to.filter(|_| self.skip_unknown) {
+                        instance.skipped_segments.push(self.take());
+                        state = next as usize;
+                        continue;
+                    }
+                    transition = info.fallback;
+                }
+                match transition {
+                    Transition::Consume { next } => {
+                        instance.segments.push(self.take());
+                        state = next as usize;
+                    }
+                    Transition::Enter { group, next } => {
+                        let child = self.assemble_group(group as usize)?;
+                        instance.child_groups.push(child);
+                        state = next as usize;
+                    }
+                    Transition::Fail { group } => {
+                        return Err(empty_group(&mig.groups[group as usize]));
+                    }
+                    Transition::Close | Transition::Unmatched => break,
+                }
+            }
+
+            repetitions.push(instance);
+        }
+
+        Ok(AssembledGroup {
+            group_id: program.id.clone(),
+            repetitions,
+        })
+    }
+}
+
+fn empty_group(program: &GroupProgram) -> AssemblyError {
+    AssemblyError::ParseError(format!("Group {} has no segments", program.id))
+}
+
+#[derive(Default)]
+struct Compiler {
+    symbols: HashMap<String, Symbol>,
+    groups: Vec<GroupProgram>,
+}
+
+/// A nested group as seen from its parent's tables.
+struct NestedRef {
+    entry: Option<Symbol>,
+    group: u32,
+}
+
+impl Compiler {
+    fn intern(&mut self, tag: &str) -> Symbol {
+        let next = self.symbols.len() as Symbol;
+        *self.symbols.entry(tag.to_ascii_uppercase()).or_insert(next)
+    }
+
+    /// Compile `group` and its nested groups; returns its index.
+    ///
+    /// States, for `R` runs of same-tag slots (run 0 = entry) and `N` nested
+    /// groups:
+    /// - `0..R`: before run `p + 1` (runs up to `p` done),
+    /// - `R..2R-1`: inside greedy run `j = state - R + 1`,
+    /// - `2R-1..2R-1+N`: nested groups before `n = state - 2R + 2` done.
+    fn compile_group(&mut self, group: &MigSegmentGroup) -> usize {
+        let index = self.groups.len();
+        self.groups.push(GroupProgram {
+            id: group.id.clone(),
+            entry: None,
+            entry_len: 0,
+            alphabet: Vec::new(),
+            states: Vec::new(),
+            table: Vec::new(),
+        });
+
+        // Runs of consecutive slots with the same tag.
+        let mut runs: Vec<Symbol> = Vec::new();
+        let mut entry_len = 0;
+        for (i, slot) in group.segments.iter().enumerate() {
+            if i > 0 && group.segments[i - 1].id == slot.id {
+                if runs.len() == 1 {
+                    entry_len += 1;
+                }
+                continue;
+            }
+            runs.push(self.intern(&slot.id));
+            if runs.len() == 1 {
+                entry_len = 1;
+            }
+        }
+
+        let nested: Vec<NestedRef> = group
+            .nested_groups
+            .iter()
+            .map(|ng| {
+                let entry = ng.segments.first().map(|s| self.intern(&s.id));
+                let group = self.compile_group(ng) as u32;
+                NestedRef { entry, group }
+            })
+            .collect();
+
+        let mut program = GroupProgram {
+            id: group.id.clone(),
+            entry: runs.first().copied(),
+            entry_len,
+            alphabet: Vec::new(),
+            states: Vec::new(),
+            table: Vec::new(),
+        };
+        if !runs.is_empty() {
+            build_tables(&mut program, &runs, &nested);
+        }
+        self.groups[index] = program;
+        index
+    }
+}
+
+fn build_tables(program: &mut GroupProgram, runs: &[Symbol], nested: &[NestedRef]) {
+    let r = runs.len();
+    let before = |p: usize| (p - 1) as u32;
+    let inside = |j: usize| (r + j - 1) as u32;
+    let nested_state = |n: usize| (2 * r - 1 + n - 1) as u32;
+
+    // First nested group from `from` on that takes `symbol`, failing on any
+    // segment-less group passed on the way.
+    let nested_from = |from: usize, symbol: Option<Symbol>| {
+        for (m, ng) in nested.iter().enumerate().skip(from) {
+            match ng.entry {
+                None => return Transition::Fail { group: ng.group },
+                Some(entry) if Some(entry) == symbol => {
+                    return Transition::Enter {
+                        group: ng.group,
+                        next: nested_state(m + 1),
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        Transition::Close
+    };
+
+    let mut alphabet: Vec<Symbol> = runs
+        .iter()
+        .copied()
+        .chain(nested.iter().filter_map(|ng| ng.entry))
+        .collect();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    let width = alphabet.len();
+
+    let mut states = Vec::new();
+    let mut table = Vec::new();
+
+    // Before run p: a later run takes the tag, else the entry tag and nested
+    // entries stop skipping and go to the nested groups.
+    let before_row = |p: usize| -> Vec<Transition> {
+        alphabet
+            .iter()
+            .map(|&symbol| {
+                if let Some(j) = (p..r).find(|&j| runs[j] == symbol) {
+                    Transition::Consume { next: inside(j) }
+                } else if symbol == runs[0] || nested.iter().any(|ng| ng.entry == Some(symbol)) {
+                    nested_from(0, Some(symbol))
+                } else {
+                    Transition::Unmatched
+                }
+            })
+            .collect()
+    };
+    let fallback = nested_from(0, None);
+
+    for p in 1..=r {
+        debug_assert_eq!(states.len() as u32, before(p));
+        states.push(GroupState {
+            skip_to: Some(before(p)),
+            fallback,
+        });
+        table.extend(before_row(p));
+    }
+    for (j, run) in runs.iter().enumerate().skip(1) {
+        debug_assert_eq!(states.len() as u32, inside(j));
+        let mut row = before_row(j + 1);
+        let column = alphabet.binary_search(run).expect("run tag in alphabet");
+        row[column] = Transition::Consume { next: inside(j) };
+        // A skip ends the greedy run.
+        states.push(GroupState {
+            skip_to: Some(before(j + 1)),
+            fallback,
+        });
+        table.extend(row);
+    }
+    for n in 1..=nested.len() {
+        debug_assert_eq!(states.len() as u32, nested_state(n));
+        states.push(GroupState {
+            skip_to: None,
+            fallback: nested_from(n, None),
+        });
+        table.extend(alphabet.iter().map(|&symbol| nested_from(n, Some(symbol))));
+    }
+    debug_assert_eq!(table.len(), states.len() * width);
+
+    program.alphabet = alphabet;
+    program.states = states;
+    program.table = table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::owned_to_assembled;
+    use crate::test_support::{make_mig_group, make_mig_segment};
+    use crate::tokenize::OwnedSegment;
+
+    fn seg(id: &str, value: &str) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: vec![vec![value.to_string()]],
+            segment_number: 0,
+            location: None,
+        }
+    }
+
+    fn mig(root: &[&str], groups: Vec<MigSegmentGroup>) -> MigSchema {
+        MigSchema {
+            message_type: "UTILMD".to_string(),
+            variant: Some("Strom".to_string()),
+            version: "S2.1".to_string(),
+            publication_date: "2025-03-20".to_string(),
+            author: "BDEW".to_string(),
+            format_version: "FV2504".to_string(),
+            source_file: "test".to_string(),
+            segments: root.iter().map(|id| make_mig_segment(id)).collect(),
+            segment_groups: groups,
+        }
+    }
+
+    fn run<'s>(
+        compiled: &CompiledMig,
+        segments: &'s [OwnedSegment],
+        config: &AssemblerConfig,
+    ) -> Result<AssembledTree<'s>, AssemblyError> {
+        let symbols = compiled.symbols(segments.iter().map(|s| s.id.as_str()));
+        compiled.assemble(symbols, segments.iter().map(owned_to_assembled), config)
+    }
+
+    fn tags<'t>(segments: &'t [AssembledSegment<'_>]) -> Vec<&'t str> {
+        segments.iter().map(|s| &*s.tag).collect()
+    }
+
+    #[test]
+    fn test_skip_ends_greedy_run() {
+        let schema = mig(
+            &["UNH", "UNT"],
+            vec![make_mig_group("SG1", vec!["RFF", "DTM"], vec![])],
+        );
+        let segments = vec![
+            seg("UNH", "1"),
+            seg("RFF", "Z13"),
+            seg("DTM", "a"),
+            seg("XXX", ""),
+            seg("DTM", "b"),
+        ];
+        let config = AssemblerConfig {
+            skip_unknown_segments: true,
+        };
+        let tree = run(&CompiledMig::compile(&schema), &segments, &config).unwrap();
+
+        let instance = &tree.groups[0].repetitions[0];
+        assert_eq!(tags(&instance.segments), vec!["RFF", "DTM"]);
+        // Once XXX is skipped the DTM run is over; the second DTM matches
+        // no remaining slot and is skipped as well.
+        assert_eq!(tags(&instance.skipped_segments), vec!["XXX", "DTM"]);
+        assert_eq!(tags(&tree.segments), vec!["UNH"]);
+    }
+
+    #[test]
+    fn test_entry_run_bounded_by_entry_slots() {
+        let schema = mig(
+            &["UNH", "UNT"],
+            vec![make_mig_group("SG4", vec!["IDE", "STS", "STS"], vec![])],
+        );
+        let segments = vec![
+            seg("UNH", "1"),
+            seg("IDE", "TX1"),
+            seg("IDE", "TX2"),
+            seg("STS", "a"),
+            seg("STS", "b"),
+            seg("STS", "c"),
+            seg("UNT", "7"),
+        ];
+        let tree = run(
+            &CompiledMig::compile(&schema),
+            &segments,
+            &AssemblerConfig::default(),
+        )
+        .unwrap();
+
+        let reps = &tree.groups[0].repetitions;
+        assert_eq!(reps.len(), 2);
+        assert_eq!(tags(&reps[0].segments), vec!["IDE"]);
+        assert_eq!(tags(&reps[1].segments), vec!["IDE", "STS", "STS", "STS"]);
+    }
+
+    #[test]
+    fn test_nested_groups_in_mig_order_and_case_insensitive_tags() {
+        let sg5 = make_mig_group("SG5", vec!["LOC"], vec![]);
+        let sg6 = make_mig_group("SG6", vec!["RFF"], vec![]);
+        let schema = mig(
+            &["UNH", "UNT"],
+            vec![make_mig_group("SG4", vec!["IDE"], vec![sg5, sg6])],
+        );
+        let segments = vec![
+            seg("UNH", "1"),
+            seg("ide", "TX1"),
+            seg("LOC", "a"),
+            seg("rff", "b"),
+            // SG5 is done once SG6 started: this LOC ends the message body
+            seg("LOC", "c"),
+            seg("UNT", "6"),
+        ];
+        let tree = run(
+            &CompiledMig::compile(&schema),
+            &segments,
+            &AssemblerConfig::default(),
+        )
+        .unwrap();
+
+        let instance = &tree.groups[0].repetitions[0];
+        let child_ids: Vec<&str> = instance
+            .child_groups
+            .iter()
+            .map(|g| g.group_id.as_str())
+            .collect();
+        assert_eq!(child_ids, vec!["SG5", "SG6"]);
+        assert_eq!(tags(&tree.segments), vec!["UNH"]);
+    }
+
+    #[test]
+    fn test_group_without_segments_fails_when_reached() {
+        let empty = make_mig_group("SG5", vec![], vec![]);
+        let schema = mig(
+            &["UNH", "UNT"],
+            vec![make_mig_group("SG4", vec!["IDE"], vec![empty])],
+        );
+        let compiled = CompiledMig::compile(&schema);
+
+        let no_group = vec![seg("UNH", "1"), seg("UNT", "2")];
+        assert!(run(&compiled, &no_group, &AssemblerConfig::default()).is_ok());
+
+        let with_group = vec![seg("UNH", "1"), seg("IDE", "TX1"), seg("UNT", "3")];
+        let err = run(&compiled, &with_group, &AssemblerConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("Group SG5 has no segments"));
+    }
+
+    #[test]
+    fn test_compiled_mig_serde_roundtrip() {
+        let sg5 = make_mig_group("SG5", vec!["LOC"], vec![]);
+        let schema = mig(
+            &["UNH", "BGM", "UNT"],
+            vec![make_mig_group("SG4", vec!["IDE", "STS"], vec![sg5])],
+        );
+        let segments = vec![
+            seg("UNH", "1"),
+            seg("BGM", "E01"),
+            seg("IDE", "TX1"),
+            seg("STS", "7"),
+            seg("LOC", "a"),
+            seg("UNT", "6"),
+        ];
+        let compiled = CompiledMig::compile(&schema);
+        let json = serde_json::to_string(&compiled).unwrap();
+        let restored: CompiledMig = serde_json::from_str(&json).unwrap();
+
+        let config = AssemblerConfig::default();
+        let expected = serde_json::to_value(run(&compiled, &segments, &config).unwrap());
+        let actual = serde_json::to_value(run(&restored, &segments, &config).unwrap());
+        assert_eq!(actual.unwrap(), expected.unwrap());
+    }
+}
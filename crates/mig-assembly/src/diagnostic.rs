@@ -1,5 +1,6 @@
 //! Structure diagnostics emitted during MIG-guided assembly.
 
+use mig_types::segment::SegmentLocation;
 use serde::{Deserialize, Serialize};
 
 /// A structure-level issue found during MIG-guided assembly.
@@ -9,6 +10,15 @@ pub struct StructureDiagnostic {
     pub segment_id: String,
     pub position: usize,
     pub message: String,
+    /// MIG path of the offending segment or group (e.g. `SG4/SG5/LOC`).
+    #[serde(default)]
+    pub mig_path: Option<String>,
+    /// Input location of the segment at `position`, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SegmentLocation>,
+    /// Repair lenient assembly made to get past this issue, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repair: Option<StructureRepair>,
 }
 
 /// Classification of structure-level diagnostic issues.
@@ -18,19 +28,40 @@ pub enum StructureDiagnosticKind {
     UnexpectedSegment,
     /// A mandatory segment defined in the MIG schema was not found.
     MissingRequiredSegment,
-    /// A segment or group exceeded its maximum allowed repetitions.
+    /// A segment exceeded its maximum allowed repetitions.
     MaxRepetitionsExceeded,
     /// A qualifier value was not recognized for the current MIG context.
     UnrecognizedQualifier,
+    /// A mandatory segment group defined in the MIG schema was not found.
+    MissingRequiredGroup,
+    /// A segment group exceeded its maximum allowed repetitions.
+    GroupMaxRepetitionsExceeded,
+    /// A segment defined in the MIG schema appeared at a position where
+    /// the MIG does not allow it.
+    OutOfOrderSegment,
+}
+
+/// Input repair applied by [`crate::assembler::Assembler::assemble_lenient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StructureRepair {
+    /// The segment at `position` was left out of the assembled tree.
+    Skipped,
+    /// An empty `segment_id` segment was assembled before `position` so the
+    /// segments following it could be placed in their group.
+    Inserted,
 }
 
 impl std::fmt::Display for StructureDiagnostic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "[{:?}] {} at position {}: {}",
-            self.kind, self.segment_id, self.position, self.message
-        )
+            "[{:?}] {} at position {}",
+            self.kind, self.segment_id, self.position
+        )?;
+        if let Some(location) = &self.location {
+            write!(f, " (line {}, column {})", location.line, location.column)?;
+        }
+        write!(f, ": {}", self.message)
     }
 }
 
@@ -45,6 +76,9 @@ mod tests {
             segment_id: "BGM".to_string(),
             position: 3,
             message: "BGM not expected after UNH".to_string(),
+            mig_path: None,
+            location: None,
+            repair: None,
         };
         let display = diag.to_string();
         assert!(display.contains("BGM"), "display should contain segment_id");
@@ -66,6 +100,9 @@ mod tests {
             segment_id: "IDE".to_string(),
             position: 5,
             message: "mandatory IDE segment missing in SG4".to_string(),
+            mig_path: None,
+            location: None,
+            repair: None,
         };
         let display = diag.to_string();
         assert_eq!(
@@ -81,6 +118,9 @@ mod tests {
             segment_id: "RFF".to_string(),
             position: 12,
             message: "RFF exceeded max repetitions of 5".to_string(),
+            mig_path: None,
+            location: None,
+            repair: None,
         };
         let json = serde_json::to_string(&diag).expect("serialize");
         let roundtripped: StructureDiagnostic = serde_json::from_str(&json).expect("deserialize");
@@ -100,6 +140,9 @@ mod tests {
             segment_id: "LOC".to_string(),
             position: 7,
             message: "qualifier Z99 not recognized for LOC in SG5".to_string(),
+            mig_path: None,
+            location: None,
+            repair: None,
         };
         let json = serde_json::to_string(&diag).expect("serialize");
         let roundtripped: StructureDiagnostic = serde_json::from_str(&json).expect("deserialize");
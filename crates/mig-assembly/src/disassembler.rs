@@ -152,10 +152,14 @@ impl<'a> Disassembler<'a> {
     }
 }
 
-fn assembled_to_disassembled(seg: &AssembledSegment) -> DisassembledSegment {
+fn assembled_to_disassembled(seg: &AssembledSegment<'_>) -> DisassembledSegment {
     DisassembledSegment {
-        tag: seg.tag.clone(),
-        elements: seg.elements.clone(),
+        tag: seg.tag.to_string(),
+        elements: seg
+            .elements
+            .iter()
+            .map(|e| e.iter().map(|c| c.to_string()).collect())
+            .collect(),
     }
 }
 
@@ -185,25 +189,23 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
+                    tag: "UNH".into(),
                     elements: vec![
-                        vec!["1".to_string()],
+                        vec!["1".into()],
                         vec![
-                            "UTILMD".to_string(),
-                            "D".to_string(),
-                            "11A".to_string(),
-                            "UN".to_string(),
-                            "S2.1".to_string(),
+                            "UTILMD".into(),
+                            "D".into(),
+                            "11A".into(),
+                            "UN".into(),
+                            "S2.1".into(),
                         ],
                     ],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![
-                        vec!["E01".to_string()],
-                        vec!["MSG001".to_string()],
-                        vec!["9".to_string()],
-                    ],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()], vec!["MSG001".into()], vec!["9".into()]],
+                    location: None,
                 },
             ],
             groups: vec![],
@@ -237,12 +239,14 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
-                    elements: vec![vec!["1".to_string()]],
+                    tag: "UNH".into(),
+                    elements: vec![vec!["1".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![vec!["E01".to_string()]],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()]],
+                    location: None,
                 },
             ],
             post_group_start: 2,
@@ -251,16 +255,18 @@ mod tests {
                 repetitions: vec![
                     AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "NAD".to_string(),
-                            elements: vec![vec!["MS".to_string()]],
+                            tag: "NAD".into(),
+                            elements: vec![vec!["MS".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
                     },
                     AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "NAD".to_string(),
-                            elements: vec![vec!["MR".to_string()]],
+                            tag: "NAD".into(),
+                            elements: vec![vec!["MR".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
@@ -299,31 +305,32 @@ mod tests {
 
         let tree = AssembledTree {
             segments: vec![AssembledSegment {
-                tag: "UNH".to_string(),
-                elements: vec![vec!["1".to_string()]],
+                tag: "UNH".into(),
+                elements: vec![vec!["1".into()]],
+                location: None,
             }],
             post_group_start: 1,
             groups: vec![AssembledGroup {
                 group_id: "SG2".to_string(),
                 repetitions: vec![AssembledGroupInstance {
                     segments: vec![AssembledSegment {
-                        tag: "NAD".to_string(),
-                        elements: vec![vec!["MS".to_string()]],
+                        tag: "NAD".into(),
+                        elements: vec![vec!["MS".into()]],
+                        location: None,
                     }],
                     child_groups: vec![AssembledGroup {
                         group_id: "SG3".to_string(),
                         repetitions: vec![AssembledGroupInstance {
                             segments: vec![
                                 AssembledSegment {
-                                    tag: "CTA".to_string(),
-                                    elements: vec![vec!["IC".to_string()]],
+                                    tag: "CTA".into(),
+                                    elements: vec![vec!["IC".into()]],
+                                    location: None,
                                 },
                                 AssembledSegment {
-                                    tag: "COM".to_string(),
-                                    elements: vec![vec![
-                                        "040@ex.com".to_string(),
-                                        "EM".to_string(),
-                                    ]],
+                                    tag: "COM".into(),
+                                    elements: vec![vec!["040@ex.com".into(), "EM".into()]],
+                                    location: None,
                                 },
                             ],
                             child_groups: vec![],
@@ -20,6 +20,9 @@ pub enum AssemblyError {
     #[error("PID detection failed: could not determine PID from segments")]
     PidDetectionFailed,
 
+    #[error("PID detection ambiguous: message matches PIDs {candidates:?}")]
+    AmbiguousPid { candidates: Vec<String> },
+
     #[error("Segment cursor out of bounds at position {0}")]
     CursorOutOfBounds(usize),
 
@@ -28,6 +31,13 @@ pub enum AssemblyError {
 
     #[error("Expected segment '{expected}' not found")]
     SegmentNotFound { expected: String },
+
+    #[error("Cannot encode segment '{segment_id}': {source}")]
+    Encoding {
+        segment_id: String,
+        #[source]
+        source: edifact_types::EncodeError,
+    },
 }
 
 impl From<mig_types::cursor::SegmentNotFound> for AssemblyError {
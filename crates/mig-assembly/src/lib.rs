@@ -9,8 +9,12 @@
 //! let segments = parse_to_segments(input);
 //! let tree = assemble_generic(&segments, &mig_schema)?;
 //! ```
+//!
+//! For multi-GB interchanges, [`MessageChunkReader`] reads from a `BufRead`
+//! and yields one message at a time instead of collecting all segments.
 
 pub mod assembler;
+pub mod automaton;
 pub mod cursor;
 pub mod diagnostic;
 pub mod disassembler;
@@ -20,14 +24,23 @@ pub mod navigator;
 pub mod parsing;
 pub mod pid_detect;
 pub mod pid_filter;
+pub mod query;
+mod recovery;
 pub mod renderer;
 pub mod roundtrip;
 pub mod service;
+pub mod streaming;
+pub mod structure;
 pub mod test_support;
 pub mod tokenize;
 
-pub use diagnostic::{StructureDiagnostic, StructureDiagnosticKind};
+pub use automaton::CompiledMig;
+pub use diagnostic::{StructureDiagnostic, StructureDiagnosticKind, StructureRepair};
 pub use error::AssemblyError;
 pub use navigator::AssembledTreeNavigator;
+pub use query::{Query, QueryError, QueryMatch};
 pub use service::ConversionService;
-pub use tokenize::{split_messages, InterchangeChunks, MessageChunk};
+pub use streaming::MessageChunkReader;
+pub use tokenize::{
+    split_interchanges, split_messages, FunctionalGroup, InterchangeChunks, MessageChunk,
+};
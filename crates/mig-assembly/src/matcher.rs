@@ -71,6 +71,7 @@ mod tests {
             id: "NAD".to_string(),
             elements: vec![vec!["Z04".to_string()]],
             segment_number: 1,
+            location: None,
         };
         assert!(matches_mig_node(&seg, "NAD", None));
         assert!(!matches_mig_node(&seg, "LOC", None));
@@ -82,6 +83,7 @@ mod tests {
             id: "NAD".to_string(),
             elements: vec![vec!["MS".to_string()]],
             segment_number: 1,
+            location: None,
         };
         assert!(matches_mig_node(&seg, "NAD", Some("MS")));
         assert!(!matches_mig_node(&seg, "NAD", Some("MR")));
@@ -93,6 +95,7 @@ mod tests {
             id: "UNH".to_string(),
             elements: vec![],
             segment_number: 1,
+            location: None,
         };
         // No qualifier check — just tag
         assert!(matches_mig_node(&seg, "UNH", None));
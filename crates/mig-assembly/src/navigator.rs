@@ -1,16 +1,17 @@
 //! GroupNavigator implementation backed by AssembledTree.
 
 use crate::assembler::{AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree};
+use crate::query::Query;
 use mig_types::navigator::GroupNavigator;
 use mig_types::segment::OwnedSegment;
 
 /// Wraps an `AssembledTree` reference to provide group-scoped segment queries.
 pub struct AssembledTreeNavigator<'a> {
-    tree: &'a AssembledTree,
+    tree: &'a AssembledTree<'a>,
 }
 
 impl<'a> AssembledTreeNavigator<'a> {
-    pub fn new(tree: &'a AssembledTree) -> Self {
+    pub fn new(tree: &'a AssembledTree<'a>) -> Self {
         Self { tree }
     }
 }
@@ -122,12 +123,30 @@ impl GroupNavigator for AssembledTreeNavigator<'_> {
         seg.elements
             .get(element_index)?
             .get(component_index)
-            .cloned()
+            .map(|c| c.to_string())
+    }
+
+    fn query_count(&self, query: &str) -> Option<usize> {
+        Some(Query::parse(query).ok()?.select(self.tree).len())
+    }
+
+    fn query_values(&self, query: &str) -> Option<Vec<String>> {
+        let matches = Query::parse(query).ok()?.select(self.tree);
+        Some(
+            matches
+                .iter()
+                .filter_map(|m| m.value())
+                .map(str::to_string)
+                .collect(),
+        )
     }
 }
 
 /// Navigate group hierarchy to find an AssembledGroup at the given path.
-fn resolve_group<'a>(groups: &'a [AssembledGroup], path: &[&str]) -> Option<&'a AssembledGroup> {
+fn resolve_group<'a, 's>(
+    groups: &'a [AssembledGroup<'s>],
+    path: &[&str],
+) -> Option<&'a AssembledGroup<'s>> {
     if path.is_empty() {
         return None;
     }
@@ -141,20 +160,25 @@ fn resolve_group<'a>(groups: &'a [AssembledGroup], path: &[&str]) -> Option<&'a
 }
 
 /// Navigate to a specific group instance at the given path.
-fn resolve_instance<'a>(
-    groups: &'a [AssembledGroup],
+fn resolve_instance<'a, 's>(
+    groups: &'a [AssembledGroup<'s>],
     path: &[&str],
     instance_index: usize,
-) -> Option<&'a AssembledGroupInstance> {
+) -> Option<&'a AssembledGroupInstance<'s>> {
     let group = resolve_group(groups, path)?;
     group.repetitions.get(instance_index)
 }
 
-fn to_owned(seg: &AssembledSegment, segment_number: u32) -> OwnedSegment {
+fn to_owned(seg: &AssembledSegment<'_>, segment_number: u32) -> OwnedSegment {
     OwnedSegment {
-        id: seg.tag.clone(),
-        elements: seg.elements.clone(),
+        id: seg.tag.to_string(),
+        elements: seg
+            .elements
+            .iter()
+            .map(|e| e.iter().map(|c| c.to_string()).collect())
+            .collect(),
         segment_number,
+        location: seg.location.clone(),
     }
 }
 
@@ -166,17 +190,18 @@ mod tests {
     };
     use mig_types::navigator::GroupNavigator;
 
-    fn make_seg(tag: &str, elements: Vec<Vec<&str>>) -> AssembledSegment {
+    fn make_seg(tag: &str, elements: Vec<Vec<&str>>) -> AssembledSegment<'static> {
         AssembledSegment {
-            tag: tag.to_string(),
+            tag: tag.to_string().into(),
             elements: elements
                 .into_iter()
-                .map(|e| e.into_iter().map(|c| c.to_string()).collect())
+                .map(|e| e.into_iter().map(|c| c.to_string().into()).collect())
                 .collect(),
+            location: None,
         }
     }
 
-    fn tree_with_sg4_sg8() -> AssembledTree {
+    fn tree_with_sg4_sg8() -> AssembledTree<'static> {
         // SG4[0] -> segments: [IDE, STS]
         //        -> SG8[0]: [SEQ+Z98, CCI+Z30++Z07]
         //             -> SG10[0]: [CCI+Z23, CAV+Z91:value1]
@@ -391,4 +416,18 @@ mod tests {
         // Invalid group path
         assert_eq!(nav.extract_value_in_group("SEQ", 0, 0, &["SG99"], 0), None);
     }
+
+    #[test]
+    fn test_query_count_and_values() {
+        let tree = tree_with_sg4_sg8();
+        let nav = AssembledTreeNavigator::new(&tree);
+        assert_eq!(nav.query_count("SG4/SG8[SEQ.0=Z98]/SG10"), Some(2));
+        assert_eq!(nav.query_count("SG4/SG8[SEQ.0=Z01]/SG10"), Some(0));
+        assert_eq!(
+            nav.query_values("//SG10/CAV.0.1"),
+            Some(vec!["value1".to_string(), "value2".to_string()])
+        );
+        // Invalid queries are not answered
+        assert_eq!(nav.query_count("SG4/"), None);
+    }
 }
@@ -1,17 +1,459 @@
 //! PID detection from EDIFACT segments.
 //!
 //! Determines the Pruefidentifikator (PID) from a list of parsed EDIFACT
-//! segments. For UTILMD messages, the PID is encoded in a combination of
-//! the BGM document name code and the RFF+Z13 reference in SG6.
+//! segments. Two detectors are available:
+//!
+//! - [`PidDetector`] is built from the generated PID schema JSON files
+//!   (`crates/mig-types/src/generated/{fv}/{msg}/pids/pid_*_schema.json`),
+//!   which carry the AHB code restrictions of every PID. It works for every
+//!   message type and returns ranked [`PidCandidate`]s with the evidence used,
+//!   so ambiguous messages can be reported instead of guessed.
+//! - [`detect_pid`] is a schema-free heuristic for UTILMD (RFF+Z13, then a
+//!   small BGM/STS table), kept for callers without access to the schemas.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
 
 use crate::tokenize::OwnedSegment;
 use crate::AssemblyError;
 
-/// Detect the PID (Pruefidentifikator) from a list of parsed EDIFACT segments.
+/// Segments whose coded values discriminate between PIDs.
+const DISCRIMINATOR_SEGMENTS: &[&str] = &["BGM", "DOC", "RFF", "IMD", "STS", "FTX"];
+
+/// A code restriction the AHB places on one element of a segment for a PID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PidCodeRule {
+    /// Segment tag (e.g., "STS").
+    pub segment: String,
+    /// Value of the segment's first component (e.g., "Z13" for RFF+Z13) when
+    /// the rule only applies to segments with that qualifier.
+    pub qualifier: Option<String>,
+    /// Element index within the segment.
+    pub element: usize,
+    /// Component index within the element.
+    pub component: usize,
+    /// Allowed code values.
+    pub codes: BTreeSet<String>,
+}
+
+/// Everything the AHB says about how to recognise one PID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PidSignature {
+    /// The PID (e.g., "55001").
+    pub pid: String,
+    /// UNH message type (S009/0065, e.g., "UTILMD").
+    pub message_type: Option<String>,
+    /// UNH association assigned code (S009/0057, e.g., "S2.1").
+    pub association_code: Option<String>,
+    /// Code restrictions on discriminating segments (BGM, DOC, RFF, IMD, STS, FTX).
+    pub rules: Vec<PidCodeRule>,
+}
+
+impl PidSignature {
+    /// Build a signature from a PID schema JSON value.
+    ///
+    /// Returns `None` for schemas without a numeric `pid`.
+    pub fn from_schema(schema: &serde_json::Value) -> Option<Self> {
+        let pid = schema.get("pid")?.as_str()?;
+        if pid.is_empty() || !pid.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut signature = Self {
+            pid: pid.to_string(),
+            message_type: None,
+            association_code: None,
+            rules: Vec::new(),
+        };
+        let mut rules: BTreeMap<(String, Option<String>, usize, usize), BTreeSet<String>> =
+            BTreeMap::new();
+
+        let root_segments = schema.get("root_segments").and_then(|v| v.as_array());
+        for segment in root_segments.into_iter().flatten() {
+            if segment.get("id").and_then(|v| v.as_str()) == Some("UNH") {
+                signature.message_type = single_code(segment, 1, 0);
+                signature.association_code = single_code(segment, 1, 4);
+            } else {
+                collect_rules(segment, &mut rules);
+            }
+        }
+        if let Some(fields) = schema.get("fields").and_then(|v| v.as_object()) {
+            for group in fields.values() {
+                collect_group_rules(group, &mut rules);
+            }
+        }
+
+        signature.rules = rules
+            .into_iter()
+            .map(
+                |((segment, qualifier, element, component), codes)| PidCodeRule {
+                    segment,
+                    qualifier,
+                    element,
+                    component,
+                    codes,
+                },
+            )
+            .collect();
+        Some(signature)
+    }
+}
+
+/// What a piece of [`PidEvidence`] was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PidEvidenceKind {
+    /// UNH message type matched.
+    MessageType,
+    /// UNH association assigned code matched.
+    AssociationCode,
+    /// RFF+Z13 carried the PID itself.
+    PidReference,
+    /// BGM document name code is allowed for the PID.
+    DocumentCode,
+    /// Another discriminating code (DOC, IMD, STS, FTX, RFF) is allowed for the PID.
+    Code,
+}
+
+impl PidEvidenceKind {
+    fn weight(self) -> u32 {
+        match self {
+            Self::PidReference => 100,
+            Self::AssociationCode => 10,
+            Self::DocumentCode => 2,
+            Self::MessageType | Self::Code => 1,
+        }
+    }
+}
+
+/// A segment value that supports a [`PidCandidate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PidEvidence {
+    pub kind: PidEvidenceKind,
+    /// Segment tag the value was read from.
+    pub segment: String,
+    /// Segment number within the message (as assigned by the tokenizer).
+    pub segment_number: u32,
+    pub element: usize,
+    pub component: usize,
+    pub value: String,
+}
+
+/// A PID consistent with a message, with the evidence supporting it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PidCandidate {
+    pub pid: String,
+    /// Sum of the evidence weights; higher is more specific.
+    pub score: u32,
+    pub evidence: Vec<PidEvidence>,
+}
+
+/// Data-driven PID detector built from PID schema JSON files.
 ///
-/// For UTILMD messages, the PID is determined by examining:
-/// - BGM document name code (first element, first component — e.g., "E01")
+/// A PID is a candidate for a message when none of the message's
+/// discriminating values contradicts the PID's AHB code lists and an RFF+Z13
+/// reference, if present, names the PID itself. Candidates are ranked by the
+/// weight of the values that positively support them.
+#[derive(Debug, Clone, Default)]
+pub struct PidDetector {
+    signatures: Vec<PidSignature>,
+}
+
+impl PidDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a detector from all `pid_*_schema.json` files in `dir`.
+    pub fn from_schema_dir(dir: &Path) -> Result<Self, std::io::Error> {
+        let mut detector = Self::new();
+        detector.add_schema_dir(dir)?;
+        Ok(detector)
+    }
+
+    /// Add the signatures of all `pid_*_schema.json` files in `dir`.
+    pub fn add_schema_dir(&mut self, dir: &Path) -> Result<(), std::io::Error> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("pid_") && n.ends_with("_schema.json"))
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let content = std::fs::read_to_string(&path)?;
+            let schema: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            self.add_schema(&schema);
+        }
+        Ok(())
+    }
+
+    /// Add the signature of one PID schema JSON value (ignored if it has no PID).
+    pub fn add_schema(&mut self, schema: &serde_json::Value) {
+        if let Some(signature) = PidSignature::from_schema(schema) {
+            self.add_signature(signature);
+        }
+    }
+
+    pub fn add_signature(&mut self, signature: PidSignature) {
+        self.signatures.push(signature);
+    }
+
+    pub fn signatures(&self) -> &[PidSignature] {
+        &self.signatures
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// All PIDs consistent with `segments`, best first.
+    ///
+    /// Candidates with equal scores are ordered by PID.
+    pub fn candidates(&self, segments: &[OwnedSegment]) -> Vec<PidCandidate> {
+        let mut candidates: Vec<PidCandidate> = self
+            .signatures
+            .iter()
+            .filter_map(|signature| evaluate(signature, segments))
+            .collect();
+        candidates.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.pid.cmp(&b.pid)));
+        candidates
+    }
+
+    /// Detect the single best PID for `segments`.
+    ///
+    /// Fails with [`AssemblyError::PidDetectionFailed`] when no PID is
+    /// consistent with the message and with [`AssemblyError::AmbiguousPid`]
+    /// when several PIDs share the best score.
+    pub fn detect(&self, segments: &[OwnedSegment]) -> Result<String, AssemblyError> {
+        let candidates = self.candidates(segments);
+        let best = candidates
+            .first()
+            .ok_or(AssemblyError::PidDetectionFailed)?;
+        let tied: Vec<String> = candidates
+            .iter()
+            .take_while(|c| c.score == best.score)
+            .map(|c| c.pid.clone())
+            .collect();
+        if tied.len() > 1 {
+            return Err(AssemblyError::AmbiguousPid { candidates: tied });
+        }
+        Ok(best.pid.clone())
+    }
+}
+
+/// Check `segments` against `signature`; `None` if anything contradicts it.
+fn evaluate(signature: &PidSignature, segments: &[OwnedSegment]) -> Option<PidCandidate> {
+    let mut evidence = Vec::new();
+
+    if let Some(unh) = segments.iter().find(|s| s.is("UNH")) {
+        // S009: 0065 message type at component 0, 0057 association code at component 4.
+        let checks = [
+            (PidEvidenceKind::MessageType, 0, &signature.message_type),
+            (
+                PidEvidenceKind::AssociationCode,
+                4,
+                &signature.association_code,
+            ),
+        ];
+        for (kind, component, expected) in checks {
+            let value = unh.get_component(1, component);
+            let Some(expected) = expected.as_deref().filter(|_| !value.is_empty()) else {
+                continue;
+            };
+            if !value.eq_ignore_ascii_case(expected) {
+                return None;
+            }
+            evidence.push(PidEvidence {
+                kind,
+                segment: unh.id.clone(),
+                segment_number: unh.segment_number,
+                element: 1,
+                component,
+                value: value.to_string(),
+            });
+        }
+    }
+
+    for seg in segments {
+        if !DISCRIMINATOR_SEGMENTS.iter().any(|tag| seg.is(tag)) {
+            continue;
+        }
+        let qualifier = seg.get_component(0, 0);
+
+        // RFF+Z13 carries the PID itself; root-level schema codes list every
+        // PID of the message type, so compare against the signature directly.
+        let is_pid_reference = seg.is("RFF") && qualifier == "Z13";
+        if is_pid_reference {
+            let value = seg.get_component(0, 1);
+            if !value.is_empty() {
+                if value != signature.pid {
+                    return None;
+                }
+                evidence.push(PidEvidence {
+                    kind: PidEvidenceKind::PidReference,
+                    segment: seg.id.clone(),
+                    segment_number: seg.segment_number,
+                    element: 0,
+                    component: 1,
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        // Union the codes of all rules that apply to this segment occurrence.
+        let mut allowed: BTreeMap<(usize, usize), BTreeSet<&str>> = BTreeMap::new();
+        for rule in &signature.rules {
+            if !seg.is(&rule.segment) {
+                continue;
+            }
+            if rule.qualifier.as_deref().is_some_and(|q| q != qualifier) {
+                continue;
+            }
+            if is_pid_reference && (rule.element, rule.component) == (0, 1) {
+                continue;
+            }
+            allowed
+                .entry((rule.element, rule.component))
+                .or_default()
+                .extend(rule.codes.iter().map(String::as_str));
+        }
+
+        for ((element, component), codes) in allowed {
+            let value = seg.get_component(element, component);
+            if value.is_empty() {
+                continue;
+            }
+            if !codes.contains(value) {
+                return None;
+            }
+            let kind = if seg.is("BGM") {
+                PidEvidenceKind::DocumentCode
+            } else {
+                PidEvidenceKind::Code
+            };
+            evidence.push(PidEvidence {
+                kind,
+                segment: seg.id.clone(),
+                segment_number: seg.segment_number,
+                element,
+                component,
+                value: value.to_string(),
+            });
+        }
+    }
+
+    Some(PidCandidate {
+        pid: signature.pid.clone(),
+        score: evidence.iter().map(|e| e.kind.weight()).sum(),
+        evidence,
+    })
+}
+
+/// Walk a schema group (and its children) collecting code rules.
+fn collect_group_rules(
+    group: &serde_json::Value,
+    rules: &mut BTreeMap<(String, Option<String>, usize, usize), BTreeSet<String>>,
+) {
+    let segments = group.get("segments").and_then(|v| v.as_array());
+    for segment in segments.into_iter().flatten() {
+        collect_rules(segment, rules);
+    }
+    let children = group.get("children").and_then(|v| v.as_object());
+    for child in children.into_iter().flat_map(|c| c.values()) {
+        collect_group_rules(child, rules);
+    }
+}
+
+/// Collect the code rules of one schema segment if it is a discriminating segment.
+///
+/// Rules on the first component are unqualified; rules on later components
+/// are qualified by the first component when the schema allows exactly one
+/// value there (e.g., RFF+Z13 or STS+7).
+fn collect_rules(
+    segment: &serde_json::Value,
+    rules: &mut BTreeMap<(String, Option<String>, usize, usize), BTreeSet<String>>,
+) {
+    let Some(tag) = segment.get("id").and_then(|v| v.as_str()) else {
+        return;
+    };
+    if !DISCRIMINATOR_SEGMENTS.contains(&tag) {
+        return;
+    }
+    let qualifier = single_code(segment, 0, 0);
+
+    for (element, component, codes) in coded_components(segment) {
+        let qualifier = if (element, component) == (0, 0) {
+            None
+        } else {
+            qualifier.clone()
+        };
+        rules
+            .entry((tag.to_string(), qualifier, element, component))
+            .or_default()
+            .extend(codes);
+    }
+}
+
+/// All `(element, component, codes)` of a schema segment that carry code lists.
+fn coded_components(segment: &serde_json::Value) -> Vec<(usize, usize, Vec<String>)> {
+    let mut out = Vec::new();
+    let elements = segment.get("elements").and_then(|v| v.as_array());
+    for element in elements.into_iter().flatten() {
+        let Some(index) = element.get("index").and_then(|v| v.as_u64()) else {
+            continue;
+        };
+        // Simple data elements carry their codes directly; composites list components.
+        let components: Vec<&serde_json::Value> = match element.get("components") {
+            Some(serde_json::Value::Array(components)) => components.iter().collect(),
+            _ => vec![element],
+        };
+        for component in components {
+            let sub_index = component
+                .get("sub_index")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let codes: Vec<String> = component
+                .get("codes")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.get("value").and_then(|v| v.as_str()))
+                .map(str::to_string)
+                .collect();
+            if !codes.is_empty() {
+                out.push((index as usize, sub_index as usize, codes));
+            }
+        }
+    }
+    out
+}
+
+/// The code at `(element, component)` of a schema segment, if exactly one is allowed.
+fn single_code(segment: &serde_json::Value, element: usize, component: usize) -> Option<String> {
+    coded_components(segment)
+        .into_iter()
+        .find(|(e, c, _)| (*e, *c) == (element, component))
+        .and_then(|(_, _, codes)| match codes.as_slice() {
+            [code] => Some(code.clone()),
+            _ => None,
+        })
+}
+
+/// Detect the PID (Pruefidentifikator) from a list of parsed EDIFACT segments
+/// without schema data.
+///
+/// This heuristic only knows UTILMD; prefer [`PidDetector`] where the PID
+/// schemas are available. It examines:
 /// - RFF+Z13 references in SG6 which directly contain the PID number
+/// - BGM document name code (first element, first component — e.g., "E01")
 ///
 /// Returns the PID as a string (e.g., "55001").
 pub fn detect_pid(segments: &[OwnedSegment]) -> Result<String, AssemblyError> {
@@ -88,6 +530,7 @@ mod tests {
                 .map(|e| e.into_iter().map(|c| c.to_string()).collect())
                 .collect(),
             segment_number: 0,
+            location: None,
         }
     }
 
@@ -150,6 +593,175 @@ mod tests {
         assert_eq!(pid, "99999"); // RFF+Z13 takes priority
     }
 
+    fn schema(pid: &str, association_code: &str, sts_reasons: &[&str]) -> serde_json::Value {
+        let codes = |values: &[&str]| -> Vec<serde_json::Value> {
+            values
+                .iter()
+                .map(|v| serde_json::json!({ "value": v }))
+                .collect()
+        };
+        serde_json::json!({
+            "pid": pid,
+            "root_segments": [
+                {
+                    "id": "UNH",
+                    "elements": [
+                        { "index": 1, "components": [
+                            { "sub_index": 0, "codes": codes(&["UTILMD"]) },
+                            { "sub_index": 4, "codes": codes(&[association_code]) }
+                        ]}
+                    ]
+                },
+                {
+                    "id": "BGM",
+                    "elements": [
+                        { "index": 0, "components": [
+                            { "sub_index": 0, "codes": codes(&["E01", "E02"]) }
+                        ]}
+                    ]
+                }
+            ],
+            "fields": {
+                "sg4": {
+                    "segments": [
+                        {
+                            "id": "STS",
+                            "elements": [
+                                { "index": 0, "components": [
+                                    { "sub_index": 0, "codes": codes(&["7"]) }
+                                ]},
+                                { "index": 2, "components": [
+                                    { "sub_index": 0, "codes": codes(sts_reasons) }
+                                ]}
+                            ]
+                        }
+                    ],
+                    "children": {
+                        "sg6": {
+                            "segments": [
+                                {
+                                    "id": "RFF",
+                                    "elements": [
+                                        { "index": 0, "components": [
+                                            { "sub_index": 0, "codes": codes(&["Z13"]) },
+                                            { "sub_index": 1, "codes": codes(&[pid]) }
+                                        ]}
+                                    ]
+                                }
+                            ]
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn detector() -> PidDetector {
+        let mut detector = PidDetector::new();
+        detector.add_schema(&schema("55001", "S2.1", &["E01"]));
+        detector.add_schema(&schema("55002", "S2.1", &["E01", "E03"]));
+        detector.add_schema(&schema("44001", "G1.0a", &["E01"]));
+        detector.add_schema(&serde_json::json!({ "pid": "##alt##" }));
+        detector
+    }
+
+    fn utilmd_message(association_code: &str, body: Vec<OwnedSegment>) -> Vec<OwnedSegment> {
+        let mut segments = vec![
+            make_segment(
+                "UNH",
+                vec![
+                    vec!["001"],
+                    vec!["UTILMD", "D", "11A", "UN", association_code],
+                ],
+            ),
+            make_segment("BGM", vec![vec!["E01"]]),
+        ];
+        segments.extend(body);
+        segments
+    }
+
+    #[test]
+    fn test_signature_from_schema_collects_rules() {
+        let signature = PidSignature::from_schema(&schema("55001", "S2.1", &["E01"])).unwrap();
+        assert_eq!(signature.message_type.as_deref(), Some("UTILMD"));
+        assert_eq!(signature.association_code.as_deref(), Some("S2.1"));
+
+        let z13 = signature
+            .rules
+            .iter()
+            .find(|r| r.segment == "RFF" && r.component == 1)
+            .unwrap();
+        assert_eq!(z13.qualifier.as_deref(), Some("Z13"));
+        assert!(z13.codes.contains("55001"));
+
+        let qualifier_rule = signature
+            .rules
+            .iter()
+            .find(|r| r.segment == "STS" && r.element == 0)
+            .unwrap();
+        assert_eq!(qualifier_rule.qualifier, None);
+
+        assert!(PidSignature::from_schema(&serde_json::json!({ "pid": "##alt##" })).is_none());
+    }
+
+    #[test]
+    fn test_detector_uses_pid_reference() {
+        let segments = utilmd_message(
+            "S2.1",
+            vec![make_segment("RFF", vec![vec!["Z13", "55002"]])],
+        );
+        let candidates = detector().candidates(&segments);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].pid, "55002");
+        assert!(candidates[0]
+            .evidence
+            .iter()
+            .any(|e| e.kind == PidEvidenceKind::PidReference && e.value == "55002"));
+        assert_eq!(detector().detect(&segments).unwrap(), "55002");
+    }
+
+    #[test]
+    fn test_detector_association_code_separates_variants() {
+        let segments = utilmd_message("G1.0a", vec![]);
+        assert_eq!(detector().detect(&segments).unwrap(), "44001");
+    }
+
+    #[test]
+    fn test_detector_discriminates_by_sts_code() {
+        let segments = utilmd_message(
+            "S2.1",
+            vec![make_segment("STS", vec![vec!["7"], vec![], vec!["E03"]])],
+        );
+        assert_eq!(detector().detect(&segments).unwrap(), "55002");
+    }
+
+    #[test]
+    fn test_detector_reports_ambiguity() {
+        let segments = utilmd_message(
+            "S2.1",
+            vec![make_segment("STS", vec![vec!["7"], vec![], vec!["E01"]])],
+        );
+        match detector().detect(&segments) {
+            Err(AssemblyError::AmbiguousPid { candidates }) => {
+                assert_eq!(candidates, vec!["55001", "55002"]);
+            }
+            other => panic!("expected ambiguity, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_detector_fails_when_nothing_matches() {
+        let segments = utilmd_message(
+            "S2.1",
+            vec![make_segment("RFF", vec![vec!["Z13", "99999"]])],
+        );
+        assert!(detector().candidates(&segments).is_empty());
+        assert!(matches!(
+            detector().detect(&segments),
+            Err(AssemblyError::PidDetectionFailed)
+        ));
+    }
+
     #[test]
     fn test_detect_pid_from_parsed_edifact() {
         // Test with actual parsed EDIFACT input
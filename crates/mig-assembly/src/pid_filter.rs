@@ -180,6 +180,38 @@ fn merge_group_variants(variants: Vec<MigSegmentGroup>) -> MigSegmentGroup {
         }
     }
 
+    // A slot stays mandatory only as often as every variant requires it;
+    // otherwise repetitions of the other variants look incomplete to the
+    // structure checks.
+    let mut required_kept: Vec<(String, usize)> = Vec::new();
+    for seg in merged_segments.iter_mut() {
+        if !seg.cardinality().is_required() {
+            continue;
+        }
+        let min_required = variants
+            .iter()
+            .map(|v| {
+                v.segments
+                    .iter()
+                    .filter(|s| s.id == seg.id && s.cardinality().is_required())
+                    .count()
+            })
+            .min()
+            .unwrap_or(0);
+        let kept = match required_kept.iter_mut().find(|(id, _)| *id == seg.id) {
+            Some((_, kept)) => kept,
+            None => {
+                required_kept.push((seg.id.clone(), 0));
+                &mut required_kept.last_mut().unwrap().1
+            }
+        };
+        if *kept < min_required {
+            *kept += 1;
+        } else {
+            seg.status_spec = Some("C".to_string());
+        }
+    }
+
     // Collect all nested groups from all variants and recursively merge
     let mut all_nested: Vec<MigSegmentGroup> = Vec::new();
     for variant in &variants {
@@ -188,6 +220,17 @@ fn merge_group_variants(variants: Vec<MigSegmentGroup>) -> MigSegmentGroup {
     let mut merged_nested = merge_same_id_groups(all_nested);
     // Sort by group number (SG9 < SG10) for standard EDIFACT ordering
     merged_nested.sort_by_key(|g| extract_group_number(&g.id));
+    // Same for nested groups: mandatory only if every variant requires them.
+    for nested in merged_nested.iter_mut() {
+        let required_everywhere = variants.iter().all(|v| {
+            v.nested_groups
+                .iter()
+                .any(|n| n.id == nested.id && n.cardinality().is_required())
+        });
+        if !required_everywhere {
+            nested.status_spec = Some("C".to_string());
+        }
+    }
 
     MigSegmentGroup {
         id: first.id.clone(),
@@ -421,6 +464,55 @@ mod tests {
         assert_eq!(sg10_tags, vec!["CCI", "CAV", "CAV"]);
     }
 
+    #[test]
+    fn test_merge_keeps_mandatory_only_when_all_variants_require_it() {
+        fn mandatory(mut s: MigSegment) -> MigSegment {
+            s.status_spec = Some("M".to_string());
+            s
+        }
+        let mut sg9 = group("SG9", vec![mandatory(seg("QTY", Some("00116")))], vec![]);
+        sg9.status_spec = Some("M".to_string());
+
+        let sg8_zd7 = group(
+            "SG8",
+            vec![
+                mandatory(seg("SEQ", Some("00089"))),
+                mandatory(seg("RFF", Some("00090"))),
+            ],
+            vec![],
+        );
+        let sg8_z98 = group("SG8", vec![mandatory(seg("SEQ", Some("00114")))], vec![sg9]);
+        let sg4 = group(
+            "SG4",
+            vec![seg("IDE", Some("00020"))],
+            vec![sg8_zd7, sg8_z98],
+        );
+
+        let mig = MigSchema {
+            message_type: "UTILMD".to_string(),
+            variant: None,
+            version: "S2.1".to_string(),
+            publication_date: String::new(),
+            author: String::new(),
+            format_version: "FV2504".to_string(),
+            source_file: String::new(),
+            segments: vec![],
+            segment_groups: vec![sg4],
+        };
+        let ahb_numbers: HashSet<String> = ["00020", "00089", "00090", "00114", "00116"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let filtered = filter_mig_for_pid(&mig, &ahb_numbers);
+        let sg8 = &filtered.segment_groups[0].nested_groups[0];
+
+        // SEQ is mandatory in both variants, RFF and SG9 only in one
+        assert!(sg8.segments[0].cardinality().is_required());
+        assert!(!sg8.segments[1].cardinality().is_required());
+        assert!(!sg8.nested_groups[0].cardinality().is_required());
+    }
+
     #[test]
     fn test_filter_removes_nested_groups_not_in_ahb() {
         let sg8_z79 = group(
@@ -0,0 +1,875 @@
+//! Path queries over assembled trees.
+//!
+//! A [`Query`] selects group instances, segments or component values from an
+//! [`AssembledTree`] with an XPath-like syntax:
+//!
+//! ```text
+//! SG4[IDE.0=24]/SG8[SEQ.0=Z01]/SG10/CCI.2.0
+//! ```
+//!
+//! # Syntax
+//!
+//! A query is a sequence of steps separated by `/` (children of the current
+//! node) or `//` (descendants at any depth). A leading `/` is optional; a
+//! leading `//` searches the whole tree.
+//!
+//! - `SG8` selects the repetitions of group SG8, `*` those of any group.
+//! - Any other tag (`DTM`, `CCI`) selects segments and must be the last step.
+//!   A trailing `.e` or `.e.c` selects component `c` (default 0) of element
+//!   `e` of those segments instead.
+//!
+//! Each step may carry predicates, applied left to right to the nodes the
+//! step selected from one parent:
+//!
+//! - `[N]` keeps the Nth node (0-based) of those left so far.
+//! - `[RFF]` keeps nodes containing an RFF segment.
+//! - `[RFF.0.1=A|B]` keeps nodes with an RFF segment whose element 0,
+//!   component 1 is `A` or `B`; `!=` keeps nodes without such a segment.
+//! - `[0.0=Z98]` without a tag tests a group's entry segment, or the
+//!   selected segment itself in a segment step.
+//!
+//! Group ids and segment tags match case-insensitively, values exactly.
+//! Root segments (including those between groups) are children of the tree.
+//!
+//! Every [`QueryMatch`] carries the path it was found at; its
+//! [`canonical_path`](QueryMatch::canonical_path), e.g.
+//! `SG4[0]/SG8[1]/CCI[0].2.0`, is itself a query for exactly that node.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::assembler::{AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree};
+
+/// A parsed path query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub steps: Vec<Step>,
+    /// Component selected from the segments of the last step (`.e.c`).
+    pub value: Option<ComponentRef>,
+}
+
+/// One `/`- or `//`-separated step of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub axis: Axis,
+    pub test: NodeTest,
+    pub predicates: Vec<Predicate>,
+}
+
+/// How a step reaches its nodes from the previous step's nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// `/`: direct children.
+    Child,
+    /// `//`: children and their descendants, in document order.
+    Descendant,
+}
+
+/// Which nodes a step selects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeTest {
+    /// Repetitions of the group with this id (e.g. `SG8`).
+    Group(String),
+    /// Repetitions of any group (`*`).
+    AnyGroup,
+    /// Segments with this tag (e.g. `DTM`).
+    Segment(String),
+}
+
+/// A filter in square brackets after a step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// `[N]`: the Nth node (0-based) selected from the same parent.
+    Index(usize),
+    /// `[TAG]`: the node contains a segment with this tag.
+    Has(String),
+    /// `[TAG.e.c=V]`, `[e.c=V]`, or their `!=` forms.
+    Value(ValueTest),
+}
+
+/// A component comparison: `[TAG.e.c=V1|V2]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueTest {
+    /// Segment tag to test; `None` tests a group's entry segment or the
+    /// selected segment itself.
+    pub tag: Option<String>,
+    pub at: ComponentRef,
+    /// `!=`: no tested segment has any of `values`.
+    pub negated: bool,
+    pub values: Vec<String>,
+}
+
+/// Position of a component within a segment (both 0-based).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentRef {
+    pub element: usize,
+    pub component: usize,
+}
+
+/// A query that could not be parsed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid query at offset {offset}: {message}")]
+pub struct QueryError {
+    /// Byte offset into the query string.
+    pub offset: usize,
+    pub message: String,
+}
+
+/// A node selected by a query, with the path it was found at.
+#[derive(Debug, Clone)]
+pub struct QueryMatch<'a> {
+    /// Group id (or segment tag) and index of every step from the context
+    /// node down. Group indices are repetition indices; a segment's index
+    /// counts the segments with the same tag in its parent.
+    pub path: Vec<PathStep<'a>>,
+    pub node: QueryNode<'a>,
+}
+
+/// One step of a [`QueryMatch`] path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathStep<'a> {
+    pub id: &'a str,
+    pub index: usize,
+}
+
+/// What a query selected.
+#[derive(Debug, Clone, Copy)]
+pub enum QueryNode<'a> {
+    Group(&'a AssembledGroupInstance<'a>),
+    Segment(&'a AssembledSegment<'a>),
+    Value { value: &'a str, at: ComponentRef },
+}
+
+impl Query {
+    /// Parse a query string (see the [module documentation](self)).
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        Parser { input, pos: 0 }.query()
+    }
+
+    /// A query over `steps` without a value selection.
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self { steps, value: None }
+    }
+
+    /// Evaluate against a whole tree.
+    pub fn select<'a>(&self, tree: &'a AssembledTree<'a>) -> Vec<QueryMatch<'a>> {
+        self.evaluate(Node::Root(tree))
+    }
+
+    /// Evaluate relative to a group instance; match paths start below it.
+    pub fn select_in<'a>(&self, instance: &'a AssembledGroupInstance<'a>) -> Vec<QueryMatch<'a>> {
+        self.evaluate(Node::Group(instance))
+    }
+
+    fn evaluate<'a>(&self, start: Node<'a>) -> Vec<QueryMatch<'a>> {
+        let mut current = vec![(Vec::new(), start)];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            let mut seen = HashSet::new();
+            for (path, node) in &current {
+                let mut candidates = Vec::new();
+                step.collect(*node, path, &mut candidates);
+                for predicate in &step.predicates {
+                    match predicate {
+                        Predicate::Index(n) => {
+                            // `//` collects nodes from many parents; count per parent.
+                            let mut ranks: HashMap<&[PathStep], usize> = HashMap::new();
+                            let keep: Vec<bool> = candidates
+                                .iter()
+                                .map(|(path, _)| {
+                                    let rank = ranks.entry(&path[..path.len() - 1]).or_default();
+                                    *rank += 1;
+                                    *rank == n + 1
+                                })
+                                .collect();
+                            let mut keep = keep.into_iter();
+                            candidates.retain(|_| keep.next().unwrap_or(false));
+                        }
+                        _ => candidates.retain(|(_, node)| predicate.matches(*node)),
+                    }
+                }
+                // Descendant steps from nested contexts reach the same node twice.
+                next.extend(
+                    candidates
+                        .into_iter()
+                        .filter(|(_, node)| step.axis == Axis::Child || seen.insert(node.addr())),
+                );
+            }
+            current = next;
+        }
+
+        current
+            .into_iter()
+            .filter_map(|(path, node)| {
+                let node = match (node, self.value) {
+                    (Node::Segment(segment), Some(at)) => QueryNode::Value {
+                        value: component(segment, at)?,
+                        at,
+                    },
+                    (Node::Segment(segment), None) => QueryNode::Segment(segment),
+                    (Node::Group(instance), None) => QueryNode::Group(instance),
+                    _ => return None,
+                };
+                Some(QueryMatch { path, node })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            match step.axis {
+                Axis::Descendant => f.write_str("//")?,
+                Axis::Child if i > 0 => f.write_str("/")?,
+                Axis::Child => {}
+            }
+            match &step.test {
+                NodeTest::Group(id) | NodeTest::Segment(id) => f.write_str(id)?,
+                NodeTest::AnyGroup => f.write_str("*")?,
+            }
+            for predicate in &step.predicates {
+                write!(f, "[{predicate}]")?;
+            }
+        }
+        if let Some(at) = self.value {
+            write!(f, ".{at}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Predicate::Index(n) => write!(f, "{n}"),
+            Predicate::Has(tag) => f.write_str(tag),
+            Predicate::Value(test) => {
+                if let Some(tag) = &test.tag {
+                    write!(f, "{tag}.")?;
+                }
+                let op = if test.negated { "!=" } else { "=" };
+                write!(f, "{}{op}{}", test.at, test.values.join("|"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ComponentRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.element, self.component)
+    }
+}
+
+impl Step {
+    /// A child step selecting the repetitions of group `id`.
+    pub fn group(id: impl Into<String>) -> Self {
+        Self::child(NodeTest::Group(id.into()))
+    }
+
+    /// A child step selecting the segments tagged `tag`.
+    pub fn segment(tag: impl Into<String>) -> Self {
+        Self::child(NodeTest::Segment(tag.into()))
+    }
+
+    fn child(test: NodeTest) -> Self {
+        Self {
+            axis: Axis::Child,
+            test,
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Append a predicate.
+    pub fn with(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Push the nodes this step selects from `node` onto `out`.
+    fn collect<'a>(
+        &self,
+        node: Node<'a>,
+        path: &[PathStep<'a>],
+        out: &mut Vec<(Vec<PathStep<'a>>, Node<'a>)>,
+    ) {
+        let descend = self.axis == Axis::Descendant;
+        match &self.test {
+            NodeTest::Segment(tag) => collect_segments(node, tag, descend, path, out),
+            test => collect_groups(node.child_groups(), test, descend, path, out),
+        }
+    }
+}
+
+impl Predicate {
+    /// `Value` predicate testing `tag` (or the entry segment for `None`)
+    /// for any of `values`.
+    pub fn value_eq<V: Into<String>>(
+        tag: Option<&str>,
+        element: usize,
+        component: usize,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        Predicate::Value(ValueTest {
+            tag: tag.map(str::to_string),
+            at: ComponentRef { element, component },
+            negated: false,
+            values: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    fn matches(&self, node: Node<'_>) -> bool {
+        let segments = node.own_segments();
+        match self {
+            Predicate::Index(_) => true,
+            Predicate::Has(tag) => segments.iter().any(|s| s.tag.eq_ignore_ascii_case(tag)),
+            Predicate::Value(test) => {
+                let found = match &test.tag {
+                    None => segments.first().into_iter().any(|s| test.holds(s)),
+                    Some(tag) => segments
+                        .iter()
+                        .filter(|s| s.tag.eq_ignore_ascii_case(tag))
+                        .any(|s| test.holds(s)),
+                };
+                found != test.negated
+            }
+        }
+    }
+}
+
+impl ValueTest {
+    fn holds(&self, segment: &AssembledSegment) -> bool {
+        component(segment, self.at).is_some_and(|v| self.values.iter().any(|e| e == v))
+    }
+}
+
+impl<'a> QueryMatch<'a> {
+    /// The matched group instance, if the query selected groups.
+    pub fn group(&self) -> Option<&'a AssembledGroupInstance<'a>> {
+        match self.node {
+            QueryNode::Group(instance) => Some(instance),
+            _ => None,
+        }
+    }
+
+    /// The matched segment, if the query selected segments.
+    pub fn segment(&self) -> Option<&'a AssembledSegment<'a>> {
+        match self.node {
+            QueryNode::Segment(segment) => Some(segment),
+            _ => None,
+        }
+    }
+
+    /// The matched value, if the query selected values.
+    pub fn value(&self) -> Option<&'a str> {
+        match self.node {
+            QueryNode::Value { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The match's path as a query selecting only this node,
+    /// e.g. `SG4[0]/SG8[1]/CCI[0].2.0`.
+    pub fn canonical_path(&self) -> String {
+        let mut out = self
+            .path
+            .iter()
+            .map(|step| format!("{}[{}]", step.id, step.index))
+            .collect::<Vec<_>>()
+            .join("/");
+        if let QueryNode::Value { at, .. } = self.node {
+            out.push_str(&format!(".{at}"));
+        }
+        out
+    }
+}
+
+/// A node queries navigate through.
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Root(&'a AssembledTree<'a>),
+    Group(&'a AssembledGroupInstance<'a>),
+    Segment(&'a AssembledSegment<'a>),
+}
+
+impl<'a> Node<'a> {
+    fn addr(&self) -> usize {
+        match self {
+            Node::Root(tree) => *tree as *const AssembledTree as usize,
+            Node::Group(instance) => *instance as *const AssembledGroupInstance as usize,
+            Node::Segment(segment) => *segment as *const AssembledSegment as usize,
+        }
+    }
+
+    fn child_groups(&self) -> &'a [AssembledGroup<'a>] {
+        match self {
+            Node::Root(tree) => &tree.groups,
+            Node::Group(instance) => &instance.child_groups,
+            Node::Segment(_) => &[],
+        }
+    }
+
+    /// Segments directly below a group (or the root, in EDIFACT order), or
+    /// the segment itself.
+    fn own_segments(&self) -> Vec<&'a AssembledSegment<'a>> {
+        match self {
+            Node::Root(tree) => {
+                let split = tree.post_group_start.min(tree.segments.len());
+                tree.segments[..split]
+                    .iter()
+                    .chain(tree.inter_group_segments.values().flatten())
+                    .chain(&tree.segments[split..])
+                    .collect()
+            }
+            Node::Group(instance) => instance.segments.iter().collect(),
+            Node::Segment(segment) => vec![segment],
+        }
+    }
+}
+
+fn collect_groups<'a>(
+    groups: &'a [AssembledGroup<'a>],
+    test: &NodeTest,
+    descend: bool,
+    path: &[PathStep<'a>],
+    out: &mut Vec<(Vec<PathStep<'a>>, Node<'a>)>,
+) {
+    for group in groups {
+        let selected = match test {
+            NodeTest::Group(id) => group.group_id.eq_ignore_ascii_case(id),
+            _ => true,
+        };
+        for (index, instance) in group.repetitions.iter().enumerate() {
+            let mut child_path = path.to_vec();
+            child_path.push(PathStep {
+                id: &group.group_id,
+                index,
+            });
+            if descend {
+                if selected {
+                    out.push((child_path.clone(), Node::Group(instance)));
+                }
+                collect_groups(&instance.child_groups, test, true, &child_path, out);
+            } else if selected {
+                out.push((child_path, Node::Group(instance)));
+            }
+        }
+    }
+}
+
+fn collect_segments<'a>(
+    node: Node<'a>,
+    tag: &str,
+    descend: bool,
+    path: &[PathStep<'a>],
+    out: &mut Vec<(Vec<PathStep<'a>>, Node<'a>)>,
+) {
+    if matches!(node, Node::Segment(_)) {
+        return;
+    }
+    let matching = node
+        .own_segments()
+        .into_iter()
+        .filter(|s| s.tag.eq_ignore_ascii_case(tag));
+    for (index, segment) in matching.enumerate() {
+        let mut segment_path = path.to_vec();
+        segment_path.push(PathStep {
+            id: &segment.tag,
+            index,
+        });
+        out.push((segment_path, Node::Segment(segment)));
+    }
+    if descend {
+        for group in node.child_groups() {
+            for (index, instance) in group.repetitions.iter().enumerate() {
+                let mut child_path = path.to_vec();
+                child_path.push(PathStep {
+                    id: &group.group_id,
+                    index,
+                });
+                collect_segments(Node::Group(instance), tag, true, &child_path, out);
+            }
+        }
+    }
+}
+
+fn component<'a>(segment: &'a AssembledSegment<'a>, at: ComponentRef) -> Option<&'a str> {
+    segment
+        .elements
+        .get(at.element)
+        .and_then(|e| e.get(at.component))
+        .map(|v| v.as_ref())
+}
+
+/// Is `id` a segment group id (`SG` followed by digits)?
+fn is_group_id(id: &str) -> bool {
+    id.len() > 2
+        && id[..2].eq_ignore_ascii_case("SG")
+        && id[2..].bytes().all(|b| b.is_ascii_digit())
+}
+
+struct Parser<'q> {
+    input: &'q str,
+    pos: usize,
+}
+
+impl<'q> Parser<'q> {
+    fn query(mut self) -> Result<Query, QueryError> {
+        if self.input.is_empty() {
+            return Err(self.error("empty query"));
+        }
+        let mut steps: Vec<Step> = Vec::new();
+        let mut value = None;
+        while self.pos < self.input.len() {
+            if steps
+                .last()
+                .is_some_and(|s| matches!(s.test, NodeTest::Segment(_)))
+            {
+                return Err(self.error("nothing may follow a segment step"));
+            }
+            let axis = if self.eat("//") {
+                Axis::Descendant
+            } else if self.eat("/") || steps.is_empty() {
+                Axis::Child
+            } else {
+                return Err(self.error("expected '/' or '//'"));
+            };
+
+            let test = if self.eat("*") {
+                NodeTest::AnyGroup
+            } else {
+                let tag = self.take_while(|c| c.is_ascii_alphanumeric());
+                if tag.is_empty() {
+                    return Err(self.error("expected a group id, segment tag or '*'"));
+                }
+                let tag = tag.to_ascii_uppercase();
+                if is_group_id(&tag) {
+                    NodeTest::Group(tag)
+                } else {
+                    NodeTest::Segment(tag)
+                }
+            };
+
+            let mut predicates = Vec::new();
+            while self.eat("[") {
+                predicates.push(self.predicate()?);
+            }
+
+            if self.eat(".") {
+                if !matches!(test, NodeTest::Segment(_)) {
+                    return Err(self.error("only segment steps can select a value"));
+                }
+                value = Some(self.component_ref()?);
+            }
+            steps.push(Step {
+                axis,
+                test,
+                predicates,
+            });
+        }
+        Ok(Query { steps, value })
+    }
+
+    /// Parse the inside of `[...]`, consuming the closing bracket.
+    fn predicate(&mut self) -> Result<Predicate, QueryError> {
+        let start = self.pos;
+        let Some(len) = self.input[start..].find(']') else {
+            return Err(self.error("unclosed '['"));
+        };
+        let inner = &self.input[start..start + len];
+        let error = |message: &str| QueryError {
+            offset: start,
+            message: message.to_string(),
+        };
+        self.pos = start + len + 1;
+
+        if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_digit()) {
+            return inner
+                .parse()
+                .map(Predicate::Index)
+                .map_err(|_| error("index out of range"));
+        }
+        let Some((lhs, rhs)) = inner.split_once('=') else {
+            if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                return Ok(Predicate::Has(inner.to_ascii_uppercase()));
+            }
+            return Err(error("expected an index, a segment tag or a comparison"));
+        };
+        let (lhs, negated) = match lhs.strip_suffix('!') {
+            Some(lhs) => (lhs, true),
+            None => (lhs, false),
+        };
+
+        let mut parts: Vec<&str> = lhs.split('.').collect();
+        let tag = if parts[0].bytes().all(|b| b.is_ascii_digit()) {
+            None
+        } else if parts[0].bytes().all(|b| b.is_ascii_alphanumeric()) {
+            Some(parts.remove(0).to_ascii_uppercase())
+        } else {
+            return Err(error("invalid segment tag"));
+        };
+        let indices = parts
+            .iter()
+            .map(|p| p.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| error("expected element[.component] indices"))?;
+        let at = match indices[..] {
+            [element] => ComponentRef {
+                element,
+                component: 0,
+            },
+            [element, component] => ComponentRef { element, component },
+            _ => return Err(error("expected element[.component] indices")),
+        };
+
+        Ok(Predicate::Value(ValueTest {
+            tag,
+            at,
+            negated,
+            values: rhs.split('|').map(str::to_string).collect(),
+        }))
+    }
+
+    /// Parse `e` or `e.c` (after the leading `.`).
+    fn component_ref(&mut self) -> Result<ComponentRef, QueryError> {
+        let element = self.index()?;
+        let component = if self.eat(".") { self.index()? } else { 0 };
+        Ok(ComponentRef { element, component })
+    }
+
+    fn index(&mut self) -> Result<usize, QueryError> {
+        let offset = self.pos;
+        self.take_while(|c| c.is_ascii_digit())
+            .parse()
+            .map_err(|_| QueryError {
+                offset,
+                message: "expected an element or component index".to_string(),
+            })
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.input[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'q str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn error(&self, message: &str) -> QueryError {
+        QueryError {
+            offset: self.pos,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(tag: &str, elements: &[&[&str]]) -> AssembledSegment<'static> {
+        AssembledSegment {
+            tag: tag.to_string().into(),
+            elements: elements
+                .iter()
+                .map(|e| e.iter().map(|c| c.to_string().into()).collect())
+                .collect(),
+            location: None,
+        }
+    }
+
+    fn instance(
+        segments: Vec<AssembledSegment<'static>>,
+        child_groups: Vec<AssembledGroup<'static>>,
+    ) -> AssembledGroupInstance<'static> {
+        AssembledGroupInstance {
+            segments,
+            child_groups,
+            skipped_segments: Vec::new(),
+        }
+    }
+
+    fn group(
+        id: &str,
+        repetitions: Vec<AssembledGroupInstance<'static>>,
+    ) -> AssembledGroup<'static> {
+        AssembledGroup {
+            group_id: id.to_string(),
+            repetitions,
+        }
+    }
+
+    /// BGM, then SG4 (IDE+24) with SG8 reps SEQ+Z01 (two SG10s) and SEQ+Z98
+    /// (one SG10), then UNT.
+    fn tree() -> AssembledTree<'static> {
+        let sg10 = |code: &str| {
+            instance(
+                vec![seg("CCI", &[&[], &[], &[code]]), seg("CAV", &[&["X"]])],
+                vec![],
+            )
+        };
+        let sg8_z01 = instance(
+            vec![seg("SEQ", &[&["Z01"]]), seg("RFF", &[&["Z18", "A"]])],
+            vec![group("SG10", vec![sg10("Z15"), sg10("Z18")])],
+        );
+        let sg8_z98 = instance(
+            vec![seg("SEQ", &[&["Z98"]])],
+            vec![group("SG10", vec![sg10("ZB3")])],
+        );
+        AssembledTree {
+            segments: vec![seg("BGM", &[&["E01"]]), seg("UNT", &[&["9"]])],
+            groups: vec![group(
+                "SG4",
+                vec![instance(
+                    vec![seg("IDE", &[&["24"], &["TX1"]])],
+                    vec![group("SG8", vec![sg8_z01, sg8_z98])],
+                )],
+            )],
+            post_group_start: 1,
+            inter_group_segments: Default::default(),
+        }
+    }
+
+    fn values<'a>(matches: &[QueryMatch<'a>]) -> Vec<&'a str> {
+        matches.iter().filter_map(QueryMatch::value).collect()
+    }
+
+    fn select<'a>(tree: &'a AssembledTree<'a>, query: &str) -> Vec<QueryMatch<'a>> {
+        Query::parse(query).unwrap().select(tree)
+    }
+
+    #[test]
+    fn test_parse_displays_back() {
+        for query in [
+            "SG4[IDE.0.0=24]/SG8[SEQ.0.0=Z01]/SG10/CCI.2.0",
+            "//SG10[1]",
+            "SG4/*[0.0!=Z98|Z01][RFF][0]",
+            "BGM",
+        ] {
+            assert_eq!(Query::parse(query).unwrap().to_string(), query);
+        }
+        assert_eq!(
+            Query::parse("/sg4[ide.0=24]/sg8/seq.0")
+                .unwrap()
+                .to_string(),
+            "SG4[IDE.0.0=24]/SG8/SEQ.0.0"
+        );
+    }
+
+    #[test]
+    fn test_select_values_with_predicates() {
+        let tree = tree();
+        let matches = select(&tree, "SG4[IDE.0=24]/SG8[SEQ.0=Z01]/SG10/CCI.2.0");
+        assert_eq!(values(&matches), ["Z15", "Z18"]);
+        assert_eq!(
+            matches[1].canonical_path(),
+            "SG4[0]/SG8[0]/SG10[1]/CCI[0].2.0"
+        );
+
+        assert!(select(&tree, "SG4[IDE.0=25]/SG8").is_empty());
+        assert_eq!(
+            values(&select(&tree, "SG4/SG8[RFF.0.1=A|B]/SEQ.0")),
+            ["Z01"]
+        );
+        assert_eq!(values(&select(&tree, "SG4/SG8[RFF.0.1!=A]/SEQ.0")), ["Z98"]);
+    }
+
+    #[test]
+    fn test_tagless_predicate_tests_entry_segment() {
+        let tree = tree();
+        // RFF is not the entry segment of SG8, so `0.0=Z18` does not see it.
+        assert!(select(&tree, "SG4/SG8[0.0=Z18]").is_empty());
+        let matches = select(&tree, "SG4/SG8[0.0=Z98]/SG10/CCI.2");
+        assert_eq!(values(&matches), ["ZB3"]);
+        assert_eq!(
+            matches[0].path[1],
+            PathStep {
+                id: "SG8",
+                index: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_index_applies_per_parent_after_filters() {
+        let tree = tree();
+        assert_eq!(values(&select(&tree, "//SG10[0]/CCI.2")), ["Z15", "ZB3"]);
+        assert_eq!(values(&select(&tree, "//SG10[1]/CCI.2")), ["Z18"]);
+        assert_eq!(values(&select(&tree, "SG4/SG8[RFF][0]/SEQ.0")), ["Z01"]);
+        assert_eq!(
+            values(&select(&tree, "SG4/SG8[1][RFF]/SEQ.0")),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_descendant_and_wildcard_steps() {
+        let tree = tree();
+        assert_eq!(select(&tree, "//CCI").len(), 3);
+        assert_eq!(values(&select(&tree, "SG4//CCI.2")), ["Z15", "Z18", "ZB3"]);
+        assert_eq!(select(&tree, "*/*").len(), 2);
+        // Nested contexts reach the same SG10 instances only once.
+        assert_eq!(select(&tree, "//*//SG10").len(), 3);
+    }
+
+    #[test]
+    fn test_root_segments_and_canonical_paths() {
+        let tree = tree();
+        assert_eq!(values(&select(&tree, "BGM.0")), ["E01"]);
+        assert_eq!(values(&select(&tree, "/UNT.0")), ["9"]);
+        assert_eq!(select(&tree, "//SEQ").len(), 2);
+
+        for m in select(&tree, "//CAV") {
+            let again = select(&tree, &m.canonical_path());
+            assert_eq!(again.len(), 1);
+            assert!(std::ptr::eq(
+                again[0].segment().unwrap(),
+                m.segment().unwrap()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_select_in_is_relative_to_instance() {
+        let tree = tree();
+        let sg8 = select(&tree, "SG4/SG8[1]")[0].group().unwrap();
+        let matches = Query::parse("SG10/CCI.2").unwrap().select_in(sg8);
+        assert_eq!(values(&matches), ["ZB3"]);
+        assert_eq!(matches[0].canonical_path(), "SG10[0]/CCI[0].2.0");
+    }
+
+    #[test]
+    fn test_parse_errors_report_offsets() {
+        let error = |q: &str| Query::parse(q).unwrap_err();
+        assert_eq!(error("").offset, 0);
+        assert_eq!(error("SG4/").offset, 4);
+        assert_eq!(error("SG4[IDE.0=24").offset, 4);
+        assert_eq!(error("SG4[IDE.x=24]").offset, 4);
+        assert_eq!(error("DTM/SG4").offset, 3);
+        assert_eq!(error("SG4.0").offset, 4);
+        assert_eq!(error("SG4 SG8").offset, 3);
+        assert_eq!(
+            error("SG4[-]").to_string(),
+            "invalid query at offset 4: expected an index, a segment tag or a comparison"
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Lenient assembly: repair the input until the MIG-guided assembler can
+//! place all of it.
+//!
+//! [`Assembler::assemble_generic`] stops at the first segment it cannot
+//! place and drops the rest of the message. Lenient assembly looks at the
+//! segment where assembly stalled and tries, guided by the MIG:
+//!
+//! - inserting an empty entry segment for a group that would accept the
+//!   stalled segment (e.g. a missing `IDE` before `STS`),
+//! - skipping the stalled segment,
+//! - if skipping one segment does not get assembly going again, skipping
+//!   ahead to the next segment that opens a group or fills a root slot.
+//!
+//! The repair under which the assembler places the most input segments
+//! wins; a single skip is preferred on ties since it invents nothing. Each
+//! repair is reported as a [`StructureDiagnostic`] with `repair` set,
+//! followed by the [`check_structure`] diagnostics for the repaired input.
+
+use std::collections::{HashMap, HashSet};
+
+use mig_types::schema::mig::{MigSchema, MigSegmentGroup};
+use mig_types::segment::OwnedSegment;
+
+use crate::assembler::{AssembledGroup, AssembledTree, Assembler};
+use crate::diagnostic::{StructureDiagnostic, StructureDiagnosticKind, StructureRepair};
+use crate::matcher;
+use crate::structure::{join_path, known_tags};
+
+/// Assemble `segments`, repairing the input wherever assembly stalls.
+///
+/// Always returns a tree. Diagnostic positions are indices into `segments`;
+/// an inserted segment is reported at the position of the segment it was
+/// inserted before.
+pub(crate) fn assemble_lenient(
+    assembler: &Assembler,
+    mig: &MigSchema,
+    segments: &[OwnedSegment],
+) -> (AssembledTree<'static>, Vec<StructureDiagnostic>) {
+    let Some(mut current) = Attempt::run(assembler, RepairedInput::new(segments)) else {
+        // The MIG itself cannot be assembled against; nothing to repair.
+        let (tree, diagnostics) = assembler.assemble_with_diagnostics(segments);
+        return (tree.into_owned(), diagnostics);
+    };
+
+    let known = known_tags(mig);
+    let anchors = anchor_tags(mig);
+    let mut repairs = Vec::new();
+
+    while current.consumed < current.input.segments.len() {
+        let stall = current.consumed;
+        let stalled_tag = current.input.segments[stall].id.clone();
+
+        let skipped = current.input.skip(stall, stall + 1);
+        let mut best = Attempt::run(assembler, skipped).map(|a| (Repair::Skip(stall + 1), a));
+
+        // Skipping one segment did not help: resync on the next anchor.
+        if best.as_ref().is_some_and(|(_, a)| a.consumed == stall) {
+            let resync = (stall + 1..current.input.segments.len())
+                .find(|&i| anchors.contains(&current.input.segments[i].id.to_ascii_uppercase()))
+                .unwrap_or(current.input.segments.len());
+            if resync > stall + 1 {
+                let skipped = current.input.skip(stall, resync);
+                if let Some(attempt) = Attempt::run(assembler, skipped) {
+                    if best
+                        .as_ref()
+                        .map_or(true, |(_, b)| attempt.placed > b.placed)
+                    {
+                        best = Some((Repair::Skip(resync), attempt));
+                    }
+                }
+            }
+        }
+
+        for (group_path, entry_tag) in insert_candidates(mig, &stalled_tag) {
+            let inserted = current.input.insert(stall, &entry_tag);
+            if let Some(attempt) = Attempt::run(assembler, inserted) {
+                // The inserted segment must at least carry the stalled one.
+                if attempt.consumed > stall + 1
+                    && best
+                        .as_ref()
+                        .map_or(true, |(_, b)| attempt.placed > b.placed)
+                {
+                    best = Some((
+                        Repair::Insert {
+                            group_path,
+                            entry_tag,
+                        },
+                        attempt,
+                    ));
+                }
+            }
+        }
+
+        let Some((repair, next)) = best else {
+            break;
+        };
+        match repair {
+            Repair::Skip(end) => {
+                let resync_tag = current.input.segments.get(end).map(|s| s.id.clone());
+                for idx in stall..end {
+                    let Some(original) = current.input.origins[idx] else {
+                        continue;
+                    };
+                    repairs.push(skip_diagnostic(
+                        &known,
+                        &segments[original],
+                        original,
+                        (end > stall + 1).then_some(resync_tag.as_deref()).flatten(),
+                    ));
+                }
+            }
+            Repair::Insert {
+                group_path,
+                entry_tag,
+            } => {
+                let position = current.input.original_index(stall);
+                repairs.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::MissingRequiredSegment,
+                    segment_id: entry_tag.clone(),
+                    position,
+                    message: format!(
+                        "Inserted empty {entry_tag} to open {group_path} for '{stalled_tag}'"
+                    ),
+                    mig_path: Some(join_path(&group_path, &entry_tag)),
+                    location: None,
+                    repair: Some(StructureRepair::Inserted),
+                });
+            }
+        }
+        current = next;
+    }
+
+    let (tree, structure) = assembler.assemble_with_diagnostics(&current.input.segments);
+    let mut diagnostics = repairs;
+    for mut diagnostic in structure {
+        diagnostic.position = current.input.original_index(diagnostic.position);
+        diagnostics.push(diagnostic);
+    }
+    // Stable sort: a repair stays ahead of what the check found at the same spot.
+    diagnostics.sort_by_key(|d| d.position);
+    for diagnostic in &mut diagnostics {
+        diagnostic.location = segments
+            .get(diagnostic.position)
+            .and_then(|s| s.location.clone());
+    }
+    (tree.into_owned(), diagnostics)
+}
+
+/// Input as the assembler sees it after repairs.
+#[derive(Clone)]
+struct RepairedInput {
+    segments: Vec<OwnedSegment>,
+    /// Index into the caller's input for every segment; `None` for inserted ones.
+    origins: Vec<Option<usize>>,
+    input_len: usize,
+}
+
+impl RepairedInput {
+    fn new(segments: &[OwnedSegment]) -> Self {
+        Self {
+            segments: segments.to_vec(),
+            origins: (0..segments.len()).map(Some).collect(),
+            input_len: segments.len(),
+        }
+    }
+
+    /// Drop `start..end`.
+    fn skip(&self, start: usize, end: usize) -> Self {
+        let mut repaired = self.clone();
+        repaired.segments.drain(start..end);
+        repaired.origins.drain(start..end);
+        repaired
+    }
+
+    /// Insert an element-less `tag` segment before `at`.
+    fn insert(&self, at: usize, tag: &str) -> Self {
+        let mut repaired = self.clone();
+        repaired.segments.insert(
+            at,
+            OwnedSegment {
+                id: tag.to_string(),
+                elements: vec![],
+                segment_number: 0,
+                location: None,
+            },
+        );
+        repaired.origins.insert(at, None);
+        repaired
+    }
+
+    /// Caller's index for `idx`; an inserted segment maps to the next
+    /// original segment, the end of the repaired input to the end of the
+    /// caller's input.
+    fn original_index(&self, idx: usize) -> usize {
+        self.origins
+            .iter()
+            .skip(idx)
+            .find_map(|o| *o)
+            .unwrap_or(self.input_len)
+    }
+}
+
+/// One assembly run over a repaired input.
+struct Attempt {
+    input: RepairedInput,
+    /// Segments of `input` the assembler consumed (always a prefix).
+    consumed: usize,
+    /// Caller's segments among the consumed ones.
+    placed: usize,
+}
+
+impl Attempt {
+    fn run(assembler: &Assembler, input: RepairedInput) -> Option<Self> {
+        let tree = assembler.assemble_generic(&input.segments).ok()?;
+        let consumed = tree_segment_count(&tree);
+        let placed = input.origins[..consumed.min(input.origins.len())]
+            .iter()
+            .flatten()
+            .count();
+        Some(Self {
+            input,
+            consumed,
+            placed,
+        })
+    }
+}
+
+enum Repair {
+    /// Skip from the stall up to (excluding) this index.
+    Skip(usize),
+    Insert {
+        group_path: String,
+        entry_tag: String,
+    },
+}
+
+fn skip_diagnostic(
+    known: &HashMap<String, String>,
+    segment: &OwnedSegment,
+    position: usize,
+    resync_tag: Option<&str>,
+) -> StructureDiagnostic {
+    let tag = segment.id.as_str();
+    let mig_path = known.get(&tag.to_ascii_uppercase()).cloned();
+    let mut message = match &mig_path {
+        Some(path) => format!("Skipped '{tag}': the MIG defines it at {path}, not here"),
+        None => format!("Skipped '{tag}': not defined in the MIG"),
+    };
+    if let Some(resync_tag) = resync_tag {
+        message.push_str(&format!(" (resynced on '{resync_tag}')"));
+    }
+    StructureDiagnostic {
+        kind: if mig_path.is_some() {
+            StructureDiagnosticKind::OutOfOrderSegment
+        } else {
+            StructureDiagnosticKind::UnexpectedSegment
+        },
+        segment_id: tag.to_string(),
+        position,
+        message,
+        mig_path,
+        location: None,
+        repair: Some(StructureRepair::Skipped),
+    }
+}
+
+/// Tags assembly can resume on: root segments and group entry segments.
+fn anchor_tags(mig: &MigSchema) -> HashSet<String> {
+    fn collect(groups: &[MigSegmentGroup], out: &mut HashSet<String>) {
+        for group in groups {
+            if let Some(entry) = group.segments.first() {
+                out.insert(entry.id.to_ascii_uppercase());
+            }
+            collect(&group.nested_groups, out);
+        }
+    }
+
+    let mut out: HashSet<String> = mig
+        .segments
+        .iter()
+        .map(|s| s.id.to_ascii_uppercase())
+        .collect();
+    collect(&mig.segment_groups, &mut out);
+    out
+}
+
+/// `(group path, entry tag)` of every group that would take a segment
+/// tagged `tag` right after its entry segment, either as one of its own
+/// segments or as the entry of a nested group. One candidate per entry tag,
+/// since the assembler selects groups by entry tag alone.
+fn insert_candidates(mig: &MigSchema, tag: &str) -> Vec<(String, String)> {
+    fn collect(
+        groups: &[MigSegmentGroup],
+        parent: &str,
+        tag: &str,
+        out: &mut Vec<(String, String)>,
+    ) {
+        for group in groups {
+            let path = join_path(parent, &group.id);
+            if let Some((entry, rest)) = group.segments.split_first() {
+                let takes_tag = rest
+                    .iter()
+                    .any(|s| matcher::matches_segment_tag(tag, &s.id))
+                    || group.nested_groups.iter().any(|ng| {
+                        ng.segments
+                            .first()
+                            .is_some_and(|es| matcher::matches_segment_tag(tag, &es.id))
+                    });
+                if takes_tag
+                    && !matcher::matches_segment_tag(tag, &entry.id)
+                    && !out
+                        .iter()
+                        .any(|(_, e)| matcher::matches_segment_tag(e, &entry.id))
+                {
+                    out.push((path.clone(), entry.id.clone()));
+                }
+            }
+            collect(&group.nested_groups, &path, tag, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    collect(&mig.segment_groups, "", tag, &mut out);
+    out
+}
+
+/// Number of input segments the assembler consumed to build `tree`.
+fn tree_segment_count(tree: &AssembledTree<'_>) -> usize {
+    fn count(groups: &[AssembledGroup]) -> usize {
+        groups
+            .iter()
+            .flat_map(|g| &g.repetitions)
+            .map(|r| r.segments.len() + r.skipped_segments.len() + count(&r.child_groups))
+            .sum()
+    }
+
+    tree.segments.len()
+        + tree
+            .inter_group_segments
+            .values()
+            .map(Vec::len)
+            .sum::<usize>()
+        + count(&tree.groups)
+}
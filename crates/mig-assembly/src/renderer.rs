@@ -1,78 +1,92 @@
 //! EDIFACT string renderer from disassembled segments.
 //!
 //! Converts a list of `DisassembledSegment` values back into a valid
-//! EDIFACT string using the provided delimiters.
+//! EDIFACT string using the provided delimiters, or into bytes in the
+//! character set declared in UNB S001.
 
 use crate::disassembler::DisassembledSegment;
-use edifact_types::EdifactDelimiters;
+use crate::error::AssemblyError;
+use edifact_types::{
+    EdifactDelimiters, EdifactWriter, EncodeError, EncodeOptions, SyntaxIdentifier,
+};
 
 /// Render a list of disassembled segments into an EDIFACT string.
 ///
-/// Follows the same rendering rules as `RawSegment::to_raw_string`:
+/// Segments are written with [`EdifactWriter`]:
 /// - Elements separated by element separator (`+`)
 /// - Components separated by component separator (`:`)
-/// - Trailing empty elements are trimmed
+/// - Delimiter characters in values escaped with the release character
+/// - All elements and components written, including trailing empty ones
 /// - Each segment terminated by segment terminator (`'`)
 pub fn render_edifact(segments: &[DisassembledSegment], delimiters: &EdifactDelimiters) -> String {
-    let mut out = String::new();
+    let mut writer = EdifactWriter::new(Vec::new()).with_delimiters(*delimiters);
 
     for seg in segments {
-        render_segment(seg, delimiters, &mut out);
+        writer
+            .write_segment(&seg.tag, &seg.elements)
+            .expect("writing UTF-8 to a Vec cannot fail");
     }
 
-    out
+    String::from_utf8(writer.into_inner()).expect("writer output is UTF-8")
 }
 
-fn render_segment(seg: &DisassembledSegment, delimiters: &EdifactDelimiters, out: &mut String) {
-    let elem_sep = delimiters.element as char;
-    let comp_sep = delimiters.component as char;
-    let seg_term = delimiters.segment as char;
-
-    out.push_str(&seg.tag);
-
-    for element in &seg.elements {
-        out.push(elem_sep);
-        // Preserve ALL components including trailing empty ones for roundtrip fidelity.
-        for (j, component) in element.iter().enumerate() {
-            if j > 0 {
-                out.push(comp_sep);
-            }
-            escape_component(component, delimiters, out);
-        }
-    }
-
-    out.push(seg_term);
-}
-
-/// Write a component value, escaping any delimiter characters with the release character.
+/// Render segments into EDIFACT bytes in the given character set.
 ///
-/// Characters that must be escaped: element separator (`+`), component separator (`:`),
-/// segment terminator (`'`), and the release character itself (`?`).
+/// Every component is first brought into the repertoire of `charset`
+/// according to `options` (see [`SyntaxIdentifier::to_repertoire`]), then
+/// escaped and encoded, e.g. as ISO 8859-1 bytes for UNOC.
 ///
-/// All values — whether parsed from EDIFACT or synthetically created — are stored
-/// without escape sequences. The tokenizer unescapes during `OwnedSegment` creation,
-/// and the renderer re-escapes here when writing EDIFACT output.
-fn escape_component(value: &str, delimiters: &EdifactDelimiters, out: &mut String) {
-    let release = delimiters.release;
-    let special = [
-        delimiters.element,
-        delimiters.component,
-        delimiters.segment,
-        delimiters.release,
-    ];
-
-    let needs_escape = value.bytes().any(|b| special.contains(&b));
-    if !needs_escape {
-        out.push_str(value);
-        return;
-    }
+/// # Errors
+///
+/// Returns [`AssemblyError::Encoding`] for the first character that is
+/// neither in the repertoire nor handled by `options`.
+pub fn render_edifact_encoded(
+    segments: &[DisassembledSegment],
+    delimiters: &EdifactDelimiters,
+    charset: SyntaxIdentifier,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, AssemblyError> {
+    let mut writer = EdifactWriter::new(Vec::new())
+        .with_delimiters(*delimiters)
+        .with_syntax_identifier(charset);
+
+    for seg in segments {
+        let encoding_error = |source| AssemblyError::Encoding {
+            segment_id: seg.tag.clone(),
+            source,
+        };
+        let elements = seg
+            .elements
+            .iter()
+            .map(|element| {
+                element
+                    .iter()
+                    .map(|component| Ok(charset.to_repertoire(component, options)?.into_owned()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(encoding_error)?;
 
-    for b in value.bytes() {
-        if special.contains(&b) {
-            out.push(release as char);
-        }
-        out.push(b as char);
+        writer.write_segment(&seg.tag, &elements).map_err(|e| {
+            // Writing to a Vec only fails on characters outside the charset
+            let source = e
+                .into_inner()
+                .and_then(|e| e.downcast::<EncodeError>().ok())
+                .expect("writer error wraps an EncodeError");
+            encoding_error(*source)
+        })?;
     }
+
+    Ok(writer.into_inner())
+}
+
+/// The syntax identifier declared in the UNB among `segments`, if any.
+pub fn declared_syntax_identifier(segments: &[DisassembledSegment]) -> Option<SyntaxIdentifier> {
+    let unb = segments
+        .iter()
+        .find(|s| s.tag.eq_ignore_ascii_case("UNB"))?;
+    let code = unb.elements.first()?.first()?;
+    SyntaxIdentifier::from_code(code)
 }
 
 #[cfg(test)]
@@ -181,6 +195,72 @@ mod tests {
         assert_eq!(rendered, "FTX+ABO+++hello???+world?:test'");
     }
 
+    fn nad(name: &str) -> Vec<DisassembledSegment> {
+        vec![
+            DisassembledSegment {
+                tag: "UNB".to_string(),
+                elements: vec![vec!["UNOC".to_string(), "3".to_string()]],
+            },
+            DisassembledSegment {
+                tag: "NAD".to_string(),
+                elements: vec![vec!["Z09".to_string()], vec![name.to_string()]],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_encoded_emits_latin1_bytes_for_unoc() {
+        let segments = nad("Müller+Söhne");
+        let charset = declared_syntax_identifier(&segments).unwrap();
+        assert_eq!(charset, SyntaxIdentifier::Unoc);
+
+        let bytes = render_edifact_encoded(
+            &segments,
+            &EdifactDelimiters::default(),
+            charset,
+            &EncodeOptions::default(),
+        )
+        .unwrap();
+
+        let expected: Vec<u8> = [
+            b"UNB+UNOC:3'NAD+Z09+M".as_slice(),
+            &[0xFC],
+            b"ller?+S",
+            &[0xF6],
+            b"hne'",
+        ]
+        .concat();
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_render_encoded_transliterates_or_fails() {
+        let segments = nad("Łukasz 5 €");
+        let delimiters = EdifactDelimiters::default();
+
+        let err = render_edifact_encoded(
+            &segments,
+            &delimiters,
+            SyntaxIdentifier::Unoc,
+            &EncodeOptions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            AssemblyError::Encoding { ref segment_id, ref source }
+                if segment_id == "NAD" && source.character == 'Ł'
+        ));
+
+        let options = EncodeOptions {
+            transliterate: true,
+            replacement: None,
+        };
+        let bytes =
+            render_edifact_encoded(&segments, &delimiters, SyntaxIdentifier::Unoa, &options)
+                .unwrap();
+        assert_eq!(bytes, b"UNB+UNOC:3'NAD+Z09+LUKASZ 5 EUR'");
+    }
+
     #[test]
     fn test_render_no_escape_needed_for_plain_values() {
         let segments = vec![DisassembledSegment {
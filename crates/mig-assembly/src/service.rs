@@ -6,8 +6,9 @@
 use std::path::Path;
 
 use crate::assembler::{Assembler, AssemblerConfig};
+use crate::automaton::CompiledMig;
 use crate::parsing::parse_mig;
-use crate::tokenize::parse_to_segments;
+use crate::tokenize::{parse_borrowed_segments, parse_str_to_segments};
 use crate::AssemblyError;
 use mig_types::schema::mig::MigSchema;
 
@@ -15,6 +16,7 @@ use mig_types::schema::mig::MigSchema;
 /// convenient methods for EDIFACT conversion.
 pub struct ConversionService {
     mig: MigSchema,
+    compiled: CompiledMig,
 }
 
 impl ConversionService {
@@ -27,30 +29,35 @@ impl ConversionService {
     ) -> Result<Self, AssemblyError> {
         let mig = parse_mig(mig_path, message_type, variant, format_version)
             .map_err(|e| AssemblyError::ParseError(e.to_string()))?;
-        Ok(Self { mig })
+        Ok(Self::from_mig(mig))
     }
 
     /// Create a `ConversionService` from an already-parsed MIG schema.
     pub fn from_mig(mig: MigSchema) -> Self {
-        Self { mig }
+        let compiled = CompiledMig::compile(&mig);
+        Self { mig, compiled }
+    }
+
+    fn assembler(&self, config: AssemblerConfig) -> Assembler<'_> {
+        Assembler::with_compiled(&self.mig, &self.compiled, config)
     }
 
     /// Convert EDIFACT input to an assembled tree, serialized as JSON.
     pub fn convert_to_tree(&self, input: &str) -> Result<serde_json::Value, AssemblyError> {
-        let segments = parse_to_segments(input.as_bytes())?;
-        let assembler = Assembler::new(&self.mig);
-        let tree = assembler.assemble_generic(&segments)?;
+        let tree = self.convert_to_assembled_tree(input)?;
         serde_json::to_value(&tree).map_err(|e| AssemblyError::ParseError(e.to_string()))
     }
 
     /// Convert EDIFACT input to an `AssembledTree` (typed, not JSON).
-    pub fn convert_to_assembled_tree(
+    ///
+    /// The tree borrows from `input`; call
+    /// [`AssembledTree::into_owned`](crate::assembler::AssembledTree::into_owned)
+    /// to keep it longer.
+    pub fn convert_to_assembled_tree<'i>(
         &self,
-        input: &str,
-    ) -> Result<crate::assembler::AssembledTree, AssemblyError> {
-        let segments = parse_to_segments(input.as_bytes())?;
-        let assembler = Assembler::new(&self.mig);
-        assembler.assemble_generic(&segments)
+        input: &'i str,
+    ) -> Result<crate::assembler::AssembledTree<'i>, AssemblyError> {
+        self.convert_to_assembled_tree_with_config(input, AssemblerConfig::default())
     }
 
     /// Convert a complete interchange into per-message assembled trees.
@@ -68,33 +75,34 @@ impl ConversionService {
     ) -> Result<
         (
             crate::tokenize::InterchangeChunks,
-            Vec<crate::assembler::AssembledTree>,
+            Vec<crate::assembler::AssembledTree<'static>>,
         ),
         AssemblyError,
     > {
-        let segments = parse_to_segments(input.as_bytes())?;
+        let segments = parse_str_to_segments(input)?;
         let chunks = crate::tokenize::split_messages(segments)?;
 
         let mut trees = Vec::with_capacity(chunks.messages.len());
         for msg in &chunks.messages {
             let all_segments = msg.all_segments();
-            let assembler = Assembler::new(&self.mig);
+            let assembler = self.assembler(AssemblerConfig::default());
             let tree = assembler.assemble_generic(&all_segments)?;
-            trees.push(tree);
+            trees.push(tree.into_owned());
         }
 
         Ok((chunks, trees))
     }
 
     /// Convert EDIFACT input to an `AssembledTree` with custom assembler config.
-    pub fn convert_to_assembled_tree_with_config(
+    ///
+    /// The tree borrows from `input`, as with [`Self::convert_to_assembled_tree`].
+    pub fn convert_to_assembled_tree_with_config<'i>(
         &self,
-        input: &str,
+        input: &'i str,
         config: AssemblerConfig,
-    ) -> Result<crate::assembler::AssembledTree, AssemblyError> {
-        let segments = parse_to_segments(input.as_bytes())?;
-        let assembler = Assembler::with_config(&self.mig, config);
-        assembler.assemble_generic(&segments)
+    ) -> Result<crate::assembler::AssembledTree<'i>, AssemblyError> {
+        let segments = parse_borrowed_segments(input)?;
+        self.assembler(config).assemble_segments(segments)
     }
 
     /// Convert a complete interchange into per-message assembled trees with custom config.
@@ -105,19 +113,19 @@ impl ConversionService {
     ) -> Result<
         (
             crate::tokenize::InterchangeChunks,
-            Vec<crate::assembler::AssembledTree>,
+            Vec<crate::assembler::AssembledTree<'static>>,
         ),
         AssemblyError,
     > {
-        let segments = parse_to_segments(input.as_bytes())?;
+        let segments = parse_str_to_segments(input)?;
         let chunks = crate::tokenize::split_messages(segments)?;
 
         let mut trees = Vec::with_capacity(chunks.messages.len());
         for msg in &chunks.messages {
             let all_segments = msg.all_segments();
-            let assembler = Assembler::with_config(&self.mig, config.clone());
+            let assembler = self.assembler(config.clone());
             let tree = assembler.assemble_generic(&all_segments)?;
-            trees.push(tree);
+            trees.push(tree.into_owned());
         }
 
         Ok((chunks, trees))
@@ -127,4 +135,9 @@ impl ConversionService {
     pub fn mig(&self) -> &MigSchema {
         &self.mig
     }
+
+    /// The loaded MIG schema compiled for assembly.
+    pub fn compiled(&self) -> &CompiledMig {
+        &self.compiled
+    }
 }
@@ -0,0 +1,397 @@
+//! Incremental message splitting for large interchanges.
+//!
+//! [`parse_to_segments`](crate::tokenize::parse_to_segments) followed by
+//! [`split_messages`](crate::tokenize::split_messages) holds the whole
+//! interchange in memory several times over. [`MessageChunkReader`] reads
+//! from any `BufRead` instead and yields each [`MessageChunk`] as soon as its
+//! UNT segment has been read, so memory is bounded by the largest single
+//! message. With the `tokio` feature, `AsyncMessageChunkReader` does the
+//! same for tokio's `AsyncBufRead` and can be turned into a `Stream`.
+
+use std::io::BufRead;
+use std::sync::Arc;
+
+use edifact_parser::SegmentReader;
+
+use crate::tokenize::{to_owned_segment, MessageChunk, OwnedSegment};
+use crate::AssemblyError;
+
+/// Iterator over the messages of an interchange read incrementally.
+///
+/// Chunks are identical to those produced by
+/// [`split_interchanges`](crate::tokenize::split_interchanges): segments
+/// before the first UNH of an interchange form its shared envelope, messages
+/// in a functional group carry its UNG, other segments outside UNH/UNT are
+/// dropped, and an unterminated message is ignored. Input with several
+/// interchanges yields the messages of all of them, each with the envelope
+/// of its own interchange. If the input contains no UNH/UNT pair at all,
+/// the iterator yields a single error.
+///
+/// # Example
+///
+/// ```
+/// use mig_assembly::streaming::MessageChunkReader;
+///
+/// let input = b"UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF'UNH+1+MSCONS:D:04B:UN:2.4c'BGM+7+DOC'UNT+3+1'UNZ+1+REF'";
+/// let mut reader = MessageChunkReader::new(&input[..]).unwrap();
+/// let chunk = reader.next().unwrap().unwrap();
+/// assert_eq!(chunk.body.len(), 1);
+/// assert!(reader.next().is_none());
+/// assert!(reader.unz().is_some());
+/// ```
+pub struct MessageChunkReader<R> {
+    segments: SegmentReader<R>,
+    release: u8,
+    splitter: ChunkSplitter,
+    finished: bool,
+}
+
+impl<R: BufRead> MessageChunkReader<R> {
+    /// Create a reader over an EDIFACT interchange.
+    pub fn new(reader: R) -> Result<Self, AssemblyError> {
+        let segments =
+            SegmentReader::new(reader).map_err(|e| AssemblyError::ParseError(e.to_string()))?;
+        let release = segments.delimiters().release;
+        Ok(Self {
+            segments,
+            release,
+            splitter: ChunkSplitter::default(),
+            finished: false,
+        })
+    }
+
+    /// The envelope of the current interchange (UNB and anything before
+    /// its first UNH).
+    ///
+    /// Available once the interchange's first message has been read.
+    pub fn envelope(&self) -> Option<&Arc<Vec<OwnedSegment>>> {
+        self.splitter.envelope.as_ref()
+    }
+
+    /// The UNZ segment of the current interchange, once the reader has
+    /// reached it.
+    pub fn unz(&self) -> Option<&OwnedSegment> {
+        self.splitter.unz.as_ref()
+    }
+
+    /// Number of messages yielded so far.
+    pub fn messages_read(&self) -> usize {
+        self.splitter.messages_read
+    }
+
+    fn read_message(&mut self) -> Result<Option<MessageChunk>, AssemblyError> {
+        loop {
+            let segment = match self.segments.next_segment() {
+                Ok(Some(segment)) => to_owned_segment(&segment, self.release),
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(AssemblyError::ParseError(e.to_string())),
+            };
+            if let Some(chunk) = self.splitter.push(segment) {
+                return Ok(Some(chunk));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for MessageChunkReader<R> {
+    type Item = Result<MessageChunk, AssemblyError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.read_message();
+        self.splitter.next_item(result, &mut self.finished)
+    }
+}
+
+/// Async counterpart of [`MessageChunkReader`] over a tokio `AsyncBufRead`.
+///
+/// Yields the same chunks, each as soon as its UNT segment has arrived, so
+/// mapping can start on the first message of an upload before the rest of
+/// it has been received. Requires the `tokio` feature.
+///
+/// # Example
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use futures_util::StreamExt;
+/// use mig_assembly::streaming::AsyncMessageChunkReader;
+///
+/// let input = b"UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF'UNH+1+MSCONS:D:04B:UN:2.4c'BGM+7+DOC'UNT+3+1'UNZ+1+REF'";
+/// let reader = AsyncMessageChunkReader::new(&input[..]).await.unwrap();
+/// let chunks: Vec<_> = reader.into_stream().collect().await;
+/// assert_eq!(chunks.len(), 1);
+/// assert_eq!(chunks[0].as_ref().unwrap().body.len(), 1);
+/// # });
+/// ```
+#[cfg(feature = "tokio")]
+pub struct AsyncMessageChunkReader<R> {
+    segments: edifact_parser::AsyncSegmentReader<R>,
+    release: u8,
+    splitter: ChunkSplitter,
+    finished: bool,
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncBufRead + Unpin> AsyncMessageChunkReader<R> {
+    /// Create a reader over an EDIFACT interchange.
+    pub async fn new(reader: R) -> Result<Self, AssemblyError> {
+        let segments = edifact_parser::AsyncSegmentReader::new(reader)
+            .await
+            .map_err(|e| AssemblyError::ParseError(e.to_string()))?;
+        let release = segments.delimiters().release;
+        Ok(Self {
+            segments,
+            release,
+            splitter: ChunkSplitter::default(),
+            finished: false,
+        })
+    }
+
+    /// The envelope of the current interchange (UNB and anything before
+    /// its first UNH).
+    ///
+    /// Available once the interchange's first message has been read.
+    pub fn envelope(&self) -> Option<&Arc<Vec<OwnedSegment>>> {
+        self.splitter.envelope.as_ref()
+    }
+
+    /// The UNZ segment of the current interchange, once the reader has
+    /// reached it.
+    pub fn unz(&self) -> Option<&OwnedSegment> {
+        self.splitter.unz.as_ref()
+    }
+
+    /// Number of messages yielded so far.
+    pub fn messages_read(&self) -> usize {
+        self.splitter.messages_read
+    }
+
+    /// Read the next message, or `None` when the input is exhausted.
+    pub async fn next_chunk(&mut self) -> Option<Result<MessageChunk, AssemblyError>> {
+        if self.finished {
+            return None;
+        }
+        let result = self.read_message().await;
+        self.splitter.next_item(result, &mut self.finished)
+    }
+
+    /// Turn the reader into a stream of message chunks.
+    ///
+    /// Input is only read while the stream is polled, so a slow consumer
+    /// applies backpressure to the underlying reader.
+    pub fn into_stream(
+        self,
+    ) -> impl futures_util::Stream<Item = Result<MessageChunk, AssemblyError>> {
+        futures_util::stream::unfold(self, |mut reader| async move {
+            let chunk = reader.next_chunk().await?;
+            Some((chunk, reader))
+        })
+    }
+
+    async fn read_message(&mut self) -> Result<Option<MessageChunk>, AssemblyError> {
+        loop {
+            let segment = match self.segments.next_segment().await {
+                Ok(Some(segment)) => to_owned_segment(&segment, self.release),
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(AssemblyError::ParseError(e.to_string())),
+            };
+            if let Some(chunk) = self.splitter.push(segment) {
+                return Ok(Some(chunk));
+            }
+        }
+    }
+}
+
+/// Message splitting state shared by the sync and async chunk readers.
+#[derive(Default)]
+struct ChunkSplitter {
+    /// Segments before the first UNH; moved into `envelope` at the first UNH.
+    pending_envelope: Vec<OwnedSegment>,
+    envelope: Option<Arc<Vec<OwnedSegment>>>,
+    /// The UNG of the open functional group.
+    group: Option<Arc<OwnedSegment>>,
+    unz: Option<OwnedSegment>,
+    unh: Option<OwnedSegment>,
+    body: Vec<OwnedSegment>,
+    messages_read: usize,
+}
+
+impl ChunkSplitter {
+    /// Take the next segment, returning the message it completes, if any.
+    fn push(&mut self, segment: OwnedSegment) -> Option<MessageChunk> {
+        match segment.id.to_uppercase().as_str() {
+            "UNB" if self.unh.is_none() => {
+                // A new interchange starts with its own envelope
+                self.envelope = None;
+                self.pending_envelope = vec![segment];
+                self.group = None;
+                self.unz = None;
+                self.unh = None;
+            }
+            "UNG" if self.unh.is_none() => {
+                self.group = Some(Arc::new(segment));
+            }
+            "UNE" if self.unh.is_none() => {
+                self.group = None;
+            }
+            "UNH" => {
+                if self.envelope.is_none() {
+                    let envelope = std::mem::take(&mut self.pending_envelope);
+                    self.envelope = Some(Arc::new(envelope));
+                }
+                self.unh = Some(segment);
+                self.body.clear();
+            }
+            "UNT" => {
+                if let Some(unh) = self.unh.take() {
+                    return Some(MessageChunk {
+                        envelope: Arc::clone(self.envelope.get_or_insert_with(Default::default)),
+                        group: self.group.clone(),
+                        unh,
+                        body: std::mem::take(&mut self.body),
+                        unt: segment,
+                    });
+                }
+            }
+            "UNZ" => {
+                self.group = None;
+                self.unz = Some(segment);
+            }
+            _ => {
+                if self.unh.is_some() {
+                    self.body.push(segment);
+                } else if self.envelope.is_none() {
+                    self.pending_envelope.push(segment);
+                }
+            }
+        }
+        None
+    }
+
+    /// Turn the result of reading a message into the reader's next item,
+    /// setting `finished` at the end of input or after an error.
+    fn next_item(
+        &mut self,
+        result: Result<Option<MessageChunk>, AssemblyError>,
+        finished: &mut bool,
+    ) -> Option<Result<MessageChunk, AssemblyError>> {
+        match result {
+            Ok(Some(chunk)) => {
+                self.messages_read += 1;
+                Some(Ok(chunk))
+            }
+            Ok(None) => {
+                *finished = true;
+                (self.messages_read == 0).then(|| {
+                    Err(AssemblyError::ParseError(
+                        "No UNH/UNT message pairs found in interchange".to_string(),
+                    ))
+                })
+            }
+            Err(e) => {
+                *finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenize::{parse_to_segments, split_messages};
+
+    const TWO_MESSAGES: &[u8] = b"UNA:+.? 'UNB+UNOC:3+SENDER+RECEIVER+210101:1200+REF001'UNH+001+UTILMD:D:11A:UN:S2.1'BGM+E01+DOC?+001'UNT+3+001'UNH+002+UTILMD:D:11A:UN:S2.1'BGM+E03+DOC002'DTM+137:20250101:102'UNT+4+002'UNZ+2+REF001'";
+
+    #[test]
+    fn test_chunks_match_split_messages() {
+        let expected = split_messages(parse_to_segments(TWO_MESSAGES).unwrap()).unwrap();
+
+        let mut reader = MessageChunkReader::new(TWO_MESSAGES).unwrap();
+        let chunks: Vec<MessageChunk> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(chunks.len(), expected.messages.len());
+        for (chunk, want) in chunks.iter().zip(&expected.messages) {
+            assert_eq!(chunk.all_segments(), want.all_segments());
+        }
+        assert_eq!(**reader.envelope().unwrap(), expected.envelope);
+        assert_eq!(reader.unz(), expected.unz.as_ref());
+        assert_eq!(reader.messages_read(), 2);
+    }
+
+    #[test]
+    fn test_chunks_share_envelope_and_unescape_values() {
+        let chunks: Vec<MessageChunk> = MessageChunkReader::new(TWO_MESSAGES)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&chunks[0].envelope, &chunks[1].envelope));
+        assert!(chunks[0].envelope[0].is("UNB"));
+        assert_eq!(chunks[0].body[0].get_element(1), "DOC+001");
+    }
+
+    #[test]
+    fn test_chunks_match_split_interchanges() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+IC1'UNG+UTILMD+S+R+210101:1200+G1'\
+UNH+001+UTILMD'UNT+2+001'UNE+1+G1'UNZ+1+IC1'\
+UNB+UNOC:3+S+R+210101:1200+IC2'UNH+002+UTILMD'UNT+2+002'UNZ+1+IC2'";
+        let expected =
+            crate::tokenize::split_interchanges(parse_to_segments(input).unwrap()).unwrap();
+
+        let mut reader = MessageChunkReader::new(&input[..]).unwrap();
+        let chunks: Vec<MessageChunk> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[0].all_segments(),
+            expected[0].messages[0].all_segments()
+        );
+        assert_eq!(
+            chunks[1].all_segments(),
+            expected[1].messages[0].all_segments()
+        );
+        assert_eq!(
+            chunks[0].group.as_deref(),
+            Some(&*expected[0].groups[0].ung)
+        );
+        assert!(chunks[1].group.is_none());
+        assert_eq!(reader.unz(), expected[1].unz.as_ref());
+    }
+
+    #[test]
+    fn test_no_messages_yields_single_error() {
+        let input = b"UNA:+.? 'UNB+UNOC:3+S+R+210101:1200+REF'UNZ+0+REF'";
+        let mut reader = MessageChunkReader::new(&input[..]).unwrap();
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert!(reader.unz().is_some());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_chunks_match_sync_chunks() {
+        use futures_util::StreamExt;
+
+        let expected: Vec<MessageChunk> = MessageChunkReader::new(TWO_MESSAGES)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        // A tiny buffer makes segments arrive in several pieces
+        let input = tokio::io::BufReader::with_capacity(4, TWO_MESSAGES);
+        let mut reader = AsyncMessageChunkReader::new(input).await.unwrap();
+        let first = reader.next_chunk().await.unwrap().unwrap();
+        assert_eq!(first.all_segments(), expected[0].all_segments());
+        assert!(reader.unz().is_none());
+
+        let rest: Vec<_> = reader.into_stream().collect().await;
+        assert_eq!(rest.len(), 1);
+        assert_eq!(
+            rest[0].as_ref().unwrap().all_segments(),
+            expected[1].all_segments()
+        );
+    }
+}
@@ -0,0 +1,855 @@
+//! MIG structure checks over an assembled tree.
+//!
+//! The assembler is deliberately forgiving: it consumes whatever matches the
+//! MIG and stops at the first segment it cannot place. [`check_structure`]
+//! compares the assembled result against the MIG and reports what the
+//! assembler tolerated silently — missing mandatory segments and groups,
+//! MaxRep overruns, and segments that could not be placed at all.
+
+use std::collections::HashMap;
+
+use mig_types::schema::mig::{MigSchema, MigSegment, MigSegmentGroup};
+use mig_types::segment::OwnedSegment;
+
+use crate::assembler::{AssembledGroup, AssembledSegment, AssembledTree};
+use crate::diagnostic::{StructureDiagnostic, StructureDiagnosticKind};
+
+/// Interchange envelope segments. Callers usually pass only UNH..UNT, so
+/// their absence is not a message structure violation.
+const INTERCHANGE_SEGMENTS: &[&str] = &["UNA", "UNB", "UNZ"];
+
+/// Compare an assembled tree against the MIG it was assembled with.
+///
+/// `segments` must be the exact input that produced `tree`; diagnostic
+/// positions are indices into it, and diagnostics take the location of the
+/// segment there.
+pub fn check_structure(
+    mig: &MigSchema,
+    tree: &AssembledTree,
+    segments: &[OwnedSegment],
+) -> Vec<StructureDiagnostic> {
+    let mut checker = walk_tree(mig, tree, segments);
+    checker.diagnostics.sort_by_key(|d| d.position);
+    for diagnostic in &mut checker.diagnostics {
+        diagnostic.location = segments
+            .get(diagnostic.position)
+            .and_then(|s| s.location.clone());
+    }
+    checker.diagnostics
+}
+
+/// The MIG slot an input segment was assembled into.
+#[derive(Debug, Clone)]
+pub struct SegmentPlacement<'m> {
+    /// Path of the enclosing group (e.g. `SG4/SG8`); empty at message root.
+    pub group_path: String,
+    /// MIG definition of the slot the segment fills.
+    pub definition: &'m MigSegment,
+}
+
+/// Resolve every input segment to the MIG slot it was assembled into.
+///
+/// Returns one entry per input segment (same indices as `segments`);
+/// segments the assembler did not consume are `None`. Where a group offers
+/// several slots with the same tag, the slot whose qualifier code list
+/// contains the segment's first value wins.
+pub fn place_segments<'m>(
+    mig: &'m MigSchema,
+    tree: &AssembledTree,
+    segments: &[OwnedSegment],
+) -> Vec<Option<SegmentPlacement<'m>>> {
+    walk_tree(mig, tree, segments).placements
+}
+
+fn walk_tree<'m, 's>(
+    mig: &'m MigSchema,
+    tree: &AssembledTree,
+    segments: &'s [OwnedSegment],
+) -> StructureChecker<'m, 's> {
+    let mut checker = StructureChecker {
+        segments,
+        next: 0,
+        known_tags: known_tags(mig),
+        diagnostics: Vec::new(),
+        placements: vec![None; segments.len()],
+    };
+    let root_variants = [mig.segments.as_slice()];
+
+    let first_pass_end = tree.post_group_start.min(tree.segments.len());
+    let mut root_positions = Vec::new();
+    for seg in &tree.segments[..first_pass_end] {
+        let pos = checker.locate(seg, "", &root_variants);
+        root_positions.push((&*seg.tag, pos));
+    }
+
+    let group_rules = group_rules(&[mig.segment_groups.as_slice()]);
+    let mut group_positions = Vec::new();
+    for (idx, group) in tree.groups.iter().enumerate() {
+        for seg in tree.inter_group_segments.get(&idx).into_iter().flatten() {
+            let pos = checker.locate(seg, "", &root_variants);
+            root_positions.push((&*seg.tag, pos));
+        }
+        let rule = group_rules.iter().find(|r| r.id == group.group_id);
+        let positions = checker.walk_group(rule, group, "");
+        group_positions.push((group.group_id.as_str(), positions));
+    }
+    for (_, trailing) in tree.inter_group_segments.range(tree.groups.len()..) {
+        for seg in trailing {
+            let pos = checker.locate(seg, "", &root_variants);
+            root_positions.push((&*seg.tag, pos));
+        }
+    }
+    for seg in &tree.segments[first_pass_end..] {
+        let pos = checker.locate(seg, "", &root_variants);
+        root_positions.push((&*seg.tag, pos));
+    }
+
+    // Whatever the assembler could not place follows the last consumed segment.
+    for idx in checker.next..segments.len() {
+        checker.report_stray(idx);
+    }
+    checker.next = segments.len();
+
+    checker.check_root_order(&mig.segments, &root_positions);
+
+    let segment_rules = segment_rules(&root_variants);
+    checker.check_segments(&segment_rules, &root_positions, "", 0);
+    checker.check_groups(&group_rules, &group_positions, "", 0);
+    checker
+}
+
+/// Pick the MIG slot for a segment among all variants' slots with its tag.
+fn select_slot<'m>(variants: &[&'m [MigSegment]], seg: &OwnedSegment) -> Option<&'m MigSegment> {
+    let qualifier = seg.get_component(0, 0);
+    let mut candidates = variants
+        .iter()
+        .flat_map(|v| v.iter())
+        .filter(|s| s.id.eq_ignore_ascii_case(&seg.id))
+        .peekable();
+    let first = *candidates.peek()?;
+    let qualified = candidates.find(|slot| {
+        let codes = slot
+            .data_elements
+            .iter()
+            .find(|de| de.position == 0)
+            .map(|de| &de.codes)
+            .or_else(|| {
+                slot.composites
+                    .iter()
+                    .find(|c| c.position == 0)
+                    .and_then(|c| c.data_elements.iter().find(|de| de.position == 0))
+                    .map(|de| &de.codes)
+            });
+        codes.is_some_and(|codes| codes.iter().any(|c| c.value == qualifier))
+    });
+    Some(qualified.unwrap_or(first))
+}
+
+/// Occurrence rules for one segment tag within a group (or the message root).
+struct SegmentRule<'a> {
+    tag: &'a str,
+    /// Number of slots with this tag that every variant requires.
+    required: usize,
+    /// Largest total MaxRep of this tag in any variant.
+    max_rep: usize,
+}
+
+/// Occurrence rules for one segment group ID within its parent.
+struct GroupRule<'a> {
+    id: &'a str,
+    entry_tag: &'a str,
+    required: bool,
+    max_rep: usize,
+    /// All MIG definitions sharing this ID (qualifier variants).
+    defs: Vec<&'a MigSegmentGroup>,
+}
+
+/// Build segment rules from the segment lists of all parent variants.
+///
+/// A slot only counts as mandatory when every variant requires it — a
+/// repetition of one variant must not be reported as incomplete because a
+/// sibling variant has a different mandatory segment.
+fn segment_rules<'a>(variants: &[&'a [MigSegment]]) -> Vec<SegmentRule<'a>> {
+    let mut rules: Vec<SegmentRule<'a>> = Vec::new();
+    for seg in variants.iter().flat_map(|v| v.iter()) {
+        if rules.iter().any(|r| r.tag == seg.id) {
+            continue;
+        }
+        let required = variants
+            .iter()
+            .map(|v| {
+                v.iter()
+                    .filter(|s| s.id == seg.id && s.cardinality().is_required())
+                    .count()
+            })
+            .min()
+            .unwrap_or(0);
+        let max_rep = variants
+            .iter()
+            .map(|v| {
+                v.iter().filter(|s| s.id == seg.id).fold(0usize, |acc, s| {
+                    acc.saturating_add(effective_max_rep(s.max_rep()))
+                })
+            })
+            .max()
+            .unwrap_or(usize::MAX);
+        rules.push(SegmentRule {
+            tag: &seg.id,
+            required,
+            max_rep,
+        });
+    }
+    rules
+}
+
+/// Build group rules from the nested group lists of all parent variants.
+///
+/// Within one variant, groups sharing an ID are alternatives: the group is
+/// required if any of them is and their MaxReps add up. Across variants the
+/// group is only required if every variant requires it.
+fn group_rules<'a>(variants: &[&'a [MigSegmentGroup]]) -> Vec<GroupRule<'a>> {
+    let mut rules: Vec<GroupRule<'a>> = Vec::new();
+    for group in variants.iter().flat_map(|v| v.iter()) {
+        if let Some(rule) = rules.iter_mut().find(|r| r.id == group.id) {
+            rule.defs.push(group);
+            continue;
+        }
+        let required = variants.iter().all(|v| {
+            v.iter()
+                .any(|g| g.id == group.id && g.cardinality().is_required())
+        });
+        let max_rep = variants
+            .iter()
+            .map(|v| {
+                v.iter()
+                    .filter(|g| g.id == group.id)
+                    .fold(0usize, |acc, g| {
+                        acc.saturating_add(effective_max_rep(g.max_rep_spec.max(g.max_rep_std)))
+                    })
+            })
+            .max()
+            .unwrap_or(usize::MAX);
+        rules.push(GroupRule {
+            id: &group.id,
+            entry_tag: group.segments.first().map(|s| s.id.as_str()).unwrap_or(""),
+            required,
+            max_rep,
+            defs: vec![group],
+        });
+    }
+    rules
+}
+
+/// A MaxRep of 0 (or less) means the MIG does not restrict repetitions.
+fn effective_max_rep(max_rep: i32) -> usize {
+    if max_rep <= 0 {
+        usize::MAX
+    } else {
+        max_rep as usize
+    }
+}
+
+/// First MIG path for every segment tag defined anywhere in the MIG.
+pub(crate) fn known_tags(mig: &MigSchema) -> HashMap<String, String> {
+    fn collect(groups: &[MigSegmentGroup], parent: &str, out: &mut HashMap<String, String>) {
+        for group in groups {
+            let path = join_path(parent, &group.id);
+            for seg in &group.segments {
+                out.entry(seg.id.to_ascii_uppercase())
+                    .or_insert_with(|| join_path(&path, &seg.id));
+            }
+            collect(&group.nested_groups, &path, out);
+        }
+    }
+
+    let mut out = HashMap::new();
+    for seg in &mig.segments {
+        out.entry(seg.id.to_ascii_uppercase())
+            .or_insert_with(|| seg.id.clone());
+    }
+    collect(&mig.segment_groups, "", &mut out);
+    out
+}
+
+pub(crate) fn join_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{parent}/{child}")
+    }
+}
+
+struct StructureChecker<'m, 's> {
+    segments: &'s [OwnedSegment],
+    /// Index of the first input segment not yet matched to the tree.
+    next: usize,
+    known_tags: HashMap<String, String>,
+    diagnostics: Vec<StructureDiagnostic>,
+    placements: Vec<Option<SegmentPlacement<'m>>>,
+}
+
+impl<'m> StructureChecker<'m, '_> {
+    /// Find the input position of the next assembled segment and record the
+    /// MIG slot it fills.
+    ///
+    /// The tree is walked in consumption order, so every input segment passed
+    /// over on the way was skipped by the assembler and gets reported.
+    fn locate(
+        &mut self,
+        seg: &AssembledSegment,
+        group_path: &str,
+        variants: &[&'m [MigSegment]],
+    ) -> Option<usize> {
+        let offset = self.segments[self.next..]
+            .iter()
+            .position(|s| s.id == seg.tag && s.elements == seg.elements)?;
+        let found = self.next + offset;
+        for idx in self.next..found {
+            self.report_stray(idx);
+        }
+        self.next = found + 1;
+        self.placements[found] =
+            select_slot(variants, &self.segments[found]).map(|definition| SegmentPlacement {
+                group_path: group_path.to_string(),
+                definition,
+            });
+        Some(found)
+    }
+
+    /// Report an input segment that is not part of the assembled tree.
+    fn report_stray(&mut self, idx: usize) {
+        let tag = &self.segments[idx].id;
+        let diagnostic = match self.known_tags.get(&tag.to_ascii_uppercase()) {
+            Some(path) => StructureDiagnostic {
+                kind: StructureDiagnosticKind::OutOfOrderSegment,
+                segment_id: tag.clone(),
+                position: idx,
+                message: format!(
+                    "Segment '{tag}' at position {idx} is defined in the MIG at {path} \
+                     but is not allowed at this position"
+                ),
+                mig_path: Some(path.clone()),
+                location: None,
+                repair: None,
+            },
+            None => StructureDiagnostic {
+                kind: StructureDiagnosticKind::UnexpectedSegment,
+                segment_id: tag.clone(),
+                position: idx,
+                message: format!(
+                    "Segment '{tag}' at position {idx} was not consumed by MIG-guided assembly"
+                ),
+                mig_path: None,
+                location: None,
+                repair: None,
+            },
+        };
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Check that root segments appear in MIG order.
+    ///
+    /// Within groups the assembler only consumes slots in order, so anything
+    /// out of place ends up unconsumed. Root segments get a second chance
+    /// after the groups (for UNT and friends), which lets misplaced ones
+    /// through — catch those here.
+    fn check_root_order(&mut self, mig_segments: &[MigSegment], present: &[(&str, Option<usize>)]) {
+        let mut located: Vec<(usize, &str)> = present
+            .iter()
+            .filter_map(|(tag, pos)| pos.map(|p| (p, *tag)))
+            .collect();
+        located.sort_unstable();
+
+        let mut highest_slot = 0;
+        let mut previous: Option<&str> = None;
+        for (position, tag) in located {
+            let slots = || {
+                mig_segments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| s.id.eq_ignore_ascii_case(tag))
+                    .map(|(i, _)| i)
+            };
+            let Some(slot) = slots()
+                .find(|i| *i >= highest_slot)
+                .or_else(|| slots().next())
+            else {
+                continue;
+            };
+            if slot < highest_slot {
+                self.diagnostics.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::OutOfOrderSegment,
+                    segment_id: tag.to_string(),
+                    position,
+                    message: format!(
+                        "Segment '{tag}' at position {position} appears after '{}' \
+                         but the MIG defines it before",
+                        previous.unwrap_or_default()
+                    ),
+                    mig_path: Some(tag.to_string()),
+                    location: None,
+                    repair: None,
+                });
+                continue;
+            }
+            highest_slot = slot;
+            previous = Some(tag);
+        }
+    }
+
+    /// Walk all repetitions of an assembled group, checking each instance.
+    ///
+    /// Returns the input position of each repetition's entry segment.
+    fn walk_group(
+        &mut self,
+        rule: Option<&GroupRule<'m>>,
+        group: &AssembledGroup,
+        parent_path: &str,
+    ) -> Vec<Option<usize>> {
+        let path = join_path(parent_path, &group.group_id);
+        let defs = rule.map(|r| r.defs.as_slice()).unwrap_or_default();
+        let segment_variants: Vec<&'m [MigSegment]> =
+            defs.iter().map(|d| d.segments.as_slice()).collect();
+        let segment_rules = segment_rules(&segment_variants);
+        let group_rules = group_rules(
+            &defs
+                .iter()
+                .map(|d| d.nested_groups.as_slice())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut entry_positions = Vec::with_capacity(group.repetitions.len());
+        for instance in &group.repetitions {
+            let mut positions = Vec::with_capacity(instance.segments.len());
+            for seg in &instance.segments {
+                let pos = self.locate(seg, &path, &segment_variants);
+                positions.push((&*seg.tag, pos));
+            }
+            let anchor = positions
+                .first()
+                .and_then(|(_, p)| *p)
+                .unwrap_or(self.next.saturating_sub(1));
+            entry_positions.push(Some(anchor));
+
+            let mut child_positions = Vec::with_capacity(instance.child_groups.len());
+            for child in &instance.child_groups {
+                let child_rule = group_rules.iter().find(|r| r.id == child.group_id);
+                let reps = self.walk_group(child_rule, child, &path);
+                child_positions.push((child.group_id.as_str(), reps));
+            }
+
+            if rule.is_some() {
+                self.check_segments(&segment_rules, &positions, &path, anchor);
+                self.check_groups(&group_rules, &child_positions, &path, anchor);
+            }
+        }
+        entry_positions
+    }
+
+    /// Check segment occurrences of one instance (or the root) against rules.
+    fn check_segments(
+        &mut self,
+        rules: &[SegmentRule<'_>],
+        present: &[(&str, Option<usize>)],
+        path: &str,
+        anchor: usize,
+    ) {
+        for rule in rules {
+            let occurrences: Vec<usize> = present
+                .iter()
+                .filter(|(tag, _)| tag.eq_ignore_ascii_case(rule.tag))
+                .map(|(_, pos)| pos.unwrap_or(anchor))
+                .collect();
+            let seg_path = join_path(path, rule.tag);
+
+            if occurrences.len() < rule.required
+                && !(path.is_empty() && INTERCHANGE_SEGMENTS.contains(&rule.tag))
+            {
+                let message = if rule.required == 1 {
+                    format!("Mandatory segment {seg_path} is missing")
+                } else {
+                    format!(
+                        "Mandatory segment {seg_path} is missing: expected at least {} \
+                         occurrences, found {}",
+                        rule.required,
+                        occurrences.len()
+                    )
+                };
+                self.diagnostics.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::MissingRequiredSegment,
+                    segment_id: rule.tag.to_string(),
+                    position: anchor,
+                    message,
+                    mig_path: Some(seg_path.clone()),
+                    location: None,
+                    repair: None,
+                });
+            }
+
+            if occurrences.len() > rule.max_rep {
+                self.diagnostics.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::MaxRepetitionsExceeded,
+                    segment_id: rule.tag.to_string(),
+                    position: occurrences[rule.max_rep],
+                    message: format!(
+                        "Segment {seg_path} occurs {} times, MIG allows at most {}",
+                        occurrences.len(),
+                        rule.max_rep
+                    ),
+                    mig_path: Some(seg_path),
+                    location: None,
+                    repair: None,
+                });
+            }
+        }
+    }
+
+    /// Check group occurrences within one instance (or the root) against rules.
+    fn check_groups(
+        &mut self,
+        rules: &[GroupRule<'_>],
+        present: &[(&str, Vec<Option<usize>>)],
+        path: &str,
+        anchor: usize,
+    ) {
+        for rule in rules {
+            let repetitions: Vec<usize> = present
+                .iter()
+                .filter(|(id, _)| *id == rule.id)
+                .flat_map(|(_, reps)| reps.iter().map(|p| p.unwrap_or(anchor)))
+                .collect();
+            let group_path = join_path(path, rule.id);
+
+            if rule.required && repetitions.is_empty() {
+                self.diagnostics.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::MissingRequiredGroup,
+                    segment_id: rule.entry_tag.to_string(),
+                    position: anchor,
+                    message: format!("Mandatory segment group {group_path} is missing"),
+                    mig_path: Some(group_path.clone()),
+                    location: None,
+                    repair: None,
+                });
+            }
+
+            if repetitions.len() > rule.max_rep {
+                self.diagnostics.push(StructureDiagnostic {
+                    kind: StructureDiagnosticKind::GroupMaxRepetitionsExceeded,
+                    segment_id: rule.entry_tag.to_string(),
+                    position: repetitions[rule.max_rep],
+                    message: format!(
+                        "Segment group {group_path} occurs {} times, MIG allows at most {}",
+                        repetitions.len(),
+                        rule.max_rep
+                    ),
+                    mig_path: Some(group_path),
+                    location: None,
+                    repair: None,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::{Assembler, AssemblerConfig};
+    use crate::test_support::{make_mig_group, make_mig_segment};
+
+    fn seg(id: &str, qualifier: &str) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: vec![vec![qualifier.to_string()]],
+            segment_number: 0,
+            location: None,
+        }
+    }
+
+    fn optional(mut seg: MigSegment) -> MigSegment {
+        seg.status_spec = Some("C".to_string());
+        seg
+    }
+
+    fn mig(segments: Vec<MigSegment>, groups: Vec<MigSegmentGroup>) -> MigSchema {
+        MigSchema {
+            message_type: "UTILMD".to_string(),
+            variant: None,
+            version: "S2.1".to_string(),
+            publication_date: String::new(),
+            author: "BDEW".to_string(),
+            format_version: "FV2504".to_string(),
+            source_file: "test".to_string(),
+            segments,
+            segment_groups: groups,
+        }
+    }
+
+    fn check(mig: &MigSchema, segments: &[OwnedSegment]) -> Vec<StructureDiagnostic> {
+        Assembler::new(mig).assemble_with_diagnostics(segments).1
+    }
+
+    fn root(ids: &[&str]) -> Vec<MigSegment> {
+        ids.iter().map(|id| make_mig_segment(id)).collect()
+    }
+
+    #[test]
+    fn test_missing_mandatory_root_segment() {
+        let mig = mig(root(&["UNH", "BGM", "DTM", "UNT"]), vec![]);
+        let diagnostics = check(&mig, &[seg("UNH", "1"), seg("BGM", "E01"), seg("UNT", "3")]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            StructureDiagnosticKind::MissingRequiredSegment
+        );
+        assert_eq!(diagnostics[0].segment_id, "DTM");
+        assert_eq!(diagnostics[0].mig_path.as_deref(), Some("DTM"));
+    }
+
+    #[test]
+    fn test_interchange_segments_not_required() {
+        let mig = mig(root(&["UNB", "UNH", "BGM", "UNT", "UNZ"]), vec![]);
+        let diagnostics = check(&mig, &[seg("UNH", "1"), seg("BGM", "E01"), seg("UNT", "3")]);
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_missing_mandatory_group_and_nested_segment() {
+        let sg4 = make_mig_group("SG4", vec!["IDE", "STS"], vec![]);
+        let sg2 = make_mig_group("SG2", vec!["NAD"], vec![]);
+        let mig = mig(root(&["UNH", "UNT"]), vec![sg2, sg4]);
+
+        // SG2 absent, SG4 present without its mandatory STS
+        let diagnostics = check(&mig, &[seg("UNH", "1"), seg("IDE", "24"), seg("UNT", "3")]);
+
+        let missing_group = diagnostics
+            .iter()
+            .find(|d| d.kind == StructureDiagnosticKind::MissingRequiredGroup)
+            .expect("SG2 should be reported");
+        assert_eq!(missing_group.mig_path.as_deref(), Some("SG2"));
+        assert_eq!(missing_group.segment_id, "NAD");
+
+        let missing_seg = diagnostics
+            .iter()
+            .find(|d| d.kind == StructureDiagnosticKind::MissingRequiredSegment)
+            .expect("SG4/STS should be reported");
+        assert_eq!(missing_seg.mig_path.as_deref(), Some("SG4/STS"));
+        assert_eq!(missing_seg.position, 1, "anchored at the SG4 entry segment");
+    }
+
+    #[test]
+    fn test_segment_max_repetitions_exceeded() {
+        let mut sg2 = make_mig_group("SG2", vec!["NAD", "CTA"], vec![]);
+        sg2.segments[1].max_rep_spec = 2;
+        sg2.segments[1].max_rep_std = 2;
+        let mig = mig(root(&["UNH", "UNT"]), vec![sg2]);
+
+        let diagnostics = check(
+            &mig,
+            &[
+                seg("UNH", "1"),
+                seg("NAD", "MS"),
+                seg("CTA", "IC"),
+                seg("CTA", "IC"),
+                seg("CTA", "IC"),
+                seg("UNT", "6"),
+            ],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            StructureDiagnosticKind::MaxRepetitionsExceeded
+        );
+        assert_eq!(diagnostics[0].mig_path.as_deref(), Some("SG2/CTA"));
+        assert_eq!(diagnostics[0].position, 4, "first excess occurrence");
+    }
+
+    #[test]
+    fn test_group_max_repetitions_exceeded() {
+        let mut sg2 = make_mig_group("SG2", vec!["NAD"], vec![]);
+        sg2.max_rep_spec = 1;
+        sg2.max_rep_std = 1;
+        let mig = mig(root(&["UNH", "UNT"]), vec![sg2]);
+
+        let diagnostics = check(
+            &mig,
+            &[
+                seg("UNH", "1"),
+                seg("NAD", "MS"),
+                seg("NAD", "MR"),
+                seg("UNT", "4"),
+            ],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            StructureDiagnosticKind::GroupMaxRepetitionsExceeded
+        );
+        assert_eq!(diagnostics[0].mig_path.as_deref(), Some("SG2"));
+        assert_eq!(diagnostics[0].position, 2);
+    }
+
+    #[test]
+    fn test_out_of_order_segment() {
+        let mig = mig(
+            vec![
+                make_mig_segment("UNH"),
+                make_mig_segment("BGM"),
+                optional(make_mig_segment("DTM")),
+                make_mig_segment("UNT"),
+            ],
+            vec![],
+        );
+
+        // DTM is defined in the MIG but appears after UNT
+        let diagnostics = check(
+            &mig,
+            &[
+                seg("UNH", "1"),
+                seg("BGM", "E01"),
+                seg("UNT", "3"),
+                seg("DTM", "137"),
+            ],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            StructureDiagnosticKind::OutOfOrderSegment
+        );
+        assert_eq!(diagnostics[0].position, 3);
+        assert_eq!(diagnostics[0].mig_path.as_deref(), Some("DTM"));
+    }
+
+    #[test]
+    fn test_diagnostics_carry_input_location() {
+        let mig = mig(root(&["UNH", "BGM", "UNT"]), vec![]);
+        let segments =
+            crate::tokenize::parse_to_segments(b"UNH+1'\nBGM+E01'\nFTX+ACB'\nUNT+4+1'").unwrap();
+        let diagnostics = check(&mig, &segments);
+
+        let ftx = diagnostics.iter().find(|d| d.segment_id == "FTX").unwrap();
+        let location = ftx.location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (3, 1));
+        assert_eq!(location.byte_offset, 16);
+        assert!(ftx.to_string().contains("(line 3, column 1)"));
+    }
+
+    #[test]
+    fn test_skipped_segments_reported_as_unexpected() {
+        let sg2 = make_mig_group("SG2", vec!["NAD", "CTA"], vec![]);
+        let mig = mig(root(&["UNH", "UNT"]), vec![sg2]);
+        let assembler = Assembler::with_config(
+            &mig,
+            AssemblerConfig {
+                skip_unknown_segments: true,
+            },
+        );
+
+        // Skip mode swallows everything up to the next group entry, so end
+        // the input inside the group.
+        let (_, diagnostics) = assembler.assemble_with_diagnostics(&[
+            seg("UNH", "1"),
+            seg("NAD", "MS"),
+            seg("ZZZ", "x"),
+            seg("CTA", "IC"),
+        ]);
+
+        let unexpected: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.kind == StructureDiagnosticKind::UnexpectedSegment)
+            .collect();
+        assert_eq!(unexpected.len(), 1, "{diagnostics:#?}");
+        assert_eq!(unexpected[0].segment_id, "ZZZ");
+        assert_eq!(unexpected[0].position, 2);
+    }
+
+    #[test]
+    fn test_segment_required_only_if_every_variant_requires_it() {
+        // Two SG8 variants: only one of them has a mandatory RFF
+        let with_rff = make_mig_group("SG8", vec!["SEQ", "RFF"], vec![]);
+        let without_rff = make_mig_group("SG8", vec!["SEQ", "PIA"], vec![]);
+        let rules = segment_rules(&[
+            with_rff.segments.as_slice(),
+            without_rff.segments.as_slice(),
+        ]);
+
+        let rff = rules.iter().find(|r| r.tag == "RFF").unwrap();
+        assert_eq!(rff.required, 0);
+        let seq = rules.iter().find(|r| r.tag == "SEQ").unwrap();
+        assert_eq!(seq.required, 1);
+    }
+
+    #[test]
+    fn test_place_segments_picks_slot_by_qualifier() {
+        use mig_types::schema::common::CodeDefinition;
+        use mig_types::schema::mig::{MigComposite, MigDataElement};
+
+        fn rff(qualifier: &str, format: &str) -> MigSegment {
+            let mut slot = optional(make_mig_segment("RFF"));
+            let element =
+                |id: &str, position: usize, codes: Vec<&str>, format: &str| MigDataElement {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    description: None,
+                    status_std: Some("M".to_string()),
+                    status_spec: Some("M".to_string()),
+                    format_std: Some(format.to_string()),
+                    format_spec: None,
+                    codes: codes
+                        .into_iter()
+                        .map(|c| CodeDefinition {
+                            value: c.to_string(),
+                            name: c.to_string(),
+                            description: None,
+                        })
+                        .collect(),
+                    position,
+                };
+            slot.composites.push(MigComposite {
+                id: "C506".to_string(),
+                name: "Referenz".to_string(),
+                description: None,
+                status_std: Some("M".to_string()),
+                status_spec: Some("M".to_string()),
+                data_elements: vec![
+                    element("1153", 0, vec![qualifier], "an..3"),
+                    element("1154", 1, vec![], format),
+                ],
+                position: 0,
+            });
+            slot
+        }
+
+        let mut sg6 = make_mig_group("SG6", vec![], vec![]);
+        sg6.segments = vec![rff("Z13", "an..35"), rff("TN", "an..70")];
+        let mig = mig(root(&["UNH", "UNT"]), vec![sg6]);
+        let segments = vec![
+            seg("UNH", "1"),
+            OwnedSegment {
+                id: "RFF".to_string(),
+                elements: vec![vec!["TN".to_string(), "ABC".to_string()]],
+                segment_number: 0,
+                location: None,
+            },
+            seg("UNT", "3"),
+        ];
+
+        let (tree, _) = Assembler::new(&mig).assemble_with_diagnostics(&segments);
+        let placements = place_segments(&mig, &tree, &segments);
+
+        assert_eq!(placements.len(), 3);
+        let rff = placements[1].as_ref().expect("RFF is placed");
+        assert_eq!(rff.group_path, "SG6");
+        assert_eq!(
+            rff.definition.composites[0].data_elements[1]
+                .format_std
+                .as_deref(),
+            Some("an..70")
+        );
+        assert_eq!(placements[0].as_ref().unwrap().group_path, "");
+    }
+}
@@ -1,15 +1,21 @@
 //! EDIFACT tokenization helper — collects segments from input into a Vec.
 //!
-//! The `edifact-parser` crate uses an event-driven (SAX-style) API.
-//! This module provides a convenience function that collects all parsed
-//! segments into an owned `Vec<OwnedSegment>` for two-pass processing.
+//! This module provides a convenience function that collects the segments
+//! read by `edifact-parser`'s [`EdifactReader`] into an owned
+//! `Vec<OwnedSegment>` for two-pass processing.
 //!
 //! `OwnedSegment` itself lives in `mig-types::segment` — re-exported here
 //! for backward compatibility.
 
+use std::borrow::Cow;
+use std::ops::Range;
 use std::sync::Arc;
 
-use edifact_types::{Control, EdifactDelimiters, RawSegment};
+use edifact_parser::{EdifactReader, Event};
+use edifact_types::{RawSegment, Span};
+use mig_types::segment::{SegmentLocation, SourceSpan};
+
+use crate::assembler::AssembledSegment;
 
 // Re-export OwnedSegment from mig-types so existing `use crate::tokenize::OwnedSegment` paths work.
 pub use mig_types::segment::OwnedSegment;
@@ -19,6 +25,9 @@ pub use mig_types::segment::OwnedSegment;
 pub struct MessageChunk {
     /// Interchange envelope segments (UNA, UNB) — shared across all messages via `Arc`.
     pub envelope: Arc<Vec<OwnedSegment>>,
+    /// The UNG segment of the functional group containing the message, if
+    /// any — shared across the messages of the group via `Arc`.
+    pub group: Option<Arc<OwnedSegment>>,
     /// The UNH segment itself.
     pub unh: OwnedSegment,
     /// Segments between UNH and UNT (exclusive of both).
@@ -30,6 +39,8 @@ pub struct MessageChunk {
 impl MessageChunk {
     /// Reconstruct the full segment list for this message (envelope + UNH + body + UNT).
     ///
+    /// The UNG of a functional group is not included.
+    ///
     /// Note: only use this when the MIG schema includes envelope segments (UNA, UNB).
     /// For MIG schemas that start at UNH, use `message_segments()` instead.
     pub fn all_segments(&self) -> Vec<OwnedSegment> {
@@ -69,11 +80,25 @@ impl MessageChunk {
     }
 }
 
+/// A UNG...UNE functional group within an interchange.
+#[derive(Debug, Clone)]
+pub struct FunctionalGroup {
+    /// The UNG segment, shared with the group's [`MessageChunk`]s.
+    pub ung: Arc<OwnedSegment>,
+    /// The UNE segment (group trailer), if present.
+    pub une: Option<OwnedSegment>,
+    /// Indices of the group's messages in [`InterchangeChunks::messages`].
+    pub messages: Range<usize>,
+}
+
 /// A complete EDIFACT interchange split into per-message chunks.
 #[derive(Debug, Clone)]
 pub struct InterchangeChunks {
     /// Interchange envelope segments (UNA, UNB) — shared across all messages.
     pub envelope: Vec<OwnedSegment>,
+    /// One entry per UNG/UNE pair, in input order; empty if the interchange
+    /// has no functional groups.
+    pub groups: Vec<FunctionalGroup>,
     /// One entry per UNH/UNT pair.
     pub messages: Vec<MessageChunk>,
     /// The UNZ segment (interchange trailer), if present.
@@ -85,10 +110,11 @@ pub struct InterchangeChunks {
 /// The EDIFACT tokenizer preserves escape sequences in borrowed slices (e.g., `?+`
 /// stays as `?+`). When converting to owned `OwnedSegment` strings, we strip the
 /// release characters so values are stored clean (e.g., `+`). The renderer then
-/// re-escapes when writing EDIFACT output.
-fn unescape_edifact(value: &str, release: u8) -> String {
+/// re-escapes when writing EDIFACT output. Values without a release character
+/// are returned borrowed.
+fn unescape_edifact(value: &str, release: u8) -> Cow<'_, str> {
     if !value.as_bytes().contains(&release) {
-        return value.to_string();
+        return Cow::Borrowed(value);
     }
     let bytes = value.as_bytes();
     let mut result = String::with_capacity(value.len());
@@ -103,46 +129,47 @@ fn unescape_edifact(value: &str, release: u8) -> String {
         }
         i += 1;
     }
-    result
+    Cow::Owned(result)
 }
 
-/// Handler that collects all segments into owned copies.
-struct SegmentCollector {
-    segments: Vec<OwnedSegment>,
-    /// Release character for unescaping component values.
-    release: u8,
-}
-
-impl edifact_parser::EdifactHandler for SegmentCollector {
-    fn on_segment(&mut self, segment: &RawSegment<'_>) -> Control {
-        let release = self.release;
-        self.segments.push(OwnedSegment {
-            id: segment.id.to_string(),
-            elements: segment
-                .elements
-                .iter()
-                .map(|e| e.iter().map(|c| unescape_edifact(c, release)).collect())
-                .collect(),
-            segment_number: segment.position.segment_number,
-        });
-        Control::Continue
-    }
-
-    fn on_delimiters(&mut self, delimiters: &EdifactDelimiters, _explicit_una: bool) {
-        self.release = delimiters.release;
-    }
-
-    fn on_interchange_start(&mut self, _unb: &RawSegment<'_>) -> Control {
-        Control::Continue
+/// Copy a borrowed segment into an `OwnedSegment`, unescaping component values.
+///
+/// The spans in its location still cover the escaped input text.
+pub(crate) fn to_owned_segment(segment: &RawSegment<'_>, release: u8) -> OwnedSegment {
+    OwnedSegment {
+        id: segment.id.to_string(),
+        elements: segment
+            .elements
+            .iter()
+            .map(|e| {
+                e.iter()
+                    .map(|c| unescape_edifact(c, release).into_owned())
+                    .collect()
+            })
+            .collect(),
+        segment_number: segment.position.segment_number,
+        location: Some(segment_location(segment)),
     }
+}
 
-    fn on_message_start(&mut self, _unh: &RawSegment<'_>) -> Control {
-        Control::Continue
+/// The location of a parsed segment in the input.
+fn segment_location(segment: &RawSegment<'_>) -> SegmentLocation {
+    let span = |span: Span| SourceSpan {
+        start: span.start,
+        end: span.end,
+    };
+    SegmentLocation {
+        byte_offset: segment.position.byte_offset,
+        line: segment.position.line,
+        column: segment.position.column,
+        message_number: segment.position.message_number,
+        span: span(segment.span),
+        components: segment
+            .component_spans
+            .iter()
+            .map(|e| e.iter().map(|&c| span(c)).collect())
+            .collect(),
     }
-
-    fn on_message_end(&mut self, _unt: &RawSegment<'_>) {}
-
-    fn on_interchange_end(&mut self, _unz: &RawSegment<'_>) {}
 }
 
 /// Parse an EDIFACT message into a list of owned segments.
@@ -151,85 +178,225 @@ impl edifact_parser::EdifactHandler for SegmentCollector {
 /// `edifact-parser` to tokenize the input and collects all segments
 /// into owned data structures suitable for random access.
 pub fn parse_to_segments(input: &[u8]) -> Result<Vec<OwnedSegment>, crate::AssemblyError> {
-    let mut collector = SegmentCollector {
-        segments: Vec::new(),
-        release: EdifactDelimiters::default().release,
-    };
-    edifact_parser::EdifactStreamParser::parse(input, &mut collector)
+    let text = EdifactReader::decode(input)
         .map_err(|e| crate::AssemblyError::ParseError(e.to_string()))?;
-    Ok(collector.segments)
+    parse_str_to_segments(&text)
+}
+
+/// Parse EDIFACT text into a list of owned segments.
+///
+/// Like [`parse_to_segments`], but for input that is already decoded (e.g.
+/// received as a JSON string), so the UNB character set is not applied.
+pub fn parse_str_to_segments(input: &str) -> Result<Vec<OwnedSegment>, crate::AssemblyError> {
+    let reader = EdifactReader::new(input);
+    let release = reader.delimiters().release;
+    reader
+        .filter_map(|event| match event {
+            Ok(Event::Segment(segment)) => Some(Ok(to_owned_segment(&segment, release))),
+            Ok(_) => None,
+            Err(e) => Some(Err(crate::AssemblyError::ParseError(e.to_string()))),
+        })
+        .collect()
+}
+
+/// Parse EDIFACT text into segments that borrow from `input`.
+///
+/// Unlike [`parse_str_to_segments`], tags and component values are not
+/// copied: only values that contain release characters are unescaped into
+/// owned strings. Feed the result to
+/// [`Assembler::assemble_segments`](crate::assembler::Assembler::assemble_segments)
+/// to assemble a tree that still borrows from `input`. Byte input must be
+/// decoded first (see [`EdifactReader::decode`]).
+pub fn parse_borrowed_segments(
+    input: &str,
+) -> Result<Vec<AssembledSegment<'_>>, crate::AssemblyError> {
+    let reader = EdifactReader::new(input);
+    let release = reader.delimiters().release;
+    reader
+        .filter_map(|event| match event {
+            Ok(Event::Segment(segment)) => Some(Ok(AssembledSegment {
+                tag: Cow::Borrowed(segment.id),
+                elements: segment
+                    .elements
+                    .iter()
+                    .map(|e| e.iter().map(|c| unescape_edifact(c, release)).collect())
+                    .collect(),
+                location: Some(segment_location(&segment)),
+            })),
+            Ok(_) => None,
+            Err(e) => Some(Err(crate::AssemblyError::ParseError(e.to_string()))),
+        })
+        .collect()
 }
 
 /// Split a flat segment list into per-message chunks at UNH/UNT boundaries.
 ///
 /// Each message gets a copy of the interchange envelope (UNB and any segments
-/// before the first UNH) so it can be independently assembled.
+/// before the first UNH) so it can be independently assembled. UNG/UNE
+/// functional groups are recorded in [`InterchangeChunks::groups`] and are
+/// not part of the envelope.
+///
+/// For large interchanges, [`MessageChunkReader`](crate::streaming::MessageChunkReader)
+/// produces the same chunks one at a time without collecting all segments first.
 ///
 /// # Errors
 ///
-/// Returns an error if no UNH/UNT pairs are found.
+/// Returns an error if no UNH/UNT pairs are found, or if the input holds
+/// several interchanges (use [`split_interchanges`] for those).
 pub fn split_messages(
     segments: Vec<OwnedSegment>,
 ) -> Result<InterchangeChunks, crate::AssemblyError> {
-    let mut envelope: Vec<OwnedSegment> = Vec::with_capacity(4);
-    // Collect (unh, body, unt) tuples first, then wrap with shared envelope Arc.
-    let mut raw_messages: Vec<(OwnedSegment, Vec<OwnedSegment>, OwnedSegment)> = Vec::new();
-    let mut unz: Option<OwnedSegment> = None;
+    let mut interchanges = split_interchanges(segments)?;
+    if interchanges.len() > 1 {
+        return Err(crate::AssemblyError::ParseError(format!(
+            "Input contains {} interchanges; use split_interchanges",
+            interchanges.len()
+        )));
+    }
+    Ok(interchanges.remove(0))
+}
 
-    // State machine
-    let mut current_unh: Option<OwnedSegment> = None;
-    let mut current_body: Vec<OwnedSegment> = Vec::with_capacity(32);
-    let mut seen_first_unh = false;
+/// Split a flat segment list into interchanges at UNB boundaries, and each
+/// interchange into per-message chunks as [`split_messages`] does.
+///
+/// Input that starts without a UNB forms one interchange with an envelope
+/// of whatever precedes the first UNH.
+///
+/// # Errors
+///
+/// Returns an error if no UNH/UNT pairs are found in the whole input.
+pub fn split_interchanges(
+    segments: Vec<OwnedSegment>,
+) -> Result<Vec<InterchangeChunks>, crate::AssemblyError> {
+    let mut interchanges = Vec::new();
+    let mut current = InterchangeSplitter::default();
 
     for seg in segments {
+        // A UNB inside an open message is message content, not a new interchange
+        if seg.is("UNB") && current.has_content() && current.unh.is_none() {
+            interchanges.push(std::mem::take(&mut current).finish());
+        }
+        current.push(seg);
+    }
+    if current.has_content() {
+        interchanges.push(current.finish());
+    }
+
+    if interchanges.iter().all(|i| i.messages.is_empty()) {
+        return Err(crate::AssemblyError::ParseError(
+            "No UNH/UNT message pairs found in interchange".to_string(),
+        ));
+    }
+    Ok(interchanges)
+}
+
+/// Collects the segments of one interchange for [`split_interchanges`].
+#[derive(Default)]
+struct InterchangeSplitter {
+    envelope: Vec<OwnedSegment>,
+    groups: Vec<FunctionalGroup>,
+    /// Completed messages as (group, unh, body, unt); wrapped with the
+    /// shared envelope `Arc` in `finish`.
+    messages: Vec<(
+        Option<Arc<OwnedSegment>>,
+        OwnedSegment,
+        Vec<OwnedSegment>,
+        OwnedSegment,
+    )>,
+    unz: Option<OwnedSegment>,
+    /// Whether the group at the end of `groups` is still open.
+    group_open: bool,
+    unh: Option<OwnedSegment>,
+    body: Vec<OwnedSegment>,
+    seen_first_unh: bool,
+}
+
+impl InterchangeSplitter {
+    fn has_content(&self) -> bool {
+        !self.envelope.is_empty()
+            || self.seen_first_unh
+            || !self.groups.is_empty()
+            || self.unz.is_some()
+    }
+
+    fn push(&mut self, seg: OwnedSegment) {
         let id_upper = seg.id.to_uppercase();
         match id_upper.as_str() {
+            "UNG" if self.unh.is_none() => {
+                self.close_group(None);
+                let start = self.messages.len();
+                self.groups.push(FunctionalGroup {
+                    ung: Arc::new(seg),
+                    une: None,
+                    messages: start..start,
+                });
+                self.group_open = true;
+            }
+            "UNE" if self.unh.is_none() => self.close_group(Some(seg)),
             "UNH" => {
-                seen_first_unh = true;
-                current_unh = Some(seg);
-                current_body.clear();
+                self.seen_first_unh = true;
+                self.unh = Some(seg);
+                self.body.clear();
             }
             "UNT" => {
-                if let Some(unh) = current_unh.take() {
-                    raw_messages.push((unh, std::mem::take(&mut current_body), seg));
+                if let Some(unh) = self.unh.take() {
+                    let group = self
+                        .group_open
+                        .then(|| self.groups.last_mut())
+                        .flatten()
+                        .map(|group| {
+                            group.messages.end += 1;
+                            Arc::clone(&group.ung)
+                        });
+                    self.messages
+                        .push((group, unh, std::mem::take(&mut self.body), seg));
                 }
             }
             "UNZ" => {
-                unz = Some(seg);
+                self.close_group(None);
+                self.unz = Some(seg);
             }
             _ => {
-                if seen_first_unh {
-                    current_body.push(seg);
+                if self.seen_first_unh {
+                    self.body.push(seg);
                 } else {
-                    envelope.push(seg);
+                    self.envelope.push(seg);
                 }
             }
         }
     }
 
-    if raw_messages.is_empty() {
-        return Err(crate::AssemblyError::ParseError(
-            "No UNH/UNT message pairs found in interchange".to_string(),
-        ));
+    fn close_group(&mut self, une: Option<OwnedSegment>) {
+        if self.group_open {
+            if let Some(group) = self.groups.last_mut() {
+                group.une = une;
+            }
+        }
+        self.group_open = false;
     }
 
-    // Share the envelope via Arc across all messages to avoid N clones.
-    let envelope_arc = Arc::new(envelope);
-    let messages = raw_messages
-        .into_iter()
-        .map(|(unh, body, unt)| MessageChunk {
-            envelope: Arc::clone(&envelope_arc),
-            unh,
-            body,
-            unt,
-        })
-        .collect();
-
-    Ok(InterchangeChunks {
-        envelope: (*envelope_arc).clone(),
-        messages,
-        unz,
-    })
+    fn finish(self) -> InterchangeChunks {
+        // Share the envelope via Arc across all messages to avoid N clones.
+        let envelope_arc = Arc::new(self.envelope);
+        let messages = self
+            .messages
+            .into_iter()
+            .map(|(group, unh, body, unt)| MessageChunk {
+                envelope: Arc::clone(&envelope_arc),
+                group,
+                unh,
+                body,
+                unt,
+            })
+            .collect();
+
+        InterchangeChunks {
+            envelope: (*envelope_arc).clone(),
+            groups: self.groups,
+            messages,
+            unz: self.unz,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -250,6 +417,38 @@ mod tests {
         assert!(segments[4].is("UNZ"));
     }
 
+    #[test]
+    fn test_parse_str_to_segments_keeps_decoded_text() {
+        let input = "UNB+UNOC:3'NAD+Z09+Müller'";
+        assert_eq!(
+            parse_str_to_segments(input).unwrap()[1].get_element(1),
+            "Müller"
+        );
+        // As bytes, the UTF-8 encoding of ü is read as two ISO-8859-1 characters
+        assert_eq!(
+            parse_to_segments(input.as_bytes()).unwrap()[1].get_element(1),
+            "MÃ¼ller"
+        );
+    }
+
+    #[test]
+    fn test_parse_borrowed_segments_borrows_unless_unescaped() {
+        let input = "UNA:+.? 'UNH+001+UTILMD:D:11A'FTX+ACB+++Preis?: 10?+5'UNT+3+001'";
+        let segments = parse_borrowed_segments(input).unwrap();
+
+        let ftx = &segments[1];
+        assert!(matches!(ftx.tag, Cow::Borrowed("FTX")));
+        assert!(matches!(ftx.elements[0][0], Cow::Borrowed("ACB")));
+        assert!(matches!(&ftx.elements[3][0], Cow::Owned(v) if v == "Preis: 10+5"));
+        assert_eq!(
+            ftx.location.as_ref().map(|l| l.byte_offset),
+            parse_str_to_segments(input).unwrap()[1]
+                .location
+                .as_ref()
+                .map(|l| l.byte_offset)
+        );
+    }
+
     #[test]
     fn test_parse_to_segments_element_access() {
         let input = b"UNA:+.? 'UNB+UNOC:3'UNH+001+UTILMD:D:11A'BGM+E03+DOC001'UNT+2+001'UNZ+1'";
@@ -277,16 +476,19 @@ mod tests {
     fn test_message_chunk_struct_exists() {
         let chunk = MessageChunk {
             envelope: Arc::new(vec![]),
+            group: None,
             unh: OwnedSegment {
                 id: "UNH".to_string(),
                 elements: vec![],
                 segment_number: 0,
+                location: None,
             },
             body: vec![],
             unt: OwnedSegment {
                 id: "UNT".to_string(),
                 elements: vec![],
                 segment_number: 1,
+                location: None,
             },
         };
         assert_eq!(chunk.unh.id, "UNH");
@@ -299,6 +501,7 @@ mod tests {
     fn test_interchange_chunks_struct_exists() {
         let chunks = InterchangeChunks {
             envelope: vec![],
+            groups: vec![],
             messages: vec![],
             unz: None,
         };
@@ -380,6 +583,62 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_split_messages_records_functional_groups() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+REF'\
+UNG+UTILMD+S+R+210101:1200+G1'UNH+001+UTILMD'UNT+2+001'UNH+002+UTILMD'UNT+2+002'UNE+2+G1'\
+UNG+UTILMD+S+R+210101:1200+G2'UNH+003+UTILMD'BGM+E01'UNT+3+003'UNE+1+G2'UNZ+2+REF'";
+        let chunks = split_messages(parse_to_segments(input).unwrap()).unwrap();
+
+        // UNG/UNE are neither envelope nor message body
+        assert_eq!(chunks.envelope.len(), 1);
+        assert_eq!(chunks.messages[2].body.len(), 1);
+
+        assert_eq!(chunks.groups.len(), 2);
+        assert_eq!(chunks.groups[0].messages, 0..2);
+        assert_eq!(chunks.groups[1].messages, 2..3);
+        assert_eq!(chunks.groups[1].ung.get_element(4), "G2");
+        assert_eq!(chunks.groups[1].une.as_ref().unwrap().get_element(1), "G2");
+        let group = chunks.messages[1].group.as_ref().unwrap();
+        assert!(Arc::ptr_eq(group, &chunks.groups[0].ung));
+    }
+
+    #[test]
+    fn test_split_interchanges_at_each_unb() {
+        let input = b"UNB+UNOC:3+S+R+210101:1200+IC1'UNH+001+UTILMD'UNT+2+001'UNZ+1+IC1'\
+UNB+UNOC:3+S+R+210101:1200+IC2'UNH+002+UTILMD'UNT+2+002'UNH+003+UTILMD'UNT+2+003'UNZ+2+IC2'";
+        let segments = parse_to_segments(input).unwrap();
+        let interchanges = split_interchanges(segments.clone()).unwrap();
+
+        assert_eq!(interchanges.len(), 2);
+        assert_eq!(interchanges[0].envelope[0].get_element(4), "IC1");
+        assert_eq!(interchanges[0].messages.len(), 1);
+        assert_eq!(interchanges[1].envelope[0].get_element(4), "IC2");
+        assert_eq!(interchanges[1].messages.len(), 2);
+        assert_eq!(interchanges[1].unz.as_ref().unwrap().get_element(1), "IC2");
+        assert_eq!(
+            interchanges[1].messages[0].envelope[0].get_element(4),
+            "IC2"
+        );
+        assert!(interchanges.iter().all(|i| i.groups.is_empty()));
+
+        // A single-interchange split refuses to flatten them
+        assert!(split_messages(segments).is_err());
+    }
+
+    #[test]
+    fn test_unb_inside_message_is_message_content() {
+        let input =
+            b"UNB+UNOC:3+S+R+210101:1200+IC1'UNH+001+MSCONS'UNB+UNOC:3+X+X'UNT+3+001'UNZ+1+IC1'";
+        let segments = parse_to_segments(input).unwrap();
+        let interchanges = split_interchanges(segments).unwrap();
+
+        assert_eq!(interchanges.len(), 1);
+        let body = &interchanges[0].messages[0].body;
+        assert_eq!(body.len(), 1);
+        assert!(body[0].is("UNB"));
+    }
+
     #[test]
     fn test_owned_segment_is_case_insensitive() {
         let input = b"UNA:+.? 'UNB+UNOC:3'UNZ+0'";
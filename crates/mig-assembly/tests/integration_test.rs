@@ -4,7 +4,7 @@
 
 use mig_assembly::assembler::Assembler;
 use mig_assembly::parsing::parse_mig;
-use mig_assembly::pid_detect::detect_pid;
+use mig_assembly::pid_detect::{detect_pid, PidDetector, PidSignature};
 use mig_assembly::test_support::{make_mig_group, make_mig_segment};
 use mig_assembly::tokenize::parse_to_segments;
 use mig_types::schema::mig::{MigSchema, MigSegmentGroup};
@@ -391,3 +391,121 @@ fn test_assemble_all_real_fixtures_with_real_mig() {
         );
     }
 }
+
+const PID_SCHEMA_BASE: &str = "../mig-types/src/generated";
+
+#[test]
+fn test_pid_detector_recognises_every_message_type() {
+    let base = Path::new(PID_SCHEMA_BASE);
+    if !base.exists() {
+        eprintln!("PID schema dir not found, skipping");
+        return;
+    }
+
+    for fv in ["fv2504", "fv2510", "fv2604"] {
+        let mut detector = PidDetector::new();
+        let mut schemas = Vec::new();
+        for entry in std::fs::read_dir(base.join(fv)).unwrap().flatten() {
+            let pids_dir = entry.path().join("pids");
+            if !pids_dir.is_dir() {
+                continue;
+            }
+            detector.add_schema_dir(&pids_dir).unwrap();
+            // Take the first schema of each message type as a probe
+            let first = std::fs::read_dir(&pids_dir)
+                .unwrap()
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.to_string_lossy().ends_with("_schema.json"))
+                .filter_map(|p| {
+                    let schema: serde_json::Value =
+                        serde_json::from_str(&std::fs::read_to_string(&p).ok()?).ok()?;
+                    PidSignature::from_schema(&schema)
+                })
+                .min_by(|a, b| a.pid.cmp(&b.pid));
+            schemas.extend(first);
+        }
+
+        for signature in schemas {
+            let message_type = signature.message_type.clone().unwrap_or_default();
+            let association = signature.association_code.clone().unwrap_or_default();
+            let input = format!(
+                "UNA:+.? 'UNB+UNOC:3+SENDER+RECEIVER+250101:1200+REF001'\
+                 UNH+MSG001+{message_type}:D:11A:UN:{association}'RFF+Z13:{}'\
+                 UNT+3+MSG001'UNZ+1+REF001'",
+                signature.pid
+            );
+            let segments = parse_to_segments(input.as_bytes()).unwrap();
+            let candidates = detector.candidates(&segments);
+            assert_eq!(
+                candidates.first().map(|c| c.pid.as_str()),
+                Some(signature.pid.as_str()),
+                "{fv} {message_type}: best candidate should be {}",
+                signature.pid
+            );
+            assert_eq!(detector.detect(&segments).unwrap(), signature.pid);
+        }
+    }
+}
+
+const GENERATED_FIXTURE_BASE: &str = "../../fixtures/generated";
+
+#[test]
+fn test_pid_detector_on_generated_fixtures() {
+    let fixture_base = Path::new(GENERATED_FIXTURE_BASE);
+    let schema_base = Path::new(PID_SCHEMA_BASE);
+    if !fixture_base.exists() || !schema_base.exists() {
+        eprintln!("Generated fixtures or PID schemas not found, skipping");
+        return;
+    }
+
+    let mut total = 0;
+    let mut failures = Vec::new();
+    for fv_entry in std::fs::read_dir(fixture_base).unwrap().flatten() {
+        let fv = fv_entry.file_name().to_string_lossy().to_string();
+        let mut detector = PidDetector::new();
+        for msg_entry in std::fs::read_dir(schema_base.join(&fv)).unwrap().flatten() {
+            let pids_dir = msg_entry.path().join("pids");
+            if pids_dir.is_dir() {
+                detector.add_schema_dir(&pids_dir).unwrap();
+            }
+        }
+
+        for msg_entry in std::fs::read_dir(fv_entry.path()).unwrap().flatten() {
+            for file in std::fs::read_dir(msg_entry.path()).unwrap().flatten() {
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("edi") {
+                    continue;
+                }
+                let expected = path.file_stem().unwrap().to_string_lossy().to_string();
+                if !expected.chars().all(|c| c.is_ascii_digit()) {
+                    continue; // APERAK/CONTRL have no PIDs
+                }
+                let content = std::fs::read(&path).unwrap();
+                let segments = parse_to_segments(&content).unwrap();
+                let chunks = mig_assembly::split_messages(segments).unwrap();
+                total += 1;
+                match detector.detect(&chunks.messages[0].message_segments()) {
+                    Ok(pid) if pid == expected => {}
+                    other => failures.push(format!("{fv}/{expected}: {other:?}")),
+                }
+            }
+        }
+    }
+
+    // Some generated fixtures carry the RFF+Z13 of another PID or placeholder
+    // values in code elements; the detector rightly rejects those.
+    let detected = total - failures.len();
+    eprintln!("PID detector: {detected}/{total} fixtures detected");
+    for failure in &failures {
+        eprintln!("  {failure}");
+    }
+    if total > 0 {
+        let rate = detected as f64 / total as f64;
+        assert!(
+            rate > 0.95,
+            "PID detector rate too low: {detected}/{total} ({:.1}%)",
+            rate * 100.0
+        );
+    }
+}
@@ -7,7 +7,10 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::error::MappingError;
 use crate::path_resolver::PathResolver;
+use crate::predicate::Predicate;
+use crate::transform::{TransformChain, TransformError};
 
 /// Root mapping definition — one per TOML file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +21,10 @@ pub struct MappingDefinition {
     pub fields: IndexMap<String, FieldMapping>,
     pub companion_fields: Option<IndexMap<String, FieldMapping>>,
     pub complex_handlers: Option<Vec<ComplexHandlerRef>>,
+    /// Path of the TOML file this definition was loaded from. Set by the
+    /// loader (not read from the TOML itself) and reported in mapping traces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
 }
 
 /// Metadata about the entity being mapped.
@@ -51,7 +58,11 @@ pub enum FieldMapping {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructuredFieldMapping {
     pub target: String,
+    /// Named value transform(s) applied in both directions, e.g. `"dtm_iso8601"`
+    /// or `"trim | scale(3)"`. See [`crate::transform`] for the library.
     pub transform: Option<String>,
+    /// Predicate over sibling fields that must hold for this field to be mapped,
+    /// e.g. `"rff.0.0 == 'Z19'"`. See [`crate::predicate`] for the syntax.
     pub when: Option<String>,
     pub default: Option<String>,
     /// Bidirectional enum translation map (EDIFACT value → BO4E value).
@@ -79,20 +90,89 @@ impl MappingDefinition {
             self.meta.discriminator = Some(resolver.resolve_discriminator(disc));
         }
 
-        // Normalize field keys
-        self.fields = self
-            .fields
-            .iter()
-            .map(|(k, v)| (resolver.resolve_path(k), v.clone()))
-            .collect();
+        // Normalize field keys (and paths inside `when` predicates)
+        self.fields = normalize_field_map(&self.fields, resolver);
 
         // Normalize companion_fields keys
         if let Some(ref cf) = self.companion_fields {
-            self.companion_fields = Some(
-                cf.iter()
-                    .map(|(k, v)| (resolver.resolve_path(k), v.clone()))
-                    .collect(),
-            );
+            self.companion_fields = Some(normalize_field_map(cf, resolver));
         }
     }
+
+    /// Check that every `transform` and `when` in this definition parses.
+    ///
+    /// Called by [`crate::MappingEngine::load`] so that typos in TOML files
+    /// surface at load time rather than silently passing values through.
+    pub fn validate(&self, file: &str) -> Result<(), MappingError> {
+        validate_field_map(&self.fields, file)?;
+        if let Some(ref cf) = self.companion_fields {
+            validate_field_map(cf, file)?;
+        }
+        Ok(())
+    }
+}
+
+fn normalize_field_map(
+    fields: &IndexMap<String, FieldMapping>,
+    resolver: &PathResolver,
+) -> IndexMap<String, FieldMapping> {
+    fields
+        .iter()
+        .map(|(k, v)| {
+            let v = match v {
+                FieldMapping::Structured(s) => {
+                    let mut s = s.clone();
+                    if let Some(ref when) = s.when {
+                        if let Ok(pred) = Predicate::parse(when) {
+                            s.when =
+                                Some(pred.map_paths(&|p| resolver.resolve_path(p)).to_string());
+                        }
+                    }
+                    FieldMapping::Structured(s)
+                }
+                FieldMapping::Nested(inner) => {
+                    FieldMapping::Nested(normalize_field_map(inner, resolver))
+                }
+                other => other.clone(),
+            };
+            (resolver.resolve_path(k), v)
+        })
+        .collect()
+}
+
+fn validate_field_map(
+    fields: &IndexMap<String, FieldMapping>,
+    file: &str,
+) -> Result<(), MappingError> {
+    for mapping in fields.values() {
+        match mapping {
+            FieldMapping::Structured(s) => {
+                if let Some(ref spec) = s.transform {
+                    TransformChain::parse(spec).map_err(|e| match e {
+                        TransformError::Unknown(name) => MappingError::UnknownTransform {
+                            name,
+                            file: file.to_string(),
+                        },
+                        TransformError::InvalidArguments { reason, .. } => {
+                            MappingError::InvalidTransform {
+                                spec: spec.clone(),
+                                file: file.to_string(),
+                                reason,
+                            }
+                        }
+                    })?;
+                }
+                if let Some(ref when) = s.when {
+                    Predicate::parse(when).map_err(|reason| MappingError::InvalidCondition {
+                        expression: when.clone(),
+                        file: file.to_string(),
+                        reason,
+                    })?;
+                }
+            }
+            FieldMapping::Nested(inner) => validate_field_map(inner, file)?,
+            FieldMapping::Simple(_) => {}
+        }
+    }
+    Ok(())
 }
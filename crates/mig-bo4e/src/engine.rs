@@ -3,18 +3,25 @@
 //! Supports nested group paths (e.g., "SG4.SG5") for navigating the assembled tree
 //! and provides `map_forward` / `map_reverse` for full entity conversion.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
 use mig_assembly::assembler::{
-    AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
+    owned_to_assembled, AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
 };
+use mig_assembly::query::{Predicate as QueryPredicate, Query, QueryMatch, Step};
 use mig_types::schema::mig::MigSchema;
 use mig_types::segment::OwnedSegment;
 
 use crate::definition::{FieldMapping, MappingDefinition};
 use crate::error::MappingError;
+use crate::handlers::HandlerRegistry;
+use crate::predicate::Predicate;
 use crate::segment_structure::SegmentStructure;
+use crate::trace::{self, MappingTrace, TraceEntry, TraceOutcome};
+use crate::transform;
 
 /// The mapping engine holds all loaded mapping definitions
 /// and provides methods for bidirectional conversion.
@@ -22,11 +29,27 @@ pub struct MappingEngine {
     definitions: Vec<MappingDefinition>,
     segment_structure: Option<SegmentStructure>,
     code_lookup: Option<crate::code_lookup::CodeLookup>,
+    handlers: Arc<HandlerRegistry>,
 }
 
 impl MappingEngine {
     /// Load all TOML mapping files from a directory.
+    ///
+    /// Definitions referencing `complex_handlers` fail to load; use
+    /// [`load_with_handlers`](Self::load_with_handlers) to supply them.
     pub fn load(dir: &Path) -> Result<Self, MappingError> {
+        Self::load_with_handlers(dir, &Arc::default())
+    }
+
+    /// Load all TOML mapping files from a directory, resolving `complex_handlers`
+    /// against `handlers`.
+    ///
+    /// A handler name that is not registered (in either direction) fails with
+    /// [`MappingError::UnknownHandler`] naming the offending TOML file.
+    pub fn load_with_handlers(
+        dir: &Path,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<Self, MappingError> {
         let mut definitions = Vec::new();
 
         let mut entries: Vec<_> = std::fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
@@ -36,11 +59,15 @@ impl MappingEngine {
             let path = entry.path();
             if path.extension().map(|e| e == "toml").unwrap_or(false) {
                 let content = std::fs::read_to_string(&path)?;
-                let def: MappingDefinition =
+                let mut def: MappingDefinition =
                     toml::from_str(&content).map_err(|e| MappingError::TomlParse {
                         file: path.display().to_string(),
                         message: e.to_string(),
                     })?;
+                let file = path.display().to_string();
+                def.validate(&file)?;
+                check_handlers(&def, handlers, &file)?;
+                def.source_file = Some(file);
                 definitions.push(def);
             }
         }
@@ -49,6 +76,7 @@ impl MappingEngine {
             definitions,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::clone(handlers),
         })
     }
 
@@ -61,8 +89,17 @@ impl MappingEngine {
         message_dir: &Path,
         transaction_dir: &Path,
     ) -> Result<(Self, Self), MappingError> {
-        let msg_engine = Self::load(message_dir)?;
-        let tx_engine = Self::load(transaction_dir)?;
+        Self::load_split_with_handlers(message_dir, transaction_dir, &Arc::default())
+    }
+
+    /// Like [`load_split`](Self::load_split), resolving `complex_handlers` against `handlers`.
+    pub fn load_split_with_handlers(
+        message_dir: &Path,
+        transaction_dir: &Path,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<(Self, Self), MappingError> {
+        let msg_engine = Self::load_with_handlers(message_dir, handlers)?;
+        let tx_engine = Self::load_with_handlers(transaction_dir, handlers)?;
         Ok((msg_engine, tx_engine))
     }
 
@@ -71,15 +108,24 @@ impl MappingEngine {
     /// Useful for combining message-level and transaction-level mappings
     /// when a single engine with all definitions is needed.
     pub fn load_merged(dirs: &[&Path]) -> Result<Self, MappingError> {
+        Self::load_merged_with_handlers(dirs, &Arc::default())
+    }
+
+    /// Like [`load_merged`](Self::load_merged), resolving `complex_handlers` against `handlers`.
+    pub fn load_merged_with_handlers(
+        dirs: &[&Path],
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<Self, MappingError> {
         let mut definitions = Vec::new();
         for dir in dirs {
-            let engine = Self::load(dir)?;
+            let engine = Self::load_with_handlers(dir, handlers)?;
             definitions.extend(engine.definitions);
         }
         Ok(Self {
             definitions,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::clone(handlers),
         })
     }
 
@@ -96,7 +142,18 @@ impl MappingEngine {
         pid_dir: &Path,
         schema_index: &crate::pid_schema_index::PidSchemaIndex,
     ) -> Result<Self, MappingError> {
-        let mut common_defs = Self::load(common_dir)?.definitions;
+        Self::load_with_common_and_handlers(common_dir, pid_dir, schema_index, &Arc::default())
+    }
+
+    /// Like [`load_with_common`](Self::load_with_common), resolving `complex_handlers`
+    /// against `handlers`.
+    pub fn load_with_common_and_handlers(
+        common_dir: &Path,
+        pid_dir: &Path,
+        schema_index: &crate::pid_schema_index::PidSchemaIndex,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<Self, MappingError> {
+        let mut common_defs = Self::load_with_handlers(common_dir, handlers)?.definitions;
 
         // Filter common defs by schema — keep only groups that exist in this PID
         common_defs.retain(|d| {
@@ -107,7 +164,7 @@ impl MappingEngine {
                 .unwrap_or(true)
         });
 
-        let pid_defs = Self::load(pid_dir)?.definitions;
+        let pid_defs = Self::load_with_handlers(pid_dir, handlers)?.definitions;
 
         // Build set of PID override keys: (source_group_normalized, discriminator)
         // Normalizations applied:
@@ -154,6 +211,7 @@ impl MappingEngine {
             definitions,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::clone(handlers),
         })
     }
 
@@ -164,7 +222,17 @@ impl MappingEngine {
         common_dir: &Path,
         schema_index: &crate::pid_schema_index::PidSchemaIndex,
     ) -> Result<Self, MappingError> {
-        let mut common_defs = Self::load(common_dir)?.definitions;
+        Self::load_common_only_with_handlers(common_dir, schema_index, &Arc::default())
+    }
+
+    /// Like [`load_common_only`](Self::load_common_only), resolving `complex_handlers`
+    /// against `handlers`.
+    pub fn load_common_only_with_handlers(
+        common_dir: &Path,
+        schema_index: &crate::pid_schema_index::PidSchemaIndex,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<Self, MappingError> {
+        let mut common_defs = Self::load_with_handlers(common_dir, handlers)?.definitions;
 
         // Filter common defs by schema — keep only groups that exist in this PID
         common_defs.retain(|d| {
@@ -179,6 +247,7 @@ impl MappingEngine {
             definitions: common_defs,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::clone(handlers),
         })
     }
 
@@ -192,8 +261,31 @@ impl MappingEngine {
         transaction_dir: &Path,
         schema_index: &crate::pid_schema_index::PidSchemaIndex,
     ) -> Result<(Self, Self), MappingError> {
-        let msg_engine = Self::load(message_dir)?;
-        let tx_engine = Self::load_with_common(common_dir, transaction_dir, schema_index)?;
+        Self::load_split_with_common_and_handlers(
+            message_dir,
+            common_dir,
+            transaction_dir,
+            schema_index,
+            &Arc::default(),
+        )
+    }
+
+    /// Like [`load_split_with_common`](Self::load_split_with_common), resolving
+    /// `complex_handlers` against `handlers`.
+    pub fn load_split_with_common_and_handlers(
+        message_dir: &Path,
+        common_dir: &Path,
+        transaction_dir: &Path,
+        schema_index: &crate::pid_schema_index::PidSchemaIndex,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<(Self, Self), MappingError> {
+        let msg_engine = Self::load_with_handlers(message_dir, handlers)?;
+        let tx_engine = Self::load_with_common_and_handlers(
+            common_dir,
+            transaction_dir,
+            schema_index,
+            handlers,
+        )?;
         Ok((msg_engine, tx_engine))
     }
 
@@ -203,6 +295,7 @@ impl MappingEngine {
             definitions,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::default(),
         }
     }
 
@@ -239,7 +332,9 @@ impl MappingEngine {
     /// Load definitions from a cache file.
     ///
     /// Returns an engine with only `definitions` populated. Attach `segment_structure`
-    /// and `code_lookup` via the builder methods if needed.
+    /// and `code_lookup` via the builder methods if needed. Handler references are
+    /// not checked; use [`load_cached_with_handlers`](Self::load_cached_with_handlers)
+    /// for caches built from definitions with `complex_handlers`.
     pub fn load_cached(path: &Path) -> Result<Self, MappingError> {
         let bytes = std::fs::read(path)?;
         let definitions: Vec<MappingDefinition> =
@@ -251,9 +346,25 @@ impl MappingEngine {
             definitions,
             segment_structure: None,
             code_lookup: None,
+            handlers: Arc::default(),
         })
     }
 
+    /// Load definitions from a cache file, resolving `complex_handlers` against
+    /// `handlers`. Unknown handler names report the cache file path.
+    pub fn load_cached_with_handlers(
+        path: &Path,
+        handlers: &Arc<HandlerRegistry>,
+    ) -> Result<Self, MappingError> {
+        let mut engine = Self::load_cached(path)?;
+        let file = path.display().to_string();
+        for def in &engine.definitions {
+            check_handlers(def, handlers, &file)?;
+        }
+        engine.handlers = Arc::clone(handlers);
+        Ok(engine)
+    }
+
     /// Attach a MIG-derived segment structure for trailing element padding.
     ///
     /// When set, `map_reverse` pads each segment's elements up to the
@@ -284,6 +395,11 @@ impl MappingEngine {
         self
     }
 
+    /// Get the handler registry used for `complex_handlers`.
+    pub fn handlers(&self) -> &HandlerRegistry {
+        &self.handlers
+    }
+
     /// Get all loaded definitions.
     pub fn definitions(&self) -> &[MappingDefinition] {
         &self.definitions
@@ -322,42 +438,16 @@ impl MappingEngine {
     /// means SG4\[0\] → SG8\[1\] → SG10\[repetition\]. Without a colon suffix,
     /// intermediate groups default to repetition 0.
     pub fn resolve_group_instance<'a>(
-        tree: &'a AssembledTree,
+        tree: &'a AssembledTree<'a>,
         group_path: &str,
         repetition: usize,
-    ) -> Option<&'a AssembledGroupInstance> {
-        let parts: Vec<&str> = group_path.split('.').collect();
-
-        let (first_id, first_rep) = parse_group_spec(parts[0]);
-        let first_group = tree.groups.iter().find(|g| g.group_id == first_id)?;
-
-        if parts.len() == 1 {
-            // Single part — use the explicit rep from spec or the `repetition` param
-            let rep = first_rep.unwrap_or(repetition);
-            return first_group.repetitions.get(rep);
-        }
-
-        // Navigate through groups; intermediate parts default to rep 0
-        // unless explicitly specified via `:N` suffix
-        let mut current_instance = first_group.repetitions.get(first_rep.unwrap_or(0))?;
-
-        for (i, part) in parts[1..].iter().enumerate() {
-            let (group_id, explicit_rep) = parse_group_spec(part);
-            let child_group = current_instance
-                .child_groups
-                .iter()
-                .find(|g| g.group_id == group_id)?;
-
-            if i == parts.len() - 2 {
-                // Last part — use explicit rep, or fall back to `repetition`
-                let rep = explicit_rep.unwrap_or(repetition);
-                return child_group.repetitions.get(rep);
-            }
-            // Intermediate — use explicit rep or 0
-            current_instance = child_group.repetitions.get(explicit_rep.unwrap_or(0))?;
-        }
-
-        None
+    ) -> Option<&'a AssembledGroupInstance<'a>> {
+        let (mut steps, leaf_rep) = group_path_steps(group_path);
+        steps
+            .last_mut()?
+            .predicates
+            .push(QueryPredicate::Index(leaf_rep.unwrap_or(repetition)));
+        Query::new(steps).select(tree).first()?.group()
     }
 
     /// Navigate the assembled tree using a source_path with qualifier suffixes.
@@ -368,45 +458,13 @@ impl MappingEngine {
     ///
     /// Returns `None` if any part of the path can't be resolved.
     pub fn resolve_by_source_path<'a>(
-        tree: &'a AssembledTree,
+        tree: &'a AssembledTree<'a>,
         source_path: &str,
-    ) -> Option<&'a AssembledGroupInstance> {
-        let parts: Vec<&str> = source_path.split('.').collect();
-        if parts.is_empty() {
-            return None;
-        }
-
-        let (first_id, first_qualifier) = parse_source_path_part(parts[0]);
-        let first_group = tree
-            .groups
-            .iter()
-            .find(|g| g.group_id.eq_ignore_ascii_case(first_id))?;
-
-        let mut current_instance = if let Some(q) = first_qualifier {
-            find_rep_by_entry_qualifier(&first_group.repetitions, q)?
-        } else {
-            first_group.repetitions.first()?
-        };
-
-        if parts.len() == 1 {
-            return Some(current_instance);
-        }
-
-        for part in &parts[1..] {
-            let (group_id, qualifier) = parse_source_path_part(part);
-            let child_group = current_instance
-                .child_groups
-                .iter()
-                .find(|g| g.group_id.eq_ignore_ascii_case(group_id))?;
-
-            current_instance = if let Some(q) = qualifier {
-                find_rep_by_entry_qualifier(&child_group.repetitions, q)?
-            } else {
-                child_group.repetitions.first()?
-            };
-        }
-
-        Some(current_instance)
+    ) -> Option<&'a AssembledGroupInstance<'a>> {
+        source_path_query(source_path, true)
+            .select(tree)
+            .first()?
+            .group()
     }
 
     /// Resolve ALL matching instances for a source_path, returning a Vec.
@@ -417,58 +475,14 @@ impl MappingEngine {
     /// returns both.  For deeper paths like "sg4.sg8_zf3.sg10", if there are
     /// two SG8 reps with ZF3, it returns SG10 children from both.
     pub fn resolve_all_by_source_path<'a>(
-        tree: &'a AssembledTree,
+        tree: &'a AssembledTree<'a>,
         source_path: &str,
-    ) -> Vec<&'a AssembledGroupInstance> {
-        let parts: Vec<&str> = source_path.split('.').collect();
-        if parts.is_empty() {
-            return vec![];
-        }
-
-        // First part: match against top-level groups
-        let (first_id, first_qualifier) = parse_source_path_part(parts[0]);
-        let first_group = match tree
-            .groups
+    ) -> Vec<&'a AssembledGroupInstance<'a>> {
+        source_path_query(source_path, false)
+            .select(tree)
             .iter()
-            .find(|g| g.group_id.eq_ignore_ascii_case(first_id))
-        {
-            Some(g) => g,
-            None => return vec![],
-        };
-
-        let mut current_instances: Vec<&AssembledGroupInstance> = if let Some(q) = first_qualifier {
-            find_all_reps_by_entry_qualifier(&first_group.repetitions, q)
-        } else {
-            first_group.repetitions.iter().collect()
-        };
-
-        // Navigate remaining parts, branching at each level when multiple
-        // instances match a qualifier (e.g., two SG8 reps with ZF3).
-        for part in &parts[1..] {
-            let (group_id, qualifier) = parse_source_path_part(part);
-            let mut next_instances = Vec::new();
-
-            for instance in &current_instances {
-                if let Some(child_group) = instance
-                    .child_groups
-                    .iter()
-                    .find(|g| g.group_id.eq_ignore_ascii_case(group_id))
-                {
-                    if let Some(q) = qualifier {
-                        next_instances.extend(find_all_reps_by_entry_qualifier(
-                            &child_group.repetitions,
-                            q,
-                        ));
-                    } else {
-                        next_instances.extend(child_group.repetitions.iter());
-                    }
-                }
-            }
-
-            current_instances = next_instances;
-        }
-
-        current_instances
+            .filter_map(QueryMatch::group)
+            .collect()
     }
 
     /// Like `resolve_all_by_source_path` but also returns the direct parent
@@ -482,104 +496,21 @@ impl MappingEngine {
     ///
     /// Returns `Vec<(parent_rep_index, &AssembledGroupInstance)>`.
     pub fn resolve_all_with_parent_indices<'a>(
-        tree: &'a AssembledTree,
+        tree: &'a AssembledTree<'a>,
         source_path: &str,
-    ) -> Vec<(usize, &'a AssembledGroupInstance)> {
-        let parts: Vec<&str> = source_path.split('.').collect();
-        if parts.is_empty() {
-            return vec![];
-        }
-
-        // First part: match against top-level groups
-        let (first_id, first_qualifier) = parse_source_path_part(parts[0]);
-        let first_group = match tree
-            .groups
+    ) -> Vec<(usize, &'a AssembledGroupInstance<'a>)> {
+        source_path_query(source_path, false)
+            .select(tree)
             .iter()
-            .find(|g| g.group_id.eq_ignore_ascii_case(first_id))
-        {
-            Some(g) => g,
-            None => return vec![],
-        };
-
-        // If single-level path, just return instances with index 0
-        if parts.len() == 1 {
-            let instances: Vec<&AssembledGroupInstance> = if let Some(q) = first_qualifier {
-                find_all_reps_by_entry_qualifier(&first_group.repetitions, q)
-            } else {
-                first_group.repetitions.iter().collect()
-            };
-            return instances.into_iter().map(|i| (0, i)).collect();
-        }
-
-        // Multi-level: navigate tracking (parent_rep_idx, instance) at each level.
-        // At intermediate levels, parent_rep_idx is updated to the current rep's
-        // position within its group. At the leaf level, the parent_rep_idx from
-        // the previous level is preserved — giving us the DIRECT parent index.
-        let first_reps: Vec<(usize, &AssembledGroupInstance)> = if let Some(q) = first_qualifier {
-            let matching = find_all_reps_by_entry_qualifier(&first_group.repetitions, q);
-            let mut result = Vec::new();
-            for m in matching {
-                let idx = first_group
-                    .repetitions
-                    .iter()
-                    .position(|r| std::ptr::eq(r, m))
-                    .unwrap_or(0);
-                result.push((idx, m));
-            }
-            result
-        } else {
-            first_group.repetitions.iter().enumerate().collect()
-        };
-
-        let mut current: Vec<(usize, &AssembledGroupInstance)> = first_reps;
-        let remaining = &parts[1..];
-
-        for (level, part) in remaining.iter().enumerate() {
-            let is_leaf = level == remaining.len() - 1;
-            let (group_id, qualifier) = parse_source_path_part(part);
-            let mut next: Vec<(usize, &AssembledGroupInstance)> = Vec::new();
-
-            for (prev_parent_idx, instance) in &current {
-                if let Some(child_group) = instance
-                    .child_groups
-                    .iter()
-                    .find(|g| g.group_id.eq_ignore_ascii_case(group_id))
-                {
-                    let matching: Vec<(usize, &AssembledGroupInstance)> = if let Some(q) = qualifier
-                    {
-                        let filtered =
-                            find_all_reps_by_entry_qualifier(&child_group.repetitions, q);
-                        filtered
-                            .into_iter()
-                            .map(|m| {
-                                let idx = child_group
-                                    .repetitions
-                                    .iter()
-                                    .position(|r| std::ptr::eq(r, m))
-                                    .unwrap_or(0);
-                                (idx, m)
-                            })
-                            .collect()
-                    } else {
-                        child_group.repetitions.iter().enumerate().collect()
-                    };
-
-                    for (rep_idx, child_rep) in matching {
-                        if is_leaf {
-                            // At the leaf: keep the parent index from the previous level
-                            next.push((*prev_parent_idx, child_rep));
-                        } else {
-                            // At intermediate: pass down the current rep index
-                            next.push((rep_idx, child_rep));
-                        }
-                    }
-                }
-            }
-
-            current = next;
-        }
-
-        current
+            .filter_map(|m| {
+                // The leaf's parent is the second-to-last step of the match path.
+                let parent_idx = match m.path.len() {
+                    0 | 1 => 0,
+                    len => m.path[len - 2].index,
+                };
+                Some((parent_idx, m.group()?))
+            })
+            .collect()
     }
 
     /// Extract a field from a group instance by path.
@@ -589,36 +520,27 @@ impl MappingEngine {
     /// - `"dtm[92].0.1"` → DTM where elements\[0\]\[0\] == "92", then elements\[0\]\[1\]
     pub fn extract_from_instance(instance: &AssembledGroupInstance, path: &str) -> Option<String> {
         let parts: Vec<&str> = path.split('.').collect();
-        if parts.is_empty() {
-            return None;
-        }
+        let segment = Self::find_segment(instance, path)?;
+        Self::resolve_field_path(segment, &parts[1..])
+    }
+
+    /// Find the segment a field path refers to (the `tag[qualifier]` part of the path).
+    fn find_segment<'a>(
+        instance: &'a AssembledGroupInstance<'a>,
+        path: &str,
+    ) -> Option<&'a AssembledSegment<'a>> {
+        let first = path.split('.').next()?;
 
         // Parse segment tag, optional qualifier, and occurrence index:
         // "dtm[92]" → ("DTM", Some("92"), 0), "rff[Z34,1]" → ("RFF", Some("Z34"), 1)
-        let (segment_tag, qualifier, occurrence) = parse_tag_qualifier(parts[0]);
+        let (segment_tag, qualifier, occurrence) = parse_tag_qualifier(first);
 
-        let segment = if let Some(q) = qualifier {
-            instance
-                .segments
-                .iter()
-                .filter(|s| {
-                    s.tag.eq_ignore_ascii_case(&segment_tag)
-                        && s.elements
-                            .first()
-                            .and_then(|e| e.first())
-                            .map(|v| v.as_str())
-                            == Some(q)
-                })
-                .nth(occurrence)?
-        } else {
-            instance
-                .segments
-                .iter()
-                .filter(|s| s.tag.eq_ignore_ascii_case(&segment_tag))
-                .nth(occurrence)?
-        };
-
-        Self::resolve_field_path(segment, &parts[1..])
+        let mut step = Step::segment(segment_tag);
+        if let Some(q) = qualifier {
+            step = step.with(QueryPredicate::value_eq(None, 0, 0, [q]));
+        }
+        let query = Query::new(vec![step.with(QueryPredicate::Index(occurrence))]);
+        query.select_in(instance).first()?.segment()
     }
 
     /// Map all fields in a definition from the assembled tree to a BO4E JSON object.
@@ -635,7 +557,20 @@ impl MappingEngine {
         def: &MappingDefinition,
         repetition: usize,
     ) -> serde_json::Value {
-        self.map_forward_inner(tree, def, repetition, true)
+        self.map_forward_inner(tree, def, repetition, true, None)
+    }
+
+    /// Like [`map_forward`](Self::map_forward), recording every field mapping in
+    /// `trace`. Target pointers are relative to the returned object.
+    pub fn map_forward_traced(
+        &self,
+        tree: &AssembledTree,
+        def: &MappingDefinition,
+        repetition: usize,
+        trace: &mut MappingTrace,
+    ) -> serde_json::Value {
+        trace.number_segments(tree);
+        self.map_forward_inner(tree, def, repetition, true, Some(trace))
     }
 
     /// Inner implementation with enrichment control.
@@ -645,6 +580,7 @@ impl MappingEngine {
         def: &MappingDefinition,
         repetition: usize,
         enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
     ) -> serde_json::Value {
         let mut result = serde_json::Map::new();
 
@@ -662,8 +598,14 @@ impl MappingEngine {
                 child_groups: vec![],
                 skipped_segments: Vec::new(),
             };
-            self.extract_fields_from_instance(&root_instance, def, &mut result, enrich_codes);
-            self.extract_companion_fields(&root_instance, def, &mut result, enrich_codes);
+            if let Some(t) = trace.as_deref_mut() {
+                let originals = tree
+                    .segments
+                    .iter()
+                    .chain(tree.inter_group_segments.values().flatten());
+                t.alias_segments(originals, &root_instance.segments);
+            }
+            self.extract_instance(&root_instance, def, &mut result, enrich_codes, trace);
             return serde_json::Value::Object(result);
         }
 
@@ -685,13 +627,98 @@ impl MappingEngine {
         };
 
         if let Some(instance) = instance {
-            self.extract_fields_from_instance(instance, def, &mut result, enrich_codes);
-            self.extract_companion_fields(instance, def, &mut result, enrich_codes);
+            self.extract_instance(instance, def, &mut result, enrich_codes, trace);
         }
 
         serde_json::Value::Object(result)
     }
 
+    /// Extract fields, companion fields and complex handler output from one instance.
+    fn extract_instance(
+        &self,
+        instance: &AssembledGroupInstance,
+        def: &MappingDefinition,
+        result: &mut serde_json::Map<String, serde_json::Value>,
+        enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
+    ) {
+        self.extract_fields_from_instance(
+            instance,
+            def,
+            result,
+            enrich_codes,
+            trace.as_deref_mut(),
+        );
+        self.extract_companion_fields(instance, def, result, enrich_codes, trace);
+        self.apply_forward_handlers(instance, def, result);
+    }
+
+    /// Invoke the definition's forward `complex_handlers` and merge their output.
+    ///
+    /// Object results are merged key by key without overwriting values produced by
+    /// declarative field mappings; any other result is stored under the handler name.
+    /// Handlers that fail or have no forward direction contribute nothing.
+    fn apply_forward_handlers(
+        &self,
+        instance: &AssembledGroupInstance,
+        def: &MappingDefinition,
+        result: &mut serde_json::Map<String, serde_json::Value>,
+    ) {
+        for handler in def.complex_handlers.iter().flatten() {
+            if !self.handlers.has_forward_handler(&handler.name) {
+                continue;
+            }
+            match self.handlers.invoke(&handler.name, instance) {
+                Ok(serde_json::Value::Object(map)) => {
+                    for (key, value) in map {
+                        deep_merge_insert(result, &key, value);
+                    }
+                }
+                Ok(serde_json::Value::Null) | Err(_) => {}
+                Ok(value) => deep_merge_insert(result, &handler.name, value),
+            }
+        }
+    }
+
+    /// Evaluate an optional `when` predicate against a group instance.
+    ///
+    /// Absent or unparseable predicates hold (definitions loaded via
+    /// [`MappingEngine::load`] have already been validated).
+    fn when_holds(instance: &AssembledGroupInstance, when: Option<&str>) -> bool {
+        match when.map(Predicate::parse) {
+            Some(Ok(pred)) => pred.evaluate(&|p| Self::extract_from_instance(instance, p)),
+            _ => true,
+        }
+    }
+
+    /// Record the outcome of one forward field mapping.
+    ///
+    /// `target` is the JSON pointer relative to the entity object; `value` and
+    /// `mapped_value` are the extracted and the written value.
+    #[allow(clippy::too_many_arguments)]
+    fn trace_forward_field(
+        trace: Option<&mut MappingTrace>,
+        instance: &AssembledGroupInstance,
+        def: &MappingDefinition,
+        path: &str,
+        target: String,
+        value: Option<&String>,
+        mapped_value: Option<&String>,
+        outcome: TraceOutcome,
+    ) {
+        let Some(trace) = trace else {
+            return;
+        };
+        let segment_number =
+            Self::find_segment(instance, path).and_then(|s| trace.segment_number(s));
+        let mut entry = TraceEntry::for_field(def, path, outcome);
+        entry.segment_number = segment_number;
+        entry.target = target;
+        entry.value = value.cloned();
+        entry.mapped_value = mapped_value.cloned();
+        trace.record(entry);
+    }
+
     /// Extract companion_fields into a nested object within the result.
     ///
     /// When a `code_lookup` is configured, code-type fields are emitted as
@@ -702,27 +729,75 @@ impl MappingEngine {
         def: &MappingDefinition,
         result: &mut serde_json::Map<String, serde_json::Value>,
         enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
     ) {
         if let Some(ref companion_fields) = def.companion_fields {
             let raw_key = def.meta.companion_type.as_deref().unwrap_or("_companion");
             let companion_key = to_camel_case(raw_key);
             let mut companion_result = serde_json::Map::new();
+            let target_pointer =
+                |target: &str| trace::pointer(&format!("{companion_key}.{target}"));
 
             for (path, field_mapping) in companion_fields {
-                let (target, enum_map) = match field_mapping {
-                    FieldMapping::Simple(t) => (t.as_str(), None),
-                    FieldMapping::Structured(s) => (s.target.as_str(), s.enum_map.as_ref()),
+                let (target, enum_map, transform, when) = match field_mapping {
+                    FieldMapping::Simple(t) => (t.as_str(), None, None, None),
+                    FieldMapping::Structured(s) => (
+                        s.target.as_str(),
+                        s.enum_map.as_ref(),
+                        s.transform.as_deref(),
+                        s.when.as_deref(),
+                    ),
                     FieldMapping::Nested(_) => continue,
                 };
                 if target.is_empty() {
                     continue;
                 }
-                if let Some(val) = Self::extract_from_instance(instance, path) {
+                if !Self::when_holds(instance, when) {
+                    Self::trace_forward_field(
+                        trace.as_deref_mut(),
+                        instance,
+                        def,
+                        path,
+                        target_pointer(target),
+                        None,
+                        None,
+                        TraceOutcome::ConditionNotMet,
+                    );
+                    continue;
+                }
+                let extracted = Self::extract_from_instance(instance, path);
+                if extracted.is_none() {
+                    Self::trace_forward_field(
+                        trace.as_deref_mut(),
+                        instance,
+                        def,
+                        path,
+                        target_pointer(target),
+                        None,
+                        None,
+                        TraceOutcome::EmptyValue,
+                    );
+                }
+                if let Some(val) = extracted {
+                    let transformed = match transform {
+                        Some(spec) => transform::apply_forward(spec, val.clone()),
+                        None => val.clone(),
+                    };
                     let mapped_val = if let Some(map) = enum_map {
-                        map.get(&val).cloned().unwrap_or_else(|| val.clone())
+                        map.get(&transformed).cloned().unwrap_or(transformed)
                     } else {
-                        val.clone()
+                        transformed
                     };
+                    Self::trace_forward_field(
+                        trace.as_deref_mut(),
+                        instance,
+                        def,
+                        path,
+                        target_pointer(target),
+                        Some(&val),
+                        Some(&mapped_val),
+                        TraceOutcome::Mapped,
+                    );
 
                     // Enrich code fields with meaning from PID schema
                     if enrich_codes {
@@ -791,22 +866,68 @@ impl MappingEngine {
         def: &MappingDefinition,
         result: &mut serde_json::Map<String, serde_json::Value>,
         enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
     ) {
         for (path, field_mapping) in &def.fields {
-            let (target, enum_map) = match field_mapping {
-                FieldMapping::Simple(t) => (t.as_str(), None),
-                FieldMapping::Structured(s) => (s.target.as_str(), s.enum_map.as_ref()),
+            let (target, enum_map, transform, when) = match field_mapping {
+                FieldMapping::Simple(t) => (t.as_str(), None, None, None),
+                FieldMapping::Structured(s) => (
+                    s.target.as_str(),
+                    s.enum_map.as_ref(),
+                    s.transform.as_deref(),
+                    s.when.as_deref(),
+                ),
                 FieldMapping::Nested(_) => continue,
             };
             if target.is_empty() {
                 continue;
             }
-            if let Some(val) = Self::extract_from_instance(instance, path) {
+            if !Self::when_holds(instance, when) {
+                Self::trace_forward_field(
+                    trace.as_deref_mut(),
+                    instance,
+                    def,
+                    path,
+                    trace::pointer(target),
+                    None,
+                    None,
+                    TraceOutcome::ConditionNotMet,
+                );
+                continue;
+            }
+            let extracted = Self::extract_from_instance(instance, path);
+            if extracted.is_none() {
+                Self::trace_forward_field(
+                    trace.as_deref_mut(),
+                    instance,
+                    def,
+                    path,
+                    trace::pointer(target),
+                    None,
+                    None,
+                    TraceOutcome::EmptyValue,
+                );
+            }
+            if let Some(val) = extracted {
+                let transformed = match transform {
+                    Some(spec) => transform::apply_forward(spec, val.clone()),
+                    None => val.clone(),
+                };
                 let mapped_val = if let Some(map) = enum_map {
-                    map.get(&val).cloned().unwrap_or_else(|| val.clone())
+                    map.get(&transformed).cloned().unwrap_or(transformed)
                 } else {
-                    val.clone()
+                    transformed
                 };
+                Self::trace_forward_field(
+                    trace.as_deref_mut(),
+                    instance,
+                    def,
+                    path,
+                    trace::pointer(target),
+                    Some(&val),
+                    Some(&mapped_val),
+                    TraceOutcome::Mapped,
+                );
 
                 // Enrich code fields with meaning from PID schema
                 if enrich_codes {
@@ -866,13 +987,8 @@ impl MappingEngine {
         segments: &[OwnedSegment],
         def: &MappingDefinition,
     ) -> serde_json::Value {
-        let assembled_segments: Vec<AssembledSegment> = segments
-            .iter()
-            .map(|s| AssembledSegment {
-                tag: s.id.clone(),
-                elements: s.elements.clone(),
-            })
-            .collect();
+        let assembled_segments: Vec<AssembledSegment> =
+            segments.iter().map(owned_to_assembled).collect();
 
         let instance = AssembledGroupInstance {
             segments: assembled_segments,
@@ -881,7 +997,7 @@ impl MappingEngine {
         };
 
         let mut result = serde_json::Map::new();
-        self.extract_fields_from_instance(&instance, def, &mut result, true);
+        self.extract_fields_from_instance(&instance, def, &mut result, true, None);
         serde_json::Value::Object(result)
     }
 
@@ -901,11 +1017,30 @@ impl MappingEngine {
         &self,
         bo4e_value: &serde_json::Value,
         def: &MappingDefinition,
-    ) -> AssembledGroupInstance {
+    ) -> AssembledGroupInstance<'static> {
+        self.map_reverse_inner(bo4e_value, def, None)
+    }
+
+    /// Like [`map_reverse`](Self::map_reverse), recording every field mapping in
+    /// `trace`. Target pointers name the BO4E values read, relative to `bo4e_value`.
+    pub fn map_reverse_traced(
+        &self,
+        bo4e_value: &serde_json::Value,
+        def: &MappingDefinition,
+        trace: &mut MappingTrace,
+    ) -> AssembledGroupInstance<'static> {
+        self.map_reverse_inner(bo4e_value, def, Some(trace))
+    }
+
+    fn map_reverse_inner(
+        &self,
+        bo4e_value: &serde_json::Value,
+        def: &MappingDefinition,
+        mut trace: Option<&mut MappingTrace>,
+    ) -> AssembledGroupInstance<'static> {
         // Collect (segment_key, element_index, component_index, value) tuples.
         // segment_key includes qualifier for disambiguation: "DTM" or "DTM[92]".
-        let mut field_values: Vec<(String, String, usize, usize, String)> =
-            Vec::with_capacity(def.fields.len());
+        let mut field_values: Vec<FieldValue> = Vec::with_capacity(def.fields.len());
 
         // Track whether any field with a non-empty target resolved to an actual
         // BO4E value.  When a definition has data fields but none resolved to
@@ -920,13 +1055,22 @@ impl MappingEngine {
         let mut seg_has_data_field: HashSet<String> = HashSet::new();
         let mut seg_has_real_data: HashSet<String> = HashSet::new();
         let mut injected_qualifiers: HashSet<String> = HashSet::new();
+        // Fields with a `when` predicate: (insertion index, predicate, entry, has BO4E value).
+        // They are evaluated once all unconditional fields are known, since the
+        // predicate usually refers to a qualifier or sibling written by another field.
+        let mut deferred: Vec<(usize, Predicate, FieldValue, bool, Option<TraceEntry>)> =
+            Vec::new();
 
         for (path, field_mapping) in &def.fields {
-            let (target, default, enum_map) = match field_mapping {
-                FieldMapping::Simple(t) => (t.as_str(), None, None),
-                FieldMapping::Structured(s) => {
-                    (s.target.as_str(), s.default.as_ref(), s.enum_map.as_ref())
-                }
+            let (target, default, enum_map, transform, condition) = match field_mapping {
+                FieldMapping::Simple(t) => (t.as_str(), None, None, None, None),
+                FieldMapping::Structured(s) => (
+                    s.target.as_str(),
+                    s.default.as_ref(),
+                    s.enum_map.as_ref(),
+                    s.transform.as_deref(),
+                    s.when.as_deref().and_then(|w| Predicate::parse(w).ok()),
+                ),
                 FieldMapping::Nested(_) => continue,
             };
 
@@ -958,15 +1102,17 @@ impl MappingEngine {
             };
 
             // Try BO4E value first, fall back to default
+            let mut real_data = false;
+            let mut traced_input = None;
             let val = if target.is_empty() {
                 default.cloned()
             } else {
                 has_data_fields = true;
                 seg_has_data_field.insert(seg_key.clone());
                 let bo4e_val = self.populate_field(bo4e_value, target);
-                if bo4e_val.is_some() {
-                    has_real_data = true;
-                    seg_has_real_data.insert(seg_key.clone());
+                real_data = bo4e_val.is_some();
+                if trace.is_some() {
+                    traced_input = bo4e_val.clone();
                 }
                 // Apply reverse enum_map: BO4E value → EDIFACT value
                 let mapped_val = match (bo4e_val, enum_map) {
@@ -979,17 +1125,49 @@ impl MappingEngine {
                     }
                     (v, _) => v,
                 };
+                let mapped_val = match transform {
+                    Some(spec) => mapped_val.map(|v| transform::apply_reverse(spec, v)),
+                    None => mapped_val,
+                };
                 mapped_val.or_else(|| default.cloned())
             };
+            let mut trace_entry = trace.as_ref().and_then(|_| {
+                reverse_trace_entry(
+                    def,
+                    path,
+                    target,
+                    trace::pointer(target),
+                    traced_input,
+                    &val,
+                )
+            });
 
             if let Some(val) = val {
-                field_values.push((
+                let entry = (
                     seg_key.clone(),
                     seg_tag.clone(),
                     element_idx,
                     component_idx,
                     val,
-                ));
+                );
+                if let Some(pred) = condition {
+                    deferred.push((
+                        field_values.len(),
+                        pred,
+                        entry,
+                        real_data,
+                        trace_entry.take(),
+                    ));
+                } else {
+                    if real_data {
+                        has_real_data = true;
+                        seg_has_real_data.insert(seg_key.clone());
+                    }
+                    field_values.push(entry);
+                }
+            }
+            if let (Some(t), Some(e)) = (trace.as_deref_mut(), trace_entry) {
+                t.record(e);
             }
 
             // If there's a qualifier, also inject it at elements[0][0]
@@ -1009,11 +1187,15 @@ impl MappingEngine {
                 .unwrap_or(&serde_json::Value::Null);
 
             for (path, field_mapping) in companion_fields {
-                let (target, default, enum_map) = match field_mapping {
-                    FieldMapping::Simple(t) => (t.as_str(), None, None),
-                    FieldMapping::Structured(s) => {
-                        (s.target.as_str(), s.default.as_ref(), s.enum_map.as_ref())
-                    }
+                let (target, default, enum_map, transform, condition) = match field_mapping {
+                    FieldMapping::Simple(t) => (t.as_str(), None, None, None, None),
+                    FieldMapping::Structured(s) => (
+                        s.target.as_str(),
+                        s.default.as_ref(),
+                        s.enum_map.as_ref(),
+                        s.transform.as_deref(),
+                        s.when.as_deref().and_then(|w| Predicate::parse(w).ok()),
+                    ),
                     FieldMapping::Nested(_) => continue,
                 };
 
@@ -1041,15 +1223,17 @@ impl MappingEngine {
                     }
                 };
 
+                let mut real_data = false;
+                let mut traced_input = None;
                 let val = if target.is_empty() {
                     default.cloned()
                 } else {
                     has_data_fields = true;
                     seg_has_data_field.insert(seg_key.clone());
                     let bo4e_val = self.populate_field(companion_value, target);
-                    if bo4e_val.is_some() {
-                        has_real_data = true;
-                        seg_has_real_data.insert(seg_key.clone());
+                    real_data = bo4e_val.is_some();
+                    if trace.is_some() {
+                        traced_input = bo4e_val.clone();
                     }
                     let mapped_val = match (bo4e_val, enum_map) {
                         (Some(v), Some(map)) => map
@@ -1059,17 +1243,43 @@ impl MappingEngine {
                             .or(Some(v)),
                         (v, _) => v,
                     };
+                    let mapped_val = match transform {
+                        Some(spec) => mapped_val.map(|v| transform::apply_reverse(spec, v)),
+                        None => mapped_val,
+                    };
                     mapped_val.or_else(|| default.cloned())
                 };
+                let mut trace_entry = trace.as_ref().and_then(|_| {
+                    let pointer = trace::pointer(&format!("{companion_key}.{target}"));
+                    reverse_trace_entry(def, path, target, pointer, traced_input, &val)
+                });
 
                 if let Some(val) = val {
-                    field_values.push((
+                    let entry = (
                         seg_key.clone(),
                         seg_tag.clone(),
                         element_idx,
                         component_idx,
                         val,
-                    ));
+                    );
+                    if let Some(pred) = condition {
+                        deferred.push((
+                            field_values.len(),
+                            pred,
+                            entry,
+                            real_data,
+                            trace_entry.take(),
+                        ));
+                    } else {
+                        if real_data {
+                            has_real_data = true;
+                            seg_has_real_data.insert(seg_key.clone());
+                        }
+                        field_values.push(entry);
+                    }
+                }
+                if let (Some(t), Some(e)) = (trace.as_deref_mut(), trace_entry) {
+                    t.record(e);
                 }
 
                 if let Some(q) = qualifier {
@@ -1080,6 +1290,39 @@ impl MappingEngine {
             }
         }
 
+        // Resolve `when` predicates against the segments built from unconditional
+        // fields. Passing entries are re-inserted at their original position so
+        // segment ordering still follows the TOML field order.
+        if !deferred.is_empty() {
+            let probe = AssembledGroupInstance {
+                segments: build_segments(&field_values),
+                child_groups: vec![],
+                skipped_segments: Vec::new(),
+            };
+            let mut deferred_trace = Vec::new();
+            for (pos, pred, entry, real_data, mut trace_entry) in deferred.into_iter().rev() {
+                if !pred.evaluate(&|p| Self::extract_from_instance(&probe, p)) {
+                    if let Some(e) = trace_entry.as_mut() {
+                        e.outcome = TraceOutcome::ConditionNotMet;
+                        e.mapped_value = None;
+                    }
+                    deferred_trace.extend(trace_entry);
+                    continue;
+                }
+                if real_data {
+                    has_real_data = true;
+                    seg_has_real_data.insert(entry.0.clone());
+                }
+                field_values.insert(pos, entry);
+                deferred_trace.extend(trace_entry);
+            }
+            if let Some(t) = trace {
+                for e in deferred_trace.into_iter().rev() {
+                    t.record(e);
+                }
+            }
+        }
+
         // Per-segment phantom prevention for qualified segments: remove entries
         // for segments using tag[qualifier] syntax (e.g., FTX[ACB], DTM[Z07])
         // that have data fields but none resolved to actual BO4E values.  This
@@ -1098,40 +1341,23 @@ impl MappingEngine {
         // return an empty instance to prevent phantom segments for groups not
         // present in the original EDIFACT message.  Definitions with only
         // qualifier/default fields (has_data_fields=false) are always kept.
+        // Segments produced by reverse complex handlers count as real data.
+        let handler_segments = self.apply_reverse_handlers(bo4e_value, def);
         if has_data_fields && !has_real_data {
-            return AssembledGroupInstance {
-                segments: vec![],
-                child_groups: vec![],
-                skipped_segments: Vec::new(),
-            };
+            if handler_segments.is_empty() {
+                return AssembledGroupInstance {
+                    segments: vec![],
+                    child_groups: vec![],
+                    skipped_segments: Vec::new(),
+                };
+            }
+            field_values.clear();
         }
 
         // Build segments with elements/components in correct positions.
         // Group by segment_key to create separate segments for "DTM[92]" vs "DTM[93]".
-        let mut segments: Vec<AssembledSegment> = Vec::with_capacity(field_values.len());
-        let mut seen_keys: HashMap<String, usize> = HashMap::new();
-
-        for (seg_key, seg_tag, element_idx, component_idx, val) in &field_values {
-            let seg = if let Some(&pos) = seen_keys.get(seg_key) {
-                &mut segments[pos]
-            } else {
-                let pos = segments.len();
-                seen_keys.insert(seg_key.clone(), pos);
-                segments.push(AssembledSegment {
-                    tag: seg_tag.clone(),
-                    elements: vec![],
-                });
-                &mut segments[pos]
-            };
-
-            while seg.elements.len() <= *element_idx {
-                seg.elements.push(vec![]);
-            }
-            while seg.elements[*element_idx].len() <= *component_idx {
-                seg.elements[*element_idx].push(String::new());
-            }
-            seg.elements[*element_idx][*component_idx] = val.clone();
-        }
+        let mut segments = build_segments(&field_values);
+        segments.extend(handler_segments);
 
         // Pad intermediate empty elements: any [] between position 0 and the last
         // populated position becomes [""] so the EDIFACT renderer emits the `+` separator.
@@ -1140,7 +1366,7 @@ impl MappingEngine {
             if let Some(last_idx) = last_populated {
                 for i in 0..last_idx {
                     if seg.elements[i].is_empty() {
-                        seg.elements[i] = vec![String::new()];
+                        seg.elements[i] = vec![Cow::Borrowed("")];
                     }
                 }
             }
@@ -1151,7 +1377,7 @@ impl MappingEngine {
             for seg in &mut segments {
                 if let Some(expected) = ss.element_count(&seg.tag) {
                     while seg.elements.len() < expected {
-                        seg.elements.push(vec![String::new()]);
+                        seg.elements.push(vec![Cow::Borrowed("")]);
                     }
                 }
             }
@@ -1164,6 +1390,26 @@ impl MappingEngine {
         }
     }
 
+    /// Invoke the definition's reverse `complex_handlers` and collect their segments.
+    ///
+    /// Handlers that fail or have no reverse direction contribute nothing.
+    fn apply_reverse_handlers(
+        &self,
+        bo4e_value: &serde_json::Value,
+        def: &MappingDefinition,
+    ) -> Vec<AssembledSegment<'static>> {
+        let mut segments = Vec::new();
+        for handler in def.complex_handlers.iter().flatten() {
+            if !self.handlers.has_reverse_handler(&handler.name) {
+                continue;
+            }
+            if let Ok(segs) = self.handlers.invoke_reverse(&handler.name, bo4e_value) {
+                segments.extend(segs);
+            }
+        }
+        segments
+    }
+
     /// Resolve a field path within a segment to extract a value.
     ///
     /// Two path conventions are supported:
@@ -1193,7 +1439,7 @@ impl MappingEngine {
                 .get(element_idx)?
                 .get(component_idx)
                 .filter(|v| !v.is_empty())
-                .cloned();
+                .map(|v| v.to_string());
         }
 
         // Named path convention
@@ -1203,13 +1449,13 @@ impl MappingEngine {
                 .first()?
                 .first()
                 .filter(|v| !v.is_empty())
-                .cloned(),
+                .map(|v| v.to_string()),
             2 => segment
                 .elements
                 .get(1)?
                 .first()
                 .filter(|v| !v.is_empty())
-                .cloned(),
+                .map(|v| v.to_string()),
             _ => None,
         }
     }
@@ -1253,16 +1499,17 @@ impl MappingEngine {
         bo4e_value: &serde_json::Value,
         segment_tag: &str,
         target_field: &str,
-    ) -> AssembledSegment {
+    ) -> AssembledSegment<'static> {
         let value = self.populate_field(bo4e_value, target_field);
         let elements = if let Some(val) = value {
-            vec![vec![val]]
+            vec![vec![Cow::Owned(val)]]
         } else {
             vec![]
         };
         AssembledSegment {
-            tag: segment_tag.to_uppercase(),
+            tag: Cow::Owned(segment_tag.to_uppercase()),
             elements,
+            location: None,
         }
     }
 
@@ -1273,67 +1520,18 @@ impl MappingEngine {
     ///
     /// Discriminator format: `"TAG.element_idx.component_idx=expected_value"`
     /// Scans all repetitions of the leaf group and returns the first rep index
-    /// where the entry segment matches.
+    /// where any `TAG` segment matches (or the Nth with a `#N` suffix).
     pub fn resolve_repetition(
         tree: &AssembledTree,
         group_path: &str,
         discriminator: &str,
     ) -> Option<usize> {
-        let (spec, expected) = discriminator.split_once('=')?;
-        let parts: Vec<&str> = spec.split('.').collect();
-        if parts.len() != 3 {
-            return None;
-        }
-        let tag = parts[0];
-        let element_idx: usize = parts[1].parse().ok()?;
-        let component_idx: usize = parts[2].parse().ok()?;
-
-        // Navigate to the parent and get the leaf group with all its repetitions
-        let path_parts: Vec<&str> = group_path.split('.').collect();
-
-        let leaf_group = if path_parts.len() == 1 {
-            let (group_id, _) = parse_group_spec(path_parts[0]);
-            tree.groups.iter().find(|g| g.group_id == group_id)?
-        } else {
-            // Navigate to the parent instance, then find the leaf group
-            let parent_parts = &path_parts[..path_parts.len() - 1];
-            let mut current_instance = {
-                let (first_id, first_rep) = parse_group_spec(parent_parts[0]);
-                let first_group = tree.groups.iter().find(|g| g.group_id == first_id)?;
-                first_group.repetitions.get(first_rep.unwrap_or(0))?
-            };
-            for part in &parent_parts[1..] {
-                let (group_id, explicit_rep) = parse_group_spec(part);
-                let child_group = current_instance
-                    .child_groups
-                    .iter()
-                    .find(|g| g.group_id == group_id)?;
-                current_instance = child_group.repetitions.get(explicit_rep.unwrap_or(0))?;
-            }
-            let (leaf_id, _) = parse_group_spec(path_parts.last()?);
-            current_instance
-                .child_groups
-                .iter()
-                .find(|g| g.group_id == leaf_id)?
-        };
-
-        // Scan all repetitions for the matching discriminator
-        let expected_values: Vec<&str> = expected.split('|').collect();
-        for (rep_idx, instance) in leaf_group.repetitions.iter().enumerate() {
-            let matches = instance.segments.iter().any(|s| {
-                s.tag.eq_ignore_ascii_case(tag)
-                    && s.elements
-                        .get(element_idx)
-                        .and_then(|e| e.get(component_idx))
-                        .map(|v| expected_values.iter().any(|ev| v == ev))
-                        .unwrap_or(false)
-            });
-            if matches {
-                return Some(rep_idx);
-            }
-        }
-
-        None
+        let (predicate, occurrence) = discriminator_predicate(discriminator)?;
+        let (mut steps, _) = group_path_steps(group_path);
+        steps.last_mut()?.predicates.push(predicate);
+        let matches = Query::new(steps).select(tree);
+        let leaf = matches.get(occurrence.unwrap_or(0))?;
+        leaf.path.last().map(|step| step.index)
     }
 
     /// Like `resolve_repetition`, but returns ALL matching rep indices instead of just the first.
@@ -1345,100 +1543,20 @@ impl MappingEngine {
         group_path: &str,
         discriminator: &str,
     ) -> Vec<usize> {
-        let Some((spec, expected)) = discriminator.split_once('=') else {
+        let Some((predicate, occurrence)) = discriminator_predicate(discriminator) else {
             return Vec::new();
         };
-        let parts: Vec<&str> = spec.split('.').collect();
-        if parts.len() != 3 {
+        let (mut steps, _) = group_path_steps(group_path);
+        let Some(leaf) = steps.last_mut() else {
             return Vec::new();
-        }
-        let tag = parts[0];
-        let element_idx: usize = match parts[1].parse() {
-            Ok(v) => v,
-            Err(_) => return Vec::new(),
-        };
-        let component_idx: usize = match parts[2].parse() {
-            Ok(v) => v,
-            Err(_) => return Vec::new(),
-        };
-
-        // Navigate to the parent and get the leaf group with all its repetitions
-        let path_parts: Vec<&str> = group_path.split('.').collect();
-
-        let leaf_group = if path_parts.len() == 1 {
-            let (group_id, _) = parse_group_spec(path_parts[0]);
-            match tree.groups.iter().find(|g| g.group_id == group_id) {
-                Some(g) => g,
-                None => return Vec::new(),
-            }
-        } else {
-            let parent_parts = &path_parts[..path_parts.len() - 1];
-            let mut current_instance = {
-                let (first_id, first_rep) = parse_group_spec(parent_parts[0]);
-                let first_group = match tree.groups.iter().find(|g| g.group_id == first_id) {
-                    Some(g) => g,
-                    None => return Vec::new(),
-                };
-                match first_group.repetitions.get(first_rep.unwrap_or(0)) {
-                    Some(i) => i,
-                    None => return Vec::new(),
-                }
-            };
-            for part in &parent_parts[1..] {
-                let (group_id, explicit_rep) = parse_group_spec(part);
-                let child_group = match current_instance
-                    .child_groups
-                    .iter()
-                    .find(|g| g.group_id == group_id)
-                {
-                    Some(g) => g,
-                    None => return Vec::new(),
-                };
-                current_instance = match child_group.repetitions.get(explicit_rep.unwrap_or(0)) {
-                    Some(i) => i,
-                    None => return Vec::new(),
-                };
-            }
-            let (leaf_id, _) = match path_parts.last() {
-                Some(p) => parse_group_spec(p),
-                None => return Vec::new(),
-            };
-            match current_instance
-                .child_groups
-                .iter()
-                .find(|g| g.group_id == leaf_id)
-            {
-                Some(g) => g,
-                None => return Vec::new(),
-            }
         };
-
-        // Parse optional occurrence index from expected value: "TN#1" → ("TN", Some(1))
-        let (expected_raw, occurrence) = parse_discriminator_occurrence(expected);
-
-        // Collect ALL matching rep indices
-        let expected_values: Vec<&str> = expected_raw.split('|').collect();
-        let mut result = Vec::new();
-        for (rep_idx, instance) in leaf_group.repetitions.iter().enumerate() {
-            let matches = instance.segments.iter().any(|s| {
-                s.tag.eq_ignore_ascii_case(tag)
-                    && s.elements
-                        .get(element_idx)
-                        .and_then(|e| e.get(component_idx))
-                        .map(|v| expected_values.iter().any(|ev| v == ev))
-                        .unwrap_or(false)
-            });
-            if matches {
-                result.push(rep_idx);
-            }
-        }
-
-        // If occurrence index specified, return only that match
-        if let Some(occ) = occurrence {
-            result.into_iter().nth(occ).into_iter().collect()
-        } else {
-            result
-        }
+        leaf.predicates.push(predicate);
+        let reps = Query::new(steps)
+            .select(tree)
+            .iter()
+            .filter_map(|m| m.path.last().map(|step| step.index))
+            .collect();
+        nth_occurrence(reps, occurrence)
     }
 
     /// Resolve a discriminated instance using source_path for parent navigation.
@@ -1463,7 +1581,7 @@ impl MappingEngine {
     /// (e.g., LOC location + SEQ info + SG10 characteristics) to contribute
     /// fields to the same BO4E entity.
     pub fn map_all_forward(&self, tree: &AssembledTree) -> serde_json::Value {
-        self.map_all_forward_inner(tree, true).0
+        self.map_all_forward_inner(tree, true, None).0
     }
 
     /// Like [`map_all_forward`](Self::map_all_forward) but with explicit
@@ -1474,7 +1592,19 @@ impl MappingEngine {
         tree: &AssembledTree,
         enrich_codes: bool,
     ) -> serde_json::Value {
-        self.map_all_forward_inner(tree, enrich_codes).0
+        self.map_all_forward_inner(tree, enrich_codes, None).0
+    }
+
+    /// Like [`map_all_forward_enriched`](Self::map_all_forward_enriched), recording
+    /// every field mapping in `trace`. Target pointers are relative to the returned object.
+    pub fn map_all_forward_traced(
+        &self,
+        tree: &AssembledTree,
+        enrich_codes: bool,
+        trace: &mut MappingTrace,
+    ) -> serde_json::Value {
+        trace.number_segments(tree);
+        self.map_all_forward_inner(tree, enrich_codes, Some(trace)).0
     }
 
     /// Inner implementation with enrichment control.
@@ -1487,6 +1617,7 @@ impl MappingEngine {
         &self,
         tree: &AssembledTree,
         enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
     ) -> (
         serde_json::Value,
         std::collections::HashMap<String, Vec<usize>>,
@@ -1497,6 +1628,12 @@ impl MappingEngine {
 
         for def in &self.definitions {
             let entity = &def.meta.entity;
+            let trace_mark = trace.as_ref().map_or(0, |t| t.len());
+            let extract = |instance: &AssembledGroupInstance, trace: Option<&mut MappingTrace>| {
+                let mut r = serde_json::Map::new();
+                self.extract_instance(instance, def, &mut r, enrich_codes, trace);
+                serde_json::Value::Object(r)
+            };
 
             let bo4e = if let Some(ref disc) = def.meta.discriminator {
                 // Has discriminator — resolve to matching rep(s).
@@ -1508,45 +1645,38 @@ impl MappingEngine {
                     .source_path
                     .as_ref()
                     .is_some_and(|sp| has_source_path_qualifiers(sp));
-                if use_source_path {
-                    // Navigate via source_path, then filter by discriminator.
+                let bo4e = if use_source_path {
+                    // Navigate via source_path, filtering the leaf by discriminator
+                    // (respects #N occurrence).
                     let sp = def.meta.source_path.as_deref().unwrap();
-                    let all_instances = Self::resolve_all_by_source_path(tree, sp);
-                    // Apply discriminator filter to resolved instances (respects #N occurrence)
-                    let instances: Vec<_> = if let Some(matcher) = DiscriminatorMatcher::parse(disc)
-                    {
-                        matcher.filter_instances(all_instances)
-                    } else {
-                        all_instances
-                    };
-                    let extract = |instance: &AssembledGroupInstance| {
-                        let mut r = serde_json::Map::new();
-                        self.extract_fields_from_instance(instance, def, &mut r, enrich_codes);
-                        self.extract_companion_fields(instance, def, &mut r, enrich_codes);
-                        serde_json::Value::Object(r)
+                    let instances = match discriminator_predicate(disc) {
+                        Some((predicate, occurrence)) => {
+                            let mut query = source_path_query(sp, false);
+                            if let Some(leaf) = query.steps.last_mut() {
+                                leaf.predicates.push(predicate);
+                            }
+                            let matches = query.select(tree);
+                            let instances = matches.iter().filter_map(QueryMatch::group).collect();
+                            nth_occurrence(instances, occurrence)
+                        }
+                        None => Self::resolve_all_by_source_path(tree, sp),
                     };
-                    match instances.len() {
-                        0 => None,
-                        1 => Some(extract(instances[0])),
-                        _ => Some(serde_json::Value::Array(
-                            instances.iter().map(|i| extract(i)).collect(),
-                        )),
-                    }
+                    Self::collect_items(&instances, trace.as_deref_mut(), |i, t| extract(i, t))
                 } else {
                     let reps = Self::resolve_all_repetitions(tree, &def.meta.source_group, disc);
-                    match reps.len() {
-                        0 => None,
-                        1 => Some(self.map_forward_inner(tree, def, reps[0], enrich_codes)),
-                        _ => Some(serde_json::Value::Array(
-                            reps.iter()
-                                .map(|&rep| self.map_forward_inner(tree, def, rep, enrich_codes))
-                                .collect(),
-                        )),
-                    }
+                    Self::collect_items(&reps, trace.as_deref_mut(), |&rep, t| {
+                        self.map_forward_inner(tree, def, rep, enrich_codes, t)
+                    })
+                };
+                if bo4e.is_none() {
+                    trace::record(trace.as_deref_mut(), || {
+                        TraceEntry::for_field(def, "", TraceOutcome::DiscriminatorMismatch)
+                    });
                 }
+                bo4e
             } else if def.meta.source_group.is_empty() {
                 // Root-level mapping — always single object
-                Some(self.map_forward_inner(tree, def, 0, enrich_codes))
+                Some(self.map_forward_inner(tree, def, 0, enrich_codes, trace.as_deref_mut()))
             } else if def.meta.source_path.as_ref().is_some_and(|sp| {
                 has_source_path_qualifiers(sp) || def.meta.source_group.contains('.')
             }) {
@@ -1556,12 +1686,6 @@ impl MappingEngine {
                 // "sg17.sg36.sg40") where multiple parent reps each have children.
                 let sp = def.meta.source_path.as_deref().unwrap();
                 let indexed = Self::resolve_all_with_parent_indices(tree, sp);
-                let extract = |instance: &AssembledGroupInstance| {
-                    let mut r = serde_json::Map::new();
-                    self.extract_fields_from_instance(instance, def, &mut r, enrich_codes);
-                    self.extract_companion_fields(instance, def, &mut r, enrich_codes);
-                    serde_json::Value::Object(r)
-                };
                 // Track parent rep indices for nesting reconstruction.
                 // Key by source_path (not entity or source_group) so that definitions
                 // at different depths or with different qualifiers don't collide.
@@ -1573,30 +1697,26 @@ impl MappingEngine {
                         nesting_info.entry(sp.clone()).or_insert(parent_indices);
                     }
                 }
-                match indexed.len() {
-                    0 => None,
-                    1 => Some(extract(indexed[0].1)),
-                    _ => Some(serde_json::Value::Array(
-                        indexed.iter().map(|(_, i)| extract(i)).collect(),
-                    )),
-                }
+                Self::collect_items(&indexed, trace.as_deref_mut(), |&(_, i), t| extract(i, t))
             } else {
                 let num_reps = Self::count_repetitions(tree, &def.meta.source_group);
                 if num_reps <= 1 {
-                    Some(self.map_forward_inner(tree, def, 0, enrich_codes))
+                    Some(self.map_forward_inner(tree, def, 0, enrich_codes, trace.as_deref_mut()))
                 } else {
                     // Multiple reps, no discriminator — map all into array
-                    let mut items = Vec::with_capacity(num_reps);
-                    for rep in 0..num_reps {
-                        items.push(self.map_forward_inner(tree, def, rep, enrich_codes));
-                    }
-                    Some(serde_json::Value::Array(items))
+                    let reps: Vec<usize> = (0..num_reps).collect();
+                    Self::collect_items(&reps, trace.as_deref_mut(), |&rep, t| {
+                        self.map_forward_inner(tree, def, rep, enrich_codes, t)
+                    })
                 }
             };
 
+            let key = to_camel_case(entity);
+            if let Some(t) = trace.as_deref_mut() {
+                t.prefix_targets(trace_mark, &key);
+            }
             if let Some(bo4e) = bo4e {
                 let bo4e = inject_bo4e_metadata(bo4e, &def.meta.bo4e_type);
-                let key = to_camel_case(entity);
                 deep_merge_insert(&mut result, &key, bo4e);
             }
         }
@@ -1604,6 +1724,34 @@ impl MappingEngine {
         (serde_json::Value::Object(result), nesting_info)
     }
 
+    /// Map each item with `map_item`: no items yield `None`, one item its
+    /// object, several items an array. Trace targets of array elements are
+    /// prefixed with the element index.
+    fn collect_items<T>(
+        items: &[T],
+        mut trace: Option<&mut MappingTrace>,
+        mut map_item: impl FnMut(&T, Option<&mut MappingTrace>) -> serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        match items {
+            [] => None,
+            [item] => Some(map_item(item, trace)),
+            _ => Some(serde_json::Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, item)| {
+                        let mark = trace.as_ref().map_or(0, |t| t.len());
+                        let value = map_item(item, trace.as_deref_mut());
+                        if let Some(t) = trace.as_deref_mut() {
+                            t.prefix_targets(mark, &idx.to_string());
+                        }
+                        value
+                    })
+                    .collect(),
+            )),
+        }
+    }
+
     /// Reverse-map a BO4E entity map back to an AssembledTree.
     ///
     /// For each definition:
@@ -1616,12 +1764,22 @@ impl MappingEngine {
         &self,
         entities: &serde_json::Value,
         nesting_info: Option<&std::collections::HashMap<String, Vec<usize>>>,
-    ) -> AssembledTree {
+    ) -> AssembledTree<'static> {
+        self.map_all_reverse_inner(entities, nesting_info, None)
+    }
+
+    fn map_all_reverse_inner(
+        &self,
+        entities: &serde_json::Value,
+        nesting_info: Option<&std::collections::HashMap<String, Vec<usize>>>,
+        mut trace: Option<&mut MappingTrace>,
+    ) -> AssembledTree<'static> {
         let mut root_segments: Vec<AssembledSegment> = Vec::new();
         let mut groups: Vec<AssembledGroup> = Vec::new();
 
         for def in &self.definitions {
             let entity_key = to_camel_case(&def.meta.entity);
+            let trace_mark = trace.as_ref().map_or(0, |t| t.len());
 
             // Look up entity value
             let entity_value = entities.get(&entity_key);
@@ -1641,12 +1799,23 @@ impl MappingEngine {
 
             if def.meta.source_group.is_empty() {
                 // Root-level: reverse into root segments
-                let instance = self.map_reverse(entity_value, def);
+                let instance = self.map_reverse_inner(entity_value, def, trace.as_deref_mut());
                 root_segments.extend(instance.segments);
             } else if entity_value.is_array() {
                 // Array entity: each element becomes a group repetition
                 let arr = entity_value.as_array().unwrap();
-                let reps: Vec<_> = arr.iter().map(|item| self.map_reverse(item, def)).collect();
+                let reps: Vec<_> = arr
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, item)| {
+                        let mark = trace.as_ref().map_or(0, |t| t.len());
+                        let instance = self.map_reverse_inner(item, def, trace.as_deref_mut());
+                        if let Some(t) = trace.as_deref_mut() {
+                            t.prefix_targets(mark, &idx.to_string());
+                        }
+                        instance
+                    })
+                    .collect();
 
                 // Merge into existing group or create new one
                 if let Some(existing) = groups.iter_mut().find(|g| g.group_id == leaf_group) {
@@ -1659,7 +1828,7 @@ impl MappingEngine {
                 }
             } else {
                 // Single object: one repetition
-                let instance = self.map_reverse(entity_value, def);
+                let instance = self.map_reverse_inner(entity_value, def, trace.as_deref_mut());
 
                 if let Some(existing) = groups.iter_mut().find(|g| g.group_id == leaf_group) {
                     existing.repetitions.push(instance);
@@ -1670,6 +1839,9 @@ impl MappingEngine {
                     });
                 }
             }
+            if let Some(t) = trace.as_deref_mut() {
+                t.prefix_targets(trace_mark, &entity_key);
+            }
         }
 
         // Post-process: move nested groups under their parent repetitions.
@@ -1748,46 +1920,8 @@ impl MappingEngine {
 
     /// Count the number of repetitions available for a group path in the tree.
     fn count_repetitions(tree: &AssembledTree, group_path: &str) -> usize {
-        let parts: Vec<&str> = group_path.split('.').collect();
-
-        let (first_id, first_rep) = parse_group_spec(parts[0]);
-        let first_group = match tree.groups.iter().find(|g| g.group_id == first_id) {
-            Some(g) => g,
-            None => return 0,
-        };
-
-        if parts.len() == 1 {
-            return first_group.repetitions.len();
-        }
-
-        // Navigate to parent, then count leaf group reps
-        let mut current_instance = match first_group.repetitions.get(first_rep.unwrap_or(0)) {
-            Some(i) => i,
-            None => return 0,
-        };
-
-        for (i, part) in parts[1..].iter().enumerate() {
-            let (group_id, explicit_rep) = parse_group_spec(part);
-            let child_group = match current_instance
-                .child_groups
-                .iter()
-                .find(|g| g.group_id == group_id)
-            {
-                Some(g) => g,
-                None => return 0,
-            };
-
-            if i == parts.len() - 2 {
-                // Last part — return rep count
-                return child_group.repetitions.len();
-            }
-            current_instance = match child_group.repetitions.get(explicit_rep.unwrap_or(0)) {
-                Some(i) => i,
-                None => return 0,
-            };
-        }
-
-        0
+        let (steps, _) = group_path_steps(group_path);
+        Query::new(steps).select(tree).len()
     }
 
     /// Map an assembled tree into message-level and transaction-level results.
@@ -1798,15 +1932,97 @@ impl MappingEngine {
     /// - `transaction_group`: The group ID that represents transactions (e.g., "SG4")
     ///
     /// Returns a `MappedMessage` with message stammdaten and per-transaction results.
+    /// To map whole interchanges, functional groups included, see
+    /// [`map_interchanges`](Self::map_interchanges).
     pub fn map_interchange(
         msg_engine: &MappingEngine,
         tx_engine: &MappingEngine,
         tree: &AssembledTree,
         transaction_group: &str,
         enrich_codes: bool,
+    ) -> crate::model::MappedMessage {
+        Self::map_interchange_inner(
+            msg_engine,
+            tx_engine,
+            tree,
+            transaction_group,
+            enrich_codes,
+            None,
+        )
+    }
+
+    /// Map split interchanges into one [`Interchange`](crate::Interchange)
+    /// per UNB.
+    ///
+    /// - `chunks`: Interchanges from [`mig_assembly::split_interchanges`]
+    /// - `transaction_group`: The group ID that represents transactions (e.g., "SG4")
+    /// - `resolve`: Assembles a message and selects its engines, as for
+    ///   [`NachrichtStream`](crate::NachrichtStream)
+    ///
+    /// Messages of UNG/UNE functional groups end up in the interchange's
+    /// `gruppen`. Stops at the first message `resolve` fails for.
+    pub fn map_interchanges<'e, F>(
+        chunks: &[mig_assembly::InterchangeChunks],
+        transaction_group: &str,
+        enrich_codes: bool,
+        mut resolve: F,
+    ) -> Result<Vec<crate::Interchange>, MappingError>
+    where
+        F: FnMut(&mig_assembly::MessageChunk) -> Result<crate::MessageMapping<'e>, MappingError>,
+    {
+        chunks
+            .iter()
+            .map(|interchange| {
+                let nachrichten = interchange
+                    .messages
+                    .iter()
+                    .map(|chunk| {
+                        let mapping = resolve(chunk)?;
+                        Ok(mapping.map_chunk(chunk, transaction_group, enrich_codes))
+                    })
+                    .collect::<Result<_, MappingError>>()?;
+                Ok(crate::Interchange::from_chunks(interchange, nachrichten))
+            })
+            .collect()
+    }
+
+    /// Like [`map_interchange`](Self::map_interchange), recording every field
+    /// mapping in `trace`. Target pointers are relative to the returned
+    /// message (e.g., `/transaktionen/0/stammdaten/marktlokation/marktlokationsId`).
+    pub fn map_interchange_traced(
+        msg_engine: &MappingEngine,
+        tx_engine: &MappingEngine,
+        tree: &AssembledTree,
+        transaction_group: &str,
+        enrich_codes: bool,
+        trace: &mut MappingTrace,
+    ) -> crate::model::MappedMessage {
+        trace.number_segments(tree);
+        Self::map_interchange_inner(
+            msg_engine,
+            tx_engine,
+            tree,
+            transaction_group,
+            enrich_codes,
+            Some(trace),
+        )
+    }
+
+    fn map_interchange_inner(
+        msg_engine: &MappingEngine,
+        tx_engine: &MappingEngine,
+        tree: &AssembledTree,
+        transaction_group: &str,
+        enrich_codes: bool,
+        mut trace: Option<&mut MappingTrace>,
     ) -> crate::model::MappedMessage {
         // Map message-level entities (also captures nesting distribution info)
-        let (stammdaten, nesting_info) = msg_engine.map_all_forward_inner(tree, enrich_codes);
+        let trace_mark = trace.as_ref().map_or(0, |t| t.len());
+        let (stammdaten, nesting_info) =
+            msg_engine.map_all_forward_inner(tree, enrich_codes, trace.as_deref_mut());
+        if let Some(t) = trace.as_deref_mut() {
+            t.prefix_targets(trace_mark, "stammdaten");
+        }
 
         // Find the transaction group and map each repetition
         let transaktionen = tree
@@ -1816,7 +2032,8 @@ impl MappingEngine {
             .map(|sg| {
                 sg.repetitions
                     .iter()
-                    .map(|instance| {
+                    .enumerate()
+                    .map(|(tx_idx, instance)| {
                         // Wrap the instance in its group so that definitions with
                         // source_group paths like "SG4.SG5" can resolve correctly.
                         let wrapped_tree = AssembledTree {
@@ -1829,8 +2046,20 @@ impl MappingEngine {
                             inter_group_segments: std::collections::BTreeMap::new(),
                         };
 
-                        let (tx_result, tx_nesting) =
-                            tx_engine.map_all_forward_inner(&wrapped_tree, enrich_codes);
+                        let trace_mark = trace.as_ref().map_or(0, |t| t.len());
+                        if let Some(t) = trace.as_deref_mut() {
+                            t.alias_instance(instance, &wrapped_tree.groups[0].repetitions[0]);
+                        }
+                        let (tx_result, tx_nesting) = tx_engine.map_all_forward_inner(
+                            &wrapped_tree,
+                            enrich_codes,
+                            trace.as_deref_mut(),
+                        );
+                        if let Some(t) = trace.as_deref_mut() {
+                            t.map_targets(trace_mark, |target| {
+                                route_transaction_pointer(tx_idx, target)
+                            });
+                        }
 
                         // Split: "Prozessdaten" entity goes into transaktionsdaten,
                         // everything else into stammdaten
@@ -1893,16 +2122,60 @@ impl MappingEngine {
         mapped: &crate::model::MappedMessage,
         transaction_group: &str,
         filtered_mig: Option<&MigSchema>,
-    ) -> AssembledTree {
+    ) -> AssembledTree<'static> {
+        Self::map_interchange_reverse_inner(
+            msg_engine,
+            tx_engine,
+            mapped,
+            transaction_group,
+            filtered_mig,
+            None,
+        )
+    }
+
+    /// Like [`map_interchange_reverse`](Self::map_interchange_reverse), recording
+    /// every field mapping in `trace`. Target pointers name the BO4E values read,
+    /// relative to `mapped` (e.g., `/transaktionen/0/stammdaten/marktlokation/marktlokationsId`).
+    pub fn map_interchange_reverse_traced(
+        msg_engine: &MappingEngine,
+        tx_engine: &MappingEngine,
+        mapped: &crate::model::MappedMessage,
+        transaction_group: &str,
+        filtered_mig: Option<&MigSchema>,
+        trace: &mut MappingTrace,
+    ) -> AssembledTree<'static> {
+        Self::map_interchange_reverse_inner(
+            msg_engine,
+            tx_engine,
+            mapped,
+            transaction_group,
+            filtered_mig,
+            Some(trace),
+        )
+    }
+
+    fn map_interchange_reverse_inner(
+        msg_engine: &MappingEngine,
+        tx_engine: &MappingEngine,
+        mapped: &crate::model::MappedMessage,
+        transaction_group: &str,
+        filtered_mig: Option<&MigSchema>,
+        mut trace: Option<&mut MappingTrace>,
+    ) -> AssembledTree<'static> {
         // Step 1: Reverse message-level stammdaten (pass nesting info for child distribution)
-        let msg_tree = msg_engine.map_all_reverse(
+        let trace_mark = trace.as_ref().map_or(0, |t| t.len());
+        let msg_tree = msg_engine.map_all_reverse_inner(
             &mapped.stammdaten,
             if mapped.nesting_info.is_empty() {
                 None
             } else {
                 Some(&mapped.nesting_info)
             },
+            trace.as_deref_mut(),
         );
+        if let Some(t) = trace.as_deref_mut() {
+            t.prefix_targets(trace_mark, "stammdaten");
+        }
 
         // Step 2: Build transaction instances from each Transaktion
         let mut sg4_reps: Vec<AssembledGroupInstance> = Vec::new();
@@ -1990,7 +2263,7 @@ impl MappingEngine {
             sorted_defs.sort_by(|a, b| a.depth.cmp(&b.depth).then(a.relative.cmp(&b.relative)));
         }
 
-        for tx in &mapped.transaktionen {
+        for (tx_idx, tx) in mapped.transaktionen.iter().enumerate() {
             let mut root_segs: Vec<AssembledSegment> = Vec::new();
             let mut child_groups: Vec<AssembledGroup> = Vec::new();
 
@@ -2003,12 +2276,18 @@ impl MappingEngine {
 
             for dm in &sorted_defs {
                 // Determine the BO4E value to reverse-map from
-                let bo4e_value = if dm.is_transaktionsdaten {
-                    &tx.transaktionsdaten
+                let entity_key = to_camel_case(&dm.def.meta.entity);
+                let (bo4e_value, trace_prefix) = if dm.is_transaktionsdaten {
+                    (
+                        &tx.transaktionsdaten,
+                        format!("/transaktionen/{tx_idx}/transaktionsdaten"),
+                    )
                 } else {
-                    let entity_key = to_camel_case(&dm.def.meta.entity);
                     match tx.stammdaten.get(&entity_key) {
-                        Some(v) => v,
+                        Some(v) => (
+                            v,
+                            format!("/transaktionen/{tx_idx}/stammdaten/{entity_key}"),
+                        ),
                         None => continue,
                     }
                 };
@@ -2023,7 +2302,16 @@ impl MappingEngine {
                 };
 
                 for (item_idx, item) in items.iter().enumerate() {
-                    let instance = tx_engine.map_reverse(item, dm.def);
+                    let trace_mark = trace.as_ref().map_or(0, |t| t.len());
+                    let instance = tx_engine.map_reverse_inner(item, dm.def, trace.as_deref_mut());
+                    if let Some(t) = trace.as_deref_mut() {
+                        let prefix = if bo4e_value.is_array() {
+                            format!("{trace_prefix}/{item_idx}")
+                        } else {
+                            trace_prefix.clone()
+                        };
+                        t.map_targets(trace_mark, |target| format!("{prefix}{target}"));
+                    }
 
                     // Skip empty instances (definition had no real BO4E data)
                     if instance.segments.is_empty() && instance.child_groups.is_empty() {
@@ -2214,7 +2502,7 @@ impl MappingEngine {
         &self,
         bo4e_value: &serde_json::Value,
         def: &MappingDefinition,
-    ) -> AssembledGroup {
+    ) -> AssembledGroup<'static> {
         let instance = self.map_reverse(bo4e_value, def);
         let leaf_group = def
             .meta
@@ -2230,24 +2518,34 @@ impl MappingEngine {
     }
 }
 
-/// Parse a group path part with optional repetition: "SG8:1" → ("SG8", Some(1)).
-/// Parse a source_path part into (group_id, optional_qualifier).
+/// Query for a source_path with qualifier suffixes.
 ///
-/// `"sg8_z98"` → `("sg8", Some("z98"))`
-/// `"sg4"` → `("sg4", None)`
-/// `"sg10"` → `("sg10", None)`
-fn parse_source_path_part(part: &str) -> (&str, Option<&str>) {
-    // Find the first underscore that separates group from qualifier.
-    // Source path parts look like "sg8_z98", "sg4", "sg10", "sg12_z04".
-    // The group ID is always "sgN", so the underscore after the digits is the separator.
-    if let Some(pos) = part.find('_') {
-        let group = &part[..pos];
-        let qualifier = &part[pos + 1..];
-        if !qualifier.is_empty() {
-            return (group, Some(qualifier));
-        }
-    }
-    (part, None)
+/// `"sg4.sg8_z98.sg10"` → `SG4/SG8[0.0=Z98]/SG10`: a `_qualifier` suffix keeps
+/// the repetitions whose entry segment has that qualifier in `elements[0][0]`,
+/// compound suffixes like `sg8_za1_za2` accept any of them. With `first_only`
+/// every step keeps only its first match.
+fn source_path_query(source_path: &str, first_only: bool) -> Query {
+    let steps = source_path
+        .split('.')
+        .map(|part| {
+            let mut step = match part.split_once('_') {
+                Some((group, qualifiers)) if !qualifiers.is_empty() => {
+                    Step::group(group.to_ascii_uppercase()).with(QueryPredicate::value_eq(
+                        None,
+                        0,
+                        0,
+                        qualifiers.split('_').map(str::to_ascii_uppercase),
+                    ))
+                }
+                _ => Step::group(part.to_ascii_uppercase()),
+            };
+            if first_only {
+                step = step.with(QueryPredicate::Index(0));
+            }
+            step
+        })
+        .collect();
+    Query::new(steps)
 }
 
 /// Build a map from group ID (e.g., "SG5", "SG8") to its position index
@@ -2263,45 +2561,6 @@ fn build_reverse_mig_group_order(mig: &MigSchema, tx_group_id: &str) -> HashMap<
     order
 }
 
-/// Find a group repetition whose entry segment has a matching qualifier.
-///
-/// The entry segment is the first segment in the instance (e.g., SEQ for SG8).
-/// The qualifier is matched against `elements[0][0]` (case-insensitive).
-fn find_rep_by_entry_qualifier<'a>(
-    reps: &'a [AssembledGroupInstance],
-    qualifier: &str,
-) -> Option<&'a AssembledGroupInstance> {
-    // Support compound qualifiers like "za1_za2" — match any part.
-    let parts: Vec<&str> = qualifier.split('_').collect();
-    reps.iter().find(|inst| {
-        inst.segments.first().is_some_and(|seg| {
-            seg.elements
-                .first()
-                .and_then(|e| e.first())
-                .is_some_and(|v| parts.iter().any(|part| v.eq_ignore_ascii_case(part)))
-        })
-    })
-}
-
-/// Find ALL repetitions whose entry segment qualifier matches (case-insensitive).
-fn find_all_reps_by_entry_qualifier<'a>(
-    reps: &'a [AssembledGroupInstance],
-    qualifier: &str,
-) -> Vec<&'a AssembledGroupInstance> {
-    // Support compound qualifiers like "za1_za2" — match any part.
-    let parts: Vec<&str> = qualifier.split('_').collect();
-    reps.iter()
-        .filter(|inst| {
-            inst.segments.first().is_some_and(|seg| {
-                seg.elements
-                    .first()
-                    .and_then(|e| e.first())
-                    .is_some_and(|v| parts.iter().any(|part| v.eq_ignore_ascii_case(part)))
-            })
-        })
-        .collect()
-}
-
 /// Check if a source_path contains qualifier suffixes (e.g., "sg8_z98").
 fn has_source_path_qualifiers(source_path: &str) -> bool {
     source_path.split('.').any(|part| {
@@ -2313,6 +2572,7 @@ fn has_source_path_qualifiers(source_path: &str) -> bool {
     })
 }
 
+/// Parse a group path part with optional repetition: "SG8:1" → ("SG8", Some(1)).
 fn parse_group_spec(part: &str) -> (&str, Option<usize>) {
     if let Some(colon_pos) = part.find(':') {
         let id = &part[..colon_pos];
@@ -2323,6 +2583,38 @@ fn parse_group_spec(part: &str) -> (&str, Option<usize>) {
     }
 }
 
+/// Query steps for a group path with optional `:N` repetitions.
+///
+/// `"SG4.SG8:1.SG10"` → `SG4[0]/SG8[1]/SG10`: groups before the last default
+/// to repetition 0. The last group's `:N`, if any, is returned instead of
+/// being applied.
+fn group_path_steps(group_path: &str) -> (Vec<Step>, Option<usize>) {
+    let parts: Vec<&str> = group_path.split('.').collect();
+    let (leaf_id, leaf_rep) = parse_group_spec(parts[parts.len() - 1]);
+    let mut steps: Vec<Step> = parts[..parts.len() - 1]
+        .iter()
+        .map(|part| {
+            let (id, rep) = parse_group_spec(part);
+            Step::group(id).with(QueryPredicate::Index(rep.unwrap_or(0)))
+        })
+        .collect();
+    steps.push(Step::group(leaf_id));
+    (steps, leaf_rep)
+}
+
+/// Move a trace pointer relative to a transaction's entity map to its place in
+/// the `MappedMessage`: "prozessdaten"/"nachricht" entities are merged into
+/// `transaktionsdaten`, all others live under `stammdaten`.
+fn route_transaction_pointer(tx_idx: usize, target: &str) -> String {
+    let entity = target.split('/').nth(1).unwrap_or("");
+    if entity == "prozessdaten" || entity == "nachricht" {
+        let rest = &target[1 + entity.len()..];
+        format!("/transaktionen/{tx_idx}/transaktionsdaten{rest}")
+    } else {
+        format!("/transaktionen/{tx_idx}/stammdaten{target}")
+    }
+}
+
 /// Strip the transaction group prefix from a source_group path.
 ///
 /// Given `source_group = "SG4.SG8:0.SG10"` and `tx_group = "SG4"`,
@@ -2345,10 +2637,10 @@ fn strip_tx_group_prefix(source_group: &str, tx_group: &str) -> String {
 /// - `"SG8:0.SG10"` → SG10 inside SG8 repetition 0
 ///
 /// Returns the repetition index used at the first nesting level.
-fn place_in_groups(
-    groups: &mut Vec<AssembledGroup>,
+fn place_in_groups<'a>(
+    groups: &mut Vec<AssembledGroup<'a>>,
     relative_path: &str,
-    instance: AssembledGroupInstance,
+    instance: AssembledGroupInstance<'a>,
 ) -> usize {
     let parts: Vec<&str> = relative_path.split('.').collect();
 
@@ -2472,63 +2764,34 @@ fn resolve_child_relative(
     relative.to_string()
 }
 
-/// Parsed discriminator for filtering assembled group instances.
+/// Parse a discriminator into a query predicate and its optional occurrence index.
 ///
 /// Discriminator format: "TAG.element_idx.component_idx=VALUE" or
 /// "TAG.element_idx.component_idx=VAL1|VAL2" (pipe-separated multi-value).
-/// E.g., "LOC.0.0=Z17" → match LOC segments where elements[0][0] == "Z17"
-/// E.g., "RFF.0.0=Z49|Z53" → match RFF where elements[0][0] is Z49 OR Z53
-struct DiscriminatorMatcher<'a> {
-    tag: &'a str,
-    element_idx: usize,
-    component_idx: usize,
-    expected_values: Vec<&'a str>,
-    /// Optional occurrence index: `#N` selects the Nth match among instances.
-    occurrence: Option<usize>,
+/// E.g., "LOC.0.0=Z17" → instances with a LOC segment where elements[0][0] == "Z17"
+/// E.g., "RFF.0.0=Z49|Z53" → instances with an RFF where elements[0][0] is Z49 OR Z53
+fn discriminator_predicate(discriminator: &str) -> Option<(QueryPredicate, Option<usize>)> {
+    let (spec, expected) = discriminator.split_once('=')?;
+    let parts: Vec<&str> = spec.split('.').collect();
+    let [tag, element_idx, component_idx] = parts[..] else {
+        return None;
+    };
+    let (expected_raw, occurrence) = parse_discriminator_occurrence(expected);
+    let predicate = QueryPredicate::value_eq(
+        Some(tag),
+        element_idx.parse().ok()?,
+        component_idx.parse().ok()?,
+        expected_raw.split('|'),
+    );
+    Some((predicate, occurrence))
 }
 
-impl<'a> DiscriminatorMatcher<'a> {
-    fn parse(disc: &'a str) -> Option<Self> {
-        let (spec, expected) = disc.split_once('=')?;
-        let parts: Vec<&str> = spec.split('.').collect();
-        if parts.len() != 3 {
-            return None;
-        }
-        let (expected_raw, occurrence) = parse_discriminator_occurrence(expected);
-        Some(Self {
-            tag: parts[0],
-            element_idx: parts[1].parse().ok()?,
-            component_idx: parts[2].parse().ok()?,
-            expected_values: expected_raw.split('|').collect(),
-            occurrence,
-        })
-    }
-
-    fn matches(&self, instance: &AssembledGroupInstance) -> bool {
-        instance.segments.iter().any(|s| {
-            s.tag.eq_ignore_ascii_case(self.tag)
-                && s.elements
-                    .get(self.element_idx)
-                    .and_then(|e| e.get(self.component_idx))
-                    .map(|v| self.expected_values.iter().any(|ev| v == ev))
-                    .unwrap_or(false)
-        })
-    }
-
-    /// Filter instances, respecting the occurrence index if present.
-    fn filter_instances<'b>(
-        &self,
-        instances: Vec<&'b AssembledGroupInstance>,
-    ) -> Vec<&'b AssembledGroupInstance> {
-        let matching: Vec<_> = instances
-            .into_iter()
-            .filter(|inst| self.matches(inst))
-            .collect();
-        if let Some(occ) = self.occurrence {
-            matching.into_iter().nth(occ).into_iter().collect()
-        } else {
-            matching
-        }
+/// Keep only the `occurrence`-th item (counted across all parents), or all
+/// items without an occurrence index.
+fn nth_occurrence<T>(items: Vec<T>, occurrence: Option<usize>) -> Vec<T> {
+    match occurrence {
+        Some(occ) => items.into_iter().nth(occ).into_iter().collect(),
+        None => items,
     }
 }
 
@@ -2569,6 +2832,86 @@ fn strip_all_rep_indices(relative: &str) -> String {
         .join(".")
 }
 
+/// Fail with [`MappingError::UnknownHandler`] if `def` references a complex
+/// handler that is not registered in either direction.
+/// Build the trace entry for one reverse field mapping.
+///
+/// `input` is the BO4E value found at `target`, `output` the EDIFACT value
+/// produced (mapped or default). Qualifier-only fields without a default
+/// produce nothing and are not traced.
+fn reverse_trace_entry(
+    def: &MappingDefinition,
+    path: &str,
+    target: &str,
+    pointer: String,
+    input: Option<String>,
+    output: &Option<String>,
+) -> Option<TraceEntry> {
+    let outcome = match (target.is_empty(), &input, output) {
+        (true, _, None) => return None,
+        (false, Some(_), _) => TraceOutcome::Mapped,
+        (_, _, Some(_)) => TraceOutcome::DefaultApplied,
+        (false, None, None) => TraceOutcome::EmptyValue,
+    };
+    let mut entry = TraceEntry::for_field(def, path, outcome);
+    entry.target = pointer;
+    entry.value = input;
+    entry.mapped_value = output.clone();
+    Some(entry)
+}
+
+fn check_handlers(
+    def: &MappingDefinition,
+    handlers: &HandlerRegistry,
+    file: &str,
+) -> Result<(), MappingError> {
+    for handler in def.complex_handlers.iter().flatten() {
+        if !handlers.has_handler(&handler.name) {
+            return Err(MappingError::UnknownHandler {
+                name: handler.name.clone(),
+                file: file.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A reverse-mapped value: (segment key, segment tag, element index, component index, value).
+/// The segment key includes the qualifier (e.g., "DTM[92]") to keep segment instances apart.
+type FieldValue = (String, String, usize, usize, String);
+
+/// Build segments from reverse-mapped field values, placing each value at its
+/// element/component position. Values sharing a segment key land in the same segment.
+fn build_segments(field_values: &[FieldValue]) -> Vec<AssembledSegment<'static>> {
+    let mut segments: Vec<AssembledSegment> = Vec::with_capacity(field_values.len());
+    let mut seen_keys: HashMap<String, usize> = HashMap::new();
+
+    for (seg_key, seg_tag, element_idx, component_idx, val) in field_values {
+        let seg = if let Some(&pos) = seen_keys.get(seg_key) {
+            &mut segments[pos]
+        } else {
+            let pos = segments.len();
+            seen_keys.insert(seg_key.clone(), pos);
+            segments.push(AssembledSegment {
+                tag: Cow::Owned(seg_tag.clone()),
+                elements: vec![],
+                location: None,
+            });
+            &mut segments[pos]
+        };
+
+        while seg.elements.len() <= *element_idx {
+            seg.elements.push(vec![]);
+        }
+        while seg.elements[*element_idx].len() <= *component_idx {
+            seg.elements[*element_idx].push(Cow::Borrowed(""));
+        }
+        seg.elements[*element_idx][*component_idx] = Cow::Owned(val.clone());
+    }
+
+    segments
+}
+
 /// Parse a segment tag with optional qualifier and occurrence index.
 ///
 /// - `"dtm[92]"`    → `("DTM", Some("92"), 0)` — first (default) occurrence
@@ -2755,6 +3098,10 @@ pub struct VariantCache {
     /// Eliminates the need to parse AHB XML files at startup.
     #[serde(default)]
     pub pid_segment_numbers: HashMap<String, Vec<String>>,
+    /// Per-PID assembly automata compiled from the PID-filtered MIG (key: "pid_55001").
+    /// Saves recompiling the MIG for every message.
+    #[serde(default)]
+    pub compiled_migs: HashMap<String, mig_assembly::CompiledMig>,
 }
 
 impl VariantCache {
@@ -2800,6 +3147,7 @@ mod tests {
             fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         }
     }
 
@@ -2811,12 +3159,14 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
-                    elements: vec![vec!["001".to_string()]],
+                    tag: "UNH".into(),
+                    elements: vec![vec!["001".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![vec!["E01".to_string()], vec!["DOC001".to_string()]],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()], vec!["DOC001".into()]],
+                    location: None,
                 },
             ],
             groups: vec![
@@ -2824,8 +3174,9 @@ mod tests {
                     group_id: "SG2".to_string(),
                     repetitions: vec![AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "NAD".to_string(),
-                            elements: vec![vec!["MS".to_string()], vec!["9900123".to_string()]],
+                            tag: "NAD".into(),
+                            elements: vec![vec!["MS".into()], vec!["9900123".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
@@ -2835,18 +3186,20 @@ mod tests {
                     group_id: "SG4".to_string(),
                     repetitions: vec![AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "IDE".to_string(),
-                            elements: vec![vec!["24".to_string()], vec!["TX001".to_string()]],
+                            tag: "IDE".into(),
+                            elements: vec![vec!["24".into()], vec!["TX001".into()]],
+                            location: None,
                         }],
                         child_groups: vec![AssembledGroup {
                             group_id: "SG5".to_string(),
                             repetitions: vec![AssembledGroupInstance {
                                 segments: vec![AssembledSegment {
-                                    tag: "LOC".to_string(),
+                                    tag: "LOC".into(),
                                     elements: vec![
-                                        vec!["Z16".to_string()],
-                                        vec!["DE000111222333".to_string()],
+                                        vec!["Z16".into()],
+                                        vec!["DE000111222333".into()],
                                     ],
+                                    location: None,
                                 }],
                                 child_groups: vec![],
                                 skipped_segments: vec![],
@@ -2888,6 +3241,7 @@ mod tests {
                 fields: tx_fields,
                 companion_fields: None,
                 complex_handlers: None,
+                source_file: None,
             },
             MappingDefinition {
                 meta: MappingMeta {
@@ -2901,6 +3255,7 @@ mod tests {
                 fields: malo_fields,
                 companion_fields: None,
                 complex_handlers: None,
+                source_file: None,
             },
         ]);
 
@@ -3015,12 +3370,14 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
-                    elements: vec![vec!["001".to_string()]],
+                    tag: "UNH".into(),
+                    elements: vec![vec!["001".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![vec!["E01".to_string()]],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()]],
+                    location: None,
                 },
             ],
             groups: vec![
@@ -3028,8 +3385,9 @@ mod tests {
                     group_id: "SG2".to_string(),
                     repetitions: vec![AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "NAD".to_string(),
-                            elements: vec![vec!["MS".to_string()], vec!["9900123".to_string()]],
+                            tag: "NAD".into(),
+                            elements: vec![vec!["MS".into()], vec!["9900123".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
@@ -3039,8 +3397,9 @@ mod tests {
                     group_id: "SG4".to_string(),
                     repetitions: vec![AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "IDE".to_string(),
-                            elements: vec![vec!["24".to_string()], vec!["TX001".to_string()]],
+                            tag: "IDE".into(),
+                            elements: vec![vec!["24".into()], vec!["TX001".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
@@ -3073,6 +3432,7 @@ mod tests {
             fields: msg_fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         };
 
         let engine = MappingEngine::from_definitions(vec![msg_def.clone()]);
@@ -3093,30 +3453,34 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
-                    elements: vec![vec!["001".to_string()]],
+                    tag: "UNH".into(),
+                    elements: vec![vec!["001".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![vec!["E01".to_string()]],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()]],
+                    location: None,
                 },
             ],
             groups: vec![AssembledGroup {
                 group_id: "SG4".to_string(),
                 repetitions: vec![AssembledGroupInstance {
                     segments: vec![AssembledSegment {
-                        tag: "IDE".to_string(),
-                        elements: vec![vec!["24".to_string()], vec!["TX001".to_string()]],
+                        tag: "IDE".into(),
+                        elements: vec![vec!["24".into()], vec!["TX001".into()]],
+                        location: None,
                     }],
                     child_groups: vec![AssembledGroup {
                         group_id: "SG5".to_string(),
                         repetitions: vec![AssembledGroupInstance {
                             segments: vec![AssembledSegment {
-                                tag: "LOC".to_string(),
+                                tag: "LOC".into(),
                                 elements: vec![
-                                    vec!["Z16".to_string()],
-                                    vec!["DE000111222333".to_string()],
+                                    vec!["Z16".into()],
+                                    vec!["DE000111222333".into()],
                                 ],
+                                location: None,
                             }],
                             child_groups: vec![],
                             skipped_segments: vec![],
@@ -3147,6 +3511,7 @@ mod tests {
             fields: proz_fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         };
 
         let mut malo_fields: IndexMap<String, FieldMapping> = IndexMap::new();
@@ -3166,6 +3531,7 @@ mod tests {
             fields: malo_fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         };
 
         let tx_engine = MappingEngine::from_definitions(vec![proz_def, malo_def]);
@@ -3200,12 +3566,14 @@ mod tests {
         let tree = AssembledTree {
             segments: vec![
                 AssembledSegment {
-                    tag: "UNH".to_string(),
-                    elements: vec![vec!["001".to_string()]],
+                    tag: "UNH".into(),
+                    elements: vec![vec!["001".into()]],
+                    location: None,
                 },
                 AssembledSegment {
-                    tag: "BGM".to_string(),
-                    elements: vec![vec!["E01".to_string()]],
+                    tag: "BGM".into(),
+                    elements: vec![vec!["E01".into()]],
+                    location: None,
                 },
             ],
             groups: vec![
@@ -3213,8 +3581,9 @@ mod tests {
                     group_id: "SG2".to_string(),
                     repetitions: vec![AssembledGroupInstance {
                         segments: vec![AssembledSegment {
-                            tag: "NAD".to_string(),
-                            elements: vec![vec!["MS".to_string()], vec!["9900123".to_string()]],
+                            tag: "NAD".into(),
+                            elements: vec![vec!["MS".into()], vec!["9900123".into()]],
+                            location: None,
                         }],
                         child_groups: vec![],
                         skipped_segments: vec![],
@@ -3225,16 +3594,18 @@ mod tests {
                     repetitions: vec![
                         AssembledGroupInstance {
                             segments: vec![AssembledSegment {
-                                tag: "IDE".to_string(),
-                                elements: vec![vec!["24".to_string()], vec!["TX001".to_string()]],
+                                tag: "IDE".into(),
+                                elements: vec![vec!["24".into()], vec!["TX001".into()]],
+                                location: None,
                             }],
                             child_groups: vec![],
                             skipped_segments: vec![],
                         },
                         AssembledGroupInstance {
                             segments: vec![AssembledSegment {
-                                tag: "IDE".to_string(),
-                                elements: vec![vec!["24".to_string()], vec!["TX002".to_string()]],
+                                tag: "IDE".into(),
+                                elements: vec![vec!["24".into()], vec!["TX002".into()]],
+                                location: None,
                             }],
                             child_groups: vec![],
                             skipped_segments: vec![],
@@ -3264,6 +3635,7 @@ mod tests {
             fields: msg_fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         }];
 
         // Transaction-level definitions (source_group includes SG4 prefix)
@@ -3284,6 +3656,7 @@ mod tests {
             fields: tx_fields,
             companion_fields: None,
             complex_handlers: None,
+            source_file: None,
         }];
 
         let msg_engine = MappingEngine::from_definitions(msg_defs);
@@ -3415,8 +3788,9 @@ mod tests {
                                 group_id: "SG10".to_string(),
                                 repetitions: vec![AssembledGroupInstance {
                                     segments: vec![AssembledSegment {
-                                        tag: "CCI".to_string(),
-                                        elements: vec![vec![], vec![], vec!["Z15".to_string()]],
+                                        tag: "CCI".into(),
+                                        elements: vec![vec![], vec![], vec!["Z15".into()]],
+                                        location: None,
                                     }],
                                     child_groups: vec![],
                                     skipped_segments: vec![],
@@ -3450,6 +3824,7 @@ mod tests {
             fields: IndexMap::new(),
             companion_fields: Some(companion_fields),
             complex_handlers: None,
+            source_file: None,
         };
 
         // Without code lookup — plain string
@@ -3527,8 +3902,9 @@ mod tests {
                                 group_id: "SG10".to_string(),
                                 repetitions: vec![AssembledGroupInstance {
                                     segments: vec![AssembledSegment {
-                                        tag: "CCI".to_string(),
-                                        elements: vec![vec![], vec![], vec!["Z15".to_string()]],
+                                        tag: "CCI".into(),
+                                        elements: vec![vec![], vec![], vec!["Z15".into()]],
+                                        location: None,
                                     }],
                                     child_groups: vec![],
                                     skipped_segments: vec![],
@@ -3562,6 +3938,7 @@ mod tests {
             fields: IndexMap::new(),
             companion_fields: Some(companion_fields),
             complex_handlers: None,
+            source_file: None,
         };
 
         let engine = MappingEngine::from_definitions(vec![]).with_code_lookup(code_lookup);
@@ -3597,6 +3974,7 @@ mod tests {
             fields: IndexMap::new(),
             companion_fields: Some(companion_fields),
             complex_handlers: None,
+            source_file: None,
         };
 
         let engine = MappingEngine::from_definitions(vec![]);
@@ -3635,6 +4013,7 @@ mod tests {
             fields: IndexMap::new(),
             companion_fields: Some(companion_fields),
             complex_handlers: None,
+            source_file: None,
         };
 
         let engine = MappingEngine::from_definitions(vec![]);
@@ -3755,15 +4134,17 @@ mod tests {
                         repetitions: vec![
                             AssembledGroupInstance {
                                 segments: vec![AssembledSegment {
-                                    tag: "SEQ".to_string(),
-                                    elements: vec![vec!["Z98".to_string()]],
+                                    tag: "SEQ".into(),
+                                    elements: vec![vec!["Z98".into()]],
+                                    location: None,
                                 }],
                                 child_groups: vec![AssembledGroup {
                                     group_id: "SG10".to_string(),
                                     repetitions: vec![AssembledGroupInstance {
                                         segments: vec![AssembledSegment {
-                                            tag: "CCI".to_string(),
-                                            elements: vec![vec![], vec![], vec!["ZB3".to_string()]],
+                                            tag: "CCI".into(),
+                                            elements: vec![vec![], vec![], vec!["ZB3".into()]],
+                                            location: None,
                                         }],
                                         child_groups: vec![],
                                         skipped_segments: vec![],
@@ -3773,15 +4154,17 @@ mod tests {
                             },
                             AssembledGroupInstance {
                                 segments: vec![AssembledSegment {
-                                    tag: "SEQ".to_string(),
-                                    elements: vec![vec!["ZD7".to_string()]],
+                                    tag: "SEQ".into(),
+                                    elements: vec![vec!["ZD7".into()]],
+                                    location: None,
                                 }],
                                 child_groups: vec![AssembledGroup {
                                     group_id: "SG10".to_string(),
                                     repetitions: vec![AssembledGroupInstance {
                                         segments: vec![AssembledSegment {
-                                            tag: "CCI".to_string(),
-                                            elements: vec![vec![], vec![], vec!["ZE6".to_string()]],
+                                            tag: "CCI".into(),
+                                            elements: vec![vec![], vec![], vec!["ZE6".into()]],
+                                            location: None,
                                         }],
                                         child_groups: vec![],
                                         skipped_segments: vec![],
@@ -3819,11 +4202,24 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_source_path_part() {
-        assert_eq!(parse_source_path_part("sg4"), ("sg4", None));
-        assert_eq!(parse_source_path_part("sg8_z98"), ("sg8", Some("z98")));
-        assert_eq!(parse_source_path_part("sg10"), ("sg10", None));
-        assert_eq!(parse_source_path_part("sg12_z04"), ("sg12", Some("z04")));
+    fn test_source_path_query() {
+        let query = |sp: &str, first_only| source_path_query(sp, first_only).to_string();
+        assert_eq!(query("sg4", false), "SG4");
+        assert_eq!(query("sg4.sg8_z98.sg10", false), "SG4/SG8[0.0=Z98]/SG10");
+        assert_eq!(query("sg4.sg8_za1_za2", false), "SG4/SG8[0.0=ZA1|ZA2]");
+        assert_eq!(query("sg4.sg12_z04", true), "SG4[0]/SG12[0.0=Z04][0]");
+    }
+
+    #[test]
+    fn test_legacy_notations_as_queries() {
+        let (steps, leaf_rep) = group_path_steps("SG4.SG8:1.SG10:2");
+        assert_eq!(Query::new(steps).to_string(), "SG4[0]/SG8[1]/SG10");
+        assert_eq!(leaf_rep, Some(2));
+
+        let (predicate, occurrence) = discriminator_predicate("RFF.0.0=Z49|Z53#1").unwrap();
+        assert_eq!(predicate.to_string(), "RFF.0.0=Z49|Z53");
+        assert_eq!(occurrence, Some(1));
+        assert!(discriminator_predicate("RFF.0=Z49").is_none());
     }
 
     #[test]
@@ -15,6 +15,23 @@ pub enum MappingError {
     #[error("Unknown handler '{name}' referenced in {file}")]
     UnknownHandler { name: String, file: String },
 
+    #[error("Unknown transform '{name}' referenced in {file}")]
+    UnknownTransform { name: String, file: String },
+
+    #[error("Invalid transform '{spec}' in {file}: {reason}")]
+    InvalidTransform {
+        spec: String,
+        file: String,
+        reason: String,
+    },
+
+    #[error("Invalid condition '{expression}' in {file}: {reason}")]
+    InvalidCondition {
+        expression: String,
+        file: String,
+        reason: String,
+    },
+
     #[error("Missing required field '{field}' during mapping")]
     MissingField { field: String },
 
@@ -24,6 +41,9 @@ pub enum MappingError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Assembly error: {0}")]
+    Assembly(#[from] mig_assembly::AssemblyError),
+
     #[error("TOML deserialization error: {0}")]
     Toml(#[from] toml::de::Error),
 
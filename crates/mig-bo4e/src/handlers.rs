@@ -2,25 +2,46 @@
 //!
 //! Handlers are Rust functions registered by name for mappings
 //! that cannot be expressed declaratively in TOML.
+//!
+//! A TOML definition references handlers by name:
+//!
+//! ```toml
+//! [[complex_handlers]]
+//! name = "zeitreihe_aggregation"
+//! description = "Sum QTY values across SG10 repetitions"
+//! ```
+//!
+//! The forward handler receives the group instance the definition maps and
+//! returns BO4E JSON that is merged into the entity. The optional reverse
+//! handler receives the entity's BO4E JSON and returns segments appended to the
+//! reconstructed group instance.
 
 use std::collections::HashMap;
 
-use mig_assembly::assembler::AssembledGroupInstance;
+use mig_assembly::assembler::{AssembledGroupInstance, AssembledSegment};
 
 use crate::error::MappingError;
 
 type HandlerFn =
     Box<dyn Fn(&AssembledGroupInstance) -> Result<serde_json::Value, MappingError> + Send + Sync>;
 
+type ReverseHandlerFn = Box<
+    dyn Fn(&serde_json::Value) -> Result<Vec<AssembledSegment<'static>>, MappingError>
+        + Send
+        + Sync,
+>;
+
 /// Registry of named complex mapping handlers.
 pub struct HandlerRegistry {
     handlers: HashMap<String, HandlerFn>,
+    reverse_handlers: HashMap<String, ReverseHandlerFn>,
 }
 
 impl HandlerRegistry {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            reverse_handlers: HashMap::new(),
         }
     }
 
@@ -35,11 +56,33 @@ impl HandlerRegistry {
         self.handlers.insert(name.to_string(), Box::new(handler));
     }
 
-    /// Check if a handler exists.
+    /// Register a reverse handler (BO4E JSON → segments) by name.
+    pub fn register_reverse<F>(&mut self, name: &str, handler: F)
+    where
+        F: Fn(&serde_json::Value) -> Result<Vec<AssembledSegment<'static>>, MappingError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.reverse_handlers
+            .insert(name.to_string(), Box::new(handler));
+    }
+
+    /// Check if a handler exists in either direction.
     pub fn has_handler(&self, name: &str) -> bool {
+        self.handlers.contains_key(name) || self.reverse_handlers.contains_key(name)
+    }
+
+    /// Check if a forward handler exists.
+    pub fn has_forward_handler(&self, name: &str) -> bool {
         self.handlers.contains_key(name)
     }
 
+    /// Check if a reverse handler exists.
+    pub fn has_reverse_handler(&self, name: &str) -> bool {
+        self.reverse_handlers.contains_key(name)
+    }
+
     /// Invoke a handler by name.
     pub fn invoke(
         &self,
@@ -56,14 +99,35 @@ impl HandlerRegistry {
         handler(instance)
     }
 
-    /// Get the number of registered handlers.
+    /// Invoke a reverse handler by name.
+    pub fn invoke_reverse(
+        &self,
+        name: &str,
+        bo4e_value: &serde_json::Value,
+    ) -> Result<Vec<AssembledSegment<'static>>, MappingError> {
+        let handler =
+            self.reverse_handlers
+                .get(name)
+                .ok_or_else(|| MappingError::UnknownHandler {
+                    name: name.to_string(),
+                    file: String::new(),
+                })?;
+        handler(bo4e_value)
+    }
+
+    /// Get the number of registered handler names (either direction).
     pub fn len(&self) -> usize {
         self.handlers.len()
+            + self
+                .reverse_handlers
+                .keys()
+                .filter(|name| !self.handlers.contains_key(*name))
+                .count()
     }
 
     /// Check if the registry is empty.
     pub fn is_empty(&self) -> bool {
-        self.handlers.is_empty()
+        self.handlers.is_empty() && self.reverse_handlers.is_empty()
     }
 }
 
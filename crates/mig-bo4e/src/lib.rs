@@ -3,8 +3,12 @@
 //! # Architecture
 //!
 //! - **TOML mapping files** define simple 1:1 field mappings
+//! - **Transforms** (`transform = "dtm_iso8601"`) convert values in both directions
+//! - **Predicates** (`when = "rff.0.0 == 'Z19'"`) make field mappings conditional
 //! - **Complex handlers** are Rust functions for non-trivial logic
 //! - **MappingEngine** loads all definitions and provides bidirectional conversion
+//! - **Mapping traces** (`*_traced` methods) record what each field mapping did
+//! - **NachrichtStream** maps large interchanges message by message from a `BufRead`
 //!
 //! # Usage
 //! ```ignore
@@ -23,13 +27,19 @@ pub mod handlers;
 pub mod model;
 pub mod path_resolver;
 pub mod pid_schema_index;
+pub mod predicate;
 pub mod segment_structure;
+pub mod streaming;
+pub mod trace;
+pub mod transform;
 
 pub use engine::MappingEngine;
 pub use engine::VariantCache;
 pub use error::MappingError;
 pub use error_mapping::Bo4eFieldIndex;
 pub use handlers::HandlerRegistry;
-pub use model::{Interchange, MappedMessage, Nachricht, Transaktion};
+pub use model::{Interchange, MappedMessage, Nachricht, Nachrichtengruppe, Transaktion};
 pub use path_resolver::PathResolver;
 pub use pid_schema_index::PidSchemaIndex;
+pub use streaming::{MessageMapping, NachrichtStream};
+pub use trace::{MappingTrace, TraceEntry, TraceOutcome};
@@ -1,8 +1,16 @@
 //! Output model types for the MIG-driven mapping pipeline.
 //!
 //! Three-level hierarchy: `Interchange` → `Nachricht` → `Transaktion`
-//! matching the EDIFACT structure: UNB/UNZ → UNH/UNT → IDE/SG4.
-
+//! matching the EDIFACT structure: UNB/UNZ → UNH/UNT → IDE/SG4. Messages
+//! in UNG/UNE functional groups sit in an optional `Nachrichtengruppe`
+//! level between interchange and message.
+
+use edifact_types::{
+    ApplicationParty, InterchangeParty, MessageIdentifier, MessageVersion, PreparationDateTime,
+    RecipientPassword, ServiceSegment, StatusOfTransfer, SyntaxIdentification, Unb, Une, Ung, Unh,
+    Unt, Unz, VersionedIdentification,
+};
+use mig_assembly::InterchangeChunks;
 use mig_types::segment::OwnedSegment;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,7 +23,70 @@ pub struct Interchange {
     /// Contains absender, empfaenger, interchange reference, etc.
     pub nachrichtendaten: serde_json::Value,
 
-    /// One entry per UNH/UNT message pair in the interchange.
+    /// One entry per UNH/UNT message pair outside functional groups.
+    pub nachrichten: Vec<Nachricht>,
+
+    /// One entry per UNG/UNE functional group, holding the group's messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gruppen: Vec<Nachrichtengruppe>,
+}
+
+impl Interchange {
+    /// Build an interchange from its split segments and the mapped messages,
+    /// given in the order of `chunks.messages`.
+    ///
+    /// Messages of a functional group are moved into `gruppen`.
+    pub fn from_chunks(chunks: &InterchangeChunks, nachrichten: Vec<Nachricht>) -> Self {
+        let mut nachrichten: Vec<Option<Nachricht>> = nachrichten.into_iter().map(Some).collect();
+        let gruppen = chunks
+            .groups
+            .iter()
+            .map(|group| Nachrichtengruppe {
+                gruppendaten: extract_gruppendaten(&group.ung),
+                nachrichten: nachrichten
+                    .get_mut(group.messages.clone())
+                    .unwrap_or_default()
+                    .iter_mut()
+                    .filter_map(Option::take)
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            nachrichtendaten: extract_nachrichtendaten(&chunks.envelope),
+            nachrichten: nachrichten.into_iter().flatten().collect(),
+            gruppen,
+        }
+    }
+
+    /// All messages, those outside functional groups first, each with its
+    /// JSON pointer relative to the interchange (e.g.
+    /// `/gruppen/0/nachrichten/1`).
+    pub fn messages(&self) -> impl Iterator<Item = (String, &Nachricht)> {
+        let ungrouped = self
+            .nachrichten
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (format!("/nachrichten/{i}"), n));
+        let grouped = self.gruppen.iter().enumerate().flat_map(|(g, gruppe)| {
+            gruppe
+                .nachrichten
+                .iter()
+                .enumerate()
+                .map(move |(i, n)| (format!("/gruppen/{g}/nachrichten/{i}"), n))
+        });
+        ungrouped.chain(grouped)
+    }
+}
+
+/// A functional group (UNG...UNE) within an interchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Nachrichtengruppe {
+    /// Service segment data extracted from UNG by `extract_gruppendaten()`.
+    pub gruppendaten: serde_json::Value,
+
+    /// One entry per UNH/UNT message pair in the group.
     pub nachrichten: Vec<Nachricht>,
 }
 
@@ -29,6 +100,12 @@ pub struct Nachricht {
     /// Message type identifier from UNH (e.g., "UTILMD", "ORDERS").
     pub nachrichten_typ: String,
 
+    /// Remaining UNH data elements (version, association code, common access
+    /// reference, ...) from `extract_nachrichtenkopf()`, so the UNH can be
+    /// rebuilt unchanged. `null` if not captured.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub nachrichtenkopf: serde_json::Value,
+
     /// Message-level BO4E entities (e.g., Marktteilnehmer from SG2).
     /// Mapped from definitions with `level = "message"` or from `message/` TOML directory.
     pub stammdaten: serde_json::Value,
@@ -93,115 +170,290 @@ pub fn extract_unh_fields(unh: &OwnedSegment) -> (String, String) {
     (referenz, typ)
 }
 
+/// Extract the remaining UNH data elements as `nachrichtenkopf` JSON.
+///
+/// Holds everything but the reference and message type (see
+/// `extract_unh_fields()`): version, release, controlling agency,
+/// association code, common access reference, status of transfer and the
+/// v4 subset/guideline/scenario identifications.
+pub fn extract_nachrichtenkopf(unh: &OwnedSegment) -> serde_json::Value {
+    let Ok(unh) = Unh::from_elements(&unh.elements) else {
+        return serde_json::Value::Null;
+    };
+    let id = &unh.message_identifier;
+    let status = unh.status_of_transfer.as_ref();
+
+    let mut result = json_fields([
+        ("version", Some(&id.version)),
+        ("release", Some(&id.release)),
+        ("organisation", Some(&id.controlling_agency)),
+        ("anwendungscode", id.association_code.as_ref()),
+        ("codelistenVersion", id.code_list_directory_version.as_ref()),
+        ("teilfunktion", id.sub_function.as_ref()),
+        (
+            "allgemeineZuordnungsreferenz",
+            unh.common_access_reference.as_ref(),
+        ),
+        ("uebertragungsfolge", status.map(|s| &s.sequence)),
+        (
+            "ersteLetzteUebertragung",
+            status.and_then(|s| s.first_last.as_ref()),
+        ),
+    ]);
+    for (key, identification) in [
+        ("nachrichtenTeilmenge", &unh.message_subset),
+        ("anwendungsrichtlinie", &unh.implementation_guideline),
+        ("szenario", &unh.scenario),
+    ] {
+        if let Some(i) = identification {
+            let value = json_fields([
+                ("kennung", Some(&i.id)),
+                ("version", i.version.as_ref()),
+                ("release", i.release.as_ref()),
+                ("organisation", i.controlling_agency.as_ref()),
+            ]);
+            result.insert(key.to_string(), serde_json::Value::Object(value));
+        }
+    }
+
+    serde_json::Value::Object(result)
+}
+
 /// Extract interchange-level metadata from envelope segments (UNB).
+///
+/// Every UNB data element present is kept, so `rebuild_unb()` can
+/// reproduce the segment.
 pub fn extract_nachrichtendaten(envelope: &[OwnedSegment]) -> serde_json::Value {
     let mut result = serde_json::Map::new();
 
     for seg in envelope {
         if seg.is("UNB") {
-            // UNB+syntax+sender+recipient+date+ref
-            if !seg.get_component(0, 0).is_empty() {
-                result.insert(
-                    "syntaxKennung".to_string(),
-                    serde_json::Value::String(seg.get_component(0, 0).to_string()),
-                );
-            }
-            if !seg.get_component(1, 0).is_empty() {
-                result.insert(
-                    "absenderCode".to_string(),
-                    serde_json::Value::String(seg.get_component(1, 0).to_string()),
-                );
-            }
-            if !seg.get_component(2, 0).is_empty() {
-                result.insert(
-                    "empfaengerCode".to_string(),
-                    serde_json::Value::String(seg.get_component(2, 0).to_string()),
-                );
-            }
-            if !seg.get_component(3, 0).is_empty() {
-                result.insert(
-                    "datum".to_string(),
-                    serde_json::Value::String(seg.get_component(3, 0).to_string()),
-                );
-            }
-            if !seg.get_component(3, 1).is_empty() {
-                result.insert(
-                    "zeit".to_string(),
-                    serde_json::Value::String(seg.get_component(3, 1).to_string()),
-                );
-            }
-            if !seg.get_element(4).is_empty() {
-                result.insert(
-                    "interchangeRef".to_string(),
-                    serde_json::Value::String(seg.get_element(4).to_string()),
-                );
-            }
+            let Ok(unb) = Unb::from_elements(&seg.elements) else {
+                continue;
+            };
+            let password = unb.recipient_password.as_ref();
+            result.extend(json_fields([
+                ("syntaxKennung", Some(&unb.syntax.identifier)),
+                ("syntaxVersion", Some(&unb.syntax.version)),
+                (
+                    "codelistenVersion",
+                    unb.syntax.code_list_directory_version.as_ref(),
+                ),
+                ("zeichenkodierung", unb.syntax.character_encoding.as_ref()),
+                ("absenderCode", Some(&unb.sender.id)),
+                ("absenderQualifier", unb.sender.qualifier.as_ref()),
+                (
+                    "absenderRoutingAdresse",
+                    unb.sender.routing_address.as_ref(),
+                ),
+                ("absenderUnterId", unb.sender.internal_sub_id.as_ref()),
+                ("empfaengerCode", Some(&unb.recipient.id)),
+                ("empfaengerQualifier", unb.recipient.qualifier.as_ref()),
+                (
+                    "empfaengerRoutingAdresse",
+                    unb.recipient.routing_address.as_ref(),
+                ),
+                ("empfaengerUnterId", unb.recipient.internal_sub_id.as_ref()),
+                ("datum", Some(&unb.prepared.date)),
+                ("zeit", Some(&unb.prepared.time)),
+                ("interchangeRef", Some(&unb.control_reference)),
+                ("passwort", password.map(|p| &p.password)),
+                (
+                    "passwortQualifier",
+                    password.and_then(|p| p.qualifier.as_ref()),
+                ),
+                ("anwendungsreferenz", unb.application_reference.as_ref()),
+                ("prioritaet", unb.priority.as_ref()),
+                (
+                    "bestaetigungAngefordert",
+                    unb.acknowledgement_request.as_ref(),
+                ),
+                ("vereinbarungsId", unb.agreement_id.as_ref()),
+                ("testKennzeichen", unb.test_indicator.as_ref()),
+            ]));
         }
     }
 
     serde_json::Value::Object(result)
 }
 
+/// Extract functional group metadata from a UNG segment.
+///
+/// Every UNG data element present is kept, so `rebuild_ung()` can
+/// reproduce the segment.
+pub fn extract_gruppendaten(ung: &OwnedSegment) -> serde_json::Value {
+    let Ok(ung) = Ung::from_elements(&ung.elements) else {
+        return serde_json::Value::Null;
+    };
+    let version = ung.message_version.as_ref();
+
+    serde_json::Value::Object(json_fields([
+        ("nachrichtengruppe", Some(&ung.message_group_id)),
+        ("absenderCode", Some(&ung.sender.id)),
+        ("absenderQualifier", ung.sender.qualifier.as_ref()),
+        ("empfaengerCode", Some(&ung.recipient.id)),
+        ("empfaengerQualifier", ung.recipient.qualifier.as_ref()),
+        ("datum", Some(&ung.prepared.date)),
+        ("zeit", Some(&ung.prepared.time)),
+        ("gruppenRef", Some(&ung.group_reference)),
+        ("organisation", ung.controlling_agency.as_ref()),
+        ("version", version.map(|v| &v.version)),
+        ("release", version.map(|v| &v.release)),
+        (
+            "anwendungscode",
+            version.and_then(|v| v.association_code.as_ref()),
+        ),
+        ("passwort", ung.application_password.as_ref()),
+    ]))
+}
+
 /// Rebuild a UNB (interchange header) segment from nachrichtendaten JSON.
 ///
 /// This is the inverse of `extract_nachrichtendaten()`.
 /// Fields not present in the JSON get sensible defaults (UNOC:3, "500" qualifier).
 pub fn rebuild_unb(nachrichtendaten: &serde_json::Value) -> OwnedSegment {
-    let syntax = nachrichtendaten
-        .get("syntaxKennung")
-        .and_then(|v| v.as_str())
-        .unwrap_or("UNOC");
-    let sender = nachrichtendaten
-        .get("absenderCode")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let receiver = nachrichtendaten
-        .get("empfaengerCode")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let datum = nachrichtendaten
-        .get("datum")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let zeit = nachrichtendaten
-        .get("zeit")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let interchange_ref = nachrichtendaten
-        .get("interchangeRef")
-        .and_then(|v| v.as_str())
-        .unwrap_or("00000");
+    let field = |key| json_field(nachrichtendaten, key);
+
+    let unb = Unb {
+        syntax: SyntaxIdentification {
+            identifier: field("syntaxKennung").unwrap_or_else(|| "UNOC".to_string()),
+            version: field("syntaxVersion").unwrap_or_else(|| "3".to_string()),
+            code_list_directory_version: field("codelistenVersion"),
+            character_encoding: field("zeichenkodierung"),
+        },
+        sender: InterchangeParty {
+            id: field("absenderCode").unwrap_or_default(),
+            qualifier: field("absenderQualifier").or_else(|| Some("500".to_string())),
+            routing_address: field("absenderRoutingAdresse"),
+            internal_sub_id: field("absenderUnterId"),
+        },
+        recipient: InterchangeParty {
+            id: field("empfaengerCode").unwrap_or_default(),
+            qualifier: field("empfaengerQualifier").or_else(|| Some("500".to_string())),
+            routing_address: field("empfaengerRoutingAdresse"),
+            internal_sub_id: field("empfaengerUnterId"),
+        },
+        prepared: PreparationDateTime {
+            date: field("datum").unwrap_or_default(),
+            time: field("zeit").unwrap_or_default(),
+        },
+        control_reference: field("interchangeRef").unwrap_or_else(|| "00000".to_string()),
+        recipient_password: field("passwort").map(|password| RecipientPassword {
+            password,
+            qualifier: field("passwortQualifier"),
+        }),
+        application_reference: field("anwendungsreferenz"),
+        priority: field("prioritaet"),
+        acknowledgement_request: field("bestaetigungAngefordert"),
+        agreement_id: field("vereinbarungsId"),
+        test_indicator: field("testKennzeichen"),
+    };
 
     OwnedSegment {
-        id: "UNB".to_string(),
-        elements: vec![
-            vec![syntax.to_string(), "3".to_string()],
-            vec![sender.to_string(), "500".to_string()],
-            vec![receiver.to_string(), "500".to_string()],
-            vec![datum.to_string(), zeit.to_string()],
-            vec![interchange_ref.to_string()],
-        ],
+        id: Unb::TAG.to_string(),
+        elements: unb.to_elements(),
         segment_number: 0,
+        location: None,
     }
 }
 
-/// Rebuild a UNH (message header) segment from reference number and message type.
+/// Rebuild a UNG (functional group header) segment from gruppendaten JSON.
 ///
-/// Produces: `UNH+referenz+typ:D:11A:UN:S2.1`
-pub fn rebuild_unh(referenz: &str, nachrichten_typ: &str) -> OwnedSegment {
+/// This is the inverse of `extract_gruppendaten()`.
+pub fn rebuild_ung(gruppendaten: &serde_json::Value) -> OwnedSegment {
+    let field = |key| json_field(gruppendaten, key);
+    let version = field("version");
+    let release = field("release");
+    let association_code = field("anwendungscode");
+
+    let ung = Ung {
+        message_group_id: field("nachrichtengruppe").unwrap_or_default(),
+        sender: ApplicationParty {
+            id: field("absenderCode").unwrap_or_default(),
+            qualifier: field("absenderQualifier"),
+        },
+        recipient: ApplicationParty {
+            id: field("empfaengerCode").unwrap_or_default(),
+            qualifier: field("empfaengerQualifier"),
+        },
+        prepared: PreparationDateTime {
+            date: field("datum").unwrap_or_default(),
+            time: field("zeit").unwrap_or_default(),
+        },
+        group_reference: field("gruppenRef").unwrap_or_default(),
+        controlling_agency: field("organisation"),
+        message_version: (version.is_some() || release.is_some() || association_code.is_some())
+            .then(|| MessageVersion {
+                version: version.unwrap_or_default(),
+                release: release.unwrap_or_default(),
+                association_code,
+            }),
+        application_password: field("passwort"),
+    };
+
     OwnedSegment {
-        id: "UNH".to_string(),
-        elements: vec![
-            vec![referenz.to_string()],
-            vec![
-                nachrichten_typ.to_string(),
-                "D".to_string(),
-                "11A".to_string(),
-                "UN".to_string(),
-                "S2.1".to_string(),
-            ],
-        ],
+        id: Ung::TAG.to_string(),
+        elements: ung.to_elements(),
+        segment_number: 0,
+        location: None,
+    }
+}
+
+/// Rebuild a UNH (message header) segment from reference number, message type
+/// and the `nachrichtenkopf` JSON from `extract_nachrichtenkopf()`.
+///
+/// Without a nachrichtenkopf (`null`), produces: `UNH+referenz+typ:D:11A:UN:S2.1`
+pub fn rebuild_unh(
+    referenz: &str,
+    nachrichten_typ: &str,
+    nachrichtenkopf: &serde_json::Value,
+) -> OwnedSegment {
+    let field = |key| json_field(nachrichtenkopf, key);
+    let identification = |key| {
+        let value = nachrichtenkopf.get(key)?;
+        Some(VersionedIdentification {
+            id: json_field(value, "kennung").unwrap_or_default(),
+            version: json_field(value, "version"),
+            release: json_field(value, "release"),
+            controlling_agency: json_field(value, "organisation"),
+        })
+    };
+    let association_code = if nachrichtenkopf.is_object() {
+        field("anwendungscode")
+    } else {
+        Some("S2.1".to_string())
+    };
+    let sequence = field("uebertragungsfolge");
+    let first_last = field("ersteLetzteUebertragung");
+
+    let unh = Unh {
+        message_reference: referenz.to_string(),
+        message_identifier: MessageIdentifier {
+            message_type: nachrichten_typ.to_string(),
+            version: field("version").unwrap_or_else(|| "D".to_string()),
+            release: field("release").unwrap_or_else(|| "11A".to_string()),
+            controlling_agency: field("organisation").unwrap_or_else(|| "UN".to_string()),
+            association_code,
+            code_list_directory_version: field("codelistenVersion"),
+            sub_function: field("teilfunktion"),
+        },
+        common_access_reference: field("allgemeineZuordnungsreferenz"),
+        status_of_transfer: (sequence.is_some() || first_last.is_some()).then(|| {
+            StatusOfTransfer {
+                sequence: sequence.unwrap_or_default(),
+                first_last,
+            }
+        }),
+        message_subset: identification("nachrichtenTeilmenge"),
+        implementation_guideline: identification("anwendungsrichtlinie"),
+        scenario: identification("szenario"),
+    };
+
+    OwnedSegment {
+        id: Unh::TAG.to_string(),
+        elements: unh.to_elements(),
         segment_number: 0,
+        location: None,
     }
 }
 
@@ -210,10 +462,15 @@ pub fn rebuild_unh(referenz: &str, nachrichten_typ: &str) -> OwnedSegment {
 /// Produces: `UNT+count+referenz`
 /// `segment_count` includes UNH and UNT themselves.
 pub fn rebuild_unt(segment_count: usize, referenz: &str) -> OwnedSegment {
+    let unt = Unt {
+        segment_count,
+        message_reference: referenz.to_string(),
+    };
     OwnedSegment {
-        id: "UNT".to_string(),
-        elements: vec![vec![segment_count.to_string()], vec![referenz.to_string()]],
+        id: Unt::TAG.to_string(),
+        elements: unt.to_elements(),
         segment_number: 0,
+        location: None,
     }
 }
 
@@ -221,16 +478,56 @@ pub fn rebuild_unt(segment_count: usize, referenz: &str) -> OwnedSegment {
 ///
 /// Produces: `UNZ+count+ref`
 pub fn rebuild_unz(message_count: usize, interchange_ref: &str) -> OwnedSegment {
+    let unz = Unz {
+        message_count,
+        control_reference: interchange_ref.to_string(),
+    };
+    OwnedSegment {
+        id: Unz::TAG.to_string(),
+        elements: unz.to_elements(),
+        segment_number: 0,
+        location: None,
+    }
+}
+
+/// Rebuild a UNE (functional group trailer) segment.
+///
+/// Produces: `UNE+count+ref`
+pub fn rebuild_une(message_count: usize, group_ref: &str) -> OwnedSegment {
+    let une = Une {
+        message_count,
+        group_reference: group_ref.to_string(),
+    };
     OwnedSegment {
-        id: "UNZ".to_string(),
-        elements: vec![
-            vec![message_count.to_string()],
-            vec![interchange_ref.to_string()],
-        ],
+        id: Une::TAG.to_string(),
+        elements: une.to_elements(),
         segment_number: 0,
+        location: None,
     }
 }
 
+/// Collect the non-empty values into a JSON object.
+fn json_fields<const N: usize>(
+    fields: [(&str, Option<&String>); N],
+) -> serde_json::Map<String, serde_json::Value> {
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = value.filter(|v| !v.is_empty())?;
+            Some((key.to_string(), serde_json::Value::String(value.clone())))
+        })
+        .collect()
+}
+
+/// A non-empty string field of a JSON object.
+fn json_field(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +556,7 @@ mod tests {
         let msg = Nachricht {
             unh_referenz: "00001".to_string(),
             nachrichten_typ: "UTILMD".to_string(),
+            nachrichtenkopf: serde_json::Value::Null,
             stammdaten: serde_json::json!({
                 "Marktteilnehmer": [
                     { "marktrolle": "MS", "rollencodenummer": "9900123" }
@@ -288,12 +586,16 @@ mod tests {
             nachrichten: vec![Nachricht {
                 unh_referenz: "00001".to_string(),
                 nachrichten_typ: "UTILMD".to_string(),
+                nachrichtenkopf: serde_json::Value::Null,
                 stammdaten: serde_json::json!({}),
                 transaktionen: vec![],
             }],
+            gruppen: vec![],
         };
 
         let json = serde_json::to_string_pretty(&interchange).unwrap();
+        // Interchanges without functional groups keep their previous shape
+        assert!(!json.contains("gruppen"));
         let de: Interchange = serde_json::from_str(&json).unwrap();
         assert_eq!(de.nachrichten.len(), 1);
         assert_eq!(de.nachrichten[0].unh_referenz, "00001");
@@ -313,6 +615,7 @@ mod tests {
                 vec!["REF001".to_string()],
             ],
             segment_number: 0,
+            location: None,
         }];
 
         let nd = extract_nachrichtendaten(&envelope);
@@ -341,6 +644,7 @@ mod tests {
                 ],
             ],
             segment_number: 0,
+            location: None,
         };
 
         let (referenz, typ) = extract_unh_fields(&unh);
@@ -380,7 +684,7 @@ mod tests {
 
     #[test]
     fn test_rebuild_unh() {
-        let unh = rebuild_unh("00001", "UTILMD");
+        let unh = rebuild_unh("00001", "UTILMD", &serde_json::Value::Null);
         assert_eq!(unh.id, "UNH");
         assert_eq!(unh.elements[0], vec!["00001"]);
         assert_eq!(unh.elements[1][0], "UTILMD");
@@ -419,6 +723,7 @@ mod tests {
                 vec!["REF001".to_string()],
             ],
             segment_number: 0,
+            location: None,
         };
 
         let nd = extract_nachrichtendaten(&[original]);
@@ -429,4 +734,110 @@ mod tests {
         assert_eq!(rebuilt.elements[3], vec!["210101", "1200"]);
         assert_eq!(rebuilt.elements[4], vec!["REF001"]);
     }
+
+    fn segment(id: &str, text: &str) -> OwnedSegment {
+        OwnedSegment {
+            id: id.to_string(),
+            elements: text
+                .split('+')
+                .map(|e| e.split(':').map(str::to_string).collect())
+                .collect(),
+            segment_number: 0,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_all_unb_elements() {
+        let original = segment(
+            "UNB",
+            "UNOC:3+9900123456789:500:ROUTE+9900987654321:14+210101:1200+REF001+PW:AA+APP+A+1+AGR+1",
+        );
+
+        let nd = extract_nachrichtendaten(std::slice::from_ref(&original));
+        assert_eq!(nd["absenderRoutingAdresse"], "ROUTE");
+        assert_eq!(nd["empfaengerQualifier"], "14");
+        assert_eq!(nd["testKennzeichen"], "1");
+        assert_eq!(rebuild_unb(&nd).elements, original.elements);
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_all_unh_elements() {
+        let original = segment("UNH", "7+APERAK:D:07B:UN:2.1i+CAR+1:C");
+
+        let (referenz, typ) = extract_unh_fields(&original);
+        let kopf = extract_nachrichtenkopf(&original);
+        assert_eq!(kopf["release"], "07B");
+        assert_eq!(kopf["allgemeineZuordnungsreferenz"], "CAR");
+        assert_eq!(
+            rebuild_unh(&referenz, &typ, &kopf).elements,
+            original.elements
+        );
+
+        // A captured header without association code does not get the default
+        let original = segment("UNH", "7+CONTRL:D:3:UN");
+        let kopf = extract_nachrichtenkopf(&original);
+        assert_eq!(
+            rebuild_unh("7", "CONTRL", &kopf).elements,
+            original.elements
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_all_ung_elements() {
+        let original = segment(
+            "UNG",
+            "UTILMD+9900123456789:14+9900987654321:14+210101:1200+G1+UN+D:11A:S2.1+PW",
+        );
+
+        let gd = extract_gruppendaten(&original);
+        assert_eq!(gd["nachrichtengruppe"], "UTILMD");
+        assert_eq!(gd["gruppenRef"], "G1");
+        assert_eq!(gd["anwendungscode"], "S2.1");
+        assert_eq!(rebuild_ung(&gd).elements, original.elements);
+        assert_eq!(rebuild_une(2, "G1").elements, vec![vec!["2"], vec!["G1"]]);
+    }
+
+    #[test]
+    fn test_interchange_from_chunks_moves_grouped_messages() {
+        let input = "UNB+UNOC:3+S+R+210101:1200+IC1'\
+UNG+UTILMD+S+R+210101:1200+G1'UNH+1+UTILMD:D:11A:UN'UNT+2+1'UNH+2+UTILMD:D:11A:UN'UNT+2+2'UNE+2+G1'\
+UNG+UTILMD+S+R+210101:1200+G2'UNH+3+UTILMD:D:11A:UN'UNT+2+3'UNE+1+G2'UNZ+2+IC1'";
+        let segments = mig_assembly::tokenize::parse_str_to_segments(input).unwrap();
+        let chunks = mig_assembly::split_messages(segments).unwrap();
+        let nachrichten = chunks
+            .messages
+            .iter()
+            .map(|chunk| {
+                let (unh_referenz, nachrichten_typ) = extract_unh_fields(&chunk.unh);
+                Nachricht {
+                    unh_referenz,
+                    nachrichten_typ,
+                    nachrichtenkopf: serde_json::Value::Null,
+                    stammdaten: serde_json::json!({}),
+                    transaktionen: vec![],
+                }
+            })
+            .collect();
+
+        let interchange = Interchange::from_chunks(&chunks, nachrichten);
+        assert_eq!(interchange.nachrichtendaten["interchangeRef"], "IC1");
+        assert!(interchange.nachrichten.is_empty());
+        assert_eq!(interchange.gruppen.len(), 2);
+        assert_eq!(interchange.gruppen[0].gruppendaten["gruppenRef"], "G1");
+        assert_eq!(interchange.gruppen[0].nachrichten.len(), 2);
+
+        let messages: Vec<(String, &str)> = interchange
+            .messages()
+            .map(|(pointer, n)| (pointer, n.unh_referenz.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                ("/gruppen/0/nachrichten/0".to_string(), "1"),
+                ("/gruppen/0/nachrichten/1".to_string(), "2"),
+                ("/gruppen/1/nachrichten/0".to_string(), "3"),
+            ]
+        );
+    }
 }
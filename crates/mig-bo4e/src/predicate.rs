@@ -0,0 +1,444 @@
+//! `when` predicates for conditional TOML field mappings.
+//!
+//! A `StructuredFieldMapping` may carry a `when` expression over sibling fields
+//! of the same group instance. The field is only mapped if the predicate holds:
+//!
+//! ```toml
+//! "rff.0.1" = { target = "referenzId", when = "rff.0.0 == 'Z19'" }
+//! "cci.2.0" = { target = "merkmal", when = "cci.0.0 in ('Z83', 'Z84') && !cav.0.1" }
+//! ```
+//!
+//! # Grammar
+//!
+//! ```text
+//! expr       := or
+//! or         := and ( "||" and )*
+//! and        := unary ( "&&" unary )*
+//! unary      := "!" unary | primary
+//! primary    := "(" expr ")" | path ( ("==" | "!=") literal | "in" "(" literal ("," literal)* ")" )?
+//! literal    := '...' | "..."
+//! ```
+//!
+//! Paths use the same syntax as field keys (`dtm[92].0.1`, `loc.c517.d3225`).
+//! A bare path is true when the field is present and non-empty; a missing field
+//! compares equal to `''`.
+//!
+//! Forward mapping evaluates the predicate against the EDIFACT group instance.
+//! Reverse mapping evaluates it against the segments being rebuilt (BO4E values
+//! and defaults), so a field guarded by `when = "rff.0.0 == 'Z19'"` is only
+//! written back when the reconstructed qualifier is `Z19`.
+
+use std::fmt;
+
+/// Parsed `when` predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// Field is present and non-empty.
+    Present(String),
+    /// Field equals the literal.
+    Eq(String, String),
+    /// Field does not equal the literal.
+    Ne(String, String),
+    /// Field equals one of the literals.
+    In(String, Vec<String>),
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Predicate {
+    /// Parse a `when` expression.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let tokens = tokenize(expr)?;
+        if tokens.is_empty() {
+            return Err("empty expression".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let predicate = parser.parse_or()?;
+        if let Some(tok) = parser.tokens.get(parser.pos) {
+            return Err(format!("unexpected {tok} after end of expression"));
+        }
+        Ok(predicate)
+    }
+
+    /// Evaluate the predicate, resolving field paths through `lookup`.
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<String>) -> bool {
+        match self {
+            Self::Present(path) => lookup(path).is_some_and(|v| !v.is_empty()),
+            Self::Eq(path, lit) => lookup(path).unwrap_or_default() == *lit,
+            Self::Ne(path, lit) => lookup(path).unwrap_or_default() != *lit,
+            Self::In(path, lits) => {
+                let val = lookup(path).unwrap_or_default();
+                lits.contains(&val)
+            }
+            Self::Not(inner) => !inner.evaluate(lookup),
+            Self::And(a, b) => a.evaluate(lookup) && b.evaluate(lookup),
+            Self::Or(a, b) => a.evaluate(lookup) || b.evaluate(lookup),
+        }
+    }
+
+    /// All field paths referenced by the predicate.
+    pub fn paths(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_paths(&mut out);
+        out
+    }
+
+    fn collect_paths<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Self::Present(p) | Self::Eq(p, _) | Self::Ne(p, _) | Self::In(p, _) => out.push(p),
+            Self::Not(inner) => inner.collect_paths(out),
+            Self::And(a, b) | Self::Or(a, b) => {
+                a.collect_paths(out);
+                b.collect_paths(out);
+            }
+        }
+    }
+
+    /// Rewrite every field path with `f` (used to normalize named paths to numeric ones).
+    pub fn map_paths(&self, f: &dyn Fn(&str) -> String) -> Self {
+        match self {
+            Self::Present(p) => Self::Present(f(p)),
+            Self::Eq(p, l) => Self::Eq(f(p), l.clone()),
+            Self::Ne(p, l) => Self::Ne(f(p), l.clone()),
+            Self::In(p, ls) => Self::In(f(p), ls.clone()),
+            Self::Not(inner) => Self::Not(Box::new(inner.map_paths(f))),
+            Self::And(a, b) => Self::And(Box::new(a.map_paths(f)), Box::new(b.map_paths(f))),
+            Self::Or(a, b) => Self::Or(Box::new(a.map_paths(f)), Box::new(b.map_paths(f))),
+        }
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Present(p) => write!(f, "{p}"),
+            Self::Eq(p, l) => write!(f, "{p} == '{l}'"),
+            Self::Ne(p, l) => write!(f, "{p} != '{l}'"),
+            Self::In(p, ls) => {
+                write!(f, "{p} in (")?;
+                for (i, l) in ls.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "'{l}'")?;
+                }
+                write!(f, ")")
+            }
+            Self::Not(inner) => match **inner {
+                Self::And(..) | Self::Or(..) => write!(f, "!({inner})"),
+                _ => write!(f, "!{inner}"),
+            },
+            Self::And(a, b) => {
+                write_operand(f, a, true)?;
+                write!(f, " && ")?;
+                write_operand(f, b, true)
+            }
+            Self::Or(a, b) => {
+                write_operand(f, a, false)?;
+                write!(f, " || ")?;
+                write_operand(f, b, false)
+            }
+        }
+    }
+}
+
+/// Parenthesize `||` operands inside `&&` so the printed form re-parses identically.
+fn write_operand(f: &mut fmt::Formatter<'_>, p: &Predicate, in_and: bool) -> fmt::Result {
+    if in_and && matches!(p, Predicate::Or(..)) {
+        write!(f, "({p})")
+    } else {
+        write!(f, "{p}")
+    }
+}
+
+// ── Tokenizer ──
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Path(String),
+    Literal(String),
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+    In,
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(p) => write!(f, "path '{p}'"),
+            Self::Literal(l) => write!(f, "literal '{l}'"),
+            Self::Eq => write!(f, "'=='"),
+            Self::Ne => write!(f, "'!='"),
+            Self::And => write!(f, "'&&'"),
+            Self::Or => write!(f, "'||'"),
+            Self::Not => write!(f, "'!'"),
+            Self::In => write!(f, "'in'"),
+            Self::LParen => write!(f, "'('"),
+            Self::RParen => write!(f, "')'"),
+            Self::Comma => write!(f, "','"),
+        }
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '\'' | '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| format!("unterminated literal starting at column {}", i + 1))?;
+                tokens.push(Token::Literal(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                // A path runs until whitespace or an operator; brackets may
+                // contain commas (e.g., `rff[Z34,1].0.1`).
+                let start = i;
+                let mut depth = 0usize;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    match ch {
+                        '[' => depth += 1,
+                        ']' => depth = depth.saturating_sub(1),
+                        ',' if depth > 0 => {}
+                        ch if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '*' | '#') => {
+                        }
+                        _ => break,
+                    }
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if word == "in" {
+                    tokens.push(Token::In);
+                } else {
+                    tokens.push(Token::Path(word));
+                }
+            }
+            other => {
+                return Err(format!(
+                    "unexpected character '{other}' at column {}",
+                    i + 1
+                ))
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// ── Parser ──
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect_literal(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Literal(l)) => Ok(l),
+            Some(other) => Err(format!("expected quoted literal, found {other}")),
+            None => Err("expected quoted literal, found end of expression".to_string()),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Predicate, String> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Predicate::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Predicate, String> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = Predicate::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Predicate, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Predicate::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Predicate, String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Path(path)) => match self.peek() {
+                Some(Token::Eq) => {
+                    self.pos += 1;
+                    Ok(Predicate::Eq(path, self.expect_literal()?))
+                }
+                Some(Token::Ne) => {
+                    self.pos += 1;
+                    Ok(Predicate::Ne(path, self.expect_literal()?))
+                }
+                Some(Token::In) => {
+                    self.pos += 1;
+                    if self.next() != Some(Token::LParen) {
+                        return Err("expected '(' after 'in'".to_string());
+                    }
+                    let mut values = vec![self.expect_literal()?];
+                    loop {
+                        match self.next() {
+                            Some(Token::Comma) => values.push(self.expect_literal()?),
+                            Some(Token::RParen) => break,
+                            _ => return Err("expected ',' or ')' in 'in' list".to_string()),
+                        }
+                    }
+                    Ok(Predicate::In(path, values))
+                }
+                _ => Ok(Predicate::Present(path)),
+            },
+            Some(other) => Err(format!("expected field path or '(', found {other}")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(values: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |path| map.get(path).cloned()
+    }
+
+    #[test]
+    fn test_parse_equality() {
+        let p = Predicate::parse("rff.0.0 == 'Z19'").unwrap();
+        assert_eq!(p, Predicate::Eq("rff.0.0".into(), "Z19".into()));
+        assert!(p.evaluate(&lookup(&[("rff.0.0", "Z19")])));
+        assert!(!p.evaluate(&lookup(&[("rff.0.0", "Z13")])));
+        assert!(!p.evaluate(&lookup(&[])));
+    }
+
+    #[test]
+    fn test_parse_boolean_operators_and_precedence() {
+        let p = Predicate::parse("a.0 == '1' || b.0 == '2' && !c.0").unwrap();
+        // && binds tighter than ||
+        assert!(p.evaluate(&lookup(&[("a.0", "1")])));
+        assert!(p.evaluate(&lookup(&[("b.0", "2")])));
+        assert!(!p.evaluate(&lookup(&[("b.0", "2"), ("c.0", "x")])));
+    }
+
+    #[test]
+    fn test_parse_in_list_and_qualified_paths() {
+        let p = Predicate::parse("rff[Z34,1].0.0 in ('Z13', \"Z14\")").unwrap();
+        assert_eq!(p.paths(), vec!["rff[Z34,1].0.0"]);
+        assert!(p.evaluate(&lookup(&[("rff[Z34,1].0.0", "Z14")])));
+        assert!(!p.evaluate(&lookup(&[("rff[Z34,1].0.0", "Z15")])));
+    }
+
+    #[test]
+    fn test_missing_field_compares_as_empty() {
+        let p = Predicate::parse("cav.0.1 != ''").unwrap();
+        assert!(!p.evaluate(&lookup(&[])));
+        assert!(p.evaluate(&lookup(&[("cav.0.1", "x")])));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Predicate::parse("").is_err());
+        assert!(Predicate::parse("rff.0.0 == Z19").is_err());
+        assert!(Predicate::parse("rff.0.0 == 'Z19").is_err());
+        assert!(Predicate::parse("(rff.0.0 == 'Z19'").is_err());
+        assert!(Predicate::parse("rff.0.0 == 'Z19' rff").is_err());
+        assert!(Predicate::parse("rff.0.0 = 'Z19'").is_err());
+    }
+
+    #[test]
+    fn test_display_roundtrips_through_parse() {
+        for expr in [
+            "rff.0.0 == 'Z19'",
+            "!(a.0 == '1' || b.0)",
+            "(a.0 || b.0) && c.0 in ('x', 'y')",
+        ] {
+            let p = Predicate::parse(expr).unwrap();
+            assert_eq!(Predicate::parse(&p.to_string()).unwrap(), p, "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_map_paths() {
+        let p = Predicate::parse("dtm.c507.d2005 == '157'").unwrap();
+        let mapped = p.map_paths(&|path| path.replace("c507.d2005", "0.0"));
+        assert_eq!(mapped.to_string(), "dtm.0.0 == '157'");
+    }
+}
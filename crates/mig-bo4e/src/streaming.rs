@@ -0,0 +1,224 @@
+//! Streaming interchange mapping for large files.
+//!
+//! [`NachrichtStream`] reads an interchange incrementally via
+//! [`MessageChunkReader`] and maps each message to a [`Nachricht`] as soon as
+//! its UNT has been read. Only the current message is held in memory, so
+//! multi-GB bulk files (e.g., nightly MSCONS) can be processed with memory
+//! bounded by the largest single message.
+//!
+//! # Usage
+//! ```ignore
+//! let file = BufReader::new(File::open("bulk.edi")?);
+//! let stream = NachrichtStream::new(file, "SG5", |chunk| {
+//!     let tree = Assembler::new(&mig).assemble_generic(&chunk.message_segments())?.into_owned();
+//!     Ok(MessageMapping { tree, msg_engine: &msg_engine, tx_engine: &tx_engine })
+//! })?;
+//! for nachricht in stream {
+//!     write_json(&nachricht?)?;
+//! }
+//! ```
+
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+use mig_assembly::assembler::AssembledTree;
+use mig_assembly::{MessageChunk, MessageChunkReader};
+
+use crate::engine::MappingEngine;
+use crate::error::MappingError;
+use crate::model::{
+    extract_nachrichtendaten, extract_nachrichtenkopf, extract_unh_fields, Nachricht,
+};
+
+/// How to map one message: its assembled tree and the engines to use.
+///
+/// Returned by the resolver passed to [`NachrichtStream::new`], which
+/// typically detects the PID and picks the matching MIG and engines.
+pub struct MessageMapping<'e> {
+    /// The assembled tree for this message.
+    pub tree: AssembledTree<'static>,
+    /// Engine with message-level definitions.
+    pub msg_engine: &'e MappingEngine,
+    /// Engine with transaction-level definitions.
+    pub tx_engine: &'e MappingEngine,
+}
+
+impl MessageMapping<'_> {
+    /// Map the tree into a [`Nachricht`] with the UNH data of `chunk`.
+    pub(crate) fn map_chunk(
+        &self,
+        chunk: &MessageChunk,
+        transaction_group: &str,
+        enrich_codes: bool,
+    ) -> Nachricht {
+        let mapped = MappingEngine::map_interchange(
+            self.msg_engine,
+            self.tx_engine,
+            &self.tree,
+            transaction_group,
+            enrich_codes,
+        );
+        let (unh_referenz, nachrichten_typ) = extract_unh_fields(&chunk.unh);
+
+        Nachricht {
+            unh_referenz,
+            nachrichten_typ,
+            nachrichtenkopf: extract_nachrichtenkopf(&chunk.unh),
+            stammdaten: mapped.stammdaten,
+            transaktionen: mapped.transaktionen,
+        }
+    }
+}
+
+/// Iterator mapping the messages of an interchange one at a time.
+///
+/// Errors from reading, resolving or assembling a message are yielded in
+/// place of that message; reading stops after a read error.
+pub struct NachrichtStream<'e, R, F> {
+    chunks: MessageChunkReader<R>,
+    resolve: F,
+    transaction_group: String,
+    enrich_codes: bool,
+    _engines: PhantomData<&'e MappingEngine>,
+}
+
+impl<'e, R, F> NachrichtStream<'e, R, F>
+where
+    R: BufRead,
+    F: FnMut(&MessageChunk) -> Result<MessageMapping<'e>, MappingError>,
+{
+    /// Create a stream over an EDIFACT interchange.
+    ///
+    /// - `transaction_group`: The group ID that represents transactions (e.g., "SG4")
+    /// - `resolve`: Assembles a message and selects its engines
+    pub fn new(reader: R, transaction_group: &str, resolve: F) -> Result<Self, MappingError> {
+        Ok(Self {
+            chunks: MessageChunkReader::new(reader)?,
+            resolve,
+            transaction_group: transaction_group.to_string(),
+            enrich_codes: true,
+            _engines: PhantomData,
+        })
+    }
+
+    /// Whether to enrich code values with their meaning (default: true).
+    pub fn with_enrich_codes(mut self, enrich_codes: bool) -> Self {
+        self.enrich_codes = enrich_codes;
+        self
+    }
+
+    /// Interchange-level metadata from the envelope (UNB) of the current
+    /// interchange.
+    ///
+    /// Available once the interchange's first message has been read.
+    pub fn nachrichtendaten(&self) -> Option<serde_json::Value> {
+        self.chunks
+            .envelope()
+            .map(|envelope| extract_nachrichtendaten(envelope))
+    }
+
+    /// The underlying message reader, e.g., for the UNZ segment after the
+    /// last message.
+    pub fn chunks(&self) -> &MessageChunkReader<R> {
+        &self.chunks
+    }
+
+    fn map_chunk(&mut self, chunk: &MessageChunk) -> Result<Nachricht, MappingError> {
+        let mapping = (self.resolve)(chunk)?;
+        Ok(mapping.map_chunk(chunk, &self.transaction_group, self.enrich_codes))
+    }
+}
+
+impl<'e, R, F> Iterator for NachrichtStream<'e, R, F>
+where
+    R: BufRead,
+    F: FnMut(&MessageChunk) -> Result<MessageMapping<'e>, MappingError>,
+{
+    type Item = Result<Nachricht, MappingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = match self.chunks.next()? {
+            Ok(chunk) => chunk,
+            Err(e) => return Some(Err(e.into())),
+        };
+        Some(self.map_chunk(&chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    const INPUT: &[u8] = b"UNA:+.? 'UNB+UNOC:3+9900123000002:500+9900456000001:500+250101:1200+REF001'UNH+M1+MSCONS:D:04B:UN:2.4c'BGM+7+DOC1'UNT+3+M1'UNH+M2+MSCONS:D:04B:UN:2.4c'BGM+7+DOC2'UNT+3+M2'UNZ+2+REF001'";
+
+    fn empty_tree() -> AssembledTree<'static> {
+        AssembledTree {
+            segments: vec![],
+            groups: vec![],
+            post_group_start: 0,
+            inter_group_segments: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_stream_maps_each_message_in_order() {
+        let engine = MappingEngine::from_definitions(vec![]);
+        let mut seen = Vec::new();
+        let stream = NachrichtStream::new(INPUT, "SG5", |chunk| {
+            seen.push(chunk.body[0].get_element(1).to_string());
+            Ok(MessageMapping {
+                tree: empty_tree(),
+                msg_engine: &engine,
+                tx_engine: &engine,
+            })
+        })
+        .unwrap();
+
+        let nachrichten: Vec<Nachricht> = stream.collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(seen, ["DOC1", "DOC2"]);
+        assert_eq!(nachrichten.len(), 2);
+        assert_eq!(nachrichten[0].unh_referenz, "M1");
+        assert_eq!(nachrichten[1].unh_referenz, "M2");
+        assert_eq!(nachrichten[1].nachrichten_typ, "MSCONS");
+    }
+
+    #[test]
+    fn test_stream_exposes_envelope_after_first_message() {
+        let engine = MappingEngine::from_definitions(vec![]);
+        let mut stream = NachrichtStream::new(INPUT, "SG5", |_| {
+            Ok(MessageMapping {
+                tree: empty_tree(),
+                msg_engine: &engine,
+                tx_engine: &engine,
+            })
+        })
+        .unwrap();
+
+        assert!(stream.nachrichtendaten().is_none());
+        stream.next().unwrap().unwrap();
+        let nd = stream.nachrichtendaten().unwrap();
+        assert_eq!(nd["absenderCode"], "9900123000002");
+    }
+
+    #[test]
+    fn test_stream_yields_resolver_errors_per_message() {
+        let engine = MappingEngine::from_definitions(vec![]);
+        let stream = NachrichtStream::new(INPUT, "SG5", |chunk| {
+            if chunk.unh.get_element(0) == "M1" {
+                return Err(MappingError::TypeConversion("unknown PID".to_string()));
+            }
+            Ok(MessageMapping {
+                tree: empty_tree(),
+                msg_engine: &engine,
+                tx_engine: &engine,
+            })
+        })
+        .unwrap();
+
+        let results: Vec<_> = stream.collect();
+        assert!(results[0].is_err());
+        assert_eq!(results[1].as_ref().unwrap().unh_referenz, "M2");
+    }
+}
@@ -0,0 +1,360 @@
+//! Mapping trace — an opt-in record of what each TOML field mapping did.
+//!
+//! Pass a [`MappingTrace`] to one of the `*_traced` methods on
+//! [`MappingEngine`](crate::MappingEngine) to collect one [`TraceEntry`] per
+//! field mapping that was considered, including the ones that were skipped.
+//!
+//! # Usage
+//! ```ignore
+//! let mut trace = MappingTrace::new();
+//! let bo4e = engine.map_forward_traced(&tree, def, 0, &mut trace);
+//! for entry in trace.entries() {
+//!     println!("{} {} -> {} ({:?})", entry.file.as_deref().unwrap_or("?"),
+//!         entry.field, entry.target, entry.outcome);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use mig_assembly::assembler::{AssembledGroupInstance, AssembledSegment, AssembledTree};
+use serde::{Deserialize, Serialize};
+
+use crate::definition::MappingDefinition;
+
+/// What happened to a field mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceOutcome {
+    /// The value was read from the source and written to the target.
+    Mapped,
+    /// No source value was present; the field's `default` was written instead.
+    DefaultApplied,
+    /// The definition's `discriminator` matched no group repetition.
+    DiscriminatorMismatch,
+    /// The source had no value for this field.
+    EmptyValue,
+    /// The field's `when` predicate did not hold.
+    ConditionNotMet,
+}
+
+impl TraceOutcome {
+    /// Whether the field was skipped (nothing was written to the target).
+    pub fn is_skipped(self) -> bool {
+        !matches!(self, Self::Mapped | Self::DefaultApplied)
+    }
+}
+
+/// One traced field mapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Entity of the mapping definition (e.g., "Marktlokation").
+    pub entity: String,
+    /// TOML file the definition was loaded from, when known.
+    pub file: Option<String>,
+    /// Field key in the TOML file (e.g., "loc.1.0"). Empty for
+    /// definition-level entries such as a discriminator mismatch.
+    pub field: String,
+    /// MIG location of the field: the definition's source path (or source
+    /// group) joined with the field key, e.g. "sg4.sg5_z16.loc.1.0".
+    pub mig_path: String,
+    /// Tag of the EDIFACT segment the field belongs to (e.g., "LOC").
+    pub segment_tag: Option<String>,
+    /// 1-based position of the source segment within the message
+    /// (forward mapping only).
+    pub segment_number: Option<u32>,
+    /// JSON pointer of the BO4E value (e.g., "/stammdaten/marktlokation/marktlokationsId").
+    /// Written to in forward mapping, read from in reverse mapping.
+    pub target: String,
+    /// Value before transform and `enum_map` (EDIFACT value in forward
+    /// mapping, BO4E value in reverse mapping).
+    pub value: Option<String>,
+    /// Value after transform and `enum_map`, as written to the target.
+    pub mapped_value: Option<String>,
+    /// What happened to the field.
+    pub outcome: TraceOutcome,
+}
+
+impl TraceEntry {
+    /// Start an entry for `field` of `def`; the remaining fields are filled in
+    /// by the engine.
+    pub(crate) fn for_field(def: &MappingDefinition, field: &str, outcome: TraceOutcome) -> Self {
+        let group = def
+            .meta
+            .source_path
+            .as_deref()
+            .unwrap_or(&def.meta.source_group);
+        let mig_path = match (group.is_empty(), field.is_empty()) {
+            (true, _) => field.to_string(),
+            (false, true) => group.to_string(),
+            (false, false) => format!("{group}.{field}"),
+        };
+        let segment_tag = field
+            .split('.')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.split('[').next().unwrap_or(s).to_uppercase());
+
+        Self {
+            entity: def.meta.entity.clone(),
+            file: def.source_file.clone(),
+            field: field.to_string(),
+            mig_path,
+            segment_tag,
+            segment_number: None,
+            target: String::new(),
+            value: None,
+            mapped_value: None,
+            outcome,
+        }
+    }
+}
+
+/// Collects [`TraceEntry`] records during a mapping run.
+#[derive(Debug, Default)]
+pub struct MappingTrace {
+    entries: Vec<TraceEntry>,
+    /// Segment address → 1-based position in the message being mapped.
+    segment_numbers: HashMap<usize, u32>,
+}
+
+impl MappingTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The recorded entries, in mapping order.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Consume the trace and return its entries.
+    pub fn into_entries(self) -> Vec<TraceEntry> {
+        self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn record(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Rewrite the target pointers of all entries recorded since `mark`
+    /// (a previous [`len`](Self::len)).
+    pub(crate) fn map_targets(&mut self, mark: usize, f: impl Fn(&str) -> String) {
+        for entry in &mut self.entries[mark..] {
+            entry.target = f(&entry.target);
+        }
+    }
+
+    /// Prefix the target pointers of all entries recorded since `mark` with
+    /// the pointer segment `token`.
+    pub(crate) fn prefix_targets(&mut self, mark: usize, token: &str) {
+        let prefix = format!("/{}", escape_pointer_token(token));
+        self.map_targets(mark, |target| format!("{prefix}{target}"));
+    }
+
+    /// Number the segments of `tree` in EDIFACT order: pre-group root segments,
+    /// groups (with inter-group segments before the group they precede), then
+    /// post-group root segments.
+    pub(crate) fn number_segments(&mut self, tree: &AssembledTree) {
+        self.segment_numbers.clear();
+        let mut next = 1;
+        let split = tree.post_group_start.min(tree.segments.len());
+        for seg in &tree.segments[..split] {
+            self.number(seg, &mut next);
+        }
+        for (idx, group) in tree.groups.iter().enumerate() {
+            for seg in tree.inter_group_segments.get(&idx).into_iter().flatten() {
+                self.number(seg, &mut next);
+            }
+            for instance in &group.repetitions {
+                self.number_instance(instance, &mut next);
+            }
+        }
+        let trailing = tree.inter_group_segments.get(&tree.groups.len());
+        for seg in trailing.into_iter().flatten() {
+            self.number(seg, &mut next);
+        }
+        for seg in &tree.segments[split..] {
+            self.number(seg, &mut next);
+        }
+    }
+
+    fn number_instance(&mut self, instance: &AssembledGroupInstance, next: &mut u32) {
+        for seg in &instance.segments {
+            self.number(seg, next);
+        }
+        for group in &instance.child_groups {
+            for child in &group.repetitions {
+                self.number_instance(child, next);
+            }
+        }
+    }
+
+    fn number(&mut self, seg: &AssembledSegment, next: &mut u32) {
+        self.segment_numbers.insert(segment_key(seg), *next);
+        *next += 1;
+    }
+
+    /// Give the segments of `copy` the numbers of the corresponding segments
+    /// of `original` (for instances the engine clones before mapping).
+    pub(crate) fn alias_instance(
+        &mut self,
+        original: &AssembledGroupInstance,
+        copy: &AssembledGroupInstance,
+    ) {
+        self.alias_segments(&original.segments, &copy.segments);
+        for (orig_group, copy_group) in original.child_groups.iter().zip(&copy.child_groups) {
+            for (o, c) in orig_group.repetitions.iter().zip(&copy_group.repetitions) {
+                self.alias_instance(o, c);
+            }
+        }
+    }
+
+    /// Segment-wise variant of [`alias_instance`](Self::alias_instance).
+    pub(crate) fn alias_segments<'a>(
+        &mut self,
+        original: impl IntoIterator<Item = &'a AssembledSegment<'a>>,
+        copy: impl IntoIterator<Item = &'a AssembledSegment<'a>>,
+    ) {
+        for (o, c) in original.into_iter().zip(copy) {
+            if let Some(&n) = self.segment_numbers.get(&segment_key(o)) {
+                self.segment_numbers.insert(segment_key(c), n);
+            }
+        }
+    }
+
+    /// The number assigned to `seg` by [`number_segments`](Self::number_segments).
+    pub(crate) fn segment_number(&self, seg: &AssembledSegment) -> Option<u32> {
+        self.segment_numbers.get(&segment_key(seg)).copied()
+    }
+}
+
+/// Segments are identified by address: the engine only ever looks them up
+/// while the numbered tree (or an aliased copy) is borrowed.
+fn segment_key(seg: &AssembledSegment) -> usize {
+    seg as *const AssembledSegment as usize
+}
+
+/// Record an entry if tracing is enabled; `entry` is only built when it is.
+pub(crate) fn record(trace: Option<&mut MappingTrace>, entry: impl FnOnce() -> TraceEntry) {
+    if let Some(trace) = trace {
+        trace.record(entry());
+    }
+}
+
+/// Convert a dotted BO4E field path (e.g., "adresse.ort") to a JSON pointer.
+pub(crate) fn pointer(path: &str) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+    path.split('.')
+        .map(|token| format!("/{}", escape_pointer_token(token)))
+        .collect()
+}
+
+/// Escape a JSON pointer reference token (RFC 6901).
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mig_assembly::assembler::AssembledGroup;
+
+    fn seg(tag: &str) -> AssembledSegment<'static> {
+        AssembledSegment {
+            tag: tag.to_string().into(),
+            elements: vec![],
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_pointer_escapes_tokens() {
+        assert_eq!(pointer(""), "");
+        assert_eq!(pointer("adresse.ort"), "/adresse/ort");
+        assert_eq!(pointer("a/b.c~d"), "/a~1b/c~0d");
+    }
+
+    #[test]
+    fn test_prefix_targets_only_touches_new_entries() {
+        let mut trace = MappingTrace::new();
+        let def: MappingDefinition = toml::from_str(
+            r#"
+[meta]
+entity = "Marktlokation"
+bo4e_type = "Marktlokation"
+source_group = "SG4.SG5"
+source_path = "sg4.sg5_z16"
+
+[fields]
+"#,
+        )
+        .unwrap();
+
+        let mut entry = TraceEntry::for_field(&def, "loc[Z16].1.0", TraceOutcome::Mapped);
+        entry.target = pointer("marktlokationsId");
+        assert_eq!(entry.mig_path, "sg4.sg5_z16.loc[Z16].1.0");
+        assert_eq!(entry.segment_tag.as_deref(), Some("LOC"));
+        trace.record(entry.clone());
+
+        let mark = trace.len();
+        trace.record(entry);
+        trace.prefix_targets(mark, "marktlokation");
+
+        assert_eq!(trace.entries()[0].target, "/marktlokationsId");
+        assert_eq!(trace.entries()[1].target, "/marktlokation/marktlokationsId");
+    }
+
+    #[test]
+    fn test_number_segments_follows_edifact_order() {
+        let tree = AssembledTree {
+            segments: vec![seg("UNH"), seg("BGM"), seg("UNT")],
+            groups: vec![AssembledGroup {
+                group_id: "SG4".to_string(),
+                repetitions: vec![AssembledGroupInstance {
+                    segments: vec![seg("IDE")],
+                    child_groups: vec![AssembledGroup {
+                        group_id: "SG5".to_string(),
+                        repetitions: vec![AssembledGroupInstance {
+                            segments: vec![seg("LOC")],
+                            child_groups: vec![],
+                            skipped_segments: vec![],
+                        }],
+                    }],
+                    skipped_segments: vec![],
+                }],
+            }],
+            post_group_start: 2,
+            inter_group_segments: [(0, vec![seg("UNS")])].into_iter().collect(),
+        };
+
+        let mut trace = MappingTrace::new();
+        trace.number_segments(&tree);
+        let sg4 = &tree.groups[0].repetitions[0];
+        assert_eq!(trace.segment_number(&tree.segments[0]), Some(1));
+        assert_eq!(
+            trace.segment_number(&tree.inter_group_segments[&0][0]),
+            Some(3)
+        );
+        assert_eq!(trace.segment_number(&sg4.segments[0]), Some(4));
+        assert_eq!(
+            trace.segment_number(&sg4.child_groups[0].repetitions[0].segments[0]),
+            Some(5)
+        );
+        assert_eq!(trace.segment_number(&tree.segments[2]), Some(6));
+
+        let copy = sg4.clone();
+        assert_eq!(trace.segment_number(&copy.segments[0]), None);
+        trace.alias_instance(sg4, &copy);
+        assert_eq!(trace.segment_number(&copy.segments[0]), Some(4));
+    }
+}
@@ -0,0 +1,778 @@
+//! Named value transforms for TOML field mappings.
+//!
+//! A `StructuredFieldMapping` may carry a `transform` spec that converts the
+//! extracted EDIFACT value before it is written to BO4E (forward) and converts
+//! the BO4E value back before it is written into the segment (reverse).
+//!
+//! # Spec syntax
+//!
+//! A spec is one or more transforms separated by `|`, each optionally taking
+//! comma-separated arguments in parentheses:
+//!
+//! ```toml
+//! "dtm[137].0.1" = { target = "erstellungsdatum", transform = "dtm_iso8601(303)" }
+//! "qty.0.1" = { target = "menge", transform = "decimal | unit(KWH, MWH)" }
+//! "rff.0.1" = { target = "referenz", transform = "trim | pad_left(10, '0')" }
+//! ```
+//!
+//! Forward applies the chain left to right, reverse applies the inverse of each
+//! transform right to left, so a chain roundtrips like a single transform.
+//!
+//! # Library
+//!
+//! | Name | Forward (EDIFACT → BO4E) | Reverse (BO4E → EDIFACT) |
+//! |------|--------------------------|--------------------------|
+//! | `dtm_iso8601` | DTM value (format detected from shape) → ISO-8601 | ISO-8601 → 102/203/303 depending on precision |
+//! | `dtm_iso8601(F)` | DTM value in format `F` (102, 203, 204, 303, 602, 610) → ISO-8601 | ISO-8601 → format `F` |
+//! | `decimal` | decimal comma → decimal point | unchanged |
+//! | `scale(n)` | value × 10ⁿ (exact decimal shift) | value × 10⁻ⁿ |
+//! | `unit(FROM, TO)` | value in unit `FROM` → unit `TO` | unit `TO` → unit `FROM` |
+//! | `trim` | strip surrounding whitespace | strip surrounding whitespace |
+//! | `pad_left(w, c)` | strip leading `c` | left-pad with `c` to width `w` |
+//! | `pad_right(w, c)` | strip trailing `c` | right-pad with `c` to width `w` |
+//! | `uppercase` | uppercase | uppercase |
+//!
+//! Specs are validated when TOML files are loaded, so an unknown name or a bad
+//! argument is a [`MappingError`](crate::error::MappingError) at load time. At
+//! runtime a value the transform cannot handle (e.g., a malformed date) passes
+//! through unchanged, mirroring how unmapped `enum_map` values behave.
+
+use std::fmt;
+
+/// Error produced while parsing a transform spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransformError {
+    /// The transform name is not part of the library.
+    Unknown(String),
+    /// The transform exists but its arguments are invalid.
+    InvalidArguments { name: String, reason: String },
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown transform '{name}'"),
+            Self::InvalidArguments { name, reason } => {
+                write!(f, "invalid arguments for transform '{name}': {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+/// EDIFACT date/time format qualifiers (data element 2379) supported by `dtm_iso8601`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DtmFormat {
+    /// `CCYYMMDD`
+    F102,
+    /// `CCYYMMDDHHMM`
+    F203,
+    /// `CCYYMMDDHHMMSS`
+    F204,
+    /// `CCYYMMDDHHMMZZZ` — ZZZ is the signed UTC offset in hours, e.g. `+00`.
+    F303,
+    /// `CCYY`
+    F602,
+    /// `CCYYMM`
+    F610,
+}
+
+impl DtmFormat {
+    /// Parse a 2379 format code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "102" => Some(Self::F102),
+            "203" => Some(Self::F203),
+            "204" => Some(Self::F204),
+            "303" => Some(Self::F303),
+            "602" => Some(Self::F602),
+            "610" => Some(Self::F610),
+            _ => None,
+        }
+    }
+
+    /// The 2379 code for this format.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::F102 => "102",
+            Self::F203 => "203",
+            Self::F204 => "204",
+            Self::F303 => "303",
+            Self::F602 => "602",
+            Self::F610 => "610",
+        }
+    }
+
+    /// Detect the format of a DTM value from its shape.
+    fn detect(value: &str) -> Option<Self> {
+        let digits = value.bytes().take_while(|b| b.is_ascii_digit()).count();
+        match (digits, value.len()) {
+            (4, 4) => Some(Self::F602),
+            (6, 6) => Some(Self::F610),
+            (8, 8) => Some(Self::F102),
+            (12, 12) => Some(Self::F203),
+            (14, 14) => Some(Self::F204),
+            (12, 15) => Some(Self::F303),
+            _ => None,
+        }
+    }
+}
+
+/// Units supported by `unit(FROM, TO)`, as (dimension, power-of-ten exponent).
+///
+/// Accepts UN/ECE Recommendation 20 codes used in EDIFACT as well as the
+/// BO4E `Mengeneinheit` spellings.
+fn unit_scale(code: &str) -> Option<(&'static str, i32)> {
+    match code.trim().to_ascii_uppercase().as_str() {
+        "WHR" | "WH" => Some(("energy", 0)),
+        "KWH" => Some(("energy", 3)),
+        "MWH" => Some(("energy", 6)),
+        "GWH" => Some(("energy", 9)),
+        "WTT" | "W" => Some(("power", 0)),
+        "KWT" | "KW" => Some(("power", 3)),
+        "MAW" | "MW" => Some(("power", 6)),
+        "A90" | "GW" => Some(("power", 9)),
+        _ => None,
+    }
+}
+
+/// A single transform from the library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transform {
+    /// DTM value ↔ ISO-8601; `None` detects the format from the value.
+    DtmIso8601(Option<DtmFormat>),
+    /// Decimal comma → decimal point.
+    Decimal,
+    /// Multiply by 10^n on forward, divide on reverse.
+    Scale(i32),
+    /// Unit conversion, stored as the power-of-ten shift applied on forward.
+    Unit {
+        from: String,
+        to: String,
+        shift: i32,
+    },
+    /// Strip surrounding whitespace.
+    Trim,
+    /// Strip leading fill characters / left-pad to width.
+    PadLeft { width: usize, fill: char },
+    /// Strip trailing fill characters / right-pad to width.
+    PadRight { width: usize, fill: char },
+    /// Uppercase in both directions.
+    Uppercase,
+}
+
+impl Transform {
+    /// Parse a single transform call such as `scale(3)` or `trim`.
+    pub fn parse(spec: &str) -> Result<Self, TransformError> {
+        let spec = spec.trim();
+        let (name, args) = match spec.find('(') {
+            Some(open) => {
+                let name = spec[..open].trim();
+                let rest = spec[open + 1..].trim_end();
+                let inner = rest
+                    .strip_suffix(')')
+                    .ok_or_else(|| invalid(name, "missing ')'"))?;
+                let args: Vec<String> = if inner.trim().is_empty() {
+                    Vec::new()
+                } else {
+                    inner.split(',').map(unquote).collect()
+                };
+                (name, args)
+            }
+            None => (spec, Vec::new()),
+        };
+
+        match name {
+            "dtm_iso8601" => match args.as_slice() {
+                [] => Ok(Self::DtmIso8601(None)),
+                [code] => DtmFormat::from_code(code)
+                    .map(|f| Self::DtmIso8601(Some(f)))
+                    .ok_or_else(|| invalid(name, &format!("unsupported DTM format '{code}'"))),
+                _ => Err(invalid(name, "expected at most one DTM format code")),
+            },
+            "decimal" => no_args(name, &args).map(|_| Self::Decimal),
+            "scale" => match args.as_slice() {
+                [n] => n
+                    .parse::<i32>()
+                    .map(Self::Scale)
+                    .map_err(|_| invalid(name, &format!("'{n}' is not an integer exponent"))),
+                _ => Err(invalid(name, "expected one integer exponent")),
+            },
+            "unit" => {
+                match args.as_slice() {
+                    [from, to] => {
+                        let (from_dim, from_exp) = unit_scale(from)
+                            .ok_or_else(|| invalid(name, &format!("unknown unit '{from}'")))?;
+                        let (to_dim, to_exp) = unit_scale(to)
+                            .ok_or_else(|| invalid(name, &format!("unknown unit '{to}'")))?;
+                        if from_dim != to_dim {
+                            return Err(invalid(
+                            name,
+                            &format!("cannot convert {from_dim} unit '{from}' to {to_dim} unit '{to}'"),
+                        ));
+                        }
+                        Ok(Self::Unit {
+                            from: from.to_ascii_uppercase(),
+                            to: to.to_ascii_uppercase(),
+                            shift: from_exp - to_exp,
+                        })
+                    }
+                    _ => Err(invalid(name, "expected source and target unit")),
+                }
+            }
+            "trim" => no_args(name, &args).map(|_| Self::Trim),
+            "pad_left" | "pad_right" => {
+                let (width, fill) = match args.as_slice() {
+                    [w] => (w, None),
+                    [w, c] => (w, Some(c)),
+                    _ => return Err(invalid(name, "expected width and optional fill character")),
+                };
+                let width = width
+                    .parse::<usize>()
+                    .map_err(|_| invalid(name, &format!("'{width}' is not a width")))?;
+                let fill = match fill {
+                    None if name == "pad_left" => '0',
+                    None => ' ',
+                    Some(c) => {
+                        let mut chars = c.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(ch), None) => ch,
+                            _ => {
+                                return Err(invalid(
+                                    name,
+                                    &format!("fill '{c}' must be a single character"),
+                                ))
+                            }
+                        }
+                    }
+                };
+                if name == "pad_left" {
+                    Ok(Self::PadLeft { width, fill })
+                } else {
+                    Ok(Self::PadRight { width, fill })
+                }
+            }
+            "uppercase" => no_args(name, &args).map(|_| Self::Uppercase),
+            _ => Err(TransformError::Unknown(name.to_string())),
+        }
+    }
+
+    /// Apply the EDIFACT → BO4E direction. Returns `None` if the value cannot be converted.
+    pub fn forward(&self, value: &str) -> Option<String> {
+        match self {
+            Self::DtmIso8601(format) => {
+                let format = format.or_else(|| DtmFormat::detect(value))?;
+                dtm_to_iso(value, format)
+            }
+            Self::Decimal => {
+                let normalized = value.trim().replace(',', ".");
+                parse_decimal(&normalized).map(|_| normalized)
+            }
+            Self::Scale(n) => shift_decimal(value, *n),
+            Self::Unit { shift, .. } => shift_decimal(value, *shift),
+            Self::Trim => Some(value.trim().to_string()),
+            Self::PadLeft { fill, .. } => Some(strip_fill(value.trim_start_matches(*fill), value)),
+            Self::PadRight { fill, .. } => Some(strip_fill(value.trim_end_matches(*fill), value)),
+            Self::Uppercase => Some(value.to_uppercase()),
+        }
+    }
+
+    /// Apply the BO4E → EDIFACT direction. Returns `None` if the value cannot be converted.
+    pub fn reverse(&self, value: &str) -> Option<String> {
+        match self {
+            Self::DtmIso8601(format) => iso_to_dtm(value, *format),
+            Self::Decimal => Some(value.to_string()),
+            Self::Scale(n) => shift_decimal(value, -*n),
+            Self::Unit { shift, .. } => shift_decimal(value, -*shift),
+            Self::Trim => Some(value.trim().to_string()),
+            Self::PadLeft { width, fill } => {
+                let len = value.chars().count();
+                let mut out: String = std::iter::repeat(*fill)
+                    .take(width.saturating_sub(len))
+                    .collect();
+                out.push_str(value);
+                Some(out)
+            }
+            Self::PadRight { width, fill } => {
+                let len = value.chars().count();
+                let mut out = value.to_string();
+                out.extend(std::iter::repeat(*fill).take(width.saturating_sub(len)));
+                Some(out)
+            }
+            Self::Uppercase => Some(value.to_uppercase()),
+        }
+    }
+}
+
+/// A `|`-separated chain of transforms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformChain {
+    steps: Vec<Transform>,
+}
+
+impl TransformChain {
+    /// Parse a full transform spec, e.g. `"trim | pad_left(10, '0')"`.
+    pub fn parse(spec: &str) -> Result<Self, TransformError> {
+        let steps = spec
+            .split('|')
+            .map(Transform::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { steps })
+    }
+
+    /// The individual transforms in application order.
+    pub fn steps(&self) -> &[Transform] {
+        &self.steps
+    }
+
+    /// Apply every transform left to right.
+    pub fn forward(&self, value: &str) -> Option<String> {
+        let mut current = value.to_string();
+        for step in &self.steps {
+            current = step.forward(&current)?;
+        }
+        Some(current)
+    }
+
+    /// Apply every inverse transform right to left.
+    pub fn reverse(&self, value: &str) -> Option<String> {
+        let mut current = value.to_string();
+        for step in self.steps.iter().rev() {
+            current = step.reverse(&current)?;
+        }
+        Some(current)
+    }
+}
+
+/// Forward-apply a transform spec, passing the value through unchanged
+/// if the spec is invalid or the value cannot be converted.
+pub fn apply_forward(spec: &str, value: String) -> String {
+    match TransformChain::parse(spec) {
+        Ok(chain) => chain.forward(&value).unwrap_or(value),
+        Err(_) => value,
+    }
+}
+
+/// Reverse-apply a transform spec, passing the value through unchanged
+/// if the spec is invalid or the value cannot be converted.
+pub fn apply_reverse(spec: &str, value: String) -> String {
+    match TransformChain::parse(spec) {
+        Ok(chain) => chain.reverse(&value).unwrap_or(value),
+        Err(_) => value,
+    }
+}
+
+fn invalid(name: &str, reason: &str) -> TransformError {
+    TransformError::InvalidArguments {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn no_args(name: &str, args: &[String]) -> Result<(), TransformError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid(name, "takes no arguments"))
+    }
+}
+
+/// Trim an argument and strip one pair of surrounding single or double quotes.
+fn unquote(arg: &str) -> String {
+    let arg = arg.trim();
+    for q in ['\'', '"'] {
+        if arg.len() >= 2 && arg.starts_with(q) && arg.ends_with(q) {
+            return arg[1..arg.len() - 1].to_string();
+        }
+    }
+    arg.to_string()
+}
+
+/// Keep at least the last character when stripping fill consumed the whole value
+/// (e.g., `"0000"` with fill `'0'` becomes `"0"`, not `""`).
+fn strip_fill(stripped: &str, original: &str) -> String {
+    if stripped.is_empty() && !original.is_empty() {
+        original
+            .chars()
+            .last()
+            .map(String::from)
+            .unwrap_or_default()
+    } else {
+        stripped.to_string()
+    }
+}
+
+// ── Decimal arithmetic ──
+
+/// A decimal number split into sign, integer digits and fraction digits.
+struct Decimal {
+    negative: bool,
+    int_digits: String,
+    frac_digits: String,
+}
+
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    let (negative, unsigned) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (int_part, frac_part) = match unsigned.find(['.', ',']) {
+        Some(pos) => (&unsigned[..pos], &unsigned[pos + 1..]),
+        None => (unsigned, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(Decimal {
+        negative,
+        int_digits: int_part.to_string(),
+        frac_digits: frac_part.to_string(),
+    })
+}
+
+/// Multiply a decimal string by 10^exp by moving the decimal point.
+///
+/// Exact (no floating point) and digit-preserving: trailing fraction zeros are
+/// kept so that `shift(shift(v, n), -n) == v` for normalized inputs.
+fn shift_decimal(value: &str, exp: i32) -> Option<String> {
+    let d = parse_decimal(value)?;
+    let mut digits = format!("{}{}", d.int_digits, d.frac_digits);
+    let mut point = d.int_digits.len() as i64 + exp as i64;
+
+    if point < 0 {
+        digits.insert_str(0, &"0".repeat((-point) as usize));
+        point = 0;
+    }
+    let point = point as usize;
+    if point > digits.len() {
+        digits.push_str(&"0".repeat(point - digits.len()));
+    }
+
+    let (int_part, frac_part) = digits.split_at(point);
+    let int_part = int_part.trim_start_matches('0');
+    let int_part = if int_part.is_empty() { "0" } else { int_part };
+
+    let mut out = String::with_capacity(digits.len() + 2);
+    let is_zero = int_part == "0" && frac_part.bytes().all(|b| b == b'0');
+    if d.negative && !is_zero {
+        out.push('-');
+    }
+    out.push_str(int_part);
+    if !frac_part.is_empty() {
+        out.push('.');
+        out.push_str(frac_part);
+    }
+    Some(out)
+}
+
+// ── Date/time conversion ──
+
+fn all_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn check_date(y: &str, m: &str, d: &str) -> Option<()> {
+    let month: u32 = m.parse().ok()?;
+    let day: u32 = d.parse().ok()?;
+    (all_digits(y) && (1..=12).contains(&month) && (1..=31).contains(&day)).then_some(())
+}
+
+fn check_time(h: &str, mi: &str, s: &str) -> Option<()> {
+    let hour: u32 = h.parse().ok()?;
+    let minute: u32 = mi.parse().ok()?;
+    let second: u32 = s.parse().ok()?;
+    (hour <= 24 && minute <= 59 && second <= 59).then_some(())
+}
+
+/// Convert an EDIFACT DTM value to ISO-8601.
+fn dtm_to_iso(value: &str, format: DtmFormat) -> Option<String> {
+    let expected_len = match format {
+        DtmFormat::F602 => 4,
+        DtmFormat::F610 => 6,
+        DtmFormat::F102 => 8,
+        DtmFormat::F203 => 12,
+        DtmFormat::F204 => 14,
+        DtmFormat::F303 => 15,
+    };
+    if value.len() != expected_len || !value.is_ascii() {
+        return None;
+    }
+    let digits_len = if format == DtmFormat::F303 {
+        12
+    } else {
+        expected_len
+    };
+    if !all_digits(&value[..digits_len]) {
+        return None;
+    }
+
+    match format {
+        DtmFormat::F602 => Some(value.to_string()),
+        DtmFormat::F610 => {
+            let month: u32 = value[4..6].parse().ok()?;
+            (1..=12)
+                .contains(&month)
+                .then(|| format!("{}-{}", &value[..4], &value[4..6]))
+        }
+        _ => {
+            let (y, m, d) = (&value[..4], &value[4..6], &value[6..8]);
+            check_date(y, m, d)?;
+            if format == DtmFormat::F102 {
+                return Some(format!("{y}-{m}-{d}"));
+            }
+            let (h, mi) = (&value[8..10], &value[10..12]);
+            let s = if format == DtmFormat::F204 {
+                &value[12..14]
+            } else {
+                "00"
+            };
+            check_time(h, mi, s)?;
+            let mut out = format!("{y}-{m}-{d}T{h}:{mi}:{s}");
+            if format == DtmFormat::F303 {
+                let sign = &value[12..13];
+                let offset = &value[13..15];
+                if !(sign == "+" || sign == "-") || !all_digits(offset) {
+                    return None;
+                }
+                out.push_str(&format!("{sign}{offset}:00"));
+            }
+            Some(out)
+        }
+    }
+}
+
+/// Components of a parsed ISO-8601 date or date-time.
+struct IsoDateTime<'a> {
+    year: &'a str,
+    month: Option<&'a str>,
+    day: Option<&'a str>,
+    /// `(hour, minute, second)`
+    time: Option<(&'a str, &'a str, &'a str)>,
+    /// `(sign, hours)` — minutes must be zero to be representable in EDIFACT.
+    offset: Option<(char, &'a str)>,
+}
+
+fn parse_iso(value: &str) -> Option<IsoDateTime<'_>> {
+    let value = value.trim();
+    if !value.is_ascii() {
+        return None;
+    }
+    let (date, time) = match value.find(['T', ' ']) {
+        Some(pos) => (&value[..pos], Some(&value[pos + 1..])),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.split('-');
+    let year = date_parts
+        .next()
+        .filter(|y| y.len() == 4 && all_digits(y))?;
+    let month = date_parts.next();
+    let day = date_parts.next();
+    if date_parts.next().is_some()
+        || month.is_some_and(|m| m.len() != 2 || !all_digits(m))
+        || day.is_some_and(|d| d.len() != 2 || !all_digits(d))
+    {
+        return None;
+    }
+    if let (Some(m), Some(d)) = (month, day) {
+        check_date(year, m, d)?;
+    }
+
+    let (time, offset) = match time {
+        None => (None, None),
+        Some(t) => {
+            let (clock, offset) = if let Some(clock) = t.strip_suffix('Z') {
+                (clock, Some(('+', "00")))
+            } else if let Some(pos) = t.rfind(['+', '-']) {
+                let sign = t[pos..].chars().next()?;
+                let off = &t[pos + 1..];
+                let (oh, om) = off.split_once(':').unwrap_or((off, "00"));
+                if oh.len() != 2 || !all_digits(oh) || om != "00" {
+                    return None;
+                }
+                (&t[..pos], Some((sign, oh)))
+            } else {
+                (t, None)
+            };
+            // Drop fractional seconds
+            let clock = clock.split('.').next()?;
+            let mut parts = clock.split(':');
+            let h = parts.next().filter(|h| h.len() == 2)?;
+            let mi = parts.next().filter(|m| m.len() == 2)?;
+            let s = parts.next().unwrap_or("00");
+            if parts.next().is_some() || s.len() != 2 {
+                return None;
+            }
+            check_time(h, mi, s)?;
+            (Some((h, mi, s)), offset)
+        }
+    };
+    if time.is_some() && day.is_none() {
+        return None;
+    }
+
+    Some(IsoDateTime {
+        year,
+        month,
+        day,
+        time,
+        offset,
+    })
+}
+
+/// Convert an ISO-8601 value to an EDIFACT DTM value.
+///
+/// Without an explicit format, date-only values become 102, date-times with an
+/// offset become 303 and naive date-times become 203.
+fn iso_to_dtm(value: &str, format: Option<DtmFormat>) -> Option<String> {
+    let iso = parse_iso(value)?;
+    let format = format.unwrap_or(match (&iso.time, &iso.offset) {
+        (None, _) => DtmFormat::F102,
+        (Some(_), Some(_)) => DtmFormat::F303,
+        (Some(_), None) => DtmFormat::F203,
+    });
+
+    let ymd = || -> Option<String> { Some(format!("{}{}{}", iso.year, iso.month?, iso.day?)) };
+    let (h, mi, s) = iso.time.unwrap_or(("00", "00", "00"));
+
+    match format {
+        DtmFormat::F602 => Some(iso.year.to_string()),
+        DtmFormat::F610 => Some(format!("{}{}", iso.year, iso.month?)),
+        DtmFormat::F102 => ymd(),
+        DtmFormat::F203 => Some(format!("{}{h}{mi}", ymd()?)),
+        DtmFormat::F204 => Some(format!("{}{h}{mi}{s}", ymd()?)),
+        DtmFormat::F303 => {
+            // Naive date-times are taken as UTC, the market convention for 303.
+            let (sign, off) = iso.offset.unwrap_or(('+', "00"));
+            Some(format!("{}{h}{mi}{sign}{off}", ymd()?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unknown_transform() {
+        assert_eq!(
+            TransformChain::parse("loc_qualifier_to_type"),
+            Err(TransformError::Unknown("loc_qualifier_to_type".to_string()))
+        );
+        assert!(matches!(
+            TransformChain::parse("trim | nope"),
+            Err(TransformError::Unknown(n)) if n == "nope"
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_arguments() {
+        assert!(TransformChain::parse("scale(x)").is_err());
+        assert!(TransformChain::parse("dtm_iso8601(999)").is_err());
+        assert!(TransformChain::parse("unit(KWH, KWT)").is_err());
+        assert!(TransformChain::parse("pad_left(10, 'ab')").is_err());
+        assert!(TransformChain::parse("trim(1)").is_err());
+    }
+
+    #[test]
+    fn test_dtm_303_roundtrip() {
+        let chain = TransformChain::parse("dtm_iso8601(303)").unwrap();
+        let iso = chain.forward("202505312200+00").unwrap();
+        assert_eq!(iso, "2025-05-31T22:00:00+00:00");
+        assert_eq!(chain.reverse(&iso).unwrap(), "202505312200+00");
+        assert_eq!(
+            chain.reverse("2025-05-31T22:00:00Z").unwrap(),
+            "202505312200+00"
+        );
+        assert_eq!(
+            chain.reverse("2025-05-31T22:00:00-02:00").unwrap(),
+            "202505312200-02"
+        );
+    }
+
+    #[test]
+    fn test_dtm_102_and_203() {
+        let d102 = TransformChain::parse("dtm_iso8601(102)").unwrap();
+        assert_eq!(d102.forward("20250531").unwrap(), "2025-05-31");
+        assert_eq!(d102.reverse("2025-05-31").unwrap(), "20250531");
+        assert_eq!(d102.reverse("2025-05-31T10:00:00Z").unwrap(), "20250531");
+
+        let d203 = TransformChain::parse("dtm_iso8601(203)").unwrap();
+        assert_eq!(d203.forward("202505311015").unwrap(), "2025-05-31T10:15:00");
+        assert_eq!(d203.reverse("2025-05-31T10:15:00").unwrap(), "202505311015");
+    }
+
+    #[test]
+    fn test_dtm_auto_detect() {
+        let auto = TransformChain::parse("dtm_iso8601").unwrap();
+        assert_eq!(auto.forward("20250531").unwrap(), "2025-05-31");
+        assert_eq!(
+            auto.forward("202505312200+00").unwrap(),
+            "2025-05-31T22:00:00+00:00"
+        );
+        assert_eq!(auto.reverse("2025-05-31").unwrap(), "20250531");
+        assert_eq!(
+            auto.reverse("2025-05-31T22:00:00+01:00").unwrap(),
+            "202505312200+01"
+        );
+        assert_eq!(auto.reverse("2025-05-31T22:00").unwrap(), "202505312200");
+    }
+
+    #[test]
+    fn test_dtm_rejects_invalid_values() {
+        let chain = TransformChain::parse("dtm_iso8601(102)").unwrap();
+        assert_eq!(chain.forward("20251301"), None);
+        assert_eq!(chain.forward("2025053"), None);
+        assert_eq!(chain.reverse("31.05.2025"), None);
+        assert_eq!(
+            apply_forward("dtm_iso8601(102)", "garbage".into()),
+            "garbage"
+        );
+    }
+
+    #[test]
+    fn test_scale_is_exact_and_reversible() {
+        let chain = TransformChain::parse("scale(-3)").unwrap();
+        assert_eq!(chain.forward("1234.5").unwrap(), "1.2345");
+        assert_eq!(chain.forward("5").unwrap(), "0.005");
+        assert_eq!(chain.forward("-1500").unwrap(), "-1.500");
+        assert_eq!(chain.reverse("1.500").unwrap(), "1500");
+        assert_eq!(chain.reverse("0.005").unwrap(), "5");
+        assert_eq!(chain.forward("abc"), None);
+    }
+
+    #[test]
+    fn test_decimal_and_unit() {
+        let chain = TransformChain::parse("decimal | unit(KWH, MWH)").unwrap();
+        assert_eq!(chain.forward("1234,5").unwrap(), "1.2345");
+        assert_eq!(chain.reverse("1.2345").unwrap(), "1234.5");
+
+        let power = TransformChain::parse("unit(MAW, KW)").unwrap();
+        assert_eq!(power.forward("2.5").unwrap(), "2500");
+        assert_eq!(power.reverse("2500").unwrap(), "2.500");
+    }
+
+    #[test]
+    fn test_string_transforms() {
+        let pad = TransformChain::parse("trim | pad_left(10, '0')").unwrap();
+        assert_eq!(pad.forward(" 0000012345 ").unwrap(), "12345");
+        assert_eq!(pad.reverse("12345").unwrap(), "0000012345");
+        assert_eq!(pad.forward("0000").unwrap(), "0");
+
+        let right = TransformChain::parse("pad_right(6)").unwrap();
+        assert_eq!(right.forward("ab    ").unwrap(), "ab");
+        assert_eq!(right.reverse("ab").unwrap(), "ab    ");
+
+        let upper = TransformChain::parse("uppercase").unwrap();
+        assert_eq!(upper.forward("z01").unwrap(), "Z01");
+        assert_eq!(upper.reverse("z01").unwrap(), "Z01");
+    }
+}
@@ -149,6 +149,7 @@ fn test_variant_cache_roundtrip() {
         mig_schema: None,
         segment_structure: None,
         pid_segment_numbers: HashMap::new(),
+        compiled_migs: HashMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
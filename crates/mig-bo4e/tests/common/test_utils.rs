@@ -448,7 +448,7 @@ pub fn run_single_fixture_roundtrip_with_tx_group(
         segs.retain(|s| s.tag != "UNT");
     }
     let original_has_unt_in_root =
-        original_tree.segments.last().map(|s| s.tag.as_str()) == Some("UNT");
+        original_tree.segments.last().map(|s| s.tag.as_ref()) == Some("UNT");
     if !original_has_unt_in_root {
         let unt_assembled_orig = owned_to_assembled(&msg_chunk.unt);
         original_tree.segments.push(unt_assembled_orig);
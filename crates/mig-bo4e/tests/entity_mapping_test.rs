@@ -45,7 +45,7 @@ fn load_pid_filtered_mig(pid_id: &str) -> Option<(MigSchema, HashSet<String>)> {
     Some((filtered, numbers))
 }
 
-fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree> {
+fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree<'static>> {
     let path = Path::new(FIXTURE_DIR).join(fixture_name);
     if !path.exists() {
         return None;
@@ -53,7 +53,10 @@ fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree
     let content = std::fs::read(&path).ok()?;
     let segments = parse_to_segments(&content).ok()?;
     let assembler = Assembler::new(mig);
-    assembler.assemble_generic(&segments).ok()
+    assembler
+        .assemble_generic(&segments)
+        .ok()
+        .map(AssembledTree::into_owned)
 }
 
 fn load_engine() -> Option<MappingEngine> {
@@ -278,7 +281,7 @@ fn test_prozessdaten_reverse_mapping() {
     let instance = engine.map_reverse(&bo4e, def);
 
     // Should produce IDE, DTM (x2), STS segments
-    let tags: Vec<&str> = instance.segments.iter().map(|s| s.tag.as_str()).collect();
+    let tags: Vec<&str> = instance.segments.iter().map(|s| s.tag.as_ref()).collect();
     assert!(tags.contains(&"IDE"), "Should have IDE segment");
     assert!(tags.contains(&"STS"), "Should have STS segment");
 
@@ -292,7 +295,7 @@ fn test_prozessdaten_reverse_mapping() {
         .iter()
         .filter(|s| s.tag == "DTM")
         .collect();
-    let dtm_qualifiers: Vec<&str> = dtms.iter().map(|d| d.elements[0][0].as_str()).collect();
+    let dtm_qualifiers: Vec<&str> = dtms.iter().map(|d| d.elements[0][0].as_ref()).collect();
     assert!(
         dtm_qualifiers.contains(&"92"),
         "Should have DTM with qualifier 92"
@@ -346,14 +349,14 @@ fn test_prozessdaten_roundtrip() {
             .segments
             .iter()
             .find(|s| {
-                s.tag == "DTM" && s.elements[0].first().map(|v| v.as_str()) == Some(qualifier)
+                s.tag == "DTM" && s.elements[0].first().map(|v| v.as_ref()) == Some(qualifier)
             })
             .unwrap_or_else(|| panic!("Original should have DTM+{qualifier}"));
         let recon_dtm = reconstructed
             .segments
             .iter()
             .find(|s| {
-                s.tag == "DTM" && s.elements[0].first().map(|v| v.as_str()) == Some(qualifier)
+                s.tag == "DTM" && s.elements[0].first().map(|v| v.as_ref()) == Some(qualifier)
             })
             .unwrap_or_else(|| panic!("Reconstructed should have DTM+{qualifier}"));
         assert_eq!(
@@ -607,6 +610,7 @@ fn test_map_forward_from_segments_marktteilnehmer() {
             ],
         ],
         segment_number: 1,
+        location: None,
     }];
 
     let bo4e = engine.map_forward_from_segments(&segments, def);
@@ -3,18 +3,19 @@ use mig_assembly::assembler::{
 };
 use mig_bo4e::engine::MappingEngine;
 
-fn make_test_tree() -> AssembledTree {
+fn make_test_tree() -> AssembledTree<'static> {
     AssembledTree {
         segments: vec![],
         groups: vec![AssembledGroup {
             group_id: "SG8".to_string(),
             repetitions: vec![AssembledGroupInstance {
                 segments: vec![AssembledSegment {
-                    tag: "LOC".to_string(),
+                    tag: "LOC".into(),
                     elements: vec![
-                        vec!["Z16".to_string()],             // qualifier (element 0)
-                        vec!["DE0001234567890".to_string()], // C517.3225 (element 1)
+                        vec!["Z16".into()],             // qualifier (element 0)
+                        vec!["DE0001234567890".into()], // C517.3225 (element 1)
                     ],
+                    location: None,
                 }],
                 child_groups: vec![],
                 skipped_segments: vec![],
@@ -131,8 +132,9 @@ source_group = "SG8"
 fn test_extract_from_instance_directly() {
     let instance = AssembledGroupInstance {
         segments: vec![AssembledSegment {
-            tag: "NAD".to_string(),
-            elements: vec![vec!["MS".to_string()], vec!["9876543210".to_string()]],
+            tag: "NAD".into(),
+            elements: vec![vec!["MS".into()], vec!["9876543210".into()]],
+            location: None,
         }],
         child_groups: vec![],
         skipped_segments: vec![],
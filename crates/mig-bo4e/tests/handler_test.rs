@@ -1,4 +1,10 @@
-use mig_assembly::assembler::{AssembledGroupInstance, AssembledSegment};
+use std::sync::Arc;
+
+use mig_assembly::assembler::{
+    AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
+};
+use mig_bo4e::engine::MappingEngine;
+use mig_bo4e::error::MappingError;
 use mig_bo4e::handlers::HandlerRegistry;
 
 #[test]
@@ -53,8 +59,9 @@ fn test_handler_receives_instance_data() {
 
     let instance = AssembledGroupInstance {
         segments: vec![AssembledSegment {
-            tag: "LOC".to_string(),
-            elements: vec![vec!["Z16".to_string()]],
+            tag: "LOC".into(),
+            elements: vec![vec!["Z16".into()]],
+            location: None,
         }],
         child_groups: vec![],
         skipped_segments: vec![],
@@ -85,3 +92,146 @@ fn test_multiple_handlers() {
     assert!(registry.has_handler("handler_b"));
     assert!(registry.has_handler("handler_c"));
 }
+
+#[test]
+fn test_register_and_invoke_reverse_handler() {
+    let mut registry = HandlerRegistry::new();
+    registry.register("forward_only", |_| Ok(serde_json::json!({})));
+    registry.register_reverse("reverse_only", |value| {
+        Ok(vec![AssembledSegment {
+            tag: "FTX".into(),
+            elements: vec![
+                vec!["ACB".into()],
+                vec![],
+                vec![],
+                vec![value["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+                    .into()],
+            ],
+            location: None,
+        }])
+    });
+
+    assert_eq!(registry.len(), 2);
+    assert!(registry.has_handler("reverse_only"));
+    assert!(registry.has_reverse_handler("reverse_only"));
+    assert!(!registry.has_forward_handler("reverse_only"));
+    assert!(!registry.has_reverse_handler("forward_only"));
+
+    let segs = registry
+        .invoke_reverse("reverse_only", &serde_json::json!({"text": "Hallo"}))
+        .unwrap();
+    assert_eq!(segs[0].elements[3], vec!["Hallo"]);
+    assert!(registry
+        .invoke_reverse("forward_only", &serde_json::json!({}))
+        .is_err());
+}
+
+const BEMERKUNG_TOML: &str = r#"
+[meta]
+entity = "Bemerkung"
+bo4e_type = "Bemerkung"
+source_group = "SG6"
+
+[fields]
+"rff.0.0" = { target = "", default = "Z13" }
+"rff.0.1" = "referenz"
+
+[[complex_handlers]]
+name = "ftx_text"
+description = "Join all FTX text components"
+"#;
+
+fn ftx_registry() -> Arc<HandlerRegistry> {
+    let mut registry = HandlerRegistry::new();
+    registry.register("ftx_text", |instance| {
+        let text: Vec<String> = instance
+            .segments
+            .iter()
+            .filter(|s| s.tag == "FTX")
+            .flat_map(|s| s.elements.get(3).cloned().unwrap_or_default())
+            .map(|c| c.into_owned())
+            .collect();
+        Ok(serde_json::json!({ "text": text.join(" ") }))
+    });
+    registry.register_reverse("ftx_text", |value| {
+        let text = value["text"].as_str().unwrap_or_default();
+        Ok(vec![AssembledSegment {
+            tag: "FTX".into(),
+            elements: vec![
+                vec!["ACB".into()],
+                vec![],
+                vec![],
+                vec![text.to_string().into()],
+            ],
+            location: None,
+        }])
+    });
+    Arc::new(registry)
+}
+
+#[test]
+fn test_load_rejects_unregistered_handler_with_file_name() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bemerkung.toml"), BEMERKUNG_TOML).unwrap();
+
+    let err = MappingEngine::load(dir.path()).err().unwrap();
+    match err {
+        MappingError::UnknownHandler { name, file } => {
+            assert_eq!(name, "ftx_text");
+            assert!(file.ends_with("bemerkung.toml"), "file: {file}");
+        }
+        other => panic!("Expected UnknownHandler, got {other:?}"),
+    }
+
+    assert!(MappingEngine::load_with_handlers(dir.path(), &ftx_registry()).is_ok());
+}
+
+#[test]
+fn test_handlers_run_in_forward_and_reverse_mapping() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bemerkung.toml"), BEMERKUNG_TOML).unwrap();
+    let engine = MappingEngine::load_with_handlers(dir.path(), &ftx_registry()).unwrap();
+
+    let tree = AssembledTree {
+        segments: vec![],
+        groups: vec![AssembledGroup {
+            group_id: "SG6".to_string(),
+            repetitions: vec![AssembledGroupInstance {
+                segments: vec![
+                    AssembledSegment {
+                        tag: "RFF".into(),
+                        elements: vec![vec!["Z13".into(), "4711".into()]],
+                        location: None,
+                    },
+                    AssembledSegment {
+                        tag: "FTX".into(),
+                        elements: vec![
+                            vec!["ACB".into()],
+                            vec![],
+                            vec![],
+                            vec!["Zähler".into(), "defekt".into()],
+                        ],
+                        location: None,
+                    },
+                ],
+                child_groups: vec![],
+                skipped_segments: vec![],
+            }],
+        }],
+        post_group_start: 0,
+        inter_group_segments: std::collections::BTreeMap::new(),
+    };
+
+    let all = engine.map_all_forward(&tree);
+    assert_eq!(all["bemerkung"]["referenz"], "4711");
+    assert_eq!(all["bemerkung"]["text"], "Zähler defekt");
+
+    let def = &engine.definitions()[0];
+    let instance = engine.map_reverse(&all["bemerkung"], def);
+    let tags: Vec<&str> = instance.segments.iter().map(|s| s.tag.as_ref()).collect();
+    assert_eq!(tags, vec!["RFF", "FTX"]);
+    assert_eq!(instance.segments[1].elements[3], vec!["Zähler defekt"]);
+}
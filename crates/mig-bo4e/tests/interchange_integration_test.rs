@@ -122,9 +122,11 @@ fn test_interchange_hierarchy_from_55001_fixture() {
                 nachrichten: vec![mig_bo4e::Nachricht {
                     unh_referenz: unh_ref,
                     nachrichten_typ: msg_type,
+                    nachrichtenkopf: mig_bo4e::model::extract_nachrichtenkopf(&msg.unh),
                     stammdaten: mapped.stammdaten,
                     transaktionen: mapped.transaktionen,
                 }],
+                gruppen: vec![],
             };
 
             // Verify it serializes to valid JSON
@@ -90,7 +90,7 @@ fn test_schema() -> serde_json::Value {
     })
 }
 
-fn make_tree() -> AssembledTree {
+fn make_tree() -> AssembledTree<'static> {
     AssembledTree {
         segments: vec![],
         groups: vec![AssembledGroup {
@@ -102,15 +102,12 @@ fn make_tree() -> AssembledTree {
                         group_id: "SG5".to_string(),
                         repetitions: vec![AssembledGroupInstance {
                             segments: vec![AssembledSegment {
-                                tag: "LOC".to_string(),
+                                tag: "LOC".into(),
                                 elements: vec![
-                                    vec!["Z16".to_string()],
-                                    vec![
-                                        "DE0001234567890".to_string(),
-                                        "".to_string(),
-                                        "9".to_string(),
-                                    ],
+                                    vec!["Z16".into()],
+                                    vec!["DE0001234567890".into(), "".into(), "9".into()],
                                 ],
+                                location: None,
                             }],
                             child_groups: vec![],
                             skipped_segments: vec![],
@@ -120,8 +117,9 @@ fn make_tree() -> AssembledTree {
                         group_id: "SG8".to_string(),
                         repetitions: vec![AssembledGroupInstance {
                             segments: vec![AssembledSegment {
-                                tag: "SEQ".to_string(),
-                                elements: vec![vec!["Z98".to_string()], vec!["REF123".to_string()]],
+                                tag: "SEQ".into(),
+                                elements: vec![vec!["Z98".into()], vec!["REF123".into()]],
+                                location: None,
                             }],
                             child_groups: vec![],
                             skipped_segments: vec![],
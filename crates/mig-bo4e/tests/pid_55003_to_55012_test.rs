@@ -864,9 +864,11 @@ macro_rules! interchange_test {
                 nachrichten: vec![mig_bo4e::Nachricht {
                     unh_referenz: unh_ref,
                     nachrichten_typ: msg_type,
+                    nachrichtenkopf: mig_bo4e::model::extract_nachrichtenkopf(&msg.unh),
                     stammdaten: mapped.stammdaten,
                     transaktionen: mapped.transaktionen,
                 }],
+                gruppen: vec![],
             };
 
             // Verify JSON serialization roundtrip
@@ -40,7 +40,7 @@ fn load_pid_filtered_mig(pid_id: &str) -> Option<MigSchema> {
     Some(filter_mig_for_pid(&mig, &numbers))
 }
 
-fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree> {
+fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree<'static>> {
     let path = Path::new(FIXTURE_DIR).join(fixture_name);
     if !path.exists() {
         return None;
@@ -48,7 +48,10 @@ fn assemble_fixture(mig: &MigSchema, fixture_name: &str) -> Option<AssembledTree
     let content = std::fs::read(&path).ok()?;
     let segments = parse_to_segments(&content).ok()?;
     let assembler = Assembler::new(mig);
-    assembler.assemble_generic(&segments).ok()
+    assembler
+        .assemble_generic(&segments)
+        .ok()
+        .map(AssembledTree::into_owned)
 }
 
 fn load_engine() -> Option<MappingEngine> {
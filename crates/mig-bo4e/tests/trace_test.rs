@@ -0,0 +1,296 @@
+use mig_assembly::assembler::{
+    AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
+};
+use mig_bo4e::engine::MappingEngine;
+use mig_bo4e::model::{MappedMessage, Transaktion};
+use mig_bo4e::trace::{MappingTrace, TraceOutcome};
+
+fn seg(tag: &str, elements: &[&[&str]]) -> AssembledSegment<'static> {
+    AssembledSegment {
+        tag: tag.to_string().into(),
+        elements: elements
+            .iter()
+            .map(|e| e.iter().map(|c| c.to_string().into()).collect())
+            .collect(),
+        location: None,
+    }
+}
+
+fn instance(segments: Vec<AssembledSegment<'static>>) -> AssembledGroupInstance<'static> {
+    AssembledGroupInstance {
+        segments,
+        child_groups: vec![],
+        skipped_segments: vec![],
+    }
+}
+
+const MARKTLOKATION_TOML: &str = r#"
+[meta]
+entity = "Marktlokation"
+bo4e_type = "Marktlokation"
+source_group = "SG4.SG5"
+source_path = "sg4.sg5_z16"
+discriminator = "LOC.0.0=Z16"
+
+[fields]
+"loc.0.0" = { target = "", default = "Z16" }
+"loc.1.0" = "marktlokationsId"
+"loc.2.0" = "bilanzkreis"
+"#;
+
+const PROZESSDATEN_TOML: &str = r#"
+[meta]
+entity = "Prozessdaten"
+bo4e_type = "Prozessdaten"
+source_group = "SG4"
+
+[fields]
+"ide.0.0" = { target = "", default = "24" }
+"ide.1.0" = "vorgangId"
+"sts.0.0" = { target = "transaktionsgrund", enum_map = { "7" = "ANMELDUNG" } }
+"#;
+
+const MESSLOKATION_TOML: &str = r#"
+[meta]
+entity = "Messlokation"
+bo4e_type = "Messlokation"
+source_group = "SG4.SG5"
+source_path = "sg4.sg5_z17"
+discriminator = "LOC.0.0=Z17"
+
+[fields]
+"loc.1.0" = "messlokationsId"
+"#;
+
+fn load_tx_engine() -> (tempfile::TempDir, MappingEngine) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("marktlokation.toml"), MARKTLOKATION_TOML).unwrap();
+    std::fs::write(dir.path().join("messlokation.toml"), MESSLOKATION_TOML).unwrap();
+    std::fs::write(dir.path().join("prozessdaten.toml"), PROZESSDATEN_TOML).unwrap();
+    let engine = MappingEngine::load(dir.path()).unwrap();
+    (dir, engine)
+}
+
+fn make_tree() -> AssembledTree<'static> {
+    let mut sg4 = instance(vec![
+        seg("IDE", &[&["24"], &["VORGANG001"]]),
+        seg("STS", &[&["7"]]),
+    ]);
+    sg4.child_groups.push(AssembledGroup {
+        group_id: "SG5".to_string(),
+        repetitions: vec![instance(vec![seg(
+            "LOC",
+            &[&["Z16"], &["DE00014545768S0000000000000003054"]],
+        )])],
+    });
+
+    AssembledTree {
+        segments: vec![
+            seg("UNH", &[&["1"], &["UTILMD", "D", "11A", "UN", "S2.1"]]),
+            seg("BGM", &[&["E01"]]),
+            seg("UNT", &[&["5"], &["1"]]),
+        ],
+        groups: vec![AssembledGroup {
+            group_id: "SG4".to_string(),
+            repetitions: vec![sg4],
+        }],
+        post_group_start: 2,
+        inter_group_segments: std::collections::BTreeMap::new(),
+    }
+}
+
+#[test]
+fn test_forward_trace_records_file_key_segment_and_pointer() {
+    let (_dir, tx_engine) = load_tx_engine();
+    let msg_engine = MappingEngine::from_definitions(vec![]);
+    let tree = make_tree();
+
+    let mut trace = MappingTrace::new();
+    let mapped = MappingEngine::map_interchange_traced(
+        &msg_engine,
+        &tx_engine,
+        &tree,
+        "SG4",
+        false,
+        &mut trace,
+    );
+    let untraced = MappingEngine::map_interchange(&msg_engine, &tx_engine, &tree, "SG4", false);
+    assert_eq!(
+        serde_json::to_value(&mapped.transaktionen).unwrap(),
+        serde_json::to_value(&untraced.transaktionen).unwrap(),
+        "tracing must not change the mapping result"
+    );
+
+    let malo_id = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "loc.1.0" && e.entity == "Marktlokation")
+        .expect("marktlokationsId traced");
+    assert!(malo_id
+        .file
+        .as_deref()
+        .unwrap()
+        .ends_with("marktlokation.toml"));
+    assert_eq!(malo_id.mig_path, "sg4.sg5_z16.loc.1.0");
+    assert_eq!(malo_id.segment_tag.as_deref(), Some("LOC"));
+    assert_eq!(malo_id.segment_number, Some(5));
+    assert_eq!(
+        malo_id.target,
+        "/transaktionen/0/stammdaten/marktlokation/marktlokationsId"
+    );
+    assert_eq!(
+        malo_id.value.as_deref(),
+        Some("DE00014545768S0000000000000003054")
+    );
+    assert_eq!(malo_id.outcome, TraceOutcome::Mapped);
+
+    let grund = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "sts.0.0")
+        .unwrap();
+    assert_eq!(
+        grund.target,
+        "/transaktionen/0/transaktionsdaten/transaktionsgrund"
+    );
+    assert_eq!(grund.segment_number, Some(4));
+    assert_eq!(grund.value.as_deref(), Some("7"));
+    assert_eq!(grund.mapped_value.as_deref(), Some("ANMELDUNG"));
+
+    let bilanzkreis = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "loc.2.0")
+        .unwrap();
+    assert_eq!(bilanzkreis.outcome, TraceOutcome::EmptyValue);
+
+    let melo = trace
+        .entries()
+        .iter()
+        .find(|e| e.entity == "Messlokation")
+        .unwrap();
+    assert_eq!(melo.outcome, TraceOutcome::DiscriminatorMismatch);
+    assert_eq!(melo.target, "/transaktionen/0/stammdaten/messlokation");
+}
+
+#[test]
+fn test_reverse_trace_records_defaults_and_missing_values() {
+    let (_dir, tx_engine) = load_tx_engine();
+    let msg_engine = MappingEngine::from_definitions(vec![]);
+    let mapped = MappedMessage {
+        stammdaten: serde_json::json!({}),
+        transaktionen: vec![Transaktion {
+            stammdaten: serde_json::json!({
+                "marktlokation": [
+                    { "marktlokationsId": "DE0001" },
+                    { "marktlokationsId": "DE0002" }
+                ]
+            }),
+            transaktionsdaten: serde_json::json!({
+                "vorgangId": "VORGANG001",
+                "transaktionsgrund": "ANMELDUNG"
+            }),
+            nesting_info: Default::default(),
+        }],
+        nesting_info: Default::default(),
+    };
+
+    let mut trace = MappingTrace::new();
+    MappingEngine::map_interchange_reverse_traced(
+        &msg_engine,
+        &tx_engine,
+        &mapped,
+        "SG4",
+        None,
+        &mut trace,
+    );
+
+    let second_id = trace
+        .entries()
+        .iter()
+        .find(|e| e.value.as_deref() == Some("DE0002"))
+        .unwrap();
+    assert_eq!(
+        second_id.target,
+        "/transaktionen/0/stammdaten/marktlokation/1/marktlokationsId"
+    );
+    assert_eq!(second_id.outcome, TraceOutcome::Mapped);
+
+    let qualifier = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "loc.0.0")
+        .unwrap();
+    assert_eq!(qualifier.outcome, TraceOutcome::DefaultApplied);
+    assert_eq!(qualifier.mapped_value.as_deref(), Some("Z16"));
+
+    let grund = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "sts.0.0")
+        .unwrap();
+    assert_eq!(
+        grund.target,
+        "/transaktionen/0/transaktionsdaten/transaktionsgrund"
+    );
+    assert_eq!(grund.value.as_deref(), Some("ANMELDUNG"));
+    assert_eq!(grund.mapped_value.as_deref(), Some("7"));
+
+    assert!(trace
+        .entries()
+        .iter()
+        .any(|e| e.field == "loc.2.0" && e.outcome == TraceOutcome::EmptyValue));
+}
+
+#[test]
+fn test_when_predicate_skip_is_traced() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("referenz.toml"),
+        r#"
+[meta]
+entity = "Referenz"
+bo4e_type = "Referenz"
+source_group = "SG6"
+
+[fields]
+"rff.0.0" = "referenzart"
+"rff.0.1" = { target = "vorgangsnummer", when = "rff.0.0 == 'Z13'" }
+"#,
+    )
+    .unwrap();
+    let engine = MappingEngine::load(dir.path()).unwrap();
+    let tree = AssembledTree {
+        segments: vec![],
+        groups: vec![AssembledGroup {
+            group_id: "SG6".to_string(),
+            repetitions: vec![instance(vec![seg("RFF", &[&["Z19", "4711"]])])],
+        }],
+        post_group_start: 0,
+        inter_group_segments: std::collections::BTreeMap::new(),
+    };
+
+    let mut trace = MappingTrace::new();
+    engine.map_forward_traced(&tree, &engine.definitions()[0], 0, &mut trace);
+    let skipped = trace
+        .entries()
+        .iter()
+        .find(|e| e.field == "rff.0.1")
+        .unwrap();
+    assert_eq!(skipped.outcome, TraceOutcome::ConditionNotMet);
+    assert!(skipped.outcome.is_skipped());
+    assert_eq!(skipped.target, "/vorgangsnummer");
+    assert_eq!(skipped.segment_number, Some(1));
+
+    let mut trace = MappingTrace::new();
+    engine.map_reverse_traced(
+        &serde_json::json!({ "referenzart": "Z19", "vorgangsnummer": "4711" }),
+        &engine.definitions()[0],
+        &mut trace,
+    );
+    let outcomes: Vec<_> = trace.entries().iter().map(|e| e.outcome).collect();
+    assert_eq!(
+        outcomes,
+        vec![TraceOutcome::Mapped, TraceOutcome::ConditionNotMet]
+    );
+}
@@ -0,0 +1,180 @@
+use mig_assembly::assembler::{
+    AssembledGroup, AssembledGroupInstance, AssembledSegment, AssembledTree,
+};
+use mig_bo4e::engine::MappingEngine;
+use mig_bo4e::error::MappingError;
+
+fn seg(tag: &str, elements: &[&[&str]]) -> AssembledSegment<'static> {
+    AssembledSegment {
+        tag: tag.to_string().into(),
+        elements: elements
+            .iter()
+            .map(|e| e.iter().map(|c| c.to_string().into()).collect())
+            .collect(),
+        location: None,
+    }
+}
+
+fn make_tree(segments: Vec<AssembledSegment>) -> AssembledTree {
+    AssembledTree {
+        segments: vec![],
+        groups: vec![AssembledGroup {
+            group_id: "SG6".to_string(),
+            repetitions: vec![AssembledGroupInstance {
+                segments,
+                child_groups: vec![],
+                skipped_segments: vec![],
+            }],
+        }],
+        post_group_start: 0,
+        inter_group_segments: std::collections::BTreeMap::new(),
+    }
+}
+
+fn load(toml: &str) -> Result<MappingEngine, MappingError> {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("zeitraum.toml"), toml).unwrap();
+    MappingEngine::load(dir.path())
+}
+
+const ZEITRAUM_TOML: &str = r#"
+[meta]
+entity = "Zeitraum"
+bo4e_type = "Zeitraum"
+source_group = "SG6"
+
+[fields]
+"dtm[163].0.0" = { target = "", default = "163" }
+"dtm[163].0.1" = { target = "startdatum", transform = "dtm_iso8601" }
+"dtm[163].0.2" = { target = "", default = "303" }
+"qty.0.0" = { target = "", default = "220" }
+"qty.0.1" = { target = "menge", transform = "unit(KWH, MWH)" }
+"rff.0.0" = { target = "referenzart" }
+"rff.0.1" = { target = "vorgangsnummer", when = "rff.0.0 == 'Z13'" }
+"#;
+
+#[test]
+fn test_forward_applies_transforms() {
+    let engine = load(ZEITRAUM_TOML).unwrap();
+    let tree = make_tree(vec![
+        seg("DTM", &[&["163", "202505312200+00", "303"]]),
+        seg("QTY", &[&["220", "1500"]]),
+        seg("RFF", &[&["Z13", "4711"]]),
+    ]);
+
+    let result = engine.map_forward(&tree, &engine.definitions()[0], 0);
+    assert_eq!(result["startdatum"], "2025-05-31T22:00:00+00:00");
+    assert_eq!(result["menge"], "1.500");
+    assert_eq!(result["vorgangsnummer"], "4711");
+}
+
+#[test]
+fn test_forward_skips_field_when_predicate_fails() {
+    let engine = load(ZEITRAUM_TOML).unwrap();
+    let tree = make_tree(vec![seg("RFF", &[&["Z19", "4711"]])]);
+
+    let result = engine.map_forward(&tree, &engine.definitions()[0], 0);
+    assert_eq!(result["referenzart"], "Z19");
+    assert!(result.get("vorgangsnummer").is_none());
+}
+
+#[test]
+fn test_reverse_applies_inverse_transforms() {
+    let engine = load(ZEITRAUM_TOML).unwrap();
+    let bo4e = serde_json::json!({
+        "startdatum": "2025-05-31T22:00:00+00:00",
+        "menge": "1.500",
+        "referenzart": "Z13",
+        "vorgangsnummer": "4711",
+    });
+
+    let instance = engine.map_reverse(&bo4e, &engine.definitions()[0]);
+    let tags: Vec<&str> = instance.segments.iter().map(|s| s.tag.as_ref()).collect();
+    assert_eq!(tags, vec!["DTM", "QTY", "RFF"]);
+    assert_eq!(
+        instance.segments[0].elements[0],
+        vec!["163", "202505312200+00", "303"]
+    );
+    assert_eq!(instance.segments[1].elements[0], vec!["220", "1500"]);
+    assert_eq!(instance.segments[2].elements[0], vec!["Z13", "4711"]);
+}
+
+#[test]
+fn test_reverse_drops_field_when_predicate_fails() {
+    let engine = load(ZEITRAUM_TOML).unwrap();
+    let bo4e = serde_json::json!({
+        "referenzart": "Z19",
+        "vorgangsnummer": "4711",
+    });
+
+    let instance = engine.map_reverse(&bo4e, &engine.definitions()[0]);
+    let rff = instance.segments.iter().find(|s| s.tag == "RFF").unwrap();
+    assert_eq!(rff.elements[0], vec!["Z19"]);
+}
+
+#[test]
+fn test_unknown_transform_fails_at_load() {
+    let err = load(
+        r#"
+[meta]
+entity = "Zeitraum"
+bo4e_type = "Zeitraum"
+source_group = "SG6"
+
+[fields]
+"dtm[163].0.1" = { target = "startdatum", transform = "dtm_to_iso" }
+"#,
+    )
+    .err()
+    .expect("unknown transform must be rejected");
+
+    match err {
+        MappingError::UnknownTransform { name, file } => {
+            assert_eq!(name, "dtm_to_iso");
+            assert!(file.ends_with("zeitraum.toml"), "file: {file}");
+        }
+        other => panic!("Expected UnknownTransform, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_invalid_transform_arguments_and_condition_fail_at_load() {
+    let err = load(
+        r#"
+[meta]
+entity = "Zeitraum"
+bo4e_type = "Zeitraum"
+source_group = "SG6"
+
+[fields]
+"qty.0.1" = { target = "menge", transform = "unit(KWH, KWT)" }
+"#,
+    )
+    .err()
+    .unwrap();
+    assert!(
+        matches!(err, MappingError::InvalidTransform { .. }),
+        "{err:?}"
+    );
+
+    let err = load(
+        r#"
+[meta]
+entity = "Zeitraum"
+bo4e_type = "Zeitraum"
+source_group = "SG6"
+
+[fields]
+"rff.0.0" = "referenzart"
+
+[companion_fields]
+"rff.0.1" = { target = "vorgangsnummer", when = "rff.0.0 = 'Z13'" }
+"#,
+    )
+    .err()
+    .unwrap();
+    assert!(
+        matches!(err, MappingError::InvalidCondition { .. }),
+        "{err:?}"
+    );
+}
@@ -268,7 +268,7 @@ fn run_generated_roundtrip(pid: &str) {
     reverse_tree.segments.insert(0, unh_assembled);
     reverse_tree.post_group_start += 1;
 
-    let original_has_unt = original_tree.segments.last().map(|s| s.tag.as_str()) == Some("UNT");
+    let original_has_unt = original_tree.segments.last().map(|s| s.tag.as_ref()) == Some("UNT");
     if original_has_unt {
         let unt_assembled = test_utils::owned_to_assembled(&msg_chunk.unt);
         reverse_tree.segments.push(unt_assembled);
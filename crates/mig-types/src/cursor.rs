@@ -117,6 +117,7 @@ mod tests {
             id: id.to_string(),
             elements: vec![],
             segment_number: 0,
+            location: None,
         }
     }
 
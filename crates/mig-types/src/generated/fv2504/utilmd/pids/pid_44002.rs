@@ -5,9 +5,11 @@
 use serde::{Deserialize, Serialize};
 use crate::segment::OwnedSegment;
 use crate::cursor::{SegmentCursor, SegmentNotFound, peek_is, consume, expect_segment};
+use crate::traits::PidTree;
+use crate::tree::{OwnedGroupInstance, OwnedTree};
 
 /// SG10 — Klassentyp, Code
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg10 {
     pub cav: Option<OwnedSegment>,
     pub cci: Option<OwnedSegment>,
@@ -15,21 +17,21 @@ pub struct Pid44002Sg10 {
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: DP
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg12Dp {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z04
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg12Z04 {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z05
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg12Z05 {
     pub nad: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -37,14 +39,14 @@ pub struct Pid44002Sg12Z05 {
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z09
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg12Z09 {
     pub nad: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
 }
 
 /// SG2 — Beteiligter, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg2 {
     pub nad: Option<OwnedSegment>,
     pub sg3_ic: Vec<Pid44002Sg3Ic>,
@@ -52,14 +54,14 @@ pub struct Pid44002Sg2 {
 
 /// SG3 — Funktion des Ansprechpartners, Code
 /// Qualifiers: IC
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg3Ic {
     pub com: Option<OwnedSegment>,
     pub cta: Option<OwnedSegment>,
 }
 
 /// SG4 — Objekt, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg4 {
     pub dtm: Option<OwnedSegment>,
     pub ftx: Option<OwnedSegment>,
@@ -87,13 +89,13 @@ pub struct Pid44002Sg4 {
 
 /// SG5 — Ortsangabe, Qualifier
 /// Qualifiers: 172
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg5172 {
     pub loc: Option<OwnedSegment>,
 }
 
 /// SG6 — Referenz, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg6 {
     pub dtm: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -101,7 +103,7 @@ pub struct Pid44002Sg6 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z01
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z01 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44002Sg10>,
@@ -110,7 +112,7 @@ pub struct Pid44002Sg8Z01 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z02
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z02 {
     pub pia: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -119,7 +121,7 @@ pub struct Pid44002Sg8Z02 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z03
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z03 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -128,7 +130,7 @@ pub struct Pid44002Sg8Z03 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z05
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z05 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -137,7 +139,7 @@ pub struct Pid44002Sg8Z05 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z07
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z07 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -146,7 +148,7 @@ pub struct Pid44002Sg8Z07 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z09
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z09 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -155,7 +157,7 @@ pub struct Pid44002Sg8Z09 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z12
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z12 {
     pub seq: Option<OwnedSegment>,
     pub sg9: Vec<Pid44002Sg9>,
@@ -163,7 +165,7 @@ pub struct Pid44002Sg8Z12 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z13
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z13 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44002Sg10>,
@@ -171,7 +173,7 @@ pub struct Pid44002Sg8Z13 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z18
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z18 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -180,7 +182,7 @@ pub struct Pid44002Sg8Z18 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z20
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z20 {
     pub pia: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -190,7 +192,7 @@ pub struct Pid44002Sg8Z20 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z35
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z35 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44002Sg10>,
@@ -198,7 +200,7 @@ pub struct Pid44002Sg8Z35 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z50
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg8Z50 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -206,7 +208,7 @@ pub struct Pid44002Sg8Z50 {
 }
 
 /// SG9 — Menge, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44002Sg9 {
     pub qty: Option<OwnedSegment>,
 }
@@ -1021,3 +1023,1043 @@ impl Pid44002 {
         })
     }
 }
+
+impl Pid44002Sg10 {
+    /// Set the `CCI` segment.
+    pub fn with_cci(mut self, segment: OwnedSegment) -> Self {
+        self.cci = Some(segment);
+        self
+    }
+
+    /// Set the `CAV` segment.
+    pub fn with_cav(mut self, segment: OwnedSegment) -> Self {
+        self.cav = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.cci.iter().cloned());
+        segments.extend(self.cav.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.cci);
+        instance.segments.extend(self.cav);
+        instance
+    }
+}
+
+impl Pid44002Sg12Dp {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44002Sg12Z04 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44002Sg12Z05 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.segments.extend(self.rff);
+        instance
+    }
+}
+
+impl Pid44002Sg12Z09 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.segments.extend(self.rff);
+        instance
+    }
+}
+
+impl Pid44002Sg2 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Add an `SG3` repetition.
+    pub fn with_sg3_ic(mut self, item: Pid44002Sg3Ic) -> Self {
+        self.sg3_ic.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        for item in &self.sg3_ic {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.push_group("SG3", self.sg3_ic.into_iter().map(Pid44002Sg3Ic::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg3Ic {
+    /// Set the `CTA` segment.
+    pub fn with_cta(mut self, segment: OwnedSegment) -> Self {
+        self.cta = Some(segment);
+        self
+    }
+
+    /// Set the `COM` segment.
+    pub fn with_com(mut self, segment: OwnedSegment) -> Self {
+        self.com = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.cta.iter().cloned());
+        segments.extend(self.com.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.cta);
+        instance.segments.extend(self.com);
+        instance
+    }
+}
+
+impl Pid44002Sg4 {
+    /// Set the `IDE` segment.
+    pub fn with_ide(mut self, segment: OwnedSegment) -> Self {
+        self.ide = Some(segment);
+        self
+    }
+
+    /// Set the `DTM` segment.
+    pub fn with_dtm(mut self, segment: OwnedSegment) -> Self {
+        self.dtm = Some(segment);
+        self
+    }
+
+    /// Set the `STS` segment.
+    pub fn with_sts(mut self, segment: OwnedSegment) -> Self {
+        self.sts = Some(segment);
+        self
+    }
+
+    /// Set the `FTX` segment.
+    pub fn with_ftx(mut self, segment: OwnedSegment) -> Self {
+        self.ftx = Some(segment);
+        self
+    }
+
+    /// Add an `SG5` repetition.
+    pub fn with_sg5_172(mut self, item: Pid44002Sg5172) -> Self {
+        self.sg5_172.push(item);
+        self
+    }
+
+    /// Add an `SG6` repetition.
+    pub fn with_sg6(mut self, item: Pid44002Sg6) -> Self {
+        self.sg6.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z01(mut self, item: Pid44002Sg8Z01) -> Self {
+        self.sg8_z01.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z02(mut self, item: Pid44002Sg8Z02) -> Self {
+        self.sg8_z02.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z07(mut self, item: Pid44002Sg8Z07) -> Self {
+        self.sg8_z07.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z12(mut self, item: Pid44002Sg8Z12) -> Self {
+        self.sg8_z12.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z18(mut self, item: Pid44002Sg8Z18) -> Self {
+        self.sg8_z18.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z03(mut self, item: Pid44002Sg8Z03) -> Self {
+        self.sg8_z03.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z50(mut self, item: Pid44002Sg8Z50) -> Self {
+        self.sg8_z50.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z09(mut self, item: Pid44002Sg8Z09) -> Self {
+        self.sg8_z09.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z20(mut self, item: Pid44002Sg8Z20) -> Self {
+        self.sg8_z20.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z05(mut self, item: Pid44002Sg8Z05) -> Self {
+        self.sg8_z05.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z13(mut self, item: Pid44002Sg8Z13) -> Self {
+        self.sg8_z13.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z35(mut self, item: Pid44002Sg8Z35) -> Self {
+        self.sg8_z35.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z09(mut self, item: Pid44002Sg12Z09) -> Self {
+        self.sg12_z09.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z04(mut self, item: Pid44002Sg12Z04) -> Self {
+        self.sg12_z04.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_dp(mut self, item: Pid44002Sg12Dp) -> Self {
+        self.sg12_dp.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z05(mut self, item: Pid44002Sg12Z05) -> Self {
+        self.sg12_z05.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.ide.iter().cloned());
+        segments.extend(self.dtm.iter().cloned());
+        segments.extend(self.sts.iter().cloned());
+        segments.extend(self.ftx.iter().cloned());
+        for item in &self.sg5_172 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg6 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z01 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z02 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z07 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z12 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z18 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z03 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z50 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z09 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z20 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z05 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z13 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z35 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z09 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z04 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_dp {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z05 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.ide);
+        instance.segments.extend(self.dtm);
+        instance.segments.extend(self.sts);
+        instance.segments.extend(self.ftx);
+        instance.push_group("SG5", self.sg5_172.into_iter().map(Pid44002Sg5172::into_group_instance));
+        instance.push_group("SG6", self.sg6.into_iter().map(Pid44002Sg6::into_group_instance));
+        instance.push_group("SG8", self.sg8_z01.into_iter().map(Pid44002Sg8Z01::into_group_instance));
+        instance.push_group("SG8", self.sg8_z02.into_iter().map(Pid44002Sg8Z02::into_group_instance));
+        instance.push_group("SG8", self.sg8_z07.into_iter().map(Pid44002Sg8Z07::into_group_instance));
+        instance.push_group("SG8", self.sg8_z12.into_iter().map(Pid44002Sg8Z12::into_group_instance));
+        instance.push_group("SG8", self.sg8_z18.into_iter().map(Pid44002Sg8Z18::into_group_instance));
+        instance.push_group("SG8", self.sg8_z03.into_iter().map(Pid44002Sg8Z03::into_group_instance));
+        instance.push_group("SG8", self.sg8_z50.into_iter().map(Pid44002Sg8Z50::into_group_instance));
+        instance.push_group("SG8", self.sg8_z09.into_iter().map(Pid44002Sg8Z09::into_group_instance));
+        instance.push_group("SG8", self.sg8_z20.into_iter().map(Pid44002Sg8Z20::into_group_instance));
+        instance.push_group("SG8", self.sg8_z05.into_iter().map(Pid44002Sg8Z05::into_group_instance));
+        instance.push_group("SG8", self.sg8_z13.into_iter().map(Pid44002Sg8Z13::into_group_instance));
+        instance.push_group("SG8", self.sg8_z35.into_iter().map(Pid44002Sg8Z35::into_group_instance));
+        instance.push_group("SG12", self.sg12_z09.into_iter().map(Pid44002Sg12Z09::into_group_instance));
+        instance.push_group("SG12", self.sg12_z04.into_iter().map(Pid44002Sg12Z04::into_group_instance));
+        instance.push_group("SG12", self.sg12_dp.into_iter().map(Pid44002Sg12Dp::into_group_instance));
+        instance.push_group("SG12", self.sg12_z05.into_iter().map(Pid44002Sg12Z05::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg5172 {
+    /// Set the `LOC` segment.
+    pub fn with_loc(mut self, segment: OwnedSegment) -> Self {
+        self.loc = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.loc.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.loc);
+        instance
+    }
+}
+
+impl Pid44002Sg6 {
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Set the `DTM` segment.
+    pub fn with_dtm(mut self, segment: OwnedSegment) -> Self {
+        self.dtm = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.rff.iter().cloned());
+        segments.extend(self.dtm.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.rff);
+        instance.segments.extend(self.dtm);
+        instance
+    }
+}
+
+impl Pid44002Sg8Z01 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG9` repetition.
+    pub fn with_sg9(mut self, item: Pid44002Sg9) -> Self {
+        self.sg9.push(item);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg9 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG9", self.sg9.into_iter().map(Pid44002Sg9::into_group_instance));
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z02 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `PIA` segment.
+    pub fn with_pia(mut self, segment: OwnedSegment) -> Self {
+        self.pia = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.pia.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.pia);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z03 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z05 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z07 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z09 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z12 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG9` repetition.
+    pub fn with_sg9(mut self, item: Pid44002Sg9) -> Self {
+        self.sg9.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg9 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG9", self.sg9.into_iter().map(Pid44002Sg9::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z13 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z18 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z20 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Set the `PIA` segment.
+    pub fn with_pia(mut self, segment: OwnedSegment) -> Self {
+        self.pia = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        segments.extend(self.pia.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.segments.extend(self.pia);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z35 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg8Z50 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44002Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44002Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44002Sg9 {
+    /// Set the `QTY` segment.
+    pub fn with_qty(mut self, segment: OwnedSegment) -> Self {
+        self.qty = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.qty.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.qty);
+        instance
+    }
+}
+
+impl Pid44002 {
+    /// Create this PID from its top-level segments, without any groups.
+    pub fn new(unh: OwnedSegment, bgm: OwnedSegment, dtm: OwnedSegment, unt: OwnedSegment) -> Self {
+        Self {
+            unh,
+            bgm,
+            dtm,
+            unt,
+            sg2: Vec::new(),
+            sg4: Vec::new(),
+        }
+    }
+
+    /// Add an `SG2` repetition.
+    pub fn with_sg2(mut self, item: Pid44002Sg2) -> Self {
+        self.sg2.push(item);
+        self
+    }
+
+    /// Add an `SG4` repetition.
+    pub fn with_sg4(mut self, item: Pid44002Sg4) -> Self {
+        self.sg4.push(item);
+        self
+    }
+
+    /// Serialize this message back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = vec![self.unh.clone(), self.bgm.clone(), self.dtm.clone()];
+        for item in &self.sg2 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg4 {
+            segments.extend(item.to_segments());
+        }
+        segments.push(self.unt.clone());
+        segments
+    }
+
+    /// Convert this message into an assembled tree.
+    pub fn into_assembled_tree(self) -> OwnedTree {
+        let mut tree = OwnedTree::default();
+        tree.segments.push(self.unh);
+        tree.segments.push(self.bgm);
+        tree.segments.push(self.dtm);
+        tree.push_group("SG2", self.sg2.into_iter().map(Pid44002Sg2::into_group_instance));
+        tree.push_group("SG4", self.sg4.into_iter().map(Pid44002Sg4::into_group_instance));
+        tree.post_group_start = tree.segments.len();
+        tree.segments.push(self.unt);
+        tree
+    }
+}
+
+impl PidTree for Pid44002 {
+    fn pid_id(&self) -> &str {
+        "44002"
+    }
+
+    fn beschreibung(&self) -> &str {
+        "Bestätigung Anmeldung"
+    }
+
+    fn kommunikation_von(&self) -> Option<&str> {
+        Some("NB an LF")
+    }
+
+    fn message_type(&self) -> &str {
+        "UTILMD"
+    }
+
+    fn format_version(&self) -> &str {
+        "FV2504"
+    }
+
+    fn to_segments(&self) -> Vec<OwnedSegment> {
+        Pid44002::to_segments(self)
+    }
+}
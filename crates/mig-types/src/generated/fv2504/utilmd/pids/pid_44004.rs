@@ -5,9 +5,11 @@
 use serde::{Deserialize, Serialize};
 use crate::segment::OwnedSegment;
 use crate::cursor::{SegmentCursor, SegmentNotFound, peek_is, consume, expect_segment};
+use crate::traits::PidTree;
+use crate::tree::{OwnedGroupInstance, OwnedTree};
 
 /// SG2 — Beteiligter, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44004Sg2 {
     pub nad: Option<OwnedSegment>,
     pub sg3_ic: Vec<Pid44004Sg3Ic>,
@@ -15,14 +17,14 @@ pub struct Pid44004Sg2 {
 
 /// SG3 — Funktion des Ansprechpartners, Code
 /// Qualifiers: IC
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44004Sg3Ic {
     pub com: Option<OwnedSegment>,
     pub cta: Option<OwnedSegment>,
 }
 
 /// SG4 — Objekt, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44004Sg4 {
     pub dtm: Option<OwnedSegment>,
     pub ftx: Option<OwnedSegment>,
@@ -34,13 +36,13 @@ pub struct Pid44004Sg4 {
 
 /// SG5 — Ortsangabe, Qualifier
 /// Qualifiers: 172
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44004Sg5172 {
     pub loc: Option<OwnedSegment>,
 }
 
 /// SG6 — Referenz, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44004Sg6 {
     pub rff: Option<OwnedSegment>,
 }
@@ -236,3 +238,253 @@ impl Pid44004 {
         })
     }
 }
+
+impl Pid44004Sg2 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Add an `SG3` repetition.
+    pub fn with_sg3_ic(mut self, item: Pid44004Sg3Ic) -> Self {
+        self.sg3_ic.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        for item in &self.sg3_ic {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.push_group("SG3", self.sg3_ic.into_iter().map(Pid44004Sg3Ic::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44004Sg3Ic {
+    /// Set the `CTA` segment.
+    pub fn with_cta(mut self, segment: OwnedSegment) -> Self {
+        self.cta = Some(segment);
+        self
+    }
+
+    /// Set the `COM` segment.
+    pub fn with_com(mut self, segment: OwnedSegment) -> Self {
+        self.com = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.cta.iter().cloned());
+        segments.extend(self.com.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.cta);
+        instance.segments.extend(self.com);
+        instance
+    }
+}
+
+impl Pid44004Sg4 {
+    /// Set the `IDE` segment.
+    pub fn with_ide(mut self, segment: OwnedSegment) -> Self {
+        self.ide = Some(segment);
+        self
+    }
+
+    /// Set the `DTM` segment.
+    pub fn with_dtm(mut self, segment: OwnedSegment) -> Self {
+        self.dtm = Some(segment);
+        self
+    }
+
+    /// Set the `STS` segment.
+    pub fn with_sts(mut self, segment: OwnedSegment) -> Self {
+        self.sts = Some(segment);
+        self
+    }
+
+    /// Set the `FTX` segment.
+    pub fn with_ftx(mut self, segment: OwnedSegment) -> Self {
+        self.ftx = Some(segment);
+        self
+    }
+
+    /// Add an `SG5` repetition.
+    pub fn with_sg5_172(mut self, item: Pid44004Sg5172) -> Self {
+        self.sg5_172.push(item);
+        self
+    }
+
+    /// Add an `SG6` repetition.
+    pub fn with_sg6(mut self, item: Pid44004Sg6) -> Self {
+        self.sg6.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.ide.iter().cloned());
+        segments.extend(self.dtm.iter().cloned());
+        segments.extend(self.sts.iter().cloned());
+        segments.extend(self.ftx.iter().cloned());
+        for item in &self.sg5_172 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg6 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.ide);
+        instance.segments.extend(self.dtm);
+        instance.segments.extend(self.sts);
+        instance.segments.extend(self.ftx);
+        instance.push_group("SG5", self.sg5_172.into_iter().map(Pid44004Sg5172::into_group_instance));
+        instance.push_group("SG6", self.sg6.into_iter().map(Pid44004Sg6::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44004Sg5172 {
+    /// Set the `LOC` segment.
+    pub fn with_loc(mut self, segment: OwnedSegment) -> Self {
+        self.loc = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.loc.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.loc);
+        instance
+    }
+}
+
+impl Pid44004Sg6 {
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.rff.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.rff);
+        instance
+    }
+}
+
+impl Pid44004 {
+    /// Create this PID from its top-level segments, without any groups.
+    pub fn new(unh: OwnedSegment, bgm: OwnedSegment, dtm: OwnedSegment, unt: OwnedSegment) -> Self {
+        Self {
+            unh,
+            bgm,
+            dtm,
+            unt,
+            sg2: Vec::new(),
+            sg4: Vec::new(),
+        }
+    }
+
+    /// Add an `SG2` repetition.
+    pub fn with_sg2(mut self, item: Pid44004Sg2) -> Self {
+        self.sg2.push(item);
+        self
+    }
+
+    /// Add an `SG4` repetition.
+    pub fn with_sg4(mut self, item: Pid44004Sg4) -> Self {
+        self.sg4.push(item);
+        self
+    }
+
+    /// Serialize this message back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = vec![self.unh.clone(), self.bgm.clone(), self.dtm.clone()];
+        for item in &self.sg2 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg4 {
+            segments.extend(item.to_segments());
+        }
+        segments.push(self.unt.clone());
+        segments
+    }
+
+    /// Convert this message into an assembled tree.
+    pub fn into_assembled_tree(self) -> OwnedTree {
+        let mut tree = OwnedTree::default();
+        tree.segments.push(self.unh);
+        tree.segments.push(self.bgm);
+        tree.segments.push(self.dtm);
+        tree.push_group("SG2", self.sg2.into_iter().map(Pid44004Sg2::into_group_instance));
+        tree.push_group("SG4", self.sg4.into_iter().map(Pid44004Sg4::into_group_instance));
+        tree.post_group_start = tree.segments.len();
+        tree.segments.push(self.unt);
+        tree
+    }
+}
+
+impl PidTree for Pid44004 {
+    fn pid_id(&self) -> &str {
+        "44004"
+    }
+
+    fn beschreibung(&self) -> &str {
+        "Abmeldung NN"
+    }
+
+    fn kommunikation_von(&self) -> Option<&str> {
+        Some("LF an NB")
+    }
+
+    fn message_type(&self) -> &str {
+        "UTILMD"
+    }
+
+    fn format_version(&self) -> &str {
+        "FV2504"
+    }
+
+    fn to_segments(&self) -> Vec<OwnedSegment> {
+        Pid44004::to_segments(self)
+    }
+}
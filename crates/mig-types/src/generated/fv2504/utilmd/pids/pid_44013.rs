@@ -5,9 +5,11 @@
 use serde::{Deserialize, Serialize};
 use crate::segment::OwnedSegment;
 use crate::cursor::{SegmentCursor, SegmentNotFound, peek_is, consume, expect_segment};
+use crate::traits::PidTree;
+use crate::tree::{OwnedGroupInstance, OwnedTree};
 
 /// SG10 — Klassentyp, Code
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg10 {
     pub cav: Option<OwnedSegment>,
     pub cci: Option<OwnedSegment>,
@@ -15,35 +17,35 @@ pub struct Pid44013Sg10 {
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: DDO
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Ddo {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: DP
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Dp {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: EO
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Eo {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z04
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Z04 {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z05
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Z05 {
     pub nad: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -51,27 +53,27 @@ pub struct Pid44013Sg12Z05 {
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z09
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Z09 {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z25
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Z25 {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG12 — Beteiligter, Qualifier
 /// Qualifiers: Z26
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg12Z26 {
     pub nad: Option<OwnedSegment>,
 }
 
 /// SG2 — Beteiligter, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg2 {
     pub nad: Option<OwnedSegment>,
     pub sg3_ic: Vec<Pid44013Sg3Ic>,
@@ -79,14 +81,14 @@ pub struct Pid44013Sg2 {
 
 /// SG3 — Funktion des Ansprechpartners, Code
 /// Qualifiers: IC
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg3Ic {
     pub com: Option<OwnedSegment>,
     pub cta: Option<OwnedSegment>,
 }
 
 /// SG4 — Objekt, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg4 {
     pub dtm: Option<OwnedSegment>,
     pub ftx: Option<OwnedSegment>,
@@ -118,13 +120,13 @@ pub struct Pid44013Sg4 {
 
 /// SG5 — Ortsangabe, Qualifier
 /// Qualifiers: 172
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg5172 {
     pub loc: Option<OwnedSegment>,
 }
 
 /// SG6 — Referenz, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg6 {
     pub dtm: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -132,7 +134,7 @@ pub struct Pid44013Sg6 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z01
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z01 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44013Sg10>,
@@ -141,7 +143,7 @@ pub struct Pid44013Sg8Z01 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z02
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z02 {
     pub pia: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -150,7 +152,7 @@ pub struct Pid44013Sg8Z02 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z03
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z03 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -159,7 +161,7 @@ pub struct Pid44013Sg8Z03 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z05
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z05 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -168,7 +170,7 @@ pub struct Pid44013Sg8Z05 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z07
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z07 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -177,7 +179,7 @@ pub struct Pid44013Sg8Z07 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z09
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z09 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -186,7 +188,7 @@ pub struct Pid44013Sg8Z09 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z12
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z12 {
     pub seq: Option<OwnedSegment>,
     pub sg9: Vec<Pid44013Sg9>,
@@ -194,7 +196,7 @@ pub struct Pid44013Sg8Z12 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z13
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z13 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44013Sg10>,
@@ -202,7 +204,7 @@ pub struct Pid44013Sg8Z13 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z18
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z18 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -211,7 +213,7 @@ pub struct Pid44013Sg8Z18 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z20
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z20 {
     pub pia: Option<OwnedSegment>,
     pub rff: Option<OwnedSegment>,
@@ -221,7 +223,7 @@ pub struct Pid44013Sg8Z20 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z35
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z35 {
     pub seq: Option<OwnedSegment>,
     pub sg10: Vec<Pid44013Sg10>,
@@ -229,7 +231,7 @@ pub struct Pid44013Sg8Z35 {
 
 /// SG8 — Handlung, Code
 /// Qualifiers: Z50
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg8Z50 {
     pub rff: Option<OwnedSegment>,
     pub seq: Option<OwnedSegment>,
@@ -237,7 +239,7 @@ pub struct Pid44013Sg8Z50 {
 }
 
 /// SG9 — Menge, Qualifier
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Pid44013Sg9 {
     pub qty: Option<OwnedSegment>,
 }
@@ -1154,3 +1156,1163 @@ impl Pid44013 {
         })
     }
 }
+
+impl Pid44013Sg10 {
+    /// Set the `CCI` segment.
+    pub fn with_cci(mut self, segment: OwnedSegment) -> Self {
+        self.cci = Some(segment);
+        self
+    }
+
+    /// Set the `CAV` segment.
+    pub fn with_cav(mut self, segment: OwnedSegment) -> Self {
+        self.cav = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.cci.iter().cloned());
+        segments.extend(self.cav.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.cci);
+        instance.segments.extend(self.cav);
+        instance
+    }
+}
+
+impl Pid44013Sg12Ddo {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Dp {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Eo {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Z04 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Z05 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.segments.extend(self.rff);
+        instance
+    }
+}
+
+impl Pid44013Sg12Z09 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Z25 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg12Z26 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance
+    }
+}
+
+impl Pid44013Sg2 {
+    /// Set the `NAD` segment.
+    pub fn with_nad(mut self, segment: OwnedSegment) -> Self {
+        self.nad = Some(segment);
+        self
+    }
+
+    /// Add an `SG3` repetition.
+    pub fn with_sg3_ic(mut self, item: Pid44013Sg3Ic) -> Self {
+        self.sg3_ic.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.nad.iter().cloned());
+        for item in &self.sg3_ic {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.nad);
+        instance.push_group("SG3", self.sg3_ic.into_iter().map(Pid44013Sg3Ic::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg3Ic {
+    /// Set the `CTA` segment.
+    pub fn with_cta(mut self, segment: OwnedSegment) -> Self {
+        self.cta = Some(segment);
+        self
+    }
+
+    /// Set the `COM` segment.
+    pub fn with_com(mut self, segment: OwnedSegment) -> Self {
+        self.com = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.cta.iter().cloned());
+        segments.extend(self.com.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.cta);
+        instance.segments.extend(self.com);
+        instance
+    }
+}
+
+impl Pid44013Sg4 {
+    /// Set the `IDE` segment.
+    pub fn with_ide(mut self, segment: OwnedSegment) -> Self {
+        self.ide = Some(segment);
+        self
+    }
+
+    /// Set the `DTM` segment.
+    pub fn with_dtm(mut self, segment: OwnedSegment) -> Self {
+        self.dtm = Some(segment);
+        self
+    }
+
+    /// Set the `STS` segment.
+    pub fn with_sts(mut self, segment: OwnedSegment) -> Self {
+        self.sts = Some(segment);
+        self
+    }
+
+    /// Set the `FTX` segment.
+    pub fn with_ftx(mut self, segment: OwnedSegment) -> Self {
+        self.ftx = Some(segment);
+        self
+    }
+
+    /// Add an `SG5` repetition.
+    pub fn with_sg5_172(mut self, item: Pid44013Sg5172) -> Self {
+        self.sg5_172.push(item);
+        self
+    }
+
+    /// Add an `SG6` repetition.
+    pub fn with_sg6(mut self, item: Pid44013Sg6) -> Self {
+        self.sg6.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z01(mut self, item: Pid44013Sg8Z01) -> Self {
+        self.sg8_z01.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z02(mut self, item: Pid44013Sg8Z02) -> Self {
+        self.sg8_z02.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z07(mut self, item: Pid44013Sg8Z07) -> Self {
+        self.sg8_z07.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z12(mut self, item: Pid44013Sg8Z12) -> Self {
+        self.sg8_z12.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z18(mut self, item: Pid44013Sg8Z18) -> Self {
+        self.sg8_z18.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z03(mut self, item: Pid44013Sg8Z03) -> Self {
+        self.sg8_z03.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z50(mut self, item: Pid44013Sg8Z50) -> Self {
+        self.sg8_z50.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z09(mut self, item: Pid44013Sg8Z09) -> Self {
+        self.sg8_z09.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z20(mut self, item: Pid44013Sg8Z20) -> Self {
+        self.sg8_z20.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z05(mut self, item: Pid44013Sg8Z05) -> Self {
+        self.sg8_z05.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z13(mut self, item: Pid44013Sg8Z13) -> Self {
+        self.sg8_z13.push(item);
+        self
+    }
+
+    /// Add an `SG8` repetition.
+    pub fn with_sg8_z35(mut self, item: Pid44013Sg8Z35) -> Self {
+        self.sg8_z35.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z09(mut self, item: Pid44013Sg12Z09) -> Self {
+        self.sg12_z09.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z04(mut self, item: Pid44013Sg12Z04) -> Self {
+        self.sg12_z04.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z25(mut self, item: Pid44013Sg12Z25) -> Self {
+        self.sg12_z25.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z26(mut self, item: Pid44013Sg12Z26) -> Self {
+        self.sg12_z26.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_eo(mut self, item: Pid44013Sg12Eo) -> Self {
+        self.sg12_eo.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_ddo(mut self, item: Pid44013Sg12Ddo) -> Self {
+        self.sg12_ddo.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_dp(mut self, item: Pid44013Sg12Dp) -> Self {
+        self.sg12_dp.push(item);
+        self
+    }
+
+    /// Add an `SG12` repetition.
+    pub fn with_sg12_z05(mut self, item: Pid44013Sg12Z05) -> Self {
+        self.sg12_z05.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.ide.iter().cloned());
+        segments.extend(self.dtm.iter().cloned());
+        segments.extend(self.sts.iter().cloned());
+        segments.extend(self.ftx.iter().cloned());
+        for item in &self.sg5_172 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg6 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z01 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z02 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z07 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z12 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z18 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z03 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z50 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z09 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z20 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z05 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z13 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg8_z35 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z09 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z04 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z25 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z26 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_eo {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_ddo {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_dp {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg12_z05 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.ide);
+        instance.segments.extend(self.dtm);
+        instance.segments.extend(self.sts);
+        instance.segments.extend(self.ftx);
+        instance.push_group("SG5", self.sg5_172.into_iter().map(Pid44013Sg5172::into_group_instance));
+        instance.push_group("SG6", self.sg6.into_iter().map(Pid44013Sg6::into_group_instance));
+        instance.push_group("SG8", self.sg8_z01.into_iter().map(Pid44013Sg8Z01::into_group_instance));
+        instance.push_group("SG8", self.sg8_z02.into_iter().map(Pid44013Sg8Z02::into_group_instance));
+        instance.push_group("SG8", self.sg8_z07.into_iter().map(Pid44013Sg8Z07::into_group_instance));
+        instance.push_group("SG8", self.sg8_z12.into_iter().map(Pid44013Sg8Z12::into_group_instance));
+        instance.push_group("SG8", self.sg8_z18.into_iter().map(Pid44013Sg8Z18::into_group_instance));
+        instance.push_group("SG8", self.sg8_z03.into_iter().map(Pid44013Sg8Z03::into_group_instance));
+        instance.push_group("SG8", self.sg8_z50.into_iter().map(Pid44013Sg8Z50::into_group_instance));
+        instance.push_group("SG8", self.sg8_z09.into_iter().map(Pid44013Sg8Z09::into_group_instance));
+        instance.push_group("SG8", self.sg8_z20.into_iter().map(Pid44013Sg8Z20::into_group_instance));
+        instance.push_group("SG8", self.sg8_z05.into_iter().map(Pid44013Sg8Z05::into_group_instance));
+        instance.push_group("SG8", self.sg8_z13.into_iter().map(Pid44013Sg8Z13::into_group_instance));
+        instance.push_group("SG8", self.sg8_z35.into_iter().map(Pid44013Sg8Z35::into_group_instance));
+        instance.push_group("SG12", self.sg12_z09.into_iter().map(Pid44013Sg12Z09::into_group_instance));
+        instance.push_group("SG12", self.sg12_z04.into_iter().map(Pid44013Sg12Z04::into_group_instance));
+        instance.push_group("SG12", self.sg12_z25.into_iter().map(Pid44013Sg12Z25::into_group_instance));
+        instance.push_group("SG12", self.sg12_z26.into_iter().map(Pid44013Sg12Z26::into_group_instance));
+        instance.push_group("SG12", self.sg12_eo.into_iter().map(Pid44013Sg12Eo::into_group_instance));
+        instance.push_group("SG12", self.sg12_ddo.into_iter().map(Pid44013Sg12Ddo::into_group_instance));
+        instance.push_group("SG12", self.sg12_dp.into_iter().map(Pid44013Sg12Dp::into_group_instance));
+        instance.push_group("SG12", self.sg12_z05.into_iter().map(Pid44013Sg12Z05::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg5172 {
+    /// Set the `LOC` segment.
+    pub fn with_loc(mut self, segment: OwnedSegment) -> Self {
+        self.loc = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.loc.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.loc);
+        instance
+    }
+}
+
+impl Pid44013Sg6 {
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Set the `DTM` segment.
+    pub fn with_dtm(mut self, segment: OwnedSegment) -> Self {
+        self.dtm = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.rff.iter().cloned());
+        segments.extend(self.dtm.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.rff);
+        instance.segments.extend(self.dtm);
+        instance
+    }
+}
+
+impl Pid44013Sg8Z01 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG9` repetition.
+    pub fn with_sg9(mut self, item: Pid44013Sg9) -> Self {
+        self.sg9.push(item);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg9 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG9", self.sg9.into_iter().map(Pid44013Sg9::into_group_instance));
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z02 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `PIA` segment.
+    pub fn with_pia(mut self, segment: OwnedSegment) -> Self {
+        self.pia = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.pia.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.pia);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z03 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z05 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z07 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z09 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z12 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG9` repetition.
+    pub fn with_sg9(mut self, item: Pid44013Sg9) -> Self {
+        self.sg9.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg9 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG9", self.sg9.into_iter().map(Pid44013Sg9::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z13 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z18 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z20 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Set the `PIA` segment.
+    pub fn with_pia(mut self, segment: OwnedSegment) -> Self {
+        self.pia = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        segments.extend(self.pia.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.segments.extend(self.pia);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z35 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg8Z50 {
+    /// Set the `SEQ` segment.
+    pub fn with_seq(mut self, segment: OwnedSegment) -> Self {
+        self.seq = Some(segment);
+        self
+    }
+
+    /// Set the `RFF` segment.
+    pub fn with_rff(mut self, segment: OwnedSegment) -> Self {
+        self.rff = Some(segment);
+        self
+    }
+
+    /// Add an `SG10` repetition.
+    pub fn with_sg10(mut self, item: Pid44013Sg10) -> Self {
+        self.sg10.push(item);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.seq.iter().cloned());
+        segments.extend(self.rff.iter().cloned());
+        for item in &self.sg10 {
+            segments.extend(item.to_segments());
+        }
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.seq);
+        instance.segments.extend(self.rff);
+        instance.push_group("SG10", self.sg10.into_iter().map(Pid44013Sg10::into_group_instance));
+        instance
+    }
+}
+
+impl Pid44013Sg9 {
+    /// Set the `QTY` segment.
+    pub fn with_qty(mut self, segment: OwnedSegment) -> Self {
+        self.qty = Some(segment);
+        self
+    }
+
+    /// Serialize this group back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = Vec::new();
+        segments.extend(self.qty.iter().cloned());
+        segments
+    }
+
+    /// Convert this group into a group instance of an assembled tree.
+    pub fn into_group_instance(self) -> OwnedGroupInstance {
+        let mut instance = OwnedGroupInstance::default();
+        instance.segments.extend(self.qty);
+        instance
+    }
+}
+
+impl Pid44013 {
+    /// Create this PID from its top-level segments, without any groups.
+    pub fn new(unh: OwnedSegment, bgm: OwnedSegment, dtm: OwnedSegment, unt: OwnedSegment) -> Self {
+        Self {
+            unh,
+            bgm,
+            dtm,
+            unt,
+            sg2: Vec::new(),
+            sg4: Vec::new(),
+        }
+    }
+
+    /// Add an `SG2` repetition.
+    pub fn with_sg2(mut self, item: Pid44013Sg2) -> Self {
+        self.sg2.push(item);
+        self
+    }
+
+    /// Add an `SG4` repetition.
+    pub fn with_sg4(mut self, item: Pid44013Sg4) -> Self {
+        self.sg4.push(item);
+        self
+    }
+
+    /// Serialize this message back to segments, in MIG order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut segments = vec![self.unh.clone(), self.bgm.clone(), self.dtm.clone()];
+        for item in &self.sg2 {
+            segments.extend(item.to_segments());
+        }
+        for item in &self.sg4 {
+            segments.extend(item.to_segments());
+        }
+        segments.push(self.unt.clone());
+        segments
+    }
+
+    /// Convert this message into an assembled tree.
+    pub fn into_assembled_tree(self) -> OwnedTree {
+        let mut tree = OwnedTree::default();
+        tree.segments.push(self.unh);
+        tree.segments.push(self.bgm);
+        tree.segments.push(self.dtm);
+        tree.push_group("SG2", self.sg2.into_iter().map(Pid44013Sg2::into_group_instance));
+        tree.push_group("SG4", self.sg4.into_iter().map(Pid44013Sg4::into_group_instance));
+        tree.post_group_start = tree.segments.len();
+        tree.segments.push(self.unt);
+        tree
+    }
+}
+
+impl PidTree for Pid44013 {
+    fn pid_id(&self) -> &str {
+        "44013"
+    }
+
+    fn beschreibung(&self) -> &str {
+        "Anmeldung EOG"
+    }
+
+    fn kommunikation_von(&self) -> Option<&str> {
+        Some("NB an LF")
+    }
+
+    fn message_type(&self) -> &str {
+        "UTILMD"
+    }
+
+    fn format_version(&self) -> &str {
+        "FV2504"
+    }
+
+    fn to_segments(&self) -> Vec<OwnedSegment> {
+        Pid44013::to_segments(self)
+    }
+}
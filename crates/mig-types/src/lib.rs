@@ -13,6 +13,7 @@
 //! Also provides crate-independent primitives:
 //! - `segment` — `OwnedSegment` for parsed EDIFACT segments
 //! - `cursor` — `SegmentCursor` and helpers for sequential segment consumption
+//! - `tree` — `OwnedTree`, the owned assembled tree generated PID types convert into
 
 pub mod cursor;
 pub mod generated;
@@ -20,3 +21,4 @@ pub mod navigator;
 pub mod schema;
 pub mod segment;
 pub mod traits;
+pub mod tree;
@@ -104,4 +104,24 @@ pub trait GroupNavigator: Send + Sync {
         );
         None
     }
+
+    /// Count the nodes a path query selects (e.g. `"SG4/SG8[SEQ.0=Z01]/SG10"`).
+    ///
+    /// The query language is defined by the tree implementation
+    /// (`mig_assembly::query` for assembled trees). Returns `None` if the
+    /// navigator does not support queries or the query is invalid.
+    fn query_count(&self, query: &str) -> Option<usize> {
+        let _ = query;
+        None
+    }
+
+    /// Values selected by a path query ending in a component
+    /// (e.g. `"SG4/SG8/SG10/CCI.2.0"`), in document order.
+    ///
+    /// Returns `None` if the navigator does not support queries or the
+    /// query is invalid.
+    fn query_values(&self, query: &str) -> Option<Vec<String>> {
+        let _ = query;
+        None
+    }
 }
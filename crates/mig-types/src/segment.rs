@@ -19,9 +19,77 @@ pub struct OwnedSegment {
     pub elements: Vec<Vec<String>>,
     /// 1-based segment number within the message.
     pub segment_number: u32,
+    /// Where the segment was found in the input, if it was parsed from one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SegmentLocation>,
+}
+
+/// Where a segment was found in the EDIFACT input.
+///
+/// Mirrors the position and spans of `edifact_types::RawSegment` with serde
+/// support. Offsets are bytes from the start of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SegmentLocation {
+    /// Byte offset of the segment's first byte.
+    pub byte_offset: usize,
+    /// 1-based line of the segment's first byte.
+    pub line: u32,
+    /// 1-based column, in bytes, of the segment's first byte.
+    pub column: u32,
+    /// 1-based message number within the interchange (0 for service
+    /// segments outside messages).
+    pub message_number: u32,
+    /// Byte span of the segment text, without terminator.
+    pub span: SourceSpan,
+    /// Byte spans of the components, parallel to `OwnedSegment::elements`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Vec<SourceSpan>>,
+}
+
+/// A half-open byte range `start..end` in the EDIFACT input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SegmentLocation {
+    /// Byte span of a component, or of a whole element if `component` is
+    /// `None`.
+    pub fn element_span(&self, element: usize, component: Option<usize>) -> Option<SourceSpan> {
+        let components = self.components.get(element)?;
+        match component {
+            Some(c) => components.get(c).copied(),
+            None => Some(SourceSpan {
+                start: components.first()?.start,
+                end: components.last()?.end,
+            }),
+        }
+    }
 }
 
 impl OwnedSegment {
+    /// Build a segment from its tag and element components, e.g.
+    /// `OwnedSegment::new("NAD", [vec!["MS"], vec!["9900123000002", "", "293"]])`.
+    ///
+    /// The segment has no segment number and no input location.
+    pub fn new<E, C, S>(id: &str, elements: E) -> Self
+    where
+        E: IntoIterator<Item = C>,
+        C: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            id: id.to_string(),
+            elements: elements
+                .into_iter()
+                .map(|e| e.into_iter().map(Into::into).collect())
+                .collect(),
+            segment_number: 0,
+            location: None,
+        }
+    }
+
     /// Gets the first component of element at `index`, or empty string if missing.
     pub fn get_element(&self, index: usize) -> &str {
         self.elements
@@ -1,5 +1,7 @@
 //! Common traits for MIG tree types.
 
+use crate::segment::OwnedSegment;
+
 /// Trait implemented by all generated PID-specific tree types.
 ///
 /// Provides common operations that work across any PID structure:
@@ -19,4 +21,7 @@ pub trait PidTree: std::fmt::Debug + Send + Sync {
 
     /// The format version (e.g., "FV2504").
     fn format_version(&self) -> &str;
+
+    /// The message's segments in MIG order, from `UNH` to `UNT`.
+    fn to_segments(&self) -> Vec<OwnedSegment>;
 }
@@ -0,0 +1,200 @@
+//! Owned assembled tree — a crate-independent form of a MIG-assembled message.
+//!
+//! Generated PID types convert into an `OwnedTree` via `into_assembled_tree()`.
+//! It mirrors `mig_assembly::assembler::AssembledTree` with owned segments, so
+//! generated code can build it without depending on `mig-assembly`, which
+//! converts it into its own tree with `AssembledTree::from`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::segment::OwnedSegment;
+
+/// A message as root segments and segment groups, in MIG order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnedTree {
+    pub segments: Vec<OwnedSegment>,
+    pub groups: Vec<OwnedGroup>,
+    /// Index in `segments` where post-group segments start (e.g., UNT).
+    pub post_group_start: usize,
+    /// Root segments between groups (e.g., UNS in MSCONS). Key = index into
+    /// `groups` of the group they precede.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inter_group_segments: BTreeMap<usize, Vec<OwnedSegment>>,
+}
+
+/// A segment group with all its repetitions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnedGroup {
+    pub group_id: String,
+    pub repetitions: Vec<OwnedGroupInstance>,
+}
+
+/// One repetition of a segment group.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnedGroupInstance {
+    pub segments: Vec<OwnedSegment>,
+    pub child_groups: Vec<OwnedGroup>,
+}
+
+impl OwnedTree {
+    /// Append repetitions of a group after the groups already present.
+    ///
+    /// Repetitions of the group pushed last are merged into it, so that
+    /// qualifier variants of one group (e.g. `SG8` with `SEQ+Z01` and
+    /// `SEQ+Z03`) form a single group like in an assembled message. Pushing
+    /// no repetitions adds nothing.
+    pub fn push_group(
+        &mut self,
+        group_id: &str,
+        repetitions: impl IntoIterator<Item = OwnedGroupInstance>,
+    ) {
+        let separated = self.inter_group_segments.contains_key(&self.groups.len());
+        push_repetitions(&mut self.groups, group_id, repetitions, !separated);
+    }
+
+    /// Append a root segment that precedes the next group pushed.
+    pub fn push_inter_group_segment(&mut self, segment: OwnedSegment) {
+        self.inter_group_segments
+            .entry(self.groups.len())
+            .or_default()
+            .push(segment);
+    }
+
+    /// All segments of the message in EDIFACT order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let post_group_start = self.post_group_start.min(self.segments.len());
+        let mut out = self.segments[..post_group_start].to_vec();
+        for (idx, group) in self.groups.iter().enumerate() {
+            if let Some(segments) = self.inter_group_segments.get(&idx) {
+                out.extend(segments.iter().cloned());
+            }
+            group.collect_segments(&mut out);
+        }
+        if let Some(segments) = self.inter_group_segments.get(&self.groups.len()) {
+            out.extend(segments.iter().cloned());
+        }
+        out.extend(self.segments[post_group_start..].iter().cloned());
+        out
+    }
+}
+
+impl OwnedGroup {
+    /// All segments of every repetition in EDIFACT order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut out = Vec::new();
+        self.collect_segments(&mut out);
+        out
+    }
+
+    fn collect_segments(&self, out: &mut Vec<OwnedSegment>) {
+        for instance in &self.repetitions {
+            instance.collect_segments(out);
+        }
+    }
+}
+
+impl OwnedGroupInstance {
+    /// Append repetitions of a child group; see [`OwnedTree::push_group`].
+    pub fn push_group(
+        &mut self,
+        group_id: &str,
+        repetitions: impl IntoIterator<Item = OwnedGroupInstance>,
+    ) {
+        push_repetitions(&mut self.child_groups, group_id, repetitions, true);
+    }
+
+    /// The instance's segments followed by its child groups in EDIFACT order.
+    pub fn to_segments(&self) -> Vec<OwnedSegment> {
+        let mut out = Vec::new();
+        self.collect_segments(&mut out);
+        out
+    }
+
+    fn collect_segments(&self, out: &mut Vec<OwnedSegment>) {
+        out.extend(self.segments.iter().cloned());
+        for group in &self.child_groups {
+            group.collect_segments(out);
+        }
+    }
+}
+
+fn push_repetitions(
+    groups: &mut Vec<OwnedGroup>,
+    group_id: &str,
+    repetitions: impl IntoIterator<Item = OwnedGroupInstance>,
+    merge: bool,
+) {
+    let mut repetitions = repetitions.into_iter().peekable();
+    if repetitions.peek().is_none() {
+        return;
+    }
+    match groups.last_mut() {
+        Some(last) if merge && last.group_id == group_id => last.repetitions.extend(repetitions),
+        _ => groups.push(OwnedGroup {
+            group_id: group_id.to_string(),
+            repetitions: repetitions.collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(id: &str, qualifier: &str) -> OwnedSegment {
+        OwnedSegment::new(id, [[qualifier]])
+    }
+
+    fn instance(segments: Vec<OwnedSegment>) -> OwnedGroupInstance {
+        OwnedGroupInstance {
+            segments,
+            child_groups: Vec::new(),
+        }
+    }
+
+    fn ids(segments: &[OwnedSegment]) -> Vec<String> {
+        segments
+            .iter()
+            .map(|s| format!("{}+{}", s.id, s.get_element(0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_push_group_merges_consecutive_variants() {
+        let mut sg4 = OwnedGroupInstance::default();
+        sg4.segments.push(seg("IDE", "24"));
+        sg4.push_group("SG8", [instance(vec![seg("SEQ", "Z01")])]);
+        sg4.push_group("SG8", [instance(vec![seg("SEQ", "Z03")])]);
+        sg4.push_group("SG10", std::iter::empty());
+        sg4.push_group("SG12", [instance(vec![seg("NAD", "Z09")])]);
+
+        assert_eq!(sg4.child_groups.len(), 2);
+        assert_eq!(sg4.child_groups[0].group_id, "SG8");
+        assert_eq!(sg4.child_groups[0].repetitions.len(), 2);
+        assert_eq!(
+            ids(&sg4.to_segments()),
+            ["IDE+24", "SEQ+Z01", "SEQ+Z03", "NAD+Z09"]
+        );
+    }
+
+    #[test]
+    fn test_tree_to_segments_places_root_segments() {
+        let mut tree = OwnedTree::default();
+        tree.segments.push(seg("UNH", "1"));
+        tree.segments.push(seg("BGM", "E01"));
+        tree.push_group("SG2", [instance(vec![seg("NAD", "MS")])]);
+        tree.push_inter_group_segment(seg("UNS", "D"));
+        tree.push_group("SG2", [instance(vec![seg("NAD", "MR")])]);
+        tree.post_group_start = tree.segments.len();
+        tree.segments.push(seg("UNT", "5"));
+
+        // The separator keeps the second SG2 push a group of its own.
+        assert_eq!(tree.groups.len(), 2);
+        assert_eq!(
+            ids(&tree.to_segments()),
+            ["UNH+1", "BGM+E01", "NAD+MS", "UNS+D", "NAD+MR", "UNT+5"]
+        );
+    }
+}
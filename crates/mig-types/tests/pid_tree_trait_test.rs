@@ -6,3 +6,45 @@ fn test_pid_tree_trait_exists() {
     // PidTree should be object-safe for dynamic dispatch
     fn _assert_object_safe(_: &dyn PidTree) {}
 }
+
+#[test]
+fn test_generated_pid_serializes_through_pid_tree() {
+    use mig_types::generated::fv2504::utilmd::pids::pid_44001::*;
+    use mig_types::segment::OwnedSegment;
+
+    let seg = |id: &str, value: &str| OwnedSegment::new(id, [[value]]);
+    let pid = Pid44001::new(
+        seg("UNH", "1"),
+        seg("BGM", "E01"),
+        seg("DTM", "137"),
+        seg("UNT", "6"),
+    )
+    .with_sg4(
+        Pid44001Sg4::default()
+            .with_dtm(seg("DTM", "92"))
+            .with_ide(seg("IDE", "24"))
+            .with_sg6(Pid44001Sg6::default().with_rff(seg("RFF", "Z13"))),
+    );
+
+    let tree: &dyn PidTree = &pid;
+    assert_eq!(tree.pid_id(), "44001");
+    assert_eq!(tree.message_type(), "UTILMD");
+    assert_eq!(tree.format_version(), "FV2504");
+    assert_eq!(tree.kommunikation_von(), Some("LF an NB"));
+
+    // MIG order, not builder or field order
+    let segments = tree.to_segments();
+    let ids: Vec<String> = segments
+        .iter()
+        .map(|s| format!("{}+{}", s.id, s.get_element(0)))
+        .collect();
+    assert_eq!(
+        ids,
+        ["UNH+1", "BGM+E01", "DTM+137", "IDE+24", "DTM+92", "RFF+Z13", "UNT+6"]
+    );
+
+    let owned = pid.into_assembled_tree();
+    assert_eq!(owned.post_group_start, 3);
+    assert_eq!(owned.groups[0].group_id, "SG4");
+    assert_eq!(owned.to_segments(), segments);
+}